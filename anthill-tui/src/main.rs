@@ -0,0 +1,354 @@
+mod render;
+mod util;
+
+use anthill_core::{ai, core, data, proc_gen, world};
+
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::enable_raw_mode,
+};
+use ratatui::DefaultTerminal;
+
+use crate::{
+    core::{
+        difficulty::DifficultyLevel,
+        game::GameState,
+        replay::{DEFAULT_REPLAY_SPEED, Playback, Replay},
+        simulation::{balance_table, monster_balance},
+    },
+    data::{mods::mod_report, perks::perk_defs},
+    proc_gen::gen_config::{GenConfig, gen_config_path},
+    render::{
+        modal_display::{ModalInterface, SelectionAction},
+        theme::{DisplayConfig, Theme, ThemeId, display_config_path, theme_path},
+        ui::UserInterface,
+    },
+    util::{
+        cli_args::{CliArgs, USAGE},
+        feedback::{Feedback, FeedbackSettings, feedback_path},
+        input_handler::{DEFAULT_TICK_RATE, KeyboardFocus},
+        text_log::{LogConfig, LogData, log_config_path},
+    },
+};
+
+fn main() -> io::Result<()> {
+    let cli_args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(cli_args) => cli_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(depth) = cli_args.balance {
+        let results = monster_balance(depth, cli_args.seed.unwrap_or(73));
+        println!("{}", balance_table(depth, &results));
+        return Ok(());
+    }
+
+    #[cfg(feature = "dev")]
+    if let Some(seeds) = cli_args.bench_gen {
+        // Seeds the generator panics on are counted in the table, their messages would only clutter it
+        std::panic::set_hook(Box::new(|_| {}));
+        let results = proc_gen::generator_bench::bench_generator(seeds);
+        let _ = std::panic::take_hook();
+        println!("{}", proc_gen::generator_bench::bench_table(seeds, &results));
+        return Ok(());
+    }
+
+    let app = match &cli_args.replay {
+        Some(path) => match Replay::load(path) {
+            Ok(replay) => App::from_replay(replay, cli_args.speed.unwrap_or(DEFAULT_REPLAY_SPEED)),
+            Err(error) => {
+                eprintln!("Couldn't load replay {}: {}", path.display(), error);
+                std::process::exit(1);
+            }
+        },
+        None => App::new(cli_args.seed, cli_args.difficulty.unwrap_or_default()),
+    };
+
+    let terminal = ratatui::init();
+    let app_result = app.run(terminal);
+    ratatui::restore();
+    app_result
+}
+
+struct App {
+    should_quit: bool,
+    keyboard_focus: KeyboardFocus,
+    game: GameState,
+    ui: UserInterface,
+    state: State,
+    /// Morgue file written when the player died.
+    morgue_path: Option<PathBuf>,
+    /// Replay that is played back instead of the player's input.
+    playback: Option<Playback>,
+    /// Rings the terminal bell on important events.
+    feedback: Feedback,
+    /// Time between two ticks of the main loop (see [Message::Tick](util::input_handler::Message::Tick)).
+    tick_rate: Duration,
+    /// When the last tick happened.
+    last_tick: Instant,
+    /// When the replay that is played back played its last turn.
+    last_turn: Instant,
+}
+
+#[derive(PartialEq)]
+enum State {
+    StartScreen,
+    Playing,
+    GameOver,
+}
+
+impl App {
+    /// Creates the app with a new run. The run uses the given seed, or a new one if there is none.
+    fn new(seed: Option<u64>, difficulty: DifficultyLevel) -> Self {
+        let mut game = match seed {
+            Some(seed) => GameState::with_seed(seed),
+            None => GameState::new(),
+        };
+        game.set_difficulty(difficulty);
+
+        let mut app = Self {
+            should_quit: false,
+            keyboard_focus: KeyboardFocus::FocusWorld,
+            game,
+            ui: UserInterface::new(),
+            state: State::StartScreen,
+            morgue_path: None,
+            playback: None,
+            feedback: Feedback::new(FeedbackSettings::default()),
+            tick_rate: DEFAULT_TICK_RATE,
+            last_tick: Instant::now(),
+            last_turn: Instant::now(),
+        };
+        app.load_theme();
+        app.load_display_config();
+        app.load_feedback_settings();
+        app.load_gen_config();
+        app.load_log_config();
+        for line in mod_report() {
+            app.game.log.print(line);
+        }
+        app
+    }
+
+    /// Creates the app to play back a replay with the given speed (in turns per second).
+    fn from_replay(replay: Replay, speed: u32) -> Self {
+        let mut game = GameState::from_replay(&replay);
+        if let Some(warning) = replay.build.mismatch_warning("replay") {
+            game.log.print(warning);
+        }
+        game.log.print(format!(
+            "Playing back a run with seed {}. Space: Pause, +/-: Change speed, .: Next turn while paused",
+            replay.seed
+        ));
+
+        let mut app = Self {
+            should_quit: false,
+            keyboard_focus: KeyboardFocus::FocusWorld,
+            game,
+            ui: UserInterface::new(),
+            state: State::Playing,
+            morgue_path: None,
+            playback: Some(Playback::new(replay, speed)),
+            feedback: Feedback::new(FeedbackSettings::default()),
+            tick_rate: DEFAULT_TICK_RATE,
+            last_tick: Instant::now(),
+            last_turn: Instant::now(),
+        };
+        app.load_theme();
+        app.load_feedback_settings();
+        app.load_log_config();
+        app
+    }
+
+    fn run(mut self, mut terminal: DefaultTerminal) -> io::Result<()> {
+        enable_raw_mode()?;
+        execute!(std::io::stdout(), EnableMouseCapture,)?;
+
+        while !self.should_quit {
+            if self.state == State::Playing && !self.game.player_is_alive() {
+                self.state = State::GameOver;
+                // Replays of a run don't count as runs of their own
+                if self.playback.is_none() {
+                    self.record_finished_run();
+                }
+            }
+            if self.state == State::Playing && self.ui.modal.is_none() {
+                self.show_pending_lore();
+            }
+            if self.state == State::Playing && self.ui.modal.is_none() {
+                self.show_pending_perks();
+            }
+            if self.state != State::StartScreen {
+                self.feedback.update(&self.game);
+            }
+            if self.state == State::Playing {
+                let hits = std::mem::take(&mut self.game.pending_hits);
+                self.ui.animations.push_hits(hits, Instant::now());
+                self.ui.recorder.capture(&self.ui.world_display, &self.ui.theme, &self.game);
+            }
+            terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
+            self.handle_events()?;
+        }
+
+        execute!(std::io::stdout(), DisableMouseCapture,)?;
+
+        Ok(())
+    }
+
+    /// Switches to the theme picked last time.
+    fn load_theme(&mut self) {
+        let Some(path) = theme_path() else {
+            return;
+        };
+
+        match ThemeId::load(&path) {
+            Ok(theme_id) => self.ui.theme = Theme::new(theme_id),
+            Err(error) => self.game.log.debug_warn(format!("Couldn't load theme: {}", error)),
+        }
+    }
+
+    /// Applies the display settings of the player's config file.
+    fn load_display_config(&mut self) {
+        let Some(path) = display_config_path() else {
+            return;
+        };
+
+        match DisplayConfig::load(&path) {
+            Ok(config) => {
+                self.ui.theme.ascii = config.ascii;
+                if let Some(tick_rate_ms) = config.tick_rate_ms {
+                    self.tick_rate = Duration::from_millis(tick_rate_ms.max(1));
+                }
+            }
+            Err(error) => {
+                self.game.log.debug_warn(format!("Couldn't load display config: {}", error))
+            }
+        }
+    }
+
+    /// Applies the feedback settings that were saved last time.
+    fn load_feedback_settings(&mut self) {
+        let Some(path) = feedback_path() else {
+            return;
+        };
+
+        match FeedbackSettings::load(&path) {
+            Ok(settings) => self.feedback.settings = settings,
+            Err(error) => {
+                self.game.log.debug_warn(format!("Couldn't load feedback settings: {}", error))
+            }
+        }
+    }
+
+    /// Applies the log file format of the player's log config.
+    fn load_log_config(&mut self) {
+        let Some(path) = log_config_path() else {
+            return;
+        };
+
+        match LogConfig::load(&path) {
+            Ok(config) => self.game.log.set_format(config.format),
+            Err(error) => self.game.log.debug_warn(format!("Couldn't load log config: {}", error)),
+        }
+    }
+
+    /// Applies the level generation parameters of the player's config file. Replays are always generated with the defaults.
+    fn load_gen_config(&mut self) {
+        let Some(path) = gen_config_path() else {
+            return;
+        };
+
+        match GenConfig::load(&path) {
+            Ok(config) => self.game.gen_config = config,
+            Err(error) => self.game.log.debug_warn(format!("Couldn't load gen config: {}", error)),
+        }
+    }
+
+    /// Opens the lore a trigger fired in a window. During playback, the lore is written into the log instead, so the replay keeps running.
+    fn show_pending_lore(&mut self) {
+        let Some(lore) = self.game.pending_lore.take() else {
+            return;
+        };
+
+        if self.playback.is_some() {
+            self.game.log.info(LogData::Lore(lore.title));
+        } else {
+            self.ui.modal = Some(ModalInterface::TextDisplay {
+                title: lore.title,
+                paragraphs: lore.paragraphs,
+            });
+        }
+    }
+
+    /// Asks the player to pick one of the perks offered on a level-up milestone. During playback, the replay picks the perk.
+    fn show_pending_perks(&mut self) {
+        if self.game.pending_perks.is_empty() || self.playback.is_some() {
+            return;
+        }
+
+        let options = self
+            .game
+            .pending_perks
+            .iter()
+            .filter_map(|perk_id| perk_defs().get(perk_id))
+            .map(|def| format!("{} - {}", def.name, def.description))
+            .collect();
+        self.ui.modal = Some(ModalInterface::SelectPrompt {
+            selection_action: SelectionAction::ChoosePerk,
+            options,
+        });
+    }
+
+    /// Writes the morgue and replay files of a run that just ended and asks for a name for the high score table.
+    fn record_finished_run(&mut self) {
+        match self.game.write_replay_file() {
+            Ok(path) => self.game.log.print(format!("Saved the replay to {}", path.display())),
+            Err(error) => {
+                self.game.log.debug_warn(format!("Could not write replay file: {}", error))
+            }
+        }
+        match self.game.write_morgue_file() {
+            Ok(path) => self.morgue_path = Some(path),
+            Err(error) => {
+                self.game.log.debug_warn(format!("Could not write morgue file: {}", error))
+            }
+        }
+        self.ui.modal = Some(ModalInterface::NameInput { buffer: self.game.player.name.clone() });
+    }
+
+    /// Starts a new run with the given seed, or a new one if there is none.
+    ///
+    /// The difficulty and the display settings changed during the run (wound indicators, wide glyphs, ASCII mode, log filters) are kept.
+    fn restart(&mut self, seed: Option<u64>) {
+        let mut app = App::new(seed, self.game.difficulty.level);
+        app.ui.world_display.wound_indicators = self.ui.world_display.wound_indicators;
+        app.ui.world_display.wide_glyphs = self.ui.world_display.wide_glyphs;
+        app.ui.theme.ascii = self.ui.theme.ascii;
+        app.game.log.copy_filters_from(&self.game.log);
+        *self = app;
+    }
+
+    /// Plays the next turn of the replay that is played back.
+    fn step_playback(&mut self) {
+        let Some(playback) = &mut self.playback else {
+            return;
+        };
+
+        let Some(input) = playback.next_input() else {
+            return;
+        };
+
+        self.game.resolve_player_action(input);
+        if playback.is_finished() {
+            self.game.log.print("The replay is over. Press Shift+Q to quit.".to_string());
+        }
+    }
+}
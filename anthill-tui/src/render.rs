@@ -0,0 +1,12 @@
+pub mod animations;
+pub mod examine_display;
+pub mod help_display;
+pub mod info_display;
+pub mod legend_display;
+pub mod menu_display;
+pub mod modal_display;
+pub mod recorder;
+pub mod start_menu;
+pub mod theme;
+pub mod ui;
+pub mod world_display;
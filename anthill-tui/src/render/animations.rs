@@ -0,0 +1,104 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use ratatui::prelude::*;
+
+use crate::{core::combat::HitMarker, render::theme::Theme, render::world_display::Viewport};
+
+/// How long a damage popup stays over the target, unless a key is pressed before.
+pub const POPUP_DURATION: Duration = Duration::from_millis(600);
+
+/// Damage number floating over an entity that was hit.
+#[derive(Clone, Copy, Debug)]
+pub struct DamagePopup {
+    pub hit: HitMarker,
+    pub shown_until: Instant,
+}
+
+/// Short-lived effects drawn on top of the world, like [DamagePopup]s. They only show what happened and never change the game.
+#[derive(Default)]
+pub struct AnimationQueue {
+    popups: VecDeque<DamagePopup>,
+}
+
+impl AnimationQueue {
+    /// Adds a popup for each hit, shown for [POPUP_DURATION] from `now`.
+    pub fn push_hits(&mut self, hits: impl IntoIterator<Item = HitMarker>, now: Instant) {
+        let shown_until = now + POPUP_DURATION;
+        self.popups.extend(hits.into_iter().map(|hit| DamagePopup { hit, shown_until }));
+    }
+
+    /// Removes the popups whose time is up.
+    pub fn expire(&mut self, now: Instant) {
+        self.popups.retain(|popup| popup.shown_until > now);
+    }
+
+    /// Removes all popups, because the player moved on.
+    pub fn clear(&mut self) {
+        self.popups.clear();
+    }
+
+    /// Draws the damage of each popup one row above the target, or on the target itself at the top edge of the world.
+    pub fn render(&self, viewport: &Viewport, theme: &Theme, buf: &mut Buffer) {
+        for popup in &self.popups {
+            let Some(target) = viewport.display_pos(popup.hit.point) else {
+                continue;
+            };
+            let y = if target.y > viewport.rect.y { target.y - 1 } else { target.y };
+
+            let mut style = if popup.hit.on_player { theme.danger } else { theme.number };
+            if popup.hit.critical {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+
+            let text = if popup.hit.critical {
+                format!("{}!", popup.hit.damage)
+            } else {
+                popup.hit.damage.to_string()
+            };
+            let width = viewport.rect.right().saturating_sub(target.x);
+            buf.set_stringn(target.x, y, text, width as usize, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::coordinate_system::Point;
+
+    fn hit(damage: u16) -> HitMarker {
+        HitMarker { point: Point::new(3, 3), damage, critical: false, on_player: false }
+    }
+
+    #[test]
+    fn popups_disappear_after_their_duration() {
+        let start = Instant::now();
+        let mut queue = AnimationQueue::default();
+        queue.push_hits([hit(4)], start);
+        queue.push_hits([hit(2)], start + POPUP_DURATION / 2);
+
+        queue.expire(start + POPUP_DURATION / 4);
+        assert_eq!(queue.popups.len(), 2);
+        queue.expire(start + POPUP_DURATION);
+        assert_eq!(queue.popups.len(), 1);
+        queue.expire(start + POPUP_DURATION * 2);
+        assert!(queue.popups.is_empty());
+    }
+
+    #[test]
+    fn damage_is_drawn_above_the_target() {
+        let mut queue = AnimationQueue::default();
+        queue.push_hits([HitMarker { critical: true, ..hit(12) }], Instant::now());
+
+        let rect = Rect::new(0, 0, 10, 10);
+        let viewport = Viewport { rect, offset: Point::new(0, 0), cell_width: 1 };
+        let mut buf = Buffer::empty(rect);
+        queue.render(&viewport, &Theme::default(), &mut buf);
+
+        let row: String = (3..6).map(|x| buf[(x, 2)].symbol().to_string()).collect();
+        assert_eq!(row, "12!");
+    }
+}
@@ -0,0 +1,300 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::{
+    ai::npc_ai::NpcAiState,
+    core::{
+        buff_effects::PotionEffectDef,
+        damage_types::DamageType,
+        entity_logic::{Disposition, Entity, Npc},
+        game::GameState,
+        game_items::GameItemKindDef,
+    },
+    render::{theme::Theme, ui::get_centered_rect},
+    world::{
+        coordinate_system::Point,
+        lighting::RoomLighting,
+        tiles::{Collision, Interactable, Opacity},
+    },
+};
+
+/// Width of the hit point bar of an examined NPC, in characters.
+const HP_BAR_WIDTH: usize = 20;
+
+/// Displays everything there is to know about a point of the world, opened from look mode.
+pub fn render_examine(point: Point, area: Rect, buf: &mut Buffer, game: &GameState, theme: &Theme) {
+    let center = get_centered_rect(70, 28, area);
+
+    let block = theme.block().title(" Examine ").padding(Padding::new(1, 1, 1, 1));
+
+    let inner = block.inner(center);
+
+    Clear.render(center, buf);
+    block.render(center, buf);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    Paragraph::new(examine_lines(game, point, theme))
+        .wrap(Wrap { trim: false })
+        .render(chunks[0], buf);
+
+    Paragraph::new("Press ESC to go back to look mode")
+        .style(Style::default().add_modifier(Modifier::DIM))
+        .render(chunks[1], buf);
+}
+
+/// Details of the NPC, the items, the corpse and the tile at the point, taken from the live game state and the definitions.
+pub fn examine_lines(game: &GameState, point: Point, theme: &Theme) -> Vec<Line<'static>> {
+    let level = game.current_level();
+    let mut lines = Vec::new();
+
+    if let Some(npc) = level.get_npc_at(point).and_then(|npc_id| level.get_npc(npc_id))
+        && !game.is_hidden(npc)
+    {
+        lines.extend(npc_lines(game, npc, theme));
+        lines.push(Line::default());
+    }
+
+    for sprite in level.item_sprites.iter().filter(|sprite| sprite.pos() == point) {
+        lines.push(header_line(sprite.name()));
+        if let Some(item) = game.get_item_by_id(sprite.item_id)
+            && let Some(def) = game.get_item_def_by_id(&item.def_id)
+        {
+            if let Some(stats) = item_stats(&def.kind) {
+                lines.push(detail_line("Stats", stats));
+            }
+            lines.push(detail_line("Value", format!("{} gold", def.value)));
+            lines.push(detail_line("Weight", def.weight.to_string()));
+        }
+        lines.push(Line::default());
+    }
+
+    if let Some(corpse) = level.get_corpse_at(point).and_then(|id| level.get_corpse(id)) {
+        lines.push(header_line(corpse.name()));
+        lines.push(detail_line("Loot", format!("{} item(s)", corpse.loot.len())));
+        lines.push(detail_line(
+            "Decays in",
+            format!("{} rounds", corpse.decays_at.saturating_sub(game.round_nr)),
+        ));
+        lines.push(Line::default());
+    }
+
+    let tile = game.current_world().get_tile(point);
+    lines.push(header_line(&tile.tile_type.to_string()));
+    let walkable = match tile.tile_type.movement_cost() {
+        Some(1) => "yes".to_string(),
+        Some(cost) => format!("yes, takes {} turns", cost),
+        None => "no".to_string(),
+    };
+    lines.push(detail_line("Walkable", walkable));
+    lines.push(detail_line("Blocks sight", yes_no(tile.tile_type.is_opaque())));
+    lines.push(detail_line("Interactable", yes_no(tile.tile_type.is_interactable())));
+    lines.push(detail_line("Lighting", lighting_name(tile.lighting).to_string()));
+
+    lines
+}
+
+/// Stats and state of an NPC.
+fn npc_lines(game: &GameState, npc: &Npc, theme: &Theme) -> Vec<Line<'static>> {
+    let hp = &npc.stats.base;
+    let depth_bonus = game.level_scaling_modifiers().damage;
+    let mut damage = if depth_bonus > 0 {
+        format!("{} + {} (depth)", npc.stats.damage, depth_bonus)
+    } else {
+        npc.stats.damage.to_string()
+    };
+    let damage_type = game.npc_damage_type(npc);
+    if damage_type != DamageType::Physical {
+        damage = format!("{} {}", damage, damage_type.name());
+    }
+
+    let mut lines = vec![
+        header_line(npc.name()),
+        Line::from(vec![
+            Span::raw(format!("{:<14}", "Hit points")),
+            Span::styled(
+                hp_bar(hp.hp_current, hp.hp_max, HP_BAR_WIDTH, theme.ascii),
+                hp_style(hp.hp_current, hp.hp_max),
+            ),
+            Span::raw(format!(" {}/{}", hp.hp_current, hp.hp_max)),
+        ]),
+        detail_line("Damage", damage),
+        detail_line("Dodge", format!("{}%", npc.stats.dodge_chance())),
+        detail_line("Mitigation", npc.stats.mitigation.to_string()),
+        detail_line("Attitude", disposition_name(npc.disposition).to_string()),
+        detail_line("Behavior", ai_state_name(&npc.ai_state).to_string()),
+    ];
+    let affinities = game.npc_affinities(npc);
+    let weaknesses: Vec<&str> =
+        affinities.vulnerabilities().map(|damage_type| damage_type.name()).collect();
+    if !weaknesses.is_empty() {
+        lines.push(detail_line("Weak to", weaknesses.join(", ")));
+    }
+    if npc.crowd_control.confused > 0 {
+        lines.push(detail_line("Confused", format!("{} turns", npc.crowd_control.confused)));
+    }
+    if npc.crowd_control.feared > 0 {
+        lines.push(detail_line("Frightened", format!("{} turns", npc.crowd_control.feared)));
+    }
+    if npc.distracted_turns > 0 {
+        lines.push(detail_line("Distracted", format!("{} turns", npc.distracted_turns)));
+    }
+    if npc.size > 1 {
+        lines.push(detail_line("Size", format!("{0}x{0} tiles", npc.size)));
+    }
+    lines
+}
+
+/// Stats of an item that depend on its kind.
+fn item_stats(kind: &GameItemKindDef) -> Option<String> {
+    match kind {
+        GameItemKindDef::Weapon { damage, crit_chance, range, damage_type } => {
+            let range = match range {
+                Some(range) => format!("range {}", range),
+                None => "melee".to_string(),
+            };
+            Some(format!(
+                "{} {} damage, {}% crit, {}",
+                damage,
+                damage_type.name(),
+                crit_chance,
+                range
+            ))
+        }
+        GameItemKindDef::Armor { mitigation, .. } => Some(format!("{} mitigation", mitigation)),
+        GameItemKindDef::Food { nutrition } => Some(format!("{} nutrition", nutrition)),
+        GameItemKindDef::Potion { effect } => Some(potion_effect_text(effect)),
+        GameItemKindDef::Bag { capacity } => Some(format!("holds {} items", capacity)),
+        GameItemKindDef::FireStarter => Some("builds a campfire".to_string()),
+        GameItemKindDef::RemoveCurse => Some("lifts curses".to_string()),
+        GameItemKindDef::JokeBook | GameItemKindDef::Trinket => None,
+    }
+}
+
+fn potion_effect_text(effect: &PotionEffectDef) -> String {
+    match effect {
+        PotionEffectDef::Heal { amount } => format!("heals {} hit points", amount),
+        PotionEffectDef::Strength { amount, duration } => {
+            format!("+{} strength for {} turns", amount, duration)
+        }
+        PotionEffectDef::Dexterity { amount, duration } => {
+            format!("+{} dexterity for {} turns", amount, duration)
+        }
+        PotionEffectDef::Poison { damage_per_tick, duration } => {
+            format!("{} poison damage per turn for {} turns", damage_per_tick, duration)
+        }
+        PotionEffectDef::Fatigue { strength_penalty, duration } => {
+            format!("-{} strength for {} turns", strength_penalty, duration)
+        }
+        PotionEffectDef::Cramp { dexterity_penalty, duration } => {
+            format!("-{} dexterity for {} turns", dexterity_penalty, duration)
+        }
+        PotionEffectDef::SeeInvisible { duration } => {
+            format!("reveals invisible creatures for {} turns", duration)
+        }
+    }
+}
+
+fn disposition_name(disposition: Disposition) -> &'static str {
+    match disposition {
+        Disposition::Hostile => "hostile",
+        Disposition::Friendly => "friendly",
+        Disposition::Neutral => "neutral",
+        Disposition::Companion => "your companion",
+    }
+}
+
+fn ai_state_name(state: &NpcAiState) -> &'static str {
+    match state {
+        NpcAiState::Inactive => "stays in place",
+        NpcAiState::Wandering => "wandering",
+        NpcAiState::Aggressive => "chasing you",
+        NpcAiState::Hunting => "hunting you",
+        NpcAiState::Companion => "following you",
+    }
+}
+
+fn lighting_name(lighting: RoomLighting) -> &'static str {
+    match lighting {
+        RoomLighting::Normal => "normal",
+        RoomLighting::Dim => "dim",
+        RoomLighting::Torchlit => "torchlit",
+        RoomLighting::FungalGlow => "glowing fungi",
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+/// Bar of `width` characters that is filled by the share of hit points left. Drawn with `#` and `-` in ASCII mode.
+fn hp_bar(current: u16, max: u16, width: usize, ascii: bool) -> String {
+    let filled = if max == 0 { 0 } else { (current as usize * width).div_ceil(max as usize) };
+    let filled = filled.min(width);
+    let (full, empty) = if ascii { ("#", "-") } else { ("█", "░") };
+    format!("{}{}", full.repeat(filled), empty.repeat(width - filled))
+}
+
+/// Red below a fifth of the hit points, like the player's own hit points (see [InfoDisplay](crate::render::info_display::InfoDisplay)).
+fn hp_style(current: u16, max: u16) -> Style {
+    if current * 5 <= max {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default().fg(Color::Green)
+    }
+}
+
+fn header_line(title: &str) -> Line<'static> {
+    Line::styled(format!("=== {} ===", title), Style::default().add_modifier(Modifier::BOLD))
+}
+
+fn detail_line(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![Span::raw(format!("{:<14}", label)), Span::raw(value)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    fn text(lines: &[Line]) -> String {
+        lines.iter().map(|line| line.to_string()).collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn hp_bar_shows_the_share_left() {
+        assert_eq!(hp_bar(10, 10, 4, false), "████");
+        assert_eq!(hp_bar(5, 10, 4, false), "██░░");
+        assert_eq!(hp_bar(1, 10, 4, false), "█░░░");
+        assert_eq!(hp_bar(0, 10, 4, false), "░░░░");
+        assert_eq!(hp_bar(5, 10, 4, true), "##--");
+    }
+
+    #[test]
+    fn npcs_are_examined_with_their_live_stats() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 10, 6));
+        game.levels.insert(0, level);
+
+        let mut goblin = game.create_npc("goblin".into(), Point::new(8, 7)).unwrap();
+        goblin.stats.base.take_damage(1);
+        let hp = format!("{}/{}", goblin.stats.base.hp_current, goblin.stats.base.hp_max);
+        let damage = goblin.stats.damage.to_string();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+
+        let details = text(&examine_lines(&game, Point::new(8, 7), &Theme::default()));
+        assert!(details.contains("Goblin"));
+        assert!(details.contains(&hp));
+        assert!(details.contains(&damage));
+        assert!(details.contains("Floor"));
+
+        let details = text(&examine_lines(&game, Point::new(9, 7), &Theme::default()));
+        assert!(!details.contains("Goblin"));
+    }
+}
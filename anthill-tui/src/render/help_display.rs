@@ -0,0 +1,393 @@
+use std::collections::BTreeMap;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Padding, Paragraph, Row, Table, Wrap},
+};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    core::{
+        combat::{CRIT_MULTIPLIER, NPC_BASE_CRIT_CHANCE},
+        game::GameState,
+    },
+    data::{item_defs::item_defs, npc_defs::npc_defs},
+    render::{
+        theme::{Theme, terminal_style},
+        ui::get_centered_rect,
+    },
+    util::{
+        command_handler::GameCommand,
+        input_handler::{GLOBAL_KEYMAP, WORLD_KEYMAP, key_label},
+    },
+    world::{
+        coordinate_system::Point,
+        tiles::{DoorType, Drawable, ShrineState, TileType},
+    },
+};
+
+/// Pages of the help browser. Switched with the arrow keys or PgUp/PgDn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter)]
+pub enum HelpPage {
+    #[default]
+    Keys,
+    Commands,
+    Symbols,
+    Combat,
+}
+
+impl HelpPage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HelpPage::Keys => "Keys",
+            HelpPage::Commands => "Commands",
+            HelpPage::Symbols => "Symbols",
+            HelpPage::Combat => "Combat",
+        }
+    }
+
+    /// The next page, starting over at the first one.
+    pub fn next(self) -> Self {
+        let pages: Vec<HelpPage> = Self::iter().collect();
+        let index = pages.iter().position(|page| *page == self).unwrap_or(0);
+        pages[(index + 1) % pages.len()]
+    }
+
+    /// The previous page, continuing at the last one.
+    pub fn previous(self) -> Self {
+        let pages: Vec<HelpPage> = Self::iter().collect();
+        let index = pages.iter().position(|page| *page == self).unwrap_or(0);
+        pages[(index + pages.len() - 1) % pages.len()]
+    }
+}
+
+/// Terrain listed on the [HelpPage::Symbols] page.
+const LEGEND_TILES: [TileType; 17] = [
+    TileType::Floor,
+    TileType::Hallway,
+    TileType::Door(DoorType::Closed),
+    TileType::Door(DoorType::Locked),
+    TileType::Door(DoorType::Open),
+    TileType::StairsDown,
+    TileType::StairsUp,
+    TileType::Campfire,
+    TileType::Stash,
+    TileType::Chest,
+    TileType::Memorial,
+    TileType::Shrine(ShrineState::Active),
+    TileType::Altar(ShrineState::Active),
+    TileType::Water,
+    TileType::Lava,
+    TileType::Chasm,
+    TileType::SpikeTrap,
+];
+
+/// Displays the help browser, opened on the given page.
+pub fn render_help(page: HelpPage, area: Rect, buf: &mut Buffer, game: &GameState, theme: &Theme) {
+    let center = get_centered_rect(150, 33, area);
+
+    let block = theme.block().title(" Help ").padding(Padding::new(1, 1, 1, 1));
+
+    let inner = block.inner(center);
+
+    Clear.render(center, buf);
+    block.render(center, buf);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // page tabs
+            Constraint::Min(1),    // page
+            Constraint::Length(1), // footer
+        ])
+        .split(inner);
+
+    // Pages in the top line, the open one highlighted
+    let tab_spans: Vec<Span> = HelpPage::iter()
+        .map(|tab| {
+            let style = if tab == page {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            };
+            Span::styled(format!(" {} ", tab.name()), style)
+        })
+        .collect();
+    buf.set_line(chunks[0].x, chunks[0].y, &Line::from(tab_spans), chunks[0].width);
+
+    match page {
+        HelpPage::Keys => render_keys_page(chunks[1], buf),
+        HelpPage::Commands => render_commands_page(chunks[1], buf),
+        HelpPage::Symbols => render_symbols_page(chunks[1], buf),
+        HelpPage::Combat => render_combat_page(chunks[1], buf, game),
+    }
+
+    Paragraph::new("←/→ or PgUp/PgDn - switch page    ESC - close this window")
+        .style(Style::default().add_modifier(Modifier::DIM))
+        .render(chunks[2], buf);
+}
+
+/// Section header of a help page.
+fn header_line(title: &str) -> Line<'static> {
+    Line::styled(format!("=== {} ===", title), Style::default().add_modifier(Modifier::BOLD))
+}
+
+/// A key and what it does.
+fn key_line(key: impl Into<String>, description: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{:<16}", key.into()), Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(description.to_string()),
+    ])
+}
+
+/// A glyph in its own style and what it stands for.
+fn symbol_line(glyph: char, style: Style, description: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(glyph.to_string(), style),
+        Span::raw(format!(" - {}", description)),
+    ])
+}
+
+/// The controls, generated from the [GLOBAL_KEYMAP] and [WORLD_KEYMAP], next to the keys of the menus and modes.
+fn render_keys_page(area: Rect, buf: &mut Buffer) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let mut controls = vec![header_line("GENERAL")];
+    for (code, action) in GLOBAL_KEYMAP {
+        controls.push(key_line(key_label(code), action.description()));
+    }
+    controls.push(key_line(":", "open command prompt"));
+    controls.push(key_line("ESC", "close menus"));
+    controls.push(Line::default());
+
+    controls.push(header_line("WORLD"));
+    for (code, action) in WORLD_KEYMAP {
+        controls.push(key_line(key_label(code), action.description()));
+    }
+    controls.push(key_line("walk into enemy", "melee attack"));
+    controls.push(key_line("walk into ally", "swap places"));
+    controls.push(key_line("walk into campfire", "cook food"));
+
+    let modes = vec![
+        header_line("INVENTORY"),
+        key_line("a, b, c…", "select item"),
+        key_line("TAB", "switch tab"),
+        key_line("PGUP/PGDN, </>", "switch page"),
+        key_line("ESC", "back to the world"),
+        Line::default(),
+        header_line("LOOK MODE / RANGED ATTACK"),
+        key_line("w/a/s/d", "move cursor"),
+        key_line("ENTER", "inspect selected tile / fire at target"),
+        key_line("t", "travel to selected tile (look mode)"),
+        key_line("ESC", "leave the mode"),
+        Line::default(),
+        header_line("COMMAND PROMPT"),
+        key_line("ENTER", "run command"),
+        key_line("TAB", "complete command or item"),
+        key_line("UP/DOWN", "recall earlier commands"),
+        key_line("ESC", "cancel"),
+        Line::default(),
+        header_line("START SCREEN"),
+        key_line("ENTER", "start game"),
+        key_line("s", "high scores"),
+        key_line("d", "change difficulty"),
+    ];
+
+    Paragraph::new(controls).render(columns[0], buf);
+    Paragraph::new(modes).render(columns[1], buf);
+}
+
+/// The commands that can be entered in the command prompt.
+fn render_commands_page(area: Rect, buf: &mut Buffer) {
+    const COMMAND_WIDTHS: [Constraint; 2] =
+        [Constraint::Percentage(13), Constraint::Percentage(87)];
+
+    let player_commands = [
+        GameCommand::Quit,
+        GameCommand::Help,
+        GameCommand::PlayerInfo,
+        GameCommand::Legend,
+        GameCommand::Wounds,
+        GameCommand::Glyphs,
+        GameCommand::Ascii,
+    ];
+
+    let dev_commands = [
+        GameCommand::MaxStats,
+        GameCommand::MaxEquip,
+        GameCommand::RngTest,
+        GameCommand::Suicide,
+        GameCommand::Teleport(Point::new(0, 0)), // dummy
+        GameCommand::Give { item_def: "".into(), amount: 0 }, // dummy
+        GameCommand::RevealAll,
+        GameCommand::NoClip,
+        GameCommand::GodMode,
+    ];
+
+    let command_rows = |commands: &[GameCommand]| -> Vec<Row> {
+        commands
+            .iter()
+            .map(|cmd| Row::new(vec![cmd.name().to_string(), cmd.description().to_string()]))
+            .collect()
+    };
+    let player_command_rows = command_rows(&player_commands);
+    let dev_command_rows = command_rows(&dev_commands);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // player commands header
+            Constraint::Length(player_command_rows.len() as u16 + 1), // player commands table
+            Constraint::Length(1), // dev commands header
+            Constraint::Length(dev_command_rows.len() as u16), // dev commands table
+        ])
+        .split(area);
+
+    Paragraph::new(header_line("PLAYER COMMANDS")).render(chunks[0], buf);
+    Widget::render(
+        Table::new(player_command_rows, COMMAND_WIDTHS).column_spacing(1),
+        chunks[1],
+        buf,
+    );
+
+    Paragraph::new(header_line("DEVELOPER COMMANDS")).render(chunks[2], buf);
+    Widget::render(Table::new(dev_command_rows, COMMAND_WIDTHS).column_spacing(1), chunks[3], buf);
+}
+
+/// Legend of the glyphs of terrain, creatures and items. Items sharing a glyph are listed together.
+fn render_symbols_page(area: Rect, buf: &mut Buffer) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+        ])
+        .split(area);
+
+    let mut terrain = vec![header_line("TERRAIN")];
+    terrain.push(symbol_line('@', Style::default().fg(Color::Yellow), "You".to_string()));
+    for tile in LEGEND_TILES {
+        terrain.push(symbol_line(tile.glyph(), terminal_style(tile.style()), tile.to_string()));
+    }
+
+    let mut npcs: Vec<_> = npc_defs().values().collect();
+    npcs.sort_by_key(|npc| npc.name);
+    let mut creatures = vec![header_line("CREATURES")];
+    for npc in npcs {
+        creatures.push(symbol_line(npc.glyph, terminal_style(npc.style), npc.name.to_string()));
+    }
+
+    let mut item_groups: BTreeMap<char, (Style, Vec<&str>)> = BTreeMap::new();
+    for item in item_defs().values() {
+        item_groups
+            .entry(item.glyph)
+            .or_insert((terminal_style(item.style), Vec::new()))
+            .1
+            .push(item.name);
+    }
+    let mut items = vec![header_line("ITEMS")];
+    for (glyph, (style, mut names)) in item_groups {
+        names.sort();
+        items.push(symbol_line(glyph, style, names.join(", ")));
+    }
+
+    Paragraph::new(terrain).render(columns[0], buf);
+    Paragraph::new(creatures).render(columns[1], buf);
+    Paragraph::new(items).wrap(Wrap { trim: false }).render(columns[2], buf);
+}
+
+/// How attacks, damage and healing work. The numbers are taken from the rules of the current run.
+fn render_combat_page(area: Rect, buf: &mut Buffer, game: &GameState) {
+    let regen = &game.regen_rules;
+    let level = game.player.character.stats.level;
+
+    let rules = [
+        (
+            "ATTACKS",
+            "Walk into an enemy to attack it with your melee weapon. A ranged weapon fires at a target picked in ranged attack mode, which has to be in range and in line of sight.".to_string(),
+        ),
+        (
+            "DODGING",
+            "The defender rolls a d100 against their dodge chance. On a success the attack misses. Your dodge chance is half your dexterity, at most 50%.".to_string(),
+        ),
+        (
+            "CRITICAL HITS",
+            format!(
+                "The attacker rolls a d100 against their crit chance: your weapon's, or {}% for monsters. A critical hit deals {}x damage.",
+                NPC_BASE_CRIT_CHANCE, CRIT_MULTIPLIER
+            ),
+        ),
+        (
+            "DAMAGE",
+            "Melee damage is increased by your strength, ranged damage by your perception. The mitigation of the defender's armor is subtracted from every hit.".to_string(),
+        ),
+        (
+            "EXPLOSIONS",
+            "Explosions cannot be dodged, but mitigation still applies. Walls between you and the blast keep you safe.".to_string(),
+        ),
+        (
+            "DEPTH",
+            format!(
+                "Monsters hit harder the deeper you go. On {} difficulty they deal {} more damage on floor 10.",
+                game.difficulty.level,
+                game.difficulty.depth_damage(10)
+            ),
+        ),
+        (
+            "HEALING",
+            format!(
+                "You regenerate {} hit point every {} rounds at level {}. Poison stops the regeneration. Rest with SHIFT + r to wait until healed.",
+                regen.amount,
+                regen.interval(level),
+                level
+            ),
+        ),
+        (
+            "EXPERIENCE",
+            "Slain monsters grant experience. You level up after gaining 100 experience times your level, which raises all your stats and restores your hit points.".to_string(),
+        ),
+    ];
+
+    let mut lines = Vec::new();
+    for (title, text) in rules {
+        lines.push(header_line(title));
+        lines.push(Line::raw(text));
+        lines.push(Line::default());
+    }
+
+    Paragraph::new(lines).wrap(Wrap { trim: false }).render(area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_cycle_in_both_directions() {
+        assert_eq!(HelpPage::Keys.next(), HelpPage::Commands);
+        assert_eq!(HelpPage::Combat.next(), HelpPage::Keys);
+        assert_eq!(HelpPage::Keys.previous(), HelpPage::Combat);
+        for page in HelpPage::iter() {
+            assert_eq!(page.next().previous(), page);
+        }
+    }
+
+    #[test]
+    fn keymap_binds_each_key_once() {
+        let mut labels: Vec<String> = GLOBAL_KEYMAP
+            .iter()
+            .map(|(code, _)| key_label(*code))
+            .chain(WORLD_KEYMAP.iter().map(|(code, _)| key_label(*code)))
+            .collect();
+        let count = labels.len();
+        labels.sort();
+        labels.dedup();
+        assert_eq!(labels.len(), count);
+        assert!(labels.contains(&"SHIFT + r".to_string()));
+    }
+}
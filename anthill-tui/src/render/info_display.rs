@@ -0,0 +1,315 @@
+use std::time::Duration;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Cell, Row, Table},
+};
+
+use crate::{
+    core::{
+        buff_effects::{PotionEffectDef, PotionType},
+        crowd_control::CrowdControl,
+        damage_types::DamageType,
+        entity_logic::Entity,
+        game::GameState,
+        game_items::{GameItem, GameItemKindDef},
+        skills::{Skill, rank_cap},
+        town::TOWN_LEVEL,
+    },
+    util::rng::Roll,
+};
+
+/// How long an expiring buff stays lit or dimmed before it switches, so it blinks on every terminal.
+pub const BLINK_INTERVAL: Duration = Duration::from_millis(400);
+
+pub struct InfoDisplay;
+
+impl InfoDisplay {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Renders the Info Display
+    ///
+    /// The info display displays character info and information about the game.
+    /// * Character Info
+    ///     * Character Strength
+    ///     * Character Dexterity
+    ///     * Character Vitality
+    ///     * Character Perception
+    ///     * Character Hit Points
+    ///     * Character equipped armor
+    ///     * Character equipped weapon
+    ///     * Character position
+    ///     * Active buffs with their remaining turns (blinking when about to expire, dimmed while `blink_on` is false)
+    ///     * Skills with their rank and the practice towards the next one
+    /// * Game Info
+    ///     * Character level and the experience points towards the next one
+    ///     * Gold carried
+    ///     * Current game round
+    ///     * Depth the character is currently on
+    pub fn render(&self, game: &GameState, blink_on: bool, rect: Rect, buf: &mut Buffer) {
+        let player_hp_current = self.format_hp(game);
+        let player_hp_max = game.player.character.stats.base.hp_max;
+        let weapon = self.format_weapon(game);
+        let armor = self.format_armor(game);
+        let buffs = self.format_buffs(game, blink_on);
+        let skills = self.format_skills(game);
+        let stats = &game.player.character.stats;
+
+        let info_rows = [
+            Row::new(vec![
+                Cell::from(Line::from(vec![
+                    Span::raw("HP: "),
+                    player_hp_current.clone(),
+                    Span::raw(format!("/{}", player_hp_max)),
+                ])),
+                Cell::from(format!("Weapon: {}", weapon)),
+                Cell::from(format!(
+                    "Level: {}, EXP: {}/{}",
+                    stats.level,
+                    stats.experience,
+                    stats.experience_needed()
+                )),
+                Cell::from(match game.level_nr {
+                    TOWN_LEVEL => "Town".to_string(),
+                    level_nr => format!("Depth: {}", level_nr),
+                }),
+            ]),
+            Row::new(vec![
+                Cell::from(Line::from(vec![
+                    Span::styled("STR: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!("{}, ", stats.strength)),
+                    Span::styled("DEX: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!("{}, ", stats.dexterity)),
+                    Span::styled("VIT: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!("{}, ", stats.vitality)),
+                    Span::styled("PER: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!("{}", stats.perception)),
+                ])),
+                Cell::from(format!("Armor: {}", armor)),
+                Cell::from(format!(
+                    "Gold: {}, Turn: {}",
+                    game.player.character.gold, game.round_nr
+                )),
+                Cell::from(format!(
+                    "x: {}, y: {}",
+                    game.player.character.pos().x,
+                    game.player.character.pos().y
+                )),
+            ]),
+        ];
+
+        const INFO_WIDTHS: [Constraint; 4] = [
+            Constraint::Percentage(28),
+            Constraint::Percentage(28),
+            Constraint::Percentage(28),
+            Constraint::Percentage(16),
+        ];
+
+        let [area_table, area_buffs, area_skills] =
+            Layout::vertical([Constraint::Length(2), Constraint::Length(1), Constraint::Length(1)])
+                .areas(rect);
+
+        let info_table = Table::new(info_rows, INFO_WIDTHS);
+
+        Widget::render(info_table, area_table, buf);
+        Widget::render(buffs, area_buffs, buf);
+        Widget::render(skills, area_skills, buf);
+    }
+
+    /// Render the player's skills with their rank and the practice towards the next rank, e.g. `Melee 2 (40%)`.
+    ///
+    /// Skills at the cap of the character level show no progress.
+    fn format_skills(&self, game: &GameState) -> Line<'_> {
+        let character = &game.player.character;
+        let cap = rank_cap(character.stats.level);
+
+        let mut spans = vec![Span::raw("Skills: ")];
+        for (index, skill) in Skill::ALL.into_iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw(", "));
+            }
+            let progress = character.skills.get(skill);
+            spans.push(Span::styled(
+                format!("{} ", skill.name()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(progress.rank.to_string()));
+            if progress.rank < cap {
+                let percent = progress.practice as u32 * 100 / progress.practice_needed() as u32;
+                spans.push(Span::raw(format!(" ({}%)", percent)));
+            }
+        }
+        Line::from(spans)
+    }
+
+    /// Render the player's active buffs as a row of badges, e.g. `STR+3 (5)`.
+    ///
+    /// Badges of buffs that are about to expire blink.
+    fn format_buffs(&self, game: &GameState, blink_on: bool) -> Line<'_> {
+        let active_buffs = &game.player.character.active_buffs;
+        let slowed = game.is_slowed();
+        let crowd_control = game.player.character.crowd_control;
+        let controls: Vec<&str> = [
+            (crowd_control.is_active(CrowdControl::Confused), "CONF"),
+            (crowd_control.is_active(CrowdControl::Feared), "FEAR"),
+        ]
+        .into_iter()
+        .filter_map(|(active, badge)| active.then_some(badge))
+        .collect();
+        if active_buffs.is_empty() && !slowed && controls.is_empty() {
+            return Line::from("Effects: None");
+        }
+
+        let mut spans = vec![Span::raw("Effects: ")];
+        if slowed {
+            spans.push(Span::styled("SLOW", Style::default().fg(Color::Red)));
+            spans.push(Span::raw(" "));
+        }
+        for badge in controls {
+            spans.push(Span::styled(badge, Style::default().fg(Color::Magenta)));
+            spans.push(Span::raw(" "));
+        }
+        for buff in active_buffs {
+            let potion_type = buff.effect.potion_type();
+            let amount = match buff.effect {
+                PotionEffectDef::Heal { amount } => Some(amount),
+                PotionEffectDef::Strength { amount, .. } => Some(amount as u16),
+                PotionEffectDef::Dexterity { amount, .. } => Some(amount as u16),
+                PotionEffectDef::Poison { damage_per_tick, .. } => Some(damage_per_tick),
+                PotionEffectDef::Fatigue { strength_penalty, .. } => Some(strength_penalty as u16),
+                PotionEffectDef::Cramp { dexterity_penalty, .. } => Some(dexterity_penalty as u16),
+                PotionEffectDef::SeeInvisible { .. } => None,
+            };
+            let color = match potion_type {
+                PotionType::Heal
+                | PotionType::Strength
+                | PotionType::Dexterity
+                | PotionType::SeeInvisible => Color::Green,
+                PotionType::Poison | PotionType::Fatigue | PotionType::Cramp => Color::Red,
+            };
+
+            let mut style = Style::default().fg(color);
+            if buff.is_expiring() && !blink_on {
+                style = style.add_modifier(Modifier::DIM);
+            }
+
+            spans.push(Span::styled(
+                format!(
+                    "{}{} ({})",
+                    potion_type.badge(),
+                    amount.map_or_else(String::new, |amount| amount.to_string()),
+                    buff.remaining_turns
+                ),
+                style,
+            ));
+            spans.push(Span::raw(" "));
+        }
+
+        Line::from(spans)
+    }
+
+    /// Render the currently equipped armor into a String, displaying its stats.
+    fn format_armor(&self, game: &GameState) -> String {
+        match &game.player.character.armor {
+            Some(w) => {
+                // look up the instance by GameItemId
+                let instance = match game.items.get(&w.0) {
+                    Some(i) => i,
+                    None => return "Invalid armor".to_string(),
+                };
+
+                // look up the definition by def_id
+                let def = match game.get_item_def_by_id(&instance.def_id) {
+                    Some(d) => d,
+                    None => return "Invalid armor".to_string(),
+                };
+
+                // extract stats from GameItemKindDef
+                match def.kind {
+                    GameItemKindDef::Armor { mitigation, .. } => {
+                        format!("{}{} <{} MIT>", def.name, curse_marker(instance), mitigation)
+                    }
+                    _ => "Invalid armor".to_string(),
+                }
+            }
+            None => "None".to_string(),
+        }
+    }
+
+    /// Render the currently equipped weapon into a String, displaying its stats.
+    pub fn format_weapon(&self, game: &GameState) -> String {
+        match &game.player.character.weapon {
+            Some(w) => {
+                // look up the instance by GameItemId
+                let instance = match game.items.get(&w.0) {
+                    Some(i) => i,
+                    None => return "Invalid weapon".to_string(),
+                };
+
+                // look up the definition by def_id
+                let def = match game.get_item_def_by_id(&instance.def_id) {
+                    Some(d) => d,
+                    None => return "Invalid weapon".to_string(),
+                };
+
+                // extract stats from GameItemKindDef
+                match def.kind {
+                    GameItemKindDef::Weapon {
+                        damage,
+                        crit_chance,
+                        range: Some(range),
+                        damage_type,
+                    } => {
+                        format!(
+                            "{}{} <{}, {}% CRIT, {} RNG>",
+                            def.name,
+                            curse_marker(instance),
+                            damage_label(damage, damage_type),
+                            crit_chance,
+                            range
+                        )
+                    }
+                    GameItemKindDef::Weapon { damage, crit_chance, range: None, damage_type } => {
+                        format!(
+                            "{}{} <{}, {}% CRIT>",
+                            def.name,
+                            curse_marker(instance),
+                            damage_label(damage, damage_type),
+                            crit_chance
+                        )
+                    }
+                    _ => "Invalid weapon".to_string(),
+                }
+            }
+            None => "Fist <1d4 DMG, 5% CRIT>".to_string(),
+        }
+    }
+
+    /// Format the player's current hit points for display.
+    pub fn format_hp(&self, game: &GameState) -> Span<'_> {
+        let hp_current = game.player.character.stats.base.hp_current;
+        let hp_max = game.player.character.stats.base.hp_max;
+        let mut color = Color::Gray;
+
+        if hp_current * 5 <= hp_max {
+            color = Color::Red;
+        }
+
+        Span::styled(hp_current.to_string(), Style::default().fg(color))
+    }
+}
+
+/// Marks equipped items that are cursed. Equipping an item reveals its curse, so the marker gives nothing away.
+fn curse_marker(item: &GameItem) -> &'static str {
+    if item.cursed { " (cursed)" } else { "" }
+}
+
+/// Damage of a weapon, naming its type if it is elemental.
+fn damage_label(damage: Roll, damage_type: DamageType) -> String {
+    match damage_type {
+        DamageType::Physical => format!("{} DMG", damage),
+        _ => format!("{} {} DMG", damage, damage_type.name().to_uppercase()),
+    }
+}
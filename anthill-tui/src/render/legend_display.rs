@@ -0,0 +1,196 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Padding, Paragraph},
+};
+
+use anthill_core::render::style::Style as GameStyle;
+
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityBase},
+        game::GameState,
+        game_items::{ITEM_STACK_GLYPH, ITEM_STACK_STYLE},
+    },
+    render::{
+        theme::{Theme, terminal_style},
+        ui::get_centered_rect,
+        world_display::{PLAYER_ASCII_GLYPH, ascii_wall_glyph, wall_glyph, wall_mask},
+    },
+    world::{
+        coordinate_system::Point,
+        tiles::{Drawable, TileType},
+    },
+};
+
+/// A symbol on the map and what it stands for. Walls are drawn with several glyphs, so an entry can have more than one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegendEntry {
+    pub glyphs: Vec<char>,
+    pub style: Style,
+    pub name: String,
+}
+
+/// Symbols shown on the map, grouped into the layers they are drawn on.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MapLegend {
+    pub terrain: Vec<LegendEntry>,
+    pub items: Vec<LegendEntry>,
+    pub creatures: Vec<LegendEntry>,
+}
+
+impl MapLegend {
+    /// Collects the symbols of the current level that the player can see on the map.
+    ///
+    /// Terrain is listed if it is visible or remembered. Items, corpses and creatures are only listed if they stand on a visible tile,
+    /// just like [WorldDisplay](crate::render::world_display::WorldDisplay) draws them.
+    pub fn of_visible_map(game: &GameState, theme: &Theme) -> Self {
+        let world = game.current_world();
+        let biome = game.current_level().biome;
+        let mut legend = Self::default();
+
+        for y in 0..world.height {
+            for x in 0..world.width {
+                let point = Point::new(x, y);
+                let tile = world.get_tile(point);
+                if !tile.visible && !tile.explored || tile.tile_type == TileType::Void {
+                    continue;
+                }
+
+                let glyph = match tile.tile_type {
+                    TileType::Wall if theme.ascii => ascii_wall_glyph(wall_mask(world, point)),
+                    TileType::Wall => wall_glyph(wall_mask(world, point)),
+                    tile_type => theme.glyph(&tile_type),
+                };
+                let style = biome.palette(tile.tile_type, tile.tile_type.style());
+                add_entry(&mut legend.terrain, glyph, style, tile.tile_type.to_string());
+            }
+        }
+
+        let in_sight = |point: Point| world.get_tile(point).visible;
+        let level = game.current_level();
+
+        for corpse in level.corpses.iter().filter(|corpse| in_sight(corpse.pos())) {
+            add_entity(&mut legend.items, &corpse.base, theme);
+        }
+        for sprite in level.item_sprites.iter().filter(|sprite| in_sight(sprite.pos())) {
+            add_entity(&mut legend.items, &sprite.base, theme);
+            if level.item_sprites_at(sprite.pos()).nth(1).is_some() {
+                add_entry(
+                    &mut legend.items,
+                    ITEM_STACK_GLYPH,
+                    ITEM_STACK_STYLE,
+                    "Pile of items".to_string(),
+                );
+            }
+        }
+
+        let player = &game.player.character.base;
+        add_entry(
+            &mut legend.creatures,
+            if theme.ascii && !player.glyph.is_ascii() { PLAYER_ASCII_GLYPH } else { player.glyph },
+            player.style(),
+            "You".to_string(),
+        );
+        for npc in level.npcs.iter().filter(|npc| game.can_see_npc(npc)) {
+            add_entity(&mut legend.creatures, &npc.base, theme);
+        }
+
+        legend
+    }
+}
+
+/// Adds the glyph of an entity to the legend.
+fn add_entity(entries: &mut Vec<LegendEntry>, base: &EntityBase, theme: &Theme) {
+    add_entry(entries, theme.glyph(base), base.style(), base.name.clone());
+}
+
+/// Adds a symbol to the legend. Symbols with the same name share an entry.
+fn add_entry(entries: &mut Vec<LegendEntry>, glyph: char, style: GameStyle, name: String) {
+    match entries.iter_mut().find(|entry| entry.name == name) {
+        Some(entry) => {
+            if !entry.glyphs.contains(&glyph) {
+                entry.glyphs.push(glyph);
+            }
+        }
+        None => {
+            entries.push(LegendEntry { glyphs: vec![glyph], style: terminal_style(style), name })
+        }
+    }
+}
+
+/// Displays the legend of all symbols the player can currently see on the map.
+pub fn render_legend(area: Rect, buf: &mut Buffer, game: &GameState, theme: &Theme) {
+    let legend = MapLegend::of_visible_map(game, theme);
+
+    let center = get_centered_rect(100, 30, area);
+
+    let block = theme.block().title(" Legend ").padding(Padding::new(1, 1, 1, 1));
+
+    let inner = block.inner(center);
+
+    Clear.render(center, buf);
+    block.render(center, buf);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+        .split(chunks[0]);
+
+    let sections =
+        [("TERRAIN", &legend.terrain), ("ITEMS", &legend.items), ("CREATURES", &legend.creatures)];
+
+    for ((title, entries), column) in sections.into_iter().zip(columns.iter()) {
+        let mut lines = vec![Line::styled(
+            format!("=== {} ===", title),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for entry in entries {
+            let glyphs: String = entry.glyphs.iter().collect();
+            lines.push(Line::from(vec![
+                Span::styled(glyphs, entry.style),
+                Span::raw(format!(" - {}", entry.name)),
+            ]));
+        }
+        Paragraph::new(lines).render(*column, buf);
+    }
+
+    Paragraph::new("Press ESC to close this window")
+        .style(Style::default().add_modifier(Modifier::DIM))
+        .render(chunks[1], buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    #[test]
+    fn only_creatures_in_sight_are_listed() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 10, 6));
+        level.world.carve_room(&Room::new(Point::new(30, 5), 10, 6));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(7, 7);
+
+        let goblin = game.create_npc("goblin".into(), Point::new(9, 7)).unwrap();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+        let rat = game.create_npc("giant_rat".into(), Point::new(33, 7)).unwrap();
+        game.current_level_mut().spawn_npc(rat).unwrap();
+        game.compute_fov();
+
+        let legend = MapLegend::of_visible_map(&game, &Theme::default());
+        let creatures: Vec<&str> =
+            legend.creatures.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(creatures, ["You", "Goblin"]);
+
+        let wall = legend.terrain.iter().find(|entry| entry.name == "Wall").unwrap();
+        assert!(wall.glyphs.len() > 1);
+        assert!(legend.terrain.iter().any(|entry| entry.name == "Floor"));
+    }
+}
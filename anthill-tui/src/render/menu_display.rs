@@ -0,0 +1,373 @@
+#![allow(dead_code)]
+
+use std::{cell::Cell, fmt};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Wrap},
+};
+
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    core::{game::GameState, game_items::GameItemKindDef},
+    data::item_defs::GameItemDef,
+    render::theme::Theme,
+};
+
+/// Different display modes for the menu
+#[derive(Debug, Clone, Copy)]
+pub enum MenuMode {
+    /// Displaying the game log
+    ///
+    /// In this mode, the menu cannot be focused and there are no interactions with the log.
+    Log,
+
+    /// Displaying the player character's inventory.
+    ///
+    /// The inventory can be opened in different modes ([InventoryAction]), which are passed as an argument.
+    Inventory(InventoryAction),
+}
+
+/// Different modes to use the inventory (Use or Drop)
+#[derive(Debug, Clone, Copy)]
+pub enum InventoryAction {
+    /// The inventory is open with the intention of using an item.
+    Use,
+
+    /// The inventory is open with the intention of dropping an item.
+    Drop,
+}
+
+/// Tabs of the inventory window. Each tab lists one kind of items. Switched with TAB.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter)]
+pub enum InventoryTab {
+    #[default]
+    All,
+    Weapons,
+    Armor,
+    /// Food and potions.
+    Consumables,
+}
+
+impl InventoryTab {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InventoryTab::All => "All",
+            InventoryTab::Weapons => "Weapons",
+            InventoryTab::Armor => "Armor",
+            InventoryTab::Consumables => "Consumables",
+        }
+    }
+
+    /// Whether items of the given kind are listed on the tab.
+    pub fn includes(&self, kind: &GameItemKindDef) -> bool {
+        match self {
+            InventoryTab::All => true,
+            InventoryTab::Weapons => matches!(kind, GameItemKindDef::Weapon { .. }),
+            InventoryTab::Armor => matches!(kind, GameItemKindDef::Armor { .. }),
+            InventoryTab::Consumables => {
+                matches!(kind, GameItemKindDef::Food { .. } | GameItemKindDef::Potion { .. })
+            }
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let tabs: Vec<InventoryTab> = InventoryTab::iter().collect();
+        let index = tabs.iter().position(|tab| tab == self).unwrap_or(0);
+        tabs[(index + 1) % tabs.len()]
+    }
+
+    pub fn previous(&self) -> Self {
+        let tabs: Vec<InventoryTab> = InventoryTab::iter().collect();
+        let index = tabs.iter().position(|tab| tab == self).unwrap_or(0);
+        tabs[(index + tabs.len() - 1) % tabs.len()]
+    }
+}
+
+impl fmt::Display for MenuMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MenuMode::Log => write!(f, "Log"),
+            MenuMode::Inventory(InventoryAction::Use) => write!(f, "Inventory (use)"),
+            MenuMode::Inventory(InventoryAction::Drop) => write!(f, "Inventory (drop)"),
+        }
+    }
+}
+
+/// Menu struct containing the state of the menu in the app.
+pub struct Menu {
+    pub mode: MenuMode,
+
+    /// Tab the inventory window is on. Kept when the inventory is closed.
+    pub inventory_tab: InventoryTab,
+
+    /// Page the inventory window is on, once the player can carry more items than there are letters.
+    pub inventory_page: usize,
+
+    /// Number of rows the log had when it was last drawn. Decides when the messages of a turn need a `--More--` prompt.
+    pub log_height: Cell<usize>,
+
+    /// First message of the page the log shows with a `--More--` prompt, while the player reads through a turn with many messages.
+    pub more_prompt: Option<usize>,
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Self {
+            mode: MenuMode::Log,
+            inventory_tab: InventoryTab::default(),
+            inventory_page: 0,
+            log_height: Cell::new(0),
+            more_prompt: None,
+        }
+    }
+
+    /// Opens the `--More--` prompt if more messages were added since the message at `first_new` than the log can show at once.
+    pub fn check_more_prompt(&mut self, first_new: usize, message_count: usize) {
+        let height = self.log_height.get();
+        if matches!(self.mode, MenuMode::Log)
+            && height > 0
+            && message_count.saturating_sub(first_new) > height
+        {
+            self.more_prompt = Some(first_new);
+        }
+    }
+
+    /// Shows the next page of the `--More--` prompt, or closes it when the remaining messages fit into the log.
+    pub fn advance_more_prompt(&mut self, message_count: usize) {
+        let Some(start) = self.more_prompt else {
+            return;
+        };
+
+        let next_start = start + self.more_page_size();
+        self.more_prompt = if message_count.saturating_sub(next_start) > self.log_height.get() {
+            Some(next_start)
+        } else {
+            None
+        };
+    }
+
+    /// Messages shown on a page of the `--More--` prompt. The last row of the log is taken by the prompt itself.
+    fn more_page_size(&self) -> usize {
+        self.log_height.get().saturating_sub(1).max(1)
+    }
+
+    /// Renders the menu. Switches between log display and inventory display depending on state.
+    pub fn render(&self, game_state: &GameState, theme: &Theme, rect: Rect, buf: &mut Buffer) {
+        match self.mode {
+            MenuMode::Log => self.render_log(game_state, theme, rect, buf),
+            MenuMode::Inventory(_) => self.render_inventory(game_state, theme, rect, buf),
+        }
+    }
+
+    /// Renders the menu in log mode.
+    pub fn render_log(&self, game_state: &GameState, theme: &Theme, rect: Rect, buf: &mut Buffer) {
+        let height = rect.height as usize;
+        let width = rect.width as usize;
+        self.log_height.set(height);
+
+        let messages = game_state.log.get_messages_for_display();
+
+        // A turn with more messages than fit is shown page by page
+        if let Some(start) = self.more_prompt {
+            let end = (start + self.more_page_size()).min(messages.len());
+            let lines: Vec<Line> = messages[start.min(end)..end]
+                .iter()
+                .map(|msg| theme.log_line(msg.line()))
+                .collect();
+            let page_rect = Rect { height: rect.height.saturating_sub(1), ..rect };
+            Paragraph::new(Text::from(lines)).wrap(Wrap { trim: true }).render(page_rect, buf);
+            buf.set_line(
+                rect.x,
+                rect.bottom().saturating_sub(1),
+                &Line::styled("--More--", theme.focus),
+                rect.width,
+            );
+            return;
+        }
+        let start = messages.len().saturating_sub(height);
+
+        // Fetch only as many lines as can be displayed (rough estimation, not accurate if lines wrap)
+        let lines: Vec<Line> =
+            messages[start..].iter().map(|msg| theme.log_line(msg.line())).collect();
+
+        // Use a heuristic to count how many lines the texts actually take up.
+        let mut used_height = 0;
+        let mut first_line = 0;
+        for (i, line) in lines.iter().enumerate().rev() {
+            let line_length: usize =
+                line.spans.iter().map(|span| span.content.to_string().len()).sum();
+            let estimated_nr_of_lines: usize = line_length.div_ceil(width).max(1);
+
+            if used_height + estimated_nr_of_lines >= height {
+                first_line = i;
+                break;
+            }
+
+            used_height += estimated_nr_of_lines;
+            first_line = i;
+        }
+
+        let lines_to_display: &[Line] = &lines[first_line..];
+
+        let paragraph =
+            Paragraph::new(Text::from(lines_to_display.to_vec())).wrap(Wrap { trim: true });
+        paragraph.render(rect, buf);
+    }
+
+    /// Renders the menu in inventory mode.
+    pub fn render_inventory(
+        &self,
+        game_state: &GameState,
+        theme: &Theme,
+        rect: Rect,
+        buf: &mut Buffer,
+    ) {
+        let inventory = &game_state.player.character.inventory;
+        let page = self.inventory_page.min(inventory.page_count() - 1);
+
+        // Tabs in the top line, the open one highlighted
+        let mut tab_spans = Vec::new();
+        for tab in InventoryTab::iter() {
+            let style = if tab == self.inventory_tab { theme.focus } else { theme.hint };
+            tab_spans.push(Span::styled(format!(" {} ", tab.name()), style));
+        }
+        buf.set_line(rect.x, rect.y, &Line::from(tab_spans), rect.width);
+
+        // Burden in the top right corner, highlighted when it slows the player down
+        let burden = format!("Burden {}/{}", game_state.burden(), game_state.carry_capacity());
+        let burden_style = if game_state.is_slowed() { theme.danger } else { Style::default() };
+        Paragraph::new(Span::styled(burden, burden_style))
+            .alignment(Alignment::Right)
+            .render(Rect { height: 1, ..rect }, buf);
+
+        let height = rect.height as usize;
+        let item_height = height.saturating_sub(2); // reserve top line for tabs and bottom line for footer
+
+        let lines: Vec<Line> = inventory
+            .entries(page)
+            .flat_map(|(list_letter, item_id)| {
+                let instance = game_state.get_item_by_id(item_id);
+                let def = instance
+                    .as_ref()
+                    .and_then(|instance| game_state.get_item_def_by_id(&instance.def_id));
+                let Some(def) = def else {
+                    return vec![Line::raw(format!("{list_letter} - <Invalid Item>"))];
+                };
+                if !self.inventory_tab.includes(&def.kind) {
+                    return Vec::new();
+                }
+
+                let mut styled = format_item_inventory(&def, theme);
+
+                styled.spans.insert(0, Span::raw(format!("{list_letter} - ")));
+
+                // The contents of bags are listed below them
+                let contents = instance.map(|instance| instance.contents).unwrap_or_default();
+                let nested = contents.into_iter().filter_map(|content_id| {
+                    let content = game_state.get_item_by_id(content_id)?;
+                    let mut line = format_item_inventory(
+                        &game_state.get_item_def_by_id(&content.def_id)?,
+                        theme,
+                    );
+                    let branch = if theme.ascii { "    `-" } else { "    └ " };
+                    line.spans.insert(0, Span::styled(branch, theme.hint));
+                    Some(line)
+                });
+
+                std::iter::once(styled).chain(nested).collect()
+            })
+            .collect();
+        let start = lines.len().saturating_sub(item_height);
+
+        // Render the inventory list
+        let list_rect = Rect {
+            x: rect.x,
+            y: rect.y + 1,
+            width: rect.width,
+            height: rect.height.saturating_sub(2),
+        };
+
+        Paragraph::new(Text::from(lines[start..].to_vec()))
+            .wrap(Wrap { trim: true })
+            .render(list_rect, buf);
+
+        // Render footer
+        let footer_y = rect.y + rect.height - 1;
+
+        let footer = if inventory.page_count() > 1 {
+            format!(
+                "Page {}/{}, PGUP/PGDN: switch page, TAB: switch tab, ESC: close the inventory",
+                page + 1,
+                inventory.page_count()
+            )
+        } else {
+            "TAB: switch tab, ESC: close the inventory".to_string()
+        };
+        buf.set_span(rect.x, footer_y, &Span::styled(footer, theme.hint), rect.width);
+    }
+}
+
+/// Formats an item's definition for display in the UI.
+pub fn format_item_inventory(def: &GameItemDef, theme: &Theme) -> Line<'static> {
+    let mut spans = vec![
+        Span::raw("["),
+        Span::styled(def.glyph.to_string(), theme.style(def.style)),
+        Span::raw("] "),
+        Span::raw(def.name),
+    ];
+
+    match &def.kind {
+        GameItemKindDef::Armor { mitigation, .. } => {
+            spans.push(Span::raw(" <"));
+            spans.push(Span::raw(format!("{} MIT", mitigation)));
+            spans.push(Span::raw(">"));
+        }
+        GameItemKindDef::Weapon { damage, crit_chance, .. } => {
+            spans.push(Span::raw(" <"));
+            spans.push(Span::raw(format!("{} DMG", damage)));
+            spans.push(Span::raw(", "));
+            spans.push(Span::raw(format!("{:.0}% CRIT", crit_chance)));
+            spans.push(Span::raw(">"));
+        }
+        GameItemKindDef::Food { nutrition } => {
+            spans.push(Span::raw(" <"));
+            spans.push(Span::raw(format!("{} NUT", nutrition)));
+            spans.push(Span::raw(">"));
+        }
+        GameItemKindDef::Potion { .. } => {}
+        GameItemKindDef::FireStarter => {}
+        GameItemKindDef::JokeBook => {}
+        GameItemKindDef::RemoveCurse => {}
+        GameItemKindDef::Trinket => {}
+        GameItemKindDef::Bag { capacity } => {
+            spans.push(Span::raw(" <"));
+            spans.push(Span::raw(format!("{} SLOTS", capacity)));
+            spans.push(Span::raw(">"));
+        }
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn more_prompt_pages_through_a_long_turn() {
+        let mut menu = Menu::new();
+        menu.log_height.set(5);
+
+        menu.check_more_prompt(10, 14);
+        assert_eq!(menu.more_prompt, None);
+
+        menu.check_more_prompt(10, 22);
+        assert_eq!(menu.more_prompt, Some(10));
+        menu.advance_more_prompt(22);
+        assert_eq!(menu.more_prompt, Some(14));
+        menu.advance_more_prompt(22);
+        assert_eq!(menu.more_prompt, None);
+    }
+}
@@ -0,0 +1,615 @@
+#![allow(dead_code)]
+
+use ratatui::{
+    prelude::*,
+    symbols::border,
+    widgets::{Cell, Clear, Paragraph, Row, Table, Wrap},
+};
+
+use crate::{
+    core::{
+        appearance::PlayerAppearance,
+        containers::Container,
+        entity_logic::EntityId,
+        game::GameState,
+        game_items::GameItemId,
+        high_scores::{HighScoreSort, HighScoreTable},
+        npc_interaction::NpcInteraction,
+        player_class::PlayerClass,
+        town::STASH_LIMIT,
+    },
+    data::item_defs::item_defs,
+    render::{
+        examine_display::render_examine,
+        help_display::{HelpPage, render_help},
+        legend_display::render_legend,
+        theme::{Theme, terminal_style},
+        ui::get_centered_rect,
+    },
+    util::command_handler::command_error,
+    world::coordinate_system::Point,
+};
+
+pub enum ModalInterface {
+    ConfirmQuit,
+    ConfirmUseItem {
+        item_id: GameItemId,
+    },
+    ConfirmDropItem {
+        item_id: GameItemId,
+    },
+    /// Asks how many of the selected item to drop, when the inventory holds more than one of its kind.
+    DropQuantity {
+        item_id: GameItemId,
+        max: usize,
+        buffer: String,
+    },
+    /// Asked before the player takes the stairs down (or up) while enemies stand next to them.
+    ConfirmUseStairs {
+        down: bool,
+    },
+    CommandInput {
+        buffer: String,
+        /// Position in the command history while the player recalls earlier commands with the arrow keys.
+        history_index: Option<usize>,
+    },
+    NameInput {
+        buffer: String,
+    },
+    /// Name of the character of a new run, asked before the character creation.
+    CharacterName {
+        buffer: String,
+    },
+    CharacterCreation {
+        appearance: PlayerAppearance,
+        class: PlayerClass,
+    },
+    HighScores {
+        table: HighScoreTable,
+        sort: HighScoreSort,
+    },
+    TextDisplay {
+        title: String,
+        paragraphs: Vec<String>,
+    },
+    HelpDisplay {
+        page: HelpPage,
+    },
+    LegendDisplay,
+    /// Details of everything at a point of the world, opened from look mode.
+    ExamineDisplay {
+        point: Point,
+    },
+    SelectPrompt {
+        selection_action: SelectionAction,
+        options: Vec<String>,
+    },
+    DirectionPrompt {
+        direction_action: DirectionAction,
+    },
+}
+
+impl ModalInterface {
+    /// Central handling for rendering modals.
+    ///
+    /// Switches to the [ModalInterface] kind that is open at the time.
+    pub fn render(&self, rect: Rect, buf: &mut Buffer, game: &GameState, theme: &Theme) {
+        match self {
+            ModalInterface::ConfirmQuit => render_confirm_quit(rect, buf, theme),
+            ModalInterface::ConfirmUseItem { item_id } => {
+                render_confirm_use_item(rect, buf, game, *item_id, theme)
+            }
+            ModalInterface::ConfirmDropItem { item_id } => {
+                render_confirm_drop_item(rect, buf, game, *item_id, theme);
+            }
+            ModalInterface::DropQuantity { item_id, max, buffer } => {
+                render_drop_quantity(rect, buf, game, *item_id, *max, buffer, theme);
+            }
+            ModalInterface::ConfirmUseStairs { down } => {
+                render_confirm_use_stairs(*down, rect, buf, theme)
+            }
+            ModalInterface::CommandInput { buffer, .. } => {
+                render_command_input(buffer, rect, buf, theme)
+            }
+            ModalInterface::NameInput { buffer } => render_name_input(
+                " Enter Your Name ",
+                buffer,
+                "ENTER - add the run to the high scores",
+                "ESC - skip",
+                rect,
+                buf,
+                theme,
+            ),
+            ModalInterface::CharacterName { buffer } => render_name_input(
+                " Name Your Character ",
+                buffer,
+                "ENTER - continue to the character's looks",
+                "ESC - back to the menu",
+                rect,
+                buf,
+                theme,
+            ),
+            ModalInterface::CharacterCreation { appearance, class } => {
+                render_character_creation(appearance, *class, rect, buf, theme)
+            }
+            ModalInterface::HighScores { table, sort } => {
+                render_high_scores(table, *sort, rect, buf, theme)
+            }
+            ModalInterface::TextDisplay { title, paragraphs } => {
+                render_text_display(title, paragraphs, rect, buf, theme)
+            }
+            ModalInterface::HelpDisplay { page } => render_help(*page, rect, buf, game, theme),
+            ModalInterface::LegendDisplay => render_legend(rect, buf, game, theme),
+            ModalInterface::ExamineDisplay { point } => {
+                render_examine(*point, rect, buf, game, theme)
+            }
+            ModalInterface::SelectPrompt { selection_action, options } => {
+                render_select_prompt(rect, buf, game, selection_action, options, theme)
+            }
+            ModalInterface::DirectionPrompt { direction_action } => {
+                render_direction_prompt(rect, buf, direction_action, theme)
+            }
+        }
+    }
+}
+
+/// Displays a large modal window that can contain multiple text paragraphs.
+pub fn render_text_display(
+    title: &str,
+    paragraphs: &[String],
+    rect: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    // Making the Window
+    let modal_area = render_modal_window(150, 33, title.to_string(), rect, buf, theme);
+
+    let page_text = Text::from(
+        paragraphs.iter().map(|paragraph| Line::from(paragraph.as_str())).collect::<Vec<Line>>(),
+    );
+
+    Paragraph::new(page_text).render(modal_area, buf);
+}
+
+/// Displays the dialog where the user has to confirm that they want to quit the game.
+fn render_confirm_quit(rect: Rect, buf: &mut Buffer, theme: &Theme) {
+    // Making the Window
+    let modal_area = render_modal_window(50, 5, " Confirm Quit ".to_string(), rect, buf, theme);
+
+    // Filling the Window
+    let text = Text::from(vec![
+        Line::from("Do you really want to quit?"),
+        Line::from(""),
+        Line::from("Press <q> again"),
+    ]);
+
+    let center_of_rect = get_centered_rect(50, 3, modal_area);
+
+    let paragraph = Paragraph::new(text).alignment(Alignment::Center);
+    paragraph.render(center_of_rect, buf);
+}
+
+/// Displays the dialog where the user has to confirm the item that they selected (e.g. for using or dropping)
+fn render_confirm_use_item(
+    rect: Rect,
+    buf: &mut Buffer,
+    game: &GameState,
+    item_id: GameItemId,
+    theme: &Theme,
+) {
+    let modal_area = render_modal_window(50, 5, " Confirm Action ".to_string(), rect, buf, theme);
+
+    // look up item name
+    let instance = &game.items[&item_id];
+    let item_name =
+        game.get_item_def_by_id(&instance.def_id).map(|def| def.name).unwrap_or("<unknown item>");
+
+    let text = Text::from(vec![
+        Line::from(format!("Selected: {}", item_name)),
+        Line::from(""),
+        Line::from("Press <y> to use, <n> to cancel"),
+    ]);
+
+    let center_of_rect = get_centered_rect(50, 3, modal_area);
+
+    Paragraph::new(text).alignment(Alignment::Center).render(center_of_rect, buf);
+}
+
+/// Displays the dialog where the user has to confirm the item that they selected (e.g. for using or dropping)
+fn render_confirm_drop_item(
+    rect: Rect,
+    buf: &mut Buffer,
+    game: &GameState,
+    item_id: GameItemId,
+    theme: &Theme,
+) {
+    let modal_area = render_modal_window(50, 5, " Confirm Action ".to_string(), rect, buf, theme);
+
+    // look up item name
+    let instance = &game.items[&item_id];
+    let item_name =
+        game.get_item_def_by_id(&instance.def_id).map(|def| def.name).unwrap_or("<unknown item>");
+
+    let text = Text::from(vec![
+        Line::from(format!("Selected: {}", item_name)),
+        Line::from(""),
+        Line::from("Press <y> to drop, <n> to cancel"),
+    ]);
+
+    let center_of_rect = get_centered_rect(50, 3, modal_area);
+
+    Paragraph::new(text).alignment(Alignment::Center).render(center_of_rect, buf);
+}
+
+/// Displays the prompt asking how many items of the selected kind to drop.
+fn render_drop_quantity(
+    rect: Rect,
+    buf: &mut Buffer,
+    game: &GameState,
+    item_id: GameItemId,
+    max: usize,
+    buffer: &str,
+    theme: &Theme,
+) {
+    let modal_area = render_modal_window(50, 7, " Drop How Many? ".to_string(), rect, buf, theme);
+
+    // look up item name
+    let instance = &game.items[&item_id];
+    let item_name =
+        game.get_item_def_by_id(&instance.def_id).map(|def| def.name).unwrap_or("<unknown item>");
+
+    let input_area = Rect {
+        x: modal_area.x + (modal_area.width.saturating_sub(30_u16)) / 2,
+        y: modal_area.y,
+        width: 30,
+        height: 3,
+    };
+    let input_block = theme.block();
+    let input_block_inner = input_block.inner(input_area);
+    input_block.render(input_area, buf);
+
+    Paragraph::new(Text::from(buffer)).render(input_block_inner, buf);
+
+    let hint_area = Rect { y: input_area.y + 3, height: 2, ..modal_area };
+    Paragraph::new(Text::from(vec![
+        Line::from(format!("You carry {} x {}", max, item_name)),
+        Line::from("ENTER - drop (all if empty), ESC - cancel"),
+    ]))
+    .alignment(Alignment::Center)
+    .render(hint_area, buf);
+}
+
+/// Displays the dialog where the user has to confirm taking the stairs while enemies are next to them.
+fn render_confirm_use_stairs(down: bool, rect: Rect, buf: &mut Buffer, theme: &Theme) {
+    let modal_area = render_modal_window(50, 5, " Confirm Action ".to_string(), rect, buf, theme);
+
+    let text = Text::from(vec![
+        Line::from("There are enemies right next to you!"),
+        Line::from(""),
+        Line::from(format!(
+            "Press <y> to go {} anyway, <n> to cancel",
+            if down { "down" } else { "up" }
+        )),
+    ]);
+
+    let center_of_rect = get_centered_rect(50, 3, modal_area);
+
+    Paragraph::new(text).alignment(Alignment::Center).render(center_of_rect, buf);
+}
+
+/// Displays the dialog into which you can enter game commands to execute.
+/// Input that would not run is highlighted, with the reason below it.
+fn render_command_input(buffer: &str, rect: Rect, buf: &mut Buffer, theme: &Theme) {
+    // Making the Window
+    let modal_area =
+        render_modal_window(50, 6, " Execute a Command ".to_string(), rect, buf, theme);
+
+    // Filling the window
+    let input_area = Rect {
+        x: modal_area.x + (modal_area.width.saturating_sub(30_u16)) / 2,
+        y: modal_area.y,
+        width: 30,
+        height: 3,
+    };
+    let input_block = theme.block();
+    let input_block_inner = input_block.inner(input_area);
+    input_block.render(input_area, buf);
+
+    let error = command_error(buffer);
+    let input_style = if error.is_some() { theme.danger } else { Style::default() };
+    Paragraph::new(Span::styled(buffer, input_style)).render(input_block_inner, buf);
+
+    let hint_area = Rect { y: input_area.y + 3, height: 2, ..modal_area };
+    let hint = match error {
+        Some(error) => Line::from(Span::styled(error, theme.danger)),
+        None => Line::from(Span::styled("TAB - complete, UP/DOWN - history", theme.hint)),
+    };
+    Paragraph::new(hint).alignment(Alignment::Center).render(hint_area, buf);
+}
+
+/// Displays the prompt where the player enters their name for the high score table after dying.
+fn render_name_input(
+    title: &str,
+    buffer: &str,
+    confirm_hint: &str,
+    cancel_hint: &str,
+    rect: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    // Making the Window
+    let modal_area = render_modal_window(50, 7, title.to_string(), rect, buf, theme);
+
+    // Filling the window
+    let input_area = Rect {
+        x: modal_area.x + (modal_area.width.saturating_sub(30_u16)) / 2,
+        y: modal_area.y,
+        width: 30,
+        height: 3,
+    };
+    let input_block = theme.block();
+    let input_block_inner = input_block.inner(input_area);
+    input_block.render(input_area, buf);
+
+    Paragraph::new(Text::from(buffer)).render(input_block_inner, buf);
+
+    let hint_area = Rect { y: input_area.y + 3, height: 2, ..modal_area };
+    Paragraph::new(Text::from(vec![Line::from(confirm_hint), Line::from(cancel_hint)]))
+        .alignment(Alignment::Center)
+        .render(hint_area, buf);
+}
+
+/// Lets the player pick the glyph, color and class of their character before the run starts.
+fn render_character_creation(
+    appearance: &PlayerAppearance,
+    class: PlayerClass,
+    rect: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    // Making the Window
+    let modal_area =
+        render_modal_window(60, 14, " Create Your Character ".to_string(), rect, buf, theme);
+
+    let mut kit = vec![class.starting_weapon()];
+    kit.extend(class.starting_armor());
+    kit.extend(class.starting_items());
+    let kit: Vec<&str> = kit
+        .into_iter()
+        .map(|def_id| item_defs().get(def_id).map_or(def_id, |def| def.name))
+        .collect();
+
+    // Filling the window
+    Paragraph::new(Text::from(vec![
+        Line::from(Span::styled(appearance.glyph.to_string(), terminal_style(appearance.style()))),
+        Line::from(""),
+        Line::from(format!("Glyph: {}   Color: {}", appearance.glyph, appearance.color.name())),
+        Line::from(Span::styled(
+            format!("Class: {}", class.name()),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(class.perk()),
+        Line::from(format!("Starts with: {}", kit.join(", "))),
+        Line::from(""),
+        Line::from("A/D - change glyph, W/S - change color"),
+        Line::from("TAB - change class"),
+        Line::from("ENTER - start the run, ESC - back"),
+    ]))
+    .wrap(Wrap { trim: true })
+    .alignment(Alignment::Center)
+    .render(modal_area, buf);
+}
+
+/// Displays the table of the best runs, sorted by the selected column.
+fn render_high_scores(
+    table: &HighScoreTable,
+    sort: HighScoreSort,
+    rect: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    // Making the Window
+    let modal_area = render_modal_window(120, 28, " High Scores ".to_string(), rect, buf, theme);
+
+    let [table_area, hint_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(modal_area);
+
+    let header = Row::new(vec!["#", "", "Name", "Score", "Depth", "Turns", "Fate", "Date"])
+        .style(Style::new().add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = table
+        .sorted(sort)
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            Row::new(vec![
+                Cell::from((index + 1).to_string()),
+                Cell::from(Span::styled(
+                    entry.appearance.glyph.to_string(),
+                    terminal_style(entry.appearance.style()),
+                )),
+                Cell::from(entry.name.clone()),
+                Cell::from(entry.score.to_string()),
+                Cell::from(entry.depth.to_string()),
+                Cell::from(entry.turns.to_string()),
+                Cell::from(entry.cause.clone()),
+                Cell::from(entry.date.clone()),
+            ])
+        })
+        .collect();
+
+    const HIGH_SCORE_WIDTHS: [Constraint; 8] = [
+        Constraint::Length(3),
+        Constraint::Length(1),
+        Constraint::Length(17),
+        Constraint::Length(7),
+        Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Min(0),
+        Constraint::Length(17),
+    ];
+
+    if rows.is_empty() {
+        Paragraph::new("No run has been finished yet.")
+            .alignment(Alignment::Center)
+            .render(table_area, buf);
+    } else {
+        Widget::render(Table::new(rows, HIGH_SCORE_WIDTHS).header(header), table_area, buf);
+    }
+
+    Paragraph::new(format!(
+        "Sorted by {}. s - sort by {}, ESC - close",
+        sort.name(),
+        sort.next().name()
+    ))
+    .alignment(Alignment::Center)
+    .render(hint_area, buf);
+}
+
+/// Helper function that does the setup for a modal window.
+///
+/// It creates a rect that is centered and has its background cleared (so it is "above" the background).
+fn render_modal_window(
+    width: u16,
+    height: u16,
+    title: String,
+    rect: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) -> Rect {
+    let area_modal = get_centered_rect(width, height, rect);
+
+    Clear.render(area_modal, buf);
+
+    let block_modal = theme
+        .block()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .border_set(theme.border_set(border::DOUBLE));
+
+    let block_modal_inner = block_modal.inner(area_modal);
+
+    block_modal.render(area_modal, buf);
+
+    block_modal_inner
+}
+
+/// Displays the help display, which explains the basics of controls in the game.
+pub enum SelectionAction {
+    Debug,
+    /// Cook the selected item. Holds the ids of the items in the same order as the options.
+    Cook {
+        item_ids: Vec<GameItemId>,
+    },
+    /// Pick up one of the items the player stands on. Holds the ids of their sprites in the same order as the options.
+    PickUp {
+        sprite_ids: Vec<EntityId>,
+    },
+    /// Interact with an NPC. Holds the interactions in the same order as the options.
+    InteractNpc {
+        npc_id: EntityId,
+        interactions: Vec<NpcInteraction>,
+    },
+    /// Buy from a shop. Holds the ids of the items for sale in the same order as the options, followed by the option to haggle.
+    Trade {
+        npc_id: EntityId,
+        item_ids: Vec<GameItemId>,
+    },
+    /// Use the stash. Holds the ids of the inventory items that can be stored in the same order as the options, followed by the stored items.
+    Stash {
+        item_ids: Vec<GameItemId>,
+    },
+    /// Pick one of the perks offered on a level-up milestone, in the same order as the options.
+    ChoosePerk,
+    /// Use a chest or bag. The options start with taking all items if there are any, followed by the items in the container, then the inventory items that can be put in.
+    Container {
+        container: Container,
+        contents: Vec<GameItemId>,
+        storable: Vec<GameItemId>,
+    },
+}
+
+/// Renders a prompt that allows the user to select from a collection of items.
+fn render_select_prompt(
+    rect: Rect,
+    buf: &mut Buffer,
+    game: &GameState,
+    selection_action: &SelectionAction,
+    options: &[String],
+    theme: &Theme,
+) {
+    let instruction = match selection_action {
+        SelectionAction::Debug => "Choose a message to be displayed".to_string(),
+        SelectionAction::Cook { .. } => "Choose something to cook over the fire".to_string(),
+        SelectionAction::PickUp { .. } => "Choose something to pick up".to_string(),
+        SelectionAction::InteractNpc { .. } => "Choose how to interact".to_string(),
+        SelectionAction::Trade { .. } => {
+            format!("You have {} gold. Choose something to buy", game.player.character.gold)
+        }
+        SelectionAction::Stash { .. } => format!(
+            "The stash holds {} of {} items. Choose something to store or take",
+            game.stash.items.len(),
+            STASH_LIMIT
+        ),
+        SelectionAction::ChoosePerk => "You reached a milestone. Choose a perk".to_string(),
+        SelectionAction::Container { container, .. } => format!(
+            "The {} holds {} of {} items. Choose something to take or put in",
+            container.name(),
+            game.container_contents(*container).map_or(0, |contents| contents.len()),
+            game.container_capacity(*container)
+        ),
+    };
+
+    // Wide enough for the instruction and the longest option (including its "a - " prefix)
+    let longest_option = options.iter().map(|option| option.chars().count() + 4).max().unwrap_or(0);
+    let modal_area_width = instruction.len().max(longest_option) as u16 + 4;
+    let modal_area_height = options.len() as u16 + 5;
+    let modal_area = render_modal_window(
+        modal_area_width,
+        modal_area_height,
+        "Select".to_string(),
+        rect,
+        buf,
+        theme,
+    );
+    let center_of_rect = get_centered_rect(modal_area_width, modal_area_height, modal_area);
+
+    let mut lines: Vec<Line> = vec![Line::raw(instruction), Line::raw("")];
+    for (i, option) in options.iter().enumerate() {
+        let list_letter = (b'a' + i as u8) as char;
+        lines.push(Line::raw(format!("{} - {}", list_letter, option)));
+    }
+
+    let paragraph =
+        Paragraph::new(Text::from(lines)).alignment(Alignment::Center).wrap(Wrap { trim: true });
+    paragraph.render(center_of_rect, buf);
+}
+
+pub enum DirectionAction {
+    Interact,
+}
+
+/// Renders a prompt that asks the user for a direction.
+fn render_direction_prompt(
+    rect: Rect,
+    buf: &mut Buffer,
+    direction_action: &DirectionAction,
+    theme: &Theme,
+) {
+    let instruction = match direction_action {
+        DirectionAction::Interact => "Interact in which direction?",
+    };
+
+    let modal_area = render_modal_window(50, 5, " Direction ".to_string(), rect, buf, theme);
+
+    let text = Text::from(vec![
+        Line::from(instruction),
+        Line::from(""),
+        Line::from("Press <w/a/s/d> or <ESC> to cancel"),
+    ]);
+
+    let center_of_rect = get_centered_rect(50, 3, modal_area);
+
+    let paragraph = Paragraph::new(text).alignment(Alignment::Center);
+    paragraph.render(center_of_rect, buf);
+}
@@ -0,0 +1,184 @@
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use ratatui::prelude::*;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    core::game::GameState,
+    render::{
+        theme::Theme,
+        world_display::{Viewport, WorldDisplay},
+    },
+    world::worldspace::{WORLD_HEIGHT, WORLD_WIDTH},
+};
+
+/// Number of turns the recorder keeps in memory. Older frames are dropped.
+pub const RECORDER_CAPACITY: usize = 200;
+
+/// Time (in seconds) each recorded turn is shown when the cast is played back.
+const CAST_FRAME_DURATION: f64 = 0.25;
+
+/// Records the world panel once per turn into a ring of frames, so that the last turns can be exported as an
+/// [asciinema](https://docs.asciinema.org/manual/asciicast/v2/) cast with the `cast` command.
+pub struct WorldRecorder {
+    frames: VecDeque<Buffer>,
+    /// Round number of the last captured frame. Used to capture only once per turn.
+    last_round: Option<u64>,
+}
+
+impl WorldRecorder {
+    pub fn new() -> Self {
+        Self { frames: VecDeque::with_capacity(RECORDER_CAPACITY), last_round: None }
+    }
+
+    /// Number of frames currently held by the recorder.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Renders the world panel into an off-screen buffer and stores it as a new frame.
+    ///
+    /// Does nothing if the current round has already been captured.
+    pub fn capture(&mut self, world_display: &WorldDisplay, theme: &Theme, game: &GameState) {
+        if self.last_round == Some(game.round_nr) {
+            return;
+        }
+
+        let cell_width = world_display.cell_width();
+        let rect = Rect::new(0, 0, WORLD_WIDTH as u16 * cell_width, WORLD_HEIGHT as u16);
+        let mut buf = Buffer::empty(rect);
+        let viewport = Viewport::new(game, rect, cell_width);
+        world_display.render_world_layers(game, &viewport, theme, &mut buf);
+
+        if self.frames.len() >= RECORDER_CAPACITY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(buf);
+        self.last_round = Some(game.round_nr);
+    }
+
+    /// Writes all recorded frames into a new asciicast (v2) file in the game's data directory.
+    ///
+    /// # Returns
+    /// * The path of the written file.
+    ///
+    /// # Errors
+    /// * [io::Error] if the file could not be created or written.
+    pub fn export_cast(&self) -> io::Result<PathBuf> {
+        let mut path = dirs::data_local_dir()
+            .ok_or(io::Error::new(io::ErrorKind::NotFound, "No data directory found on this OS"))?;
+        path.push("Anthill");
+        path.push("casts");
+        fs::create_dir_all(&path)?;
+
+        let filename =
+            format!("anthill_cast_{}.cast", chrono::Local::now().format("%Y-%m-%d-%H-%M-%S"));
+        path.push(filename);
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writeln!(
+            writer,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"title\": \"Anthill\"}}",
+            self.frames.front().map_or(WORLD_WIDTH as u16, |frame| frame.area.width),
+            WORLD_HEIGHT,
+            chrono::Local::now().timestamp()
+        )?;
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            let time = index as f64 * CAST_FRAME_DURATION;
+            writeln!(writer, "[{:.3}, \"o\", \"{}\"]", time, escape_json(&frame_to_ansi(frame)))?;
+        }
+        writer.flush()?;
+
+        Ok(path)
+    }
+}
+
+/// Converts a buffer into a string of ANSI escape sequences that redraws the whole screen.
+fn frame_to_ansi(buf: &Buffer) -> String {
+    let mut output = String::from("\x1b[2J\x1b[H");
+
+    for y in 0..buf.area.height {
+        let mut current_fg: Option<Color> = None;
+        // Cells covered by a wide glyph to their left
+        let mut covered = 0;
+        for x in 0..buf.area.width {
+            let Some(cell) = buf.cell(Position::new(x, y)) else {
+                continue;
+            };
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            covered = cell.symbol().width().saturating_sub(1);
+
+            if current_fg != Some(cell.fg) {
+                let _ = write!(output, "\x1b[{}m", ansi_fg_code(cell.fg));
+                current_fg = Some(cell.fg);
+            }
+            output.push_str(cell.symbol());
+        }
+        output.push_str("\x1b[0m\r\n");
+    }
+
+    output
+}
+
+/// Returns the SGR parameters that set the given foreground color.
+fn ansi_fg_code(color: Color) -> String {
+    match color {
+        Color::Reset => "39".to_string(),
+        Color::Black => "30".to_string(),
+        Color::Red => "31".to_string(),
+        Color::Green => "32".to_string(),
+        Color::Yellow => "33".to_string(),
+        Color::Blue => "34".to_string(),
+        Color::Magenta => "35".to_string(),
+        Color::Cyan => "36".to_string(),
+        Color::Gray => "37".to_string(),
+        Color::DarkGray => "90".to_string(),
+        Color::LightRed => "91".to_string(),
+        Color::LightGreen => "92".to_string(),
+        Color::LightYellow => "93".to_string(),
+        Color::LightBlue => "94".to_string(),
+        Color::LightMagenta => "95".to_string(),
+        Color::LightCyan => "96".to_string(),
+        Color::White => "97".to_string(),
+        Color::Rgb(r, g, b) => format!("38;2;{};{};{}", r, g, b),
+        Color::Indexed(index) => format!("38;5;{}", index),
+    }
+}
+
+/// Escapes a string so it can be placed inside a JSON string literal.
+fn escape_json(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_escaping_of_control_characters() {
+        assert_eq!(escape_json("\x1b[31m\"a\"\r\n"), "\\u001b[31m\\\"a\\\"\\r\\n");
+    }
+}
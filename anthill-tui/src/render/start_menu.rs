@@ -0,0 +1,89 @@
+use ratatui::{prelude::*, widgets::Paragraph};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{core::game::GameState, render::theme::Theme};
+
+/// Entries of the start menu, in the order they are listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum StartMenuEntry {
+    NewGame,
+    /// Cycles through the difficulties. The picked difficulty is used for the run that is started next.
+    Difficulty,
+    HighScores,
+    Help,
+    Quit,
+}
+
+impl StartMenuEntry {
+    pub fn label(&self, game: &GameState) -> String {
+        match self {
+            StartMenuEntry::NewGame => "New Game".to_string(),
+            StartMenuEntry::Difficulty => format!("Difficulty: {}", game.difficulty.level),
+            StartMenuEntry::HighScores => "High Scores".to_string(),
+            StartMenuEntry::Help => "Help".to_string(),
+            StartMenuEntry::Quit => "Quit".to_string(),
+        }
+    }
+}
+
+/// The menu on the start screen and the entry that is selected in it.
+#[derive(Default)]
+pub struct StartMenu {
+    selected: usize,
+}
+
+impl StartMenu {
+    pub fn selected_entry(&self) -> StartMenuEntry {
+        StartMenuEntry::iter().nth(self.selected).unwrap_or(StartMenuEntry::NewGame)
+    }
+
+    /// Selects the entry below the selected one, starting over at the top.
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % StartMenuEntry::iter().len();
+    }
+
+    /// Selects the entry above the selected one, starting over at the bottom.
+    pub fn select_previous(&mut self) {
+        let count = StartMenuEntry::iter().len();
+        self.selected = (self.selected + count - 1) % count;
+    }
+
+    /// Lists the entries centered in the area, with the selected one highlighted.
+    pub fn render(&self, game: &GameState, theme: &Theme, area: Rect, buf: &mut Buffer) {
+        let selected = self.selected_entry();
+        let mut lines: Vec<Line> = StartMenuEntry::iter()
+            .map(|entry| {
+                if entry == selected {
+                    Line::styled(
+                        format!("> {} <", entry.label(game)),
+                        Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                    )
+                } else {
+                    Line::from(entry.label(game))
+                }
+            })
+            .collect();
+        lines.push(Line::default());
+        lines.push(Line::styled("UP/DOWN - select   ENTER - confirm", theme.hint));
+
+        Paragraph::new(lines).alignment(Alignment::Center).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_wraps_around() {
+        let mut menu = StartMenu::default();
+        assert_eq!(menu.selected_entry(), StartMenuEntry::NewGame);
+
+        menu.select_previous();
+        assert_eq!(menu.selected_entry(), StartMenuEntry::Quit);
+        menu.select_next();
+        menu.select_next();
+        assert_eq!(menu.selected_entry(), StartMenuEntry::Difficulty);
+    }
+}
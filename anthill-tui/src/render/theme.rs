@@ -0,0 +1,416 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::border;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders};
+use ron::de::from_reader;
+use serde::{Deserialize, Serialize};
+
+use anthill_core::render::style::{
+    Color as GameColor, Modifier as GameModifier, Style as GameStyle,
+};
+pub use anthill_core::render::theme::{PackTheme, ThemeId, theme_path};
+
+use crate::{
+    util::{
+        errors_results::{GameError, IoError},
+        text_log::{LogLine, LogTag},
+    },
+    world::tiles::Drawable,
+};
+
+/// Borders drawn with plain ASCII characters.
+pub const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Display settings of the player's config file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    /// Whether the game starts in ASCII mode (see [Theme::ascii]).
+    #[serde(default)]
+    pub ascii: bool,
+    /// Milliseconds between two ticks of the game's main loop, which play animations, rests and travels.
+    /// `None` if the game's default is used.
+    #[serde(default)]
+    pub tick_rate_ms: Option<u64>,
+}
+
+impl DisplayConfig {
+    /// Loads the config from the given file. A missing file means the defaults are used.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path).map_err(IoError::FileReading)?;
+        Ok(from_reader(BufReader::new(file)).map_err(IoError::MapParsing)?)
+    }
+}
+
+/// Path of the player's display config file.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn display_config_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("display_config.ron");
+    Some(path)
+}
+
+/// Colors and styles the UI is drawn with.
+///
+/// The world and the menus keep defining their own styles (e.g. [Drawable::style]).
+/// The theme recolors them with [Theme::style] right before they are drawn. The log is drawn with the styles of the
+/// theme for the [LogTag]s of its messages (see [Theme::log_line]).
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub id: ThemeId,
+    /// Theme of a content pack that changes the colors of the theme of `id` (see [mods](anthill_core::data::mods)).
+    pub pack: Option<PackTheme>,
+    /// Whether tiles, creatures and borders are drawn with ASCII characters only, for terminals or fonts that show the
+    /// box-drawing characters poorly. Toggled with the `ascii` command.
+    pub ascii: bool,
+
+    /// Mentions of the player in the log.
+    pub you: Style,
+    /// Names of NPCs in the log.
+    pub npc: Style,
+    /// Names of items in the log.
+    pub item: Style,
+    /// Damage, gold and other numbers in the log.
+    pub number: Style,
+    /// Critical hits, burns and other dangers in the log.
+    pub danger: Style,
+    /// Lore, inscriptions and what NPCs say.
+    pub lore: Style,
+    /// Damage that was blocked by armor.
+    pub blocked: Style,
+    pub debug_info: Style,
+    pub debug_warn: Style,
+
+    /// Tiles that were explored, but are not in sight.
+    pub remembered: Style,
+    /// Hints like "Press ESC to close".
+    pub hint: Style,
+    /// Border of the focused panel.
+    pub focus: Style,
+}
+
+impl Theme {
+    pub fn new(id: ThemeId) -> Self {
+        Self::build(id, None)
+    }
+
+    pub fn from_pack(pack: &PackTheme) -> Self {
+        Self::build(pack.base, Some(pack.clone()))
+    }
+
+    /// Name of the theme as used by the `theme` command.
+    pub fn name(&self) -> &str {
+        self.pack.as_ref().map_or(self.id.name(), |pack| &pack.name)
+    }
+
+    fn build(id: ThemeId, pack: Option<PackTheme>) -> Self {
+        let mut theme = Self {
+            id,
+            pack,
+            ascii: false,
+            you: Style::new(),
+            npc: Style::new(),
+            item: Style::new(),
+            number: Style::new(),
+            danger: Style::new(),
+            lore: Style::new(),
+            blocked: Style::new(),
+            debug_info: Style::new(),
+            debug_warn: Style::new(),
+            remembered: Style::new(),
+            hint: Style::new(),
+            focus: Style::new(),
+        };
+
+        let [
+            you,
+            npc,
+            item,
+            number,
+            danger,
+            lore,
+            blocked,
+            debug_info,
+            debug_warn,
+            remembered,
+            hint,
+            focus,
+        ] = [
+            GameStyle::new().add_modifier(GameModifier::ITALIC),
+            GameStyle::new().fg(GameColor::Yellow).add_modifier(GameModifier::ITALIC),
+            GameStyle::new().fg(GameColor::Magenta).add_modifier(GameModifier::BOLD),
+            GameStyle::new().fg(GameColor::Cyan),
+            GameStyle::new().fg(GameColor::Red),
+            GameStyle::new().add_modifier(GameModifier::ITALIC),
+            GameStyle::new().fg(GameColor::DarkGray),
+            GameStyle::new().fg(GameColor::DarkGray),
+            GameStyle::new().fg(GameColor::Red),
+            GameStyle::new().fg(GameColor::DarkGray),
+            GameStyle::new().fg(GameColor::DarkGray),
+            GameStyle::new().fg(GameColor::LightBlue),
+        ]
+        .map(|style| theme.style(style));
+        theme = Self {
+            you,
+            npc,
+            item,
+            number,
+            danger,
+            lore,
+            blocked,
+            debug_info,
+            debug_warn,
+            remembered,
+            hint,
+            focus,
+            ..theme
+        };
+        if id == ThemeId::HighContrast {
+            theme.danger = theme.danger.add_modifier(Modifier::BOLD);
+            theme.number = theme.number.add_modifier(Modifier::BOLD);
+        }
+
+        theme
+    }
+
+    /// The glyph of something in the world, or its [Drawable::ascii_glyph] in ASCII mode.
+    pub fn glyph(&self, drawable: &impl Drawable) -> char {
+        if self.ascii { drawable.ascii_glyph() } else { drawable.glyph() }
+    }
+
+    /// The given borders, or [ASCII_BORDER] in ASCII mode.
+    pub fn border_set(&self, set: border::Set) -> border::Set {
+        if self.ascii { ASCII_BORDER } else { set }
+    }
+
+    /// A block with borders all around, as most panels and windows have.
+    pub fn block(&self) -> Block<'static> {
+        Block::default().borders(Borders::ALL).border_set(self.border_set(border::PLAIN))
+    }
+
+    /// Recolors a style of the game, which is defined for the default theme, and turns it into a terminal style.
+    pub fn style(&self, style: GameStyle) -> Style {
+        terminal_style(GameStyle {
+            fg: style.fg.map(|color| self.game_color(color)),
+            bg: style.bg.map(|color| self.game_color(color)),
+            ..style
+        })
+    }
+
+    /// Recolors a color of the default theme and turns it into a terminal color.
+    pub fn color(&self, color: GameColor) -> Color {
+        terminal_color(self.game_color(color))
+    }
+
+    /// Recolors a color of the default theme.
+    fn game_color(&self, color: GameColor) -> GameColor {
+        if let Some((_, replacement)) =
+            self.pack.iter().flat_map(|pack| &pack.colors).find(|(original, _)| *original == color)
+        {
+            return *replacement;
+        }
+
+        match self.id {
+            ThemeId::Default => color,
+            ThemeId::HighContrast => match color {
+                GameColor::DarkGray => GameColor::Gray,
+                GameColor::Gray => GameColor::White,
+                GameColor::Red => GameColor::LightRed,
+                GameColor::Green => GameColor::LightGreen,
+                GameColor::Blue => GameColor::LightBlue,
+                GameColor::Magenta => GameColor::LightMagenta,
+                GameColor::Cyan => GameColor::LightCyan,
+                GameColor::Yellow => GameColor::LightYellow,
+                GameColor::Rgb(r, g, b) => GameColor::Rgb(brighten(r), brighten(g), brighten(b)),
+                other => other,
+            },
+            ThemeId::Deuteranopia => match color {
+                GameColor::Red => GameColor::Rgb(213, 94, 0),
+                GameColor::LightRed => GameColor::Rgb(230, 159, 0),
+                GameColor::Green => GameColor::Rgb(0, 114, 178),
+                GameColor::LightGreen => GameColor::Rgb(86, 180, 233),
+                GameColor::Yellow | GameColor::LightYellow => GameColor::Rgb(240, 228, 66),
+                GameColor::Magenta | GameColor::LightMagenta => GameColor::Rgb(204, 121, 167),
+                GameColor::Blue => GameColor::Rgb(0, 114, 178),
+                // Greenish colors of the biomes turn blue
+                GameColor::Rgb(r, g, b) if g > r && g > b => GameColor::Rgb(r, b, g),
+                other => other,
+            },
+        }
+    }
+
+    /// The style of the parts of log messages with the given tag.
+    pub fn log_style(&self, tag: LogTag) -> Style {
+        match tag {
+            LogTag::Plain => Style::new(),
+            LogTag::You => self.you,
+            LogTag::Npc => self.npc,
+            LogTag::Item => self.item,
+            LogTag::Number => self.number,
+            LogTag::Danger => self.danger,
+            LogTag::Lore => self.lore,
+            LogTag::Blocked => self.blocked,
+            LogTag::DebugInfo => self.debug_info,
+            LogTag::DebugWarn => self.debug_warn,
+        }
+    }
+
+    /// Turns a message of the log into a line of the terminal, styled with the theme.
+    pub fn log_line<'a>(&self, line: LogLine<'a>) -> Line<'a> {
+        Line::from(
+            line.spans
+                .into_iter()
+                .map(|span| {
+                    let style =
+                        self.log_style(span.tag).add_modifier(terminal_modifier(span.modifier));
+                    Span::styled(span.text, style)
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(ThemeId::Default)
+    }
+}
+
+/// Moves a color channel halfway to full brightness.
+fn brighten(channel: u8) -> u8 {
+    channel + (u8::MAX - channel) / 2
+}
+
+/// The terminal style of a style of the game, without recoloring it (see [Theme::style]).
+pub fn terminal_style(style: GameStyle) -> Style {
+    let mut terminal = Style::new()
+        .add_modifier(terminal_modifier(style.add_modifier))
+        .remove_modifier(terminal_modifier(style.sub_modifier));
+    terminal.fg = style.fg.map(terminal_color);
+    terminal.bg = style.bg.map(terminal_color);
+    terminal
+}
+
+/// The terminal color of a color of the game.
+pub fn terminal_color(color: GameColor) -> Color {
+    match color {
+        GameColor::Reset => Color::Reset,
+        GameColor::Black => Color::Black,
+        GameColor::Red => Color::Red,
+        GameColor::Green => Color::Green,
+        GameColor::Yellow => Color::Yellow,
+        GameColor::Blue => Color::Blue,
+        GameColor::Magenta => Color::Magenta,
+        GameColor::Cyan => Color::Cyan,
+        GameColor::Gray => Color::Gray,
+        GameColor::DarkGray => Color::DarkGray,
+        GameColor::LightRed => Color::LightRed,
+        GameColor::LightGreen => Color::LightGreen,
+        GameColor::LightYellow => Color::LightYellow,
+        GameColor::LightBlue => Color::LightBlue,
+        GameColor::LightMagenta => Color::LightMagenta,
+        GameColor::LightCyan => Color::LightCyan,
+        GameColor::White => Color::White,
+        GameColor::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
+
+/// The terminal modifiers of modifiers of the game.
+fn terminal_modifier(modifier: GameModifier) -> Modifier {
+    [
+        (GameModifier::BOLD, Modifier::BOLD),
+        (GameModifier::DIM, Modifier::DIM),
+        (GameModifier::ITALIC, Modifier::ITALIC),
+        (GameModifier::UNDERLINED, Modifier::UNDERLINED),
+        (GameModifier::REVERSED, Modifier::REVERSED),
+    ]
+    .into_iter()
+    .filter(|(game, _)| modifier.contains(*game))
+    .fold(Modifier::empty(), |terminal, (_, flag)| terminal | flag)
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::util::text_log::LogData;
+
+    #[test]
+    fn themes_recolor_styles() {
+        let theme = Theme::new(ThemeId::Deuteranopia);
+        let danger = GameStyle::new().fg(GameColor::Red).bg(GameColor::Green);
+        assert_eq!(
+            theme.style(danger),
+            Style::new().fg(Color::Rgb(213, 94, 0)).bg(Color::Rgb(0, 114, 178))
+        );
+        assert_eq!(theme.danger.fg, Some(Color::Rgb(213, 94, 0)));
+        assert_eq!(Theme::default().style(danger), Style::new().fg(Color::Red).bg(Color::Green));
+
+        let contrast = Theme::new(ThemeId::HighContrast);
+        assert_eq!(contrast.remembered.fg, Some(Color::Gray));
+        assert!(contrast.danger.add_modifier.contains(Modifier::BOLD));
+
+        for id in ThemeId::iter() {
+            assert_eq!(ThemeId::from_name(id.name()), Some(id));
+        }
+
+        let pack = Theme::from_pack(&PackTheme {
+            name: "ember".to_string(),
+            base: ThemeId::HighContrast,
+            colors: vec![(GameColor::Red, GameColor::Rgb(255, 80, 0))],
+        });
+        assert_eq!(pack.name(), "ember");
+        assert_eq!(pack.danger.fg, Some(Color::Rgb(255, 80, 0)));
+        assert_eq!(pack.remembered.fg, Some(Color::Gray));
+    }
+
+    #[test]
+    fn log_tags_are_styled_by_the_theme() {
+        let theme = Theme::default();
+        let data = LogData::GauntletCompleted;
+        let line = theme.log_line(data.line());
+
+        assert_eq!(line.to_string(), "You have conquered the Gauntlet!");
+        assert_eq!(line.spans[0].style, theme.you);
+        assert_eq!(line.spans[1].style, Style::new());
+        assert_eq!(line.spans[2].style, theme.danger.add_modifier(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn ascii_mode_replaces_glyphs_and_borders() {
+        use crate::world::tiles::TileType;
+
+        let mut theme = Theme::default();
+        assert_eq!(theme.glyph(&TileType::Hallway), '░');
+        assert_eq!(theme.border_set(border::DOUBLE), border::DOUBLE);
+
+        theme.ascii = true;
+        assert_eq!(theme.glyph(&TileType::Hallway), '#');
+        assert_eq!(theme.glyph(&TileType::StairsDown), '>');
+        assert_eq!(theme.border_set(border::DOUBLE), ASCII_BORDER);
+    }
+}
@@ -1,19 +1,27 @@
 #![allow(dead_code)]
 
+use std::path::Path;
+
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Padding, Paragraph, Wrap},
 };
 
+use crate::render::animations::AnimationQueue;
+use crate::render::info_display::{BLINK_INTERVAL, InfoDisplay};
 use crate::{
     App, KeyboardFocus, State,
     core::{entity_logic::Entity, game::GameState},
     data::ascii_art::{GRAVESTONE, STARTSCREEN_ASCII},
-    render::{menu_display::Menu, modal_display::ModalInterface, world_display::WorldDisplay},
-};
-use crate::{
-    render::info_display::InfoDisplay,
-    world::worldspace::{WORLD_HEIGHT, WORLD_WIDTH},
+    render::{
+        menu_display::Menu,
+        modal_display::ModalInterface,
+        recorder::WorldRecorder,
+        start_menu::StartMenu,
+        theme::Theme,
+        world_display::{Viewport, WorldDisplay},
+    },
+    util::build_info::BuildInfo,
 };
 
 const MIN_WIDTH: u16 = 150;
@@ -25,35 +33,44 @@ impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Size Check
         if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
-            render_window_size_warning(area, buf);
+            render_window_size_warning(area, buf, &self.ui.theme);
         } else {
             match self.state {
                 State::StartScreen => {
-                    render_start_screen(area, buf);
+                    render_start_screen(area, buf, &self.game, &self.ui);
                 }
                 State::Playing => {
                     self.render_game(area, buf);
                 }
                 State::GameOver => {
-                    render_game_over(area, buf, &self.game);
+                    render_game_over(
+                        area,
+                        buf,
+                        &self.game,
+                        self.morgue_path.as_deref(),
+                        &self.ui.theme,
+                    );
                 }
             }
         }
 
         // Modal
         if let Some(modal) = &self.ui.modal {
-            modal.render(area, buf, &self.game);
+            modal.render(area, buf, &self.game, &self.ui.theme);
         }
     }
 }
 
 impl App {
+    /// Whether blinking parts of the UI are lit. Switches every [BLINK_INTERVAL], counted in ticks of the main loop.
+    fn blink_on(&self) -> bool {
+        let ticks_per_blink =
+            (BLINK_INTERVAL.as_millis() / self.tick_rate.as_millis().max(1)).max(1);
+        (self.ui.ticks as u128 / ticks_per_blink).is_multiple_of(2)
+    }
+
     /// Renders the game's main UI.
     fn render_game(&self, rect: Rect, buf: &mut Buffer) {
-        // Normal
-        let world_width_u16: u16 = WORLD_WIDTH.try_into().unwrap();
-        let world_height_u16: u16 = WORLD_HEIGHT.try_into().unwrap();
-
         // Layout from top to bottom. Divided into:
         // +-------------------------+
         // |                         |
@@ -62,7 +79,7 @@ impl App {
         // +-------------------------+
         // | Info Display            |
         // +-------------------------+
-        let layout_top_bottom = Layout::vertical([Constraint::Min(0), Constraint::Length(4)]);
+        let layout_top_bottom = Layout::vertical([Constraint::Min(0), Constraint::Length(6)]);
         let [area_game, area_info] = layout_top_bottom.areas(rect);
 
         // +----------------+--------+
@@ -77,62 +94,64 @@ impl App {
         ]);
         let [area_world, _empty, area_menu] = layout_left_right.areas(area_game);
 
-        // Calculating automatic padding for fixed-size worldspace
-        let outer_width = world_width_u16 + 2;
-        let outer_height = world_height_u16 + 2;
+        // Calculating automatic padding, so the worldspace is centered. Levels too large for the area fill it and scroll.
+        let world = self.game.current_world();
+        let outer_width = (world.width as u16).saturating_add(2).min(area_world.width);
+        let outer_height = (world.height as u16).saturating_add(2).min(area_world.height);
         let area_worldspace = Layout::vertical([Constraint::Length(outer_height)])
             .horizontal_margin((area_world.width.saturating_sub(outer_width)) / 2)
             .vertical_margin((area_world.height.saturating_sub(outer_height)) / 2)
             .split(area_world)[0];
 
         // AREA: Character Info
-        let block_info = Block::default().title(" Character Info ").borders(Borders::ALL);
+        let block_info = self.ui.theme.block().title(format!(
+            " {} the {} ",
+            self.game.player.character.name(),
+            self.game.player.class.name()
+        ));
         let block_info_inner = block_info.inner(area_info);
         block_info.render(area_info, buf);
 
-        self.ui.info.render(&self.game, block_info_inner, buf);
+        self.ui.info.render(&self.game, self.blink_on(), block_info_inner, buf);
 
         // AREA: World
-        let block_world = Block::default()
-            .title(" World ")
-            .border_style(if self.keyboard_focus == KeyboardFocus::FocusWorld {
-                Style::default().fg(Color::LightBlue)
+        let block_world = self.ui.theme.block().title(" World ").border_style(
+            if self.keyboard_focus == KeyboardFocus::FocusWorld {
+                self.ui.theme.focus
             } else {
                 Style::default()
-            })
-            .borders(Borders::ALL);
+            },
+        );
         block_world.render(area_world, buf);
 
         // AREA: World Space
         // (Space actually occupied by tiles)
-        let block_world = Block::default().title(" World Space ").borders(Borders::ALL);
+        let block_world = self.ui.theme.block().title(" World Space ");
         let block_world_inner = block_world.inner(area_worldspace);
         block_world.render(area_worldspace, buf);
 
-        // Z-layer 0
-        self.ui.world_display.render(&self.game, block_world_inner, buf);
-        // Z-layer 1
-        self.ui.world_display.render_items(&self.game, block_world_inner, buf);
-        // Z-layer 2
-        self.ui.world_display.render_npcs(&self.game, block_world_inner, buf);
-        // Z-layer 3
-        self.ui.world_display.render_player(&self.game.player.character, block_world_inner, buf);
+        let viewport =
+            Viewport::new(&self.game, block_world_inner, self.ui.world_display.cell_width());
+        // Z-layers 0-3
+        self.ui.world_display.render_world_layers(&self.game, &viewport, &self.ui.theme, buf);
         // Z-layer 4
-        self.ui.world_display.render_cursor(&self.game, block_world_inner, buf);
+        self.ui.world_display.render_cursor(&self.game, &viewport, &self.ui.theme, buf);
+        // Z-layer 5
+        self.ui.animations.render(&viewport, &self.ui.theme, buf);
 
         // AREA: Menu (Log, menus, tables)
-        let block_menu = Block::default()
-            .title(format!(" Menu:{} ", self.ui.menu.mode))
-            .border_style(if self.keyboard_focus == KeyboardFocus::FocusMenu {
-                Style::default().fg(Color::LightBlue)
-            } else {
-                Style::default()
-            })
-            .borders(Borders::ALL);
+        let block_menu =
+            self.ui.theme.block().title(format!(" Menu:{} ", self.ui.menu.mode)).border_style(
+                if self.keyboard_focus == KeyboardFocus::FocusMenu {
+                    self.ui.theme.focus
+                } else {
+                    Style::default()
+                },
+            );
         let block_menu_inner = block_menu.inner(area_menu);
         block_menu.render(area_menu, buf);
 
-        self.ui.menu.render(&self.game, block_menu_inner, buf);
+        self.ui.menu.render(&self.game, &self.ui.theme, block_menu_inner, buf);
     }
 }
 
@@ -140,22 +159,17 @@ impl App {
 ///
 /// # Note
 /// The game is still accessible while a warning is displayed, meaning a player can still make inputs (e.g. 'q', 'wasd')
-fn render_warning(text: String, rect: Rect, buf: &mut Buffer) {
+fn render_warning(text: String, rect: Rect, buf: &mut Buffer, theme: &Theme) {
     let center_rect = get_centered_rect(50, 10, rect);
     let paragraph = Paragraph::new(Text::from(text))
         .wrap(Wrap { trim: true })
         .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .title(" Warning ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow)),
-        );
+        .block(theme.block().title(" Warning ").border_style(Style::default().fg(Color::Yellow)));
 
     paragraph.render(center_rect, buf);
 }
 
-fn render_window_size_warning(rect: Rect, buf: &mut Buffer) {
+fn render_window_size_warning(rect: Rect, buf: &mut Buffer, theme: &Theme) {
     render_warning(
         format!(
             "Your Terminal window is too small.\nIn order to play the game, your Terminal must at least have the dimensions of {}x{} characters.\n(Current {}x{})\n\nIncrease window size of your terminal or decrease your font size (Ctrl + -) to continue.",
@@ -163,6 +177,7 @@ fn render_window_size_warning(rect: Rect, buf: &mut Buffer) {
         ),
         rect,
         buf,
+        theme,
     );
 }
 
@@ -171,7 +186,7 @@ pub struct UserInterface {
     /// State of the Menu (which contains log, inventory, ...)
     pub menu: Menu,
 
-    /// Empty struct to hold the render method for the world display.
+    /// Render methods and options of the world display.
     pub world_display: WorldDisplay,
 
     /// Optional Modal interface. By default `None`, but if a modal interface is displayed, it becomes `Some(_)`
@@ -179,15 +194,39 @@ pub struct UserInterface {
 
     /// Empty struct to hold the render method for the info display.
     pub info: InfoDisplay,
+
+    /// Keeps the world panel of the last turns, so they can be exported with the `cast` command.
+    pub recorder: WorldRecorder,
+
+    /// Colors the world, log and menus are drawn with. Picked with the `theme` command.
+    pub theme: Theme,
+
+    /// Damage popups shown over the world for a moment after a hit.
+    pub animations: AnimationQueue,
+
+    /// Menu of the start screen.
+    pub start_menu: StartMenu,
+
+    /// Commands entered in the command prompt this session, oldest first. Recalled with the arrow keys.
+    pub command_history: Vec<String>,
+
+    /// Ticks of the main loop so far. Times blinking, which not every terminal can do on its own.
+    pub ticks: u64,
 }
 
 impl UserInterface {
     pub fn new() -> Self {
         Self {
             menu: Menu::new(),
-            world_display: WorldDisplay {},
+            world_display: WorldDisplay::default(),
             modal: None,
             info: InfoDisplay::new(),
+            recorder: WorldRecorder::new(),
+            theme: Theme::default(),
+            animations: AnimationQueue::default(),
+            start_menu: StartMenu::default(),
+            command_history: Vec::new(),
+            ticks: 0,
         }
     }
 }
@@ -218,7 +257,7 @@ pub fn get_centered_rect(width: u16, height: u16, area: Rect) -> Rect {
 }
 
 /// Render the main menu screen that is displayed when starting the game.
-fn render_start_screen(area: Rect, buf: &mut Buffer) {
+fn render_start_screen(area: Rect, buf: &mut Buffer, game: &GameState, ui: &UserInterface) {
     let center_rect = get_centered_rect(150, 33, area);
     let block = Block::default().borders(Borders::NONE);
 
@@ -227,11 +266,29 @@ fn render_start_screen(area: Rect, buf: &mut Buffer) {
     block.render(center_rect, buf);
 
     Paragraph::new(Text::from(STARTSCREEN_ASCII)).render(block_inner, buf);
+
+    // The menu takes the free space below the title of the art
+    let menu_area = Rect { x: block_inner.x + 55, y: block_inner.y + 20, width: 40, height: 7 }
+        .intersection(block_inner);
+    ui.start_menu.render(game, &ui.theme, menu_area, buf);
+
+    // Version in the bottom right corner, so it can be included in bug reports
+    let version_area = Rect { y: area.bottom().saturating_sub(1), height: 1, ..area };
+    Paragraph::new(format!("Anthill {} ", BuildInfo::current().summary()))
+        .alignment(Alignment::Right)
+        .style(ui.theme.hint)
+        .render(version_area, buf);
 }
 
 /// Render the Game Over Screen that appears when you lose the game (when the player character die).
-fn render_game_over(area: Rect, buf: &mut Buffer, game: &GameState) {
-    Block::default().borders(Borders::ALL).title(" Game Over ").render(area, buf);
+fn render_game_over(
+    area: Rect,
+    buf: &mut Buffer,
+    game: &GameState,
+    morgue_path: Option<&Path>,
+    theme: &Theme,
+) {
+    theme.block().title(" Game Over ").render(area, buf);
 
     let columns = Layout::default()
         .direction(Direction::Horizontal)
@@ -257,7 +314,7 @@ fn render_game_over(area: Rect, buf: &mut Buffer, game: &GameState) {
         .block(Block::default().padding(Padding::new(10, 0, 0, 0)))
         .render(left_vertical[1], buf);
 
-    let lines = [
+    let mut lines = vec![
         format!("Goodbye, {}", game.player.character.name()),
         "You have died in the Anthill".into(),
         format!("You reached floor {}", game.level_nr),
@@ -266,15 +323,29 @@ fn render_game_over(area: Rect, buf: &mut Buffer, game: &GameState) {
             game.player.character.stats.level, game.player.character.stats.experience
         ),
         "".into(),
-        "Press ENTER to start a new game".into(),
-        "Press SHIFT + q to quit".into(),
     ];
+    lines.extend(game.death_recap());
+    lines.push(format!("Seed: {}", game.seed));
+    if let Some(path) = morgue_path {
+        lines.push(format!("Morgue file written to {}", path.display()));
+    }
+    lines.extend([
+        "".into(),
+        "Press r to try the same seed again".into(),
+        "Press ENTER or n to start a new game with a new seed".into(),
+        "Press q to quit".into(),
+    ]);
 
+    let text_height = lines.len() as u16;
     let text = Text::from(lines.iter().map(|l| Line::from(l.as_str())).collect::<Vec<Line>>());
 
     let right_vertical = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(40), Constraint::Min(0), Constraint::Percentage(40)])
+        .constraints([
+            Constraint::Min((right.height.saturating_sub(text_height)) / 2),
+            Constraint::Length(text_height),
+            Constraint::Min(0),
+        ])
         .split(right);
 
     Paragraph::new(text).alignment(Alignment::Left).render(right_vertical[1], buf);
@@ -0,0 +1,536 @@
+use std::sync::OnceLock;
+
+use ratatui::prelude::*;
+use unicode_width::UnicodeWidthChar;
+
+use anthill_core::render::style::{Color as GameColor, Style as GameStyle};
+
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityBase},
+        game::GameState,
+        game_items::{ITEM_STACK_GLYPH, ITEM_STACK_STYLE},
+        player::PlayerCharacter,
+    },
+    data::{item_defs::item_defs, npc_defs::npc_defs},
+    render::theme::Theme,
+    world::{
+        coordinate_system::{Direction, Point},
+        tiles::{Drawable, Tile, TileType, fallback_glyph},
+        worldspace::World,
+    },
+};
+
+pub struct WorldDisplay {
+    /// Whether wounded NPCs are shaded by the hit points they have left. Toggled with the `wounds` command.
+    pub wound_indicators: bool,
+
+    /// Whether glyphs that are two cells wide (like emoji) are drawn. Toggled with the `glyphs` command.
+    ///
+    /// If any creature or item has such a glyph, every tile then takes two cells of the terminal, so the grid stays aligned.
+    /// Otherwise wide glyphs fall back to a letter of the name (see [fallback_glyph]), for terminals or fonts that cannot show them.
+    pub wide_glyphs: bool,
+}
+
+impl Default for WorldDisplay {
+    fn default() -> Self {
+        Self { wound_indicators: false, wide_glyphs: true }
+    }
+}
+
+/// Section of the world that is shown in an area of the terminal.
+///
+/// Levels that are smaller than the area are shown in its top left corner. Levels that are larger scroll along with the player,
+/// so the player stays in the middle of the area until the edge of the level is reached.
+#[derive(Clone, Copy, Debug)]
+pub struct Viewport {
+    /// Area of the terminal the world is drawn into.
+    pub rect: Rect,
+
+    /// Point of the world shown in the top left corner of the area.
+    pub offset: Point,
+
+    /// How many cells of the terminal a tile takes horizontally (see [WorldDisplay::cell_width]).
+    pub cell_width: u16,
+}
+
+impl Viewport {
+    /// Creates the viewport of the current level for the given area, scrolled to the player.
+    pub fn new(game: &GameState, rect: Rect, cell_width: u16) -> Self {
+        let world = game.current_world();
+        let center = game.player.character.pos();
+        let offset = Point::new(
+            scroll_offset(center.x, world.width, (rect.width / cell_width) as usize),
+            scroll_offset(center.y, world.height, rect.height as usize),
+        );
+
+        Self { rect, offset, cell_width }
+    }
+
+    /// Translates a position in the world into coordinates of characters in the terminal screen.
+    ///
+    /// # Returns
+    /// * `None` if the position is scrolled out of view.
+    pub fn display_pos(&self, pos: Point) -> Option<Position> {
+        let x = pos.x.checked_sub(self.offset.x)?;
+        let y = pos.y.checked_sub(self.offset.y)?;
+        if x >= (self.rect.width / self.cell_width) as usize || y >= self.rect.height as usize {
+            return None;
+        }
+
+        Some(Position::new(self.rect.x + x as u16 * self.cell_width, self.rect.y + y as u16))
+    }
+
+    /// Positions of the cells of the terminal a tile takes, starting with the one its glyph is drawn into.
+    pub fn display_cells(&self, pos: Point) -> impl Iterator<Item = Position> {
+        let cell_width = self.cell_width;
+        self.display_pos(pos).into_iter().flat_map(move |first| {
+            (0..cell_width).map(move |dx| Position::new(first.x + dx, first.y))
+        })
+    }
+}
+
+/// How far a view of `view_len` cells has to be scrolled along one axis of the world to keep `center` in its middle.
+/// The view never scrolls beyond the edges of the world.
+fn scroll_offset(center: usize, world_len: usize, view_len: usize) -> usize {
+    if world_len <= view_len {
+        return 0;
+    }
+
+    center.saturating_sub(view_len / 2).min(world_len - view_len)
+}
+
+impl WorldDisplay {
+    /// Main function to display the worldspace
+    ///
+    /// Renders every tile of the worldspace by placing the characer manually.
+    /// * Skips invisible and unexplored tiles
+    /// * Applies conditional rendering to walls so they connect
+    /// * Renders invisible explored tiles in gray
+    pub fn render(&self, game: &GameState, viewport: &Viewport, theme: &Theme, buf: &mut Buffer) {
+        for y in 0..game.current_world().height {
+            for x in 0..game.current_world().width {
+                let point: Point = Point { x, y };
+                let tile: &Tile = game.current_world().get_tile(point);
+
+                // Skip invisible and unexplored tiles
+                if !tile.visible && !tile.explored {
+                    continue;
+                }
+
+                // Walls are a special case due to their conditional rendering (wall mask)
+                let (glyph, padding) = if tile.tile_type == TileType::Wall {
+                    let mask = wall_mask(game.current_world(), point);
+                    let glyph = if theme.ascii { ascii_wall_glyph } else { wall_glyph };
+                    (glyph(mask), if mask & EAST != 0 { glyph(EAST | WEST) } else { ' ' })
+                } else {
+                    (theme.glyph(&tile.tile_type), ' ')
+                };
+
+                // Invisible explored tiles are styled in a shade of grey, others normally
+                let style = if !tile.visible && tile.explored {
+                    theme.remembered
+                } else {
+                    let biome = game.current_level().biome;
+                    let style = biome.palette(tile.tile_type, tile.tile_type.style());
+
+                    if tile.tile_type == TileType::Floor {
+                        theme.style(tile.lighting.modulate(style))
+                    } else {
+                        theme.style(style)
+                    }
+                };
+
+                // Cells on the terminal canvas. Wide tiles are padded, walls keep connecting to the east.
+                for (index, display_pos) in viewport.display_cells(point).enumerate() {
+                    if let Some(cell_content) = buf.cell_mut(display_pos) {
+                        cell_content.set_char(if index == 0 { glyph } else { padding });
+                        cell_content.set_style(style);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Renders all layers of the world that are part of the game itself (tiles, corpses, items, npcs and the player).
+    ///
+    /// UI overlays like the cursor are not included.
+    pub fn render_world_layers(
+        &self,
+        game: &GameState,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        // Z-layer 0
+        self.render(game, viewport, theme, buf);
+        // Z-layer 1
+        self.render_corpses(game, viewport, theme, buf);
+        self.render_items(game, viewport, theme, buf);
+        // Z-layer 2
+        self.render_npcs(game, viewport, theme, buf);
+        // Z-layer 3
+        self.render_player(&game.player.character, viewport, theme, buf);
+    }
+
+    /// Renders the player character at their own position in the world.
+    pub fn render_player(
+        &self,
+        pc: &PlayerCharacter,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        if theme.ascii && !pc.base.glyph.is_ascii() {
+            // Whatever the player picked, in ASCII they are the classic @
+            let base = EntityBase { glyph: PLAYER_ASCII_GLYPH, ..pc.base.clone() };
+            self.render_sprite(&base, viewport, theme, buf);
+        } else {
+            self.render_sprite(&pc.base, viewport, theme, buf);
+        }
+    }
+
+    /// Renders all Npcs at their position in the world. Large NPCs are drawn on every visible tile they cover.
+    pub fn render_npcs(
+        &self,
+        game: &GameState,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        for npc in &game.current_level().npcs {
+            if game.is_hidden(npc) {
+                continue;
+            }
+            let wound = if self.wound_indicators {
+                wound_color(npc.stats.base.hp_current, npc.stats.base.hp_max)
+            } else {
+                None
+            };
+
+            for point in npc.footprint().points() {
+                if game.current_world().get_tile(point).visible {
+                    self.render_glyph(&npc.base, point, viewport, theme, buf);
+
+                    if let Some(color) = wound {
+                        for display_pos in viewport.display_cells(point) {
+                            if let Some(cell_content) = buf.cell_mut(display_pos) {
+                                cell_content.set_style(
+                                    theme.style(GameStyle::new().fg(GameColor::Black).bg(color)),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Renders all Corpses at their position in the world.
+    pub fn render_corpses(
+        &self,
+        game: &GameState,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        for corpse in &game.current_level().corpses {
+            if game.current_world().get_tile(corpse.pos()).visible {
+                self.render_sprite(&corpse.base, viewport, theme, buf);
+            }
+        }
+    }
+
+    /// Renders all Items at their position in the world. Tiles with several items show the [ITEM_STACK_GLYPH] instead.
+    pub fn render_items(
+        &self,
+        game: &GameState,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        let level = game.current_level();
+        for item_sprite in &level.item_sprites {
+            if !game.current_world().get_tile(item_sprite.pos()).visible {
+                continue;
+            }
+            if level.item_sprites_at(item_sprite.pos()).nth(1).is_some() {
+                let Some(display_pos) = viewport.display_pos(item_sprite.pos()) else {
+                    continue;
+                };
+                if let Some(cell_content) = buf.cell_mut(display_pos) {
+                    cell_content.set_char(ITEM_STACK_GLYPH);
+                    cell_content.set_style(theme.style(ITEM_STACK_STYLE));
+                }
+            } else {
+                self.render_sprite(&item_sprite.base, viewport, theme, buf);
+            }
+        }
+    }
+
+    /// Renders a sprite (a single, dynamic character) on top of the worldspace.
+    ///
+    /// Can be used to render items, npcs, and the player character.
+    fn render_sprite(
+        &self,
+        entity_base: &EntityBase,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        self.render_glyph(entity_base, entity_base.pos, viewport, theme, buf);
+    }
+
+    /// Renders the glyph of an entity at the given point of the world.
+    fn render_glyph(
+        &self,
+        entity_base: &EntityBase,
+        point: Point,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        let glyph = if theme.ascii {
+            entity_base.ascii_glyph()
+        } else {
+            fit_glyph(entity_base.glyph(), &entity_base.name, viewport.cell_width)
+        };
+        let style = theme.style(entity_base.style());
+
+        for (index, display_pos) in viewport.display_cells(point).enumerate() {
+            let Some(cell_content) = buf.cell_mut(display_pos) else {
+                continue;
+            };
+
+            if index == 0 {
+                cell_content.set_char(glyph);
+            } else if glyph.width() == Some(2) {
+                // The wide glyph covers this cell
+                cell_content.reset();
+            } else {
+                cell_content.set_char(' ');
+            }
+            cell_content.set_style(style);
+        }
+    }
+
+    /// How many cells of the terminal a tile takes horizontally: Two if wide glyphs are drawn and any creature or item has one.
+    pub fn cell_width(&self) -> u16 {
+        if self.wide_glyphs && wide_glyphs_in_use() { 2 } else { 1 }
+    }
+
+    pub fn render_cursor(
+        &self,
+        game: &GameState,
+        viewport: &Viewport,
+        theme: &Theme,
+        buf: &mut Buffer,
+    ) {
+        if let Some(cursor) = &game.cursor {
+            for display_pos in viewport.display_cells(cursor.point) {
+                if let Some(cell) = buf.cell_mut(display_pos) {
+                    let style = cell.style().bg(theme.color(GameColor::LightCyan)).fg(Color::Black);
+                    cell.set_style(style);
+                }
+            }
+        }
+    }
+}
+
+/// Glyph of the player in ASCII mode if the one they picked is not ASCII.
+pub const PLAYER_ASCII_GLYPH: char = '@';
+
+// Wide Glyphs
+
+/// Whether any creature or item is drawn with a glyph that is two cells wide. Checked once, the definitions don't change.
+fn wide_glyphs_in_use() -> bool {
+    static WIDE_GLYPHS: OnceLock<bool> = OnceLock::new();
+    *WIDE_GLYPHS.get_or_init(|| {
+        item_defs()
+            .values()
+            .map(|def| def.glyph)
+            .chain(npc_defs().values().map(|def| def.glyph))
+            .any(|glyph| glyph.width() == Some(2))
+    })
+}
+
+/// Returns the glyph if it fits into the given number of cells, or its [fallback_glyph] otherwise.
+pub fn fit_glyph(glyph: char, name: &str, cell_width: u16) -> char {
+    match glyph.width() {
+        Some(1) => glyph,
+        Some(2) if cell_width >= 2 => glyph,
+        _ => fallback_glyph(name),
+    }
+}
+
+// Conditional Wall Rendering
+
+/// Bitmask, defining that a wall can be found to the north of the given position.
+const NORTH: u8 = 1 << 0; // 0001 -> 1
+/// Bitmask, defining that a wall can be found to the south of the given position.
+const SOUTH: u8 = 1 << 1; // 0010 -> 2
+/// Bitmask, defining that a wall can be found to the east of the given position.
+const WEST: u8 = 1 << 2; // 0100 -> 4
+/// Bitmask, defining that a wall can be found to the west of the given position.
+const EAST: u8 = 1 << 3; // 1000 -> 8
+
+/// Background color of a wounded NPC: green while it has more than half of its hit points, yellow down to a quarter, red below.
+///
+/// # Returns
+/// * `None` if the NPC is unhurt.
+fn wound_color(hp_current: u16, hp_max: u16) -> Option<GameColor> {
+    if hp_current >= hp_max {
+        None
+    } else if hp_current * 2 > hp_max {
+        Some(GameColor::Green)
+    } else if hp_current * 4 > hp_max {
+        Some(GameColor::Yellow)
+    } else {
+        Some(GameColor::Red)
+    }
+}
+
+/// Helper function that takes a position of a wall tile and calculates a wall mask for it.
+///
+/// # Returns
+/// This function returns a `u8` number, where the four least significant bits represent whether a wall tile is at one of the neighbouring points.
+///
+/// The result consists of the following bits: `X X X X E W S N` (X=empty, N=North, S=South, E=East, W=West)
+///
+/// If a bit at the given position is 1, then that means there's a wall tile neighbouring the given tile in the given direction.
+pub fn wall_mask(world: &World, point: Point) -> u8 {
+    let mut mask = 0;
+
+    if matches!(world.get_tile(point + Direction::Up).tile_type, TileType::Wall | TileType::Door(_))
+    {
+        mask |= NORTH; // +0001 -> +1
+    }
+    if matches!(
+        world.get_tile(point + Direction::Down).tile_type,
+        TileType::Wall | TileType::Door(_)
+    ) {
+        mask |= SOUTH; // +0010 -> +2
+    }
+    if matches!(
+        world.get_tile(point + Direction::Left).tile_type,
+        TileType::Wall | TileType::Door(_)
+    ) {
+        mask |= WEST; // +0100 -> +4
+    }
+    if matches!(
+        world.get_tile(point + Direction::Right).tile_type,
+        TileType::Wall | TileType::Door(_)
+    ) {
+        mask |= EAST; // +1000 -> +8
+    }
+
+    mask
+}
+
+/// Translates a wall mask (`u8`), created by [wall_mask], into the ASCII character used instead of [wall_glyph] in ASCII mode.
+///
+/// Straight walls are `|` and `-`, corners and junctions `+`. Walls without neighbours are `#`.
+pub fn ascii_wall_glyph(mask: u8) -> char {
+    match wall_glyph(mask) {
+        _ if mask == 0 => '#',
+        '│' => '|',
+        '─' => '-',
+        _ => '+',
+    }
+}
+
+/// Translates a wall mask (`u8`), created by [wall_mask], into an unicode character with the correct orientation that connects to adjacent wall tiles.
+///
+/// # Returns
+/// Returns a glyph (`char`) to render as the wall tile.
+pub fn wall_glyph(mask: u8) -> char {
+    if mask == NORTH | SOUTH {
+        '│' // 0011 -> 3
+    } else if mask == EAST | WEST {
+        '─' // 1100 -> 12
+    } else if mask == NORTH | EAST {
+        '└' // 1001 -> 9
+    } else if mask == NORTH | WEST {
+        '┘' // 0101 -> 5
+    } else if mask == SOUTH | EAST {
+        '┌' // 1010 -> 10
+    } else if mask == SOUTH | WEST {
+        '┐' // 0110 -> 6
+    } else if mask == NORTH | SOUTH | EAST {
+        '├' // 1011 -> 11
+    } else if mask == NORTH | SOUTH | WEST {
+        '┤' // 0111 -> 7
+    } else if mask == EAST | WEST | NORTH {
+        '┴' // 1011 -> 13
+    } else if mask == EAST | WEST | SOUTH {
+        '┬' // 1110 -> 14
+    } else if mask == NORTH | SOUTH | EAST | WEST {
+        '┼' // 1111-> 15
+    } else if mask == NORTH || mask == SOUTH {
+        '│'
+    } else if mask == EAST || mask == WEST {
+        '─'
+    } else {
+        '│'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::World};
+
+    #[test]
+    fn wounds_are_colored_by_the_hit_points_left() {
+        assert_eq!(wound_color(10, 10), None);
+        assert_eq!(wound_color(6, 10), Some(GameColor::Green));
+        assert_eq!(wound_color(5, 10), Some(GameColor::Yellow));
+        assert_eq!(wound_color(2, 10), Some(GameColor::Red));
+    }
+
+    #[test]
+    fn wide_glyphs_fall_back_when_tiles_are_narrow() {
+        assert_eq!(fit_glyph('ω', "Wisp", 1), 'ω');
+        assert_eq!(fit_glyph('🐀', "Giant Rat", 2), '🐀');
+        assert_eq!(fit_glyph('🐀', "Giant Rat", 1), 'G');
+        assert_eq!(fit_glyph('\u{200b}', "???", 2), '?');
+
+        let rect = Rect::new(1, 1, 20, 5);
+        let viewport = Viewport { rect, offset: Point::new(0, 0), cell_width: 2 };
+        assert_eq!(viewport.display_pos(Point::new(3, 2)), Some(Position::new(7, 3)));
+        assert_eq!(viewport.display_pos(Point::new(10, 2)), None);
+        assert_eq!(viewport.display_cells(Point::new(3, 2)).count(), 2);
+    }
+
+    #[test]
+    fn ascii_walls_keep_their_shape() {
+        assert_eq!(ascii_wall_glyph(NORTH | SOUTH), '|');
+        assert_eq!(ascii_wall_glyph(EAST), '-');
+        assert_eq!(ascii_wall_glyph(SOUTH | EAST), '+');
+        assert_eq!(ascii_wall_glyph(0), '#');
+    }
+
+    #[test]
+    fn viewport_scrolls_with_the_player_on_large_levels() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world = World::with_size(300, 60);
+        game.levels.insert(0, level);
+        let rect = Rect::new(1, 1, 100, 25);
+
+        game.player.character.base.pos = Point::new(150, 30);
+        let viewport = Viewport::new(&game, rect, 1);
+        assert_eq!(viewport.offset, Point::new(100, 18));
+        assert_eq!(viewport.display_pos(Point::new(150, 30)), Some(Position::new(51, 13)));
+        assert_eq!(viewport.display_pos(Point::new(10, 30)), None);
+
+        // The view stops at the edge of the level
+        game.player.character.base.pos = Point::new(295, 58);
+        assert_eq!(Viewport::new(&game, rect, 1).offset, Point::new(200, 35));
+
+        // Small levels don't scroll
+        game.current_level_mut().world = World::with_size(40, 10);
+        game.player.character.base.pos = Point::new(30, 8);
+        assert_eq!(Viewport::new(&game, rect, 1).offset, Point::new(0, 0));
+    }
+}
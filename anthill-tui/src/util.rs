@@ -0,0 +1,5 @@
+pub mod cli_args;
+pub mod command_handler;
+pub mod feedback;
+pub mod input_handler;
+pub use anthill_core::util::{build_info, errors_results, rng, text_log};
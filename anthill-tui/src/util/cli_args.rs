@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+
+use crate::core::difficulty::DifficultyLevel;
+
+/// Usage text printed when the arguments could not be understood.
+pub const USAGE: &str = "Usage: anthill [--seed <number>] [--difficulty <easy|normal|hard>] [--replay <file> [--speed <turns per second>]] [--balance <floor>] [--bench-gen <seeds>]";
+
+/// Options the game was started with on the command line.
+#[derive(Debug, Default, PartialEq)]
+pub struct CliArgs {
+    /// Master seed of the run. With the same seed, the same world is generated.
+    pub seed: Option<u64>,
+
+    /// Difficulty the run starts with. Can still be changed on the start screen.
+    pub difficulty: Option<DifficultyLevel>,
+
+    /// Replay file that is played back instead of starting a new run.
+    pub replay: Option<PathBuf>,
+
+    /// Turns per second the replay is played back with.
+    pub speed: Option<u32>,
+
+    /// Floor for which the damage of all monsters is measured and printed, without starting the game.
+    pub balance: Option<usize>,
+
+    /// Number of seeds the level generator is timed with, without starting the game. Only in dev builds.
+    #[cfg(feature = "dev")]
+    pub bench_gen: Option<u64>,
+}
+
+impl CliArgs {
+    /// Parses the arguments the game was started with, without the name of the program.
+    ///
+    /// Accepts `--seed 12345` as well as `--seed=12345`. The same goes for all other options.
+    ///
+    /// # Errors
+    /// * A description of the first argument that could not be understood.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut cli_args = CliArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            let value =
+                || value.or_else(|| args.next()).ok_or(format!("Missing value for {}", name));
+
+            match name.as_str() {
+                "--seed" => {
+                    let value = value()?;
+                    let seed =
+                        value.parse::<u64>().map_err(|_| format!("Invalid seed {}", value))?;
+                    cli_args.seed = Some(seed);
+                }
+                "--difficulty" => {
+                    let value = value()?;
+                    let difficulty = DifficultyLevel::from_name(&value)
+                        .ok_or(format!("Invalid difficulty {}", value))?;
+                    cli_args.difficulty = Some(difficulty);
+                }
+                "--replay" => cli_args.replay = Some(PathBuf::from(value()?)),
+                "--speed" => {
+                    let value = value()?;
+                    let speed = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|speed| *speed > 0)
+                        .ok_or(format!("Invalid speed {}", value))?;
+                    cli_args.speed = Some(speed);
+                }
+                "--balance" => {
+                    let value = value()?;
+                    let depth =
+                        value.parse::<usize>().map_err(|_| format!("Invalid floor {}", value))?;
+                    cli_args.balance = Some(depth);
+                }
+                #[cfg(feature = "dev")]
+                "--bench-gen" => {
+                    let value = value()?;
+                    let seeds = value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|seeds| *seeds > 0)
+                        .ok_or(format!("Invalid number of seeds {}", value))?;
+                    cli_args.bench_gen = Some(seeds);
+                }
+                _ => return Err(format!("Unknown argument {}", name)),
+            }
+        }
+
+        Ok(cli_args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_seeds_and_replays() {
+        let parse = |args: &[&str]| CliArgs::parse(args.iter().map(|arg| arg.to_string()));
+
+        assert_eq!(parse(&[]), Ok(CliArgs::default()));
+        assert_eq!(
+            parse(&["--seed", "12345"]),
+            Ok(CliArgs { seed: Some(12345), ..CliArgs::default() })
+        );
+        assert_eq!(parse(&["--seed=7"]), Ok(CliArgs { seed: Some(7), ..CliArgs::default() }));
+        assert_eq!(
+            parse(&["--replay", "run.ron", "--speed=4"]),
+            Ok(CliArgs {
+                replay: Some(PathBuf::from("run.ron")),
+                speed: Some(4),
+                ..CliArgs::default()
+            })
+        );
+        assert_eq!(
+            parse(&["--difficulty=hard"]),
+            Ok(CliArgs { difficulty: Some(DifficultyLevel::Hard), ..CliArgs::default() })
+        );
+        assert!(parse(&["--difficulty", "nightmare"]).is_err());
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--seed", "ants"]).is_err());
+        assert!(parse(&["--speed", "0"]).is_err());
+        assert!(parse(&["--sped", "3"]).is_err());
+    }
+}
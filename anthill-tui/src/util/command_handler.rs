@@ -0,0 +1,930 @@
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    App,
+    core::{
+        entity_logic::{Entity, EntityId},
+        game::GameRules,
+        inventory::{InventoryOrder, inventory_capacity},
+    },
+    data::{item_defs::item_defs, mods::pack_themes, npc_defs::npc_defs},
+    render::theme::{Theme, ThemeId, theme_path},
+    util::{
+        errors_results::GameOutcome,
+        feedback::{FeedbackEvent, feedback_path},
+        rng::{Check, DieSize, Roll},
+        text_log::{LogCategory, LogData},
+    },
+    world::{
+        coordinate_system::{Direction, Point},
+        level::LevelEntrance,
+        tiles::Collision,
+    },
+};
+
+/// Different available commands in the game.
+#[derive(Debug, EnumIter)]
+pub enum GameCommand {
+    /// Quits the game by closing the App. This does the same thing as pressing the quit button.
+    ///
+    /// # GameCommand Syntax
+    /// `quit`
+    Quit,
+
+    /// Adds an item to the player character's inventory.
+    ///
+    /// # GameCommand Syntax
+    /// `give <item_def> <amount>`
+    /// * `item_def` - String of the `item_def_id`
+    /// * `amount` - Number of items to give. Must be coercible into a `u32`!
+    Give { item_def: String, amount: u32 },
+
+    /// Displays all available commands and their descriptions.
+    ///
+    /// # GameCommand Syntax
+    /// `help`
+    Help,
+
+    /// Gives the player character high statistics.
+    ///
+    /// # GameCommand Syntax
+    /// `maxstats`
+    MaxStats,
+
+    /// Gives the player the best equipment in the game.
+    ///
+    /// # GameCommand Syntax
+    /// `maxequip`
+    MaxEquip,
+
+    /// Prints player character debug info to the log.
+    ///
+    /// # GameCommand Syntax
+    /// `playerinfo` or `pi`
+    PlayerInfo,
+
+    /// Prints rng debug info into the log.
+    ///
+    /// # GameCommand Syntax
+    /// `rngtest`
+    RngTest,
+
+    /// Teleports the player.
+    ///
+    /// # GameCommand Syntax
+    /// `teleport <x> <y>`
+    /// * `x`/`y` - Coordinates to teleport to (must be coercible into a `usize`)
+    Teleport(Point),
+
+    /// Reduces player to 0 HP, resulting in the Game Over screen.
+    ///
+    /// # GameCommand Syntax
+    /// `suicide`
+    Suicide,
+
+    /// Reveals all tiles on the map for 1 round.
+    /// This also sets the exploration status of all tiles to `true`.
+    ///
+    /// # GameCommand Syntax
+    /// `revealall`
+    RevealAll,
+
+    /// Displays a legend for every glyph in the log
+    ///
+    /// # GameCommand Syntax
+    /// `legend`
+    Legend,
+
+    /// Toggles tile collision for the player character, allowing them to walk through walls.
+    ///
+    /// # GameCommand Syntax
+    /// `noclip`
+    NoClip,
+
+    /// Toggles god mode for the player, making them immortal.
+    ///
+    /// # GameCommand Syntax
+    /// `godmode`
+    GodMode,
+
+    /// Exports the recorded last turns of the world panel as an asciinema cast file.
+    ///
+    /// # GameCommand Syntax
+    /// `cast`
+    Cast,
+
+    /// Steps the game back one turn. Only works in dev builds, which keep snapshots of the last turns.
+    ///
+    /// # GameCommand Syntax
+    /// `rewind`
+    Rewind,
+
+    /// Prints the statistics of the current run into the log.
+    ///
+    /// # GameCommand Syntax
+    /// `stats`
+    Stats,
+
+    /// Prints the master seed of the run, so the run can be started again with `anthill --seed <seed>`.
+    ///
+    /// # GameCommand Syntax
+    /// `seed`
+    Seed,
+
+    /// Saves the inputs of the run so far as a replay file, which can be played back with `anthill --replay <file>`.
+    ///
+    /// # GameCommand Syntax
+    /// `replay`
+    Replay,
+
+    /// Switches the color theme. Without a name, the available themes are listed.
+    ///
+    /// # GameCommand Syntax
+    /// `theme <name>`
+    /// * `name` - Name of the theme (`default`, `contrast` or `deuteranopia`)
+    Theme(Option<String>),
+
+    /// Turns the terminal bell for an event (low health, level up or death) on or off. Without an event, the settings are listed.
+    ///
+    /// # GameCommand Syntax
+    /// `feedback <event>`
+    /// * `event` - Name of the event (`lowhealth`, `levelup` or `death`)
+    Feedback(Option<String>),
+
+    /// Sorts the inventory, which gives the items new letters.
+    ///
+    /// # GameCommand Syntax
+    /// `sort <order>`
+    /// * `order` - `kind` (weapons, armor, potions, food, others) or `name`. Defaults to `kind`.
+    Sort(Option<String>),
+
+    /// Shows or hides a category of log messages. Without a category, the categories are listed.
+    ///
+    /// # GameCommand Syntax
+    /// `logfilter <category>`
+    /// * `category` - Name of the category (`combat`, `items`, `system`, `lore` or `debug`)
+    LogFilter(Option<String>),
+
+    /// Turns the shading of wounded NPCs in the world display on or off.
+    ///
+    /// # GameCommand Syntax
+    /// `wounds`
+    Wounds,
+
+    /// Turns the drawing of wide glyphs (like emoji) in the world display on or off. If off, they fall back to ASCII letters.
+    ///
+    /// # GameCommand Syntax
+    /// `glyphs`
+    Glyphs,
+
+    /// Turns the ASCII mode on or off, which draws tiles, creatures and borders with ASCII characters only.
+    ///
+    /// # GameCommand Syntax
+    /// `ascii`
+    Ascii,
+
+    /// Spawns an NPC on the current level. Only works in dev builds.
+    ///
+    /// # GameCommand Syntax
+    /// `spawn <npc_def> [x y]`
+    /// * `npc_def` - String of the `npc_def_id`
+    /// * `x`/`y` - Coordinates to spawn the NPC at (must be coercible into a `usize`). Defaults to a free tile next to the player.
+    Spawn { npc_def: String, pos: Option<Point> },
+
+    /// Marks every tile of the current level as explored, without granting vision. Only works in dev builds.
+    ///
+    /// # GameCommand Syntax
+    /// `revealmap`
+    RevealMap,
+
+    /// Restores the player's hit points. Only works in dev builds.
+    ///
+    /// # GameCommand Syntax
+    /// `heal`
+    Heal,
+
+    /// Takes the player to the entry of the level with the given index. Levels on the way are generated. Only works in dev builds.
+    ///
+    /// # GameCommand Syntax
+    /// `teleport-level <n>`
+    /// * `n` - Index of the level, `0` being the town (must be coercible into a `usize`)
+    TeleportLevel(usize),
+
+    /// Kills an NPC of the current level as if the player had slain it. Only works in dev builds.
+    ///
+    /// # GameCommand Syntax
+    /// `kill <entity_id>`
+    /// * `entity_id` - Id of the NPC, as printed by `spawn` (must be coercible into a `u32`)
+    Kill(EntityId),
+}
+
+impl GameCommand {
+    /// Returns the description of the command as displayed in-game using the 'help' command.
+    pub fn description(&self) -> &'static str {
+        match self {
+            GameCommand::Quit => "Quit the game",
+            GameCommand::Give { .. } => "Give an item to the player: `give <item def id> <amount>`",
+            GameCommand::Help => "List available commands",
+            GameCommand::MaxStats => "Grant max stats to player",
+            GameCommand::MaxEquip => "Grant the best equipment to the player",
+            GameCommand::PlayerInfo => "Print player info to log",
+            GameCommand::RngTest => "Make a roll and a check to test the RNG Engine",
+            GameCommand::Teleport(_) => {
+                "Teleport the player to the given absolute position: `teleport <x> <y>`"
+            }
+            GameCommand::RevealAll => "Get vision over the entire map for 1 round",
+            GameCommand::Suicide => "Set HP to zero to test game over state",
+            GameCommand::Legend => "Show list of all map symbols",
+            GameCommand::NoClip => "Toggle to walk through impassable terrain",
+            GameCommand::GodMode => "Toggle invulnerability (dev builds only)",
+            GameCommand::Cast => "Export the last turns as an asciinema cast file",
+            GameCommand::Rewind => "Undo the last turn (dev builds only)",
+            GameCommand::Stats => "Show the statistics of the current run",
+            GameCommand::Seed => "Show the seed of the current run",
+            GameCommand::Replay => "Save the run so far as a replay file",
+            GameCommand::Theme(_) => "Switch the color theme: `theme <name>`",
+            GameCommand::Feedback(_) => "Toggle the bell for an event: `feedback <event>`",
+            GameCommand::Sort(_) => "Sort the inventory: `sort <kind|name>`",
+            GameCommand::LogFilter(_) => "Show or hide log messages: `logfilter <category>`",
+            GameCommand::Wounds => "Toggle the health shading of wounded NPCs",
+            GameCommand::Glyphs => "Toggle wide glyphs, or draw them as ASCII letters",
+            GameCommand::Ascii => "Toggle drawing the world and borders in plain ASCII",
+            GameCommand::Spawn { .. } => {
+                "Spawn an NPC (dev builds only): `spawn <npc def id> [x y]`"
+            }
+            GameCommand::RevealMap => "Explore the entire map (dev builds only)",
+            GameCommand::Heal => "Restore the player's HP (dev builds only)",
+            GameCommand::TeleportLevel(_) => {
+                "Go to the level with the given index (dev builds only): `teleport-level <n>`"
+            }
+            GameCommand::Kill(_) => "Kill an NPC (dev builds only): `kill <entity id>`",
+        }
+    }
+
+    /// Returns the name of the command as displayed in-game using the 'help' command.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameCommand::Quit => "quit",
+            GameCommand::Give { .. } => "give",
+            GameCommand::Help => "help",
+            GameCommand::MaxStats => "maxstats",
+            GameCommand::MaxEquip => "maxequip",
+            GameCommand::PlayerInfo => "playerinfo",
+            GameCommand::RngTest => "rngtest",
+            GameCommand::Teleport(_) => "teleport",
+            GameCommand::Suicide => "suicide",
+            GameCommand::RevealAll => "revealall",
+            GameCommand::Legend => "legend",
+            GameCommand::NoClip => "noclip",
+            GameCommand::GodMode => "godmode",
+            GameCommand::Cast => "cast",
+            GameCommand::Rewind => "rewind",
+            GameCommand::Stats => "stats",
+            GameCommand::Seed => "seed",
+            GameCommand::Replay => "replay",
+            GameCommand::Theme(_) => "theme",
+            GameCommand::Feedback(_) => "feedback",
+            GameCommand::Sort(_) => "sort",
+            GameCommand::LogFilter(_) => "logfilter",
+            GameCommand::Wounds => "wounds",
+            GameCommand::Glyphs => "glyphs",
+            GameCommand::Ascii => "ascii",
+            GameCommand::Spawn { .. } => "spawn",
+            GameCommand::RevealMap => "revealmap",
+            GameCommand::Heal => "heal",
+            GameCommand::TeleportLevel(_) => "teleport-level",
+            GameCommand::Kill(_) => "kill",
+        }
+    }
+
+    /// Debug commands make testing deep levels feasible without playing through them. They only work in dev builds.
+    pub fn is_debug_only(&self) -> bool {
+        matches!(
+            self,
+            GameCommand::GodMode
+                | GameCommand::Spawn { .. }
+                | GameCommand::RevealMap
+                | GameCommand::Heal
+                | GameCommand::TeleportLevel(_)
+                | GameCommand::Kill(_)
+        )
+    }
+}
+
+impl TryFrom<String> for GameCommand {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut tokens = value.split_whitespace();
+
+        let command = tokens.next().ok_or("No command given")?.to_lowercase();
+
+        match command.as_str() {
+            "quit" => Ok(GameCommand::Quit),
+            "exit" => Ok(GameCommand::Quit),
+
+            "help" => Ok(GameCommand::Help),
+
+            "give" => {
+                let item_def = tokens.next().ok_or("Missing item name")?.to_string();
+
+                let amount =
+                    tokens.next().and_then(|string| string.parse::<u32>().ok()).unwrap_or(1);
+
+                Ok(GameCommand::Give { item_def, amount })
+            }
+
+            "maxstats" => Ok(GameCommand::MaxStats),
+            "maxequip" => Ok(GameCommand::MaxEquip),
+
+            "playerinfo" => Ok(GameCommand::PlayerInfo),
+            "pi" => Ok(GameCommand::PlayerInfo),
+            "rngtest" => Ok(GameCommand::RngTest),
+            "teleport" => {
+                let arg_x = tokens
+                    .next()
+                    .ok_or("Missing coordinates")?
+                    .parse::<usize>()
+                    .map_err(|_| "Invalid format for coordinates")?;
+                let arg_y = tokens
+                    .next()
+                    .ok_or("Missing y-coordinate")?
+                    .parse::<usize>()
+                    .map_err(|_| "Invalid format for y-coordinate")?;
+
+                Ok(GameCommand::Teleport(Point { x: arg_x, y: arg_y }))
+            }
+            "suicide" => Ok(GameCommand::Suicide),
+            "revealall" => Ok(GameCommand::RevealAll),
+            "legend" => Ok(GameCommand::Legend),
+            "noclip" => Ok(GameCommand::NoClip),
+            "godmode" => Ok(GameCommand::GodMode),
+            "cast" => Ok(GameCommand::Cast),
+            "rewind" => Ok(GameCommand::Rewind),
+            "stats" => Ok(GameCommand::Stats),
+            "seed" => Ok(GameCommand::Seed),
+            "replay" => Ok(GameCommand::Replay),
+            "theme" => Ok(GameCommand::Theme(tokens.next().map(str::to_string))),
+            "feedback" => Ok(GameCommand::Feedback(tokens.next().map(str::to_string))),
+            "sort" => Ok(GameCommand::Sort(tokens.next().map(str::to_string))),
+            "logfilter" => Ok(GameCommand::LogFilter(tokens.next().map(str::to_string))),
+            "wounds" => Ok(GameCommand::Wounds),
+            "glyphs" => Ok(GameCommand::Glyphs),
+            "ascii" => Ok(GameCommand::Ascii),
+            "spawn" => {
+                let npc_def = tokens.next().ok_or("Missing NPC name")?.to_string();
+                let pos = match tokens.next() {
+                    None => None,
+                    Some(arg_x) => {
+                        let x =
+                            arg_x.parse::<usize>().map_err(|_| "Invalid format for coordinates")?;
+                        let y = tokens
+                            .next()
+                            .ok_or("Missing y-coordinate")?
+                            .parse::<usize>()
+                            .map_err(|_| "Invalid format for y-coordinate")?;
+                        Some(Point { x, y })
+                    }
+                };
+
+                Ok(GameCommand::Spawn { npc_def, pos })
+            }
+            "revealmap" => Ok(GameCommand::RevealMap),
+            "heal" => Ok(GameCommand::Heal),
+            "teleport-level" => {
+                let index = tokens
+                    .next()
+                    .ok_or("Missing level")?
+                    .parse::<usize>()
+                    .map_err(|_| "Invalid format for level")?;
+
+                Ok(GameCommand::TeleportLevel(index))
+            }
+            "kill" => {
+                let entity_id = tokens
+                    .next()
+                    .ok_or("Missing entity id")?
+                    .parse::<EntityId>()
+                    .map_err(|_| "Invalid format for entity id")?;
+
+                Ok(GameCommand::Kill(entity_id))
+            }
+            _ => Err(format!("Unknown Command {}", command)),
+        }
+    }
+}
+
+/// Completes the command name or argument at the end of the input, as far as all candidates starting with it agree.
+/// Command names are completed, and so are the item definitions given with `give` and the NPC definitions spawned with `spawn`.
+/// A command name that is completed in full gets a space, so its argument can follow right away.
+pub fn complete_command(input: &str) -> String {
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    let completing_argument = tokens.len() > 1 || (tokens.len() == 1 && input.ends_with(' '));
+
+    let (prefix, partial, candidates): (&str, &str, Vec<&str>) = match tokens.as_slice() {
+        [] => return input.to_string(),
+        [partial] if !completing_argument => {
+            ("", partial, GameCommand::iter().map(|command| command.name()).collect())
+        }
+        [command, rest @ ..] if rest.len() <= 1 => {
+            let partial = rest.first().copied().unwrap_or("");
+            let candidates = match command.to_lowercase().as_str() {
+                "give" => item_defs().keys().map(String::as_str).collect(),
+                "spawn" => npc_defs().keys().map(String::as_str).collect(),
+                _ => return input.to_string(),
+            };
+            (&input[..input.len() - partial.len()], partial, candidates)
+        }
+        _ => return input.to_string(),
+    };
+
+    let matches: Vec<&str> =
+        candidates.into_iter().filter(|candidate| candidate.starts_with(partial)).collect();
+    let Some(first) = matches.first() else {
+        return input.to_string();
+    };
+
+    let common_len = matches.iter().fold(first.len(), |len, candidate| {
+        first.bytes().zip(candidate.bytes()).take(len).take_while(|(a, b)| a == b).count()
+    });
+    let mut completed = format!("{}{}", prefix, &first[..common_len]);
+    if matches.len() == 1 && !completing_argument {
+        completed.push(' ');
+    }
+    completed
+}
+
+/// Checks the input of the command prompt while it is typed, so mistakes can be highlighted before the command is run.
+///
+/// # Returns
+/// * The error the command would fail with, or `None` if it is fine or nothing was typed yet.
+pub fn command_error(input: &str) -> Option<String> {
+    if input.trim().is_empty() {
+        return None;
+    }
+
+    match GameCommand::try_from(input.to_string()) {
+        Err(error) => Some(error),
+        Ok(GameCommand::Give { item_def, .. }) if !item_defs().contains_key(&item_def) => {
+            Some(format!("Unknown item {}", item_def))
+        }
+        Ok(GameCommand::Spawn { npc_def, .. }) if !npc_defs().contains_key(&npc_def) => {
+            Some(format!("Unknown NPC {}", npc_def))
+        }
+        Ok(_) => None,
+    }
+}
+
+impl App {
+    /// Handles the execution of a given [`GameCommand`] in the `App` State.
+    ///
+    /// This is written into the app state, because it not only needs access to the `GameState`, but also some App functions.
+    fn execute_command(&mut self, command: GameCommand) {
+        match command {
+            GameCommand::Quit => {
+                self.should_quit = true;
+            }
+            GameCommand::Help => {
+                for command in GameCommand::iter() {
+                    self.game.log.print(format!(
+                        "{:<12} - {}",
+                        command.name(),
+                        command.description(),
+                    ))
+                }
+            }
+            GameCommand::Give { item_def: item_def_id, amount } => {
+                if self.game.get_item_def_by_id(&item_def_id).is_none() {
+                    self.game.log.print("No item with this def_id exists.".to_string());
+                    return;
+                }
+
+                let mut amount_given: u32 = 0;
+                for _ in 0..amount {
+                    if let Ok(item_id) = self.game.register_item(&item_def_id) {
+                        match self.game.add_item_to_inv(item_id) {
+                            Ok(GameOutcome::Success) => {
+                                amount_given += 1;
+                            }
+                            _ => {
+                                self.game.log.info(LogData::InventoryFull);
+                                let _ = self.game.deregister_item(item_id);
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                if amount_given > 0 {
+                    self.game.log.print(format!(
+                        "Added {} (x{}) to player's inventory",
+                        item_def_id, amount
+                    ));
+                }
+            }
+            GameCommand::MaxStats => {
+                self.game.player.character.stats.level = 100;
+                self.game.player.character.stats.dexterity = 100;
+                self.game.player.character.stats.perception = 100;
+                self.game.player.character.stats.strength = 100;
+                self.game.player.character.inventory.set_capacity(inventory_capacity(100));
+                self.game.player.character.stats.vitality = 100;
+                self.game.player.character.stats.base.hp_max = 500;
+                self.game.player.character.stats.base.hp_current = 500;
+                self.game.log.print("Advanced Player to Level 100.".to_string());
+            }
+            GameCommand::MaxEquip => {
+                self.execute_command(GameCommand::Give {
+                    item_def: "weapon_bow_cross".to_string(),
+                    amount: 1,
+                });
+                self.execute_command(GameCommand::Give {
+                    item_def: "weapon_warhammer".to_string(),
+                    amount: 1,
+                });
+                self.execute_command(GameCommand::Give {
+                    item_def: "armor_rustacean".to_string(),
+                    amount: 1,
+                });
+                self.execute_command(GameCommand::Give {
+                    item_def: "food_meat".to_string(),
+                    amount: 5,
+                });
+                self.execute_command(GameCommand::Give {
+                    item_def: "potion_healing_small".to_string(),
+                    amount: 2,
+                });
+            }
+
+            GameCommand::PlayerInfo => {
+                self.game.log.print(format!(
+                    "Character \"{}\" ({})\n-  HP: {}/{}\n-  Position: x: {}, y: {}\n-  S:{}, D:{}, V:{}, P:{}",
+                    self.game.player.character.base.name,
+                    self.game.player.class.name(),
+                    self.game.player.character.stats.base.hp_current,
+                    self.game.player.character.stats.base.hp_max,
+                    self.game.player.character.base.pos.x,
+                    self.game.player.character.base.pos.y,
+                    self.game.player.character.stats.dexterity,
+                    self.game.player.character.stats.perception,
+                    self.game.player.character.stats.strength,
+                    self.game.player.character.stats.vitality,
+                ));
+            }
+
+            GameCommand::RngTest => {
+                let roll: i16 = self.game.roll(&Roll::new(1, DieSize::D6));
+                let check = self.game.check(&Check::default().set_difficulty(10));
+                self.game.log.print(format!(
+                    "Rolling 1d6: {:?}\nChecking 1d20 against difficulty 10: {:?}",
+                    roll, check,
+                ))
+            }
+
+            GameCommand::Teleport(point) => {
+                if !self.game.current_world().is_in_bounds(point.x as isize, point.y as isize) {
+                    self.game.log.print(format!("Position {} is out of bounds", point));
+                    return;
+                }
+
+                if !self.game.current_world().get_tile(point).tile_type.is_walkable() {
+                    self.game.log.print(format!("Position {} cannot be occupied by player", point));
+                    return;
+                }
+
+                self.game.player.character.base.pos = point;
+                self.game.log.print("You were teleported.".to_string());
+            }
+
+            GameCommand::Suicide => {
+                self.game.log.print("Player committed suicide".to_string());
+                self.game.player.character.stats.base.hp_current = 0;
+            }
+
+            GameCommand::RevealAll => {
+                self.game.log.print("Revealing all tiles.".to_string());
+
+                for tile in self.game.current_world_mut().tiles.iter_mut() {
+                    tile.make_visible();
+                    tile.make_explored();
+                }
+            }
+
+            GameCommand::Legend => {
+                self.game.log.print("@ - Player Character (you)".to_string());
+                self.game.log.print("+ - Door (closed)".to_string());
+                self.game.log.print("_ - Door (open)".to_string());
+                for item in item_defs().values() {
+                    self.game.log.print(format!("{} - {}", item.glyph, item.name));
+                }
+                for npc in npc_defs().values() {
+                    self.game.log.print(format!("{} - {}", npc.glyph, npc.name));
+                }
+            }
+
+            GameCommand::NoClip => {
+                self.game.game_rules.toggle(GameRules::NO_CLIP);
+                self.game.log.print("Toggled No-Clip Mode.".to_string());
+            }
+
+            GameCommand::GodMode => {
+                self.game.game_rules.toggle(GameRules::GOD_MODE);
+                self.game.log.print("Toggled God Mode.".to_string());
+            }
+
+            GameCommand::Cast => match self.ui.recorder.export_cast() {
+                Ok(path) => self.game.log.print(format!(
+                    "Exported the last {} turns to {}",
+                    self.ui.recorder.len(),
+                    path.display()
+                )),
+                Err(error) => self.game.log.print(format!("Could not export cast: {}", error)),
+            },
+
+            GameCommand::Rewind => match self.game.rewind() {
+                Ok(true) => self.game.log.print(format!(
+                    "Rewound to round {}. {} more turns can be rewound.",
+                    self.game.round_nr,
+                    self.game.rewind_history.len()
+                )),
+                Ok(false) => self.game.log.print("There is no turn left to rewind.".to_string()),
+                Err(error) => self.game.log.debug_warn(error.to_string()),
+            },
+
+            GameCommand::Stats => {
+                for line in self.game.run_stats_lines() {
+                    self.game.log.print(line);
+                }
+                for kill_line in self.game.kill_lines() {
+                    self.game.log.print(format!("  {}", kill_line));
+                }
+            }
+
+            GameCommand::Seed => self.game.log.print(format!(
+                "Seed: {}. Start this run again with `anthill --seed {}`",
+                self.game.seed, self.game.seed
+            )),
+            GameCommand::Replay => match self.game.write_replay_file() {
+                Ok(path) => self.game.log.print(format!(
+                    "Saved the replay of {} turns to {}. Play it with `anthill --replay <file>`",
+                    self.game.replay.inputs.len(),
+                    path.display()
+                )),
+                Err(error) => self.game.log.print(format!("Could not save the replay: {}", error)),
+            },
+
+            GameCommand::Theme(None) => {
+                let names: Vec<&str> = ThemeId::iter()
+                    .map(|theme_id| theme_id.name())
+                    .chain(pack_themes().iter().map(|theme| theme.name.as_str()))
+                    .collect();
+                self.game.log.print(format!(
+                    "Current theme: {}. Available themes: {}",
+                    self.ui.theme.name(),
+                    names.join(", ")
+                ));
+            }
+
+            GameCommand::Theme(Some(name)) => {
+                let theme = match ThemeId::from_name(&name) {
+                    Some(theme_id) => Theme::new(theme_id),
+                    None => {
+                        match pack_themes().iter().find(|theme| theme.name == name.to_lowercase()) {
+                            Some(pack) => Theme::from_pack(pack),
+                            None => {
+                                self.game.log.print(format!("There is no theme called {}.", name));
+                                return;
+                            }
+                        }
+                    }
+                };
+
+                self.game.log.print(format!("Switched to the {} theme.", theme.name()));
+                // Only the built-in theme is remembered, the packs may be gone next time
+                let theme_id = theme.id;
+                self.ui.theme = Theme { ascii: self.ui.theme.ascii, ..theme };
+                if let Some(path) = theme_path()
+                    && let Err(error) = theme_id.save(&path)
+                {
+                    self.game.log.debug_warn(format!("Couldn't save theme: {}", error));
+                }
+            }
+
+            GameCommand::Feedback(None) => {
+                let events: Vec<String> = FeedbackEvent::iter()
+                    .map(|event| {
+                        let state =
+                            if self.feedback.settings.is_enabled(event) { "on" } else { "off" };
+                        format!("{} ({})", event.name(), state)
+                    })
+                    .collect();
+                self.game.log.print(format!("Bell on: {}", events.join(", ")));
+            }
+
+            GameCommand::Feedback(Some(name)) => {
+                let Some(event) = FeedbackEvent::from_name(&name) else {
+                    self.game.log.print(format!("There is no event called {}.", name));
+                    return;
+                };
+
+                let enabled = !self.feedback.settings.is_enabled(event);
+                self.feedback.settings.set_enabled(event, enabled);
+                let state = if enabled { "on" } else { "off" };
+                self.game.log.print(format!("Turned the bell for {} {}.", event.name(), state));
+                if let Some(path) = feedback_path()
+                    && let Err(error) = self.feedback.settings.save(&path)
+                {
+                    self.game.log.debug_warn(format!("Couldn't save feedback settings: {}", error));
+                }
+            }
+
+            GameCommand::Sort(order) => {
+                let order = match order.as_deref().map(InventoryOrder::from_name) {
+                    None => InventoryOrder::Kind,
+                    Some(Some(order)) => order,
+                    Some(None) => {
+                        self.game
+                            .log
+                            .print("The inventory can be sorted by kind or name.".to_string());
+                        return;
+                    }
+                };
+
+                self.game.sort_inventory(order);
+                self.game.log.print("Sorted the inventory.".to_string());
+            }
+
+            GameCommand::LogFilter(None) => {
+                let categories: Vec<String> = LogCategory::iter()
+                    .map(|category| {
+                        let state =
+                            if self.game.log.is_shown(category) { "shown" } else { "hidden" };
+                        format!("{} ({})", category.name(), state)
+                    })
+                    .collect();
+                self.game.log.print(format!("Log categories: {}", categories.join(", ")));
+            }
+
+            GameCommand::LogFilter(Some(name)) => {
+                let Some(category) = LogCategory::from_name(&name) else {
+                    self.game.log.print(format!("There is no log category called {}.", name));
+                    return;
+                };
+
+                let shown = !self.game.log.is_shown(category);
+                self.game.log.set_shown(category, shown);
+                let state = if shown { "Showing" } else { "Hiding" };
+                self.game.log.print(format!("{} {} messages.", state, category.name()));
+            }
+
+            GameCommand::Wounds => {
+                let display = &mut self.ui.world_display;
+                display.wound_indicators = !display.wound_indicators;
+                let state = if display.wound_indicators { "on" } else { "off" };
+                self.game.log.print(format!("Turned the wound shading of NPCs {}.", state));
+            }
+
+            GameCommand::Glyphs => {
+                let display = &mut self.ui.world_display;
+                display.wide_glyphs = !display.wide_glyphs;
+                let message = if display.wide_glyphs {
+                    "Drawing wide glyphs. Tiles take two cells if creatures or items use them."
+                } else {
+                    "Drawing wide glyphs as ASCII letters."
+                };
+                self.game.log.print(message.to_string());
+            }
+
+            GameCommand::Ascii => {
+                self.ui.theme.ascii = !self.ui.theme.ascii;
+                let state = if self.ui.theme.ascii { "on" } else { "off" };
+                self.game.log.print(format!("Turned the ASCII mode {}.", state));
+            }
+
+            GameCommand::Spawn { npc_def, pos } => {
+                if let Some(point) = pos
+                    && !self.game.current_world().is_in_bounds(point.x as isize, point.y as isize)
+                {
+                    self.game.log.print(format!("Position {} is out of bounds", point));
+                    return;
+                }
+
+                let player_pos = self.game.player.character.pos();
+                let pos = pos.or_else(|| {
+                    Direction::iter()
+                        .map(|direction| player_pos.get_adjacent(direction))
+                        .find(|point| self.game.current_level().is_available(*point))
+                });
+                let Some(pos) = pos else {
+                    self.game.log.print("There is no free space next to the player.".to_string());
+                    return;
+                };
+
+                let spawned = self.game.create_npc(npc_def.clone(), pos).and_then(|npc| {
+                    let npc_id = npc.id();
+                    self.game.current_level_mut().spawn_npc(npc).map(|_| npc_id)
+                });
+                match spawned {
+                    Ok(npc_id) => self
+                        .game
+                        .log
+                        .print(format!("Spawned {} (ID: {}) at {}.", npc_def, npc_id, pos)),
+                    Err(error) => {
+                        self.game.log.print(format!("Could not spawn {}: {}", npc_def, error))
+                    }
+                }
+            }
+
+            GameCommand::RevealMap => {
+                for tile in self.game.current_world_mut().tiles.iter_mut() {
+                    tile.make_explored();
+                }
+                self.game.log.print("Revealed the map.".to_string());
+            }
+
+            GameCommand::Heal => {
+                let stats = &mut self.game.player.character.stats.base;
+                stats.hp_current = stats.hp_max;
+                self.game.log.print("Restored the player's HP.".to_string());
+            }
+
+            GameCommand::TeleportLevel(index) => {
+                match self.game.goto_level(index, LevelEntrance::Entry) {
+                    Ok(()) => self.game.log.print(format!("Teleported to level {}.", index)),
+                    Err(error) => {
+                        self.game.log.print(format!("Could not go to level {}: {}", index, error))
+                    }
+                }
+            }
+
+            GameCommand::Kill(npc_id) => {
+                let Some(npc) = self.game.current_level().get_npc(npc_id).cloned() else {
+                    self.game
+                        .log
+                        .print(format!("There is no NPC with ID {} on this level.", npc_id));
+                    return;
+                };
+
+                if let Err(error) = self.game.npc_died(npc, true) {
+                    self.game.log.debug_warn(error.to_string());
+                }
+            }
+        }
+    }
+
+    /// Tries to run a [`GameCommand`] from the string that was input by the user.
+    ///
+    /// If the String matches an available command, it is executed.
+    pub fn run_command(&mut self, input: String) {
+        match GameCommand::try_from(input) {
+            Ok(command) if command.is_debug_only() && !cfg!(feature = "dev") => self
+                .game
+                .log
+                .print(format!("The command {} only works in dev builds.", command.name())),
+            Ok(command) => self.execute_command(command),
+            Err(error) => self.game.log.print(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_and_items_are_completed() {
+        assert_eq!(complete_command("tele"), "teleport");
+        assert_eq!(complete_command("teleport-"), "teleport-level ");
+        assert_eq!(complete_command("max"), "max");
+        assert_eq!(complete_command("give food_app"), "give food_apple");
+        assert_eq!(complete_command("unknown"), "unknown");
+        assert_eq!(complete_command("spawn gobl"), "spawn goblin");
+
+        assert_eq!(command_error(""), None);
+        assert_eq!(command_error("give food_apple 2"), None);
+        assert!(command_error("give food_pineapple").is_some());
+        assert!(command_error("teleport x").is_some());
+        assert!(command_error("spawn dragonn 3 4").is_some());
+    }
+
+    #[test]
+    fn debug_commands_take_their_arguments() {
+        assert!(matches!(
+            GameCommand::try_from("spawn goblin 3 4".to_string()),
+            Ok(GameCommand::Spawn { pos: Some(Point { x: 3, y: 4 }), .. })
+        ));
+        assert!(matches!(
+            GameCommand::try_from("spawn goblin".to_string()),
+            Ok(GameCommand::Spawn { pos: None, .. })
+        ));
+        assert!(GameCommand::try_from("spawn goblin 3".to_string()).is_err());
+        assert!(matches!(
+            GameCommand::try_from("teleport-level 7".to_string()),
+            Ok(GameCommand::TeleportLevel(7))
+        ));
+        assert!(matches!(GameCommand::try_from("kill 12".to_string()), Ok(GameCommand::Kill(12))));
+        assert!(GameCommand::Heal.is_debug_only());
+        assert!(!GameCommand::Help.is_debug_only());
+    }
+}
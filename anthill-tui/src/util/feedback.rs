@@ -0,0 +1,222 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use ron::de::from_reader;
+use ron::ser::{PrettyConfig, to_writer_pretty};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{
+    core::game::GameState,
+    util::{
+        errors_results::{GameError, IoError},
+        text_log::LogData,
+    },
+};
+
+/// The player is warned once their hit points drop below this share (in percent) of their maximum.
+pub const LOW_HEALTH_PERCENT: u16 = 25;
+
+/// Events of the game the player is alerted to with the terminal bell, even when they are not looking at the log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum FeedbackEvent {
+    /// The hit points of the player dropped below [LOW_HEALTH_PERCENT].
+    LowHealth,
+    LevelUp,
+    Death,
+}
+
+impl FeedbackEvent {
+    /// Name of the event as used by the `feedback` command.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FeedbackEvent::LowHealth => "lowhealth",
+            FeedbackEvent::LevelUp => "levelup",
+            FeedbackEvent::Death => "death",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "lowhealth" | "lowhp" => Some(FeedbackEvent::LowHealth),
+            "levelup" => Some(FeedbackEvent::LevelUp),
+            "death" => Some(FeedbackEvent::Death),
+            _ => None,
+        }
+    }
+}
+
+/// Which [FeedbackEvent]s ring the bell. Changed with the `feedback` command.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackSettings {
+    pub low_health: bool,
+    pub level_up: bool,
+    pub death: bool,
+}
+
+impl Default for FeedbackSettings {
+    fn default() -> Self {
+        Self { low_health: true, level_up: true, death: true }
+    }
+}
+
+impl FeedbackSettings {
+    pub fn is_enabled(&self, event: FeedbackEvent) -> bool {
+        match event {
+            FeedbackEvent::LowHealth => self.low_health,
+            FeedbackEvent::LevelUp => self.level_up,
+            FeedbackEvent::Death => self.death,
+        }
+    }
+
+    /// Turns the bell for the event on or off.
+    pub fn set_enabled(&mut self, event: FeedbackEvent, enabled: bool) {
+        match event {
+            FeedbackEvent::LowHealth => self.low_health = enabled,
+            FeedbackEvent::LevelUp => self.level_up = enabled,
+            FeedbackEvent::Death => self.death = enabled,
+        }
+    }
+
+    /// Loads the settings from the given file. A missing file means they were never changed.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path).map_err(IoError::FileReading)?;
+        Ok(from_reader(BufReader::new(file)).map_err(IoError::MapParsing)?)
+    }
+
+    /// Writes the settings to the given file.
+    ///
+    /// # Errors
+    /// * [IoError::FileCreation] if the file or its directory could not be created.
+    /// * [IoError::MapWriting] if the settings could not be written.
+    pub fn save(&self, path: &Path) -> Result<(), GameError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(IoError::FileCreation)?;
+        }
+
+        let file = File::create(path).map_err(IoError::FileCreation)?;
+        to_writer_pretty(BufWriter::new(file), self, PrettyConfig::default())
+            .map_err(IoError::MapWriting)?;
+        Ok(())
+    }
+}
+
+/// Path of the file the feedback settings are saved to.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn feedback_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("saves");
+    path.push("feedback.ron");
+    Some(path)
+}
+
+/// Watches the game for [FeedbackEvent]s and rings the terminal bell for the enabled ones.
+///
+/// Level ups are read from the log, which describes every event of the game. Low health and death are states of the player,
+/// so they are noticed when the player enters them.
+pub struct Feedback {
+    pub settings: FeedbackSettings,
+
+    /// Number of log messages that were already looked at.
+    log_cursor: usize,
+
+    /// Whether the player was below [LOW_HEALTH_PERCENT] the last time the game was looked at.
+    was_low_health: bool,
+
+    /// Whether the player was alive the last time the game was looked at.
+    was_alive: bool,
+}
+
+impl Feedback {
+    pub fn new(settings: FeedbackSettings) -> Self {
+        Self { settings, log_cursor: 0, was_low_health: false, was_alive: true }
+    }
+
+    /// Collects the events that happened since the last call. Called once per frame.
+    pub fn observe(&mut self, game: &GameState) -> Vec<FeedbackEvent> {
+        let mut events = Vec::new();
+
+        let new_messages = game.log.messages.get(self.log_cursor..).unwrap_or_default();
+        if new_messages.iter().any(|entry| matches!(entry.data, LogData::LevelUp { .. })) {
+            events.push(FeedbackEvent::LevelUp);
+        }
+        self.log_cursor = game.log.messages.len();
+
+        let is_alive = game.player_is_alive();
+        let stats = &game.player.character.stats.base;
+        let is_low_health = is_alive
+            && (stats.hp_current as u32 * 100) < (stats.hp_max as u32 * LOW_HEALTH_PERCENT as u32);
+
+        if is_low_health && !self.was_low_health {
+            events.push(FeedbackEvent::LowHealth);
+        }
+        if !is_alive && self.was_alive {
+            events.push(FeedbackEvent::Death);
+        }
+        self.was_low_health = is_low_health;
+        self.was_alive = is_alive;
+
+        events
+    }
+
+    /// Rings the terminal bell if an enabled event happened since the last call.
+    pub fn update(&mut self, game: &GameState) {
+        let events = self.observe(game);
+        if events.iter().any(|&event| self.settings.is_enabled(event)) {
+            ring_bell();
+        }
+    }
+}
+
+/// Sends the bell character to the terminal. Most terminals beep or flash their window.
+fn ring_bell() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn events_are_noticed_once() {
+        let mut game = GameState::default();
+        let mut feedback = Feedback::new(FeedbackSettings::default());
+        assert!(feedback.observe(&game).is_empty());
+
+        game.log.info(LogData::LevelUp { new_level: 2 });
+        assert_eq!(feedback.observe(&game), vec![FeedbackEvent::LevelUp]);
+        assert!(feedback.observe(&game).is_empty());
+
+        let hp_max = game.player.character.stats.base.hp_max;
+        game.player.character.stats.base.hp_current = hp_max / 10;
+        assert_eq!(feedback.observe(&game), vec![FeedbackEvent::LowHealth]);
+        assert!(feedback.observe(&game).is_empty());
+
+        game.player.character.stats.base.hp_current = 0;
+        assert_eq!(feedback.observe(&game), vec![FeedbackEvent::Death]);
+        assert!(feedback.observe(&game).is_empty());
+
+        let mut settings = FeedbackSettings::default();
+        settings.set_enabled(FeedbackEvent::LevelUp, false);
+        assert!(!settings.is_enabled(FeedbackEvent::LevelUp));
+        for event in FeedbackEvent::iter() {
+            assert_eq!(FeedbackEvent::from_name(event.name()), Some(event));
+        }
+    }
+}
@@ -0,0 +1,1246 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    App, State,
+    core::{
+        appearance::{PlayerAppearance, appearance_path},
+        containers::Container,
+        entity_logic::{Entity, EntityId},
+        game::{CursorMode, CursorState},
+        game_items::GameItemKindDef,
+        high_scores::{HIGH_SCORE_NAME_LENGTH, HighScoreSort, HighScoreTable, high_scores_path},
+        inventory::letter_to_index,
+        npc_interaction::NpcInteraction,
+        player_actions::PlayerInput,
+        player_class::PlayerClass,
+        resting::RestEnd,
+        travel::TravelEnd,
+    },
+    render::{
+        help_display::HelpPage,
+        menu_display::{InventoryAction, MenuMode},
+        modal_display::{DirectionAction, ModalInterface, SelectionAction},
+        start_menu::StartMenuEntry,
+    },
+    util::{command_handler::complete_command, errors_results::GameOutcome, text_log::LogData},
+    world::{
+        coordinate_system::Direction,
+        tiles::{Interactable, TileType},
+    },
+};
+
+/// Time between two ticks of the main loop, unless the display config sets another tick rate.
+///
+/// Every tick waits one round of a rest or takes one step of a travel, so this is also how long each of them is shown.
+pub const DEFAULT_TICK_RATE: Duration = Duration::from_millis(20);
+
+/// What the main loop hands to [App::update].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    /// The player pressed a key.
+    Key(KeyEvent),
+
+    /// The tick rate has passed since the last tick. Drives animations, rests, travels and replays.
+    Tick,
+}
+
+/// Hotkeys that are always available (see [GLOBAL_KEYMAP]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlobalKeyAction {
+    Quit,
+    Help,
+}
+
+impl GlobalKeyAction {
+    pub fn description(&self) -> &'static str {
+        match self {
+            GlobalKeyAction::Quit => "quit game",
+            GlobalKeyAction::Help => "open this help",
+        }
+    }
+}
+
+/// Actions of the game's main controls, available while the focus is on the world (see [WORLD_KEYMAP]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorldKeyAction {
+    Move(Direction),
+    Wait,
+    Rest,
+    UnequipWeapon,
+    UnequipArmor,
+    UseItem,
+    DropItem,
+    Interact,
+    Journal,
+    Look,
+    RangedAttack,
+    Legend,
+    PickUp,
+    Descend,
+    Ascend,
+}
+
+impl WorldKeyAction {
+    pub fn description(&self) -> &'static str {
+        match self {
+            WorldKeyAction::Move(Direction::Up) => "move up",
+            WorldKeyAction::Move(Direction::Down) => "move down",
+            WorldKeyAction::Move(Direction::Left) => "move left",
+            WorldKeyAction::Move(Direction::Right) => "move right",
+            WorldKeyAction::Wait => "wait one turn",
+            WorldKeyAction::Rest => "rest until healed",
+            WorldKeyAction::UnequipWeapon => "unequip weapon",
+            WorldKeyAction::UnequipArmor => "unequip armor",
+            WorldKeyAction::UseItem => "open inventory",
+            WorldKeyAction::DropItem => "open inventory in drop mode",
+            WorldKeyAction::Interact => "interact with neighbour (then w/a/s/d)",
+            WorldKeyAction::Journal => "journal and quests",
+            WorldKeyAction::Look => "enter look mode",
+            WorldKeyAction::RangedAttack => "enter ranged attack mode",
+            WorldKeyAction::Legend => "legend of the symbols on the map",
+            WorldKeyAction::PickUp => "pick up items you stand on",
+            WorldKeyAction::Descend => "go down the stairs you stand on",
+            WorldKeyAction::Ascend => "go up the stairs you stand on",
+        }
+    }
+}
+
+/// Keys of the hotkeys that are always available.
+pub const GLOBAL_KEYMAP: [(KeyCode, GlobalKeyAction); 2] =
+    [(KeyCode::Char('Q'), GlobalKeyAction::Quit), (KeyCode::Char('H'), GlobalKeyAction::Help)];
+
+/// Keys of the game's main controls. The key reference of the help browser is generated from this table, so it always shows the actual keys.
+pub const WORLD_KEYMAP: [(KeyCode, WorldKeyAction); 19] = [
+    (KeyCode::Char('w'), WorldKeyAction::Move(Direction::Up)),
+    (KeyCode::Char('a'), WorldKeyAction::Move(Direction::Left)),
+    (KeyCode::Char('s'), WorldKeyAction::Move(Direction::Down)),
+    (KeyCode::Char('d'), WorldKeyAction::Move(Direction::Right)),
+    (KeyCode::Char('.'), WorldKeyAction::Wait),
+    (KeyCode::Char('R'), WorldKeyAction::Rest),
+    (KeyCode::Char('i'), WorldKeyAction::UseItem),
+    (KeyCode::Char('D'), WorldKeyAction::DropItem),
+    (KeyCode::Char('W'), WorldKeyAction::UnequipWeapon),
+    (KeyCode::Char('A'), WorldKeyAction::UnequipArmor),
+    (KeyCode::Char('e'), WorldKeyAction::Interact),
+    (KeyCode::Char('j'), WorldKeyAction::Journal),
+    (KeyCode::Char('l'), WorldKeyAction::Look),
+    (KeyCode::Char('r'), WorldKeyAction::RangedAttack),
+    (KeyCode::Char('/'), WorldKeyAction::Legend),
+    (KeyCode::Char('g'), WorldKeyAction::PickUp),
+    (KeyCode::Char(','), WorldKeyAction::PickUp),
+    (KeyCode::Char('>'), WorldKeyAction::Descend),
+    (KeyCode::Char('<'), WorldKeyAction::Ascend),
+];
+
+/// The action bound to a key in the [WORLD_KEYMAP].
+fn world_key_action(code: KeyCode) -> Option<WorldKeyAction> {
+    WORLD_KEYMAP.iter().find(|(key, _)| *key == code).map(|(_, action)| *action)
+}
+
+/// Name of a key as shown to the player, like `SHIFT + r` for an uppercase `R`.
+pub fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(c) if c.is_uppercase() => format!("SHIFT + {}", c.to_lowercase()),
+        KeyCode::Char(c) => c.to_string(),
+        other => other.to_string().to_uppercase(),
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default)]
+pub enum KeyboardFocus {
+    #[default]
+    FocusWorld,
+    FocusMenu,
+}
+
+pub enum ModalAction {
+    Idle,
+    CloseModal,
+    RunCommand(String),
+    Interact(Direction),
+    Trade(EntityId),
+    Stash,
+    Container(Container),
+    SubmitHighScore(String),
+    NameCharacter(String),
+    StartRun(PlayerAppearance, PlayerClass),
+}
+
+impl App {
+    /// Sets the Application Focus to the given menu type (e.g. Inventory)
+    fn focus_menu(&mut self, mode: MenuMode) {
+        self.ui.menu.mode = mode;
+        self.keyboard_focus = KeyboardFocus::FocusMenu;
+    }
+
+    /// Resets the Application Focus to the default (World) and resets the menu to display the Log.
+    fn focus_reset(&mut self) {
+        self.keyboard_focus = KeyboardFocus::FocusWorld;
+        self.ui.menu.mode = MenuMode::Log;
+    }
+
+    /// Central event handler.
+    ///
+    /// Waits for input until the next tick is due, so the game keeps running while no key is pressed.
+    /// Only keyboard events are taken into consideration.
+    pub fn handle_events(&mut self) -> io::Result<()> {
+        let timeout = self.tick_rate.saturating_sub(self.last_tick.elapsed());
+        if event::poll(timeout)?
+            && let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+        {
+            self.update(Message::Key(key_event));
+        }
+
+        if self.last_tick.elapsed() >= self.tick_rate {
+            self.last_tick = Instant::now();
+            self.update(Message::Tick);
+        }
+        Ok(())
+    }
+
+    /// Moves the app forward by one message.
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Key(key_event) => {
+                self.ui.animations.clear();
+
+                // Any key stops a rest or a travel, and does nothing else
+                if self.runs_on_its_own() {
+                    self.game.stop_resting(RestEnd::Cancelled);
+                    self.game.stop_travel(TravelEnd::Cancelled);
+                    return;
+                }
+                self.handle_key_event(key_event);
+            }
+            Message::Tick => self.handle_tick(),
+        }
+    }
+
+    /// Expires the animations and plays the next round of whatever runs on its own: a rest, a travel or a replay.
+    ///
+    /// Replays play their next turn once the turn duration has passed since the last one.
+    fn handle_tick(&mut self) {
+        let now = Instant::now();
+        self.ui.ticks += 1;
+        self.ui.animations.expire(now);
+
+        if self.runs_on_its_own() {
+            let first_new = self.game.log.get_messages_for_display().len();
+            if self.game.resting.is_some() {
+                self.game.continue_resting();
+            } else {
+                self.game.continue_travel();
+            }
+            self.check_more_prompt(first_new);
+            return;
+        }
+
+        if let Some(playback) = &self.playback
+            && self.state == State::Playing
+            && !playback.paused
+            && !playback.is_finished()
+            && now.duration_since(self.last_turn) >= playback.turn_duration()
+        {
+            self.last_turn = now;
+            self.step_playback();
+        }
+    }
+
+    /// Whether the player rests or travels, and nothing in the UI waits for them.
+    fn runs_on_its_own(&self) -> bool {
+        (self.game.resting.is_some() || self.game.travel.is_some())
+            && self.state == State::Playing
+            && self.ui.modal.is_none()
+            && self.ui.menu.more_prompt.is_none()
+    }
+
+    /// Central event handler for keyboard input.
+    ///
+    /// Here it switches the event handling logic depending on what menu or ui-section the user is interacting with.
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        // 1. Prioritise Modal
+        if self.ui.modal.is_some() {
+            self.handle_modal_key_event(key_event);
+            return;
+        }
+
+        // 2. Messages of the last turn that did not fit into the log. ESC skips them.
+        if self.state == State::Playing && self.ui.menu.more_prompt.is_some() {
+            if key_event.code == KeyCode::Esc {
+                self.ui.menu.more_prompt = None;
+            } else {
+                let message_count = self.game.log.get_messages_for_display().len();
+                self.ui.menu.advance_more_prompt(message_count);
+            }
+            return;
+        }
+
+        // 3. Global hotkeys (work in all states)
+        if self.handle_global_hotkeys(key_event) {
+            return;
+        }
+
+        // 4. State-specific input
+        match self.state {
+            State::StartScreen => {
+                self.handle_start_screen_input(key_event);
+            }
+            State::Playing => {
+                let first_new = self.game.log.get_messages_for_display().len();
+                self.handle_playing_input(key_event);
+                self.check_more_prompt(first_new);
+            }
+            State::GameOver => {
+                self.handle_game_over_input(key_event);
+            }
+        }
+    }
+
+    /// Pauses with a `--More--` prompt if the turn added more messages (starting at `first_new`) than the log can show.
+    ///
+    /// Replays are not paused, so they keep running.
+    fn check_more_prompt(&mut self, first_new: usize) {
+        if self.playback.is_none() {
+            let message_count = self.game.log.get_messages_for_display().len();
+            self.ui.menu.check_more_prompt(first_new, message_count);
+        }
+    }
+
+    /// Hotkeys that are always available regardless of ui state.
+    fn handle_global_hotkeys(&mut self, key_event: KeyEvent) -> bool {
+        let Some((_, action)) = GLOBAL_KEYMAP.iter().find(|(code, _)| *code == key_event.code)
+        else {
+            return false;
+        };
+
+        self.ui.modal = Some(match action {
+            GlobalKeyAction::Quit => ModalInterface::ConfirmQuit,
+            GlobalKeyAction::Help => ModalInterface::HelpDisplay { page: HelpPage::default() },
+        });
+        true
+    }
+
+    /// Handling input in the starting screen.
+    fn handle_start_screen_input(&mut self, key_event: KeyEvent) {
+        let selected = self.ui.start_menu.selected_entry();
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('w') => self.ui.start_menu.select_previous(),
+            KeyCode::Down | KeyCode::Char('s') => self.ui.start_menu.select_next(),
+            KeyCode::Left | KeyCode::Right if selected == StartMenuEntry::Difficulty => {
+                self.cycle_difficulty()
+            }
+            KeyCode::Enter => match selected {
+                StartMenuEntry::NewGame => {
+                    self.ui.modal = Some(ModalInterface::CharacterName {
+                        buffer: self.game.player.name.clone(),
+                    })
+                }
+                StartMenuEntry::Difficulty => self.cycle_difficulty(),
+                StartMenuEntry::HighScores => self.open_high_scores(),
+                StartMenuEntry::Help => {
+                    self.ui.modal = Some(ModalInterface::HelpDisplay { page: HelpPage::default() })
+                }
+                StartMenuEntry::Quit => self.should_quit = true,
+            },
+            _ => {}
+        }
+    }
+
+    /// Switches to the next difficulty before the run is started.
+    fn cycle_difficulty(&mut self) {
+        let difficulty = self.game.difficulty.level.next();
+        self.game.set_difficulty(difficulty);
+    }
+
+    /// Handling while playing the game.
+    ///
+    /// Here it switches the event handling logic depending on if the UI focus is on the world or the menu.
+    fn handle_playing_input(&mut self, key_event: KeyEvent) {
+        if self.playback.is_some() {
+            self.handle_playback_input(key_event);
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Char(':') => {
+                self.ui.modal = Some(ModalInterface::CommandInput {
+                    buffer: "".to_string(),
+                    history_index: None,
+                })
+            }
+            // Other key events depending on keyboard focus
+            _ => match self.keyboard_focus {
+                KeyboardFocus::FocusWorld => self.handle_world_key_event(key_event),
+                KeyboardFocus::FocusMenu => self.handle_menu_key_event(key_event),
+            },
+        }
+    }
+
+    /// Handling input while a replay is played back. The player only controls the playback, not the game.
+    fn handle_playback_input(&mut self, key_event: KeyEvent) {
+        let Some(playback) = &mut self.playback else {
+            return;
+        };
+
+        match key_event.code {
+            KeyCode::Char(' ') => playback.paused = !playback.paused,
+            KeyCode::Char('+') => playback.speed_up(),
+            KeyCode::Char('-') => playback.slow_down(),
+            KeyCode::Char('.') if playback.paused => {
+                self.step_playback();
+                return;
+            }
+            _ => return,
+        }
+
+        let status = playback.status();
+        self.game.log.print(status);
+    }
+
+    /// Handling input in the Game Over screen.
+    fn handle_game_over_input(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Char('n') => self.restart(None),
+            KeyCode::Char('r') => self.restart(Some(self.game.seed)),
+            KeyCode::Char('q') => self.should_quit = true,
+            _ => {}
+        }
+    }
+
+    /// Handling input while the focus is on the world. This is where the game's main controls for the player character are.
+    fn handle_world_key_event(&mut self, key_event: KeyEvent) {
+        if self.game.cursor.is_some() {
+            self.handle_cursor_key_event(key_event);
+            return;
+        }
+
+        if let Some(action) = world_key_action(key_event.code) {
+            self.perform_world_key_action(action);
+            return;
+        }
+
+        match key_event.code {
+            // Debug: Print player pos
+            KeyCode::Char('p') => self.game.log.debug_info(format!(
+                "Player at position x: {}, y: {}",
+                self.game.player.character.base.pos.x, self.game.player.character.base.pos.y
+            )),
+
+            // Debug: Print game iten register
+            KeyCode::Char('o') => {
+                for (item_id, item) in self.game.items.iter() {
+                    self.game.log.debug_info(format!("Item ID: {} DEF: {}", item_id, item.def_id))
+                }
+            }
+            // Debug: Open Test Modal
+            KeyCode::Char('9') => {
+                self.ui.modal = Some(ModalInterface::TextDisplay {
+                    title: "Test Display".to_string(),
+                    paragraphs: vec![
+                        "Das ist ein Test".to_string(),
+                        "Hier ein weiterer Paragraph".to_string(),
+                    ],
+                })
+            }
+            KeyCode::Char('8') => {
+                self.ui.modal = Some(ModalInterface::SelectPrompt {
+                    selection_action: SelectionAction::Debug,
+                    options: vec!["Message 1".into(), "Message 2".into(), "Message 3".into()],
+                })
+            }
+            _ => {}
+        }
+    }
+
+    /// Carries out an action of the game's main controls (see [WORLD_KEYMAP]).
+    fn perform_world_key_action(&mut self, action: WorldKeyAction) {
+        match action {
+            WorldKeyAction::Move(direction) => self.handle_direction_input(direction),
+            WorldKeyAction::Wait => self.game.resolve_player_action(PlayerInput::Wait),
+            WorldKeyAction::PickUp => self.open_pickup_menu(),
+            // Taking the stairs with enemies next to the player needs a confirmation
+            WorldKeyAction::Descend | WorldKeyAction::Ascend => {
+                let down = action == WorldKeyAction::Descend;
+                if self.game.hostile_adjacent() {
+                    self.ui.modal = Some(ModalInterface::ConfirmUseStairs { down });
+                } else {
+                    let input = if down { PlayerInput::Descend } else { PlayerInput::Ascend };
+                    self.game.resolve_player_action(input);
+                }
+            }
+            WorldKeyAction::Rest => self.game.start_resting(),
+            WorldKeyAction::UnequipWeapon => {
+                self.game.resolve_player_action(PlayerInput::UnequipWeapon)
+            }
+            WorldKeyAction::UnequipArmor => {
+                self.game.resolve_player_action(PlayerInput::UnequipArmor)
+            }
+
+            // Shifts focus to the menu
+            WorldKeyAction::UseItem => self.focus_menu(MenuMode::Inventory(InventoryAction::Use)),
+            WorldKeyAction::DropItem => self.focus_menu(MenuMode::Inventory(InventoryAction::Drop)),
+
+            // Asks for a direction first
+            WorldKeyAction::Interact => {
+                self.ui.modal = Some(ModalInterface::DirectionPrompt {
+                    direction_action: DirectionAction::Interact,
+                });
+            }
+
+            // The journal starts with the quests
+            WorldKeyAction::Journal => {
+                let mut paragraphs = self.game.quest_paragraphs();
+                paragraphs.push(String::new());
+                paragraphs.extend(self.game.journal_paragraphs());
+                self.ui.modal = Some(ModalInterface::TextDisplay {
+                    title: " Journal ".to_string(),
+                    paragraphs,
+                });
+            }
+
+            WorldKeyAction::Look => {
+                self.game.cursor = Some(CursorState {
+                    kind: CursorMode::Look,
+                    point: self.game.player.character.pos(),
+                });
+            }
+            WorldKeyAction::RangedAttack => {
+                self.game.cursor = Some(CursorState {
+                    kind: CursorMode::RangedAttack,
+                    point: self.game.player.character.pos(),
+                });
+            }
+            WorldKeyAction::Legend => self.ui.modal = Some(ModalInterface::LegendDisplay),
+        }
+    }
+
+    /// Handling directional input of the player.
+    ///
+    /// Walking into a campfire opens the cooking menu, walking into a stash opens the stash menu. Everything else is resolved as a player action.
+    fn handle_direction_input(&mut self, direction: Direction) {
+        let target_point = self.game.player.character.pos().get_adjacent(direction);
+
+        let target_tile_type = if self
+            .game
+            .current_world()
+            .is_in_bounds(target_point.x as isize, target_point.y as isize)
+        {
+            Some(self.game.current_world().get_tile(target_point).tile_type)
+        } else {
+            None
+        };
+
+        match target_tile_type {
+            Some(TileType::Campfire) => self.open_cooking_menu(),
+            Some(TileType::Stash) => self.open_stash_menu(),
+            Some(TileType::Chest) => self.open_container_menu(Container::Chest(target_point)),
+            _ => self.game.resolve_player_action(PlayerInput::Direction(direction)),
+        }
+    }
+
+    /// Interacts with whatever is next to the player in the given direction.
+    ///
+    /// NPCs open a selection prompt with the interactions they offer. Interactable tiles are treated like walking into them.
+    fn interact_in_direction(&mut self, direction: Direction) {
+        let target_point = self.game.player.character.pos().get_adjacent(direction);
+
+        if !self.game.current_world().is_in_bounds(target_point.x as isize, target_point.y as isize)
+        {
+            self.game.log.info(LogData::NothingToInteract);
+            return;
+        }
+
+        if let Some(npc_id) = self.game.current_level().get_npc_at(target_point) {
+            match self.game.npc_interactions(npc_id) {
+                Ok(interactions) => {
+                    let options = interactions.iter().map(|i| i.to_string()).collect();
+                    self.ui.modal = Some(ModalInterface::SelectPrompt {
+                        selection_action: SelectionAction::InteractNpc { npc_id, interactions },
+                        options,
+                    });
+                }
+                Err(error) => self.game.log.debug_warn(error.to_string()),
+            }
+        } else if self.game.current_world().get_tile(target_point).tile_type.is_interactable() {
+            self.handle_direction_input(direction);
+        } else {
+            self.game.log.info(LogData::NothingToInteract);
+        }
+    }
+
+    /// Picks up the item the player stands on. If several items lie there, a selection prompt lists them.
+    fn open_pickup_menu(&mut self) {
+        let (sprite_ids, options): (Vec<_>, Vec<_>) =
+            self.game.items_underfoot().into_iter().unzip();
+
+        match sprite_ids.as_slice() {
+            [] => self.game.log.info(LogData::NothingToPickUp),
+            [sprite_id] => self.game.resolve_player_action(PlayerInput::PickUp(*sprite_id)),
+            _ => {
+                self.ui.modal = Some(ModalInterface::SelectPrompt {
+                    selection_action: SelectionAction::PickUp { sprite_ids },
+                    options,
+                });
+            }
+        }
+    }
+
+    /// Opens a selection prompt listing all cookable items in the player's inventory.
+    fn open_cooking_menu(&mut self) {
+        let (item_ids, options): (Vec<_>, Vec<_>) = self.game.cookable_items().into_iter().unzip();
+
+        if item_ids.is_empty() {
+            self.game.log.info(LogData::NothingToCook);
+            return;
+        }
+
+        self.ui.modal = Some(ModalInterface::SelectPrompt {
+            selection_action: SelectionAction::Cook { item_ids },
+            options,
+        });
+    }
+
+    /// Opens a selection prompt listing the items of the inventory that can be stored and the items in the stash that can be taken.
+    fn open_stash_menu(&mut self) {
+        let (item_ids, options) = self.game.stash_offers();
+
+        if options.is_empty() {
+            self.game.log.info(LogData::StashEmpty);
+            return;
+        }
+
+        self.ui.modal = Some(ModalInterface::SelectPrompt {
+            selection_action: SelectionAction::Stash { item_ids },
+            options,
+        });
+    }
+
+    /// Opens a selection prompt listing the items in a chest or bag that can be taken and the items of the inventory that can be put in.
+    fn open_container_menu(&mut self, container: Container) {
+        let Some(offers) = self.game.container_offers(container) else {
+            return;
+        };
+
+        if offers.options.is_empty() {
+            self.game.log.info(LogData::ContainerEmpty);
+            return;
+        }
+
+        self.ui.modal = Some(ModalInterface::SelectPrompt {
+            selection_action: SelectionAction::Container {
+                container,
+                contents: offers.contents,
+                storable: offers.storable,
+            },
+            options: offers.options,
+        });
+    }
+
+    /// Loads the high score table from its file. If it cannot be loaded, an empty table is used and the problem is logged.
+    fn load_high_scores(&mut self) -> HighScoreTable {
+        match high_scores_path().map(|path| HighScoreTable::load(&path)) {
+            Some(Ok(table)) => table,
+            Some(Err(error)) => {
+                self.game.log.debug_warn(format!("Couldn't load high scores: {}", error));
+                HighScoreTable::default()
+            }
+            None => HighScoreTable::default(),
+        }
+    }
+
+    /// Opens the high score table, sorted by score.
+    fn open_high_scores(&mut self) {
+        let table = self.load_high_scores();
+        self.ui.modal = Some(ModalInterface::HighScores { table, sort: HighScoreSort::default() });
+    }
+
+    /// Opens the character creation, starting with the appearance picked last time.
+    fn open_character_creation(&mut self) {
+        let appearance = appearance_path()
+            .map(|path| PlayerAppearance::load(&path))
+            .unwrap_or(Ok(PlayerAppearance::default()))
+            .unwrap_or_else(|error| {
+                self.game.log.debug_warn(format!("Couldn't load appearance: {}", error));
+                PlayerAppearance::default()
+            });
+        self.ui.modal =
+            Some(ModalInterface::CharacterCreation { appearance, class: PlayerClass::default() });
+    }
+
+    /// Gives the player character the picked appearance and class, remembers the appearance for the next character and starts the run.
+    fn start_run(&mut self, appearance: PlayerAppearance, class: PlayerClass) {
+        self.game.set_player_appearance(appearance);
+        if let Err(error) = self.game.set_player_class(class) {
+            self.game.log.debug_warn(format!("Couldn't hand out the starting kit: {}", error));
+        }
+        if let Some(path) = appearance_path()
+            && let Err(error) = appearance.save(&path)
+        {
+            self.game.log.debug_warn(format!("Couldn't save appearance: {}", error));
+        }
+
+        self.ui.modal = None;
+        self.state = State::Playing;
+    }
+
+    /// Adds the finished run to the high score table under the given name and shows the table.
+    fn record_high_score(&mut self, name: String) {
+        let name = if name.trim().is_empty() { self.game.player.name.clone() } else { name };
+
+        let mut table = self.load_high_scores();
+        table.insert(self.game.high_score_entry(name.trim()));
+        if let Err(error) = table.save() {
+            self.game.log.debug_warn(format!("Couldn't save high scores: {}", error));
+        }
+
+        self.ui.modal = Some(ModalInterface::HighScores { table, sort: HighScoreSort::default() });
+    }
+
+    /// Opens a selection prompt listing the stock of an adjacent shopkeeper, with the breakdown of each price, and the option to haggle.
+    ///
+    /// If the shopkeeper refuses to trade, the player is told why and nothing opens.
+    fn open_shop(&mut self, npc_id: EntityId) {
+        if !self.game.player_is_alive() {
+            return;
+        }
+
+        match self.game.stock_shop(npc_id) {
+            Ok(GameOutcome::Success) => {}
+            Ok(GameOutcome::Fail(reason)) => {
+                if let Some(log_data) = reason.notify_user() {
+                    self.game.log.info(log_data);
+                }
+                return;
+            }
+            Err(error) => {
+                self.game.log.debug_warn(error.to_string());
+                return;
+            }
+        }
+
+        let (item_ids, mut options): (Vec<_>, Vec<_>) =
+            self.game.shop_offers(npc_id).into_iter().unzip();
+        options.push("Haggle".to_string());
+
+        self.ui.modal = Some(ModalInterface::SelectPrompt {
+            selection_action: SelectionAction::Trade { npc_id, item_ids },
+            options,
+        });
+    }
+
+    /// Handling input while the focus is on the menu.
+    ///
+    /// Here it switches the event handling logic depending on if the inventory was opened or the log. The log has no controls and is generally not accessible to the player.
+    fn handle_menu_key_event(&mut self, key_event: KeyEvent) {
+        match &self.ui.menu.mode {
+            MenuMode::Inventory(_) => self.handle_inventory_key_event(key_event),
+            MenuMode::Log => {}
+        }
+    }
+
+    /// Handling the input while a modal display is opened.
+    ///
+    /// Handling input for each of the different modal types.
+    fn handle_modal_key_event(&mut self, key_event: KeyEvent) {
+        let modal_action = if let Some(modal) = &mut self.ui.modal {
+            match modal {
+                ModalInterface::ConfirmQuit => match key_event.code {
+                    KeyCode::Char('q') => {
+                        self.should_quit = true;
+                        ModalAction::Idle
+                    }
+                    _ => ModalAction::CloseModal,
+                },
+                ModalInterface::ConfirmUseItem { item_id } => match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.game.resolve_player_action(PlayerInput::UseItem(*item_id));
+
+                        ModalAction::CloseModal
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::ConfirmUseStairs { down } => match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        let input = if *down { PlayerInput::Descend } else { PlayerInput::Ascend };
+                        self.game.resolve_player_action(input);
+
+                        ModalAction::CloseModal
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::ConfirmDropItem { item_id } => match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.game.resolve_player_action(PlayerInput::DropItem(*item_id));
+
+                        ModalAction::CloseModal
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::DropQuantity { item_id, max, buffer } => match key_event.code {
+                    KeyCode::Char(c) if c.is_ascii_digit() && buffer.len() < 3 => {
+                        buffer.push(c);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Backspace => {
+                        buffer.pop();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Enter => {
+                        let amount = match buffer.parse::<usize>() {
+                            Ok(amount) => amount.min(*max),
+                            Err(_) => *max,
+                        };
+                        if amount > 0 {
+                            self.game
+                                .resolve_player_action(PlayerInput::DropItems(*item_id, amount));
+                        }
+
+                        ModalAction::CloseModal
+                    }
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::CommandInput { buffer, history_index } => match key_event.code {
+                    KeyCode::Char(c) => {
+                        buffer.push(c);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Backspace => {
+                        buffer.pop();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Tab => {
+                        *buffer = complete_command(buffer);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Up if !self.ui.command_history.is_empty() => {
+                        let index = history_index
+                            .unwrap_or(self.ui.command_history.len())
+                            .saturating_sub(1);
+                        *history_index = Some(index);
+                        *buffer = self.ui.command_history[index].clone();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Down => {
+                        if let Some(index) = *history_index {
+                            *history_index = Some(index + 1)
+                                .filter(|index| *index < self.ui.command_history.len());
+                            *buffer = history_index
+                                .map(|index| self.ui.command_history[index].clone())
+                                .unwrap_or_default();
+                        }
+                        ModalAction::Idle
+                    }
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::RunCommand(buffer.to_string()),
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::NameInput { buffer } => match key_event.code {
+                    KeyCode::Char(c) if buffer.chars().count() < HIGH_SCORE_NAME_LENGTH => {
+                        buffer.push(c);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Backspace => {
+                        buffer.pop();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::SubmitHighScore(buffer.to_string()),
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::CharacterName { buffer } => match key_event.code {
+                    KeyCode::Char(c) if buffer.chars().count() < HIGH_SCORE_NAME_LENGTH => {
+                        buffer.push(c);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Backspace => {
+                        buffer.pop();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::NameCharacter(buffer.trim().to_string()),
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::CharacterCreation { appearance, class } => match key_event.code {
+                    KeyCode::Tab => {
+                        *class = class.next();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Char('a') | KeyCode::Left => {
+                        appearance.cycle_glyph(false);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Char('d') | KeyCode::Right => {
+                        appearance.cycle_glyph(true);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Char('w') | KeyCode::Up => {
+                        appearance.cycle_color(false);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Char('s') | KeyCode::Down => {
+                        appearance.cycle_color(true);
+                        ModalAction::Idle
+                    }
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::StartRun(*appearance, *class),
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::HighScores { sort, .. } => match key_event.code {
+                    KeyCode::Char('s') => {
+                        *sort = sort.next();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::TextDisplay { .. } => match key_event.code {
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::ExamineDisplay { .. } => match key_event.code {
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::LegendDisplay => match key_event.code {
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::HelpDisplay { page } => match key_event.code {
+                    KeyCode::Right | KeyCode::PageDown => {
+                        *page = page.next();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Left | KeyCode::PageUp => {
+                        *page = page.previous();
+                        ModalAction::Idle
+                    }
+                    KeyCode::Esc => ModalAction::CloseModal,
+                    KeyCode::Enter => ModalAction::CloseModal,
+                    _ => ModalAction::Idle,
+                },
+                ModalInterface::SelectPrompt { selection_action, options } => {
+                    match key_event.code {
+                        KeyCode::Esc => ModalAction::CloseModal,
+                        KeyCode::Char(c) => {
+                            // Getting the selected option
+                            if let Some(index) = letter_to_index(c)
+                                && let Some(option) = options.get(index)
+                            {
+                                // Appying the selection action to the selected option
+                                match selection_action {
+                                    SelectionAction::Debug => {
+                                        self.game.log.debug_info(option.to_string());
+                                        ModalAction::Idle
+                                    }
+                                    SelectionAction::PickUp { sprite_ids } => {
+                                        if let Some(sprite_id) = sprite_ids.get(index) {
+                                            self.game.resolve_player_action(PlayerInput::PickUp(
+                                                *sprite_id,
+                                            ));
+                                        }
+                                        ModalAction::CloseModal
+                                    }
+                                    SelectionAction::Cook { item_ids } => {
+                                        if let Some(item_id) = item_ids.get(index) {
+                                            self.game
+                                                .resolve_player_action(PlayerInput::Cook(*item_id));
+                                        }
+                                        ModalAction::CloseModal
+                                    }
+                                    SelectionAction::InteractNpc { npc_id, interactions } => {
+                                        match interactions.get(index) {
+                                            Some(NpcInteraction::Trade) => {
+                                                ModalAction::Trade(*npc_id)
+                                            }
+                                            Some(interaction) => {
+                                                self.game.resolve_player_action(
+                                                    PlayerInput::InteractNpc(*npc_id, *interaction),
+                                                );
+                                                ModalAction::CloseModal
+                                            }
+                                            None => ModalAction::CloseModal,
+                                        }
+                                    }
+                                    SelectionAction::Trade { npc_id, item_ids } => {
+                                        // The option after the items is haggling
+                                        let input = match item_ids.get(index) {
+                                            Some(item_id) => PlayerInput::Buy(*npc_id, *item_id),
+                                            None => PlayerInput::Haggle(*npc_id),
+                                        };
+                                        self.game.resolve_player_action(input);
+                                        ModalAction::Trade(*npc_id)
+                                    }
+                                    SelectionAction::ChoosePerk => {
+                                        self.game
+                                            .resolve_player_action(PlayerInput::ChoosePerk(index));
+                                        ModalAction::CloseModal
+                                    }
+                                    SelectionAction::Stash { item_ids } => {
+                                        // The options after the inventory items are the stored items
+                                        let input = match item_ids.get(index) {
+                                            Some(item_id) => PlayerInput::StoreItem(*item_id),
+                                            None => {
+                                                PlayerInput::TakeFromStash(index - item_ids.len())
+                                            }
+                                        };
+                                        self.game.resolve_player_action(input);
+                                        ModalAction::Stash
+                                    }
+                                    SelectionAction::Container {
+                                        container,
+                                        contents,
+                                        storable,
+                                    } => {
+                                        // "Take all" comes first, unless the container is empty
+                                        let take_all = usize::from(!contents.is_empty());
+                                        let input = if index < take_all {
+                                            PlayerInput::TakeAllFromContainer(*container)
+                                        } else if let Some(item_id) = contents.get(index - take_all)
+                                        {
+                                            PlayerInput::TakeFromContainer(*container, *item_id)
+                                        } else {
+                                            let item_id =
+                                                storable[index - take_all - contents.len()];
+                                            PlayerInput::PutIntoContainer(*container, item_id)
+                                        };
+                                        self.game.resolve_player_action(input);
+                                        ModalAction::Container(*container)
+                                    }
+                                }
+                            } else {
+                                ModalAction::Idle
+                            }
+                        }
+                        _ => ModalAction::Idle,
+                    }
+                }
+                ModalInterface::DirectionPrompt { direction_action } => {
+                    let direction = match key_event.code {
+                        KeyCode::Char('w') => Some(Direction::Up),
+                        KeyCode::Char('s') => Some(Direction::Down),
+                        KeyCode::Char('a') => Some(Direction::Left),
+                        KeyCode::Char('d') => Some(Direction::Right),
+                        _ => None,
+                    };
+
+                    match (key_event.code, direction, direction_action) {
+                        (KeyCode::Esc, _, _) => ModalAction::CloseModal,
+                        (_, Some(direction), DirectionAction::Interact) => {
+                            ModalAction::Interact(direction)
+                        }
+                        _ => ModalAction::Idle,
+                    }
+                }
+            }
+        } else {
+            return;
+        };
+
+        match modal_action {
+            ModalAction::Idle => {}
+            ModalAction::CloseModal => self.ui.modal = None,
+            ModalAction::RunCommand(command) => {
+                if !command.trim().is_empty() && self.ui.command_history.last() != Some(&command) {
+                    self.ui.command_history.push(command.clone());
+                }
+                self.run_command(command);
+                self.ui.modal = None;
+            }
+            ModalAction::Interact(direction) => {
+                self.ui.modal = None;
+                self.interact_in_direction(direction);
+            }
+            ModalAction::Trade(npc_id) => {
+                self.ui.modal = None;
+                self.open_shop(npc_id);
+            }
+            ModalAction::Stash => {
+                self.ui.modal = None;
+                self.open_stash_menu();
+            }
+            ModalAction::Container(container) => {
+                self.ui.modal = None;
+                self.open_container_menu(container);
+            }
+            ModalAction::SubmitHighScore(name) => self.record_high_score(name),
+            ModalAction::NameCharacter(name) => {
+                if !name.is_empty() {
+                    self.game.set_player_name(name);
+                }
+                self.open_character_creation();
+            }
+            ModalAction::StartRun(appearance, class) => self.start_run(appearance, class),
+        }
+    }
+
+    /// Handling input while the menu is focused and the inventory is open. Allows interaction with the inventory.
+    fn handle_inventory_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => {
+                self.focus_reset();
+            }
+            KeyCode::Char('W') => {
+                if let Err(e) = self.game.unequip_weapon() {
+                    self.game.log.debug_warn(format!("{}", e));
+                }
+            }
+            KeyCode::Char('A') => {
+                if let Err(e) = self.game.unequip_armor() {
+                    self.game.log.debug_warn(format!("{}", e));
+                }
+            }
+            KeyCode::Tab => self.ui.menu.inventory_tab = self.ui.menu.inventory_tab.next(),
+            KeyCode::BackTab => self.ui.menu.inventory_tab = self.ui.menu.inventory_tab.previous(),
+            KeyCode::PageDown | KeyCode::Char('>') => {
+                let last_page = self.game.player.character.inventory.page_count() - 1;
+                self.ui.menu.inventory_page = (self.ui.menu.inventory_page + 1).min(last_page);
+            }
+            KeyCode::PageUp | KeyCode::Char('<') => {
+                self.ui.menu.inventory_page = self.ui.menu.inventory_page.saturating_sub(1);
+            }
+            KeyCode::Char(c) => {
+                let inventory = &self.game.player.character.inventory;
+                let page = self.ui.menu.inventory_page.min(inventory.page_count() - 1);
+
+                // Only the items on the open tab and page can be picked
+                if let Some(item_id) = inventory.get(page, c)
+                    && let Some(item) = self.game.get_item_by_id(item_id)
+                    && let Some(def) = self.game.get_item_def_by_id(&item.def_id)
+                    && self.ui.menu.inventory_tab.includes(&def.kind)
+                {
+                    match self.ui.menu.mode {
+                        // Bags are opened instead of used
+                        MenuMode::Inventory(InventoryAction::Use)
+                            if matches!(def.kind, GameItemKindDef::Bag { .. }) =>
+                        {
+                            self.open_container_menu(Container::Bag(item_id));
+                        }
+                        MenuMode::Inventory(InventoryAction::Use) => {
+                            self.ui.modal = Some(ModalInterface::ConfirmUseItem { item_id });
+                        }
+                        MenuMode::Inventory(InventoryAction::Drop) => {
+                            let max = self.game.items_like(item_id).len();
+                            self.ui.modal = Some(if max > 1 {
+                                ModalInterface::DropQuantity { item_id, max, buffer: String::new() }
+                            } else {
+                                ModalInterface::ConfirmDropItem { item_id }
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Handling input while there is an instance of the cursor. Allows moving the cursor and performing actions with the cursor.
+    fn handle_cursor_key_event(&mut self, key_event: KeyEvent) {
+        if let Some(cursor) = &self.game.cursor {
+            match key_event.code {
+                KeyCode::Char('t') if matches!(cursor.kind, CursorMode::Look) => {
+                    let goal = cursor.point;
+                    self.game.cursor = None;
+                    self.game.start_travel(goal);
+                }
+                KeyCode::Char(c) => {
+                    let cursor_move_result = match c {
+                        'w' => self.game.move_cursor(Direction::Up),
+                        's' => self.game.move_cursor(Direction::Down),
+                        'a' => self.game.move_cursor(Direction::Left),
+                        'd' => self.game.move_cursor(Direction::Right),
+                        _ => Ok(GameOutcome::Success),
+                    };
+
+                    if let Err(error) = cursor_move_result {
+                        self.game.log.debug_warn(error.to_string());
+                        self.game.cursor = None;
+                    }
+                }
+
+                // Run cursor action
+                KeyCode::Enter => {
+                    // Non-visible target points can't be interacted with.
+                    if !self.game.current_world().get_tile(cursor.point).visible {
+                        self.game.log.info(LogData::TileNotVisible);
+                        return;
+                    }
+
+                    match cursor.kind {
+                        CursorMode::Look => {
+                            self.ui.modal =
+                                Some(ModalInterface::ExamineDisplay { point: cursor.point });
+
+                            // Unoccupied target points only output tile type.
+                            if !self.game.current_level().is_occupied(cursor.point)
+                                && self.game.current_level().get_corpse_at(cursor.point).is_none()
+                            {
+                                let tile = self.game.current_world().get_tile(cursor.point);
+                                self.game
+                                    .log
+                                    .info(LogData::LookAt { name: tile.tile_type.to_string() });
+                                return;
+                            }
+
+                            // Otherwise, a target point is occupied, so info about NPCs and/or Item Sprites is displayed.
+                            if let Some(entity_id) =
+                                self.game.current_level().get_npc_at(cursor.point)
+                                && let Some(npc) = self.game.current_level().get_npc(entity_id)
+                                && !self.game.is_hidden(npc)
+                            {
+                                self.game
+                                    .log
+                                    .info(LogData::LookAt { name: npc.name().to_string() });
+                            }
+
+                            if let Some(entity_id) =
+                                self.game.current_level().get_item_sprite_at(cursor.point)
+                                && let Some(item_sprite) =
+                                    self.game.current_level().get_item_sprite(entity_id)
+                            {
+                                self.game
+                                    .log
+                                    .info(LogData::LookAt { name: item_sprite.name().to_string() });
+                            }
+
+                            if let Some(entity_id) =
+                                self.game.current_level().get_corpse_at(cursor.point)
+                                && let Some(corpse) =
+                                    self.game.current_level().get_corpse(entity_id)
+                            {
+                                self.game
+                                    .log
+                                    .info(LogData::LookAt { name: corpse.name().to_string() });
+                            }
+                        }
+                        CursorMode::RangedAttack => {
+                            if let Some(entity_id) =
+                                self.game.current_level().get_npc_at(cursor.point)
+                            {
+                                self.game
+                                    .resolve_player_action(PlayerInput::RangedAttack(entity_id));
+                            }
+                        }
+                    }
+                }
+
+                KeyCode::Esc => self.game.cursor = None,
+                _ => {}
+            };
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Benchmarks of the level generator, run with `cargo bench -p anthill-core`.
+//!
+//! Each stage is measured on maps smaller than, equal to and larger than the default size. Every iteration generates the
+//! same fixed set of seeds, so results stay comparable between runs and criterion can compare them to the last baseline.
+
+use std::hint::black_box;
+
+use anthill_core::{
+    proc_gen::{
+        bsp::MapBSPTree, gen_config::GenConfig, proc_gen_level::ProcGenLevel,
+        proc_gen_room::ProcGenRoom, proc_gen_world::ProcGenWorld,
+    },
+    world::worldspace::{WORLD_HEIGHT, WORLD_WIDTH},
+};
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+
+/// Map sizes the generator is measured with.
+const MAP_SIZES: [(usize, usize); 3] = [(60, 20), (WORLD_WIDTH, WORLD_HEIGHT), (160, 40)];
+
+/// Seeds generated in every iteration.
+const SEEDS: [u64; 4] = [1, 73, 4242, 987_654_321];
+
+/// Depth the full levels are generated for, so different level themes are measured.
+fn depth_for(seed: u64) -> usize {
+    1 + (seed % 20) as usize
+}
+
+/// Partitioning the map with [MapBSPTree::generate_bsp].
+fn bench_bsp(c: &mut Criterion) {
+    let config = GenConfig::default();
+    let mut group = c.benchmark_group("generate_bsp");
+    for (width, height) in MAP_SIZES {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", width, height)),
+            &(width, height),
+            |b, &(width, height)| {
+                b.iter(|| {
+                    for seed in SEEDS {
+                        black_box(MapBSPTree::generate_bsp(seed, width, height, &config));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Connecting the rooms of a partitioned map with [ProcGenWorld::a_star_corridors]. Partitioning is not measured.
+fn bench_corridors(c: &mut Criterion) {
+    let config = GenConfig::default();
+    let mut group = c.benchmark_group("a_star_corridors");
+    for (width, height) in MAP_SIZES {
+        let partitioned = || -> Vec<(u64, ProcGenWorld)> {
+            SEEDS
+                .iter()
+                .map(|&seed| {
+                    let bsp = MapBSPTree::generate_bsp(seed, width, height, &config);
+                    let rooms = bsp.collect_leaves().into_iter().map(ProcGenRoom::from).collect();
+                    let mut world = ProcGenWorld {
+                        rooms,
+                        corridors: Vec::new(),
+                        floor: Vec::new(),
+                        width,
+                        height,
+                    };
+                    world.shrink_rooms(seed, config.shrink_factor_range.clone());
+                    (seed, world)
+                })
+                .collect()
+        };
+
+        group.bench_function(BenchmarkId::from_parameter(format!("{}x{}", width, height)), |b| {
+            b.iter_batched(
+                partitioned,
+                |mut worlds| {
+                    for (seed, world) in &mut worlds {
+                        world.a_star_corridors(*seed, config.extra_corridor_chance);
+                    }
+                    worlds
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// Everything that happens when a new level is generated, see [ProcGenLevel::generate].
+fn bench_level(c: &mut Criterion) {
+    let config = GenConfig::default();
+    let mut group = c.benchmark_group("proc_gen_level");
+    group.sample_size(20);
+    for (width, height) in MAP_SIZES {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", width, height)),
+            &(width, height),
+            |b, &(width, height)| {
+                b.iter(|| {
+                    for seed in SEEDS {
+                        black_box(ProcGenLevel::generate_with_size(
+                            seed,
+                            depth_for(seed),
+                            width,
+                            height,
+                            &config,
+                        ));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_bsp, bench_corridors, bench_level);
+criterion_main!(benches);
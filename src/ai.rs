@@ -1,2 +1,8 @@
+pub mod behavior;
+pub mod companion;
+pub mod dijkstra_map;
+pub mod movement_profile;
 pub mod npc_ai;
+pub mod pack;
 pub mod pathfinding;
+pub mod traffic;
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::{
+    ai::{
+        movement_profile::MovementProfile,
+        npc_ai::{NpcActionKind, NpcAiState},
+    },
+    core::{
+        crowd_control::CrowdControl,
+        entity_logic::{Entity, EntityId, Npc},
+        game::GameState,
+    },
+    util::errors_results::{DataError, EngineError, GameError},
+    world::{
+        coordinate_system::{Direction, Point},
+        tiles::Collision,
+    },
+};
+
+/// Id of the built-in behavior of NPCs that have not spotted the player yet.
+pub const WANDERING_BEHAVIOR: &str = "wandering";
+
+/// Id of the built-in behavior that chases the player and attacks them. Used by most monsters.
+pub const AGGRESSIVE_BEHAVIOR: &str = "aggressive";
+
+/// Id of the built-in behavior that fights like [AGGRESSIVE_BEHAVIOR], but runs away while badly hurt.
+pub const FLEEING_BEHAVIOR: &str = "fleeing";
+
+/// Id of the built-in behavior of invisible NPCs, which strike while unseen and back off once revealed.
+pub const STALKING_BEHAVIOR: &str = "stalking";
+
+/// Controller that decides what an NPC does on its turn.
+///
+/// Behaviors are registered under an id with [register_ai_behavior]. NPC definitions reference the behavior they use
+/// once they spotted the player (see [NpcDef::behavior](crate::data::npc_defs::NpcDef::behavior)).
+/// The built-in behaviors ([WANDERING_BEHAVIOR], [AGGRESSIVE_BEHAVIOR], [FLEEING_BEHAVIOR] and [STALKING_BEHAVIOR]) are implemented on this trait as well,
+/// so custom behaviors have the same means as the built-in ones.
+///
+/// This trait is a stable interface: New features are added as new methods with default implementations.
+pub trait AiBehavior: Send + Sync {
+    /// Decides on the action of the NPC for this turn. The action is resolved by [GameState::npc_take_turn].
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is no longer in the Level data structure.
+    fn choose_action(
+        &self,
+        game: &mut GameState,
+        npc_id: EntityId,
+    ) -> Result<NpcActionKind, GameError>;
+}
+
+/// Lazy loads the registry of AI behaviors, starting with the built-in ones.
+fn ai_behaviors() -> &'static RwLock<HashMap<String, Arc<dyn AiBehavior>>> {
+    static AI_BEHAVIORS: OnceLock<RwLock<HashMap<String, Arc<dyn AiBehavior>>>> = OnceLock::new();
+    AI_BEHAVIORS.get_or_init(|| {
+        let mut m: HashMap<String, Arc<dyn AiBehavior>> = HashMap::new();
+        m.insert(WANDERING_BEHAVIOR.to_string(), Arc::new(Wandering));
+        m.insert(AGGRESSIVE_BEHAVIOR.to_string(), Arc::new(Aggressive));
+        m.insert(FLEEING_BEHAVIOR.to_string(), Arc::new(Fleeing));
+        m.insert(STALKING_BEHAVIOR.to_string(), Arc::new(Stalking));
+        RwLock::new(m)
+    })
+}
+
+/// Registers a behavior under the given id, so NPC definitions can reference it. A behavior with the same id is replaced.
+pub fn register_ai_behavior(behavior_id: &str, behavior: impl AiBehavior + 'static) {
+    ai_behaviors()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(behavior_id.to_string(), Arc::new(behavior));
+}
+
+pub fn get_ai_behavior(behavior_id: &str) -> Option<Arc<dyn AiBehavior>> {
+    ai_behaviors().read().unwrap_or_else(|poisoned| poisoned.into_inner()).get(behavior_id).cloned()
+}
+
+impl GameState {
+    /// Lets the behavior of the given id decide on the action of an NPC.
+    ///
+    /// # Errors
+    /// * [DataError::MissingAiBehavior] if no behavior is registered under the id.
+    /// * Errors of the behavior itself.
+    pub fn run_ai_behavior(
+        &mut self,
+        behavior_id: &str,
+        npc_id: EntityId,
+    ) -> Result<NpcActionKind, GameError> {
+        let behavior = get_ai_behavior(behavior_id)
+            .ok_or_else(|| DataError::MissingAiBehavior(behavior_id.to_string()))?;
+        behavior.choose_action(self, npc_id)
+    }
+}
+
+/// NPCs that have not spotted the player walk around at random. Animals walk towards food they smell and wait next to it until they eat it.
+pub struct Wandering;
+
+impl AiBehavior for Wandering {
+    fn choose_action(
+        &self,
+        game: &mut GameState,
+        npc_id: EntityId,
+    ) -> Result<NpcActionKind, GameError> {
+        let npc = game.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_pos = npc.pos();
+
+        let action = if let Some((_, food_pos)) = game.smelled_food(npc_id) {
+            match game.next_step_toward(MovementProfile::MONSTER, npc_pos, food_pos) {
+                Some(next_step) if npc_pos.distance_squared_from(food_pos) > 2 => {
+                    NpcActionKind::Move(next_step)
+                }
+                _ => NpcActionKind::Wait,
+            }
+        } else {
+            NpcActionKind::Move(Direction::random(&mut game.rng))
+        };
+        Ok(action)
+    }
+}
+
+/// NPCs chase the player and attack them once they are next to them.
+///
+/// Hunters (see [NpcAiState::Hunting]) that cannot find a path still walk in the general direction of the player.
+pub struct Aggressive;
+
+impl AiBehavior for Aggressive {
+    fn choose_action(
+        &self,
+        game: &mut GameState,
+        npc_id: EntityId,
+    ) -> Result<NpcActionKind, GameError> {
+        let npc = game.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_pos = npc.pos();
+        let footprint = npc.footprint();
+        let hunting = matches!(npc.ai_state, NpcAiState::Hunting);
+        let player_pos = game.player.character.pos();
+
+        let action = if footprint.is_adjacent_to(player_pos) {
+            NpcActionKind::Attack
+        } else if let Some(next_step) =
+            game.flanking_step(npc_id).or_else(|| game.next_step_toward_player(footprint))
+        {
+            NpcActionKind::Move(next_step)
+        } else if hunting {
+            // The path is too long to find, but hunters know roughly where the player is
+            NpcActionKind::Move(direction_toward(npc_pos, player_pos))
+        } else {
+            NpcActionKind::Move(Direction::random(&mut game.rng))
+        };
+        Ok(action)
+    }
+}
+
+/// NPCs fight like [Aggressive] ones until they are down to half of their hit points or frightened. Then they run away from the player
+/// and only fight back when they are cornered.
+pub struct Fleeing;
+
+impl AiBehavior for Fleeing {
+    fn choose_action(
+        &self,
+        game: &mut GameState,
+        npc_id: EntityId,
+    ) -> Result<NpcActionKind, GameError> {
+        let npc = game.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        if !npc.crowd_control.is_active(CrowdControl::Feared)
+            && npc.stats.base.hp_current * 2 > npc.stats.base.hp_max
+        {
+            return Aggressive.choose_action(game, npc_id);
+        }
+        Ok(run_away(game, npc))
+    }
+}
+
+/// Invisible NPCs sneak up on the player and attack while they cannot be seen. Once an attack gave them away
+/// (see [REVEAL_TURNS](crate::core::invisibility::REVEAL_TURNS)), they back off like [Fleeing] ones until they fade from sight again.
+pub struct Stalking;
+
+impl AiBehavior for Stalking {
+    fn choose_action(
+        &self,
+        game: &mut GameState,
+        npc_id: EntityId,
+    ) -> Result<NpcActionKind, GameError> {
+        let npc = game.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        if npc.revealed_turns == 0 {
+            return Aggressive.choose_action(game, npc_id);
+        }
+        Ok(run_away(game, npc))
+    }
+}
+
+/// Step that takes the NPC further away from the player. NPCs that are cornered attack instead.
+fn run_away(game: &GameState, npc: &Npc) -> NpcActionKind {
+    let npc_pos = npc.pos();
+    let footprint = npc.footprint();
+    let player_pos = game.player.character.pos();
+    let distance = npc_pos.distance_squared_from(player_pos);
+
+    let escape = [Direction::Up, Direction::Right, Direction::Down, Direction::Left]
+        .into_iter()
+        .map(|direction| (direction, npc_pos.get_adjacent(direction)))
+        .filter(|(_, point)| {
+            game.current_world().get_tile(*point).tile_type.is_walkable()
+                && game.current_level().is_available(*point)
+        })
+        .map(|(direction, point)| (direction, point.distance_squared_from(player_pos)))
+        .filter(|(_, escape_distance)| *escape_distance > distance)
+        .max_by_key(|(_, escape_distance)| *escape_distance);
+
+    match escape {
+        Some((direction, _)) => NpcActionKind::Move(direction),
+        None if footprint.is_adjacent_to(player_pos) => NpcActionKind::Attack,
+        None => NpcActionKind::Wait,
+    }
+}
+
+/// Direction of the longer axis between two points. Used to walk towards a point without knowing a path.
+pub(crate) fn direction_toward(from: Point, to: Point) -> Direction {
+    let (dx, dy) = (to.x as isize - from.x as isize, to.y as isize - from.y as isize);
+    if dx.abs() >= dy.abs() {
+        if dx > 0 { Direction::Right } else { Direction::Left }
+    } else if dy > 0 {
+        Direction::Down
+    } else {
+        Direction::Up
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::errors_results::{FailReason, GameOutcome};
+    use crate::world::{level::Level, tiles::TileType, worldspace::Room};
+
+    struct Sleeping;
+
+    impl AiBehavior for Sleeping {
+        fn choose_action(
+            &self,
+            _: &mut GameState,
+            _: EntityId,
+        ) -> Result<NpcActionKind, GameError> {
+            Ok(NpcActionKind::Wait)
+        }
+    }
+
+    #[test]
+    fn behaviors_are_looked_up_by_id() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        let npc = game.create_npc("goblin".into(), Point::new(11, 10)).unwrap();
+        let npc_id = npc.id();
+        game.current_level_mut().spawn_npc(npc).unwrap();
+
+        assert!(matches!(
+            game.run_ai_behavior(FLEEING_BEHAVIOR, npc_id),
+            Ok(NpcActionKind::Attack)
+        ));
+
+        // Badly hurt, the goblin runs away
+        game.current_level_mut().get_npc_mut(npc_id).unwrap().stats.base.hp_current = 1;
+        assert!(matches!(
+            game.run_ai_behavior(FLEEING_BEHAVIOR, npc_id),
+            Ok(NpcActionKind::Move(Direction::Right))
+        ));
+
+        assert!(game.run_ai_behavior("sleeping", npc_id).is_err());
+        register_ai_behavior("sleeping", Sleeping);
+        assert!(matches!(game.run_ai_behavior("sleeping", npc_id), Ok(NpcActionKind::Wait)));
+    }
+
+    #[test]
+    fn large_monsters_need_room_for_their_footprint() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(20, 9);
+
+        let ogre = game.create_npc("ogre".into(), Point::new(15, 8)).unwrap();
+        let ogre_id = ogre.id();
+        game.current_level_mut().spawn_npc(ogre).unwrap();
+
+        // All four tiles belong to the ogre
+        assert_eq!(game.current_level().get_npc_at(Point::new(16, 9)), Some(ogre_id));
+        assert!(!game.current_level().is_available(Point::new(15, 9)));
+
+        assert!(matches!(
+            game.run_ai_behavior(AGGRESSIVE_BEHAVIOR, ogre_id),
+            Ok(NpcActionKind::Move(Direction::Right))
+        ));
+
+        // A wall in front of the lower half blocks the ogre
+        game.current_world_mut().get_tile_mut(Point::new(17, 9)).tile_type = TileType::Wall;
+        assert!(matches!(
+            game.move_npc(ogre_id, 1, 0),
+            Ok(GameOutcome::Fail(FailReason::TileNotWalkable(_)))
+        ));
+
+        // Next to any of its tiles, it attacks
+        game.player.character.base.pos = Point::new(16, 10);
+        assert!(matches!(
+            game.run_ai_behavior(AGGRESSIVE_BEHAVIOR, ogre_id),
+            Ok(NpcActionKind::Attack)
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    ai::{
+        movement_profile::MovementProfile,
+        npc_ai::{AGGRO_RADIUS, NpcActionKind},
+    },
+    core::{
+        entity_logic::{Disposition, Entity, EntityId},
+        game::GameState,
+    },
+    util::errors_results::{EngineError, GameError},
+};
+
+/// Companions with nothing to fight stay within this distance (squared) of the player.
+const FOLLOW_DISTANCE_SQUARED: usize = 4;
+
+impl GameState {
+    /// Decides on a [NpcActionKind] for a companion.
+    ///
+    /// The companion attacks the closest hostile NPC within [AGGRO_RADIUS] or walks towards it.
+    /// If there is none, it follows the player.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the companion is no longer in the Level data structure.
+    pub fn companion_choose_action(
+        &mut self,
+        npc_id: EntityId,
+    ) -> Result<NpcActionKind, GameError> {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_pos = npc.pos();
+        let player_pos = self.player.character.pos();
+
+        let target = self
+            .current_level()
+            .npcs
+            .iter()
+            .filter(|other| {
+                other.disposition == Disposition::Hostile
+                    && other.pos().distance_squared_from(npc_pos) <= AGGRO_RADIUS * AGGRO_RADIUS
+            })
+            .min_by_key(|other| (other.pos().distance_squared_from(npc_pos), other.id()))
+            .map(|other| (other.id(), other.pos()));
+
+        if let Some((target_id, target_pos)) = target {
+            if target_pos.distance_squared_from(npc_pos) == 1 {
+                return Ok(NpcActionKind::AttackNpc(target_id));
+            }
+            if let Some(next_step) =
+                self.next_step_toward(MovementProfile::COMPANION, npc_pos, target_pos)
+            {
+                return Ok(NpcActionKind::Move(next_step));
+            }
+        }
+
+        if npc_pos.distance_squared_from(player_pos) > FOLLOW_DISTANCE_SQUARED
+            && let Some(next_step) =
+                self.next_step_toward(MovementProfile::COMPANION, npc_pos, player_pos)
+        {
+            return Ok(NpcActionKind::Move(next_step));
+        }
+
+        Ok(NpcActionKind::Wait)
+    }
+}
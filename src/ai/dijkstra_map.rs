@@ -0,0 +1,188 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem;
+
+use crate::{
+    ai::movement_profile::MovementProfile,
+    core::{entity_logic::Entity, game::GameState},
+    world::{
+        coordinate_system::{Direction, Footprint, Point},
+        worldspace::World,
+    },
+};
+
+/// Distance of every tile of the level to a goal, weighed by a [MovementProfile].
+///
+/// Computed once per round with the player as the goal (see [GameState::refresh_player_map]). Any number of NPCs can then
+/// walk towards the player by stepping onto the neighboring tile with the lowest distance, instead of each running its own A* search.
+///
+/// The buffers are kept between computations (see [ScratchBuffers](crate::core::scratch::ScratchBuffers)), so they don't have to be allocated again.
+#[derive(Default)]
+pub struct DijkstraMap {
+    width: usize,
+    goal: Option<Point>,
+
+    // Cost of the cheapest path from each tile to the goal, indexed like the tiles of the world. `usize::MAX` if the goal can't be reached.
+    distances: Vec<usize>,
+
+    // Tiles that still need to be visited, by their distance and index.
+    open_list: BinaryHeap<Reverse<(usize, usize)>>,
+}
+
+impl DijkstraMap {
+    /// Computes the distances of all tiles of the world to the goal.
+    pub fn compute(&mut self, world: &World, goal: Point, profile: MovementProfile) {
+        self.width = world.width;
+        self.goal = Some(goal);
+        self.distances.clear();
+        self.distances.resize(world.width * world.height, usize::MAX);
+        self.open_list.clear();
+
+        let goal_index = goal.y * world.width + goal.x;
+        self.distances[goal_index] = 0;
+        self.open_list.push(Reverse((0, goal_index)));
+
+        while let Some(Reverse((distance, index))) = self.open_list.pop() {
+            if distance > self.distances[index] {
+                continue; // Already visited on a cheaper path
+            }
+            let point = Point::new(index % world.width, index / world.width);
+
+            // Stepping onto this tile from a neighbor costs what the tile costs. The goal can always be stepped onto (e.g. to attack).
+            let step_cost = profile.tile_cost(world.get_tile(point).tile_type).unwrap_or(1);
+
+            for direction in [Direction::Up, Direction::Right, Direction::Down, Direction::Left] {
+                let neighbor = point.get_adjacent(direction);
+                if !world.is_in_bounds(neighbor.x as isize, neighbor.y as isize) {
+                    continue;
+                }
+                // Tiles the entity doesn't walk onto are never on a path
+                if profile.tile_cost(world.get_tile(neighbor).tile_type).is_none() {
+                    continue;
+                }
+                let neighbor_index = neighbor.y * world.width + neighbor.x;
+                let neighbor_distance = distance + step_cost;
+                if neighbor_distance < self.distances[neighbor_index] {
+                    self.distances[neighbor_index] = neighbor_distance;
+                    self.open_list.push(Reverse((neighbor_distance, neighbor_index)));
+                }
+            }
+        }
+    }
+
+    /// The goal the distances were computed for, if they were computed at all.
+    pub fn goal(&self) -> Option<Point> {
+        self.goal
+    }
+
+    /// Cost of the cheapest path from the point to the goal, if there is one.
+    pub fn distance(&self, point: Point) -> Option<usize> {
+        self.distances
+            .get(point.y * self.width + point.x)
+            .copied()
+            .filter(|distance| *distance != usize::MAX)
+    }
+
+    /// The step that brings an entity at the point closer to the goal, skipping neighboring tiles that are not free.
+    ///
+    /// # Returns
+    /// * [None] if the goal can't be reached from the point, or every step closer is blocked.
+    pub fn descend(&self, from: Point, is_free: impl Fn(Point) -> bool) -> Option<Direction> {
+        let current = self.distance(from)?;
+
+        [Direction::Up, Direction::Right, Direction::Down, Direction::Left]
+            .into_iter()
+            .filter_map(|direction| {
+                let neighbor = from.get_adjacent(direction);
+                let distance = self.distance(neighbor)?;
+                (distance < current && is_free(neighbor)).then_some((direction, distance))
+            })
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(direction, _)| direction)
+    }
+}
+
+impl GameState {
+    /// Recomputes the distance map towards the player (see [DijkstraMap]). Called once per round, before the NPCs take their turns.
+    pub fn refresh_player_map(&mut self) {
+        let mut player_map = mem::take(&mut self.scratch.player_map);
+        player_map.compute(
+            self.current_world(),
+            self.player.character.pos(),
+            MovementProfile::MONSTER,
+        );
+        self.scratch.player_map = player_map;
+    }
+
+    /// The next step of a monster covering the footprint towards the player.
+    ///
+    /// Monsters covering a single tile descend the distance map of the player. Large monsters, monsters blocked by others and
+    /// monsters acting after the player moved since the map was computed (e.g. when knocked back) search their own path with A* instead.
+    pub fn next_step_toward_player(&mut self, footprint: Footprint) -> Option<Direction> {
+        let player_pos = self.player.character.pos();
+        let player_map = &self.scratch.player_map;
+
+        if footprint.size == 1 && player_map.goal() == Some(player_pos) {
+            let level = self.current_level();
+            let step = player_map.descend(footprint.origin, |point| {
+                point == player_pos || level.get_npc_at(point).is_none()
+            });
+            if step.is_some() {
+                return step;
+            }
+        }
+
+        self.next_step_for(MovementProfile::MONSTER, footprint, player_pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, tiles::TileType, worldspace::Room};
+
+    #[test]
+    fn npcs_descend_the_player_map_around_obstacles() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        // A wall between the NPC and the player, with a gap at the bottom
+        for y in 5..13 {
+            level.world.get_tile_mut(Point::new(12, y)).tile_type = TileType::Wall;
+        }
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(16, 8);
+        game.refresh_player_map();
+
+        let map = &game.scratch.player_map;
+        assert_eq!(map.distance(Point::new(16, 8)), Some(0));
+        assert_eq!(map.distance(Point::new(12, 8)), None);
+        assert!(map.distance(Point::new(10, 8)) > map.distance(Point::new(10, 12)));
+
+        // Each step brings the NPC closer. If another NPC stands in the way, it takes another step that does.
+        let start = Point::new(10, 11);
+        let closer = |game: &GameState, step: Direction| {
+            game.scratch.player_map.distance(start.get_adjacent(step))
+                < game.scratch.player_map.distance(start)
+        };
+        let step = game.next_step_toward_player(Footprint::single(start)).unwrap();
+        assert!(closer(&game, step));
+
+        let goblin = game.create_npc("goblin".into(), start.get_adjacent(step)).unwrap();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+        let detour = game.next_step_toward_player(Footprint::single(start)).unwrap();
+        assert!(detour != step && closer(&game, detour));
+
+        // Once the player moved, the stale map is not used
+        game.player.character.base.pos = Point::new(6, 6);
+        let goblin = game.current_level().npcs[0].pos();
+        assert_eq!(
+            game.next_step_toward_player(Footprint::single(goblin)),
+            game.next_step_for(
+                MovementProfile::MONSTER,
+                Footprint::single(goblin),
+                Point::new(6, 6)
+            )
+        );
+    }
+}
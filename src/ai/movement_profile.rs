@@ -0,0 +1,131 @@
+use crate::{
+    ai::traffic::CONGESTION_COST,
+    core::game::GameState,
+    world::{
+        coordinate_system::{Footprint, Point},
+        tiles::{Collision, DoorType, TileType},
+    },
+};
+
+/// How an entity weighs the world when it looks for a path. Produces the cost function of [a_star](crate::ai::pathfinding::a_star).
+///
+/// Tiles cost what their [Collision::movement_cost] says, unless the profile says otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MovementProfile {
+    /// Whether the entity never walks over spike traps, instead of only avoiding them when there is a way around.
+    pub avoids_traps: bool,
+    /// Cost of opening and walking through a closed door. `None` if the entity cannot open doors.
+    pub door_cost: Option<usize>,
+    /// Extra cost of walking through a tile occupied by an NPC. `None` if NPCs block the way.
+    pub congestion_cost: Option<usize>,
+    /// Whether the entity only walks over explored tiles, because it does not know the rest of the level.
+    pub explored_only: bool,
+}
+
+impl MovementProfile {
+    /// Monsters walk over traps if the way around is much longer and take detours around crowds, but cannot open doors.
+    pub const MONSTER: MovementProfile = MovementProfile {
+        avoids_traps: false,
+        door_cost: None,
+        congestion_cost: Some(CONGESTION_COST),
+        explored_only: false,
+    };
+
+    /// Companions move like monsters, but never step on traps the player would have to watch them walk into.
+    pub const COMPANION: MovementProfile =
+        MovementProfile { avoids_traps: true, ..MovementProfile::MONSTER };
+
+    /// The player opens doors on their way, but never steps on traps, does not walk through NPCs and only travels
+    /// over tiles they have explored.
+    pub const PLAYER: MovementProfile = MovementProfile {
+        avoids_traps: true,
+        door_cost: Some(2),
+        congestion_cost: None,
+        explored_only: true,
+    };
+
+    /// Cost of walking onto a tile of the given type. `None` if the entity does not walk there.
+    pub fn tile_cost(&self, tile_type: TileType) -> Option<usize> {
+        match tile_type {
+            TileType::SpikeTrap if self.avoids_traps => None,
+            TileType::Door(DoorType::Closed) => self.door_cost,
+            tile_type => tile_type.movement_cost(),
+        }
+    }
+
+    /// Cost function for an entity covering the footprint that walks towards the goal. Every step has to leave room
+    /// for all of its tiles, so large monsters don't squeeze through narrow corridors.
+    pub fn cost_fn<'a>(
+        &'a self,
+        game: &'a GameState,
+        footprint: Footprint,
+        goal: Point,
+    ) -> impl FnMut(Point) -> Option<usize> + 'a {
+        move |origin| {
+            if origin == goal {
+                return self.known_tile_cost(game, goal);
+            }
+
+            let mut cost = 0;
+            let mut congested = false;
+            for point in footprint.moved_to(origin).points() {
+                if !game.current_world().is_in_bounds(point.x as isize, point.y as isize) {
+                    return None;
+                }
+                cost = cost.max(self.known_tile_cost(game, point)?);
+
+                // The goal may be occupied by the NPC that is being chased.
+                congested |=
+                    !footprint.contains(point) && game.current_level().get_npc_at(point).is_some();
+            }
+
+            if congested {
+                // Other NPCs are only a detour for most, so crowds don't block the way for good.
+                return self.congestion_cost.map(|congestion_cost| cost + congestion_cost);
+            }
+
+            Some(cost)
+        }
+    }
+
+    /// Cost of walking onto the tile at the point, as far as the entity knows about it.
+    fn known_tile_cost(&self, game: &GameState, point: Point) -> Option<usize> {
+        let tile = game.current_world().get_tile(point);
+        if self.explored_only && !tile.explored {
+            return None;
+        }
+        self.tile_cost(tile.tile_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{coordinate_system::Direction, level::Level, worldspace::Room};
+
+    #[test]
+    fn profiles_weigh_doors_and_traps_differently() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        // A wall splits the room, the only way through is over the trap
+        for y in 5..20 {
+            level.world.get_tile_mut(Point::new(49, y)).tile_type = TileType::Wall;
+        }
+        level.world.get_tile_mut(Point::new(49, 10)).tile_type = TileType::SpikeTrap;
+        game.levels.insert(0, level);
+
+        assert_eq!(MovementProfile::MONSTER.tile_cost(TileType::Door(DoorType::Closed)), None);
+        assert_eq!(MovementProfile::PLAYER.tile_cost(TileType::Door(DoorType::Closed)), Some(2));
+        assert!(MovementProfile::MONSTER.tile_cost(TileType::SpikeTrap).is_some());
+        assert_eq!(MovementProfile::COMPANION.tile_cost(TileType::SpikeTrap), None);
+
+        // Without a way around, a monster walks over the trap, a companion stays behind
+        let (start, goal) = (Point::new(50, 10), Point::new(48, 10));
+        assert_eq!(
+            game.next_step_toward(MovementProfile::MONSTER, start, goal),
+            Some(Direction::Left)
+        );
+        assert_eq!(game.next_step_toward(MovementProfile::COMPANION, start, goal), None);
+    }
+}
@@ -1,11 +1,21 @@
+use std::mem;
+
+use rand::Rng;
+
 use crate::{
+    ai::behavior::{AGGRESSIVE_BEHAVIOR, FLEEING_BEHAVIOR, WANDERING_BEHAVIOR},
     core::{
-        entity_logic::{Entity, EntityId, Npc},
+        crowd_control::{CONFUSED_STUMBLE_CHANCE, CrowdControl},
+        entity_logic::{Disposition, Entity, EntityId, Npc},
         game::GameState,
+        shop::SHOPKEEPER_NPCS,
+        town::HEALER_NPCS,
     },
+    data::npc_defs::npc_defs,
     util::errors_results::{EngineError, GameError, GameOutcome, GameResult},
     world::{
-        coordinate_system::{Direction, Point, PointVector},
+        coordinate_system::{Direction, Point},
+        los::has_line_of_sight,
         tiles::Collision,
     },
 };
@@ -15,7 +25,7 @@ pub const AGGRO_RADIUS: usize = 6;
 /// State tracked for each NPC. This dictates the actions the NPC will take.
 #[derive(Default, Clone)]
 pub enum NpcAiState {
-    /// Default state, doesn't occur normally. Will result in [NpcActionKind::Wait].
+    /// Default state, only used by NPCs that stay in place (like shopkeepers). Will result in [NpcActionKind::Wait].
     #[default]
     Inactive,
 
@@ -24,6 +34,12 @@ pub enum NpcAiState {
 
     /// The NPC spotted the player. It will chase them and attack them.
     Aggressive,
+
+    /// The NPC hunts the player across the whole level, even if it cannot see them. Spawned by floor affixes (see [FloorAffixDef::hunters](crate::data::floor_affixes::FloorAffixDef::hunters)).
+    Hunting,
+
+    /// The NPC was tamed. It follows the player and attacks hostile NPCs (see [GameState::companion_choose_action]).
+    Companion,
 }
 
 pub enum NpcActionKind {
@@ -35,6 +51,9 @@ pub enum NpcActionKind {
 
     /// The NPC attacks the player.
     Attack,
+
+    /// The NPC attacks another NPC.
+    AttackNpc(EntityId),
 }
 
 impl GameState {
@@ -44,6 +63,14 @@ impl GameState {
     /// * [EngineError::NpcNotFound] if the NPC is no longer in the Level data structure.
     /// * Ok([GameOutcome::Success]) if the action was successful.
     pub fn npc_take_turn(&mut self, npc_id: EntityId) -> GameResult {
+        // Distracted NPCs skip their turn
+        let npc =
+            self.current_level_mut().get_npc_mut(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        if npc.distracted_turns > 0 {
+            npc.distracted_turns -= 1;
+            return Ok(GameOutcome::Success);
+        }
+
         // Update NpcAiState
         self.npc_refresh_ai_state(npc_id)?;
 
@@ -54,12 +81,14 @@ impl GameState {
         match npc_action {
             NpcActionKind::Wait => {}
             NpcActionKind::Move(direction) => {
-                let delta = PointVector::from(direction);
-                let _ = self.move_npc(npc_id, delta.x, delta.y);
+                let _ = self.npc_move_in_traffic(npc_id, direction);
             }
             NpcActionKind::Attack => {
                 let _ = self.npc_attack_player(npc_id);
             }
+            NpcActionKind::AttackNpc(target_id) => {
+                let _ = self.npc_attack_npc(npc_id, target_id);
+            }
         }
 
         Ok(GameOutcome::Success)
@@ -74,36 +103,35 @@ impl GameState {
     /// * [EngineError::NpcNotFound] if the NPC is no longer in the Level data structure.
     fn npc_choose_action(&mut self, npc_id: EntityId) -> Result<NpcActionKind, GameError> {
         let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
-        // let melee_area = self.current_world().get_points_in_radius(npc.pos(), 1);
-        let npc_pos = npc.pos();
-        let melee_area: Vec<Point> = vec![
-            npc_pos + Direction::Up,
-            npc_pos + Direction::Right,
-            npc_pos + Direction::Down,
-            npc_pos + Direction::Left,
-        ];
-
-        let action = match npc.ai_state {
-            NpcAiState::Inactive => NpcActionKind::Wait,
+        let behavior_id =
+            npc_defs().get(&npc.def_id).map_or(AGGRESSIVE_BEHAVIOR, |def| def.behavior);
 
-            NpcAiState::Wandering => {
-                let random_direction = Direction::random(&mut self.rng);
-                NpcActionKind::Move(random_direction)
-            }
+        let frightened = npc.crowd_control.is_active(CrowdControl::Feared);
+        let confused = npc.crowd_control.is_active(CrowdControl::Confused);
+        let ai_state = npc.ai_state.clone();
+        let stumbles = confused
+            && !matches!(ai_state, NpcAiState::Inactive)
+            && self.rng.random_range(0..100) < CONFUSED_STUMBLE_CHANCE;
 
-            NpcAiState::Aggressive => {
-                if melee_area.contains(&self.player.character.pos()) {
-                    NpcActionKind::Attack
-                } else if let Some(next_step) =
-                    self.next_step_toward(npc.pos(), self.player.character.pos())
-                {
-                    NpcActionKind::Move(next_step)
-                } else {
-                    let random_direction = Direction::random(&mut self.rng);
-                    NpcActionKind::Move(random_direction)
-                }
+        let action = match ai_state {
+            NpcAiState::Inactive => NpcActionKind::Wait,
+            // Confused NPCs stumble around and hit whoever is in their way
+            _ if stumbles => self.npc_stumble(npc_id)?,
+            // Frightened NPCs run away, no matter how they usually fight
+            NpcAiState::Aggressive | NpcAiState::Hunting if frightened => {
+                self.run_ai_behavior(FLEEING_BEHAVIOR, npc_id)?
             }
+            NpcAiState::Wandering => self.run_ai_behavior(WANDERING_BEHAVIOR, npc_id)?,
+            NpcAiState::Aggressive | NpcAiState::Hunting => {
+                self.run_ai_behavior(behavior_id, npc_id)?
+            }
+            NpcAiState::Companion => self.companion_choose_action(npc_id)?,
         };
+
+        if let Some(npc) = self.current_level_mut().get_npc_mut(npc_id) {
+            npc.crowd_control.tick();
+            npc.revealed_turns = npc.revealed_turns.saturating_sub(1);
+        }
         Ok(action)
     }
 
@@ -115,26 +143,63 @@ impl GameState {
     /// * [EngineError::NpcNotFound] if the NPC is no longer in the Level data structure.
     /// * [Ok] if the ai state was successfully updated.
     fn npc_refresh_ai_state(&mut self, npc_id: EntityId) -> Result<(), GameError> {
-        let npc_pos: Point = {
+        let (npc_pos, disposition, alert) = {
             let npc: &Npc =
                 self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
-            npc.pos()
+            let alert = matches!(npc.ai_state, NpcAiState::Aggressive | NpcAiState::Hunting);
+            (npc.pos(), npc.disposition, alert)
         };
 
         let player_pos: Point = self.player.character.pos();
-        let detectable_area: Vec<Point> = self.current_world().get_points_in_radius(npc_pos, 6);
-
         let player_reachable = self.current_world().get_tile(player_pos).tile_type.is_walkable();
-        // Only aggressive if player in detection radius and player is on a reachable tile (e.g. not inside walls)
-        let should_be_agressive = detectable_area.contains(&player_pos) && player_reachable;
+        // Only aggressive if hostile, the player is on a reachable tile (e.g. not inside walls) and the NPC or its pack sees them.
+        // A stealthy player may slip past NPCs that have not noticed them yet.
+        let should_be_agressive = disposition == Disposition::Hostile
+            && player_reachable
+            && (self.npc_sees_player(npc_pos) || self.packmate_sees_player(npc_id))
+            && (alert || !self.stealth_check());
 
         let npc: &mut Npc =
             self.current_level_mut().get_npc_mut(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
 
+        let spotted_player = should_be_agressive && !alert;
+
         // If the detection radius contains the player AND the player position is reachable.
-        npc.ai_state =
-            if should_be_agressive { NpcAiState::Aggressive } else { NpcAiState::Wandering };
+        // Shopkeepers stay behind their counter, healers stay in their house. Hunters never give up.
+        npc.ai_state = if disposition == Disposition::Companion {
+            NpcAiState::Companion
+        } else if matches!(npc.ai_state, NpcAiState::Hunting) {
+            NpcAiState::Hunting
+        } else if should_be_agressive {
+            NpcAiState::Aggressive
+        } else if SHOPKEEPER_NPCS.contains(&npc.def_id.as_str())
+            || HEALER_NPCS.contains(&npc.def_id.as_str())
+        {
+            NpcAiState::Inactive
+        } else {
+            NpcAiState::Wandering
+        };
+
+        if spotted_player {
+            self.try_frog_distraction(npc_id)?;
+        }
 
         Ok(())
     }
+
+    /// Whether an NPC standing at the point sees the player, i.e. the player is in the detection radius and not hidden behind walls.
+    pub(crate) fn npc_sees_player(&mut self, npc_pos: Point) -> bool {
+        let player_pos: Point = self.player.character.pos();
+        let mut detectable_area: Vec<Point> = mem::take(&mut self.scratch.points);
+        self.current_world().points_in_radius_into(
+            npc_pos,
+            AGGRO_RADIUS as isize,
+            &mut detectable_area,
+        );
+
+        let sees_player = detectable_area.contains(&player_pos)
+            && has_line_of_sight(npc_pos, player_pos, self.current_world());
+        self.scratch.points = detectable_area;
+        sees_player
+    }
 }
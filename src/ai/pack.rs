@@ -0,0 +1,231 @@
+use std::ops::Range;
+
+use crate::{
+    ai::movement_profile::MovementProfile,
+    core::{
+        crowd_control::{CrowdControl, CrowdControlTarget},
+        entity_logic::{Disposition, Entity, EntityId, Npc},
+        game::GameState,
+    },
+    render::style::Modifier,
+    util::{errors_results::GameError, text_log::LogData},
+    world::{
+        coordinate_system::{Direction, Point},
+        level::Level,
+    },
+};
+
+/// NPCs that are spawned in packs, with the name of their pack leader.
+pub const PACK_NPCS: [(&str, &str); 2] = [("wolf", "Alpha Wolf"), ("bandit", "Bandit Chief")];
+
+/// Range the number of NPCs in a pack (including the leader) is picked from.
+pub const PACK_SIZE: Range<usize> = 2..5;
+
+/// How far from their leader the members of a pack are spawned.
+const PACK_SPREAD: isize = 2;
+
+/// Number of turns the members of a pack are frightened after their leader died.
+pub const MORALE_BREAK_TURNS: u8 = 8;
+
+/// Whether NPCs of the definition are spawned in packs (see [PACK_NPCS]).
+pub fn is_pack_npc(def_id: &str) -> bool {
+    PACK_NPCS.iter().any(|(pack_npc, _)| *pack_npc == def_id)
+}
+
+/// Free tiles for a pack of the given size, starting with the leader's and followed by the closest ones around it.
+///
+/// There may be fewer tiles than members if the leader stands in a cramped spot.
+pub fn pack_positions(level: &Level, leader_pos: Point, size: usize) -> Vec<Point> {
+    let mut positions = vec![leader_pos];
+
+    for distance in 1..=PACK_SPREAD {
+        for dy in -distance..=distance {
+            for dx in -distance..=distance {
+                // Only the ring at this distance, the inner ones were visited already
+                if dx.abs() != distance && dy.abs() != distance {
+                    continue;
+                }
+                let (x, y) = (leader_pos.x as isize + dx, leader_pos.y as isize + dy);
+                if positions.len() >= size || !level.world.is_in_bounds(x, y) {
+                    continue;
+                }
+                let point = Point::new(x as usize, y as usize);
+                if level.is_available(point) {
+                    positions.push(point);
+                }
+            }
+        }
+    }
+
+    positions
+}
+
+impl GameState {
+    /// Creates a pack of NPCs of the definition on the given positions. The NPC on the first position leads the pack.
+    ///
+    /// # Errors
+    /// * [DataError::MissingNpcDefinition](crate::util::errors_results::DataError::MissingNpcDefinition) if the NPC definition does not exist.
+    pub fn create_pack(
+        &mut self,
+        def_id: &str,
+        positions: &[Point],
+    ) -> Result<Vec<Npc>, GameError> {
+        let mut pack = Vec::new();
+        for &point in positions {
+            let mut npc = self.create_npc(def_id.to_string(), point)?;
+            let leader_id = pack.first().map_or(npc.id(), |leader: &Npc| leader.id());
+            npc.pack = Some(leader_id);
+
+            if leader_id == npc.id() {
+                if let Some((_, leader_name)) =
+                    PACK_NPCS.iter().find(|(pack_npc, _)| *pack_npc == def_id)
+                {
+                    npc.base.name = leader_name.to_string();
+                }
+                npc.base.style = npc.base.style.add_modifier(Modifier::BOLD);
+            }
+            pack.push(npc);
+        }
+        Ok(pack)
+    }
+
+    /// Whether another member of the NPC's pack sees the player. Packs share their aggro, so one member spotting the player alerts all of them.
+    pub(crate) fn packmate_sees_player(&mut self, npc_id: EntityId) -> bool {
+        let Some(pack) = self.current_level().get_npc(npc_id).and_then(|npc| npc.pack) else {
+            return false;
+        };
+
+        // Indexed, so the members' sight can be checked without collecting them first
+        for index in 0..self.current_level().npcs.len() {
+            let member = &self.current_level().npcs[index];
+            if member.id() == npc_id || member.pack != Some(pack) {
+                continue;
+            }
+            let member_pos = member.pos();
+            if self.npc_sees_player(member_pos) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Turns a neutral NPC hostile, together with the neutral members of its pack.
+    pub(crate) fn provoke_pack(&mut self, npc_id: EntityId) {
+        let pack = self.current_level().get_npc(npc_id).and_then(|npc| npc.pack);
+
+        for npc in self.current_level_mut().npcs.iter_mut() {
+            let provoked = npc.id() == npc_id || (pack.is_some() && npc.pack == pack);
+            if provoked && npc.disposition == Disposition::Neutral {
+                npc.disposition = Disposition::Hostile;
+            }
+        }
+    }
+
+    /// Free tile next to the player that a member of a pack heads for, so the pack surrounds the player instead of queueing up behind each other.
+    ///
+    /// # Returns
+    /// * [None] if the NPC is not in a pack, covers more than one tile or every tile around the player is taken.
+    pub(crate) fn flanking_tile(&self, npc_id: EntityId) -> Option<Point> {
+        let npc = self.current_level().get_npc(npc_id)?;
+        npc.pack?;
+        if npc.size > 1 {
+            return None;
+        }
+        let npc_pos = npc.pos();
+        let player_pos = self.player.character.pos();
+
+        [Direction::Up, Direction::Right, Direction::Down, Direction::Left]
+            .into_iter()
+            .map(|direction| player_pos.get_adjacent(direction))
+            .filter(|point| self.current_level().is_available(*point))
+            .min_by_key(|point| point.distance_squared_from(npc_pos))
+    }
+
+    /// Step of a member of a pack towards its [flanking tile](GameState::flanking_tile).
+    pub(crate) fn flanking_step(&mut self, npc_id: EntityId) -> Option<Direction> {
+        let target = self.flanking_tile(npc_id)?;
+        let npc_pos = self.current_level().get_npc(npc_id)?.pos();
+        self.next_step_toward(MovementProfile::MONSTER, npc_pos, target)
+    }
+
+    /// Frightens the remaining members of a pack whose leader died for [MORALE_BREAK_TURNS] turns.
+    ///
+    /// # Errors
+    /// * Errors of [GameState::apply_crowd_control].
+    pub(crate) fn break_pack_morale(&mut self, leader: &Npc) -> Result<(), GameError> {
+        if leader.pack != Some(leader.id()) {
+            return Ok(());
+        }
+
+        let members: Vec<EntityId> = self
+            .current_level()
+            .npcs
+            .iter()
+            .filter(|npc| npc.pack == leader.pack && npc.disposition != Disposition::Companion)
+            .map(|npc| npc.id())
+            .collect();
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        self.log.info(LogData::PackLeaderDied { npc_name: leader.name().to_string() });
+        for member in members {
+            self.apply_crowd_control(
+                CrowdControlTarget::Npc(member),
+                CrowdControl::Feared,
+                MORALE_BREAK_TURNS,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ai::npc_ai::NpcAiState, world::worldspace::Room};
+
+    #[test]
+    fn packs_share_aggro_flank_and_scatter_without_their_leader() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 25, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(25, 10);
+
+        let positions = pack_positions(game.current_level(), Point::new(10, 10), 3);
+        assert_eq!(positions.len(), 3);
+        let pack = game.create_pack("wolf", &positions).unwrap();
+        let ids: Vec<EntityId> = pack.iter().map(|npc| npc.id()).collect();
+        assert_eq!(pack[0].name(), "Alpha Wolf");
+        for npc in pack {
+            game.current_level_mut().spawn_npc(npc).unwrap();
+        }
+        fn npc(game: &GameState, npc_id: EntityId) -> &Npc {
+            game.current_level().get_npc(npc_id).unwrap()
+        }
+
+        // Attacking one wolf angers the whole pack
+        game.provoke_pack(ids[1]);
+        assert!(ids.iter().all(|id| npc(&game, *id).disposition == Disposition::Hostile));
+
+        // Only one wolf can see the player, but all of them go after them
+        game.current_level_mut().get_npc_mut(ids[2]).unwrap().base.pos = Point::new(20, 10);
+        game.npc_take_turn(ids[1]).unwrap();
+        assert!(matches!(npc(&game, ids[1]).ai_state, NpcAiState::Aggressive));
+
+        // The tile in front of the player is taken, so the next wolf goes around
+        game.current_level_mut().get_npc_mut(ids[2]).unwrap().base.pos = Point::new(24, 10);
+        game.current_level_mut().get_npc_mut(ids[1]).unwrap().base.pos = Point::new(21, 10);
+        let flank = game.flanking_tile(ids[1]).unwrap();
+        assert_ne!(flank, Point::new(24, 10));
+        assert_eq!(flank.distance_squared_from(Point::new(25, 10)), 1);
+
+        // Without their leader, the others lose heart
+        let leader = npc(&game, ids[0]).clone();
+        game.current_level_mut().despawn(ids[0]);
+        game.break_pack_morale(&leader).unwrap();
+        assert!(npc(&game, ids[1]).crowd_control.is_active(CrowdControl::Feared));
+        assert!(npc(&game, ids[2]).crowd_control.is_active(CrowdControl::Feared));
+    }
+}
@@ -1,9 +1,10 @@
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
+use std::mem;
 
+use crate::ai::movement_profile::MovementProfile;
 use crate::core::game::GameState;
-use crate::world::coordinate_system::{Direction, Point};
-use crate::world::tiles::Collision;
+use crate::world::coordinate_system::{Direction, Footprint, Point};
 
 // Max iterations the A* algorithm is allowed to run with.
 const MAX_ITERS: usize = 200;
@@ -56,30 +57,56 @@ fn heuristic(a: Point, b: Point) -> usize {
 }
 
 impl GameState {
-    /// Uses the A* algorithm to find the next direction to move in.
+    /// Uses the A* algorithm to find the next direction to move in, weighing the world by the [MovementProfile].
+    ///
+    /// The search reuses the buffers in [GameState::scratch], so it does not allocate once they have grown large enough.
     ///
     /// # Returns
     /// * [None] if no path could be found
     /// * Some([Direction]) for the next required step
-    pub fn next_step_toward(&self, start: Point, goal: Point) -> Option<Direction> {
-        let a_star_path: Vec<Point> = a_star(start, goal, |point| {
-            if !self.current_world().get_tile(point).tile_type.is_walkable() {
-                return None;
-            }
-            if self.current_level().get_npc_at(point).is_some() {
-                return None;
-            }
-
-            Some(1)
-        })?;
-        let next = a_star_path.get(1)?;
+    pub fn next_step_toward(
+        &mut self,
+        profile: MovementProfile,
+        start: Point,
+        goal: Point,
+    ) -> Option<Direction> {
+        self.next_step_for(profile, Footprint::single(start), goal)
+    }
 
-        let delta = *next - start;
+    /// Same as [GameState::next_step_toward], but for an entity that covers the given footprint.
+    pub fn next_step_for(
+        &mut self,
+        profile: MovementProfile,
+        footprint: Footprint,
+        goal: Point,
+    ) -> Option<Direction> {
+        let start = footprint.origin;
+        let mut scratch = mem::take(&mut self.scratch.a_star);
+        let next = a_star_in(&mut scratch, start, goal, profile.cost_fn(self, footprint, goal))
+            .and_then(|path| path.get(1).copied());
+        self.scratch.a_star = scratch;
+
+        let delta = next? - start;
 
         Direction::try_from(delta).ok()
     }
 }
 
+/// Buffers of the A* algorithm. Kept between searches (see [a_star_in]), so they don't have to be allocated again.
+#[derive(Default)]
+pub struct AStarScratch {
+    open_list: BinaryHeap<Node>,
+
+    // Best-known cost to reach given tile
+    g_score: HashMap<Point, usize>,
+
+    // Reconstructible path
+    came_from: HashMap<Point, Point>,
+
+    // Path found by the last search
+    path: Vec<Point>,
+}
+
 /// A* Algorithm to find the shortest path between two Points on the Map.
 ///
 /// Taken from [idiomatic-rust-snippets.org](https://idiomatic-rust-snippets.org/algorithms/graph/a-star.html) and adapted to our world space.
@@ -89,19 +116,35 @@ impl GameState {
 /// * start - Start point of A*.
 /// * goal - Goal point of A*.
 /// * cost - Cost Function that takes in a Point and returns its cost. The cost can either be [usize] (representing cost) or [None] (representing a forbidden Point).
-pub fn a_star<F>(start: Point, goal: Point, mut cost: F) -> Option<Vec<Point>>
+pub fn a_star<F>(start: Point, goal: Point, cost: F) -> Option<Vec<Point>>
 where
     F: FnMut(Point) -> Option<usize>,
 {
-    let mut iterations: usize = 0;
-
-    let mut open_list = BinaryHeap::new();
+    let mut scratch = AStarScratch::default();
+    a_star_in(&mut scratch, start, goal, cost)?;
+    Some(mem::take(&mut scratch.path))
+}
 
-    // Best-known cost to reach given tile
-    let mut g_score = HashMap::new();
+/// Same as [a_star], but works in the given buffers instead of allocating new ones.
+///
+/// # Returns
+/// * The path from `start` to `goal`, which lives in the buffers until the next search.
+pub fn a_star_in<F>(
+    scratch: &mut AStarScratch,
+    start: Point,
+    goal: Point,
+    mut cost: F,
+) -> Option<&[Point]>
+where
+    F: FnMut(Point) -> Option<usize>,
+{
+    let mut iterations: usize = 0;
 
-    // Reconstructible path
-    let mut came_from = HashMap::new();
+    let AStarScratch { open_list, g_score, came_from, path } = scratch;
+    open_list.clear();
+    g_score.clear();
+    came_from.clear();
+    path.clear();
 
     g_score.insert(start, 0);
 
@@ -114,7 +157,7 @@ where
         }
 
         if current.point == goal {
-            let mut path = vec![current.point];
+            path.push(current.point);
             let mut current_position = current.point;
             while let Some(&prev_position) = came_from.get(&current_position) {
                 path.push(prev_position);
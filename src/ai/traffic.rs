@@ -0,0 +1,202 @@
+use rand::Rng;
+
+use crate::{
+    ai::npc_ai::NpcAiState,
+    core::{
+        entity_logic::{Entity, EntityId, Movable},
+        game::GameState,
+    },
+    util::errors_results::{EngineError, FailReason, GameError, GameOutcome, GameResult},
+    world::coordinate_system::{Direction, PointVector},
+};
+
+/// Number of turns an NPC waits behind another NPC before it tries to get around it.
+pub const TRAFFIC_PATIENCE: u8 = 1;
+
+/// Extra cost of walking through a tile occupied by an NPC when pathfinding.
+/// NPCs take a detour around a crowd, unless the detour is much longer than waiting in line.
+pub const CONGESTION_COST: usize = 6;
+
+impl GameState {
+    /// Moves an NPC one step in the given direction and resolves traffic if another NPC stands in the way.
+    ///
+    /// An NPC that has waited for more than [TRAFFIC_PATIENCE] turns swaps places with the blocking NPC if it is a willing ally,
+    /// or otherwise sidesteps it.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is no longer in the Level data structure.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with the reason of [GameState::move_npc] if the NPC could not move at all.
+    /// * [GameOutcome::Success] if the NPC moved, swapped or sidestepped.
+    pub fn npc_move_in_traffic(&mut self, npc_id: EntityId, direction: Direction) -> GameResult {
+        let delta = PointVector::from(direction);
+        let outcome = self.move_npc(npc_id, delta.x, delta.y)?;
+
+        let blocker_id = match outcome {
+            GameOutcome::Success => None,
+            GameOutcome::Fail(FailReason::TileOccupied(point)) => {
+                self.current_level().get_npc_at(point)
+            }
+            GameOutcome::Fail(_) => return Ok(outcome),
+        };
+        let Some(blocker_id) = blocker_id else {
+            self.set_blocked_turns(npc_id, 0);
+            return Ok(outcome);
+        };
+
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let blocked_turns = npc.blocked_turns + 1;
+        self.set_blocked_turns(npc_id, blocked_turns);
+        if blocked_turns <= TRAFFIC_PATIENCE {
+            return Ok(outcome);
+        }
+
+        let resolved = if self.is_willing_ally(npc_id, blocker_id) {
+            self.swap_npcs(npc_id, blocker_id)
+        } else {
+            self.sidestep(npc_id, direction)?
+        };
+
+        if resolved {
+            self.set_blocked_turns(npc_id, 0);
+            Ok(GameOutcome::Success)
+        } else {
+            Ok(outcome)
+        }
+    }
+
+    /// Whether the other NPC lets the NPC swap places with it: Both must be on the same side and the other NPC must not be bound to its place (like a shopkeeper).
+    /// Large NPCs never swap, since they would not fit into the place of a smaller one.
+    fn is_willing_ally(&self, npc_id: EntityId, other_id: EntityId) -> bool {
+        let (Some(npc), Some(other)) =
+            (self.current_level().get_npc(npc_id), self.current_level().get_npc(other_id))
+        else {
+            return false;
+        };
+
+        npc.disposition == other.disposition
+            && !matches!(other.ai_state, NpcAiState::Inactive)
+            && npc.size == 1
+            && other.size == 1
+    }
+
+    /// Swaps the positions of two NPCs.
+    ///
+    /// # Returns
+    /// * `true` if both NPCs were found and swapped.
+    fn swap_npcs(&mut self, npc_id: EntityId, other_id: EntityId) -> bool {
+        let (Some(npc), Some(other)) =
+            (self.current_level().get_npc(npc_id), self.current_level().get_npc(other_id))
+        else {
+            return false;
+        };
+        let (npc_pos, other_pos) = (npc.pos(), other.pos());
+
+        let level = self.current_level_mut();
+        if let Some(npc) = level.get_npc_mut(npc_id) {
+            npc.move_to(other_pos);
+        }
+        if let Some(other) = level.get_npc_mut(other_id) {
+            other.move_to(npc_pos);
+            other.blocked_turns = 0;
+        }
+        true
+    }
+
+    /// Steps to one of the sides of the given direction, whichever is free. The side that is tried first is random.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is no longer in the Level data structure.
+    ///
+    /// # Returns
+    /// * `true` if the NPC stepped aside.
+    fn sidestep(&mut self, npc_id: EntityId, direction: Direction) -> Result<bool, GameError> {
+        let mut sides = match direction {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        };
+        if self.rng.random_bool(0.5) {
+            sides.reverse();
+        }
+
+        for side in sides {
+            let delta = PointVector::from(side);
+            if matches!(self.move_npc(npc_id, delta.x, delta.y)?, GameOutcome::Success) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn set_blocked_turns(&mut self, npc_id: EntityId, blocked_turns: u8) {
+        if let Some(npc) = self.current_level_mut().get_npc_mut(npc_id) {
+            npc.blocked_turns = blocked_turns;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::movement_profile::MovementProfile;
+    use crate::world::{coordinate_system::Point, level::Level, tiles::TileType};
+
+    #[test]
+    fn corridor_deadlocks_resolve() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        for x in 40..=60 {
+            level.world.get_tile_mut(Point::new(x, 10)).tile_type = TileType::Hallway;
+        }
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(60, 10);
+
+        let spawn = |game: &mut GameState, def_id: &str, x: usize| {
+            let npc = game.create_npc(def_id.into(), Point::new(x, 10)).unwrap();
+            let npc_id = npc.id();
+            game.current_level_mut().spawn_npc(npc).unwrap();
+            npc_id
+        };
+        let east = spawn(&mut game, "goblin", 45);
+        let west = spawn(&mut game, "goblin", 46);
+        let wolf = spawn(&mut game, "wolf", 50);
+
+        // Two goblins walking into each other wait for a turn, then swap places
+        for _ in 0..TRAFFIC_PATIENCE {
+            assert!(!matches!(
+                game.npc_move_in_traffic(east, Direction::Right),
+                Ok(GameOutcome::Success)
+            ));
+        }
+        assert!(matches!(
+            game.npc_move_in_traffic(east, Direction::Right),
+            Ok(GameOutcome::Success)
+        ));
+        assert_eq!(game.current_level().get_npc(east).unwrap().pos(), Point::new(46, 10));
+        assert_eq!(game.current_level().get_npc(west).unwrap().pos(), Point::new(45, 10));
+
+        // A goblin cannot swap with a wolf and there is no room to sidestep in a corridor
+        game.current_level_mut().get_npc_mut(east).unwrap().move_to(Point::new(49, 10));
+        for _ in 0..3 {
+            assert!(game.npc_move_in_traffic(east, Direction::Right).is_ok());
+        }
+        assert_eq!(game.current_level().get_npc(east).unwrap().pos(), Point::new(49, 10));
+
+        // With room to the side, it walks around the wolf
+        game.current_level_mut().world.get_tile_mut(Point::new(49, 11)).tile_type =
+            TileType::Hallway;
+        assert!(matches!(
+            game.npc_move_in_traffic(east, Direction::Right),
+            Ok(GameOutcome::Success)
+        ));
+        assert_eq!(game.current_level().get_npc(east).unwrap().pos(), Point::new(49, 11));
+
+        // Pathfinding leads through the wolf when there is no other way, instead of giving up
+        assert_eq!(
+            game.next_step_toward(MovementProfile::MONSTER, Point::new(48, 10), Point::new(52, 10)),
+            Some(Direction::Right)
+        );
+        assert!(game.current_level().get_npc(wolf).is_some());
+    }
+}
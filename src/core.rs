@@ -1,8 +1,50 @@
+pub mod altars;
+pub mod appearance;
 pub mod buff_effects;
+pub mod catch_up;
 pub mod combat;
+pub mod containers;
+pub mod cooking;
+pub mod corpses;
+pub mod crowd_control;
+pub mod curses;
+pub mod damage_types;
+pub mod difficulty;
+pub mod distortion;
+pub mod encumbrance;
 pub mod entity_logic;
+pub mod entity_registry;
+pub mod flavor;
 pub mod game;
 pub mod game_items;
+pub mod gauntlet;
+pub mod high_scores;
 pub mod inventory;
+pub mod invisibility;
+pub mod journal;
+pub mod morgue;
+pub mod npc_interaction;
+pub mod perks;
 pub mod player;
 pub mod player_actions;
+pub mod player_class;
+pub mod quests;
+pub mod regeneration;
+pub mod replay;
+pub mod resistances;
+pub mod resting;
+pub mod rewind;
+pub mod run_stats;
+pub mod scratch;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod shop;
+pub mod simulation;
+pub mod skills;
+pub mod taming;
+pub mod terrain;
+pub mod town;
+pub mod travel;
+pub mod triggers;
+pub mod uniques;
+pub mod wandering;
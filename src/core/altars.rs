@@ -0,0 +1,177 @@
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{
+        buff_effects::{ActiveBuff, PotionEffectDef},
+        entity_logic::Entity,
+        game::GameState,
+    },
+    util::{
+        errors_results::{GameError, GameOutcome, GameResult},
+        rng::{Check, CheckOutcome},
+        text_log::LogData,
+    },
+    world::{
+        coordinate_system::{Direction, Point},
+        tiles::{ShrineState, TileType},
+    },
+};
+
+/// Difficulty of the check that decides whether the gods are pleased by a prayer. The player's level is added to the roll.
+pub const ALTAR_FAVOUR_DIFFICULTY: i16 = 10;
+
+/// Difficulty of the second check, which decides how strong the boon or curse is.
+pub const ALTAR_FATE_DIFFICULTY: i16 = 12;
+
+/// Damage bonus a blessed weapon gains.
+pub const ALTAR_BLESSING: i16 = 2;
+
+/// Strength lost to the curse of an altar.
+pub const ALTAR_CURSE_PENALTY: u8 = 2;
+
+/// Number of turns the curse of an altar lasts.
+pub const ALTAR_CURSE_DURATION: u8 = 40;
+
+/// NPC summoned to guard an altar the gods are displeased with.
+pub const ALTAR_GUARDIAN: &str = "skeleton";
+
+/// Number of guardians summoned at most.
+pub const ALTAR_GUARDIANS: usize = 2;
+
+/// What praying at an altar brings. Decided by two [Check]s: one for the mood of the gods, one for the strength of the outcome.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AltarOutcome {
+    /// Raises the damage of the equipped weapon by [ALTAR_BLESSING]. Heals instead if no weapon is equipped.
+    BlessWeapon,
+    /// Heals all hit points.
+    Heal,
+    /// Lowers strength by [ALTAR_CURSE_PENALTY] for [ALTAR_CURSE_DURATION] turns.
+    Curse,
+    /// Summons [ALTAR_GUARDIANS] hostile [ALTAR_GUARDIAN]s next to the player.
+    SummonGuardians,
+}
+
+impl GameState {
+    /// Asks the gods for their answer to a prayer. A critical result of the favour check decides the outcome on its own.
+    fn roll_altar_outcome(&mut self) -> AltarOutcome {
+        let piety = self.player.character.stats.level as i16;
+        let favour = self
+            .check(&Check::default().add_modifier(piety).set_difficulty(ALTAR_FAVOUR_DIFFICULTY));
+        match favour {
+            CheckOutcome::CriticalSuccess => return AltarOutcome::BlessWeapon,
+            CheckOutcome::CriticalFailure => return AltarOutcome::SummonGuardians,
+            _ => {}
+        }
+        let strong = self.check(&Check::default().set_difficulty(ALTAR_FATE_DIFFICULTY));
+
+        match (favour.is_success(), strong.is_success()) {
+            (true, true) => AltarOutcome::BlessWeapon,
+            (true, false) => AltarOutcome::Heal,
+            (false, false) => AltarOutcome::Curse,
+            (false, true) => AltarOutcome::SummonGuardians,
+        }
+    }
+
+    /// The player prays at the altar at the given point and receives a boon or a curse (see [AltarOutcome]).
+    /// Afterwards, the altar is desecrated.
+    ///
+    /// # Errors
+    /// * [GameError] if a guardian could not be spawned.
+    pub fn pray_at_altar(&mut self, point: Point) -> GameResult {
+        self.current_world_mut().get_tile_mut(point).tile_type =
+            TileType::Altar(ShrineState::Depleted);
+
+        let outcome = self.roll_altar_outcome();
+        self.apply_altar_outcome(outcome)?;
+
+        Ok(GameOutcome::Success)
+    }
+
+    fn apply_altar_outcome(&mut self, outcome: AltarOutcome) -> Result<(), GameError> {
+        let weapon = self.player.character.weapon.map(|weapon| weapon.0);
+
+        match (outcome, weapon) {
+            (AltarOutcome::BlessWeapon, Some(weapon_id)) => {
+                if let Some(weapon) = self.items.get_mut(&weapon_id) {
+                    weapon.blessing += ALTAR_BLESSING;
+                }
+                let item_name = self.container_item_name(weapon_id)?;
+                self.log.info(LogData::AltarBlessesWeapon {
+                    item_name: item_name.to_string(),
+                    bonus: ALTAR_BLESSING,
+                });
+            }
+            (AltarOutcome::BlessWeapon, None) | (AltarOutcome::Heal, _) => {
+                let hp_max = self.player.character.stats.base.hp_max;
+                self.player.character.heal(hp_max);
+                self.log.info(LogData::AltarHeals);
+            }
+            (AltarOutcome::Curse, _) => {
+                self.player.character.active_buffs.push(ActiveBuff {
+                    effect: PotionEffectDef::Fatigue {
+                        strength_penalty: ALTAR_CURSE_PENALTY,
+                        duration: ALTAR_CURSE_DURATION,
+                    },
+                    remaining_turns: ALTAR_CURSE_DURATION,
+                });
+                self.log.info(LogData::AltarCurses {
+                    strength_penalty: ALTAR_CURSE_PENALTY,
+                    duration: ALTAR_CURSE_DURATION,
+                });
+            }
+            (AltarOutcome::SummonGuardians, _) => {
+                let player_pos = self.player.character.pos();
+                let free_points: Vec<Point> = Direction::iter()
+                    .map(|direction| player_pos.get_adjacent(direction))
+                    .filter(|point| self.current_level().is_available(*point))
+                    .take(ALTAR_GUARDIANS)
+                    .collect();
+
+                for point in free_points.iter().copied() {
+                    let npc = self.create_npc(ALTAR_GUARDIAN.to_string(), point)?;
+                    self.current_level_mut().spawn_npc(npc)?;
+                }
+                self.log.info(LogData::AltarSummonsGuardians { amount: free_points.len() });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    #[test]
+    fn altar_outcomes_bless_curse_and_summon() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 10, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(8, 8);
+
+        let sword = game.register_item(&"weapon_sword_dull".to_string()).unwrap();
+        game.add_item_to_inv(sword).unwrap();
+        game.use_weapon(sword).unwrap();
+        let damage_before = game.player_attack_modifiers(false).damage;
+        game.apply_altar_outcome(AltarOutcome::BlessWeapon).unwrap();
+        assert_eq!(game.player_attack_modifiers(false).damage, damage_before + ALTAR_BLESSING);
+
+        game.apply_altar_outcome(AltarOutcome::Curse).unwrap();
+        assert_eq!(game.player.character.active_buffs.len(), 1);
+
+        game.apply_altar_outcome(AltarOutcome::SummonGuardians).unwrap();
+        assert_eq!(game.current_level().npcs.len(), ALTAR_GUARDIANS);
+
+        let altar = Point::new(9, 8);
+        game.current_world_mut().get_tile_mut(altar).tile_type =
+            TileType::Altar(ShrineState::Active);
+        game.pray_at_altar(altar).unwrap();
+        assert_eq!(
+            game.current_world().get_tile(altar).tile_type,
+            TileType::Altar(ShrineState::Depleted)
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use ron::de::from_reader;
+use ron::ser::{PrettyConfig, to_writer_pretty};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::game::GameState,
+    render::style::{Color, Style},
+    util::errors_results::{GameError, IoError},
+};
+
+/// Glyphs the player can pick for their character. None of them is used by NPCs, items or tiles, so the character stays recognizable.
+pub const PLAYER_GLYPHS: [char; 6] = ['@', 'Ж', '§', 'Ω', '¤', 'ß'];
+
+/// Colors the player can pick for their character. All of them are bright enough to stand out on the dark background.
+pub const PLAYER_COLORS: [PlayerColor; 6] = [
+    PlayerColor::Yellow,
+    PlayerColor::White,
+    PlayerColor::LightCyan,
+    PlayerColor::LightGreen,
+    PlayerColor::LightMagenta,
+    PlayerColor::LightBlue,
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayerColor {
+    #[default]
+    Yellow,
+    White,
+    LightCyan,
+    LightGreen,
+    LightMagenta,
+    LightBlue,
+}
+
+impl PlayerColor {
+    pub fn color(&self) -> Color {
+        match self {
+            PlayerColor::Yellow => Color::Yellow,
+            PlayerColor::White => Color::White,
+            PlayerColor::LightCyan => Color::LightCyan,
+            PlayerColor::LightGreen => Color::LightGreen,
+            PlayerColor::LightMagenta => Color::LightMagenta,
+            PlayerColor::LightBlue => Color::LightBlue,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayerColor::Yellow => "Yellow",
+            PlayerColor::White => "White",
+            PlayerColor::LightCyan => "Light Cyan",
+            PlayerColor::LightGreen => "Light Green",
+            PlayerColor::LightMagenta => "Light Magenta",
+            PlayerColor::LightBlue => "Light Blue",
+        }
+    }
+}
+
+/// Glyph and color of the player character, picked at character creation.
+///
+/// The last picked appearance is saved, so the next character starts with it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerAppearance {
+    pub glyph: char,
+    pub color: PlayerColor,
+}
+
+impl Default for PlayerAppearance {
+    fn default() -> Self {
+        Self { glyph: PLAYER_GLYPHS[0], color: PlayerColor::default() }
+    }
+}
+
+/// Path of the file the last picked appearance is saved to.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn appearance_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("saves");
+    path.push("appearance.ron");
+    Some(path)
+}
+
+impl PlayerAppearance {
+    pub fn style(&self) -> Style {
+        Style::default().fg(self.color.color())
+    }
+
+    /// Describes the appearance for the morgue file, e.g. `@ in Light Cyan`.
+    pub fn description(&self) -> String {
+        format!("{} in {}", self.glyph, self.color.name())
+    }
+
+    /// Picks the next (or previous) glyph of [PLAYER_GLYPHS].
+    pub fn cycle_glyph(&mut self, forward: bool) {
+        self.glyph = cycle(&PLAYER_GLYPHS, self.glyph, forward);
+    }
+
+    /// Picks the next (or previous) color of [PLAYER_COLORS].
+    pub fn cycle_color(&mut self, forward: bool) {
+        self.color = cycle(&PLAYER_COLORS, self.color, forward);
+    }
+
+    /// Checks whether the glyph and color are from the curated lists. Hand-edited files might contain others.
+    pub fn is_curated(&self) -> bool {
+        PLAYER_GLYPHS.contains(&self.glyph) && PLAYER_COLORS.contains(&self.color)
+    }
+
+    /// Loads the last picked appearance from the given file. A missing file means none was picked yet.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path).map_err(IoError::FileReading)?;
+        let appearance: Self = from_reader(BufReader::new(file)).map_err(IoError::MapParsing)?;
+        Ok(if appearance.is_curated() { appearance } else { Self::default() })
+    }
+
+    /// Writes the appearance to the given file.
+    ///
+    /// # Errors
+    /// * [IoError::FileCreation] if the file or its directory could not be created.
+    /// * [IoError::MapWriting] if the appearance could not be written.
+    pub fn save(&self, path: &Path) -> Result<(), GameError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(IoError::FileCreation)?;
+        }
+
+        let file = File::create(path).map_err(IoError::FileCreation)?;
+        to_writer_pretty(BufWriter::new(file), self, PrettyConfig::default())
+            .map_err(IoError::MapWriting)?;
+        Ok(())
+    }
+}
+
+/// Gets the option after (or before) `current`, wrapping around at the ends. Unknown options start over at the first one.
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, forward: bool) -> T {
+    let Some(index) = options.iter().position(|option| *option == current) else {
+        return options[0];
+    };
+    let next = if forward { index + 1 } else { index + options.len() - 1 };
+    options[next % options.len()]
+}
+
+impl GameState {
+    /// Gives the player character the picked appearance.
+    pub fn set_player_appearance(&mut self, appearance: PlayerAppearance) {
+        self.player.appearance = appearance;
+        self.player.character.base.glyph = appearance.glyph;
+        self.player.character.base.style = appearance.style();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appearance_cycles_through_curated_options() {
+        let mut appearance = PlayerAppearance::default();
+        for _ in 0..PLAYER_GLYPHS.len() {
+            appearance.cycle_glyph(true);
+            appearance.cycle_color(false);
+            assert!(appearance.is_curated());
+        }
+        assert_eq!(appearance, PlayerAppearance::default());
+
+        appearance.cycle_color(false);
+        assert_eq!(appearance.color, PlayerColor::LightBlue);
+
+        let hand_edited = PlayerAppearance { glyph: 'g', color: PlayerColor::Yellow };
+        assert!(!hand_edited.is_curated());
+
+        let mut game = GameState::default();
+        game.set_player_appearance(appearance);
+        assert_eq!(game.player.character.base.style.fg, Some(Color::LightBlue));
+    }
+}
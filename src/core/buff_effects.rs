@@ -1,8 +1,19 @@
 #![allow(dead_code)]
 
-use crate::{core::game::GameState, util::text_log::LogData};
+use serde::Serialize;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+use crate::{
+    core::{game::GameState, run_stats::DamageSource},
+    util::text_log::LogData,
+};
+
+/// Number of rounds after which one recorded use of a potion type is forgotten.
+pub const POTION_USAGE_DECAY_ROUNDS: u64 = 30;
+
+/// Number of remaining turns at which an active buff counts as expiring.
+pub const BUFF_EXPIRY_WARNING_TURNS: u8 = 2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize)]
 pub enum PotionType {
     Heal,
     Strength,
@@ -10,6 +21,22 @@ pub enum PotionType {
     Poison,
     Fatigue,
     Cramp,
+    SeeInvisible,
+}
+
+impl PotionType {
+    /// Short label used for the HUD badge of active buffs.
+    pub fn badge(&self) -> &'static str {
+        match self {
+            PotionType::Heal => "HEAL",
+            PotionType::Strength => "STR+",
+            PotionType::Dexterity => "DEX+",
+            PotionType::Poison => "PSN",
+            PotionType::Fatigue => "STR-",
+            PotionType::Cramp => "DEX-",
+            PotionType::SeeInvisible => "SEE",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -20,30 +47,73 @@ pub enum PotionEffectDef {
     Poison { damage_per_tick: u16, duration: u8 },
     Fatigue { strength_penalty: u8, duration: u8 },
     Cramp { dexterity_penalty: u8, duration: u8 },
+    SeeInvisible { duration: u8 },
 }
 
+/// Tracks how often a potion type was used recently. All timing is measured in game rounds.
 #[derive(Clone, Debug)]
 pub struct PotionUsage {
     pub count: u8,
+    /// Round number of the last use.
     pub last_used: u64,
 }
 
-#[derive(Clone, Debug)]
-pub struct ActiveBuff {
-    pub effect: PotionEffectDef,
-    pub remaining_turns: u8,
+impl PotionUsage {
+    /// Returns the usage count after decay. For every [POTION_USAGE_DECAY_ROUNDS] rounds since the last use, one use is forgotten.
+    pub fn decayed_count(&self, round_nr: u64) -> u8 {
+        let decayed_uses = round_nr.saturating_sub(self.last_used) / POTION_USAGE_DECAY_ROUNDS;
+        self.count.saturating_sub(decayed_uses.min(u8::MAX as u64) as u8)
+    }
+
+    /// Registers a new use in the given round, after applying the decay of earlier uses.
+    pub fn register_use(&mut self, round_nr: u64) {
+        self.count = self.decayed_count(round_nr).saturating_add(1);
+        self.last_used = round_nr;
+    }
 }
 
-impl GameState {
-    pub fn apply_potion_effect(&mut self, effect: PotionEffectDef) {
-        let potion_type: PotionType = match effect {
+impl PotionEffectDef {
+    pub fn potion_type(&self) -> PotionType {
+        match self {
             PotionEffectDef::Heal { .. } => PotionType::Heal,
             PotionEffectDef::Strength { .. } => PotionType::Strength,
             PotionEffectDef::Dexterity { .. } => PotionType::Dexterity,
             PotionEffectDef::Poison { .. } => PotionType::Poison,
             PotionEffectDef::Fatigue { .. } => PotionType::Fatigue,
             PotionEffectDef::Cramp { .. } => PotionType::Cramp,
-        };
+            PotionEffectDef::SeeInvisible { .. } => PotionType::SeeInvisible,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ActiveBuff {
+    pub effect: PotionEffectDef,
+    pub remaining_turns: u8,
+}
+
+impl ActiveBuff {
+    /// Whether the buff is about to run out (within the last [BUFF_EXPIRY_WARNING_TURNS] turns).
+    pub fn is_expiring(&self) -> bool {
+        self.remaining_turns <= BUFF_EXPIRY_WARNING_TURNS
+    }
+}
+
+impl GameState {
+    /// Advances all active buffs of the player by one turn and notifies the player about every buff that ran out.
+    pub fn tick_player_buffs(&mut self) {
+        let hp_before = self.player.character.stats.base.hp_current;
+        let expired_buffs = self.player.character.tick_buffs();
+        let poison_damage = hp_before - self.player.character.stats.base.hp_current;
+        self.record_damage_taken(poison_damage, DamageSource::Poison);
+
+        for buff in expired_buffs {
+            self.log.info(LogData::BuffExpired { potion_type: buff.effect.potion_type() });
+        }
+    }
+
+    pub fn apply_potion_effect(&mut self, effect: PotionEffectDef) {
+        let potion_type = effect.potion_type();
 
         let (usage_count, rounds_since_last_use) = {
             let usage = self
@@ -53,9 +123,8 @@ impl GameState {
                 .entry(potion_type)
                 .or_insert(PotionUsage { count: 0, last_used: self.round_nr });
 
-            let rounds_since_last_use = self.round_nr - usage.last_used;
-            usage.count += 1;
-            usage.last_used = self.round_nr;
+            let rounds_since_last_use = self.round_nr.saturating_sub(usage.last_used);
+            usage.register_use(self.round_nr);
             (usage.count, rounds_since_last_use)
         };
 
@@ -64,11 +133,8 @@ impl GameState {
                 self.player.character.heal(amount);
                 self.log.info(LogData::PlayerHealed { amount });
 
-                if usage_count >= 3 && rounds_since_last_use < 30 {
-                    self.player.character.active_buffs.push(ActiveBuff {
-                        effect: PotionEffectDef::Poison { damage_per_tick: 2, duration: 10 },
-                        remaining_turns: 10,
-                    });
+                if usage_count >= 3 && rounds_since_last_use < POTION_USAGE_DECAY_ROUNDS {
+                    self.poison_player(2, 10);
 
                     self.log.info(LogData::Overdose);
                 }
@@ -93,10 +159,7 @@ impl GameState {
                     ));
 
                     if usage_count >= 4 {
-                        self.player.character.active_buffs.push(ActiveBuff {
-                            effect: PotionEffectDef::Poison { damage_per_tick: 2, duration: 5 },
-                            remaining_turns: 5,
-                        });
+                        self.poison_player(2, 5);
                         self.log.info(LogData::Overdose);
                     }
                 }
@@ -123,19 +186,14 @@ impl GameState {
                     ));
 
                     if usage_count >= 4 {
-                        self.player.character.active_buffs.push(ActiveBuff {
-                            effect: PotionEffectDef::Poison { damage_per_tick: 2, duration: 5 },
-                            remaining_turns: 5,
-                        });
+                        self.poison_player(2, 5);
                         self.log.info(LogData::Overdose);
                     }
                 }
             }
-            PotionEffectDef::Poison { damage_per_tick: _, duration } => self
-                .player
-                .character
-                .active_buffs
-                .push(ActiveBuff { effect, remaining_turns: duration }),
+            PotionEffectDef::Poison { damage_per_tick, duration } => {
+                self.poison_player(damage_per_tick, duration)
+            }
             PotionEffectDef::Fatigue { strength_penalty: _, duration } => self
                 .player
                 .character
@@ -146,6 +204,34 @@ impl GameState {
                 .character
                 .active_buffs
                 .push(ActiveBuff { effect, remaining_turns: duration }),
+            PotionEffectDef::SeeInvisible { duration } => {
+                self.player
+                    .character
+                    .active_buffs
+                    .push(ActiveBuff { effect, remaining_turns: duration });
+                self.log.print(format!("Your eyes pierce the unseen for {} turns.", duration));
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn potion_usage_decays_over_rounds() {
+        let mut usage = PotionUsage { count: 0, last_used: 0 };
+        usage.register_use(0);
+        usage.register_use(1);
+        usage.register_use(2);
+        assert_eq!(usage.count, 3);
+
+        assert_eq!(usage.decayed_count(2 + POTION_USAGE_DECAY_ROUNDS - 1), 3);
+        assert_eq!(usage.decayed_count(2 + POTION_USAGE_DECAY_ROUNDS), 2);
+        assert_eq!(usage.decayed_count(2 + POTION_USAGE_DECAY_ROUNDS * 10), 0);
+
+        usage.register_use(2 + POTION_USAGE_DECAY_ROUNDS * 2);
+        assert_eq!(usage.count, 2);
+    }
+}
@@ -0,0 +1,127 @@
+use rand::seq::IndexedRandom;
+
+use crate::{
+    ai::npc_ai::NpcAiState,
+    core::{
+        entity_logic::{Entity, Movable},
+        game::GameState,
+    },
+    world::{
+        coordinate_system::Direction,
+        tiles::{Collision, TileType},
+    },
+};
+
+/// Rounds the player has to be away from a level for its NPCs to take one catch-up tick.
+pub const CATCH_UP_ROUNDS_PER_TICK: u64 = 5;
+
+/// Most catch-up ticks a level takes at once, so returning after a long time stays cheap.
+pub const MAX_CATCH_UP_TICKS: u64 = 40;
+
+/// Hit points NPCs regenerate in each catch-up tick.
+pub const CATCH_UP_REGEN: u16 = 1;
+
+impl GameState {
+    /// Lets the current level catch up with the rounds the player was away, so the world does not stand still while they are gone.
+    ///
+    /// Instead of simulating every level every round, the NPCs take one cheap tick for every [CATCH_UP_ROUNDS_PER_TICK] rounds
+    /// (at most [MAX_CATCH_UP_TICKS]): They regenerate and wanderers take a random step. NPCs that were chasing the player lose track of them.
+    /// Companions, shopkeepers and other NPCs that stay in place are left alone.
+    pub fn catch_up_level(&mut self) {
+        let Some(left_round) = self.current_level_mut().left_round.take() else {
+            return;
+        };
+        let ticks = (self.round_nr.saturating_sub(left_round) / CATCH_UP_ROUNDS_PER_TICK)
+            .min(MAX_CATCH_UP_TICKS);
+        if ticks == 0 {
+            return;
+        }
+
+        for npc in self.current_level_mut().npcs.iter_mut() {
+            if matches!(npc.ai_state, NpcAiState::Aggressive) {
+                npc.ai_state = NpcAiState::Wandering;
+            }
+        }
+
+        for _ in 0..ticks {
+            self.catch_up_tick();
+        }
+        self.log.debug_info(format!("Level {} caught up with {} ticks", self.level_nr, ticks));
+    }
+
+    /// One catch-up tick of the NPCs of the current level (see [GameState::catch_up_level]).
+    fn catch_up_tick(&mut self) {
+        let player_pos = self.player.character.pos();
+
+        // Indexed, so the level can be checked while the NPCs are moved
+        for index in 0..self.current_level().npcs.len() {
+            let npc = &mut self.current_level_mut().npcs[index];
+            if !npc.stats.base.is_alive() || matches!(npc.ai_state, NpcAiState::Companion) {
+                continue;
+            }
+            npc.stats.base.heal(CATCH_UP_REGEN);
+            if !matches!(npc.ai_state, NpcAiState::Wandering) {
+                continue;
+            }
+
+            let (npc_id, npc_pos, footprint) = (npc.id(), npc.pos(), npc.footprint());
+            let Some(&direction) =
+                [Direction::Up, Direction::Right, Direction::Down, Direction::Left]
+                    .choose(&mut self.rng)
+            else {
+                continue;
+            };
+            let target = npc_pos.get_adjacent(direction);
+            let target_footprint = footprint.moved_to(target);
+
+            // A cheap step: Only onto free, safe tiles, so nothing happens that the player should have seen
+            let level = self.current_level();
+            let free = target_footprint.points().all(|point| {
+                level.world.is_in_bounds(point.x as isize, point.y as isize)
+                    && point != player_pos
+                    && level.world.get_tile(point).tile_type.movement_cost().is_some()
+                    && level.world.get_tile(point).tile_type != TileType::SpikeTrap
+                    && level.get_npc_at(point).is_none_or(|other_id| other_id == npc_id)
+            });
+            if free {
+                self.current_level_mut().npcs[index].move_to(target);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{coordinate_system::Point, level::Level, worldspace::Room};
+
+    #[test]
+    fn levels_catch_up_with_the_rounds_the_player_was_away() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(6, 6);
+
+        let mut npc = game.create_npc("goblin".to_string(), Point::new(15, 10)).unwrap();
+        npc.stats.base.hp_current = 1;
+        npc.ai_state = NpcAiState::Aggressive;
+        let npc_id = npc.id();
+        game.current_level_mut().spawn_npc(npc).unwrap();
+
+        // Away for too short a time, nothing happens
+        game.current_level_mut().left_round = Some(0);
+        game.round_nr = CATCH_UP_ROUNDS_PER_TICK - 1;
+        game.catch_up_level();
+        assert_eq!(game.current_level().get_npc(npc_id).unwrap().stats.base.hp_current, 1);
+
+        game.current_level_mut().left_round = Some(0);
+        game.round_nr = CATCH_UP_ROUNDS_PER_TICK * 20;
+        game.catch_up_level();
+        let npc = game.current_level().get_npc(npc_id).unwrap();
+        assert!(npc.stats.base.hp_current > 1);
+        assert!(matches!(npc.ai_state, NpcAiState::Wandering));
+        assert_ne!(npc.pos(), Point::new(15, 10));
+        assert_eq!(game.current_level().left_round, None);
+    }
+}
@@ -1,26 +1,190 @@
+use std::ops::Add;
+
+use rand::Rng;
+
 use crate::{
+    ai::npc_ai::NpcAiState,
     core::{
-        entity_logic::{Entity, EntityId},
+        crowd_control::{CrowdControl, CrowdControlTarget},
+        damage_types::{DamageAffinities, DamageType},
+        entity_logic::{Disposition, Entity, EntityId, Npc, NpcStats},
         game::GameState,
         game_items::{AttackRange, GameItemKindDef},
+        quests::QuestEvent,
+        resistances::FEAR_TURNS,
+        run_stats::DamageSource,
+        skills::Skill,
     },
+    data::npc_defs::npc_defs,
     util::{
         errors_results::{DataError, EngineError, FailReason, GameError, GameOutcome, GameResult},
         rng::{DieSize, Roll},
         text_log::LogData,
     },
+    world::{
+        coordinate_system::{Footprint, Point},
+        los::has_line_of_sight,
+    },
 };
 
+#[cfg(feature = "scripting")]
+use crate::core::scripting::ScriptEvent;
+
+/// Chance (in percent) of an NPC landing a critical hit, before any modifiers are applied.
+pub const NPC_BASE_CRIT_CHANCE: i16 = 5;
+
+/// Factor by which damage is multiplied on a critical hit.
+pub const CRIT_MULTIPLIER: u16 = 2;
+
+/// An explosion or spell that damages everything around its center (see [GameState::resolve_area_of_effect]).
+#[derive(Clone, Debug)]
+pub struct AreaOfEffect {
+    pub center: Point,
+    pub radius: usize,
+    pub damage: Roll,
+    pub damage_type: DamageType,
+    pub source: BlastSource,
+}
+
+/// Who caused an [AreaOfEffect]. Decides who is spared by the blast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlastSource {
+    /// Cast by the player (e.g. a fireball scroll). Spares the player and their companions.
+    Player,
+
+    /// Cast by an NPC (e.g. a boss ability). Spares the caster and NPCs on its side.
+    Npc(EntityId),
+
+    /// Not caused by anyone (e.g. an exploding barrel). Hits everyone.
+    Environment,
+}
+
 /// Defines the degrees of success an attack can have.
-enum AttackDegree {
-    /// The attack missed and nothing happens.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AttackDegree {
+    /// The attack was dodged and nothing happens.
     Miss,
 
-    /// The attack hits and deals the listed damage.
-    Hit(u16),
+    /// The attack hits and deals the listed damage. `blocked` is the amount of damage absorbed by mitigation.
+    Hit { damage: u16, blocked: u16 },
 
     /// The attack hits critically and deals the listed damage, which is even more than on a hit.
-    CriticalHit(u16),
+    CriticalHit { damage: u16, blocked: u16 },
+}
+
+/// Bonuses and penalties that apply to one side of an attack.
+///
+/// Equipment, active effects and level scaling each contribute their own `CombatModifiers`, which are summed up with `+` before the attack is resolved in [resolve_attack].
+/// On the attacking side `damage` and `crit_chance` are used, on the defending side `dodge_chance` and `mitigation`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CombatModifiers {
+    /// Flat bonus added to the rolled damage.
+    pub damage: i16,
+
+    /// Chance (in percent) to land a critical hit.
+    pub crit_chance: i16,
+
+    /// Chance (in percent) to dodge an incoming attack.
+    pub dodge_chance: i16,
+
+    /// Flat amount subtracted from incoming damage.
+    pub mitigation: i16,
+}
+
+impl CombatModifiers {
+    /// Modifiers that only add flat damage.
+    pub fn damage(amount: i16) -> Self {
+        Self { damage: amount, ..Self::default() }
+    }
+
+    /// Modifiers that only add critical hit chance.
+    pub fn crit_chance(chance: i16) -> Self {
+        Self { crit_chance: chance, ..Self::default() }
+    }
+
+    /// Modifiers of a defender, made up of their dodge chance and their mitigation.
+    pub fn defense(dodge_chance: i16, mitigation: i16) -> Self {
+        Self { dodge_chance, mitigation, ..Self::default() }
+    }
+}
+
+impl Add for CombatModifiers {
+    type Output = CombatModifiers;
+
+    fn add(self, other: CombatModifiers) -> Self::Output {
+        CombatModifiers {
+            damage: self.damage.saturating_add(other.damage),
+            crit_chance: self.crit_chance.saturating_add(other.crit_chance),
+            dodge_chance: self.dodge_chance.saturating_add(other.dodge_chance),
+            mitigation: self.mitigation.saturating_add(other.mitigation),
+        }
+    }
+}
+
+/// A hit that landed on an entity, shown by the UI as a damage popup over the target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HitMarker {
+    pub point: Point,
+    pub damage: u16,
+    pub critical: bool,
+    /// Whether the player was hit, which is shown in the danger color.
+    pub on_player: bool,
+}
+
+/// Resolves all computation steps as part of an attack. Returns the degree of success and the damage dealt (if any).
+///
+/// This is the central combat formula. It is kept free of the [GameState], so it can be tested with a seeded rng.
+///
+/// 1. The defender rolls a d100 against their dodge chance. On a success the attack misses.
+/// 2. The attacker rolls a d100 against their crit chance. On a success the damage is multiplied by [CRIT_MULTIPLIER].
+/// 3. The defender's mitigation is subtracted from the damage. Negative mitigation (a vulnerability) is added to it instead.
+pub fn resolve_attack<R: Rng + ?Sized>(
+    rolled_damage: u16,
+    attacker: CombatModifiers,
+    defender: CombatModifiers,
+    rng: &mut R,
+) -> AttackDegree {
+    if percent_roll(defender.dodge_chance, rng) {
+        return AttackDegree::Miss;
+    }
+
+    let is_critical_strike = percent_roll(attacker.crit_chance, rng);
+
+    let mut damage_unmitigated = rolled_damage.saturating_add_signed(attacker.damage);
+    if is_critical_strike {
+        damage_unmitigated = damage_unmitigated.saturating_mul(CRIT_MULTIPLIER);
+    }
+
+    let damage = damage_unmitigated.saturating_add_signed(-defender.mitigation);
+    let blocked = damage_unmitigated.saturating_sub(damage);
+
+    if is_critical_strike {
+        AttackDegree::CriticalHit { damage, blocked }
+    } else {
+        AttackDegree::Hit { damage, blocked }
+    }
+}
+
+/// Rolls a d100 and checks it against a chance in percent.
+fn percent_roll<R: Rng + ?Sized>(chance: i16, rng: &mut R) -> bool {
+    Roll::new(1, DieSize::D100).roll(rng) <= chance
+}
+
+impl NpcStats {
+    /// Modifiers the NPC contributes when attacking.
+    pub fn attack_modifiers(&self) -> CombatModifiers {
+        CombatModifiers::crit_chance(NPC_BASE_CRIT_CHANCE)
+    }
+
+    /// Modifiers the NPC contributes when being attacked with damage of the given type.
+    pub fn defense_against(
+        &self,
+        damage_type: DamageType,
+        affinities: DamageAffinities,
+    ) -> CombatModifiers {
+        let mitigation = affinities.mitigation(damage_type, self.mitigation as i16);
+        CombatModifiers::defense(self.dodge_chance() as i16, mitigation)
+    }
 }
 
 impl GameState {
@@ -31,6 +195,7 @@ impl GameState {
     /// * Calls `Npc::stats.base.take_damage()`
     /// * Calls `GameState::player_add_experience()`
     /// * Calls `Level::despawn()`
+    /// * Calls `GameState::create_corpse()` and `Level::spawn_corpse()`
     ///
     /// # Errors
     /// * [EngineError::NpcNotFound] if the NPC with the given id could not be found in the current Level.
@@ -41,64 +206,125 @@ impl GameState {
     /// # Returns
     /// * [GameOutcome::Success] if the attack resolution was successful.
     pub fn player_attack_npc(&mut self, npc_id: EntityId) -> GameResult {
+        // Neutral NPCs fight back, together with their pack
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let unnoticed = !matches!(npc.ai_state, NpcAiState::Aggressive | NpcAiState::Hunting);
+        if npc.disposition == Disposition::Neutral {
+            self.provoke_pack(npc_id);
+        }
+
         // Fetching values
+        let (weapon_damage, crit_chance, range, damage_type): (Roll, u8, AttackRange, DamageType) =
+            self.get_player_weapon_stats()?;
         let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
         let npc_name = npc.name().to_string();
-        let npc_mitigation = npc.stats.mitigation;
-        let npc_dodge_chance = npc.stats.dodge_chance();
+        let defender = npc.stats.defense_against(damage_type, self.npc_affinities(npc));
 
         // Damage
-        let (weapon_damage, crit_chance, range): (Roll, u8, AttackRange) =
-            self.get_player_weapon_stats()?;
-        let base_damage = if range.is_some() {
-            self.player.character.attack_damage_bonus_ranged()
-        } else {
-            self.player.character.attack_damage_bonus_melee()
-        };
-        let rolled_damage = self.roll(&weapon_damage) as u16;
+        let attacker = self.player_attack_modifiers(range.is_some())
+            + CombatModifiers::crit_chance(crit_chance as i16);
+        let rolled_damage = self.roll(&weapon_damage).max(0) as u16;
+        self.train_skill(if range.is_some() { Skill::Archery } else { Skill::Melee });
+        if unnoticed {
+            self.train_skill(Skill::Stealth);
+        }
 
         // Calculate resulting damage (if any)
-        let attack_result = self.resolve_attack(
-            rolled_damage.saturating_add_signed(base_damage),
-            crit_chance,
-            npc_dodge_chance,
-            npc_mitigation,
-        );
+        let attack_result = resolve_attack(rolled_damage, attacker, defender, &mut self.rng);
+        let critical = matches!(attack_result, AttackDegree::CriticalHit { .. });
 
         let attack_message: LogData = match attack_result {
             AttackDegree::Miss => LogData::PlayerAttackMiss { npc_name },
-            AttackDegree::Hit(damage) => {
+            AttackDegree::Hit { damage, blocked } => {
                 let npc = self
                     .current_level_mut()
                     .get_npc_mut(npc_id)
                     .ok_or(EngineError::NpcNotFound(npc_id))?;
                 npc.stats.base.take_damage(damage);
-                LogData::PlayerAttackHit { npc_name, damage }
+                let point = npc.pos();
+                self.run_stats.damage_dealt += damage as u32;
+                self.mark_hit(point, damage, false, false);
+                LogData::PlayerAttackHit { npc_name, damage, blocked }
             }
-            AttackDegree::CriticalHit(damage) => {
+            AttackDegree::CriticalHit { damage, blocked } => {
                 let npc = self
                     .current_level_mut()
                     .get_npc_mut(npc_id)
                     .ok_or(EngineError::NpcNotFound(npc_id))?;
                 npc.stats.base.take_damage(damage);
-                LogData::PlayerAttackHitCritical { npc_name, damage }
+                let point = npc.pos();
+                self.run_stats.damage_dealt += damage as u32;
+                self.mark_hit(point, damage, true, false);
+                LogData::PlayerAttackHitCritical { npc_name, damage, blocked }
             }
         };
 
         self.log.info(attack_message);
+        if critical || matches!(attack_result, AttackDegree::Hit { .. }) {
+            let npc = self
+                .current_level()
+                .get_npc(npc_id)
+                .ok_or(EngineError::NpcNotFound(npc_id))?
+                .clone();
+            self.elemental_hit_on_npc(&npc, damage_type)?;
+        }
 
         // Checks if the npc is dead. Later this will be moved into some central event handler.
         let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
-        let npc_name = npc.name().to_string();
         if !npc.stats.base.is_alive() {
-            self.log.info(LogData::NpcDied { npc_name });
-            self.current_level_mut().despawn(npc_id);
-            self.player_add_experience(25);
+            self.npc_died(npc.clone(), true)?;
+        } else if critical && range.is_none() {
+            // Critical melee hits knock the target back
+            self.knock_back_npc(npc_id)?;
         }
 
         Ok(GameOutcome::Success)
     }
 
+    /// Removes a dead NPC from the level and leaves its corpse behind.
+    ///
+    /// If the player killed the NPC, the kill is recorded and the player is rewarded with experience and gold.
+    pub fn npc_died(&mut self, npc: Npc, killed_by_player: bool) -> Result<(), GameError> {
+        let npc_name = npc.name().to_string();
+        if killed_by_player {
+            self.record_kill(&npc_name);
+            if npc.unique.is_some() {
+                self.write_journal(&format!("I slew {}.", npc_name));
+            }
+            self.notify_quests(QuestEvent::Killed(&npc_name));
+        }
+        self.log.info(LogData::NpcDied { npc_name });
+        self.current_level_mut().despawn(npc.id());
+        // A failing script does not keep the NPC from leaving its corpse and loot behind
+        #[cfg(feature = "scripting")]
+        let script_result = self.fire_script_event(&ScriptEvent::Death(npc.def_id.clone()));
+        #[cfg(not(feature = "scripting"))]
+        let script_result = Ok(());
+        if killed_by_player {
+            self.frighten_npcs_near(npc.pos());
+        }
+        self.break_pack_morale(&npc)?;
+
+        let corpse = self.create_corpse(&npc)?;
+        self.current_level_mut().spawn_corpse(corpse)?;
+
+        if !killed_by_player {
+            return script_result;
+        }
+        self.player_add_experience(25);
+
+        // Monsters carry gold depending on how dangerous they are
+        let danger = npc_defs().get(&npc.def_id).map(|def| def.danger).unwrap_or(0);
+        if danger > 0 {
+            let amount = self.roll(&Roll::new(danger, DieSize::D6)).max(0) as u32;
+            self.player.character.gold += amount;
+            self.run_stats.gold_looted += amount;
+            self.log.info(LogData::GoldLooted { amount });
+        }
+
+        script_result
+    }
+
     /// Handles a player attacking an npc with a ranged weapon. Conducts all checks required to validate the ranged attack and then calls [GameState::player_attack_npc]
     /// Find side effects and returns [GameState::player_attack_npc].
     ///
@@ -116,10 +342,14 @@ impl GameState {
     /// * [GameOutcome::Fail] with [FailReason::EquipmentSlotEmpty] if the player has no weapon equipped.
     /// * [GameOutcome::Fail] with [FailReason::EquipmentSlotEmpty] if the player has no weapon equipped.
     /// * [GameOutcome::Fail] with [FailReason::OutOfRange] if the ranged weapon's range is not sufficient for the attack.
+    /// * [GameOutcome::Fail] with [FailReason::NoLineOfSight] if something blocks the shot.
     pub fn player_ranged_attack_npc(&mut self, npc_id: EntityId) -> GameResult {
         let Some(npc) = self.current_level().get_npc(npc_id) else {
             return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id))); // Target entity is not an npc
         };
+        if self.is_hidden(npc) {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id))); // The player cannot see what they aim at
+        }
 
         let Some(weapon_id) = self.player.character.weapon else {
             return Ok(GameOutcome::Fail(FailReason::EquipmentSlotEmpty)); // No weapon equipped
@@ -140,6 +370,10 @@ impl GameState {
             return Ok(GameOutcome::Fail(FailReason::OutOfRange)); // Bow attack out of range
         }
 
+        if !has_line_of_sight(self.player.character.pos(), npc.pos(), self.current_world()) {
+            return Ok(GameOutcome::Fail(FailReason::NoLineOfSight)); // Shot blocked by a wall
+        }
+
         self.player_attack_npc(npc_id)
     }
 
@@ -151,73 +385,286 @@ impl GameState {
     /// # Returns
     /// * [Ok] if the procedure was successful.
     pub fn npc_attack_player(&mut self, npc_id: EntityId) -> Result<(), GameError> {
-        let (npc_name, npc_damage) = {
+        // Attacking gives invisible NPCs away
+        self.reveal_npc(npc_id)?;
+
+        let (npc_name, npc_damage, npc_modifiers, npc_pos, npc_size, damage_type) = {
             let npc =
                 self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
-            (npc.base.name.to_string(), npc.stats.damage)
+            (
+                npc.base.name.to_string(),
+                npc.stats.damage,
+                npc.stats.attack_modifiers(),
+                npc.pos(),
+                npc.size,
+                self.npc_damage_type(npc),
+            )
         };
 
-        // Roll the damage and add the current level. This increases monster damage the deeper you go, increasing difficulty.
-        let rolled_damage = self.roll(&npc_damage.add_modifier(self.level_nr as i16)) as u16;
+        let attacker = npc_modifiers + self.level_scaling_modifiers();
+        let defender = self.player_defense_against(damage_type);
+        let rolled_damage = self.roll(&npc_damage).max(0) as u16;
 
-        let attack_result = self.resolve_attack(
-            rolled_damage,
-            5,
-            self.player.character.dodge_chance(),
-            self.get_player_armor_mitigation().unwrap_or(0),
-        );
+        let attack_result = resolve_attack(rolled_damage, attacker, defender, &mut self.rng);
 
         match attack_result {
             AttackDegree::Miss => {
-                self.log.info(LogData::NpcAttackMiss { npc_name });
+                self.log.info(LogData::NpcAttackMiss { npc_name: npc_name.clone() });
             }
-            AttackDegree::Hit(damage) => {
+            AttackDegree::Hit { damage, blocked } => {
                 self.player.character.take_damage(damage);
-                self.log.info(LogData::NpcAttackHit { npc_name, damage });
+                self.mark_hit(self.player.character.pos(), damage, false, true);
+                self.record_gauntlet_damage(damage);
+                self.record_damage_taken(damage, DamageSource::Npc(npc_name.clone()));
+                self.log.info(LogData::NpcAttackHit {
+                    npc_name: npc_name.clone(),
+                    damage,
+                    blocked,
+                });
             }
-            AttackDegree::CriticalHit(damage) => {
+            AttackDegree::CriticalHit { damage, blocked } => {
                 self.player.character.take_damage(damage);
-                self.log.info(LogData::NpcAttackHitCritical { npc_name, damage });
+                self.mark_hit(self.player.character.pos(), damage, true, true);
+                self.record_gauntlet_damage(damage);
+                self.record_damage_taken(damage, DamageSource::Npc(npc_name.clone()));
+                self.log.info(LogData::NpcAttackHitCritical {
+                    npc_name: npc_name.clone(),
+                    damage,
+                    blocked,
+                });
             }
         }
 
+        if attack_result != AttackDegree::Miss {
+            self.elemental_hit_on_player(damage_type)?;
+        }
+
+        // Large monsters knock the player back when they hit
+        if npc_size > 1 && attack_result != AttackDegree::Miss && self.player.character.is_alive() {
+            self.knock_back_player(npc_pos, &npc_name);
+        }
+
+        // Critical hits of large monsters frighten the player
+        if npc_size > 1
+            && matches!(attack_result, AttackDegree::CriticalHit { .. })
+            && self.player.character.is_alive()
+        {
+            self.apply_crowd_control(CrowdControlTarget::Player, CrowdControl::Feared, FEAR_TURNS)?;
+        }
+
         Ok(())
     }
 
-    /// Rolls to see if a dodg occurs.
-    fn dodge_roll(&mut self, dodge_chance: u8) -> bool {
-        self.roll(&Roll::new(1, DieSize::D100)) as u8 <= dodge_chance
-    }
+    /// Handles an NPC attacking another NPC (e.g. a companion attacking a monster).
+    ///
+    /// The target dies just like from the player's attacks, but the player gets no experience or gold for it.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if one of the NPCs could not be found in the current Level.
+    pub fn npc_attack_npc(&mut self, attacker_id: EntityId, target_id: EntityId) -> GameResult {
+        let attacker = self
+            .current_level()
+            .get_npc(attacker_id)
+            .ok_or(EngineError::NpcNotFound(attacker_id))?;
+        let (attacker_name, attacker_damage, attacker_modifiers, damage_type) = (
+            attacker.name().to_string(),
+            attacker.stats.damage,
+            attacker.stats.attack_modifiers(),
+            self.npc_damage_type(attacker),
+        );
 
-    /// Rolls to see if a critical strike occurs.
-    fn is_critical_strike(&mut self, crit_chance: u8) -> bool {
-        self.roll(&Roll::new(1, DieSize::D100)) as u8 <= crit_chance
+        let target =
+            self.current_level().get_npc(target_id).ok_or(EngineError::NpcNotFound(target_id))?;
+        let (target_name, defender) = (
+            target.name().to_string(),
+            target.stats.defense_against(damage_type, self.npc_affinities(target)),
+        );
+
+        let rolled_damage = self.roll(&attacker_damage).max(0) as u16;
+        let attack_result =
+            resolve_attack(rolled_damage, attacker_modifiers, defender, &mut self.rng);
+
+        let (damage, critical) = match attack_result {
+            AttackDegree::Miss => {
+                self.log.info(LogData::NpcAttackNpcMiss { attacker_name, target_name });
+                return Ok(GameOutcome::Success);
+            }
+            AttackDegree::Hit { damage, .. } => (damage, false),
+            AttackDegree::CriticalHit { damage, .. } => (damage, true),
+        };
+
+        let target = self
+            .current_level_mut()
+            .get_npc_mut(target_id)
+            .ok_or(EngineError::NpcNotFound(target_id))?;
+        target.stats.base.take_damage(damage);
+        let target = target.clone();
+        self.mark_hit(target.pos(), damage, critical, false);
+
+        self.log.info(LogData::NpcAttackNpcHit {
+            attacker_name,
+            target_name: target_name.clone(),
+            damage,
+        });
+        self.elemental_hit_on_npc(&target, damage_type)?;
+
+        if !target.stats.base.is_alive() {
+            self.npc_died(target, false)?;
+        }
+
+        Ok(GameOutcome::Success)
     }
 
-    /// Resolves all computation steps as part of attack. Returns the damage dealt (if any).
-    fn resolve_attack(
-        &mut self,
-        attacker_damage: u16,
-        attacker_crit_chance: u8,
-        defender_dodge_chance: u8,
-        defender_mitigation: u16,
-    ) -> AttackDegree {
-        if self.dodge_roll(defender_dodge_chance) {
-            return AttackDegree::Miss;
+    /// Resolves an area of effect (e.g. a fireball or an exploding barrel), damaging every entity caught in the blast.
+    ///
+    /// The damage is rolled once for the whole blast. It cannot be dodged, but each target's mitigation against its damage type is subtracted.
+    /// Only entities that are within the radius and in line of sight of the center are hit (see [has_line_of_sight]).
+    /// Friendly fire depends on the [BlastSource].
+    ///
+    /// # Side Effects
+    /// * `GameState::rng` is used.
+    /// * NPCs killed by the blast die (with rewards if the player caused it).
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if an NPC hit by the blast vanished from the current Level.
+    pub fn resolve_area_of_effect(&mut self, blast: &AreaOfEffect) -> Result<(), GameError> {
+        let rolled_damage = self.roll(&blast.damage).max(0) as u16;
+
+        let targets: Vec<EntityId> = self
+            .current_level()
+            .npcs
+            .iter()
+            .filter(|npc| self.is_caught_in_blast(blast, npc.footprint()))
+            .filter(|npc| match blast.source {
+                BlastSource::Player => npc.disposition != Disposition::Companion,
+                BlastSource::Npc(caster_id) => {
+                    npc.id() != caster_id
+                        && self
+                            .current_level()
+                            .get_npc(caster_id)
+                            .is_none_or(|caster| caster.disposition != npc.disposition)
+                }
+                BlastSource::Environment => true,
+            })
+            .map(|npc| npc.id())
+            .collect();
+
+        for npc_id in targets {
+            let npc = self
+                .current_level_mut()
+                .get_npc_mut(npc_id)
+                .ok_or(EngineError::NpcNotFound(npc_id))?;
+            let npc = npc.clone();
+            let mitigation =
+                npc.stats.defense_against(blast.damage_type, self.npc_affinities(&npc)).mitigation;
+            let damage = rolled_damage.saturating_add_signed(-mitigation);
+            let npc = self
+                .current_level_mut()
+                .get_npc_mut(npc_id)
+                .ok_or(EngineError::NpcNotFound(npc_id))?;
+            npc.stats.base.take_damage(damage);
+            let npc = npc.clone();
+            self.mark_hit(npc.pos(), damage, false, false);
+
+            if matches!(blast.source, BlastSource::Player) {
+                self.run_stats.damage_dealt += damage as u32;
+            }
+            self.log.info(LogData::BlastHitNpc {
+                npc_name: npc.name().to_string(),
+                damage,
+                blocked: rolled_damage.saturating_sub(damage),
+            });
+            self.elemental_hit_on_npc(&npc, blast.damage_type)?;
+
+            if !npc.stats.base.is_alive() {
+                self.npc_died(npc, matches!(blast.source, BlastSource::Player))?;
+            }
+        }
+
+        let player_hit = !matches!(blast.source, BlastSource::Player)
+            && self.is_caught_in_blast(blast, self.player.character.footprint());
+        if player_hit {
+            let mitigation = self.player_defense_against(blast.damage_type).mitigation;
+            let damage = rolled_damage.saturating_add_signed(-mitigation);
+            let source = match blast.source {
+                BlastSource::Npc(caster_id) => DamageSource::Npc(
+                    self.current_level()
+                        .get_npc(caster_id)
+                        .map_or_else(String::new, |caster| caster.name().to_string()),
+                ),
+                _ => DamageSource::Explosion,
+            };
+
+            self.player.character.take_damage(damage);
+            self.mark_hit(self.player.character.pos(), damage, false, true);
+            self.record_gauntlet_damage(damage);
+            self.record_damage_taken(damage, source);
+            self.log.info(LogData::BlastHitPlayer {
+                damage,
+                blocked: rolled_damage.saturating_sub(damage),
+            });
+            self.elemental_hit_on_player(blast.damage_type)?;
         }
 
-        let is_critical_strike = self.is_critical_strike(attacker_crit_chance);
+        Ok(())
+    }
 
-        if is_critical_strike {
-            let damage_unmitigated = 2 * attacker_damage;
-            let damage_mitigated = damage_unmitigated.saturating_sub(defender_mitigation);
+    /// Remembers a hit, so the UI can show its damage over the target (see [GameState::pending_hits]).
+    pub(crate) fn mark_hit(&mut self, point: Point, damage: u16, critical: bool, on_player: bool) {
+        self.pending_hits.push(HitMarker { point, damage, critical, on_player });
+    }
+
+    /// Whether any tile of the footprint is within the radius of the blast and can be seen from its center.
+    fn is_caught_in_blast(&self, blast: &AreaOfEffect, footprint: Footprint) -> bool {
+        footprint.points().any(|point| {
+            point.distance_squared_from(blast.center) <= blast.radius.pow(2)
+                && has_line_of_sight(blast.center, point, self.current_world())
+        })
+    }
 
-            AttackDegree::CriticalHit(damage_mitigated)
+    /// Modifiers that scale with the depth of the current level.
+    ///
+    /// Monster damage increases with each level, increasing difficulty the deeper you go. How fast depends on the [Difficulty](crate::core::difficulty::Difficulty) (1 per level on normal).
+    pub fn level_scaling_modifiers(&self) -> CombatModifiers {
+        CombatModifiers::damage(self.difficulty.depth_damage(self.level_nr))
+    }
+
+    /// Modifiers the player character contributes when attacking. Made up of their stats, active effects, the blessing of their weapon,
+    /// the perk of their class, the perks they picked and their melee or archery skill.
+    ///
+    /// Melee attacks scale with strength, ranged attacks with perception.
+    pub fn player_attack_modifiers(&self, ranged: bool) -> CombatModifiers {
+        let damage = if ranged {
+            self.player.character.attack_damage_bonus_ranged()
         } else {
-            let damage_mitigated = attacker_damage.saturating_sub(defender_mitigation);
+            self.player.character.attack_damage_bonus_melee()
+        };
+        let blessing = self
+            .player
+            .character
+            .weapon
+            .and_then(|weapon| self.items.get(&weapon.0))
+            .map_or(0, |weapon| weapon.blessing);
+
+        let perk = self.player.class.damage_bonus(ranged);
+        let skill = self.player.character.skill_damage_bonus(ranged);
+
+        CombatModifiers::damage(damage)
+            + CombatModifiers::damage(blessing + perk + skill)
+            + CombatModifiers::crit_chance(self.player.character.perk_crit_chance())
+    }
 
-            AttackDegree::Hit(damage_mitigated)
-        }
+    /// Modifiers the player character contributes when being attacked with damage of the given type.
+    /// Made up of their stats, active effects, armor (or its affinities against elemental damage) and perks.
+    ///
+    /// # Note
+    /// If the armor cannot be resolved, it is treated as if no armor was worn.
+    pub fn player_defense_against(&self, damage_type: DamageType) -> CombatModifiers {
+        let armor = self.get_player_armor_mitigation().unwrap_or(0) as i16;
+        let mitigation = self.player_affinities().mitigation(damage_type, armor)
+            + self.player.character.perk_mitigation();
+
+        CombatModifiers::defense(self.player.character.dodge_chance() as i16, mitigation)
     }
 
     /// Retrieves the player's weapon stats in a tuple.
@@ -232,7 +679,8 @@ impl GameState {
     /// * 0 - Damage (as [Roll])
     /// * 1 - Crit Chance (as [u8])
     /// * 2 - Range of the attack (as [AttackRange])
-    fn get_player_weapon_stats(&self) -> Result<(Roll, u8, AttackRange), GameError> {
+    /// * 3 - Type of the damage (as [DamageType])
+    fn get_player_weapon_stats(&self) -> Result<(Roll, u8, AttackRange, DamageType), GameError> {
         if let Some(weapon) = &self.player.character.weapon {
             let item =
                 self.get_item_by_id(weapon.0).ok_or(EngineError::UnregisteredItem(weapon.0))?;
@@ -241,28 +689,25 @@ impl GameState {
                 .ok_or(DataError::MissingItemDefinition(item.def_id))?;
 
             match item_def.kind {
-                GameItemKindDef::Weapon { damage, crit_chance, range } => {
-                    Ok((damage, crit_chance, range))
+                GameItemKindDef::Weapon { damage, crit_chance, range, damage_type } => {
+                    Ok((damage, crit_chance, range, damage_type))
                 }
                 _ => Err(GameError::from(EngineError::InvalidItem(item_def.kind))),
             }
         } else {
-            Ok((Roll::new(1, DieSize::D4), 5, None)) // If no weapon is equipped, fist damage is just 1d4.
+            Ok((Roll::new(1, DieSize::D4), 5, None, DamageType::Physical)) // If no weapon is equipped, fist damage is just 1d4.
         }
     }
 
     /// Retrieves the player's armor's mitigation statistic.
     ///
     /// # Errors
-    /// * [EngineError::UnregisteredItem] if the Player's weapon is not registered.
-    /// * [DataError::MissingItemDefinition] if the Player's weapon has no definition.
-    /// * [EngineError::InvalidItem] if the Player's item equipped as weapon is not a weapon.
+    /// * [EngineError::UnregisteredItem] if the Player's armor is not registered.
+    /// * [DataError::MissingItemDefinition] if the Player's armor has no definition.
+    /// * [EngineError::InvalidItem] if the Player's item equipped as armor is not an armor.
     ///
     /// # Returns
-    /// A tuple containing the statistics of the weapon
-    /// * 0 - Damage (as [Roll])
-    /// * 1 - Crit Chance (as [u8])
-    /// * 2 - Range of the attack (as [AttackRange])
+    /// The mitigation of the equipped armor, or `0` if no armor is worn.
     fn get_player_armor_mitigation(&self) -> Result<u16, GameError> {
         if let Some(armor) = &self.player.character.armor {
             let item =
@@ -272,7 +717,7 @@ impl GameState {
                 .ok_or(DataError::MissingItemDefinition(item.def_id))?;
 
             match item_def.kind {
-                GameItemKindDef::Armor { mitigation } => Ok(mitigation),
+                GameItemKindDef::Armor { mitigation, .. } => Ok(mitigation),
                 _ => Err(GameError::from(EngineError::InvalidItem(item_def.kind))),
             }
         } else {
@@ -280,3 +725,152 @@ impl GameState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, tiles::TileType, worldspace::Room};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Game on a single open room, with the player standing at (10, 10).
+    fn game_in_room() -> GameState {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+        game
+    }
+
+    fn spawn(game: &mut GameState, def_id: &str, pos: Point) -> EntityId {
+        let npc = game.create_npc(def_id.into(), pos).unwrap();
+        let npc_id = npc.id();
+        game.current_level_mut().spawn_npc(npc).unwrap();
+        npc_id
+    }
+
+    #[test]
+    fn guaranteed_dodge_misses() {
+        let mut rng = StdRng::seed_from_u64(73);
+
+        let result = resolve_attack(
+            10,
+            CombatModifiers::default(),
+            CombatModifiers::defense(100, 0),
+            &mut rng,
+        );
+
+        assert_eq!(result, AttackDegree::Miss);
+    }
+
+    #[test]
+    fn guaranteed_crit_multiplies_damage() {
+        let mut rng = StdRng::seed_from_u64(73);
+
+        let result = resolve_attack(
+            10,
+            CombatModifiers::crit_chance(100),
+            CombatModifiers::default(),
+            &mut rng,
+        );
+
+        assert_eq!(result, AttackDegree::CriticalHit { damage: 10 * CRIT_MULTIPLIER, blocked: 0 });
+    }
+
+    #[test]
+    fn mitigation_is_reported_as_blocked() {
+        let mut rng = StdRng::seed_from_u64(73);
+
+        let result = resolve_attack(
+            4,
+            CombatModifiers::damage(2),
+            CombatModifiers::defense(0, 10),
+            &mut rng,
+        );
+
+        assert_eq!(result, AttackDegree::Hit { damage: 0, blocked: 6 });
+    }
+
+    #[test]
+    fn modifiers_stack() {
+        let sum = CombatModifiers::damage(3)
+            + CombatModifiers::crit_chance(5)
+            + CombatModifiers::defense(10, 2)
+            + CombatModifiers::damage(-1);
+
+        assert_eq!(
+            sum,
+            CombatModifiers { damage: 2, crit_chance: 5, dodge_chance: 10, mitigation: 2 }
+        );
+    }
+
+    #[test]
+    fn fireballs_spare_the_player_their_companion_and_anyone_behind_walls() {
+        let mut game = game_in_room();
+        let goblin_id = spawn(&mut game, "goblin", Point::new(12, 10));
+        let wolf_id = spawn(&mut game, "wolf", Point::new(11, 11));
+        game.current_level_mut().get_npc_mut(wolf_id).unwrap().disposition = Disposition::Companion;
+        let hidden_id = spawn(&mut game, "goblin", Point::new(11, 13));
+        game.current_world_mut().get_tile_mut(Point::new(11, 12)).tile_type = TileType::Wall;
+
+        let fireball = AreaOfEffect {
+            center: Point::new(11, 10),
+            radius: 3,
+            damage: Roll::new(0, DieSize::D6).add_modifier(500),
+            damage_type: DamageType::Fire,
+            source: BlastSource::Player,
+        };
+        game.resolve_area_of_effect(&fireball).unwrap();
+
+        assert!(game.current_level().get_npc(goblin_id).is_none());
+        assert!(game.current_level().get_npc(wolf_id).is_some());
+        assert!(game.current_level().get_npc(hidden_id).is_some());
+        assert!(game.player.character.is_alive());
+    }
+
+    #[test]
+    fn explosions_hit_everyone_in_the_radius() {
+        let mut game = game_in_room();
+        let goblin_id = spawn(&mut game, "goblin", Point::new(12, 10));
+        let far_id = spawn(&mut game, "goblin", Point::new(20, 10));
+        let hp_before = game.player.character.stats.base.hp_current;
+
+        let barrel = AreaOfEffect {
+            center: Point::new(11, 10),
+            radius: 2,
+            damage: Roll::new(0, DieSize::D6).add_modifier(4),
+            damage_type: DamageType::Physical,
+            source: BlastSource::Environment,
+        };
+        game.resolve_area_of_effect(&barrel).unwrap();
+
+        let goblin = game.current_level().get_npc(goblin_id).unwrap();
+        assert!(goblin.stats.base.hp_current < goblin.stats.base.hp_max);
+        let far = game.current_level().get_npc(far_id).unwrap();
+        assert_eq!(far.stats.base.hp_current, far.stats.base.hp_max);
+        assert!(game.player.character.stats.base.hp_current < hp_before);
+    }
+
+    #[test]
+    fn npc_blasts_spare_the_caster_and_its_allies() {
+        let mut game = game_in_room();
+        let mage_id = spawn(&mut game, "dark_mage", Point::new(12, 10));
+        let goblin_id = spawn(&mut game, "goblin", Point::new(11, 11));
+        let wolf_id = spawn(&mut game, "wolf", Point::new(12, 11));
+        game.current_level_mut().get_npc_mut(wolf_id).unwrap().disposition = Disposition::Companion;
+
+        let blast = AreaOfEffect {
+            center: Point::new(11, 10),
+            radius: 2,
+            damage: Roll::new(0, DieSize::D6).add_modifier(500),
+            damage_type: DamageType::Arcane,
+            source: BlastSource::Npc(mage_id),
+        };
+        game.resolve_area_of_effect(&blast).unwrap();
+
+        assert!(game.current_level().get_npc(mage_id).is_some());
+        assert!(game.current_level().get_npc(goblin_id).is_some());
+        assert!(game.current_level().get_npc(wolf_id).is_none());
+        assert!(!game.player.character.is_alive());
+    }
+}
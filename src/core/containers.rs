@@ -0,0 +1,316 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        entity_logic::Entity,
+        game::GameState,
+        game_items::{GameItemId, GameItemKindDef},
+    },
+    data::item_defs::item_defs,
+    util::{
+        errors_results::{DataError, EngineError, FailReason, GameError, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+    world::coordinate_system::Point,
+};
+
+/// Number of items a chest can hold.
+pub const CHEST_CAPACITY: usize = 12;
+
+/// Something that holds items, which the player can move to and from their inventory.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Container {
+    /// A [TileType::Chest](crate::world::tiles::TileType::Chest) at the given point of the current level.
+    Chest(Point),
+
+    /// A bag in the player's inventory (see [GameItemKindDef::Bag]).
+    Bag(GameItemId),
+}
+
+impl Container {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Container::Chest(_) => "chest",
+            Container::Bag(_) => "bag",
+        }
+    }
+}
+
+/// Everything the player can do with a container, as listed by [GameState::container_offers].
+pub struct ContainerOffers {
+    /// Items in the container, which can be taken.
+    pub contents: Vec<GameItemId>,
+
+    /// Items of the inventory that can be put into the container.
+    pub storable: Vec<GameItemId>,
+
+    /// Labels of all options: "Take all" if the container is not empty, then taking each item, then putting in each item.
+    pub options: Vec<String>,
+}
+
+impl GameState {
+    /// Items held by the container.
+    ///
+    /// # Returns
+    /// * `None` if there is no chest at the point or the bag is not in the player's inventory.
+    pub fn container_contents(&self, container: Container) -> Option<&Vec<GameItemId>> {
+        match container {
+            Container::Chest(point) => self.current_level().chests.get(&point),
+            Container::Bag(item_id) => {
+                if !self.player.character.inventory.contains(&item_id) {
+                    return None;
+                }
+                self.items.get(&item_id).map(|item| &item.contents)
+            }
+        }
+    }
+
+    fn container_contents_mut(&mut self, container: Container) -> Option<&mut Vec<GameItemId>> {
+        match container {
+            Container::Chest(point) => self.current_level_mut().chests.get_mut(&point),
+            Container::Bag(item_id) => {
+                if !self.player.character.inventory.contains(&item_id) {
+                    return None;
+                }
+                self.items.get_mut(&item_id).map(|item| &mut item.contents)
+            }
+        }
+    }
+
+    /// Number of items the container can hold.
+    pub fn container_capacity(&self, container: Container) -> usize {
+        match container {
+            Container::Chest(_) => CHEST_CAPACITY,
+            Container::Bag(item_id) => match self.item_kind(item_id) {
+                Some(GameItemKindDef::Bag { capacity }) => capacity,
+                _ => 0,
+            },
+        }
+    }
+
+    /// Chests have to be next to the player. Bags are always in reach, since they are carried.
+    fn container_in_reach(&self, container: Container) -> bool {
+        match container {
+            Container::Chest(point) => {
+                point.distance_squared_from(self.player.character.pos()) == 1
+            }
+            Container::Bag(_) => true,
+        }
+    }
+
+    pub(crate) fn item_kind(&self, item_id: GameItemId) -> Option<GameItemKindDef> {
+        let item = self.items.get(&item_id)?;
+        item_defs().get(&item.def_id).map(|def| def.kind.clone())
+    }
+
+    pub(crate) fn container_item_name(
+        &self,
+        item_id: GameItemId,
+    ) -> Result<&'static str, GameError> {
+        let item = self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
+        let item_def = self
+            .get_item_def_by_id(&item.def_id)
+            .ok_or(DataError::MissingItemDefinition(item.def_id))?;
+        Ok(item_def.name)
+    }
+
+    /// Whether the item could be put into the container. Bags cannot hold other bags.
+    fn fits_into(&self, container: Container, item_id: GameItemId) -> bool {
+        match container {
+            Container::Chest(_) => true,
+            Container::Bag(_) => {
+                !matches!(self.item_kind(item_id), Some(GameItemKindDef::Bag { .. }))
+            }
+        }
+    }
+
+    /// Lists what the player can do with the container.
+    ///
+    /// # Returns
+    /// * `None` if the container does not exist.
+    pub fn container_offers(&self, container: Container) -> Option<ContainerOffers> {
+        let contents = self.container_contents(container)?.clone();
+        let storable: Vec<GameItemId> = self
+            .player
+            .character
+            .inventory
+            .iter()
+            .copied()
+            .filter(|item_id| self.fits_into(container, *item_id))
+            .collect();
+
+        let mut options = Vec::new();
+        if !contents.is_empty() {
+            options.push("Take all".to_string());
+        }
+        for item_id in &contents {
+            options.push(format!(
+                "Take {}",
+                self.container_item_name(*item_id).unwrap_or("<Invalid Item>")
+            ));
+        }
+        for item_id in &storable {
+            options.push(format!(
+                "Put in {}",
+                self.container_item_name(*item_id).unwrap_or("<Invalid Item>")
+            ));
+        }
+
+        Some(ContainerOffers { contents, storable, options })
+    }
+
+    /// Moves an item from the container into the player's inventory.
+    ///
+    /// # Errors
+    /// * [EngineError::UnregisteredItem] if the item is not registered.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoInteraction] if the container does not exist, is out of reach or does not hold the item.
+    /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player cannot carry the item.
+    /// * [GameOutcome::Success] if the item was taken.
+    pub fn take_from_container(&mut self, container: Container, item_id: GameItemId) -> GameResult {
+        if !self.container_in_reach(container)
+            || !self.container_contents(container).is_some_and(|items| items.contains(&item_id))
+        {
+            return Ok(GameOutcome::Fail(FailReason::NoInteraction));
+        }
+        if self.player.character.inventory.is_full() {
+            return Ok(GameOutcome::Fail(FailReason::InventoryFull));
+        }
+
+        let item_name = self.container_item_name(item_id)?;
+        if let Some(contents) = self.container_contents_mut(container) {
+            contents.retain(|content| *content != item_id);
+        }
+        self.add_item_to_inv(item_id)?;
+
+        self.log.info(LogData::ItemTakenFromContainer {
+            item_name: item_name.to_string(),
+            container_name: container.name().to_string(),
+        });
+        Ok(GameOutcome::Success)
+    }
+
+    /// Moves as many items from the container into the player's inventory as they can carry. Takes a single round.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoInteraction] if the container does not exist, is out of reach or is empty.
+    /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player cannot carry any of the items.
+    /// * [GameOutcome::Success] if at least one item was taken.
+    pub fn take_all_from_container(&mut self, container: Container) -> GameResult {
+        let Some(contents) = self.container_contents(container).cloned() else {
+            return Ok(GameOutcome::Fail(FailReason::NoInteraction));
+        };
+        if !self.container_in_reach(container) || contents.is_empty() {
+            return Ok(GameOutcome::Fail(FailReason::NoInteraction));
+        }
+        if self.player.character.inventory.is_full() {
+            return Ok(GameOutcome::Fail(FailReason::InventoryFull));
+        }
+
+        let mut taken = 0;
+        for item_id in contents {
+            if self.player.character.inventory.insert(item_id).is_none() {
+                self.log.info(LogData::InventoryFull);
+                break;
+            }
+            if let Some(contents) = self.container_contents_mut(container) {
+                contents.retain(|content| *content != item_id);
+            }
+            taken += 1;
+        }
+
+        self.log.info(LogData::TookAllFromContainer {
+            amount: taken,
+            container_name: container.name().to_string(),
+        });
+        Ok(GameOutcome::Success)
+    }
+
+    /// Moves an item from the player's inventory into the container.
+    ///
+    /// # Errors
+    /// * [EngineError::ItemNotInInventory] if the item is not in the player's inventory.
+    /// * [EngineError::UnregisteredItem] if the item is not registered.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoInteraction] if the container does not exist or is out of reach, or the item is a bag that should go into a bag.
+    /// * [GameOutcome::Fail] with [FailReason::ContainerFull] if the container cannot hold any more items.
+    /// * [GameOutcome::Success] if the item was put in.
+    pub fn put_into_container(&mut self, container: Container, item_id: GameItemId) -> GameResult {
+        if !self.player.character.inventory.contains(&item_id) {
+            return Err(GameError::from(EngineError::ItemNotInInventory(item_id)));
+        }
+        let Some(contents) = self.container_contents(container) else {
+            return Ok(GameOutcome::Fail(FailReason::NoInteraction));
+        };
+        if !self.container_in_reach(container) || !self.fits_into(container, item_id) {
+            return Ok(GameOutcome::Fail(FailReason::NoInteraction));
+        }
+        if contents.len() >= self.container_capacity(container) {
+            return Ok(GameOutcome::Fail(FailReason::ContainerFull));
+        }
+
+        let item_name = self.container_item_name(item_id)?;
+        self.remove_item_from_inv(item_id)?;
+        if let Some(contents) = self.container_contents_mut(container) {
+            contents.push(item_id);
+        }
+
+        self.log.info(LogData::ItemPutIntoContainer {
+            item_name: item_name.to_string(),
+            container_name: container.name().to_string(),
+        });
+        Ok(GameOutcome::Success)
+    }
+
+    /// Whether the item is a bag that still holds items. Those cannot leave the game (e.g. into the stash), or their contents would be lost.
+    pub fn is_filled_bag(&self, item_id: GameItemId) -> bool {
+        self.items.get(&item_id).is_some_and(|item| !item.contents.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, tiles::TileType};
+
+    #[test]
+    fn chests_and_bags_hold_items() {
+        let mut game = GameState::default();
+        game.levels.insert(0, Level::new());
+        let level = game.current_level_mut();
+        for x in 0..3 {
+            level.world.get_tile_mut(Point::new(x, 0)).tile_type = TileType::Floor;
+        }
+
+        let contents = game.register_items(&["food_bread".into(), "food_apple".into()]).unwrap();
+        game.current_level_mut().spawn_chest(Point::new(1, 0), contents).unwrap();
+        let chest = Container::Chest(Point::new(1, 0));
+
+        let offers = game.container_offers(chest).unwrap();
+        assert_eq!(offers.options[0], "Take all");
+        assert_eq!(offers.contents.len(), 2);
+        assert!(matches!(game.take_all_from_container(chest), Ok(GameOutcome::Success)));
+        assert_eq!(game.player.character.inventory.len(), 2);
+        assert!(game.container_contents(chest).unwrap().is_empty());
+
+        let bag_id = game.register_item(&"misc_bag_leather".to_string()).unwrap();
+        game.add_item_to_inv(bag_id).unwrap();
+        let bag = Container::Bag(bag_id);
+        let bread = game.player.character.inventory.get(0, 'a').unwrap();
+
+        // The bag itself is not offered to be put into itself
+        assert_eq!(game.container_offers(bag).unwrap().storable.len(), 2);
+        assert!(matches!(game.put_into_container(bag, bread), Ok(GameOutcome::Success)));
+        assert!(!game.player.character.inventory.contains(&bread));
+        assert!(game.is_filled_bag(bag_id));
+        assert!(matches!(
+            game.put_into_container(bag, bag_id),
+            Ok(GameOutcome::Fail(FailReason::NoInteraction))
+        ));
+
+        assert!(matches!(game.take_from_container(bag, bread), Ok(GameOutcome::Success)));
+        assert!(game.player.character.inventory.contains(&bread));
+    }
+}
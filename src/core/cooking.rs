@@ -0,0 +1,164 @@
+use rand::Rng;
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{entity_logic::Entity, game::GameState, game_items::GameItemId},
+    data::{
+        item_defs::item_defs,
+        recipes::{BURNT_FOOD, get_cooking_recipe},
+    },
+    util::{
+        errors_results::{DataError, EngineError, FailReason, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+    world::{
+        coordinate_system::{Direction, Point},
+        tiles::TileType,
+    },
+};
+
+impl GameState {
+    /// Checks whether there is a campfire on a tile adjacent to the player.
+    pub fn is_next_to_campfire(&self) -> bool {
+        let player_pos = self.player.character.pos();
+
+        Direction::iter().any(|direction| {
+            self.current_world().get_tile(player_pos.get_adjacent(direction)).tile_type
+                == TileType::Campfire
+        })
+    }
+
+    /// Collects the items in the player's inventory that have a cooking recipe.
+    ///
+    /// # Returns
+    /// The ids of the cookable items together with their names, in inventory order.
+    pub fn cookable_items(&self) -> Vec<(GameItemId, String)> {
+        self.player
+            .character
+            .inventory
+            .iter()
+            .filter_map(|item_id| {
+                let item = self.items.get(item_id)?;
+                get_cooking_recipe(&item.def_id)?;
+                let item_def = item_defs().get(&item.def_id)?;
+                Some((*item_id, item_def.name.to_string()))
+            })
+            .collect()
+    }
+
+    /// Cooks a raw food item from the player's inventory at an adjacent campfire.
+    ///
+    /// The item is transformed in place into the result of its [CookingRecipe](crate::data::recipes::CookingRecipe).
+    /// Depending on the recipe's burn chance, the food is burnt instead.
+    ///
+    /// # Errors
+    /// * [EngineError::ItemNotInInventory] if the item is not in the player's inventory.
+    /// * [EngineError::UnregisteredItem] if the item is not registered.
+    /// * [DataError::MissingItemDefinition] if the raw or the cooked item is not defined.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoCampfireNearby] if the player is not next to a campfire.
+    /// * [GameOutcome::Fail] with [FailReason::NotCookable] if the item has no cooking recipe.
+    /// * [GameOutcome::Success] if the food was cooked (or burnt).
+    pub fn cook_item(&mut self, item_id: GameItemId) -> GameResult {
+        if !self.player.character.inventory.contains(&item_id) {
+            return Err(EngineError::ItemNotInInventory(item_id).into());
+        }
+
+        if !self.is_next_to_campfire() {
+            return Ok(GameOutcome::Fail(FailReason::NoCampfireNearby));
+        }
+
+        let item = self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
+        let Some(recipe) = get_cooking_recipe(&item.def_id) else {
+            return Ok(GameOutcome::Fail(FailReason::NotCookable(item_id)));
+        };
+        let raw_def = self
+            .get_item_def_by_id(&item.def_id)
+            .ok_or(DataError::MissingItemDefinition(item.def_id))?;
+
+        let burnt = self.rng.random_range(0..100) < recipe.burn_chance;
+        let result_def_id = if burnt { BURNT_FOOD } else { recipe.result }.to_string();
+        let result_def = self
+            .get_item_def_by_id(&result_def_id)
+            .ok_or_else(|| DataError::MissingItemDefinition(result_def_id.clone()))?;
+
+        if let Some(item) = self.items.get_mut(&item_id) {
+            item.def_id = result_def_id;
+        }
+
+        let raw_name = raw_def.name.to_string();
+        if burnt {
+            self.log.info(LogData::FoodBurnt { raw_name });
+        } else {
+            self.log
+                .info(LogData::FoodCooked { raw_name, cooked_name: result_def.name.to_string() });
+        }
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// Uses up a fire starter to build a campfire on a free floor tile next to the player.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoFreeSpace] if there is no free floor tile next to the player.
+    /// * [GameOutcome::Success] if the campfire was built.
+    pub fn use_fire_starter(&mut self, item_id: GameItemId) -> GameResult {
+        let player_pos = self.player.character.pos();
+
+        let free_point: Option<Point> =
+            Direction::iter().map(|direction| player_pos.get_adjacent(direction)).find(|point| {
+                self.current_world().get_tile(*point).tile_type == TileType::Floor
+                    && self.current_level().is_available(*point)
+                    && self.current_level().get_corpse_at(*point).is_none()
+            });
+
+        let Some(point) = free_point else {
+            return Ok(GameOutcome::Fail(FailReason::NoFreeSpace));
+        };
+
+        self.remove_item_from_inv(item_id)?;
+        self.deregister_item(item_id)?;
+
+        self.current_world_mut().get_tile_mut(point).tile_type = TileType::Campfire;
+        self.log.info(LogData::CampfireBuilt);
+
+        Ok(GameOutcome::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::entity_logic::Movable,
+        world::{level::Level, worldspace::Room},
+    };
+
+    #[test]
+    fn cooking_transforms_raw_food_at_campfire() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        game.levels.insert(0, level);
+        game.player.character.move_to(Point::new(50, 7));
+
+        let raw_id = game.register_item(&"food_meat_raw".to_string()).unwrap();
+        game.add_item_to_inv(raw_id).unwrap();
+        let apple_id = game.register_item(&"food_apple".to_string()).unwrap();
+        game.add_item_to_inv(apple_id).unwrap();
+
+        assert_eq!(game.cookable_items().len(), 1);
+        assert!(matches!(
+            game.cook_item(raw_id),
+            Ok(GameOutcome::Fail(FailReason::NoCampfireNearby))
+        ));
+
+        game.current_world_mut().get_tile_mut(Point::new(51, 7)).tile_type = TileType::Campfire;
+        assert!(matches!(game.cook_item(raw_id), Ok(GameOutcome::Success)));
+
+        let cooked = game.get_item_by_id(raw_id).unwrap();
+        assert!(cooked.def_id == "food_meat" || cooked.def_id == BURNT_FOOD);
+        assert!(game.cookable_items().is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityBase, EntityId, Npc, get_npc_def_by_id},
+        game::GameState,
+        game_items::GameItemId,
+    },
+    data::{loot_tables::get_loot_table_by_id, uniques::get_unique_def_by_id},
+    render::style::{Color, Style},
+    util::{
+        errors_results::{DataError, EngineError, GameError, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+    world::coordinate_system::Point,
+};
+
+/// Number of rounds a corpse lies on the ground before it decays, together with all loot left on it.
+pub const CORPSE_DECAY_ROUNDS: u64 = 100;
+
+/// Glyph used to render corpses.
+const CORPSE_GLYPH: char = '%';
+
+/// Remains of a killed NPC. Corpses don't block movement and are looted by walking over them.
+#[derive(Clone)]
+pub struct Corpse {
+    pub base: EntityBase,
+    /// Registered items that can still be looted from the corpse.
+    pub loot: Vec<GameItemId>,
+    /// Round number in which the corpse decays.
+    pub decays_at: u64,
+}
+
+impl Entity for Corpse {
+    fn name(&self) -> &str {
+        &self.base.name
+    }
+
+    fn id(&self) -> EntityId {
+        self.base.id
+    }
+
+    fn pos(&self) -> Point {
+        self.base.pos
+    }
+}
+
+impl GameState {
+    /// Creates the corpse of a killed NPC, rolling its loot from the NPC's loot table at the current depth.
+    ///
+    /// # Errors
+    /// * [DataError::MissingNpcDefinition] if the NPC has no definition.
+    /// * [DataError::MissingLootTable] if the NPC's loot table is not defined.
+    /// * [DataError::MissingItemDefinition] if the loot table references an item that is not defined.
+    pub fn create_corpse(&mut self, npc: &Npc) -> Result<Corpse, GameError> {
+        let npc_def = get_npc_def_by_id(npc.def_id.clone())
+            .ok_or_else(|| DataError::MissingNpcDefinition(npc.def_id.clone()))?;
+
+        // Uniques drop their special loot instead
+        let loot_table_id = npc
+            .unique
+            .as_deref()
+            .and_then(get_unique_def_by_id)
+            .map_or(npc_def.loot_table, |unique_def| unique_def.loot_table);
+        let loot_table = get_loot_table_by_id(loot_table_id)
+            .ok_or_else(|| DataError::MissingLootTable(loot_table_id.to_string()))?;
+
+        let mut loot = Vec::new();
+        for item_def_id in loot_table.roll(self.level_nr, &mut self.rng) {
+            loot.push(self.register_found_item(&item_def_id.to_string())?);
+        }
+
+        let entity_id = self.id_system.next_entity_id();
+        let style = Style::default().fg(npc.base.style.fg.unwrap_or(Color::Gray));
+
+        Ok(Corpse {
+            base: EntityBase {
+                id: entity_id,
+                name: format!("{} Corpse", npc.name()),
+                pos: npc.pos(),
+                glyph: CORPSE_GLYPH,
+                style,
+            },
+            loot,
+            decays_at: self.round_nr + CORPSE_DECAY_ROUNDS,
+        })
+    }
+
+    /// Moves all loot of a corpse into the player's inventory. Items that don't fit stay on the corpse.
+    ///
+    /// # Errors
+    /// * [EngineError::CorpseNotFound] if there is no corpse with the given id in the current level.
+    /// * [EngineError::UnregisteredItem] if an item on the corpse is not registered.
+    /// * [DataError::MissingItemDefinition] if an item on the corpse has no definition.
+    pub fn loot_corpse(&mut self, corpse_id: EntityId) -> GameResult {
+        let corpse = self
+            .current_level()
+            .get_corpse(corpse_id)
+            .ok_or(EngineError::CorpseNotFound(corpse_id))?;
+        let corpse_name = corpse.name().to_string();
+        let loot = corpse.loot.clone();
+
+        let mut looted: Vec<GameItemId> = Vec::new();
+        for item_id in loot {
+            let item =
+                self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
+            let item_def = self
+                .get_item_def_by_id(&item.def_id)
+                .ok_or(DataError::MissingItemDefinition(item.def_id))?;
+
+            match self.add_item_to_inv(item_id)? {
+                GameOutcome::Success => {
+                    looted.push(item_id);
+                    self.log.info(LogData::CorpseLooted {
+                        corpse_name: corpse_name.clone(),
+                        item_name: item_def.name.to_string(),
+                    });
+                }
+                // Inventory is full, the remaining loot stays on the corpse.
+                GameOutcome::Fail(_) => break,
+            }
+        }
+
+        if let Some(corpse) = self.current_level_mut().get_corpse_mut(corpse_id) {
+            corpse.loot.retain(|item_id| !looted.contains(item_id));
+        }
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// Removes all corpses (in all levels) that have reached their decay round. Loot left on them is deregistered.
+    pub fn decay_corpses(&mut self) {
+        let round_nr = self.round_nr;
+        let mut decayed_loot: Vec<GameItemId> = Vec::new();
+
+        for level in &mut self.levels {
+            let decayed: Vec<EntityId> = level
+                .corpses
+                .iter()
+                .filter(|corpse| corpse.decays_at <= round_nr)
+                .map(|corpse| corpse.id())
+                .collect();
+
+            for corpse_id in decayed {
+                if let Some(corpse) = level.get_corpse(corpse_id) {
+                    decayed_loot.extend(&corpse.loot);
+                }
+                level.despawn(corpse_id);
+            }
+        }
+
+        for item_id in decayed_loot {
+            let _ = self.deregister_item(item_id);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use rand::{Rng, seq::IndexedRandom};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use crate::{
+    ai::{
+        behavior::direction_toward,
+        npc_ai::{AGGRO_RADIUS, NpcActionKind},
+    },
+    core::{
+        entity_logic::{Disposition, Entity, EntityId},
+        game::GameState,
+        resistances::Ailment,
+    },
+    util::{
+        errors_results::{EngineError, GameError},
+        text_log::LogData,
+    },
+    world::coordinate_system::Direction,
+};
+
+/// Number of turns a confusing hit leaves its target confused.
+pub const CONFUSION_TURNS: u8 = 4;
+
+/// Chance (in percent) of an arcane hit to confuse its target.
+pub const ARCANE_CONFUSE_CHANCE: u32 = 25;
+
+/// Chance (in percent) of a confused NPC or player to stumble in a random direction instead of doing what they intended.
+pub const CONFUSED_STUMBLE_CHANCE: u32 = 50;
+
+/// Statuses that take away the control over an NPC or the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum CrowdControl {
+    /// Moves in random directions and may hit whoever is standing there.
+    Confused,
+    /// Runs away from its enemies.
+    Feared,
+}
+
+impl CrowdControl {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrowdControl::Confused => "confused",
+            CrowdControl::Feared => "frightened",
+        }
+    }
+}
+
+/// Remaining turns of each [CrowdControl] status of an NPC or the player.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CrowdControlTurns {
+    pub confused: u8,
+    pub feared: u8,
+}
+
+impl CrowdControlTurns {
+    pub fn turns(&self, status: CrowdControl) -> u8 {
+        match status {
+            CrowdControl::Confused => self.confused,
+            CrowdControl::Feared => self.feared,
+        }
+    }
+
+    pub fn is_active(&self, status: CrowdControl) -> bool {
+        self.turns(status) > 0
+    }
+
+    /// Puts the status on for the given number of turns. A longer status that is already active is kept.
+    pub fn apply(&mut self, status: CrowdControl, turns: u8) {
+        let remaining = match status {
+            CrowdControl::Confused => &mut self.confused,
+            CrowdControl::Feared => &mut self.feared,
+        };
+        *remaining = (*remaining).max(turns);
+    }
+
+    /// Counts down all statuses by one turn.
+    ///
+    /// # Returns
+    /// The statuses that ran out.
+    pub fn tick(&mut self) -> Vec<CrowdControl> {
+        let mut expired = Vec::new();
+        for (remaining, status) in
+            [(&mut self.confused, CrowdControl::Confused), (&mut self.feared, CrowdControl::Feared)]
+        {
+            if *remaining == 1 {
+                expired.push(status);
+            }
+            *remaining = remaining.saturating_sub(1);
+        }
+        expired
+    }
+}
+
+/// Who a [CrowdControl] status is applied to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrowdControlTarget {
+    Player,
+    Npc(EntityId),
+}
+
+impl GameState {
+    /// Puts the status on the target for the given number of turns. Fear can be resisted (see [Ailment::Fear]).
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the targeted NPC is not on the current level.
+    pub fn apply_crowd_control(
+        &mut self,
+        target: CrowdControlTarget,
+        status: CrowdControl,
+        turns: u8,
+    ) -> Result<(), GameError> {
+        let resistances = match target {
+            CrowdControlTarget::Player => self.player_resistances(),
+            CrowdControlTarget::Npc(npc_id) => self.npc_resistances(npc_id),
+        };
+        let resisted =
+            status == CrowdControl::Feared && self.roll_resistance(resistances, Ailment::Fear);
+
+        let message = match target {
+            CrowdControlTarget::Player if resisted => {
+                LogData::PlayerResists { ailment: Ailment::Fear }
+            }
+            CrowdControlTarget::Player => {
+                self.player.character.crowd_control.apply(status, turns);
+                LogData::PlayerCrowdControlled { status }
+            }
+            CrowdControlTarget::Npc(npc_id) => {
+                let npc = self
+                    .current_level_mut()
+                    .get_npc_mut(npc_id)
+                    .ok_or(EngineError::NpcNotFound(npc_id))?;
+                let npc_name = npc.name().to_string();
+                if resisted {
+                    LogData::NpcResists { npc_name, ailment: Ailment::Fear }
+                } else {
+                    npc.crowd_control.apply(status, turns);
+                    LogData::NpcCrowdControlled { npc_name, status }
+                }
+            }
+        };
+        self.log.info(message);
+        Ok(())
+    }
+
+    /// Counts down the player's statuses and tells them about every status that wore off.
+    pub fn tick_player_crowd_control(&mut self) {
+        for status in self.player.character.crowd_control.tick() {
+            self.log.info(LogData::CrowdControlEnded { status });
+        }
+    }
+
+    /// The direction the player actually moves in when they try to move in the given direction.
+    ///
+    /// A frightened player runs away from the closest hostile NPC they can see. A confused player stumbles in a random
+    /// direction half of the time (see [CONFUSED_STUMBLE_CHANCE]).
+    pub fn controlled_direction(&mut self, direction: Direction) -> Direction {
+        let crowd_control = self.player.character.crowd_control;
+
+        if crowd_control.is_active(CrowdControl::Feared)
+            && let Some(direction) = self.fleeing_direction()
+        {
+            return direction;
+        }
+
+        if crowd_control.is_active(CrowdControl::Confused)
+            && self.rng.random_range(0..100) < CONFUSED_STUMBLE_CHANCE
+        {
+            let directions: Vec<Direction> = Direction::iter().collect();
+            return *directions.choose(&mut self.rng).unwrap_or(&direction);
+        }
+
+        direction
+    }
+
+    /// Direction away from the closest hostile NPC within [AGGRO_RADIUS] of the player, if there is one.
+    fn fleeing_direction(&self) -> Option<Direction> {
+        let player_pos = self.player.character.pos();
+        self.current_level()
+            .npcs
+            .iter()
+            .filter(|npc| npc.disposition == Disposition::Hostile && !self.is_hidden(npc))
+            .map(|npc| (npc.pos(), npc.pos().distance_squared_from(player_pos)))
+            .filter(|(_, distance)| *distance <= AGGRO_RADIUS.pow(2))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(npc_pos, _)| direction_toward(npc_pos, player_pos))
+    }
+
+    /// A confused NPC stumbles in a random direction. If someone stands there, the NPC hits them instead.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not on the current level.
+    pub(crate) fn npc_stumble(&mut self, npc_id: EntityId) -> Result<NpcActionKind, GameError> {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let footprint = npc.footprint();
+
+        let directions: Vec<Direction> = Direction::iter().collect();
+        let Some(&direction) = directions.choose(&mut self.rng) else {
+            return Ok(NpcActionKind::Wait);
+        };
+
+        // The tiles the NPC would move onto, which are outside its own footprint
+        let target_points: Vec<_> = footprint
+            .points()
+            .map(|point| point.get_adjacent(direction))
+            .filter(|point| !footprint.contains(*point))
+            .collect();
+
+        if target_points.contains(&self.player.character.pos()) {
+            return Ok(NpcActionKind::Attack);
+        }
+        let other_npc = target_points
+            .iter()
+            .find_map(|point| self.current_level().get_npc_at(*point))
+            .filter(|other_id| *other_id != npc_id);
+
+        Ok(match other_npc {
+            Some(other_id) => NpcActionKind::AttackNpc(other_id),
+            None => NpcActionKind::Move(direction),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{coordinate_system::Point, level::Level, worldspace::Room};
+
+    #[test]
+    fn statuses_wear_off_and_steer_the_player() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        let goblin = game.create_npc("goblin".into(), Point::new(12, 10)).unwrap();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+
+        // Frightened, the player runs away from the goblin, whichever way they try to go
+        game.player.character.crowd_control.apply(CrowdControl::Feared, 2);
+        assert_eq!(game.controlled_direction(Direction::Right), Direction::Left);
+
+        game.tick_player_crowd_control();
+        assert!(game.player.character.crowd_control.is_active(CrowdControl::Feared));
+        game.tick_player_crowd_control();
+        assert!(!game.player.character.crowd_control.is_active(CrowdControl::Feared));
+        assert_eq!(game.controlled_direction(Direction::Right), Direction::Right);
+
+        // A longer status is not cut short
+        let mut turns = CrowdControlTurns::default();
+        turns.apply(CrowdControl::Confused, 4);
+        turns.apply(CrowdControl::Confused, 2);
+        assert_eq!(turns.turns(CrowdControl::Confused), 4);
+    }
+}
@@ -0,0 +1,136 @@
+use rand::Rng;
+
+use crate::{
+    core::{
+        game::GameState,
+        game_items::{GameItemId, GameItemKindDef},
+    },
+    data::item_defs::GameItemDefId,
+    util::{
+        errors_results::{FailReason, GameError, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+};
+
+/// Chance (in percent) that a weapon or armor found in the dungeon is cursed.
+pub const CURSE_CHANCE: u32 = 10;
+
+impl GameState {
+    /// Registers an item found in the dungeon (see [GameState::register_item]).
+    /// With a chance of [CURSE_CHANCE] percent, weapons and armor are cursed. The curse stays hidden until the item is equipped.
+    ///
+    /// # Errors
+    /// * [DataError::MissingItemDefinition](crate::util::errors_results::DataError::MissingItemDefinition) if the item is not defined.
+    pub fn register_found_item(&mut self, def_id: &GameItemDefId) -> Result<GameItemId, GameError> {
+        let item_id = self.register_item(def_id)?;
+
+        let equippable = matches!(
+            self.item_kind(item_id),
+            Some(GameItemKindDef::Weapon { .. } | GameItemKindDef::Armor { .. })
+        );
+        if equippable
+            && self.rng.random_range(0..100) < CURSE_CHANCE
+            && let Some(item) = self.items.get_mut(&item_id)
+        {
+            item.cursed = true;
+        }
+
+        Ok(item_id)
+    }
+
+    /// Registers a found item for each of the given definitions (see [GameState::register_found_item]).
+    pub fn register_found_items(
+        &mut self,
+        def_ids: &[GameItemDefId],
+    ) -> Result<Vec<GameItemId>, GameError> {
+        def_ids.iter().map(|def_id| self.register_found_item(def_id)).collect()
+    }
+
+    pub fn is_cursed(&self, item_id: GameItemId) -> bool {
+        self.get_item_by_id(item_id).is_some_and(|item| item.cursed)
+    }
+
+    /// Tells the player that the item they just equipped is cursed, if it is.
+    pub(crate) fn reveal_curse(&mut self, item_id: GameItemId) -> Result<(), GameError> {
+        if self.is_cursed(item_id) {
+            let item_name = self.container_item_name(item_id)?;
+            self.log.info(LogData::CurseRevealed { item_name: item_name.to_string() });
+        }
+        Ok(())
+    }
+
+    /// Lifts the curses of the player's equipped weapon and armor.
+    ///
+    /// # Returns
+    /// Whether any curse was lifted.
+    pub fn remove_curses(&mut self) -> bool {
+        let equipped = [
+            self.player.character.weapon.map(|weapon| weapon.0),
+            self.player.character.armor.map(|armor| armor.0),
+        ];
+
+        let mut lifted = false;
+        for item_id in equipped.into_iter().flatten() {
+            if let Some(item) = self.items.get_mut(&item_id)
+                && item.cursed
+            {
+                item.cursed = false;
+                lifted = true;
+            }
+        }
+
+        if lifted {
+            self.log.info(LogData::CursesLifted);
+        }
+        lifted
+    }
+
+    /// The player reads a scroll that lifts the curses of their equipment. The scroll is used up.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NothingCursed] if nothing equipped is cursed. The scroll is kept.
+    /// * [GameOutcome::Success] if the curses were lifted.
+    pub fn read_remove_curse(&mut self, item_id: GameItemId) -> GameResult {
+        if !self.remove_curses() {
+            return Ok(GameOutcome::Fail(FailReason::NothingCursed));
+        }
+
+        self.remove_item_from_inv(item_id)?;
+        self.deregister_item(item_id)?;
+        Ok(GameOutcome::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursed_equipment_stays_on_until_uncursed() {
+        let mut game = GameState::default();
+        let sword = game.register_item(&"weapon_short_sword".to_string()).unwrap();
+        let dagger = game.register_item(&"weapon_dagger".to_string()).unwrap();
+        let scroll = game.register_item(&"scroll_remove_curse".to_string()).unwrap();
+        game.items.get_mut(&sword).unwrap().cursed = true;
+        for item_id in [sword, dagger, scroll] {
+            game.add_item_to_inv(item_id).unwrap();
+        }
+
+        assert!(matches!(game.use_item(scroll), Ok(GameOutcome::Fail(FailReason::NothingCursed))));
+
+        assert!(matches!(game.use_item(sword), Ok(GameOutcome::Success)));
+        assert!(matches!(
+            game.unequip_weapon(),
+            Ok(GameOutcome::Fail(FailReason::EquipmentCursed))
+        ));
+        assert!(matches!(
+            game.use_item(dagger),
+            Ok(GameOutcome::Fail(FailReason::EquipmentCursed))
+        ));
+
+        assert!(matches!(game.use_item(scroll), Ok(GameOutcome::Success)));
+        assert!(!game.is_cursed(sword));
+        assert!(!game.player.character.inventory.contains(&scroll));
+        assert!(matches!(game.unequip_weapon(), Ok(GameOutcome::Success)));
+    }
+}
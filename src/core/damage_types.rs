@@ -0,0 +1,179 @@
+use rand::Rng;
+use serde::Serialize;
+
+use crate::{
+    core::{
+        crowd_control::{ARCANE_CONFUSE_CHANCE, CONFUSION_TURNS, CrowdControl, CrowdControlTarget},
+        entity_logic::{Entity, Npc},
+        game::GameState,
+        game_items::GameItemKindDef,
+    },
+    data::npc_defs::npc_defs,
+    util::{errors_results::GameError, text_log::LogData},
+};
+
+/// Kind of damage an attack deals. Armor and monsters mitigate each kind differently (see [DamageAffinities]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Fire,
+    Cold,
+    Poison,
+    Arcane,
+}
+
+impl DamageType {
+    /// The damage types that are not physical.
+    pub const ELEMENTS: [DamageType; 4] =
+        [DamageType::Fire, DamageType::Cold, DamageType::Poison, DamageType::Arcane];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Physical => "physical",
+            DamageType::Fire => "fire",
+            DamageType::Cold => "cold",
+            DamageType::Poison => "poison",
+            DamageType::Arcane => "arcane",
+        }
+    }
+
+    /// What the damage does to whoever it hits, used in the log (e.g. "The Zombie is burned!").
+    pub fn effect(&self) -> &'static str {
+        match self {
+            DamageType::Physical => "hurt",
+            DamageType::Fire => "burned",
+            DamageType::Cold => "frozen",
+            DamageType::Poison => "sickened by venom",
+            DamageType::Arcane => "seared by arcane energy",
+        }
+    }
+}
+
+/// Mitigation against each elemental damage type. Negative values are vulnerabilities, which add to the damage taken.
+///
+/// Physical damage is mitigated by the regular mitigation of armor and NPCs instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DamageAffinities {
+    pub fire: i16,
+    pub cold: i16,
+    pub poison: i16,
+    pub arcane: i16,
+}
+
+impl DamageAffinities {
+    pub const NONE: DamageAffinities = DamageAffinities { fire: 0, cold: 0, poison: 0, arcane: 0 };
+
+    /// Mitigation against damage of the given type. Physical damage is mitigated by `physical_mitigation`.
+    pub fn mitigation(&self, damage_type: DamageType, physical_mitigation: i16) -> i16 {
+        match damage_type {
+            DamageType::Physical => physical_mitigation,
+            DamageType::Fire => self.fire,
+            DamageType::Cold => self.cold,
+            DamageType::Poison => self.poison,
+            DamageType::Arcane => self.arcane,
+        }
+    }
+
+    /// Elemental damage types that deal extra damage.
+    pub fn vulnerabilities(&self) -> impl Iterator<Item = DamageType> + '_ {
+        DamageType::ELEMENTS.into_iter().filter(|damage_type| self.mitigation(*damage_type, 0) < 0)
+    }
+}
+
+impl GameState {
+    /// Affinities of the armor the player is wearing. Without armor, the player has none.
+    pub fn player_affinities(&self) -> DamageAffinities {
+        self.player
+            .character
+            .armor
+            .and_then(|armor| self.get_item_by_id(armor.0))
+            .and_then(|item| self.get_item_def_by_id(&item.def_id))
+            .map_or(DamageAffinities::NONE, |def| match def.kind {
+                GameItemKindDef::Armor { affinities, .. } => affinities,
+                _ => DamageAffinities::NONE,
+            })
+    }
+
+    /// Type of the damage the NPC deals, as given by its definition.
+    pub fn npc_damage_type(&self, npc: &Npc) -> DamageType {
+        npc_defs().get(&npc.def_id).map_or(DamageType::Physical, |def| def.damage_type)
+    }
+
+    /// Affinities of the NPC, as given by its definition.
+    pub fn npc_affinities(&self, npc: &Npc) -> DamageAffinities {
+        npc_defs().get(&npc.def_id).map_or(DamageAffinities::NONE, |def| def.affinities)
+    }
+
+    /// Tells the player how elemental damage affected the NPC. Physical damage needs no extra message.
+    /// Arcane damage may leave a surviving NPC confused (see [ARCANE_CONFUSE_CHANCE]).
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound](crate::util::errors_results::EngineError::NpcNotFound) if the NPC is not on the current level.
+    pub(crate) fn elemental_hit_on_npc(
+        &mut self,
+        npc: &Npc,
+        damage_type: DamageType,
+    ) -> Result<(), GameError> {
+        if damage_type == DamageType::Physical {
+            return Ok(());
+        }
+        self.log.info(LogData::NpcElementalHit { npc_name: npc.name().to_string(), damage_type });
+
+        if npc.stats.base.is_alive() && self.rolls_confusion(damage_type) {
+            self.apply_crowd_control(
+                CrowdControlTarget::Npc(npc.id()),
+                CrowdControl::Confused,
+                CONFUSION_TURNS,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Tells the player how elemental damage affected them. Physical damage needs no extra message.
+    /// Arcane damage may leave the player confused (see [ARCANE_CONFUSE_CHANCE]).
+    pub(crate) fn elemental_hit_on_player(
+        &mut self,
+        damage_type: DamageType,
+    ) -> Result<(), GameError> {
+        if damage_type == DamageType::Physical {
+            return Ok(());
+        }
+        self.log.info(LogData::PlayerElementalHit { damage_type });
+
+        if self.player.character.is_alive() && self.rolls_confusion(damage_type) {
+            self.apply_crowd_control(
+                CrowdControlTarget::Player,
+                CrowdControl::Confused,
+                CONFUSION_TURNS,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn rolls_confusion(&mut self, damage_type: DamageType) -> bool {
+        damage_type == DamageType::Arcane && self.rng.random_range(0..100) < ARCANE_CONFUSE_CHANCE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::game_items::ArmorItem;
+
+    #[test]
+    fn affinities_replace_mitigation_for_elemental_damage() {
+        let zombie = npc_defs().get("zombie").unwrap();
+        assert!(zombie.affinities.mitigation(DamageType::Fire, 5) < 0);
+        assert_eq!(zombie.affinities.mitigation(DamageType::Physical, 5), 5);
+
+        let mut game = GameState::default();
+        let cloak = game.register_item(&"armor_cloak".to_string()).unwrap();
+        game.player.character.armor = Some(ArmorItem(cloak));
+        assert!(game.player_affinities().cold > 0);
+        assert!(
+            game.player_defense_against(DamageType::Cold).mitigation
+                > game.player_defense_against(DamageType::Fire).mitigation
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{core::game::GameState, proc_gen::gen_config::GenConfig};
+
+/// Difficulty the player picks on the start screen (or with `--difficulty`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DifficultyLevel {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl DifficultyLevel {
+    pub const ALL: [DifficultyLevel; 3] =
+        [DifficultyLevel::Easy, DifficultyLevel::Normal, DifficultyLevel::Hard];
+
+    /// The next harder difficulty, starting over at the easiest one.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|level| *level == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Parses the name of a difficulty, as given on the command line.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.to_string().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for DifficultyLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DifficultyLevel::Easy => write!(f, "Easy"),
+            DifficultyLevel::Normal => write!(f, "Normal"),
+            DifficultyLevel::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+/// How the [DifficultyLevel] of the run changes the game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Difficulty {
+    pub level: DifficultyLevel,
+
+    /// Bonus damage of monsters for each floor of depth, in percent of a point (see [GameState::level_scaling_modifiers]).
+    pub depth_damage_percent: i16,
+
+    /// Monsters added to (or removed from) each room with enemies (see [GenConfig::room_npcs]).
+    pub extra_room_npcs: isize,
+
+    /// Experience the player gains, in percent.
+    pub experience_percent: u32,
+}
+
+impl From<DifficultyLevel> for Difficulty {
+    fn from(level: DifficultyLevel) -> Self {
+        match level {
+            DifficultyLevel::Easy => Self {
+                level,
+                depth_damage_percent: 50,
+                extra_room_npcs: -1,
+                experience_percent: 125,
+            },
+            DifficultyLevel::Normal => Self {
+                level,
+                depth_damage_percent: 100,
+                extra_room_npcs: 0,
+                experience_percent: 100,
+            },
+            DifficultyLevel::Hard => Self {
+                level,
+                depth_damage_percent: 150,
+                extra_room_npcs: 1,
+                experience_percent: 75,
+            },
+        }
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::from(DifficultyLevel::default())
+    }
+}
+
+impl Difficulty {
+    /// Bonus damage of monsters on the floor of the given depth.
+    pub fn depth_damage(&self, depth: usize) -> i16 {
+        (depth as i16).saturating_mul(self.depth_damage_percent) / 100
+    }
+
+    /// The level generation parameters with the number of monsters per room adjusted. A room with enemies always has at least one.
+    pub fn adjust_gen_config(&self, config: &GenConfig) -> GenConfig {
+        let shift = |count: usize| count.saturating_add_signed(self.extra_room_npcs).max(1);
+        let room_npcs = shift(config.room_npcs.start)..shift(config.room_npcs.end);
+        GenConfig { room_npcs, ..config.clone() }
+    }
+
+    /// Experience the player gains instead of the given amount.
+    pub fn scale_experience(&self, amount: u32) -> u32 {
+        amount.saturating_mul(self.experience_percent) / 100
+    }
+}
+
+impl GameState {
+    /// Sets the difficulty of the run. It is recorded in the replay, so the run plays out the same way when played back.
+    pub fn set_difficulty(&mut self, level: DifficultyLevel) {
+        self.difficulty = Difficulty::from(level);
+        self.replay.difficulty = level;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn harder_difficulties_hit_harder_and_spawn_more() {
+        let easy = Difficulty::from(DifficultyLevel::Easy);
+        let normal = Difficulty::default();
+        let hard = Difficulty::from(DifficultyLevel::Hard);
+
+        assert_eq!(normal.depth_damage(6), 6);
+        assert!(easy.depth_damage(6) < normal.depth_damage(6));
+        assert!(hard.depth_damage(6) > normal.depth_damage(6));
+
+        let config = GenConfig::default();
+        assert_eq!(normal.adjust_gen_config(&config), config);
+        assert_eq!(easy.adjust_gen_config(&config).room_npcs, 1..2);
+        assert_eq!(hard.adjust_gen_config(&config).room_npcs, 2..4);
+
+        assert_eq!(normal.scale_experience(25), 25);
+        assert!(hard.scale_experience(25) < 25);
+    }
+
+    #[test]
+    fn names_are_parsed_and_cycled() {
+        assert_eq!(DifficultyLevel::from_name("hard"), Some(DifficultyLevel::Hard));
+        assert_eq!(DifficultyLevel::from_name("Easy"), Some(DifficultyLevel::Easy));
+        assert_eq!(DifficultyLevel::from_name("nightmare"), None);
+        assert_eq!(DifficultyLevel::Hard.next(), DifficultyLevel::Easy);
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+
+use crate::{
+    core::{entity_logic::Entity, game::GameState},
+    util::text_log::LogData,
+    world::{
+        coordinate_system::{Direction, Point},
+        tiles::{Collision, Tile, TileType},
+        worldspace::World,
+    },
+};
+
+/// Levels this deep and deeper belong to the final depth band, where the burrow shifts around the player.
+pub const DISTORTION_DEPTH: usize = 16;
+
+/// Chance (in percent) each round that a part of the level shifts.
+pub const DISTORTION_CHANCE: u32 = 5;
+
+/// Width and height of a shifting section. Its outermost tiles never change.
+pub const DISTORTION_SECTION_SIZE: (usize, usize) = (9, 6);
+
+/// Sections closer to the player than this are left alone, so nothing shifts right next to them.
+pub const DISTORTION_MIN_DISTANCE: usize = 12;
+
+/// Chance (in percent) that a tile of a shifting section becomes a wall.
+pub const DISTORTION_WALL_CHANCE: u32 = 20;
+
+/// Number of sections that are tried each time before giving up.
+const DISTORTION_ATTEMPTS: usize = 10;
+
+impl GameState {
+    /// On the deepest levels, a distant part of the level occasionally shifts. Called once per round.
+    ///
+    /// Gauntlets are handcrafted and never shift.
+    pub fn apply_burrow_distortion(&mut self) {
+        if self.level_nr < DISTORTION_DEPTH || self.current_level().gauntlet.is_some() {
+            return;
+        }
+
+        if self.rng.random_range(0..100) < DISTORTION_CHANCE && self.distort_burrow() {
+            self.log.info(LogData::BurrowShifts);
+        }
+    }
+
+    /// Regenerates a random section of the current level that the player cannot see.
+    ///
+    /// Inside the section, floor and walls that are surrounded by floor and walls are rolled again, which moves walls and pillars around.
+    /// Doors, hallways, stairs and other special tiles never change. Changed tiles are forgotten by the player.
+    ///
+    /// If the change would cut the player off from anything they could reach before (like the stairs), it is undone.
+    ///
+    /// # Returns
+    /// * `true` if a section was changed.
+    pub fn distort_burrow(&mut self) -> bool {
+        let (width, height) = DISTORTION_SECTION_SIZE;
+        let player_pos = self.player.character.pos();
+
+        for _ in 0..DISTORTION_ATTEMPTS {
+            let Some(origin) = self.distortion_section() else {
+                continue;
+            };
+
+            let world = self.current_world();
+            let shiftable: Vec<Point> = (origin.y + 1..origin.y + height - 1)
+                .flat_map(|y| (origin.x + 1..origin.x + width - 1).map(move |x| Point::new(x, y)))
+                .filter(|point| is_shiftable(world, *point))
+                .collect();
+            if shiftable.is_empty() {
+                continue;
+            }
+
+            let reachable_before = reachable_tiles(world, player_pos);
+            let previous: Vec<Tile> =
+                shiftable.iter().map(|point| *world.get_tile(*point)).collect();
+
+            let mut changed = false;
+            for point in &shiftable {
+                let tile_type = if self.rng.random_range(0..100) < DISTORTION_WALL_CHANCE {
+                    TileType::Wall
+                } else {
+                    TileType::Floor
+                };
+
+                let tile = self.current_level_mut().world.get_tile_mut(*point);
+                if tile.tile_type != tile_type {
+                    tile.tile_type = tile_type;
+                    tile.explored = false;
+                    changed = true;
+                }
+            }
+
+            let world = self.current_world();
+            let reachable_after = reachable_tiles(world, player_pos);
+            let still_connected = reachable_before.iter().enumerate().all(|(index, reachable)| {
+                !reachable
+                    || reachable_after[index]
+                    || shiftable.iter().any(|point| world.index(point.x, point.y) == index)
+            });
+
+            if changed && still_connected {
+                return true;
+            }
+
+            for (point, tile) in shiftable.iter().zip(previous) {
+                *self.current_level_mut().world.get_tile_mut(*point) = tile;
+            }
+        }
+
+        false
+    }
+
+    /// Picks a random section of [DISTORTION_SECTION_SIZE] that may shift.
+    ///
+    /// # Returns
+    /// * `None` if the picked section is visible, too close to the player or has something in it.
+    fn distortion_section(&mut self) -> Option<Point> {
+        let (width, height) = DISTORTION_SECTION_SIZE;
+        let world = &self.levels[self.level_nr].world;
+        let origin = Point::new(
+            self.rng.random_range(0..=world.width - width),
+            self.rng.random_range(0..=world.height - height),
+        );
+
+        let center = Point::new(origin.x + width / 2, origin.y + height / 2);
+        if center.distance_squared_from(self.player.character.pos())
+            < DISTORTION_MIN_DISTANCE * DISTORTION_MIN_DISTANCE
+        {
+            return None;
+        }
+
+        let level = self.current_level();
+        for y in origin.y..origin.y + height {
+            for x in origin.x..origin.x + width {
+                let point = Point::new(x, y);
+                if level.world.get_tile(point).visible
+                    || level.get_npc_at(point).is_some()
+                    || level.get_item_sprite_at(point).is_some()
+                    || level.get_corpse_at(point).is_some()
+                {
+                    return None;
+                }
+            }
+        }
+
+        Some(origin)
+    }
+}
+
+/// Whether a tile may shift: Only floor and walls that are surrounded by floor and walls (diagonals included).
+fn is_shiftable(world: &World, point: Point) -> bool {
+    let is_plain = |tile_type: TileType| matches!(tile_type, TileType::Floor | TileType::Wall);
+
+    is_plain(world.get_tile(point).tile_type)
+        && (point.y - 1..=point.y + 1)
+            .flat_map(|y| (point.x - 1..=point.x + 1).map(move |x| Point::new(x, y)))
+            .all(|neighbor| is_plain(world.get_tile(neighbor).tile_type))
+}
+
+/// Finds all tiles the player can walk to from the given point. Closed doors count as passable, since the player can open them.
+///
+/// # Returns
+/// Whether each tile is reachable, indexed like [World::tiles].
+fn reachable_tiles(world: &World, start: Point) -> Vec<bool> {
+    let is_passable =
+        |tile_type: TileType| tile_type.is_walkable() || matches!(tile_type, TileType::Door(_));
+
+    let mut reachable = vec![false; world.tiles.len()];
+    let mut queue = VecDeque::from([start]);
+    reachable[world.index(start.x, start.y)] = true;
+
+    while let Some(point) = queue.pop_front() {
+        for direction in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+            let neighbor = point.get_adjacent(direction);
+            if !world.is_in_bounds(neighbor.x as isize, neighbor.y as isize) {
+                continue;
+            }
+
+            let index = world.index(neighbor.x, neighbor.y);
+            if !reachable[index] && is_passable(world.get_tile(neighbor).tile_type) {
+                reachable[index] = true;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    #[test]
+    fn shifting_never_cuts_off_the_stairs() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 80, 15));
+        level.exit = Point::new(80, 12);
+        level.world.get_tile_mut(level.exit).tile_type = TileType::StairsDown;
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(8, 12);
+
+        let mut shifts = 0;
+        for _ in 0..50 {
+            if game.distort_burrow() {
+                shifts += 1;
+            }
+
+            let world = game.current_world();
+            let reachable = reachable_tiles(world, game.player.character.pos());
+            assert!(reachable[world.index(80, 12)]);
+        }
+        assert!(shifts > 0);
+
+        // Nothing shifts where the player can see it
+        for tile in game.current_level_mut().world.tiles.iter_mut() {
+            tile.make_visible();
+        }
+        assert!(!game.distort_burrow());
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    core::{game::GameState, game_items::GameItemId},
+    data::item_defs::item_defs,
+    util::text_log::LogData,
+};
+
+/// Weight the player can carry with a strength of zero.
+pub const BASE_CARRY_CAPACITY: u16 = 20;
+
+/// Weight the player can carry in addition for each point of strength.
+pub const CARRY_CAPACITY_PER_STRENGTH: u16 = 5;
+
+/// A slowed player loses every this many rounds: The NPCs take a second turn before the player can act again.
+pub const SLOWED_INTERVAL: u64 = 3;
+
+impl GameState {
+    /// Total weight of the items the player carries, including the equipped weapon and armor and the contents of bags.
+    pub fn burden(&self) -> u16 {
+        let character = &self.player.character;
+        let equipped =
+            [character.weapon.map(|weapon| weapon.0), character.armor.map(|armor| armor.0)];
+
+        character
+            .inventory
+            .iter()
+            .copied()
+            .chain(equipped.into_iter().flatten())
+            .flat_map(|item_id| {
+                let contents = self.items.get(&item_id).map_or(&[][..], |item| &item.contents);
+                std::iter::once(item_id).chain(contents.iter().copied())
+            })
+            .map(|item_id| self.item_weight(item_id))
+            .sum()
+    }
+
+    /// Weight the player can carry without being slowed. Grows with strength.
+    pub fn carry_capacity(&self) -> u16 {
+        BASE_CARRY_CAPACITY
+            + self.player.character.stats.strength as u16 * CARRY_CAPACITY_PER_STRENGTH
+    }
+
+    /// The player is slowed while they carry more than their [GameState::carry_capacity].
+    pub fn is_slowed(&self) -> bool {
+        self.burden() > self.carry_capacity()
+    }
+
+    /// Lets a slowed player lose every [SLOWED_INTERVAL]th round, which the NPCs spend on another turn. Called after every round.
+    pub fn apply_slowness(&mut self) {
+        if !self.round_nr.is_multiple_of(SLOWED_INTERVAL)
+            || !self.is_slowed()
+            || !self.player_is_alive()
+        {
+            return;
+        }
+
+        self.log.info(LogData::SlowedByBurden);
+        self.next_round();
+    }
+
+    fn item_weight(&self, item_id: GameItemId) -> u16 {
+        self.items
+            .get(&item_id)
+            .and_then(|item| item_defs().get(&item.def_id))
+            .map_or(0, |def| def.weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::level::Level;
+
+    #[test]
+    fn heavy_loads_cost_turns() {
+        let mut game = GameState::default();
+        game.levels.insert(0, Level::new());
+
+        let plate = game.register_item(&"armor_plate".to_string()).unwrap();
+        game.add_item_to_inv(plate).unwrap();
+        assert_eq!(game.burden(), 20);
+        assert!(!game.is_slowed());
+
+        let warhammer = game.register_item(&"weapon_warhammer".to_string()).unwrap();
+        game.add_item_to_inv(warhammer).unwrap();
+        assert!(game.is_slowed());
+
+        // Every third round is lost
+        game.round_nr = SLOWED_INTERVAL - 1;
+        game.next_round();
+        game.apply_slowness();
+        assert_eq!(game.round_nr, SLOWED_INTERVAL + 1);
+
+        // Stronger characters carry more
+        game.player.character.stats.strength = 3;
+        assert!(!game.is_slowed());
+    }
+}
@@ -1,16 +1,17 @@
 #![allow(dead_code)]
 
-use ratatui::style::Style;
-
 use crate::ai::npc_ai::NpcAiState;
+use crate::core::crowd_control::CrowdControlTurns;
 use crate::core::game::GameState;
 use crate::data::npc_defs::{NpcDef, NpcDefId, npc_defs};
+use crate::data::uniques::UniqueId;
+use crate::render::style::Style;
 use crate::util::errors_results::{
     DataError, EngineError, FailReason, GameError, GameOutcome, GameResult,
 };
 use crate::util::rng::Roll;
-use crate::world::coordinate_system::Point;
-use crate::world::tiles::{Collision, Drawable};
+use crate::world::coordinate_system::{Footprint, Point};
+use crate::world::tiles::{Collision, Drawable, TileType, fallback_glyph};
 
 impl GameState {
     /// Creates a new entity of type `Npc`.
@@ -24,18 +25,21 @@ impl GameState {
     pub fn create_npc(&mut self, npc_def_id: NpcDefId, point: Point) -> Result<Npc, GameError> {
         // Looking if the npc_def exists.
         let npc_def = get_npc_def_by_id(npc_def_id.clone())
-            .ok_or(DataError::MissingNpcDefinition(npc_def_id))?;
+            .ok_or_else(|| DataError::MissingNpcDefinition(npc_def_id.clone()))?;
 
         // Creating npc and assigning id.
         let entity_id = self.id_system.next_entity_id();
-        let npc = Npc::new(
+        let mut npc = Npc::new(
             entity_id,
+            npc_def_id,
             npc_def.name.to_string(),
             point,
             npc_def.glyph,
             npc_def.style,
             npc_def.stats,
         );
+        npc.disposition = npc_def.disposition;
+        npc.size = npc_def.size;
 
         Ok(npc)
     }
@@ -48,13 +52,30 @@ impl GameState {
             let new_x = npc.pos().x as isize + dx;
             let new_y = npc.pos().y as isize + dy;
             let new_point = Point::new(new_x as usize, new_y as usize);
+            let new_footprint = npc.footprint().moved_to(new_point);
 
             if !self.current_world().is_in_bounds(new_x, new_y) {
                 return Ok(GameOutcome::Fail(FailReason::PointOutOfBounds(new_point)));
             }
 
-            if !self.current_world().get_tile(new_point).tile_type.is_walkable() {
-                return Ok(GameOutcome::Fail(FailReason::TileNotWalkable(new_point)));
+            // Large NPCs need room for all the tiles they cover
+            for point in new_footprint.points() {
+                if !self.current_world().is_in_bounds(point.x as isize, point.y as isize) {
+                    return Ok(GameOutcome::Fail(FailReason::PointOutOfBounds(point)));
+                }
+
+                // NPCs don't walk into hazards on their own
+                if self.current_world().get_tile(point).tile_type.movement_cost().is_none() {
+                    return Ok(GameOutcome::Fail(FailReason::TileNotWalkable(point)));
+                }
+
+                // NPCs cannot walk through each other or the player
+                let other_npc = self.current_level().get_npc_at(point);
+                if point == self.player.character.pos()
+                    || other_npc.is_some_and(|other_id| other_id != npc_id)
+                {
+                    return Ok(GameOutcome::Fail(FailReason::TileOccupied(point)));
+                }
             }
 
             (new_x, new_y)
@@ -64,6 +85,15 @@ impl GameState {
             self.current_level_mut().get_npc_mut(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
         npc.move_to(Point::new(new_x as usize, new_y as usize));
 
+        // NPCs spring the traps they step on, just like the player
+        let footprint = npc.footprint();
+        if footprint
+            .points()
+            .any(|point| self.current_world().get_tile(point).tile_type == TileType::SpikeTrap)
+        {
+            self.spring_spike_trap_on_npc(npc_id)?;
+        }
+
         Ok(GameOutcome::Success)
     }
 }
@@ -76,6 +106,11 @@ pub trait Entity {
     fn name(&self) -> &str;
     fn id(&self) -> EntityId;
     fn pos(&self) -> Point;
+
+    /// Tiles the entity covers. Most entities only cover the tile at their position.
+    fn footprint(&self) -> Footprint {
+        Footprint::single(self.pos())
+    }
 }
 
 pub trait Movable {
@@ -90,7 +125,7 @@ pub struct EntityBase {
     pub name: String,
     pub pos: Point,
     pub glyph: char,
-    pub style: Style, // from ratatui
+    pub style: Style,
 }
 
 impl Drawable for EntityBase {
@@ -100,6 +135,9 @@ impl Drawable for EntityBase {
     fn style(&self) -> Style {
         self.style
     }
+    fn ascii_glyph(&self) -> char {
+        if self.glyph.is_ascii() { self.glyph } else { fallback_glyph(&self.name) }
+    }
 }
 
 #[derive(Clone)]
@@ -112,8 +150,43 @@ pub struct BaseStats {
 #[derive(Clone)]
 pub struct Npc {
     pub base: EntityBase,
+    /// Id of the definition the NPC was created from.
+    pub def_id: NpcDefId,
     pub stats: NpcStats,
     pub ai_state: NpcAiState,
+    /// Attitude of the NPC towards the player.
+    pub disposition: Disposition,
+    /// Number of turns the NPC is distracted and does nothing.
+    pub distracted_turns: u8,
+    /// Remaining turns of the NPC being confused or frightened (see [CrowdControl](crate::core::crowd_control::CrowdControl)).
+    pub crowd_control: CrowdControlTurns,
+    /// Number of turns an invisible NPC stays visible after it gave itself away (see [REVEAL_TURNS](crate::core::invisibility::REVEAL_TURNS)).
+    pub revealed_turns: u8,
+    /// Number of turns the NPC has been trying to walk into another NPC.
+    pub blocked_turns: u8,
+    /// Width and height of the tiles the NPC covers (see [NpcDef::size]).
+    pub size: usize,
+    /// Id of the leader of the NPC's pack, if it was spawned in one (see [pack](crate::ai::pack)). Members keep it after their leader died.
+    pub pack: Option<EntityId>,
+    /// Id of the unique monster the NPC is (see [UniqueDef](crate::data::uniques::UniqueDef)), if it is one.
+    pub unique: Option<UniqueId>,
+}
+
+/// Attitude of an NPC towards the player.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Disposition {
+    /// The NPC attacks the player on sight.
+    #[default]
+    Hostile,
+
+    /// The NPC leaves the player alone and can be talked to.
+    Friendly,
+
+    /// The NPC leaves the player alone, but turns hostile when attacked. Some neutral animals can be tamed.
+    Neutral,
+
+    /// The NPC was tamed and fights alongside the player.
+    Companion,
 }
 
 impl Entity for Npc {
@@ -126,6 +199,9 @@ impl Entity for Npc {
     fn pos(&self) -> Point {
         self.base.pos
     }
+    fn footprint(&self) -> Footprint {
+        Footprint::new(self.base.pos, self.size)
+    }
 }
 
 impl Movable for Npc {
@@ -138,6 +214,7 @@ impl Movable for Npc {
 impl Npc {
     pub fn new(
         id: EntityId,
+        def_id: NpcDefId,
         name: String,
         pos: Point,
         glyph: char,
@@ -146,8 +223,17 @@ impl Npc {
     ) -> Self {
         Self {
             base: EntityBase { id, name, pos, glyph, style },
+            def_id,
             stats,
             ai_state: NpcAiState::Wandering,
+            disposition: Disposition::default(),
+            distracted_turns: 0,
+            crowd_control: CrowdControlTurns::default(),
+            revealed_turns: 0,
+            blocked_turns: 0,
+            size: 1,
+            pack: None,
+            unique: None,
         }
     }
 }
@@ -168,6 +254,7 @@ impl NpcStats {
 
 #[cfg(test)]
 mod tests {
+    use crate::core::corpses::CORPSE_DECAY_ROUNDS;
     use crate::data::item_defs::GameItemDefId;
     use crate::world::level::Level;
     use crate::world::worldspace::Room;
@@ -331,4 +418,27 @@ mod tests {
         assert_eq!(game.current_level().npc_index.get(&npc1_id), Some(&0));
         assert_eq!(game.current_level().npc_index.get(&npc2_id), Some(&1));
     }
+
+    #[test]
+    fn test_corpse_does_not_block_and_decays() {
+        let mut game = GameState::default();
+        let mut level: Level = Level::new();
+        level.world.carve_room(&Room::new(Point { x: 35, y: 5 }, 30, 15));
+
+        let point = Point::new(50, 7);
+        let npc = game.create_npc("wolf".into(), point).unwrap();
+        let corpse = game.create_corpse(&npc).unwrap();
+        let corpse_id = corpse.id();
+        let _ = level.spawn_corpse(corpse);
+
+        game.levels.insert(0, level);
+
+        assert_eq!(game.current_level().get_corpse_at(point), Some(corpse_id));
+        assert!(game.current_level().is_available(point));
+
+        game.round_nr += CORPSE_DECAY_ROUNDS;
+        game.decay_corpses();
+
+        assert!(game.current_level().get_corpse(corpse_id).is_none());
+    }
 }
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityId, Npc},
+        game::GameState,
+    },
+    world::level::Level,
+};
+
+/// What an [EntityId] belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityKind {
+    Npc,
+    ItemSprite,
+    Corpse,
+}
+
+/// Level an entity is on, and what kind of entity it is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityLocation {
+    pub level_nr: usize,
+    pub kind: EntityKind,
+}
+
+/// Index of the entities of all levels of the run, so systems like quests can find an entity regardless of the level the player is on.
+///
+/// Entities live in the vectors of their [Level], the registry only remembers where. It is updated for a level when the level is created,
+/// when the player leaves it and at the end of each round (see [GameState::sync_entity_registry]), so it may lag behind by a turn.
+#[derive(Clone, Debug, Default)]
+pub struct EntityRegistry {
+    locations: HashMap<EntityId, EntityLocation>,
+}
+
+impl EntityRegistry {
+    pub fn get(&self, id: EntityId) -> Option<EntityLocation> {
+        self.locations.get(&id).copied()
+    }
+
+    /// Records the NPCs, item sprites and corpses of the level, and forgets the entities that left it.
+    ///
+    /// Entries of entities that are still in place are overwritten, so a level without changes does not allocate.
+    pub fn index_level(&mut self, level_nr: usize, level: &Level) {
+        self.locations.retain(|id, location| {
+            location.level_nr != level_nr
+                || match location.kind {
+                    EntityKind::Npc => level.npc_index.contains_key(id),
+                    EntityKind::ItemSprite => level.item_sprites_index.contains_key(id),
+                    EntityKind::Corpse => level.corpses_index.contains_key(id),
+                }
+        });
+
+        let npcs = level.npcs.iter().map(|npc| (npc.id(), EntityKind::Npc));
+        let item_sprites =
+            level.item_sprites.iter().map(|item_sprite| (item_sprite.id(), EntityKind::ItemSprite));
+        let corpses = level.corpses.iter().map(|corpse| (corpse.id(), EntityKind::Corpse));
+        for (id, kind) in npcs.chain(item_sprites).chain(corpses) {
+            self.locations.insert(id, EntityLocation { level_nr, kind });
+        }
+    }
+}
+
+impl GameState {
+    /// Updates the [EntityRegistry] with the entities of the current level.
+    pub fn sync_entity_registry(&mut self) {
+        if let Some(level) = self.levels.get(self.level_nr) {
+            self.entity_registry.index_level(self.level_nr, level);
+        }
+    }
+
+    /// Finds the level an entity is on, whether the player is on it or not.
+    pub fn locate_entity(&self, id: EntityId) -> Option<EntityLocation> {
+        self.entity_registry.get(id)
+    }
+
+    /// Finds an NPC on any level of the run.
+    ///
+    /// # Returns
+    /// * The number of the level the NPC is on, and the NPC.
+    /// * `None` if there is no such NPC (anymore).
+    pub fn find_npc(&self, id: EntityId) -> Option<(usize, &Npc)> {
+        let location =
+            self.locate_entity(id).filter(|location| location.kind == EntityKind::Npc)?;
+        let npc = self.levels.get(location.level_nr)?.get_npc(id)?;
+        Some((location.level_nr, npc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{coordinate_system::Point, worldspace::Room};
+
+    #[test]
+    fn entities_are_found_on_other_levels_until_they_are_gone() {
+        let mut game = GameState::default();
+        for _ in 0..2 {
+            let mut level = Level::new();
+            level.world.carve_room(&Room::new(Point::new(5, 5), 10, 10));
+            game.levels.push(level);
+        }
+
+        let npc = game.create_npc("goblin".to_string(), Point::new(8, 8)).unwrap();
+        let goblin_id = npc.id();
+        game.current_level_mut().spawn_npc(npc).unwrap();
+        game.sync_entity_registry();
+
+        // The goblin stays on level 0 while the player is on level 1
+        game.level_nr = 1;
+        game.sync_entity_registry();
+        assert_eq!(game.find_npc(goblin_id).map(|(level_nr, _)| level_nr), Some(0));
+
+        game.levels[0].despawn(goblin_id);
+        assert!(game.find_npc(goblin_id).is_none());
+        game.entity_registry.index_level(0, &game.levels[0]);
+        assert_eq!(game.locate_entity(goblin_id), None);
+    }
+}
@@ -0,0 +1,150 @@
+use bitflags::bitflags;
+use rand::seq::IndexedRandom;
+use strum::IntoEnumIterator;
+
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityId},
+        game::GameState,
+    },
+    data::dialogue::JOKES,
+    util::{
+        errors_results::{EngineError, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+    world::coordinate_system::Direction,
+};
+
+/// NPCs that count as bosses. The Funny Frog can distract one of them per run.
+pub const BOSS_NPCS: [&str; 3] = ["ferris", "martin", "borrowchecker"];
+
+/// Number of turns a boss is distracted by the Funny Frog.
+pub const FROG_DISTRACTION_TURNS: u8 = 3;
+
+/// Additional turns of distraction if the player has read the joke book (the frog tells the boss a joke).
+pub const JOKE_DISTRACTION_BONUS: u8 = 2;
+
+bitflags! {
+    /// Bitflag collection for harmless things the player did during the run, which may pay off later.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct EventFlags: u8 {
+        /// The player pet the Funny Frog.
+        const FROG_PETTED = 0b00000001;
+        /// The player read the joke book.
+        const JOKE_BOOK_READ = 0b00000010;
+        /// The Funny Frog already distracted a boss this run.
+        const FROG_DISTRACTED_BOSS = 0b00000100;
+    }
+}
+
+impl GameState {
+    /// Remembers that the player pet the Funny Frog. Only the first time is written into the journal.
+    pub fn remember_frog_petted(&mut self) {
+        if self.event_flags.contains(EventFlags::FROG_PETTED) {
+            return;
+        }
+
+        self.event_flags.insert(EventFlags::FROG_PETTED);
+        self.write_journal("I pet a frog. It looked at me like it would remember this.");
+    }
+
+    /// The player reads a joke from the joke book.
+    ///
+    /// The book is not used up. The first reading is written into the journal.
+    pub fn read_joke_book(&mut self) -> GameResult {
+        if let Some(joke) = JOKES.choose(&mut self.rng) {
+            self.log.info(LogData::JokeRead { joke: joke.to_string() });
+        }
+
+        if !self.event_flags.contains(EventFlags::JOKE_BOOK_READ) {
+            self.event_flags.insert(EventFlags::JOKE_BOOK_READ);
+            self.write_journal("I read a book of terrible jokes. Someone might appreciate them.");
+        }
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// Hook that is called when an NPC spots the player.
+    ///
+    /// If the NPC is a boss and the player pet the Funny Frog, the frog shows up next to the boss and distracts it.
+    /// This happens only once per run.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not in the current level.
+    pub fn try_frog_distraction(&mut self, npc_id: EntityId) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+
+        if !BOSS_NPCS.contains(&npc.def_id.as_str())
+            || !self.event_flags.contains(EventFlags::FROG_PETTED)
+            || self.event_flags.contains(EventFlags::FROG_DISTRACTED_BOSS)
+        {
+            return Ok(GameOutcome::Success);
+        }
+
+        let npc_name = npc.name().to_string();
+        let npc_pos = npc.pos();
+
+        // The frog needs a free tile next to the boss to show up
+        let Some(frog_pos) = Direction::iter()
+            .map(|direction| npc_pos.get_adjacent(direction))
+            .find(|point| self.current_level().is_available(*point))
+        else {
+            return Ok(GameOutcome::Success);
+        };
+
+        let frog = self.create_npc("funny_frog".to_string(), frog_pos)?;
+        self.current_level_mut().spawn_npc(frog)?;
+
+        let mut turns = FROG_DISTRACTION_TURNS;
+        if self.event_flags.contains(EventFlags::JOKE_BOOK_READ) {
+            turns += JOKE_DISTRACTION_BONUS;
+        }
+
+        if let Some(npc) = self.current_level_mut().get_npc_mut(npc_id) {
+            npc.distracted_turns = turns;
+        }
+
+        self.event_flags.insert(EventFlags::FROG_DISTRACTED_BOSS);
+        self.log.info(LogData::FrogDistractsBoss { npc_name: npc_name.clone() });
+        self.write_journal(&format!("The frog came back and distracted {}. Good frog.", npc_name));
+
+        Ok(GameOutcome::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::entity_logic::Movable,
+        world::{coordinate_system::Point, level::Level, worldspace::Room},
+    };
+
+    #[test]
+    fn frog_distracts_one_boss_after_being_pet() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+
+        let ferris = game.create_npc("ferris".into(), Point::new(50, 10)).unwrap();
+        let ferris_id = ferris.id();
+        level.spawn_npc(ferris).unwrap();
+        let martin = game.create_npc("martin".into(), Point::new(40, 10)).unwrap();
+        let martin_id = martin.id();
+        level.spawn_npc(martin).unwrap();
+        game.levels.insert(0, level);
+        game.player.character.move_to(Point::new(45, 10));
+
+        game.try_frog_distraction(ferris_id).unwrap();
+        assert_eq!(game.current_level().npcs.len(), 2);
+
+        game.remember_frog_petted();
+        game.try_frog_distraction(ferris_id).unwrap();
+        game.try_frog_distraction(martin_id).unwrap();
+
+        assert_eq!(game.current_level().npcs.len(), 3);
+        assert_eq!(game.current_level().get_npc(ferris_id).unwrap().distracted_turns, 3);
+        assert_eq!(game.current_level().get_npc(martin_id).unwrap().distracted_turns, 0);
+        assert_eq!(game.journal.entries.len(), 2);
+    }
+}
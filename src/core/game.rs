@@ -3,12 +3,33 @@
 use rand::RngCore;
 use rand::{SeedableRng, rngs::StdRng};
 use std::collections::HashMap;
+use std::mem;
 
 use bitflags::bitflags;
 
-use crate::core::entity_logic::EntityId;
+use crate::core::combat::HitMarker;
+use crate::core::difficulty::Difficulty;
+use crate::core::entity_logic::{Entity, EntityId};
+use crate::core::entity_registry::EntityRegistry;
+use crate::core::flavor::EventFlags;
 use crate::core::game_items::{GameItem, GameItemId};
+use crate::core::journal::Journal;
 use crate::core::player::Player;
+use crate::core::quests::Quest;
+use crate::core::regeneration::RegenRules;
+use crate::core::replay::Replay;
+use crate::core::resting::RestState;
+use crate::core::rewind::RewindHistory;
+use crate::core::run_stats::RunStats;
+use crate::core::scratch::ScratchBuffers;
+use crate::core::shop::ShopRegister;
+use crate::core::town::{Stash, TUTORIAL_LEVEL, stash_path};
+use crate::core::travel::TravelState;
+use crate::core::triggers::LoreText;
+use crate::core::uniques::UniqueRegistry;
+use crate::data::perks::PerkId;
+use crate::proc_gen::gen_config::GenConfig;
+use crate::util::build_info::BuildInfo;
 use crate::util::errors_results::{EngineError, FailReason, GameError, GameOutcome, GameResult};
 use crate::util::text_log::Log;
 use crate::world::coordinate_system::{Direction, Point};
@@ -18,6 +39,9 @@ use crate::world::level::{Level, LevelEntrance};
 //                Game State Struct
 // ----------------------------------------------
 pub struct GameState {
+    /// Master seed of the run. Everything random in the run is derived from it.
+    pub seed: u64,
+
     /// Contains the data for every level in the game.
     pub levels: Vec<Level>,
 
@@ -39,6 +63,9 @@ pub struct GameState {
     /// Contains the system which generates new Ids for [Npc] and [GameItem]s
     pub id_system: IdSystem,
 
+    /// Knows the level of every NPC, item sprite and corpse, so they can be found from any level.
+    pub entity_registry: EntityRegistry,
+
     /// Tracks all items currently in play.
     pub items: HashMap<GameItemId, GameItem>, // stores all items that are currently in the game
 
@@ -48,31 +75,123 @@ pub struct GameState {
     /// Rng instance that is generated once from [GameState::rng] and is used exclusively for Procedural Generation.
     pub proc_gen: StdRng,
 
+    /// Parameters the levels of the run are generated with.
+    pub gen_config: GenConfig,
+
+    /// Difficulty the player picked for the run.
+    pub difficulty: Difficulty,
+
     /// Game Rules, specific toggles changing the way the game handles some events.
     pub game_rules: GameRules,
+
+    /// How fast the player heals over time.
+    pub regen_rules: RegenRules,
+
+    /// Harmless things the player did during the run that may pay off later.
+    pub event_flags: EventFlags,
+
+    /// Noteworthy moments of the run.
+    pub journal: Journal,
+
+    /// Quests the player accepted from friendly NPCs, completed ones included.
+    pub quests: Vec<Quest>,
+
+    /// Shops of the run and what the player bought in them.
+    pub shops: ShopRegister,
+
+    /// Unique monsters that appeared this run, so each of them only appears once.
+    pub uniques: UniqueRegistry,
+
+    /// Items the player keeps in the town between runs.
+    pub stash: Stash,
+
+    /// Snapshots of the last turns, so they can be rewound with the `rewind` command. Only recorded in dev builds.
+    pub rewind_history: RewindHistory,
+
+    /// Statistics of the run, shown when the player dies.
+    pub run_stats: RunStats,
+
+    /// Seed, starting stash and inputs of the run, so it can be played again (see [Replay]).
+    pub replay: Replay,
+
+    /// Lore a trigger fired this turn, waiting to be shown by the UI.
+    pub pending_lore: Option<LoreText>,
+
+    /// Hits that landed this turn, waiting to be shown as damage popups by the UI.
+    pub pending_hits: Vec<HitMarker>,
+
+    /// Perks offered on the last level-up milestone, waiting for the player to pick one (see [GameState::offer_perks]).
+    pub pending_perks: Vec<PerkId>,
+
+    /// The rest the player is taking, if any. The UI keeps waiting rounds until it is over (see [GameState::continue_resting]).
+    pub resting: Option<RestState>,
+
+    /// The travel the player is on, if any. The UI keeps taking steps until it is over (see [GameState::continue_travel]).
+    pub travel: Option<TravelState>,
+
+    /// Buffers reused by the routines that run every round, so a round does not allocate.
+    pub scratch: ScratchBuffers,
 }
 
 impl GameState {
     pub fn new() -> Self {
-        let (mut rng, rng_seed) = rng_instance();
+        Self::with_seed(default_seed())
+    }
+
+    /// Starts a new run from the given master seed. Runs with the same seed generate the same world.
+    pub fn with_seed(rng_seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(rng_seed);
 
         let proc_gen_seed: u64 = rng.next_u64();
         let proc_gen = StdRng::seed_from_u64(proc_gen_seed);
 
         let mut state = Self {
+            seed: rng_seed,
             levels: Vec::new(),
             player: Player::new(0),
             cursor: None,
             log: Log::new(),
             round_nr: 0,
-            level_nr: 0,
+            level_nr: TUTORIAL_LEVEL,
             id_system: IdSystem::default(),
+            entity_registry: EntityRegistry::default(),
             items: HashMap::new(),
             rng,
             proc_gen,
+            gen_config: GenConfig::default(),
+            difficulty: Difficulty::default(),
             game_rules: GameRules::empty(),
+            regen_rules: RegenRules::default(),
+            event_flags: EventFlags::empty(),
+            journal: Journal::default(),
+            quests: Vec::new(),
+            shops: ShopRegister::default(),
+            uniques: UniqueRegistry::default(),
+            stash: Stash::default(),
+            rewind_history: RewindHistory::default(),
+            run_stats: RunStats::default(),
+            replay: Replay::default(),
+            pending_lore: None,
+            pending_hits: Vec::new(),
+            pending_perks: Vec::new(),
+            resting: None,
+            travel: None,
+            scratch: ScratchBuffers::default(),
         };
 
+        match stash_path().map(|path| Stash::load(&path)) {
+            Some(Ok(stash)) => {
+                if let Some(warning) = stash.build.mismatch_warning("stash") {
+                    state.log.debug_warn(warning);
+                }
+                // The stash is written by this build from now on
+                state.stash = Stash { build: BuildInfo::current(), ..stash };
+            }
+            Some(Err(error)) => state.log.debug_warn(format!("Couldn't load stash: {}", error)),
+            None => state.log.debug_warn("No data directory found for the stash".to_string()),
+        }
+        state.replay = Replay::new(rng_seed, &state.stash);
+
         state.log.debug_info(format!("Current RNG Seed: {}", rng_seed));
         state.log.debug_info(format!("Current Level-Gen Seed: {}", proc_gen_seed));
         state.log.print_lore();
@@ -91,16 +210,35 @@ impl GameState {
     ///
     /// This function is exclusively called by the user's input, meaning the "game loop" is not a while loop, but ticked by the player's actions.
     pub fn next_round(&mut self) {
-        self.player.character.tick_buffs();
-        let npc_ids: Vec<EntityId> = self.current_level().npc_index.keys().copied().collect();
-
-        for npc_id in npc_ids {
+        self.tick_player_buffs();
+        self.tick_player_crowd_control();
+        self.regenerate();
+        self.apply_terrain_effects();
+        self.refresh_player_map();
+        // NPCs take their turns in a fixed order, so the same inputs always play out the same way
+        let mut npc_ids = mem::take(&mut self.scratch.npc_ids);
+        npc_ids.clear();
+        npc_ids.extend(self.current_level().npcs.iter().map(|npc| npc.id()));
+
+        for &npc_id in &npc_ids {
             let _ = self.npc_take_turn(npc_id);
         }
+        self.scratch.npc_ids = npc_ids;
+
+        self.feed_animals();
 
+        self.check_gauntlet_completion();
+        self.unlock_cleared_doors();
+        self.decay_corpses();
+        self.apply_burrow_distortion();
+        if let Err(error) = self.spawn_wandering_monsters() {
+            self.log.debug_warn(format!("Couldn't spawn a wandering monster: {}", error));
+        }
         self.compute_fov();
+        self.sync_entity_registry();
 
         self.round_nr += 1;
+        self.log.set_round(self.round_nr);
     }
 }
 
@@ -109,6 +247,7 @@ impl Default for GameState {
     /// Used in tests.
     fn default() -> Self {
         Self {
+            seed: 73,
             levels: Vec::new(),
             level_nr: 0,
             player: Player::default(),
@@ -116,31 +255,45 @@ impl Default for GameState {
             log: Log::new(),
             round_nr: 0,
             id_system: IdSystem::default(),
+            entity_registry: EntityRegistry::default(),
             items: HashMap::new(),
             rng: StdRng::seed_from_u64(73),
             proc_gen: StdRng::seed_from_u64(42),
+            gen_config: GenConfig::default(),
+            difficulty: Difficulty::default(),
             game_rules: GameRules::empty(),
+            regen_rules: RegenRules::default(),
+            event_flags: EventFlags::empty(),
+            journal: Journal::default(),
+            quests: Vec::new(),
+            shops: ShopRegister::default(),
+            uniques: UniqueRegistry::default(),
+            stash: Stash::default(),
+            rewind_history: RewindHistory::default(),
+            run_stats: RunStats::default(),
+            replay: Replay::default(),
+            pending_lore: None,
+            pending_hits: Vec::new(),
+            pending_perks: Vec::new(),
+            resting: None,
+            travel: None,
+            scratch: ScratchBuffers::default(),
         }
     }
 }
 
-/// Generates an RNG instance
+/// Picks the master seed for a run that was not given one.
 ///
-/// # Returns
-/// * 0 - A [StdRng] instance
-/// * 1 - The seed of the rng instance
-fn rng_instance() -> (StdRng, u64) {
+/// Dev builds always use the same seed, so bugs can be reproduced.
+fn default_seed() -> u64 {
     #[cfg(feature = "dev")]
     {
-        // let seed: u64 = 73;
-        let seed: u64 = 8694791637633420993;
-        (StdRng::seed_from_u64(seed), seed)
+        8694791637633420993
     }
 
     #[cfg(not(feature = "dev"))]
     {
-        let seed: u64 = rand::rng().next_u64();
-        (StdRng::seed_from_u64(seed), seed)
+        rand::rng().next_u64()
     }
 }
 
@@ -150,7 +303,7 @@ fn rng_instance() -> (StdRng, u64) {
 
 /// Contains the counters for entity ids and item ids.
 /// Accessed through [IdSystem::next_entity_id] and [IdSystem::next_item_id]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct IdSystem {
     entity_id_counter: EntityId,
     item_id_counter: GameItemId,
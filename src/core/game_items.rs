@@ -1,13 +1,15 @@
 use core::fmt;
-use ratatui::style::Style;
 
 use crate::{
     core::{
         buff_effects::PotionEffectDef,
+        damage_types::{DamageAffinities, DamageType},
         entity_logic::{Entity, EntityBase, EntityId},
         game::GameState,
+        resistances::Resistances,
     },
     data::item_defs::{GameItemDef, GameItemDefId, item_defs},
+    render::style::{Color, Modifier, Style},
     util::{
         errors_results::{DataError, EngineError, GameError},
         rng::Roll,
@@ -20,10 +22,37 @@ use crate::{
 
 #[derive(Clone, Debug)]
 pub enum GameItemKindDef {
-    Weapon { damage: Roll, crit_chance: u8, range: AttackRange },
-    Armor { mitigation: u16 },
-    Food { nutrition: u16 },
-    Potion { effect: PotionEffectDef },
+    Weapon {
+        damage: Roll,
+        crit_chance: u8,
+        range: AttackRange,
+        damage_type: DamageType,
+    },
+    Armor {
+        mitigation: u16,
+        /// Chances to resist ailments while the armor is worn.
+        resistances: Resistances,
+        /// Mitigation against elemental damage, which the regular mitigation does not cover.
+        affinities: DamageAffinities,
+    },
+    Food {
+        nutrition: u16,
+    },
+    Potion {
+        effect: PotionEffectDef,
+    },
+    /// Used up to build a campfire next to the player.
+    FireStarter,
+    /// Can be read for a joke. Not used up.
+    JokeBook,
+    /// Lifts the curses of the equipped items when read. Used up.
+    RemoveCurse,
+    /// Only of value to whoever lost it. Fetch quests send the player after these.
+    Trinket,
+    /// Holds up to `capacity` other items, which then need no letter of the inventory. Bags cannot hold other bags.
+    Bag {
+        capacity: usize,
+    },
 }
 
 // Type to denote the range of an attack (weapon).
@@ -56,6 +85,15 @@ pub type GameItemId = u32;
 #[derive(Clone)]
 pub struct GameItem {
     pub def_id: GameItemDefId,
+
+    /// Items held by the item, if it is a bag. Empty for all other items.
+    pub contents: Vec<GameItemId>,
+
+    /// Bonus added to the damage of a weapon blessed at an altar.
+    pub blessing: i16,
+
+    /// A cursed weapon or armor cannot be taken off once equipped, until the curse is lifted.
+    pub cursed: bool,
 }
 
 impl GameState {
@@ -72,12 +110,23 @@ impl GameState {
         item_defs().get(def_id).ok_or(DataError::MissingItemDefinition(def_id.to_string()))?;
 
         let id: GameItemId = self.id_system.next_item_id();
-        self.items.insert(id, GameItem { def_id: def_id.clone() });
+        self.items.insert(
+            id,
+            GameItem { def_id: def_id.clone(), contents: Vec::new(), blessing: 0, cursed: false },
+        );
         self.log.debug_info(format!("Registered item {} (ID: {})", def_id, id));
 
         Ok(id)
     }
 
+    /// Registers an item for each of the given definitions (see [GameState::register_item]).
+    pub fn register_items(
+        &mut self,
+        def_ids: &[GameItemDefId],
+    ) -> Result<Vec<GameItemId>, GameError> {
+        def_ids.iter().map(|def_id| self.register_item(def_id)).collect()
+    }
+
     pub fn deregister_item(&mut self, item_id: GameItemId) -> Result<(), GameError> {
         match self.items.remove(&item_id) {
             Some(_) => {
@@ -133,6 +182,12 @@ impl GameState {
     }
 }
 
+/// Glyph shown on tiles where several items lie on top of each other.
+pub const ITEM_STACK_GLYPH: char = '*';
+
+/// Style of the [ITEM_STACK_GLYPH].
+pub const ITEM_STACK_STYLE: Style = Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
+
 // Item Sprite
 // Items lying on the ground in the world as entities.
 #[derive(Clone)]
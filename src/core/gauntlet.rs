@@ -0,0 +1,200 @@
+use crate::{
+    core::{entity_logic::Disposition, game::GameState},
+    util::{
+        errors_results::{FailReason, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+    world::{
+        coordinate_system::Point,
+        tiles::{ShrineState, TileType},
+    },
+};
+
+/// Maximum hit points the shrine of a completed gauntlet grants permanently.
+pub const SHRINE_HP_BONUS: u16 = 5;
+
+/// Radius around the entry of a gauntlet in which the memorial and the shrine are placed.
+const MEMORIAL_RADIUS: isize = 4;
+
+/// Record of the player's run through a gauntlet level. Every gauntlet level keeps its own record.
+#[derive(Clone, Copy, Debug)]
+pub struct GauntletRecord {
+    /// Round in which the player first entered the gauntlet.
+    pub entered_round: u64,
+    /// Damage the player took from enemies while in the gauntlet.
+    pub damage_taken: u32,
+    /// Round in which the last enemy was defeated. `None` while the gauntlet is not completed.
+    pub completed_round: Option<u64>,
+    /// Whether the memorial and the shrine have been placed in the level.
+    pub memorial_raised: bool,
+}
+
+impl GauntletRecord {
+    pub fn new(entered_round: u64) -> Self {
+        Self { entered_round, damage_taken: 0, completed_round: None, memorial_raised: false }
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed_round.is_some()
+    }
+
+    /// Number of turns it took the player to complete the gauntlet.
+    pub fn turns_taken(&self) -> Option<u64> {
+        self.completed_round.map(|round| round - self.entered_round)
+    }
+}
+
+impl GameState {
+    /// Adds damage the player took to the gauntlet record of the current level. Does nothing outside of gauntlets.
+    pub fn record_gauntlet_damage(&mut self, damage: u16) {
+        if let Some(record) = self.current_level_mut().gauntlet.as_mut()
+            && !record.is_completed()
+        {
+            record.damage_taken += damage as u32;
+        }
+    }
+
+    /// Marks the gauntlet of the current level as completed once all of its enemies are defeated.
+    ///
+    /// Only hostile NPCs count as enemies. Friendly and neutral NPCs, as well as companions, may still be around.
+    pub fn check_gauntlet_completion(&mut self) {
+        let round_nr = self.round_nr;
+        let level = self.current_level_mut();
+
+        if !level.npcs.iter().any(|npc| npc.disposition == Disposition::Hostile)
+            && let Some(record) = level.gauntlet.as_mut()
+            && !record.is_completed()
+        {
+            record.completed_round = Some(round_nr);
+            self.log.info(LogData::GauntletCompleted);
+        }
+    }
+
+    /// Turns a completed gauntlet into its memorial state. Called when the player returns to a level.
+    ///
+    /// A memorial describing the player's run and a shrine granting a permanent blessing are placed near the entry of the level.
+    /// This only happens once per level. Since levels are kept once generated, defeated enemies stay defeated.
+    pub fn raise_gauntlet_memorial(&mut self) {
+        let Some(record) = self.current_level().gauntlet else {
+            return;
+        };
+        if !record.is_completed() || record.memorial_raised {
+            return;
+        }
+
+        let level = self.current_level();
+        let mut free_points: Vec<Point> = level
+            .world
+            .get_points_in_radius(level.entry, MEMORIAL_RADIUS)
+            .into_iter()
+            .filter(|point| {
+                level.world.get_tile(*point).tile_type == TileType::Floor
+                    && level.is_available(*point)
+                    && level.get_corpse_at(*point).is_none()
+                    && *point != level.entry
+                    && *point != level.exit
+            })
+            .collect();
+        free_points
+            .sort_by_key(|point| (point.distance_squared_from(level.entry), point.y, point.x));
+
+        let level = self.current_level_mut();
+        let placements = [TileType::Memorial, TileType::Shrine(ShrineState::Active)];
+        for (point, tile_type) in free_points.into_iter().zip(placements) {
+            level.world.get_tile_mut(point).tile_type = tile_type;
+        }
+        if let Some(record) = level.gauntlet.as_mut() {
+            record.memorial_raised = true;
+        }
+
+        self.log.info(LogData::MemorialRaised);
+    }
+
+    /// The player reads the inscription of the memorial of the current level.
+    pub fn read_memorial(&mut self) -> GameResult {
+        let Some(record) = self.current_level().gauntlet else {
+            return Ok(GameOutcome::Fail(FailReason::NoInteraction));
+        };
+
+        self.log.info(LogData::MemorialInscription {
+            turns: record.turns_taken().unwrap_or_default(),
+            damage: record.damage_taken,
+        });
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// The player prays at the shrine at the given point, permanently raising their maximum hit points by [SHRINE_HP_BONUS]
+    /// and lifting the curses of their equipment. Afterwards, the shrine is depleted.
+    pub fn pray_at_shrine(&mut self, point: Point) -> GameResult {
+        self.player.character.stats.base.hp_max += SHRINE_HP_BONUS;
+        self.player.character.heal(SHRINE_HP_BONUS);
+        self.remove_curses();
+
+        self.current_world_mut().get_tile_mut(point).tile_type =
+            TileType::Shrine(ShrineState::Depleted);
+        self.log.info(LogData::ShrineBlessing { hp_bonus: SHRINE_HP_BONUS });
+
+        Ok(GameOutcome::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::entity_logic::Entity,
+        world::{level::Level, worldspace::Room},
+    };
+
+    #[test]
+    fn completed_gauntlet_gets_memorial_and_shrine() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        level.entry = Point::new(50, 10);
+        level.gauntlet = Some(GauntletRecord::new(0));
+        game.levels.insert(0, level);
+
+        game.round_nr = 40;
+        game.record_gauntlet_damage(12);
+        game.check_gauntlet_completion();
+        let record = game.current_level().gauntlet.unwrap();
+        assert_eq!(record.turns_taken(), Some(40));
+        assert_eq!(record.damage_taken, 12);
+
+        game.raise_gauntlet_memorial();
+        let tiles: Vec<TileType> =
+            game.current_world().tiles.iter().map(|tile| tile.tile_type).collect();
+        assert!(tiles.contains(&TileType::Memorial));
+        assert!(tiles.contains(&TileType::Shrine(ShrineState::Active)));
+
+        // Damage after completion doesn't count towards the record
+        game.record_gauntlet_damage(5);
+        assert_eq!(game.current_level().gauntlet.unwrap().damage_taken, 12);
+    }
+
+    #[test]
+    fn only_hostile_npcs_keep_the_gauntlet_going() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        level.gauntlet = Some(GauntletRecord::new(0));
+        game.levels.insert(0, level);
+
+        let frog = game.create_npc("funny_frog".into(), Point::new(8, 8)).unwrap();
+        assert_eq!(frog.disposition, Disposition::Friendly);
+        game.current_level_mut().spawn_npc(frog).unwrap();
+        let goblin = game.create_npc("goblin".into(), Point::new(12, 10)).unwrap();
+        let goblin_id = goblin.id();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+
+        game.check_gauntlet_completion();
+        assert!(!game.current_level().gauntlet.unwrap().is_completed());
+
+        game.current_level_mut().npcs.retain(|npc| npc.id() != goblin_id);
+        game.check_gauntlet_completion();
+        assert!(game.current_level().gauntlet.unwrap().is_completed());
+        assert_eq!(game.current_level().npcs.len(), 1);
+    }
+}
@@ -0,0 +1,210 @@
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use ron::de::from_reader;
+use ron::ser::{PrettyConfig, to_writer_pretty};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{appearance::PlayerAppearance, game::GameState},
+    util::errors_results::{GameError, IoError},
+};
+
+/// Number of runs kept in the high score table. Worse runs are dropped.
+pub const HIGH_SCORE_LIMIT: usize = 20;
+
+/// Longest name that can be entered for the high score table.
+pub const HIGH_SCORE_NAME_LENGTH: usize = 16;
+
+/// Summary of a finished run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    pub depth: usize,
+    pub experience: u32,
+    pub gold: u32,
+    pub turns: u64,
+    pub cause: String,
+    pub date: String,
+
+    /// Glyph and color of the character, so fallen characters can be recognized.
+    #[serde(default)]
+    pub appearance: PlayerAppearance,
+}
+
+/// Columns the high score table can be sorted by.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HighScoreSort {
+    #[default]
+    Score,
+    Depth,
+    Turns,
+    Date,
+}
+
+impl HighScoreSort {
+    /// The column the table is sorted by after this one.
+    pub fn next(self) -> Self {
+        match self {
+            HighScoreSort::Score => HighScoreSort::Depth,
+            HighScoreSort::Depth => HighScoreSort::Turns,
+            HighScoreSort::Turns => HighScoreSort::Date,
+            HighScoreSort::Date => HighScoreSort::Score,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HighScoreSort::Score => "score",
+            HighScoreSort::Depth => "depth",
+            HighScoreSort::Turns => "turns",
+            HighScoreSort::Date => "date",
+        }
+    }
+}
+
+/// Best runs of all games, kept in a file in the game's data directory.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HighScoreTable {
+    pub entries: Vec<HighScoreEntry>,
+
+    /// File the table is saved to. Tables without a file (e.g. in tests) are forgotten when the game ends.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Path of the file the high score table is saved to.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn high_scores_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("saves");
+    path.push("high_scores.ron");
+    Some(path)
+}
+
+/// Score of a run. Going deeper counts the most, finishing in fewer turns gives a small bonus.
+pub fn compute_score(depth: usize, experience: u32, gold: u32, turns: u64) -> u32 {
+    let turn_bonus = 1000u64.saturating_sub(turns / 10) as u32;
+    depth as u32 * 500 + experience + gold + turn_bonus
+}
+
+impl HighScoreTable {
+    /// Loads the table from the given file. A missing file means no run has been finished yet.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        let mut table = if path.exists() {
+            let file = File::open(path).map_err(IoError::FileReading)?;
+            from_reader::<_, HighScoreTable>(BufReader::new(file)).map_err(IoError::MapParsing)?
+        } else {
+            HighScoreTable::default()
+        };
+
+        table.path = Some(path.to_path_buf());
+        Ok(table)
+    }
+
+    /// Writes the table to its file, if it has one.
+    ///
+    /// # Errors
+    /// * [IoError::FileCreation] if the file or its directory could not be created.
+    /// * [IoError::MapWriting] if the table could not be written.
+    pub fn save(&self) -> Result<(), GameError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(IoError::FileCreation)?;
+        }
+
+        let file = File::create(path).map_err(IoError::FileCreation)?;
+        to_writer_pretty(BufWriter::new(file), self, PrettyConfig::default())
+            .map_err(IoError::MapWriting)?;
+        Ok(())
+    }
+
+    /// Adds a run to the table. Only the best [HIGH_SCORE_LIMIT] runs are kept.
+    ///
+    /// # Returns
+    /// * The rank of the run (starting at 1), if it made it into the table.
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self.entries.iter().take_while(|other| other.score >= entry.score).count();
+        if rank >= HIGH_SCORE_LIMIT {
+            return None;
+        }
+
+        self.entries.insert(rank, entry);
+        self.entries.truncate(HIGH_SCORE_LIMIT);
+        Some(rank + 1)
+    }
+
+    /// Gets the runs sorted by the given column, best first.
+    pub fn sorted(&self, sort: HighScoreSort) -> Vec<&HighScoreEntry> {
+        let mut entries: Vec<&HighScoreEntry> = self.entries.iter().collect();
+        match sort {
+            HighScoreSort::Score => entries.sort_by_key(|entry| Reverse(entry.score)),
+            HighScoreSort::Depth => entries.sort_by_key(|entry| Reverse(entry.depth)),
+            HighScoreSort::Turns => entries.sort_by_key(|entry| entry.turns),
+            HighScoreSort::Date => entries.sort_by(|a, b| b.date.cmp(&a.date)),
+        }
+        entries
+    }
+}
+
+impl GameState {
+    /// Summarizes the finished run for the high score table.
+    pub fn high_score_entry(&self, name: &str) -> HighScoreEntry {
+        let depth = self.run_stats.deepest_level.max(self.level_nr);
+        let experience = self.player.character.stats.experience;
+        let gold = self.player.character.gold;
+
+        HighScoreEntry {
+            name: name.to_string(),
+            score: compute_score(depth, experience, gold, self.round_nr),
+            depth,
+            experience,
+            gold,
+            turns: self.round_nr,
+            cause: self.cause_of_death(),
+            date: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            appearance: self.player.appearance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_keeps_the_best_runs_in_order() {
+        let mut table = HighScoreTable::default();
+        let mut game = GameState::default();
+
+        for depth in 0..HIGH_SCORE_LIMIT {
+            game.run_stats.deepest_level = depth;
+            assert!(table.insert(game.high_score_entry("Hero")).is_some());
+        }
+        assert_eq!(table.entries.len(), HIGH_SCORE_LIMIT);
+
+        // A run that is worse than all others does not make it in
+        game.run_stats.deepest_level = 0;
+        game.round_nr = 10_000;
+        assert_eq!(table.insert(game.high_score_entry("Slowpoke")), None);
+
+        game.run_stats.deepest_level = 40;
+        assert_eq!(table.insert(game.high_score_entry("Digger")), Some(1));
+        assert_eq!(table.entries.len(), HIGH_SCORE_LIMIT);
+        assert_eq!(table.sorted(HighScoreSort::Depth)[0].name, "Digger");
+        assert_eq!(table.sorted(HighScoreSort::Turns)[0].name, "Hero");
+    }
+}
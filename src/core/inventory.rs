@@ -5,31 +5,233 @@ use crate::{
         buff_effects::PotionEffectDef,
         game::GameState,
         game_items::{ArmorItem, GameItemId, GameItemKindDef, WeaponItem},
+        skills::Skill,
     },
+    data::item_defs::item_defs,
     util::{
         errors_results::{DataError, EngineError, FailReason, GameError, GameOutcome, GameResult},
         text_log::LogData,
     },
 };
 
-/// Constant defining the inventory limit.
+#[cfg(feature = "scripting")]
+use crate::core::scripting::ScriptEvent;
+
+/// Number of items listed on one page of the inventory window.
+///
+/// Is equal to 26, so the 26 letters of the alphabet can be used as indices on each page.
+pub const INVENTORY_PAGE_SIZE: usize = 26;
+
+/// Constant defining the inventory limit. No amount of strength lets the player carry more than two pages of items.
+pub const INVENTORY_LIMIT: usize = 2 * INVENTORY_PAGE_SIZE;
+
+/// Number of items the player can carry with a strength of zero.
+pub const BASE_INVENTORY_CAPACITY: usize = 24;
+
+/// Number of items the player can carry in addition for each point of strength, like the [carry capacity](crate::core::encumbrance::CARRY_CAPACITY_PER_STRENGTH).
+/// Items in bags do not count against it.
+pub const INVENTORY_CAPACITY_PER_STRENGTH: usize = 2;
+
+/// Number of items a character with the given strength can carry, up to the [INVENTORY_LIMIT].
+pub fn inventory_capacity(strength: u8) -> usize {
+    (BASE_INVENTORY_CAPACITY + strength as usize * INVENTORY_CAPACITY_PER_STRENGTH)
+        .min(INVENTORY_LIMIT)
+}
+
+/// Items carried by the player.
 ///
-/// Is equal to 26, so the 26 letters of the alphabet can be used as indices in the inventory window.
-pub const INVENTORY_LIMIT: usize = 26;
+/// Every item has a slot, which the inventory window lists as a page and a letter (a-z) on that page. The slot stays the same until
+/// the item leaves the inventory or the inventory is sorted, so letters don't shuffle around when other items are used up.
+/// New items get the first free slot within the capacity.
+#[derive(Clone, Debug)]
+pub struct Inventory {
+    slots: [Option<GameItemId>; INVENTORY_LIMIT],
+    capacity: usize,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self { slots: [None; INVENTORY_LIMIT], capacity: inventory_capacity(1) }
+    }
+}
+
+impl Inventory {
+    pub fn len(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.slots[..self.capacity].iter().all(Option::is_some)
+    }
+
+    pub fn contains(&self, item_id: &GameItemId) -> bool {
+        self.slots.contains(&Some(*item_id))
+    }
+
+    /// Number of items that fit into the inventory.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the number of items that fit into the inventory. Items already in slots beyond the new capacity stay there.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.min(INVENTORY_LIMIT);
+    }
+
+    /// Number of pages the inventory window needs to list every slot.
+    pub fn page_count(&self) -> usize {
+        let last_used = self.slots.iter().rposition(Option::is_some).map_or(0, |index| index + 1);
+        self.capacity.max(last_used).div_ceil(INVENTORY_PAGE_SIZE).max(1)
+    }
+
+    /// Iterates over the items in the order of their slots.
+    pub fn iter(&self) -> impl Iterator<Item = &GameItemId> {
+        self.slots.iter().flatten()
+    }
+
+    /// Iterates over the items on the given page together with their letters.
+    pub fn entries(&self, page: usize) -> impl Iterator<Item = (char, GameItemId)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .skip(page * INVENTORY_PAGE_SIZE)
+            .take(INVENTORY_PAGE_SIZE)
+            .filter_map(|(index, slot)| slot.map(|item_id| (index_to_letter(index), item_id)))
+    }
+
+    /// The item with the given letter on the given page.
+    pub fn get(&self, page: usize, letter: char) -> Option<GameItemId> {
+        let index = letter_to_index(letter)?;
+        self.slots.get(page * INVENTORY_PAGE_SIZE + index).copied().flatten()
+    }
+
+    /// Puts the item into the first free slot.
+    ///
+    /// # Returns
+    /// * The slot of the item, or `None` if the inventory is full.
+    pub fn insert(&mut self, item_id: GameItemId) -> Option<usize> {
+        let index = self.slots[..self.capacity].iter().position(Option::is_none)?;
+        self.slots[index] = Some(item_id);
+        Some(index)
+    }
+
+    /// Takes the item out of the inventory. The letters of the other items stay the same.
+    ///
+    /// # Returns
+    /// * `false` if the item was not in the inventory.
+    pub fn remove(&mut self, item_id: GameItemId) -> bool {
+        match self.slots.iter_mut().find(|slot| **slot == Some(item_id)) {
+            Some(slot) => {
+                *slot = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Gives the items new slots: The first item of the given order gets `a` on the first page, the second one `b` and so on.
+    /// Items that are not part of the order keep their place after them.
+    fn reorder(&mut self, order: &[GameItemId]) {
+        let mut slots = [None; INVENTORY_LIMIT];
+        let rest = self.iter().filter(|item_id| !order.contains(item_id));
+        for (slot, item_id) in slots.iter_mut().zip(order.iter().chain(rest)) {
+            *slot = Some(*item_id);
+        }
+        self.slots = slots;
+    }
+}
+
+impl<'a> IntoIterator for &'a Inventory {
+    type Item = &'a GameItemId;
+    type IntoIter = std::iter::Flatten<std::slice::Iter<'a, Option<GameItemId>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slots.iter().flatten()
+    }
+}
+
+/// Converts a letter [a-z] into the index [0-25] of an inventory slot on a page.
+pub fn letter_to_index(c: char) -> Option<usize> {
+    if c.is_ascii_lowercase() { Some((c as u8 - b'a') as usize) } else { None }
+}
+
+fn index_to_letter(index: usize) -> char {
+    (b'a' + (index % INVENTORY_PAGE_SIZE) as u8) as char
+}
+
+/// Orders the inventory can be sorted in with the `sort` command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InventoryOrder {
+    /// Weapons first, then armor, potions, food and other items. Items of the same kind are sorted by name.
+    Kind,
+
+    /// Alphabetically by name.
+    Name,
+}
+
+impl InventoryOrder {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "kind" | "type" => Some(InventoryOrder::Kind),
+            "name" => Some(InventoryOrder::Name),
+            _ => None,
+        }
+    }
+}
+
+/// Position of an item kind when the inventory is sorted by [InventoryOrder::Kind].
+fn kind_rank(kind: &GameItemKindDef) -> u8 {
+    match kind {
+        GameItemKindDef::Weapon { .. } => 0,
+        GameItemKindDef::Armor { .. } => 1,
+        GameItemKindDef::Potion { .. } => 2,
+        GameItemKindDef::Food { .. } => 3,
+        GameItemKindDef::FireStarter => 4,
+        GameItemKindDef::JokeBook => 5,
+        GameItemKindDef::RemoveCurse => 6,
+        GameItemKindDef::Trinket => 7,
+        GameItemKindDef::Bag { .. } => 8,
+    }
+}
 
 impl GameState {
+    /// Sorts the player's inventory, which gives the items new letters. Items with the same name keep the order they had before.
+    pub fn sort_inventory(&mut self, order: InventoryOrder) {
+        let mut items: Vec<(u8, &'static str, GameItemId)> = self
+            .player
+            .character
+            .inventory
+            .iter()
+            .filter_map(|item_id| {
+                let def = item_defs().get(&self.items.get(item_id)?.def_id)?;
+                let rank = match order {
+                    InventoryOrder::Kind => kind_rank(&def.kind),
+                    InventoryOrder::Name => 0,
+                };
+                Some((rank, def.name, *item_id))
+            })
+            .collect();
+        items.sort_by_key(|&(rank, name, _)| (rank, name));
+
+        let order: Vec<GameItemId> = items.into_iter().map(|(_, _, item_id)| item_id).collect();
+        self.player.character.inventory.reorder(&order);
+    }
+
     /// Takes an item's item_id and adds it to the player's inventory.
     ///
     /// # Returns
     /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player's inventory cannot take any more items.
     /// * [GameOutcome::Success] if the procedure was successful.
     pub fn add_item_to_inv(&mut self, item_id: u32) -> GameResult {
-        if self.player.character.inventory.len() >= INVENTORY_LIMIT {
+        if self.player.character.inventory.insert(item_id).is_none() {
             self.log.info(LogData::InventoryFull);
             return Ok(GameOutcome::Fail(FailReason::InventoryFull));
         }
 
-        self.player.character.inventory.push(item_id);
         Ok(GameOutcome::Success)
     }
 
@@ -41,11 +243,7 @@ impl GameState {
     /// # Returns
     /// * [GameOutcome::Success] if the procedure was successful.
     pub fn remove_item_from_inv(&mut self, item_id: u32) -> GameResult {
-        let search_item = self.player.character.inventory.iter().position(|item| *item == item_id);
-
-        if let Some(found_item) = search_item {
-            self.player.character.inventory.swap_remove(found_item);
-        } else {
+        if !self.player.character.inventory.remove(item_id) {
             let error = GameError::from(EngineError::ItemNotInInventory(item_id));
             self.log.debug_warn(format!("Couldn't remove item {}: {}", item_id, error));
             return Err(error);
@@ -57,25 +255,36 @@ impl GameState {
     /// Uses an item from the player's inventory.
     ///
     /// Checks whether the item is present, resolves its definition, and
-    /// dispatches to the appropriate handler (armor, weapon, or food).  
+    /// dispatches to the appropriate handler (armor, weapon, food, potion, fire starter or book).  
     /// Returns an error if the item is missing or unregistered.
     pub fn use_item(&mut self, item_id: u32) -> GameResult {
-        let search_item = self.player.character.inventory.iter().position(|item| *item == item_id);
-
-        if search_item.is_some() {
+        if self.player.character.inventory.contains(&item_id) {
             let item =
                 self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
 
             let item_def = self
                 .get_item_def_by_id(&item.def_id)
-                .ok_or(DataError::MissingItemDefinition(item.def_id))?;
+                .ok_or_else(|| DataError::MissingItemDefinition(item.def_id.clone()))?;
 
-            match item_def.kind {
+            let result = match item_def.kind {
                 GameItemKindDef::Armor { .. } => self.use_armor(item_id),
                 GameItemKindDef::Weapon { .. } => self.use_weapon(item_id),
                 GameItemKindDef::Food { nutrition } => self.use_food(item_id, nutrition),
                 GameItemKindDef::Potion { effect } => self.use_potion(&item_id, effect),
+                GameItemKindDef::FireStarter => self.use_fire_starter(item_id),
+                GameItemKindDef::JokeBook => self.read_joke_book(),
+                GameItemKindDef::RemoveCurse => self.read_remove_curse(item_id),
+                GameItemKindDef::Trinket => Ok(GameOutcome::Fail(FailReason::NoInteraction)),
+                // Bags open the container menu, which is handled by the UI.
+                GameItemKindDef::Bag { .. } => Ok(GameOutcome::Fail(FailReason::NoInteraction)),
+            };
+
+            #[cfg(feature = "scripting")]
+            if let Ok(GameOutcome::Success) = result {
+                self.fire_script_event(&ScriptEvent::Use(item.def_id))?;
             }
+
+            result
         } else {
             let error = GameError::from(EngineError::ItemNotInInventory(item_id));
             self.log.debug_warn(format!("Couldn't use item {}: {}", item_id, error));
@@ -89,9 +298,16 @@ impl GameState {
     /// * [EngineError::ItemNotInInventory] if the armor item couldn't be found in the inventory.
     ///
     /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::EquipmentCursed] if the equipped armor is cursed.
     /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player's inventory cannot take any more items.
     /// * [GameOutcome::Success] if the procedure was successful.
     pub fn use_armor(&mut self, item_id: GameItemId) -> GameResult {
+        if let Some(old_armor) = self.player.character.armor
+            && self.is_cursed(old_armor.0)
+        {
+            return Ok(GameOutcome::Fail(FailReason::EquipmentCursed));
+        }
+
         self.remove_item_from_inv(item_id)?;
 
         // if old armor exists, return it to inventory
@@ -101,6 +317,7 @@ impl GameState {
 
         // equip the new armor
         self.player.character.armor = Some(ArmorItem(item_id));
+        self.reveal_curse(item_id)?;
 
         Ok(GameOutcome::Success)
     }
@@ -111,9 +328,16 @@ impl GameState {
     /// * [EngineError::ItemNotInInventory] if the weapon item couldn't be found in the inventory.
     ///
     /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::EquipmentCursed] if the equipped weapon is cursed.
     /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player's inventory cannot take any more items.
     /// * [GameOutcome::Success] if the procedure was successful.
     pub fn use_weapon(&mut self, item_id: GameItemId) -> GameResult {
+        if let Some(old_weapon) = self.player.character.weapon
+            && self.is_cursed(old_weapon.0)
+        {
+            return Ok(GameOutcome::Fail(FailReason::EquipmentCursed));
+        }
+
         self.remove_item_from_inv(item_id)?;
 
         // if old weapon exists, return it to inventory
@@ -122,6 +346,7 @@ impl GameState {
         }
 
         self.player.character.weapon = Some(WeaponItem(item_id));
+        self.reveal_curse(item_id)?;
 
         Ok(GameOutcome::Success)
     }
@@ -159,9 +384,16 @@ impl GameState {
     ///
     /// # Returns
     /// * [GameOutcome::Fail] with [FailReason::EquipmentSlotEmpty] if the slot is empty (meaning nothing can be unequipped)
+    /// * [GameOutcome::Fail] with [FailReason::EquipmentCursed] if the armor is cursed.
     /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player's inventory cannot take any more items.
     /// * [GameOutcome::Success] if the procedure was successful.
     pub fn unequip_armor(&mut self) -> GameResult {
+        if let Some(armor_item) = self.player.character.armor
+            && self.is_cursed(armor_item.0)
+        {
+            return Ok(GameOutcome::Fail(FailReason::EquipmentCursed));
+        }
+
         if let Some(armor_item) = self.player.character.armor.take() {
             self.add_item_to_inv(armor_item.0)?;
 
@@ -175,9 +407,16 @@ impl GameState {
     ///
     /// # Returns
     /// * [GameOutcome::Fail] with [FailReason::EquipmentSlotEmpty] if the slot is empty (meaning nothing can be unequipped)
+    /// * [GameOutcome::Fail] with [FailReason::EquipmentCursed] if the weapon is cursed.
     /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player's inventory cannot take any more items.
     /// * [GameOutcome::Success] if the procedure was successful.
     pub fn unequip_weapon(&mut self) -> GameResult {
+        if let Some(weapon_item) = self.player.character.weapon
+            && self.is_cursed(weapon_item.0)
+        {
+            return Ok(GameOutcome::Fail(FailReason::EquipmentCursed));
+        }
+
         if let Some(weapon_item) = self.player.character.weapon.take() {
             self.add_item_to_inv(weapon_item.0)?;
 
@@ -197,9 +436,68 @@ impl GameState {
     /// # Returns
     /// * [GameOutcome::Success] if the procedure was successful.
     pub fn use_potion(&mut self, item_id: &GameItemId, effect: PotionEffectDef) -> GameResult {
+        let effect = match effect {
+            PotionEffectDef::Heal { amount } => {
+                let amount = self.player.character.alchemy_healing(amount);
+                PotionEffectDef::Heal { amount: self.player.class.potion_healing(amount) }
+            }
+            effect => effect,
+        };
         self.apply_potion_effect(effect);
+        self.run_stats.potions_drunk += 1;
+        self.train_skill(Skill::Alchemy);
 
         self.remove_item_from_inv(*item_id)?;
         Ok(GameOutcome::Success)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_stay_until_the_inventory_is_sorted() {
+        let mut game = GameState::default();
+        let [cake, dagger, leather] = ["food_cake", "weapon_dagger", "armor_leather"]
+            .map(|def_id| game.register_item(&def_id.to_string()).unwrap());
+        for item_id in [cake, dagger, leather] {
+            game.add_item_to_inv(item_id).unwrap();
+        }
+
+        // Removing an item leaves a gap, which the next item fills
+        game.remove_item_from_inv(cake).unwrap();
+        let inventory = &game.player.character.inventory;
+        assert_eq!(inventory.get(0, 'b'), Some(dagger));
+        assert_eq!(inventory.get(0, 'c'), Some(leather));
+        game.add_item_to_inv(cake).unwrap();
+        assert_eq!(game.player.character.inventory.get(0, 'a'), Some(cake));
+
+        game.sort_inventory(InventoryOrder::Kind);
+        let entries: Vec<(char, GameItemId)> = game.player.character.inventory.entries(0).collect();
+        assert_eq!(entries, vec![('a', dagger), ('b', leather), ('c', cake)]);
+    }
+
+    #[test]
+    fn strength_opens_a_second_page() {
+        let mut game = GameState::default();
+        assert_eq!(game.player.character.inventory.capacity(), INVENTORY_PAGE_SIZE);
+
+        for _ in 0..INVENTORY_PAGE_SIZE {
+            let item_id = game.register_item(&"food_apple".to_string()).unwrap();
+            game.add_item_to_inv(item_id).unwrap();
+        }
+        let apple = game.register_item(&"food_apple".to_string()).unwrap();
+        assert!(matches!(
+            game.add_item_to_inv(apple),
+            Ok(GameOutcome::Fail(FailReason::InventoryFull))
+        ));
+        assert_eq!(game.player.character.inventory.page_count(), 1);
+
+        game.player.character.gain_experience(game.player.character.stats.experience_needed());
+        assert_eq!(game.player.character.inventory.capacity(), INVENTORY_PAGE_SIZE + 2);
+        assert!(matches!(game.add_item_to_inv(apple), Ok(GameOutcome::Success)));
+        assert_eq!(game.player.character.inventory.page_count(), 2);
+        assert_eq!(game.player.character.inventory.get(1, 'a'), Some(apple));
+    }
+}
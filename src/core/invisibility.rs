@@ -0,0 +1,105 @@
+use crate::{
+    core::{
+        buff_effects::PotionEffectDef,
+        entity_logic::{Entity, EntityId, Npc},
+        game::GameState,
+    },
+    data::npc_defs::npc_defs,
+    util::{
+        errors_results::{EngineError, GameError},
+        text_log::LogData,
+    },
+};
+
+/// Number of turns an invisible NPC stays visible after it gave itself away by attacking.
+pub const REVEAL_TURNS: u8 = 3;
+
+impl GameState {
+    /// Whether the player drank a potion that lets them see invisible creatures, and it has not worn off yet.
+    pub fn player_sees_invisible(&self) -> bool {
+        self.player
+            .character
+            .active_buffs
+            .iter()
+            .any(|buff| matches!(buff.effect, PotionEffectDef::SeeInvisible { .. }))
+    }
+
+    /// Whether the NPC is invisible to the player right now.
+    ///
+    /// Invisible NPCs (see [NpcDef::invisible](crate::data::npc_defs::NpcDef::invisible)) can be seen while they are
+    /// revealed, while the player sees invisible creatures and while they stand right next to the player.
+    pub fn is_hidden(&self, npc: &Npc) -> bool {
+        let invisible = npc_defs().get(&npc.def_id).is_some_and(|def| def.invisible);
+        invisible
+            && npc.revealed_turns == 0
+            && !self.player_sees_invisible()
+            && !npc.footprint().is_adjacent_to(self.player.character.pos())
+    }
+
+    /// Whether the player can see the NPC, i.e. it is not hidden and stands on a tile in sight.
+    pub fn can_see_npc(&self, npc: &Npc) -> bool {
+        !self.is_hidden(npc)
+            && npc.footprint().points().any(|point| self.current_world().get_tile(point).visible)
+    }
+
+    /// Makes an invisible NPC visible for [REVEAL_TURNS] turns. The player is told unless it was revealed already.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not on the current level.
+    pub fn reveal_npc(&mut self, npc_id: EntityId) -> Result<(), GameError> {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        if !npc_defs().get(&npc.def_id).is_some_and(|def| def.invisible) {
+            return Ok(());
+        }
+        let was_unseen = npc.revealed_turns == 0 && !self.player_sees_invisible();
+        let npc_name = npc.name().to_string();
+
+        if let Some(npc) = self.current_level_mut().get_npc_mut(npc_id) {
+            npc.revealed_turns = REVEAL_TURNS;
+        }
+        if was_unseen {
+            self.log.info(LogData::NpcRevealed { npc_name });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::buff_effects::ActiveBuff,
+        world::{coordinate_system::Point, level::Level, worldspace::Room},
+    };
+
+    #[test]
+    fn invisible_npcs_are_seen_up_close_when_revealed_or_with_see_invisible() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        let wraith = game.create_npc("wraith".into(), Point::new(13, 10)).unwrap();
+        let wraith_id = wraith.id();
+        game.current_level_mut().spawn_npc(wraith).unwrap();
+        let hidden =
+            |game: &GameState| game.is_hidden(game.current_level().get_npc(wraith_id).unwrap());
+
+        assert!(hidden(&game));
+
+        game.reveal_npc(wraith_id).unwrap();
+        assert!(!hidden(&game));
+        game.current_level_mut().get_npc_mut(wraith_id).unwrap().revealed_turns = 0;
+
+        game.player.character.active_buffs.push(ActiveBuff {
+            effect: PotionEffectDef::SeeInvisible { duration: 5 },
+            remaining_turns: 5,
+        });
+        assert!(!hidden(&game));
+        game.player.character.active_buffs.clear();
+
+        game.player.character.base.pos = Point::new(12, 10);
+        assert!(!hidden(&game));
+    }
+}
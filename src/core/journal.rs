@@ -0,0 +1,45 @@
+use crate::core::game::GameState;
+
+/// Noteworthy moments of the run, written down by the player character.
+///
+/// Unlike the log, the journal is kept for the whole run and only contains things worth remembering.
+#[derive(Clone, Default)]
+pub struct Journal {
+    pub entries: Vec<JournalEntry>,
+}
+
+/// A single entry of the [Journal].
+#[derive(Clone)]
+pub struct JournalEntry {
+    /// Round in which the entry was written.
+    pub round_nr: u64,
+    /// Level the player was on.
+    pub level_nr: usize,
+    pub text: String,
+}
+
+impl GameState {
+    /// Writes an entry into the player's journal.
+    pub fn write_journal(&mut self, text: &str) {
+        self.journal.entries.push(JournalEntry {
+            round_nr: self.round_nr,
+            level_nr: self.level_nr,
+            text: text.to_string(),
+        });
+    }
+
+    /// Formats the journal as paragraphs for displaying, oldest entries first.
+    pub fn journal_paragraphs(&self) -> Vec<String> {
+        if self.journal.entries.is_empty() {
+            return vec!["Nothing worth remembering has happened yet.".to_string()];
+        }
+
+        self.journal
+            .entries
+            .iter()
+            .map(|entry| {
+                format!("Round {}, Level {}: {}", entry.round_nr, entry.level_nr, entry.text)
+            })
+            .collect()
+    }
+}
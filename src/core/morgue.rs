@@ -0,0 +1,155 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::{
+    core::{entity_logic::Entity, game::GameState, game_items::GameItemId, skills::Skill},
+    util::{
+        build_info::BuildInfo,
+        text_log::{LogData, LogEntry},
+    },
+};
+
+/// Number of log messages written into the morgue file.
+pub const MORGUE_LOG_LENGTH: usize = 20;
+
+impl GameState {
+    /// Summarizes the run for the game over screen.
+    pub fn death_recap(&self) -> Vec<String> {
+        let character = &self.player.character;
+        let weapon = character.weapon.map(|weapon| self.item_name(weapon.0));
+        let armor = character.armor.map(|armor| self.item_name(armor.0));
+
+        let mut lines = vec![self.cause_of_death()];
+        lines.extend(self.run_stats_lines());
+        lines.push(format!("Gold: {} ({} looted)", character.gold, self.run_stats.gold_looted));
+        lines.push(format!(
+            "Items: {} carried, wielding {}, wearing {}",
+            character.inventory.len(),
+            weapon.unwrap_or("nothing"),
+            armor.unwrap_or("nothing")
+        ));
+        lines
+    }
+
+    /// Writes an obituary of the run into a new text file in the game's logs directory, like the morgue files of classic roguelikes.
+    ///
+    /// # Returns
+    /// * The path of the written file.
+    ///
+    /// # Errors
+    /// * [io::Error] if the file could not be created or written.
+    pub fn write_morgue_file(&self) -> io::Result<PathBuf> {
+        let mut path = dirs::data_local_dir()
+            .ok_or(io::Error::new(io::ErrorKind::NotFound, "No data directory found on this OS"))?;
+        path.push("Anthill");
+        path.push("logs");
+        fs::create_dir_all(&path)?;
+
+        let filename =
+            format!("anthill_morgue_{}.txt", chrono::Local::now().format("%Y-%m-%d-%H-%M-%S"));
+        path.push(filename);
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writer.write_all(self.morgue_text().as_bytes())?;
+        writer.flush()?;
+
+        Ok(path)
+    }
+
+    /// Contents of the morgue file: The recap, the character, its belongings, the journal and the last messages of the log.
+    pub fn morgue_text(&self) -> String {
+        let character = &self.player.character;
+        let stats = &character.stats;
+        let mut lines = vec![
+            format!("Anthill obituary of {}", character.name()),
+            format!("Died on {}", chrono::Local::now().format("%Y-%m-%d %H:%M")),
+            format!("Anthill {}", BuildInfo::current().summary()),
+            String::new(),
+        ];
+
+        lines.extend(self.death_recap());
+
+        lines.push(String::new());
+        lines.push("Character".to_string());
+        lines.push(format!("  Appearance: {}", self.player.appearance.description()));
+        lines.push(format!("  Level {} with {} EXP", stats.level, stats.experience));
+        lines.push(format!("  HP {}/{}", stats.base.hp_current, stats.base.hp_max));
+        let skills: Vec<String> = Skill::ALL
+            .iter()
+            .map(|skill| format!("{} {}", skill, character.skills.rank(*skill)))
+            .collect();
+        lines.push(format!("  Skills: {}", skills.join(", ")));
+
+        lines.push(String::new());
+        lines.push("Kills".to_string());
+        if self.run_stats.kills.is_empty() {
+            lines.push("  (none)".to_string());
+        }
+        for kill_line in self.kill_lines() {
+            lines.push(format!("  {}", kill_line));
+        }
+
+        lines.push(String::new());
+        lines.push("Inventory".to_string());
+        if character.inventory.is_empty() {
+            lines.push("  (empty)".to_string());
+        }
+        for item_id in &character.inventory {
+            lines.push(format!("  {}", self.item_name(*item_id)));
+        }
+
+        lines.push(String::new());
+        lines.push("Journal".to_string());
+        for paragraph in self.journal_paragraphs() {
+            lines.push(format!("  {}", paragraph));
+        }
+
+        lines.push(String::new());
+        lines.push("Last messages".to_string());
+        let messages: Vec<&LogEntry> = self
+            .log
+            .messages
+            .iter()
+            .filter(|entry| !matches!(entry.data, LogData::DebugInfo(_) | LogData::DebugWarn(_)))
+            .collect();
+        for message in &messages[messages.len().saturating_sub(MORGUE_LOG_LENGTH)..] {
+            lines.push(format!("  {}", message.line()));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Name of a registered item, for the recap.
+    fn item_name(&self, item_id: GameItemId) -> &'static str {
+        self.get_item_by_id(item_id)
+            .and_then(|item| self.get_item_def_by_id(&item.def_id))
+            .map_or("<unknown item>", |item_def| item_def.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::run_stats::DamageSource, world::level::LevelEntrance};
+
+    #[test]
+    fn recap_knows_the_cause_of_death() {
+        let mut game = GameState::default();
+        game.goto_level(2, LevelEntrance::Entry).unwrap();
+        game.goto_level(1, LevelEntrance::Exit).unwrap();
+
+        let sword = game.register_item(&"weapon_sword_dull".to_string()).unwrap();
+        game.add_item_to_inv(sword).unwrap();
+        game.record_damage_taken(0, DamageSource::Lava);
+        game.record_damage_taken(3, DamageSource::Npc("Goblin".into()));
+
+        let recap = game.death_recap();
+        assert_eq!(recap[0], "Slain by a Goblin");
+        assert_eq!(recap[1], "Depth: floor 1 (deepest 2)");
+
+        let morgue = game.morgue_text();
+        assert!(morgue.contains("Slain by a Goblin"));
+        assert!(morgue.contains("  Dull Sword"));
+    }
+}
@@ -0,0 +1,310 @@
+use std::{fmt, ops::RangeInclusive};
+
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::npc_ai::NpcAiState,
+    core::{
+        entity_logic::{Disposition, Entity, EntityId, Movable},
+        game::GameState,
+        shop::SHOPKEEPER_NPCS,
+        town::HEALER_NPCS,
+    },
+    data::dialogue::get_dialogue,
+    util::{
+        errors_results::{EngineError, FailReason, GameError, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+    world::coordinate_system::Point,
+};
+
+/// NPCs that let the player pet them.
+const PETTABLE_NPCS: [&str; 1] = ["funny_frog"];
+
+/// Amount of gold the player takes when stealing from an NPC.
+const STOLEN_GOLD: RangeInclusive<u32> = 5..=20;
+
+/// Interactions the player can choose from when interacting with an adjacent NPC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NpcInteraction {
+    /// Say something to the NPC. Only NPCs with dialogue can be talked to.
+    Talk,
+
+    /// Pet the NPC. Only a few NPCs let you do that.
+    Pet,
+
+    /// Buy from the NPC's shop. Only shopkeepers trade.
+    Trade,
+
+    /// Pay the NPC to heal your wounds. Only healers do that.
+    Heal,
+
+    /// Ask the NPC for a quest. Each friendly NPC gives one quest per run.
+    Quest,
+
+    /// Push the NPC one tile away from the player.
+    Shove,
+
+    /// Try to steal gold from the NPC. Needs a perk like Light Fingers, and an NPC that catches you turns hostile.
+    Steal,
+
+    /// Attack the NPC, just like bumping into it.
+    Attack,
+}
+
+impl fmt::Display for NpcInteraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpcInteraction::Talk => write!(f, "Talk"),
+            NpcInteraction::Pet => write!(f, "Pet"),
+            NpcInteraction::Trade => write!(f, "Trade"),
+            NpcInteraction::Heal => write!(f, "Heal"),
+            NpcInteraction::Quest => write!(f, "Ask for work"),
+            NpcInteraction::Shove => write!(f, "Shove"),
+            NpcInteraction::Steal => write!(f, "Steal"),
+            NpcInteraction::Attack => write!(f, "Attack"),
+        }
+    }
+}
+
+impl GameState {
+    /// Lists the interactions the player can choose from for the given NPC.
+    ///
+    /// Hostile NPCs can only be attacked.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not in the current level.
+    pub fn npc_interactions(&self, npc_id: EntityId) -> Result<Vec<NpcInteraction>, GameError> {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+
+        if npc.disposition == Disposition::Hostile {
+            return Ok(vec![NpcInteraction::Attack]);
+        }
+
+        let mut interactions = Vec::new();
+        if get_dialogue(&npc.def_id).is_some_and(|lines| !lines.is_empty()) {
+            interactions.push(NpcInteraction::Talk);
+        }
+        if PETTABLE_NPCS.contains(&npc.def_id.as_str()) || npc.disposition == Disposition::Companion
+        {
+            interactions.push(NpcInteraction::Pet);
+        }
+        if SHOPKEEPER_NPCS.contains(&npc.def_id.as_str()) {
+            interactions.push(NpcInteraction::Trade);
+        }
+        if HEALER_NPCS.contains(&npc.def_id.as_str()) {
+            interactions.push(NpcInteraction::Heal);
+        }
+        if self.offers_quest(npc_id) {
+            interactions.push(NpcInteraction::Quest);
+        }
+        interactions.push(NpcInteraction::Shove);
+        if self.player.character.perk_steal_chance() > 0
+            && npc.disposition != Disposition::Companion
+        {
+            interactions.push(NpcInteraction::Steal);
+        }
+        interactions.push(NpcInteraction::Attack);
+
+        Ok(interactions)
+    }
+
+    /// The player performs an interaction with an adjacent NPC.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not in the current level.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::OutOfRange] if the NPC is not adjacent to the player.
+    /// * [GameOutcome::Fail] with [FailReason::InvalidTarget] if the interaction is not available for this NPC.
+    /// * The result of the interaction otherwise.
+    pub fn interact_with_npc(
+        &mut self,
+        npc_id: EntityId,
+        interaction: NpcInteraction,
+    ) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        if npc.pos().distance_squared_from(self.player.character.pos()) != 1 {
+            return Ok(GameOutcome::Fail(FailReason::OutOfRange));
+        }
+
+        if !self.npc_interactions(npc_id)?.contains(&interaction) {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id)));
+        }
+
+        match interaction {
+            NpcInteraction::Talk => self.talk_to_npc(npc_id),
+            NpcInteraction::Pet => self.pet_npc(npc_id),
+            NpcInteraction::Trade => self.stock_shop(npc_id),
+            NpcInteraction::Heal => self.buy_healing(npc_id),
+            NpcInteraction::Quest => self.accept_quest(npc_id),
+            NpcInteraction::Shove => self.shove_npc(npc_id),
+            NpcInteraction::Steal => self.steal_from_npc(npc_id),
+            NpcInteraction::Attack => self.player_attack_npc(npc_id),
+        }
+    }
+
+    /// The NPC answers with a random line of its dialogue.
+    fn talk_to_npc(&mut self, npc_id: EntityId) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_name = npc.name().to_string();
+
+        let Some(line) = get_dialogue(&npc.def_id).and_then(|lines| lines.choose(&mut self.rng))
+        else {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id)));
+        };
+
+        self.log.info(LogData::NpcSays { npc_name, line: line.to_string() });
+        Ok(GameOutcome::Success)
+    }
+
+    /// The player pets the NPC.
+    fn pet_npc(&mut self, npc_id: EntityId) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+
+        let is_frog = npc.def_id == "funny_frog";
+
+        self.log.info(LogData::NpcPetted { npc_name: npc.name().to_string() });
+        if is_frog {
+            self.remember_frog_petted();
+        }
+        Ok(GameOutcome::Success)
+    }
+
+    /// Pushes the NPC one tile away from the player.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::TileOccupied] if the tile behind the NPC is blocked.
+    /// * [GameOutcome::Success] if the NPC was pushed.
+    fn shove_npc(&mut self, npc_id: EntityId) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_name = npc.name().to_string();
+        let npc_pos = npc.pos();
+
+        let push = npc_pos - self.player.character.pos();
+        let target_x = npc_pos.x as isize + push.x;
+        let target_y = npc_pos.y as isize + push.y;
+        let target = Point::new(target_x as usize, target_y as usize);
+
+        if !self.current_world().is_in_bounds(target_x, target_y)
+            || !self.current_level().is_available(target)
+        {
+            return Ok(GameOutcome::Fail(FailReason::TileOccupied(target)));
+        }
+
+        if let Some(npc) = self.current_level_mut().get_npc_mut(npc_id) {
+            npc.move_to(target);
+        }
+        self.log.info(LogData::NpcShoved { npc_name });
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// The player tries to steal gold from the NPC. The perks decide the chance to succeed (see [PlayerCharacter::perk_steal_chance](crate::core::player::PlayerCharacter::perk_steal_chance)).
+    ///
+    /// If the NPC notices, it turns hostile and attacks the player.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Success] if the player tried to steal (caught or not).
+    fn steal_from_npc(&mut self, npc_id: EntityId) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_name = npc.name().to_string();
+
+        let chance = self.player.character.perk_steal_chance();
+        if self.rng.random_range(0..100) >= chance {
+            if let Some(npc) = self.current_level_mut().get_npc_mut(npc_id) {
+                npc.disposition = Disposition::Hostile;
+                npc.ai_state = NpcAiState::Aggressive;
+            }
+            self.log.info(LogData::CaughtStealing { npc_name });
+            return Ok(GameOutcome::Success);
+        }
+
+        let amount = self.rng.random_range(STOLEN_GOLD);
+        self.player.character.gold += amount;
+        self.log.info(LogData::GoldStolen { npc_name, amount });
+        Ok(GameOutcome::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    #[test]
+    fn hostile_npcs_only_offer_attack() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+
+        let goblin = game.create_npc("goblin".into(), Point::new(50, 7)).unwrap();
+        let goblin_id = goblin.id();
+        level.spawn_npc(goblin).unwrap();
+        let frog = game.create_npc("funny_frog".into(), Point::new(51, 10)).unwrap();
+        let frog_id = frog.id();
+        level.spawn_npc(frog).unwrap();
+        game.levels.insert(0, level);
+
+        assert_eq!(game.npc_interactions(goblin_id).unwrap(), vec![NpcInteraction::Attack]);
+        assert!(game.npc_interactions(frog_id).unwrap().contains(&NpcInteraction::Pet));
+
+        game.player.character.move_to(Point::new(50, 10));
+        assert!(matches!(
+            game.interact_with_npc(frog_id, NpcInteraction::Shove),
+            Ok(GameOutcome::Success)
+        ));
+        assert_eq!(game.current_level().get_npc(frog_id).unwrap().pos(), Point::new(52, 10));
+    }
+
+    /// Game with the player next to a frog, who has the perk to steal from it. The seed decides whether the frog notices.
+    fn game_with_frog_to_steal_from(seed: u64) -> (GameState, EntityId) {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        let frog = game.create_npc("funny_frog".into(), Point::new(51, 10)).unwrap();
+        let frog_id = frog.id();
+        level.spawn_npc(frog).unwrap();
+        game.levels.insert(0, level);
+        game.player.character.move_to(Point::new(50, 10));
+
+        assert!(!game.npc_interactions(frog_id).unwrap().contains(&NpcInteraction::Steal));
+        game.choose_perk("light_fingers".to_string()).unwrap();
+        game.rng = StdRng::seed_from_u64(seed);
+        (game, frog_id)
+    }
+
+    #[test]
+    fn stealing_unnoticed_gives_gold() {
+        let (mut game, frog_id) = game_with_frog_to_steal_from(2);
+
+        assert!(matches!(
+            game.interact_with_npc(frog_id, NpcInteraction::Steal),
+            Ok(GameOutcome::Success)
+        ));
+        assert!(STOLEN_GOLD.contains(&game.player.character.gold));
+        assert_ne!(
+            game.current_level().get_npc(frog_id).unwrap().disposition,
+            Disposition::Hostile
+        );
+    }
+
+    #[test]
+    fn getting_caught_stealing_turns_the_npc_hostile() {
+        let (mut game, frog_id) = game_with_frog_to_steal_from(0);
+
+        assert!(matches!(
+            game.interact_with_npc(frog_id, NpcInteraction::Steal),
+            Ok(GameOutcome::Success)
+        ));
+        assert_eq!(game.player.character.gold, 0);
+        assert_eq!(
+            game.current_level().get_npc(frog_id).unwrap().disposition,
+            Disposition::Hostile
+        );
+        assert_eq!(game.npc_interactions(frog_id).unwrap(), vec![NpcInteraction::Attack]);
+    }
+}
@@ -0,0 +1,127 @@
+use rand::seq::IndexedRandom;
+
+use crate::{
+    core::{game::GameState, player::PlayerCharacter},
+    data::perks::{PerkEffect, PerkId, perk_defs},
+    util::{
+        errors_results::{DataError, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+};
+
+/// Every this many levels, the player gets to pick a perk.
+pub const PERK_LEVEL_INTERVAL: u8 = 3;
+
+/// Number of perks offered to pick from.
+pub const PERK_CHOICES: usize = 3;
+
+impl PlayerCharacter {
+    /// Effects of the perks the character picked.
+    pub fn perk_effects(&self) -> impl Iterator<Item = PerkEffect> + '_ {
+        self.perks.iter().filter_map(|perk_id| perk_defs().get(perk_id)).map(|def| def.effect)
+    }
+
+    /// Critical hit chance (in percent) the perks add to the character's attacks.
+    pub fn perk_crit_chance(&self) -> i16 {
+        self.perk_effects()
+            .map(|effect| if let PerkEffect::CritChance(chance) = effect { chance } else { 0 })
+            .sum()
+    }
+
+    /// Mitigation the perks add to the character's defense.
+    pub fn perk_mitigation(&self) -> i16 {
+        self.perk_effects()
+            .map(|effect| if let PerkEffect::Mitigation(amount) = effect { amount } else { 0 })
+            .sum()
+    }
+
+    /// Dodge chance (in percent) the perks add to the character's defense.
+    pub fn perk_dodge_chance(&self) -> u8 {
+        self.perk_effects()
+            .map(|effect| if let PerkEffect::Dodge(chance) = effect { chance } else { 0 })
+            .sum()
+    }
+
+    /// Chance (in percent) the character steals without getting caught. Without a perk for it, they can't steal at all.
+    pub fn perk_steal_chance(&self) -> u8 {
+        self.perk_effects()
+            .map(|effect| if let PerkEffect::Pickpocket(chance) = effect { chance } else { 0 })
+            .sum()
+    }
+
+    /// Poison damage the character takes in a turn, after their perks.
+    pub fn poison_damage(&self, damage: u16) -> u16 {
+        if self.perk_effects().any(|effect| effect == PerkEffect::PoisonResistance) {
+            damage.div_ceil(2)
+        } else {
+            damage
+        }
+    }
+}
+
+impl GameState {
+    /// Offers a random selection of perks the player does not have yet, if they reached a level that is a perk milestone.
+    /// The UI lets the player pick one of the [GameState::pending_perks].
+    pub fn offer_perks(&mut self, level: u8) {
+        if !level.is_multiple_of(PERK_LEVEL_INTERVAL) || !self.pending_perks.is_empty() {
+            return;
+        }
+
+        // The registry has no order, so the perks are sorted to keep runs with the same seed the same
+        let mut available: Vec<&PerkId> = perk_defs()
+            .keys()
+            .filter(|perk_id| !self.player.character.perks.contains(perk_id))
+            .collect();
+        available.sort();
+
+        self.pending_perks =
+            available.choose_multiple(&mut self.rng, PERK_CHOICES).map(|&id| id.clone()).collect();
+    }
+
+    /// Gives the player the perk and clears the offer of [GameState::pending_perks].
+    ///
+    /// # Errors
+    /// * [DataError::MissingPerkDefinition] if the perk is not defined.
+    pub fn choose_perk(&mut self, perk_id: PerkId) -> GameResult {
+        let def = perk_defs()
+            .get(&perk_id)
+            .ok_or_else(|| DataError::MissingPerkDefinition(perk_id.clone()))?;
+
+        if let PerkEffect::MaxHp(amount) = def.effect {
+            let base = &mut self.player.character.stats.base;
+            base.hp_max += amount;
+            base.hp_current += amount;
+        }
+
+        self.player.character.perks.push(perk_id);
+        self.pending_perks.clear();
+        self.log.info(LogData::PerkGained { perk_name: def.name.to_string() });
+        Ok(GameOutcome::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perks_are_offered_on_milestones_and_apply_their_effects() {
+        let mut game = GameState::default();
+        game.offer_perks(PERK_LEVEL_INTERVAL - 1);
+        assert!(game.pending_perks.is_empty());
+
+        game.offer_perks(PERK_LEVEL_INTERVAL);
+        assert_eq!(game.pending_perks.len(), PERK_CHOICES);
+
+        game.choose_perk("antivenom".to_string()).unwrap();
+        game.choose_perk("keen_edge".to_string()).unwrap();
+        assert!(game.pending_perks.is_empty());
+
+        let character = &game.player.character;
+        assert_eq!(character.poison_damage(5), 3);
+        assert_eq!(character.perk_crit_chance(), 10);
+
+        game.offer_perks(PERK_LEVEL_INTERVAL * 2);
+        assert!(game.pending_perks.iter().all(|id| id != "antivenom" && id != "keen_edge"));
+    }
+}
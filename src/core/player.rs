@@ -1,40 +1,72 @@
 use std::collections::HashMap;
 
+use crate::core::appearance::PlayerAppearance;
 use crate::core::buff_effects::{ActiveBuff, PotionEffectDef, PotionType, PotionUsage};
+use crate::core::crowd_control::CrowdControlTurns;
 use crate::core::entity_logic::{BaseStats, Entity, EntityBase, EntityId, Movable};
 use crate::core::game::{GameRules, GameState};
-use crate::core::game_items::{ArmorItem, GameItemId, WeaponItem};
+use crate::core::game_items::{ArmorItem, WeaponItem};
+use crate::core::inventory::{Inventory, inventory_capacity};
+use crate::core::player_class::PlayerClass;
+use crate::core::skills::Skills;
+use crate::data::perks::PerkId;
+use crate::render::style::Color;
 use crate::util::text_log::LogData;
 use crate::world::coordinate_system::Point;
-use ratatui::style::Color;
 
+#[derive(Clone)]
 pub struct Player {
     #[allow(dead_code)]
     pub name: String,
     pub character: PlayerCharacter,
+    /// Glyph and color picked at character creation.
+    pub appearance: PlayerAppearance,
+    /// Class picked at character creation (see [GameState::set_player_class]).
+    pub class: PlayerClass,
 }
 
 impl Player {
     pub fn new(id: EntityId) -> Self {
-        Self { name: "Hero".to_string(), character: PlayerCharacter::new(id) }
+        Self {
+            name: "Hero".to_string(),
+            character: PlayerCharacter::new(id),
+            appearance: PlayerAppearance::default(),
+            class: PlayerClass::default(),
+        }
     }
 }
 
 impl Default for Player {
     // for testing, don't insert default player into the world!
     fn default() -> Self {
-        Self { name: "Hero".to_string(), character: PlayerCharacter::default() }
+        Self {
+            name: "Hero".to_string(),
+            character: PlayerCharacter::default(),
+            appearance: PlayerAppearance::default(),
+            class: PlayerClass::default(),
+        }
     }
 }
 
+#[derive(Clone)]
 pub struct PlayerCharacter {
     pub base: EntityBase,
     pub stats: PcStats,
-    pub inventory: Vec<GameItemId>,
+    pub inventory: Inventory,
     pub armor: Option<ArmorItem>,
     pub weapon: Option<WeaponItem>,
     pub active_buffs: Vec<ActiveBuff>,
+    /// Remaining turns of the player being confused or frightened (see [CrowdControl](crate::core::crowd_control::CrowdControl)).
+    pub crowd_control: CrowdControlTurns,
     pub potion_usage: HashMap<PotionType, PotionUsage>,
+    /// Gold looted from slain monsters. Spent at shops.
+    pub gold: u32,
+    /// Rounds since the player last regenerated hit points (see [RegenRules](crate::core::regeneration::RegenRules)).
+    pub regen_progress: u16,
+    /// Perks picked on level-up milestones (see [perks](crate::core::perks)).
+    pub perks: Vec<PerkId>,
+    /// Proficiencies that improve with use (see [skills](crate::core::skills)).
+    pub skills: Skills,
 }
 
 impl PlayerCharacter {
@@ -48,11 +80,16 @@ impl PlayerCharacter {
                 style: Color::Yellow.into(),
             },
             stats: PcStats::new(),
-            inventory: Vec::new(),
+            inventory: Inventory::default(),
             armor: None,
             weapon: None,
             active_buffs: Vec::new(),
+            crowd_control: CrowdControlTurns::default(),
             potion_usage: HashMap::new(),
+            gold: 0,
+            regen_progress: 0,
+            perks: Vec::new(),
+            skills: Skills::default(),
         }
     }
     pub fn attack_damage_bonus_melee(&self) -> i16 {
@@ -84,7 +121,7 @@ impl PlayerCharacter {
     }
 
     pub fn dodge_chance(&self) -> u8 {
-        let mut dodge = (self.stats.dexterity / 2).min(50);
+        let mut dodge = (self.stats.dexterity / 2).min(50) + self.perk_dodge_chance();
 
         for buff in &self.active_buffs {
             match buff.effect {
@@ -119,7 +156,7 @@ impl PlayerCharacter {
     pub fn gain_experience(&mut self, amount: u32) -> bool {
         self.stats.experience += amount;
 
-        let required_xp = self.stats.level as u32 * 100;
+        let required_xp = self.stats.experience_needed();
         if self.stats.experience >= required_xp {
             self.stats.experience -= required_xp;
             self.level_up();
@@ -135,12 +172,17 @@ impl PlayerCharacter {
         self.stats.dexterity += 1;
         self.stats.vitality += 1;
         self.stats.perception += 1;
+        self.inventory.set_capacity(inventory_capacity(self.stats.strength));
 
         self.stats.base.hp_max += 10;
         self.stats.base.hp_current = self.stats.base.hp_max;
     }
 
-    pub fn tick_buffs(&mut self) {
+    /// Applies the per-turn effects of all active buffs and counts down their duration.
+    ///
+    /// # Returns
+    /// * The buffs that expired this turn and were removed.
+    pub fn tick_buffs(&mut self) -> Vec<ActiveBuff> {
         let mut damage_accrued: u16 = 0;
         for buff in &mut self.active_buffs {
             if let PotionEffectDef::Poison { damage_per_tick, duration: _ } = &buff.effect {
@@ -150,8 +192,12 @@ impl PlayerCharacter {
                 buff.remaining_turns -= 1;
             }
         }
-        self.take_damage(damage_accrued);
-        self.active_buffs.retain(|buff| buff.remaining_turns > 0);
+        self.take_damage(self.poison_damage(damage_accrued));
+
+        let (active, expired) =
+            self.active_buffs.drain(..).partition(|buff| buff.remaining_turns > 0);
+        self.active_buffs = active;
+        expired
     }
 }
 
@@ -162,13 +208,23 @@ impl Default for PlayerCharacter {
 }
 
 impl GameState {
+    /// Adds experience to the player character, scaled by the [Difficulty](crate::core::difficulty::Difficulty) of the run.
     pub fn player_add_experience(&mut self, amount: u32) {
+        let amount = self.difficulty.scale_experience(amount);
         let did_level_up = self.player.character.gain_experience(amount);
         if did_level_up {
-            self.log.info(LogData::LevelUp { new_level: self.player.character.stats.level });
+            let new_level = self.player.character.stats.level;
+            self.log.info(LogData::LevelUp { new_level });
+            self.offer_perks(new_level);
         }
     }
 
+    /// Names the player character. The name is shown in the game and suggested for the high score table.
+    pub fn set_player_name(&mut self, name: String) {
+        self.player.character.base.name = name.clone();
+        self.player.name = name;
+    }
+
     pub fn player_is_alive(&self) -> bool {
         if self.game_rules.contains(GameRules::GOD_MODE) {
             return true;
@@ -177,6 +233,7 @@ impl GameState {
     }
 }
 
+#[derive(Clone)]
 pub struct PcStats {
     pub base: BaseStats,
     pub strength: u8,
@@ -202,6 +259,17 @@ impl PcStats {
             experience: 0,
         }
     }
+
+    /// Experience points needed to reach the next level.
+    pub fn experience_needed(&self) -> u32 {
+        self.level as u32 * 100
+    }
+}
+
+impl Default for PcStats {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BaseStats {
@@ -1,20 +1,30 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
 use crate::{
     core::{
-        entity_logic::{Entity, EntityId, Movable},
+        containers::Container,
+        entity_logic::{Disposition, Entity, EntityId, Movable},
         game::{GameRules, GameState},
         game_items::GameItemId,
+        npc_interaction::NpcInteraction,
+        quests::QuestEvent,
     },
+    data::perks::PerkId,
     util::{
         errors_results::{DataError, EngineError, FailReason, GameError, GameOutcome, GameResult},
         text_log::LogData,
     },
     world::{
         coordinate_system::{Direction, Point, PointVector},
-        tiles::{Collision, DoorType, Interactable, TileType},
+        tiles::{Collision, DoorType, Interactable, ShrineState, TileType},
     },
 };
 
 /// Abstraction layer over player input. These represent the player's input separated from the concrete keybindings.
+///
+/// Inputs are recorded into the [Replay](crate::core::replay::Replay) of the run.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum PlayerInput {
     /// Wait in place for 1 round.
     Wait,
@@ -28,6 +38,9 @@ pub enum PlayerInput {
     /// Drop an item from the inventory.
     DropItem(GameItemId),
 
+    /// Drop the given item and more items of the same kind from the inventory, up to the given amount.
+    DropItems(GameItemId, usize),
+
     /// Unequip the weapon currently in the weapon slot.
     UnequipWeapon,
 
@@ -36,6 +49,45 @@ pub enum PlayerInput {
 
     /// Make a ranged attack.
     RangedAttack(EntityId),
+
+    /// Cook an item from the inventory at an adjacent campfire.
+    Cook(GameItemId),
+
+    /// Perform the chosen interaction with an adjacent NPC.
+    InteractNpc(EntityId, NpcInteraction),
+
+    /// Buy an item from the shop of an adjacent shopkeeper.
+    Buy(EntityId, GameItemId),
+
+    /// Haggle with an adjacent shopkeeper.
+    Haggle(EntityId),
+
+    /// Put an item from the inventory into the stash.
+    StoreItem(GameItemId),
+
+    /// Take the item at the given position out of the stash.
+    TakeFromStash(usize),
+
+    /// Take an item out of a chest or bag.
+    TakeFromContainer(Container, GameItemId),
+
+    /// Take as many items out of a chest or bag as the inventory can hold.
+    TakeAllFromContainer(Container),
+
+    /// Put an item from the inventory into a chest or bag.
+    PutIntoContainer(Container, GameItemId),
+
+    /// Pick the perk at the given position of the offered perks.
+    ChoosePerk(usize),
+
+    /// Pick up the given item sprite lying under the player.
+    PickUp(EntityId),
+
+    /// Go down the stairs the player stands on.
+    Descend,
+
+    /// Go up the stairs the player stands on.
+    Ascend,
 }
 
 /// Actions/Intentions of the player. Are translated from [PlayerInput] in the context of the game state.
@@ -49,7 +101,10 @@ pub enum ActionKind {
     /// Attack the given Entity (likely Npc)
     Attack(EntityId),
 
-    /// Pick up the item contained in the given Entity (likely GameItemSprite)
+    /// Swap places with the given friendly NPC (like a companion or a shopkeeper).
+    Swap(EntityId),
+
+    /// Pick up the item contained in the given Entity (likely GameItemSprite) under the player.
     PickUpItem(EntityId),
 
     /// Use an item from the inventory.
@@ -58,6 +113,9 @@ pub enum ActionKind {
     /// Drop an item from the inventory onto the ground as an ItemSprite.
     DropItem(GameItemId),
 
+    /// Drop the given amount of items of the same kind as the given item onto the ground.
+    DropItems(GameItemId, usize),
+
     /// Unequip the weapon in the current weapon slot.
     UnequipWeapon,
 
@@ -69,6 +127,39 @@ pub enum ActionKind {
 
     /// Make a ranged attack against the given Entity.
     RangedAttack(EntityId),
+
+    /// Cook an item from the inventory at an adjacent campfire.
+    Cook(GameItemId),
+
+    /// Perform the chosen interaction with the given adjacent NPC.
+    InteractNpc(EntityId, NpcInteraction),
+
+    /// Buy the item from the shop of the given adjacent shopkeeper.
+    Buy(EntityId, GameItemId),
+
+    /// Haggle with the given adjacent shopkeeper.
+    Haggle(EntityId),
+
+    /// Put the item from the inventory into the stash.
+    StoreItem(GameItemId),
+
+    /// Take the item at the given position out of the stash.
+    TakeFromStash(usize),
+
+    /// Take the item out of the chest or bag.
+    TakeFromContainer(Container, GameItemId),
+
+    /// Take as many items out of the chest or bag as the inventory can hold.
+    TakeAllFromContainer(Container),
+
+    /// Put the item from the inventory into the chest or bag.
+    PutIntoContainer(Container, GameItemId),
+
+    /// Pick the offered perk.
+    ChoosePerk(PerkId),
+
+    /// Take the stairs the player stands on, down (`true`) or up (`false`).
+    UseStairs(bool),
 }
 
 impl GameState {
@@ -83,23 +174,55 @@ impl GameState {
     /// These break the game's state, meaning that the game cannot be continued.
     pub fn resolve_player_action(&mut self, input: PlayerInput) {
         if let Some(intended_action) = self.interpret_player_input(input) {
+            #[cfg(feature = "dev")]
+            let snapshot = self.snapshot();
+
+            self.replay.inputs.push(input);
+
             let action_result: GameResult = match intended_action {
                 ActionKind::Wait => Ok(GameOutcome::Success),
                 ActionKind::Move(direction) => {
                     self.move_player_character(PointVector::from(direction))
                 }
                 ActionKind::Attack(npc_id) => self.player_attack_npc(npc_id),
+                ActionKind::Swap(npc_id) => self.swap_with_npc(npc_id),
                 ActionKind::PickUpItem(entity_id) => self.pick_up_item(entity_id),
-                ActionKind::DropItem(item_id) => self.drop_item(item_id),
+                ActionKind::DropItem(item_id) => self.drop_items(item_id, 1),
+                ActionKind::DropItems(item_id, amount) => self.drop_items(item_id, amount),
                 ActionKind::UseItem(item_id) => self.use_item(item_id),
                 ActionKind::UnequipWeapon => self.unequip_weapon(),
                 ActionKind::UnequipArmor => self.unequip_armor(),
                 ActionKind::TileInteraction(point) => self.tile_interaction(point),
                 ActionKind::RangedAttack(npc_id) => self.player_ranged_attack_npc(npc_id),
+                ActionKind::Cook(item_id) => self.cook_item(item_id),
+                ActionKind::InteractNpc(npc_id, interaction) => {
+                    self.interact_with_npc(npc_id, interaction)
+                }
+                ActionKind::Buy(npc_id, item_id) => self.buy_item(npc_id, item_id),
+                ActionKind::Haggle(npc_id) => self.haggle(npc_id),
+                ActionKind::StoreItem(item_id) => self.store_item(item_id),
+                ActionKind::TakeFromStash(index) => self.take_from_stash(index),
+                ActionKind::TakeFromContainer(container, item_id) => {
+                    self.take_from_container(container, item_id)
+                }
+                ActionKind::TakeAllFromContainer(container) => {
+                    self.take_all_from_container(container)
+                }
+                ActionKind::PutIntoContainer(container, item_id) => {
+                    self.put_into_container(container, item_id)
+                }
+                ActionKind::ChoosePerk(perk_id) => self.choose_perk(perk_id),
+                ActionKind::UseStairs(down) => self.use_stairs(down),
             };
 
             match action_result {
-                Ok(GameOutcome::Success) => self.next_round(),
+                Ok(GameOutcome::Success) => {
+                    #[cfg(feature = "dev")]
+                    self.rewind_history.push(snapshot);
+
+                    self.next_round();
+                    self.apply_slowness();
+                }
                 Ok(GameOutcome::Fail(reason)) => {
                     // Log for user only if message is defined for user
                     if let Some(log_data) = reason.notify_user() {
@@ -143,6 +266,8 @@ impl GameState {
         match input {
             PlayerInput::Wait => Some(ActionKind::Wait),
             PlayerInput::Direction(direction) => {
+                // Confused or frightened players do not always go where they want to
+                let direction = self.controlled_direction(direction);
                 let target_point: Point = self.player.character.pos().get_adjacent(direction);
 
                 // Fallback if point is out of bounds
@@ -154,13 +279,12 @@ impl GameState {
                 }
 
                 if let Some(entity_id) = self.current_level().get_npc_at(target_point) {
+                    if self.is_swappable(entity_id) {
+                        return Some(ActionKind::Swap(entity_id));
+                    }
                     return Some(ActionKind::Attack(entity_id));
                 }
 
-                if let Some(entity_id) = self.current_level().get_item_sprite_at(target_point) {
-                    return Some(ActionKind::PickUpItem(entity_id));
-                }
-
                 let target_tile = self.current_world().get_tile(target_point);
                 if target_tile.tile_type.is_interactable() {
                     return Some(ActionKind::TileInteraction(target_point));
@@ -170,48 +294,134 @@ impl GameState {
             }
             PlayerInput::UseItem(item_id) => Some(ActionKind::UseItem(item_id)),
             PlayerInput::DropItem(item_id) => Some(ActionKind::DropItem(item_id)),
+            PlayerInput::DropItems(item_id, amount) => Some(ActionKind::DropItems(item_id, amount)),
             PlayerInput::UnequipWeapon => Some(ActionKind::UnequipWeapon),
             PlayerInput::UnequipArmor => Some(ActionKind::UnequipArmor),
             PlayerInput::RangedAttack(entity_id) => Some(ActionKind::RangedAttack(entity_id)),
+            PlayerInput::Cook(item_id) => Some(ActionKind::Cook(item_id)),
+            PlayerInput::InteractNpc(npc_id, interaction) => {
+                Some(ActionKind::InteractNpc(npc_id, interaction))
+            }
+            PlayerInput::Buy(npc_id, item_id) => Some(ActionKind::Buy(npc_id, item_id)),
+            PlayerInput::Haggle(npc_id) => Some(ActionKind::Haggle(npc_id)),
+            PlayerInput::StoreItem(item_id) => Some(ActionKind::StoreItem(item_id)),
+            PlayerInput::TakeFromStash(index) => Some(ActionKind::TakeFromStash(index)),
+            PlayerInput::TakeFromContainer(container, item_id) => {
+                Some(ActionKind::TakeFromContainer(container, item_id))
+            }
+            PlayerInput::TakeAllFromContainer(container) => {
+                Some(ActionKind::TakeAllFromContainer(container))
+            }
+            PlayerInput::PutIntoContainer(container, item_id) => {
+                Some(ActionKind::PutIntoContainer(container, item_id))
+            }
+            PlayerInput::ChoosePerk(index) => {
+                self.pending_perks.get(index).cloned().map(ActionKind::ChoosePerk)
+            }
+            PlayerInput::PickUp(entity_id) => Some(ActionKind::PickUpItem(entity_id)),
+            PlayerInput::Descend => Some(ActionKind::UseStairs(true)),
+            PlayerInput::Ascend => Some(ActionKind::UseStairs(false)),
         }
     }
 
-    /// Used to pick up items off the ground. Moves the item from a [GameItemSprite] to the player's inventory.
+    /// Items lying under the player, with their names. Used by the UI to offer them for picking up.
+    pub fn items_underfoot(&self) -> Vec<(EntityId, String)> {
+        self.current_level()
+            .item_sprites_at(self.player.character.pos())
+            .map(|item_sprite| (item_sprite.id(), item_sprite.name().to_string()))
+            .collect()
+    }
+
+    /// Used to pick up items off the ground. Moves the item from a [GameItemSprite](crate::core::game_items::GameItemSprite) under the player to their inventory.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::InvalidTarget] if the item does not lie under the player.
     fn pick_up_item(&mut self, entity_id: EntityId) -> GameResult {
         let item_sprite = self
             .current_level()
             .get_item_sprite(entity_id)
             .ok_or(EngineError::ItemSpriteNotFound(entity_id))?;
+        if item_sprite.pos() != self.player.character.pos() {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(entity_id)));
+        }
 
         let item = self
             .get_item_by_id(item_sprite.item_id)
             .ok_or(EngineError::UnregisteredItem(item_sprite.item_id))?;
         let item_def = self
             .get_item_def_by_id(&item.def_id)
-            .ok_or(DataError::MissingItemDefinition(item.def_id))?;
+            .ok_or(DataError::MissingItemDefinition(item.def_id.clone()))?;
 
         let result = self.add_item_to_inv(item_sprite.item_id);
 
         if let Ok(GameOutcome::Success) = result {
             self.current_level_mut().despawn(entity_id);
-            self.log.info(LogData::ItemPickUp { item_name: item_def.name.to_string() })
+            self.log.info(LogData::ItemPickUp { item_name: item_def.name.to_string() });
+            self.notify_quests(QuestEvent::PickedUp(&item.def_id));
         }
 
         result
     }
 
-    /// Used to drop items from the inventory onto the ground. Spawns a new [GameItemSprite] in the world.
-    fn drop_item(&mut self, item_id: GameItemId) -> GameResult {
+    /// Items in the inventory of the same kind as the given one, starting with it. Used by the UI to ask how many to drop.
+    pub fn items_like(&self, item_id: GameItemId) -> Vec<GameItemId> {
+        let Some(def_id) = self.get_item_by_id(item_id).map(|item| item.def_id) else {
+            return Vec::new();
+        };
+
+        let mut item_ids = vec![item_id];
+        item_ids.extend(self.player.character.inventory.iter().copied().filter(|other_id| {
+            *other_id != item_id
+                && self.get_item_by_id(*other_id).is_some_and(|item| item.def_id == def_id)
+        }));
+        item_ids
+    }
+
+    /// Where dropped items land. Items are put down under the player, or on the nearest tile next to them
+    /// if they would be lost there (e.g. while swimming).
+    fn drop_point(&self) -> Option<Point> {
         let player_pos = self.player.character.pos();
 
-        if self.current_level().is_occupied(player_pos) {
-            return Ok(GameOutcome::Fail(FailReason::TileOccupied(player_pos)));
+        std::iter::once(player_pos)
+            .chain(Direction::iter().map(|direction| player_pos.get_adjacent(direction)))
+            .find(|point| self.current_level().can_hold_items(*point))
+    }
+
+    /// Used to drop items from the inventory onto the ground. Drops the given item and, if `amount` is larger than 1,
+    /// more items of the same kind. Every item spawns a new [GameItemSprite](crate::core::game_items::GameItemSprite)
+    /// in the world, on top of the items already lying there.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoRoomToDrop] if there is no tile around the player that can hold items.
+    fn drop_items(&mut self, item_id: GameItemId, amount: usize) -> GameResult {
+        if !self.player.character.inventory.contains(&item_id) {
+            return Err(GameError::from(EngineError::ItemNotInInventory(item_id)));
         }
 
-        self.remove_item_from_inv(item_id)?;
+        let Some(drop_point) = self.drop_point() else {
+            return Ok(GameOutcome::Fail(FailReason::NoRoomToDrop));
+        };
+
+        let item = self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
+        let item_def = self
+            .get_item_def_by_id(&item.def_id)
+            .ok_or(DataError::MissingItemDefinition(item.def_id.clone()))?;
+        let item_name = item_def.name.to_string();
+
+        let item_ids: Vec<GameItemId> =
+            self.items_like(item_id).into_iter().take(amount.max(1)).collect();
+        for item_id in &item_ids {
+            self.remove_item_from_inv(*item_id)?;
+
+            let item_sprite = self.create_item_sprite(*item_id, drop_point)?;
+            self.current_level_mut().spawn_item_sprite(item_sprite)?;
+        }
 
-        let item_sprite = self.create_item_sprite(item_id, player_pos)?;
-        self.current_level_mut().spawn_item_sprite(item_sprite)?;
+        self.log.info(LogData::ItemsDropped {
+            item_name,
+            amount: item_ids.len(),
+            beside: drop_point != self.player.character.pos(),
+        });
 
         Ok(GameOutcome::Success)
     }
@@ -219,6 +429,8 @@ impl GameState {
     /// Moves the player character to a new relative position described by the `point_vector` argument.
     ///
     /// Performs out of bounds and tile accessibility checks.
+    /// Entering deep water requires a swim check and stepping into a chasm makes the player fall to the next level.
+    /// Spike traps hurt the player.
     fn move_player_character(&mut self, point_vector: PointVector) -> GameResult {
         let new_pos = self.player.character.pos() + point_vector;
 
@@ -232,11 +444,119 @@ impl GameState {
             return Ok(GameOutcome::Fail(FailReason::TileNotWalkable(new_pos)));
         }
 
+        let target_tile_type = self.current_world().get_tile(new_pos).tile_type;
+
+        // Failing to swim still takes up the turn
+        if target_tile_type == TileType::Water
+            && self.current_world().get_tile(self.player.character.pos()).tile_type
+                != TileType::Water
+            && !self.swim_check()
+        {
+            return Ok(GameOutcome::Success);
+        }
+
         self.player.character.move_to(new_pos);
 
+        if target_tile_type == TileType::Chasm {
+            return self.fall_into_chasm();
+        }
+        if target_tile_type == TileType::SpikeTrap {
+            self.spring_spike_trap_on_player();
+        }
+
+        self.fire_triggers();
+
+        // Walking over a corpse loots it.
+        if let Some(corpse_id) = self.current_level().get_corpse_at(new_pos) {
+            self.loot_corpse(corpse_id)?;
+        }
+
+        // Items are only picked up on purpose, the player is told what lies here
+        let mut items_underfoot = self.current_level().item_sprites_at(new_pos);
+        if let Some(item_sprite) = items_underfoot.next() {
+            let item_name = item_sprite.name().to_string();
+            let amount = 1 + items_underfoot.count();
+            self.log.info(LogData::ItemsHere { item_name, amount });
+        }
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// Whether the player swaps places with the NPC instead of attacking it when walking into it.
+    /// Only friendly NPCs and companions make room. Neutral NPCs can still be attacked, and large NPCs don't fit into the player's place.
+    fn is_swappable(&self, npc_id: EntityId) -> bool {
+        self.current_level().get_npc(npc_id).is_some_and(|npc| {
+            matches!(npc.disposition, Disposition::Friendly | Disposition::Companion)
+                && npc.size == 1
+        })
+    }
+
+    /// Swaps the positions of the player and an adjacent friendly NPC.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC could not be found in the current Level.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::TileNotWalkable] if the NPC would have to stand on a hazard (like lava).
+    /// * [GameOutcome::Success] if the player and the NPC swapped places.
+    fn swap_with_npc(&mut self, npc_id: EntityId) -> GameResult {
+        let player_pos = self.player.character.pos();
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let (npc_pos, npc_name) = (npc.pos(), npc.name().to_string());
+
+        // NPCs don't get pushed into hazards
+        if self.current_world().get_tile(player_pos).tile_type.movement_cost().is_none() {
+            return Ok(GameOutcome::Fail(FailReason::TileNotWalkable(player_pos)));
+        }
+
+        let npc =
+            self.current_level_mut().get_npc_mut(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        npc.move_to(player_pos);
+        self.player.character.move_to(npc_pos);
+        self.log.info(LogData::SwapPlaces { npc_name });
+
+        self.fire_triggers();
+
+        if let Some(corpse_id) = self.current_level().get_corpse_at(npc_pos) {
+            self.loot_corpse(corpse_id)?;
+        }
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// The player takes the stairs they stand on. Stairs are only taken on purpose, walking onto them does not change the level.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoStairs] if the player does not stand on stairs leading that way.
+    /// * [GameOutcome::Success] if the player arrived on the other level.
+    fn use_stairs(&mut self, down: bool) -> GameResult {
+        let tile_type = self.current_world().get_tile(self.player.character.pos()).tile_type;
+
+        match (tile_type, down) {
+            (TileType::StairsDown, true) => {
+                self.log.info(LogData::UseStairsDown);
+                self.goto_level_next()?;
+            }
+            (TileType::StairsUp, false) => {
+                self.log.info(LogData::UseStairsUp);
+                self.goto_level_previous()?;
+            }
+            _ => return Ok(GameOutcome::Fail(FailReason::NoStairs)),
+        }
         Ok(GameOutcome::Success)
     }
 
+    /// Whether a hostile NPC stands next to the player. The UI asks before the player takes the stairs with enemies at their heels.
+    pub fn hostile_adjacent(&self) -> bool {
+        let player_pos = self.player.character.pos();
+        self.current_level().npcs.iter().any(|npc| {
+            npc.disposition == Disposition::Hostile
+                && npc.footprint().points().any(|point| {
+                    point.x.abs_diff(player_pos.x) <= 1 && point.y.abs_diff(player_pos.y) <= 1
+                })
+        })
+    }
+
     /// The player performs an interaction with a tile at the given point.
     ///
     /// Does nothing if the target tile has no defined interactions.
@@ -250,19 +570,148 @@ impl GameState {
                 Ok(GameOutcome::Success)
             }
 
-            TileType::StairsDown => {
-                self.log.info(LogData::UseStairsDown);
-                self.goto_level_next()?;
-                Ok(GameOutcome::Success)
-            }
+            TileType::Door(DoorType::Locked) => Ok(GameOutcome::Fail(FailReason::DoorLocked)),
 
-            TileType::StairsUp => {
-                self.log.info(LogData::UseStairsUp);
-                self.goto_level_previous()?;
-                Ok(GameOutcome::Success)
-            }
+            TileType::Memorial => self.read_memorial(),
+
+            TileType::Shrine(ShrineState::Active) => self.pray_at_shrine(point),
 
+            TileType::Altar(ShrineState::Active) => self.pray_at_altar(point),
+
+            // Campfires open the cooking menu, stashes the stash menu and chests the container menu, which are handled by the UI.
             _ => Ok(GameOutcome::Fail(FailReason::NoInteraction)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    #[test]
+    fn walking_into_friendly_npcs_swaps_places() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        let healer = game.create_npc("healer".into(), Point::new(11, 10)).unwrap();
+        let healer_id = healer.id();
+        game.current_level_mut().spawn_npc(healer).unwrap();
+        let goblin = game.create_npc("goblin".into(), Point::new(10, 11)).unwrap();
+        let goblin_id = goblin.id();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+
+        assert!(matches!(
+            game.interpret_player_input(PlayerInput::Direction(Direction::Down)),
+            Some(ActionKind::Attack(npc_id)) if npc_id == goblin_id
+        ));
+        let Some(ActionKind::Swap(npc_id)) =
+            game.interpret_player_input(PlayerInput::Direction(Direction::Right))
+        else {
+            panic!("Walking into a friendly NPC should swap places");
+        };
+        assert_eq!(npc_id, healer_id);
+
+        assert!(matches!(game.swap_with_npc(healer_id), Ok(GameOutcome::Success)));
+        assert_eq!(game.player.character.pos(), Point::new(11, 10));
+        assert_eq!(game.current_level().get_npc(healer_id).unwrap().pos(), Point::new(10, 10));
+    }
+
+    #[test]
+    fn stairs_are_only_taken_on_purpose() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        level.world.get_tile_mut(Point::new(11, 10)).tile_type = TileType::StairsDown;
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        // Walking onto the stairs does not take them
+        assert!(matches!(
+            game.interpret_player_input(PlayerInput::Direction(Direction::Right)),
+            Some(ActionKind::Move(Direction::Right))
+        ));
+        game.resolve_player_action(PlayerInput::Direction(Direction::Right));
+        assert_eq!(game.level_nr, 0);
+
+        // Stairs down do not lead up
+        assert!(matches!(game.use_stairs(false), Ok(GameOutcome::Fail(FailReason::NoStairs))));
+
+        assert!(!game.hostile_adjacent());
+        let goblin = game.create_npc("goblin".into(), Point::new(12, 11)).unwrap();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+        assert!(game.hostile_adjacent());
+    }
+
+    #[test]
+    fn items_pile_up_and_are_picked_up_on_purpose() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        let pile = Point::new(11, 10);
+        let mut sprite_ids = Vec::new();
+        for def_id in ["food_apple", "weapon_dagger"] {
+            let item_id = game.register_item(&def_id.to_string()).unwrap();
+            let sprite = game.create_item_sprite(item_id, pile).unwrap();
+            sprite_ids.push(sprite.id());
+            game.current_level_mut().spawn_item_sprite(sprite).unwrap();
+        }
+
+        // Walking onto the pile does not pick anything up
+        game.resolve_player_action(PlayerInput::Direction(Direction::Right));
+        assert_eq!(game.player.character.pos(), pile);
+        assert_eq!(game.items_underfoot().len(), 2);
+
+        game.resolve_player_action(PlayerInput::PickUp(sprite_ids[1]));
+        assert_eq!(game.items_underfoot().len(), 1);
+        assert_eq!(game.player.character.inventory.len(), 1);
+
+        // Items out of reach stay where they are
+        game.player.character.base.pos = Point::new(10, 10);
+        assert!(matches!(
+            game.pick_up_item(sprite_ids[0]),
+            Ok(GameOutcome::Fail(FailReason::InvalidTarget(_)))
+        ));
+    }
+
+    #[test]
+    fn dropped_items_land_next_to_the_player_if_they_would_be_lost() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        let mut apple_ids = Vec::new();
+        for _ in 0..3 {
+            let item_id = game.register_item(&"food_apple".to_string()).unwrap();
+            game.add_item_to_inv(item_id).unwrap();
+            apple_ids.push(item_id);
+        }
+        assert_eq!(game.items_like(apple_ids[1]).len(), 3);
+
+        // Items sink in water, so they land next to a swimming player
+        game.current_world_mut().get_tile_mut(Point::new(10, 10)).tile_type = TileType::Water;
+        game.resolve_player_action(PlayerInput::DropItems(apple_ids[1], 2));
+        assert_eq!(game.player.character.inventory.len(), 1);
+        assert!(game.items_underfoot().is_empty());
+        assert_eq!(game.current_level().item_sprites_at(Point::new(10, 9)).count(), 2);
+
+        // With water all around there is no room left
+        for direction in Direction::iter() {
+            let point = Point::new(10, 10).get_adjacent(direction);
+            game.current_world_mut().get_tile_mut(point).tile_type = TileType::Water;
+        }
+        assert!(matches!(
+            game.drop_items(apple_ids[2], 1),
+            Ok(GameOutcome::Fail(FailReason::NoRoomToDrop))
+        ));
+        assert_eq!(game.player.character.inventory.len(), 1);
+    }
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        game::GameState,
+        game_items::{ArmorItem, WeaponItem},
+        inventory::inventory_capacity,
+        player::PcStats,
+    },
+    util::errors_results::GameError,
+};
+
+/// Extra damage of the attacks a class specializes in (see [PlayerClass::damage_bonus]).
+pub const CLASS_DAMAGE_BONUS: i16 = 2;
+
+/// Class the player picks at character creation. It decides the starting stats, the starting kit and a perk of the character.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayerClass {
+    #[default]
+    Fighter,
+    Ranger,
+    Alchemist,
+}
+
+impl PlayerClass {
+    pub const ALL: [PlayerClass; 3] =
+        [PlayerClass::Fighter, PlayerClass::Ranger, PlayerClass::Alchemist];
+
+    /// The next class in the character creation, starting over at the first one.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|class| *class == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayerClass::Fighter => "Fighter",
+            PlayerClass::Ranger => "Ranger",
+            PlayerClass::Alchemist => "Alchemist",
+        }
+    }
+
+    /// Description of the perk of the class, shown at character creation.
+    pub fn perk(&self) -> &'static str {
+        match self {
+            PlayerClass::Fighter => "Weapon Master: +2 damage with melee attacks",
+            PlayerClass::Ranger => "Sharpshooter: +2 damage with ranged attacks",
+            PlayerClass::Alchemist => "Potion Lore: healing potions heal half again as much",
+        }
+    }
+
+    /// Raises the stats the class is good at. Each class gets three points.
+    fn apply_stats(&self, stats: &mut PcStats) {
+        match self {
+            PlayerClass::Fighter => {
+                stats.strength += 2;
+                stats.vitality += 1;
+            }
+            PlayerClass::Ranger => {
+                stats.dexterity += 1;
+                stats.perception += 2;
+            }
+            PlayerClass::Alchemist => {
+                stats.dexterity += 1;
+                stats.vitality += 1;
+                stats.perception += 1;
+            }
+        }
+        stats.base.hp_max = 20 + stats.vitality as u16 * 10;
+        stats.base.hp_current = stats.base.hp_max;
+    }
+
+    /// Weapon the character starts with in hand.
+    pub fn starting_weapon(&self) -> &'static str {
+        match self {
+            PlayerClass::Fighter => "weapon_short_sword",
+            PlayerClass::Ranger => "weapon_bow_short",
+            PlayerClass::Alchemist => "weapon_dagger",
+        }
+    }
+
+    /// Armor the character starts wearing.
+    pub fn starting_armor(&self) -> Option<&'static str> {
+        match self {
+            PlayerClass::Fighter => Some("armor_leather"),
+            PlayerClass::Ranger => Some("armor_cloak"),
+            PlayerClass::Alchemist => None,
+        }
+    }
+
+    /// Items the character starts with in the inventory.
+    pub fn starting_items(&self) -> &'static [&'static str] {
+        match self {
+            PlayerClass::Fighter => &["food_bread"],
+            PlayerClass::Ranger => &["weapon_dagger", "food_apple"],
+            PlayerClass::Alchemist => {
+                &["potion_healing_small", "potion_healing_small", "potion_strength"]
+            }
+        }
+    }
+
+    /// Extra damage the perk of the class gives to melee or ranged attacks.
+    pub fn damage_bonus(&self, ranged: bool) -> i16 {
+        match (self, ranged) {
+            (PlayerClass::Fighter, false) | (PlayerClass::Ranger, true) => CLASS_DAMAGE_BONUS,
+            _ => 0,
+        }
+    }
+
+    /// Hit points a healing potion of the given strength heals, after the perk of the class.
+    pub fn potion_healing(&self, amount: u16) -> u16 {
+        match self {
+            PlayerClass::Alchemist => amount + amount / 2,
+            _ => amount,
+        }
+    }
+}
+
+impl GameState {
+    /// Makes the player character a member of the class: Raises their stats and hands out the starting kit.
+    /// The class is recorded in the replay, so the run plays out the same way when played back.
+    ///
+    /// # Errors
+    /// * [DataError::MissingItemDefinition](crate::util::errors_results::DataError::MissingItemDefinition) if an item of the kit is not defined.
+    pub fn set_player_class(&mut self, class: PlayerClass) -> Result<(), GameError> {
+        self.player.class = class;
+        self.replay.class = class;
+        class.apply_stats(&mut self.player.character.stats);
+        let strength = self.player.character.stats.strength;
+        self.player.character.inventory.set_capacity(inventory_capacity(strength));
+
+        let weapon = self.register_item(&class.starting_weapon().to_string())?;
+        self.player.character.weapon = Some(WeaponItem(weapon));
+        if let Some(def_id) = class.starting_armor() {
+            let armor = self.register_item(&def_id.to_string())?;
+            self.player.character.armor = Some(ArmorItem(armor));
+        }
+        for def_id in class.starting_items() {
+            let item_id = self.register_item(&def_id.to_string())?;
+            self.add_item_to_inv(item_id)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classes_start_with_their_kit_and_stats() {
+        let mut game = GameState::default();
+        game.set_player_class(PlayerClass::Ranger).unwrap();
+
+        let character = &game.player.character;
+        assert_eq!(character.stats.perception, 3);
+        assert_eq!(character.inventory.len(), 2);
+        let weapon = game.get_item_by_id(character.weapon.unwrap().0).unwrap();
+        assert_eq!(weapon.def_id, "weapon_bow_short");
+        assert_eq!(game.replay.class, PlayerClass::Ranger);
+
+        assert_eq!(PlayerClass::Ranger.damage_bonus(true), CLASS_DAMAGE_BONUS);
+        assert_eq!(PlayerClass::Ranger.damage_bonus(false), 0);
+        assert_eq!(PlayerClass::Alchemist.potion_healing(10), 15);
+    }
+}
@@ -0,0 +1,319 @@
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    core::{
+        entity_logic::{Disposition, Entity, EntityId},
+        game::GameState,
+    },
+    data::{item_defs::item_defs, npc_defs::npc_defs},
+    util::{
+        errors_results::{EngineError, FailReason, GameError, GameOutcome, GameResult},
+        text_log::LogData,
+    },
+};
+
+/// Items fetch quests send the player after. They are placed on the level of the quest when the player enters it.
+const FETCH_QUEST_ITEMS: [&str; 3] = ["trinket_locket", "trinket_idol", "trinket_signet"];
+
+/// Gold paid for each monster of a kill quest, multiplied by the monster's danger.
+const KILL_GOLD_PER_DANGER: u32 = 8;
+
+/// Experience granted for each monster of a kill quest, multiplied by the monster's danger.
+const KILL_EXPERIENCE_PER_DANGER: u32 = 10;
+
+/// Gold paid for a fetch quest for each level of depth.
+const FETCH_GOLD_PER_DEPTH: u32 = 10;
+
+/// Experience granted for a fetch quest for each level of depth.
+const FETCH_EXPERIENCE_PER_DEPTH: u32 = 15;
+
+/// What the player has to do to complete a [Quest].
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuestObjective {
+    /// Kill the given number of monsters with the given name.
+    Kill { npc_name: String, amount: u32 },
+
+    /// Pick up the item from the level of the given depth.
+    Fetch { item_def_id: String, depth: usize, placed: bool },
+}
+
+/// A task a friendly NPC gave to the player. Rewards are paid as soon as the objective is met.
+#[derive(Clone, Debug)]
+pub struct Quest {
+    /// NPC that gave the quest.
+    pub giver_id: EntityId,
+    pub giver_name: String,
+
+    pub objective: QuestObjective,
+
+    /// Monsters killed or items found so far.
+    pub progress: u32,
+
+    pub reward_gold: u32,
+    pub reward_experience: u32,
+
+    pub completed: bool,
+}
+
+impl Quest {
+    /// Short description of the objective, for the log and journal.
+    pub fn description(&self) -> String {
+        match &self.objective {
+            QuestObjective::Kill { npc_name, amount } => {
+                format!("Kill {} {} for the {}", amount, npc_name, self.giver_name)
+            }
+            QuestObjective::Fetch { item_def_id, depth, .. } => {
+                let item_name = item_defs().get(item_def_id).map_or("something", |def| def.name);
+                format!("Find the {} on level {} for the {}", item_name, depth, self.giver_name)
+            }
+        }
+    }
+
+    fn goal(&self) -> u32 {
+        match &self.objective {
+            QuestObjective::Kill { amount, .. } => *amount,
+            QuestObjective::Fetch { .. } => 1,
+        }
+    }
+}
+
+/// Things that happened in the game and may advance quests.
+pub enum QuestEvent<'a> {
+    /// The player killed a monster with the given name.
+    Killed(&'a str),
+
+    /// The player picked up an item of the given definition.
+    PickedUp(&'a str),
+}
+
+impl GameState {
+    /// Whether the NPC still has a quest to give. Each friendly NPC gives one quest per run.
+    pub fn offers_quest(&self, npc_id: EntityId) -> bool {
+        self.current_level().get_npc(npc_id).is_some_and(|npc| {
+            npc.disposition == Disposition::Friendly
+                && !self.quests.iter().any(|quest| quest.giver_id == npc_id)
+        })
+    }
+
+    /// The NPC gives the player a new quest, either killing monsters of the level's depth or fetching an item from further down.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not in the current level.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::InvalidTarget] if the NPC has no quest to give.
+    /// * [GameOutcome::Success] if the quest was accepted.
+    pub fn accept_quest(&mut self, npc_id: EntityId) -> GameResult {
+        if !self.offers_quest(npc_id) {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id)));
+        }
+        let giver_name = self
+            .current_level()
+            .get_npc(npc_id)
+            .ok_or(EngineError::NpcNotFound(npc_id))?
+            .name()
+            .to_string();
+
+        let depth = self.run_stats.deepest_level.max(self.level_nr);
+        let danger = (1 + depth / 2).min(u8::MAX as usize) as u8;
+
+        let mut targets: Vec<(&'static str, u8)> = npc_defs()
+            .values()
+            .filter(|def| def.disposition == Disposition::Hostile && def.danger <= danger)
+            .map(|def| (def.name, def.danger))
+            .collect();
+        targets.sort();
+
+        let kill_target = targets.choose(&mut self.rng).copied();
+        let (objective, reward_gold, reward_experience) = match kill_target {
+            Some((npc_name, npc_danger)) if self.rng.random_bool(0.5) => {
+                let amount = self.rng.random_range(2..=4);
+                let danger = npc_danger.max(1) as u32;
+                (
+                    QuestObjective::Kill { npc_name: npc_name.to_string(), amount },
+                    amount * danger * KILL_GOLD_PER_DANGER,
+                    amount * danger * KILL_EXPERIENCE_PER_DANGER,
+                )
+            }
+            _ => {
+                let item_def_id =
+                    FETCH_QUEST_ITEMS.choose(&mut self.rng).unwrap_or(&"trinket_locket");
+                let quest_depth = depth + self.rng.random_range(1..=2);
+                (
+                    QuestObjective::Fetch {
+                        item_def_id: item_def_id.to_string(),
+                        depth: quest_depth,
+                        placed: false,
+                    },
+                    quest_depth as u32 * FETCH_GOLD_PER_DEPTH,
+                    quest_depth as u32 * FETCH_EXPERIENCE_PER_DEPTH,
+                )
+            }
+        };
+
+        let quest = Quest {
+            giver_id: npc_id,
+            giver_name,
+            objective,
+            progress: 0,
+            reward_gold,
+            reward_experience,
+            completed: false,
+        };
+
+        let description = quest.description();
+        self.log.info(LogData::QuestAccepted { description: description.clone() });
+        self.write_journal(&format!("Accepted a quest: {}.", description));
+        self.quests.push(quest);
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// Advances the quests the event counts towards and pays the rewards of the completed ones.
+    pub fn notify_quests(&mut self, event: QuestEvent) {
+        let mut completed = Vec::new();
+
+        for (index, quest) in self.quests.iter_mut().enumerate() {
+            if quest.completed {
+                continue;
+            }
+
+            let counts = match (&quest.objective, &event) {
+                (QuestObjective::Kill { npc_name, .. }, QuestEvent::Killed(killed)) => {
+                    npc_name == killed
+                }
+                (QuestObjective::Fetch { item_def_id, .. }, QuestEvent::PickedUp(picked_up)) => {
+                    item_def_id == picked_up
+                }
+                _ => false,
+            };
+
+            if counts {
+                quest.progress += 1;
+                if quest.progress >= quest.goal() {
+                    quest.completed = true;
+                    completed.push(index);
+                }
+            }
+        }
+
+        for index in completed {
+            let quest = self.quests[index].clone();
+            self.player.character.gold += quest.reward_gold;
+            self.log.info(LogData::QuestCompleted {
+                description: quest.description(),
+                gold: quest.reward_gold,
+                experience: quest.reward_experience,
+            });
+            self.write_journal(&format!("Completed a quest: {}.", quest.description()));
+            self.player_add_experience(quest.reward_experience);
+        }
+    }
+
+    /// Places the items of fetch quests that belong on the current level. Called whenever the player enters a level.
+    ///
+    /// # Errors
+    /// * [GameError] if an item could not be registered or spawned.
+    pub fn place_quest_items(&mut self) -> Result<(), GameError> {
+        for index in 0..self.quests.len() {
+            let QuestObjective::Fetch { item_def_id, depth, placed: false } =
+                &self.quests[index].objective
+            else {
+                continue;
+            };
+            if *depth != self.level_nr || self.quests[index].completed {
+                continue;
+            }
+
+            let item_def_id = item_def_id.clone();
+            let point = self.random_landing_point();
+            if !self.current_level().is_available(point) || point == self.player.character.pos() {
+                continue;
+            }
+
+            let item_id = self.register_item(&item_def_id)?;
+            let item_sprite = self.create_item_sprite(item_id, point)?;
+            self.current_level_mut().spawn_item_sprite(item_sprite)?;
+
+            if let QuestObjective::Fetch { placed, .. } = &mut self.quests[index].objective {
+                *placed = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Formats the quests as paragraphs for the journal, open quests first.
+    pub fn quest_paragraphs(&self) -> Vec<String> {
+        if self.quests.is_empty() {
+            return vec!["Quests: None. Friendly folk may have work for you.".to_string()];
+        }
+
+        let mut paragraphs = vec!["Quests:".to_string()];
+        let (completed, open): (Vec<&Quest>, Vec<&Quest>) =
+            self.quests.iter().partition(|quest| quest.completed);
+        for quest in open {
+            // The giver may be on another level by now
+            let giver = match self.find_npc(quest.giver_id) {
+                Some((level_nr, _)) => format!(", waiting on level {}", level_nr),
+                None => String::new(),
+            };
+            paragraphs.push(format!(
+                "[ ] {} ({}/{}) - {} gold, {} EXP{}",
+                quest.description(),
+                quest.progress,
+                quest.goal(),
+                quest.reward_gold,
+                quest.reward_experience,
+                giver
+            ));
+        }
+        for quest in completed {
+            paragraphs.push(format!("[x] {}", quest.description()));
+        }
+
+        paragraphs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::level::LevelEntrance;
+
+    #[test]
+    fn quests_pay_out_once_completed() {
+        let mut game = GameState::default();
+        game.goto_level(crate::core::town::TUTORIAL_LEVEL, LevelEntrance::Entry).unwrap();
+        game.goto_level_previous().unwrap();
+
+        let healer_id = game
+            .current_level()
+            .npcs
+            .iter()
+            .find(|npc| npc.disposition == Disposition::Friendly)
+            .map(|npc| npc.id())
+            .unwrap();
+        assert!(game.offers_quest(healer_id));
+
+        game.quests.push(Quest {
+            giver_id: healer_id,
+            giver_name: "Healer".to_string(),
+            objective: QuestObjective::Kill { npc_name: "Goblin".to_string(), amount: 2 },
+            progress: 0,
+            reward_gold: 30,
+            reward_experience: 20,
+            completed: false,
+        });
+        assert!(!game.offers_quest(healer_id));
+
+        let gold = game.player.character.gold;
+        game.notify_quests(QuestEvent::Killed("Goblin"));
+        game.notify_quests(QuestEvent::Killed("Wolf"));
+        assert!(!game.quests[0].completed);
+        game.notify_quests(QuestEvent::Killed("Goblin"));
+        game.notify_quests(QuestEvent::Killed("Goblin"));
+        assert!(game.quests[0].completed);
+        assert_eq!(game.player.character.gold, gold + 30);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::core::{buff_effects::PotionEffectDef, game::GameState};
+
+/// Rules of the player's natural healing over time. Kept in [GameState::regen_rules], so they can be tuned without touching the routine itself.
+///
+/// The player regenerates [RegenRules::amount] hit points every few rounds. The interval gets shorter the higher the player's level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegenRules {
+    /// Rounds between two regenerations at level 1.
+    pub base_interval: u16,
+
+    /// Rounds the interval gets shorter with each level after the first.
+    pub interval_per_level: u16,
+
+    /// The interval never gets shorter than this.
+    pub min_interval: u16,
+
+    /// Hit points regenerated at once.
+    pub amount: u16,
+}
+
+impl Default for RegenRules {
+    fn default() -> Self {
+        Self { base_interval: 10, interval_per_level: 1, min_interval: 4, amount: 1 }
+    }
+}
+
+impl RegenRules {
+    /// Rounds between two regenerations at the given level.
+    pub fn interval(&self, level: u8) -> u16 {
+        let reduction = self.interval_per_level.saturating_mul(level.saturating_sub(1) as u16);
+        self.base_interval.saturating_sub(reduction).max(self.min_interval).max(1)
+    }
+}
+
+impl GameState {
+    /// The player regenerates hit points according to the [RegenRules]. Called once per round.
+    ///
+    /// Poison stops the regeneration and resets its progress.
+    pub fn regenerate(&mut self) {
+        let character = &mut self.player.character;
+        let poisoned = character
+            .active_buffs
+            .iter()
+            .any(|buff| matches!(buff.effect, PotionEffectDef::Poison { .. }));
+
+        if poisoned || !character.is_alive() {
+            character.regen_progress = 0;
+            return;
+        }
+
+        character.regen_progress += 1;
+        if character.regen_progress >= self.regen_rules.interval(character.stats.level) {
+            character.regen_progress = 0;
+            character.heal(self.regen_rules.amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::buff_effects::ActiveBuff;
+
+    #[test]
+    fn interval_shrinks_with_level_down_to_the_minimum() {
+        let rules = RegenRules::default();
+        assert_eq!(rules.interval(1), 10);
+        assert_eq!(rules.interval(4), 7);
+        assert_eq!(rules.interval(50), rules.min_interval);
+    }
+
+    #[test]
+    fn poison_stops_regeneration() {
+        let mut game = GameState::default();
+        game.player.character.take_damage(5);
+        let hp = game.player.character.stats.base.hp_current;
+
+        game.player.character.active_buffs.push(ActiveBuff {
+            effect: PotionEffectDef::Poison { damage_per_tick: 0, duration: 20 },
+            remaining_turns: 20,
+        });
+        for _ in 0..20 {
+            game.regenerate();
+        }
+        assert_eq!(game.player.character.stats.base.hp_current, hp);
+
+        game.player.character.active_buffs.clear();
+        for _ in 0..10 {
+            game.regenerate();
+        }
+        assert_eq!(game.player.character.stats.base.hp_current, hp + 1);
+    }
+}
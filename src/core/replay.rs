@@ -0,0 +1,212 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ron::de::from_reader;
+use ron::ser::{PrettyConfig, to_writer_pretty};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        difficulty::DifficultyLevel, game::GameState, player_actions::PlayerInput,
+        player_class::PlayerClass, town::Stash,
+    },
+    data::item_defs::GameItemDefId,
+    util::{
+        build_info::BuildInfo,
+        errors_results::{GameError, IoError},
+    },
+};
+
+/// Turns per second a replay is played back with, unless another speed is given.
+pub const DEFAULT_REPLAY_SPEED: u32 = 8;
+
+/// Fastest speed (in turns per second) a replay can be played back with.
+pub const MAX_REPLAY_SPEED: u32 = 64;
+
+/// Everything needed to play a run again: The master seed, the contents of the stash at the start and every input of the player.
+///
+/// Since all randomness of a run comes from its seed, feeding the same inputs into a new game with the same seed plays out the same run.
+/// Rewound turns are removed again, but commands (like `give`) are not recorded, so runs that used them cannot be replayed faithfully.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+
+    /// Contents of the stash when the run started. The stash is kept between runs, so it is part of the starting conditions.
+    pub stash: Vec<GameItemDefId>,
+
+    /// Inputs of the player that resolved into an action, in order.
+    pub inputs: Vec<PlayerInput>,
+
+    /// Difficulty the run was played on.
+    #[serde(default)]
+    pub difficulty: DifficultyLevel,
+
+    /// Class of the player character, which decides their starting stats and kit.
+    #[serde(default)]
+    pub class: PlayerClass,
+
+    /// Build of the game that recorded the run. Other builds may play the inputs out differently.
+    #[serde(default = "BuildInfo::unknown")]
+    pub build: BuildInfo,
+}
+
+impl Replay {
+    /// Starts recording a run with the given seed and stash.
+    pub fn new(seed: u64, stash: &Stash) -> Self {
+        Self {
+            seed,
+            stash: stash.items.clone(),
+            inputs: Vec::new(),
+            difficulty: DifficultyLevel::default(),
+            class: PlayerClass::default(),
+            build: BuildInfo::current(),
+        }
+    }
+
+    /// Loads a replay from the given file.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file could not be read.
+    /// * [IoError::MapParsing] if the file is not a replay.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        let file = File::open(path).map_err(IoError::FileReading)?;
+        Ok(from_reader::<_, Replay>(BufReader::new(file)).map_err(IoError::MapParsing)?)
+    }
+}
+
+impl GameState {
+    /// Starts a new run from the starting conditions of a replay.
+    ///
+    /// The stash of the replay has no file, so playing back a replay never changes the player's actual stash.
+    pub fn from_replay(replay: &Replay) -> Self {
+        let mut game = GameState::with_seed(replay.seed);
+        game.stash = Stash { items: replay.stash.clone(), ..Stash::default() };
+        game.replay = Replay::new(replay.seed, &game.stash);
+        game.set_difficulty(replay.difficulty);
+        if let Err(error) = game.set_player_class(replay.class) {
+            game.log.debug_warn(format!("Couldn't hand out the starting kit: {}", error));
+        }
+        game
+    }
+
+    /// Writes the replay of the run so far into a new file in the game's replays directory.
+    ///
+    /// # Returns
+    /// * The path of the written file.
+    ///
+    /// # Errors
+    /// * [io::Error] if the file could not be created or written.
+    pub fn write_replay_file(&self) -> io::Result<PathBuf> {
+        let mut path = dirs::data_local_dir()
+            .ok_or(io::Error::new(io::ErrorKind::NotFound, "No data directory found on this OS"))?;
+        path.push("Anthill");
+        path.push("replays");
+        fs::create_dir_all(&path)?;
+
+        let filename =
+            format!("anthill_replay_{}.ron", chrono::Local::now().format("%Y-%m-%d-%H-%M-%S"));
+        path.push(filename);
+
+        let writer = BufWriter::new(File::create(&path)?);
+        to_writer_pretty(writer, &self.replay, PrettyConfig::default())
+            .map_err(io::Error::other)?;
+
+        Ok(path)
+    }
+}
+
+/// Plays a [Replay] back, one input per turn.
+pub struct Playback {
+    inputs: Vec<PlayerInput>,
+    next: usize,
+
+    /// Turns played per second.
+    pub speed: u32,
+
+    pub paused: bool,
+}
+
+impl Playback {
+    pub fn new(replay: Replay, speed: u32) -> Self {
+        Self {
+            inputs: replay.inputs,
+            next: 0,
+            speed: speed.clamp(1, MAX_REPLAY_SPEED),
+            paused: false,
+        }
+    }
+
+    /// Takes the input of the next turn.
+    ///
+    /// # Returns
+    /// * `None` if the replay is over.
+    pub fn next_input(&mut self) -> Option<PlayerInput> {
+        let input = self.inputs.get(self.next).copied()?;
+        self.next += 1;
+        Some(input)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.inputs.len()
+    }
+
+    /// Plays the replay faster, up to [MAX_REPLAY_SPEED].
+    pub fn speed_up(&mut self) {
+        self.speed = (self.speed * 2).min(MAX_REPLAY_SPEED);
+    }
+
+    /// Plays the replay slower, down to one turn per second.
+    pub fn slow_down(&mut self) {
+        self.speed = (self.speed / 2).max(1);
+    }
+
+    /// Time between two turns.
+    pub fn turn_duration(&self) -> Duration {
+        Duration::from_secs(1) / self.speed
+    }
+
+    /// Formats the progress of the playback for displaying, e.g. `Turn 12/80, 8 turns per second`.
+    pub fn status(&self) -> String {
+        let state = if self.paused { ", paused" } else { "" };
+        format!(
+            "Turn {}/{}, {} turns per second{}",
+            self.next,
+            self.inputs.len(),
+            self.speed,
+            state
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::entity_logic::Entity, world::coordinate_system::Direction};
+
+    #[test]
+    fn replays_play_out_the_same_run() {
+        let mut game = GameState::with_seed(1234);
+        game.stash = Stash::default();
+        game.replay = Replay::new(game.seed, &game.stash);
+
+        let directions = [Direction::Right, Direction::Down, Direction::Left, Direction::Up];
+        for turn in 0..40 {
+            game.resolve_player_action(PlayerInput::Direction(directions[turn / 3 % 4]));
+        }
+        game.resolve_player_action(PlayerInput::Wait);
+
+        let replay = game.replay.clone();
+        let mut replayed = GameState::from_replay(&replay);
+        let mut playback = Playback::new(replay, DEFAULT_REPLAY_SPEED);
+        while let Some(input) = playback.next_input() {
+            replayed.resolve_player_action(input);
+        }
+
+        assert!(playback.is_finished());
+        assert_eq!(replayed.round_nr, game.round_nr);
+        assert_eq!(replayed.player.character.pos(), game.player.character.pos());
+        assert_eq!(replayed.replay.inputs, game.replay.inputs);
+    }
+}
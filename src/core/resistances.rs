@@ -0,0 +1,228 @@
+use rand::Rng;
+use serde::Serialize;
+
+use crate::{
+    ai::behavior::direction_toward,
+    core::{
+        buff_effects::{ActiveBuff, PotionEffectDef},
+        crowd_control::{CrowdControl, CrowdControlTarget},
+        entity_logic::{Disposition, Entity, EntityId, Movable},
+        game::GameState,
+        game_items::GameItemKindDef,
+    },
+    data::{npc_defs::npc_defs, perks::PerkEffect},
+    util::{
+        errors_results::{EngineError, GameError, GameOutcome},
+        text_log::LogData,
+    },
+    world::{
+        coordinate_system::{Point, PointVector},
+        tiles::Collision,
+    },
+};
+
+/// Number of turns a frightened NPC runs away from the player.
+pub const FEAR_TURNS: u8 = 4;
+
+/// NPCs within this radius of a monster the player killed may be frightened by its death.
+pub const FEAR_RADIUS: usize = 3;
+
+/// Harmful effects that can be shrugged off by a [Resistances] roll.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Ailment {
+    Poison,
+    Fear,
+    Knockback,
+}
+
+impl Ailment {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ailment::Poison => "poison",
+            Ailment::Fear => "fear",
+            Ailment::Knockback => "knockback",
+        }
+    }
+}
+
+/// Chances (in percent) to resist each [Ailment]. A chance of 100 or more means immunity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Resistances {
+    pub poison: u8,
+    pub fear: u8,
+    pub knockback: u8,
+}
+
+impl Resistances {
+    pub const NONE: Resistances = Resistances { poison: 0, fear: 0, knockback: 0 };
+
+    pub fn chance(&self, ailment: Ailment) -> u8 {
+        match ailment {
+            Ailment::Poison => self.poison,
+            Ailment::Fear => self.fear,
+            Ailment::Knockback => self.knockback,
+        }
+    }
+
+    /// Adds a chance to resist the ailment, capped at 100.
+    pub fn add(&mut self, ailment: Ailment, chance: u8) {
+        let resistance = match ailment {
+            Ailment::Poison => &mut self.poison,
+            Ailment::Fear => &mut self.fear,
+            Ailment::Knockback => &mut self.knockback,
+        };
+        *resistance = resistance.saturating_add(chance).min(100);
+    }
+}
+
+impl GameState {
+    /// Resistances of the player character, given by their armor and their perks.
+    pub fn player_resistances(&self) -> Resistances {
+        let mut resistances = self
+            .player
+            .character
+            .armor
+            .and_then(|armor| self.get_item_by_id(armor.0))
+            .and_then(|item| self.get_item_def_by_id(&item.def_id))
+            .map_or(Resistances::NONE, |def| match def.kind {
+                GameItemKindDef::Armor { resistances, .. } => resistances,
+                _ => Resistances::NONE,
+            });
+
+        for effect in self.player.character.perk_effects() {
+            if let PerkEffect::Resistance(ailment, chance) = effect {
+                resistances.add(ailment, chance);
+            }
+        }
+        resistances
+    }
+
+    /// Resistances of the NPC, as given by its definition.
+    pub fn npc_resistances(&self, npc_id: EntityId) -> Resistances {
+        self.current_level()
+            .get_npc(npc_id)
+            .and_then(|npc| npc_defs().get(&npc.def_id))
+            .map_or(Resistances::NONE, |def| def.resistances)
+    }
+
+    /// Rolls whether the resistances shrug off the ailment.
+    pub(crate) fn roll_resistance(&mut self, resistances: Resistances, ailment: Ailment) -> bool {
+        self.rng.random_range(0..100) < resistances.chance(ailment) as u32
+    }
+
+    /// Poisons the player, unless they resist it.
+    pub fn poison_player(&mut self, damage_per_tick: u16, duration: u8) {
+        if self.roll_resistance(self.player_resistances(), Ailment::Poison) {
+            self.log.info(LogData::PlayerResists { ailment: Ailment::Poison });
+            return;
+        }
+
+        self.player.character.active_buffs.push(ActiveBuff {
+            effect: PotionEffectDef::Poison { damage_per_tick, duration },
+            remaining_turns: duration,
+        });
+    }
+
+    /// Knocks the player one tile away from the given point, unless they resist it or the tile behind them is blocked.
+    pub fn knock_back_player(&mut self, from: Point, npc_name: &str) {
+        if self.roll_resistance(self.player_resistances(), Ailment::Knockback) {
+            self.log.info(LogData::PlayerResists { ailment: Ailment::Knockback });
+            return;
+        }
+
+        let player_pos = self.player.character.pos();
+        let target = player_pos.get_adjacent(direction_toward(from, player_pos));
+        // The player is not pushed into hazards
+        let free = self.current_level().is_available(target)
+            && self.current_world().get_tile(target).tile_type.movement_cost().is_some();
+        if free {
+            self.player.character.move_to(target);
+            self.log.info(LogData::PlayerKnockedBack { npc_name: npc_name.to_string() });
+        }
+    }
+
+    /// Knocks the NPC one tile away from the player, unless it resists it or the tile behind it is blocked.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not on the current level.
+    pub fn knock_back_npc(&mut self, npc_id: EntityId) -> Result<(), GameError> {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_name = npc.name().to_string();
+        let npc_pos = npc.pos();
+
+        if self.roll_resistance(self.npc_resistances(npc_id), Ailment::Knockback) {
+            self.log.info(LogData::NpcResists { npc_name, ailment: Ailment::Knockback });
+            return Ok(());
+        }
+
+        let direction = direction_toward(self.player.character.pos(), npc_pos);
+        let step = PointVector::from(direction);
+        if let GameOutcome::Success = self.move_npc(npc_id, step.x, step.y)? {
+            self.log.info(LogData::NpcKnockedBack { npc_name });
+        }
+        Ok(())
+    }
+
+    /// Frightens the hostile NPCs near the point where the player killed a monster. NPCs that do not resist
+    /// run away from the player for [FEAR_TURNS] turns.
+    pub fn frighten_npcs_near(&mut self, point: Point) {
+        let witnesses: Vec<EntityId> = self
+            .current_level()
+            .npcs
+            .iter()
+            .filter(|npc| npc.disposition == Disposition::Hostile)
+            .filter(|npc| npc.pos().distance_squared_from(point) <= FEAR_RADIUS.pow(2))
+            .map(|npc| npc.id())
+            .collect();
+
+        for npc_id in witnesses {
+            // The witnesses were just collected from the current level
+            let _ = self.apply_crowd_control(
+                CrowdControlTarget::Npc(npc_id),
+                CrowdControl::Feared,
+                FEAR_TURNS,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    #[test]
+    fn resistances_come_from_definitions_equipment_and_perks() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(10, 10);
+
+        // Skeletons are immune to fear, goblins are not
+        let skeleton = game.create_npc("skeleton".into(), Point::new(11, 10)).unwrap();
+        let skeleton_id = skeleton.id();
+        game.current_level_mut().spawn_npc(skeleton).unwrap();
+        let goblin = game.create_npc("goblin".into(), Point::new(9, 10)).unwrap();
+        let goblin_id = goblin.id();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+
+        game.frighten_npcs_near(Point::new(10, 11));
+        assert_eq!(game.current_level().get_npc(skeleton_id).unwrap().crowd_control.feared, 0);
+        assert_eq!(
+            game.current_level().get_npc(goblin_id).unwrap().crowd_control.feared,
+            FEAR_TURNS
+        );
+
+        game.knock_back_npc(goblin_id).unwrap();
+        assert_eq!(game.current_level().get_npc(goblin_id).unwrap().pos(), Point::new(8, 10));
+
+        let plate = game.register_item(&"armor_plate".to_string()).unwrap();
+        game.player.character.armor = Some(crate::core::game_items::ArmorItem(plate));
+        game.player.character.perks.push("steady_stance".to_string());
+        assert_eq!(game.player_resistances().knockback, 100);
+
+        game.knock_back_player(Point::new(11, 10), "Ogre");
+        assert_eq!(game.player.character.pos(), Point::new(10, 10));
+    }
+}
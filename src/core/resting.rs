@@ -0,0 +1,149 @@
+use serde::Serialize;
+
+use crate::{
+    core::{entity_logic::Disposition, game::GameState, player_actions::PlayerInput},
+    util::text_log::LogData,
+};
+
+/// Maximum number of rounds a single rest lasts.
+pub const REST_MAX_TURNS: u16 = 100;
+
+/// Progress of a rest, which waits round after round until it is over (see [GameState::continue_resting]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RestState {
+    /// Rounds left until the rest is over, even if the player has not fully healed.
+    pub turns_left: u16,
+}
+
+/// Why a rest came to an end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum RestEnd {
+    /// The player has all their hit points.
+    Healed,
+
+    /// The rest lasted [REST_MAX_TURNS] rounds.
+    TimeUp,
+
+    /// A hostile NPC came into sight.
+    Interrupted,
+
+    /// The player pressed a key.
+    Cancelled,
+}
+
+impl GameState {
+    /// Starts resting. The rest is carried out by calling [GameState::continue_resting] until it is over.
+    ///
+    /// The player cannot rest with enemies in sight or with full hit points.
+    pub fn start_resting(&mut self) {
+        if self.hostile_in_sight() {
+            self.log.info(LogData::RestEnemiesNearby);
+            return;
+        }
+        if self.player_fully_healed() {
+            self.log.info(LogData::RestNotNeeded);
+            return;
+        }
+
+        self.log.info(LogData::RestStarted);
+        self.resting = Some(RestState { turns_left: REST_MAX_TURNS });
+    }
+
+    /// Waits one round of the current rest, or ends the rest if it is over.
+    ///
+    /// The rounds are waited like the player pressed wait, so a rest plays out the same way in a [Replay](crate::core::replay::Replay).
+    pub fn continue_resting(&mut self) {
+        let Some(rest) = self.resting else {
+            return;
+        };
+
+        let end = if self.hostile_in_sight() {
+            Some(RestEnd::Interrupted)
+        } else if self.player_fully_healed() {
+            Some(RestEnd::Healed)
+        } else if rest.turns_left == 0 {
+            Some(RestEnd::TimeUp)
+        } else {
+            None
+        };
+
+        if let Some(end) = end {
+            self.stop_resting(end);
+            return;
+        }
+
+        self.resting = Some(RestState { turns_left: rest.turns_left - 1 });
+        self.resolve_player_action(PlayerInput::Wait);
+    }
+
+    /// Ends the current rest and tells the player why.
+    pub fn stop_resting(&mut self, end: RestEnd) {
+        if self.resting.take().is_some() {
+            self.log.info(LogData::RestEnded { end });
+        }
+    }
+
+    /// Whether a hostile NPC stands on a tile the player can see.
+    pub(crate) fn hostile_in_sight(&self) -> bool {
+        self.current_level()
+            .npcs
+            .iter()
+            .any(|npc| npc.disposition == Disposition::Hostile && self.can_see_npc(npc))
+    }
+
+    fn player_fully_healed(&self) -> bool {
+        let hp = &self.player.character.stats.base;
+        hp.hp_current >= hp.hp_max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::entity_logic::Entity;
+    use crate::world::{coordinate_system::Point, level::Level, worldspace::Room};
+
+    fn wounded_in_room() -> GameState {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 30, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(8, 8);
+        game.player.character.take_damage(3);
+        game.compute_fov();
+        game
+    }
+
+    #[test]
+    fn resting_heals_until_full() {
+        let mut game = wounded_in_room();
+
+        game.start_resting();
+        while game.resting.is_some() {
+            game.continue_resting();
+        }
+
+        assert!(game.player_fully_healed());
+        assert_eq!(game.round_nr, 3 * game.regen_rules.base_interval as u64);
+    }
+
+    #[test]
+    fn enemies_in_sight_interrupt_resting() {
+        let mut game = wounded_in_room();
+        game.start_resting();
+        game.continue_resting();
+        assert!(game.resting.is_some());
+
+        let goblin = game.create_npc("goblin".into(), Point::new(12, 8)).unwrap();
+        let goblin_id = goblin.id();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+        game.continue_resting();
+
+        assert!(game.resting.is_none());
+        assert!(game.current_level().get_npc(goblin_id).is_some());
+
+        // Resting cannot even start while the goblin is around
+        game.start_resting();
+        assert!(game.resting.is_none());
+    }
+}
@@ -0,0 +1,176 @@
+#![cfg_attr(not(feature = "dev"), allow(dead_code))]
+
+use std::collections::{HashMap, VecDeque};
+
+use rand::rngs::StdRng;
+
+use crate::{
+    core::{
+        entity_registry::EntityRegistry,
+        flavor::EventFlags,
+        game::{GameState, IdSystem},
+        game_items::{GameItem, GameItemId},
+        journal::Journal,
+        player::Player,
+        quests::Quest,
+        run_stats::RunStats,
+        shop::ShopRegister,
+        town::Stash,
+        uniques::UniqueRegistry,
+    },
+    util::errors_results::GameError,
+    world::level::Level,
+};
+
+/// Number of turns that can be rewound. Older snapshots are dropped.
+pub const REWIND_CAPACITY: usize = 20;
+
+/// Copy of the game state at the start of a turn.
+///
+/// The log is not part of it, so the messages of rewound turns can still be read.
+#[derive(Clone)]
+pub struct TurnSnapshot {
+    levels: Vec<Level>,
+    level_nr: usize,
+    player: Player,
+    round_nr: u64,
+    id_system: IdSystem,
+    entity_registry: EntityRegistry,
+    items: HashMap<GameItemId, GameItem>,
+    rng: StdRng,
+    proc_gen: StdRng,
+    event_flags: EventFlags,
+    journal: Journal,
+    quests: Vec<Quest>,
+    shops: ShopRegister,
+    uniques: UniqueRegistry,
+    stash: Stash,
+    run_stats: RunStats,
+    /// Number of recorded inputs, so the inputs of rewound turns are removed from the replay.
+    replay_len: usize,
+}
+
+/// Snapshots of the last [REWIND_CAPACITY] turns, newest last. Only recorded in dev builds.
+#[derive(Default)]
+pub struct RewindHistory {
+    snapshots: VecDeque<TurnSnapshot>,
+}
+
+impl RewindHistory {
+    /// Number of turns that can currently be rewound.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Stores the snapshot of a turn that was just played.
+    pub fn push(&mut self, snapshot: TurnSnapshot) {
+        if self.snapshots.len() >= REWIND_CAPACITY {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+}
+
+impl GameState {
+    /// Copies everything a turn can change.
+    pub fn snapshot(&self) -> TurnSnapshot {
+        TurnSnapshot {
+            levels: self.levels.clone(),
+            level_nr: self.level_nr,
+            player: self.player.clone(),
+            round_nr: self.round_nr,
+            id_system: self.id_system.clone(),
+            entity_registry: self.entity_registry.clone(),
+            items: self.items.clone(),
+            rng: self.rng.clone(),
+            proc_gen: self.proc_gen.clone(),
+            event_flags: self.event_flags,
+            journal: self.journal.clone(),
+            quests: self.quests.clone(),
+            shops: self.shops.clone(),
+            uniques: self.uniques.clone(),
+            stash: self.stash.clone(),
+            run_stats: self.run_stats.clone(),
+            replay_len: self.replay.inputs.len(),
+        }
+    }
+
+    /// Steps the game back to the start of the last turn that was played.
+    ///
+    /// Since the rng is restored as well, repeating the same input plays out the turn exactly like before.
+    ///
+    /// # Errors
+    /// * [GameError::Io] if the restored stash could not be saved.
+    ///
+    /// # Returns
+    /// * `false` if there is no turn left to rewind.
+    /// * `true` if the last turn was rewound.
+    pub fn rewind(&mut self) -> Result<bool, GameError> {
+        let Some(snapshot) = self.rewind_history.snapshots.pop_back() else {
+            return Ok(false);
+        };
+
+        self.levels = snapshot.levels;
+        self.level_nr = snapshot.level_nr;
+        self.player = snapshot.player;
+        self.round_nr = snapshot.round_nr;
+        self.log.set_round(self.round_nr);
+        self.id_system = snapshot.id_system;
+        self.entity_registry = snapshot.entity_registry;
+        self.items = snapshot.items;
+        self.rng = snapshot.rng;
+        self.proc_gen = snapshot.proc_gen;
+        self.event_flags = snapshot.event_flags;
+        self.journal = snapshot.journal;
+        self.quests = snapshot.quests;
+        self.shops = snapshot.shops;
+        self.uniques = snapshot.uniques;
+        self.stash = snapshot.stash;
+        self.run_stats = snapshot.run_stats;
+        self.replay.inputs.truncate(snapshot.replay_len);
+        self.cursor = None;
+
+        self.stash.save()?;
+        Ok(true)
+    }
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{entity_logic::Entity, player_actions::PlayerInput},
+        world::{coordinate_system::Direction, level::LevelEntrance},
+    };
+
+    #[test]
+    fn rewinding_restores_the_last_turns() {
+        let mut game = GameState::default();
+        game.goto_level(2, LevelEntrance::Entry).unwrap();
+        let start_pos = game.player.character.pos();
+
+        game.resolve_player_action(PlayerInput::Wait);
+        let waited_npcs: Vec<_> = game.current_level().npcs.iter().map(|npc| npc.pos()).collect();
+        for direction in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+            game.resolve_player_action(PlayerInput::Direction(direction));
+        }
+        assert_eq!(game.rewind_history.len(), game.round_nr as usize);
+
+        while game.round_nr > 1 {
+            assert!(game.rewind().unwrap());
+        }
+        assert_eq!(
+            game.current_level().npcs.iter().map(|npc| npc.pos()).collect::<Vec<_>>(),
+            waited_npcs
+        );
+
+        assert!(game.rewind().unwrap());
+        assert!(!game.rewind().unwrap());
+        assert_eq!(game.round_nr, 0);
+        assert_eq!(game.player.character.pos(), start_pos);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::core::game::GameState;
+
+/// What hurt the player. The last one is the cause of death.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DamageSource {
+    Npc(String),
+    Lava,
+    Fall,
+    Poison,
+    Explosion,
+    SpikeTrap,
+}
+
+impl fmt::Display for DamageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DamageSource::Npc(npc_name) => write!(f, "Slain by a {}", npc_name),
+            DamageSource::Lava => write!(f, "Burned to ashes in lava"),
+            DamageSource::Fall => write!(f, "Fell to death into a chasm"),
+            DamageSource::Poison => write!(f, "Succumbed to poison"),
+            DamageSource::Explosion => write!(f, "Blown up by an explosion"),
+            DamageSource::SpikeTrap => write!(f, "Impaled on a spike trap"),
+        }
+    }
+}
+
+/// Statistics of the current run, shown with the `stats` command and when the run ends.
+#[derive(Clone, Default)]
+pub struct RunStats {
+    /// Number of monsters the player killed, by name.
+    pub kills: BTreeMap<String, u32>,
+
+    /// Damage the player dealt to NPCs.
+    pub damage_dealt: u32,
+
+    /// Damage the player took from any source.
+    pub damage_taken: u32,
+
+    /// Number of potions the player drank.
+    pub potions_drunk: u32,
+
+    /// Gold the player looted from monsters.
+    pub gold_looted: u32,
+
+    /// Deepest level the player reached.
+    pub deepest_level: usize,
+
+    /// What hurt the player last.
+    pub last_damage_source: Option<DamageSource>,
+}
+
+impl RunStats {
+    /// Number of monsters the player killed in total.
+    pub fn total_kills(&self) -> u32 {
+        self.kills.values().sum()
+    }
+}
+
+impl GameState {
+    /// Counts a monster the player killed.
+    pub fn record_kill(&mut self, npc_name: &str) {
+        *self.run_stats.kills.entry(npc_name.to_string()).or_insert(0) += 1;
+    }
+
+    /// Counts damage the player took and remembers what hurt them, so the cause of death is known.
+    pub fn record_damage_taken(&mut self, damage: u16, source: DamageSource) {
+        if damage > 0 {
+            self.run_stats.damage_taken += damage as u32;
+            self.run_stats.last_damage_source = Some(source);
+        }
+    }
+
+    /// Describes how the player died.
+    pub fn cause_of_death(&self) -> String {
+        match &self.run_stats.last_damage_source {
+            Some(source) => source.to_string(),
+            None => "Died of unknown causes".to_string(),
+        }
+    }
+
+    /// Number of tiles the player has seen, on all levels.
+    pub fn explored_tiles(&self) -> usize {
+        self.levels
+            .iter()
+            .map(|level| level.world.tiles.iter().filter(|tile| tile.explored).count())
+            .sum()
+    }
+
+    /// Formats the statistics of the run for displaying.
+    pub fn run_stats_lines(&self) -> Vec<String> {
+        let stats = &self.run_stats;
+        let affix = match self.current_level().affix_def() {
+            Some(affix) => format!(", {}", affix.name),
+            None => String::new(),
+        };
+        vec![
+            format!(
+                "Depth: floor {}{} (deepest {})",
+                self.level_nr,
+                affix,
+                stats.deepest_level.max(self.level_nr)
+            ),
+            format!("Turns: {}", self.round_nr),
+            format!("Kills: {}", stats.total_kills()),
+            format!("Damage: {} dealt, {} taken", stats.damage_dealt, stats.damage_taken),
+            format!("Potions drunk: {}", stats.potions_drunk),
+            format!("Tiles explored: {}", self.explored_tiles()),
+        ]
+    }
+
+    /// Lists how many monsters of each kind the player killed, e.g. `Goblin x3`.
+    pub fn kill_lines(&self) -> Vec<String> {
+        self.run_stats.kills.iter().map(|(name, count)| format!("{} x{}", name, count)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kills_are_counted_per_monster() {
+        let mut game = GameState::default();
+        game.record_kill("Goblin");
+        game.record_kill("Wolf");
+        game.record_kill("Goblin");
+        game.record_damage_taken(4, DamageSource::Poison);
+        game.record_damage_taken(0, DamageSource::Lava);
+
+        assert_eq!(game.run_stats.total_kills(), 3);
+        assert_eq!(game.kill_lines(), vec!["Goblin x2".to_string(), "Wolf x1".to_string()]);
+        assert_eq!(game.run_stats.damage_taken, 4);
+        assert_eq!(game.cause_of_death(), "Succumbed to poison");
+    }
+}
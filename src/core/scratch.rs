@@ -0,0 +1,89 @@
+use crate::{
+    ai::{dijkstra_map::DijkstraMap, pathfinding::AStarScratch},
+    core::entity_logic::EntityId,
+    world::coordinate_system::Point,
+};
+
+/// Buffers that are reused by the routines running every round (NPC turns, pathfinding), so a round doesn't allocate once they have grown large enough.
+///
+/// A routine takes the buffer it needs out of the [GameState](crate::core::game::GameState) with [std::mem::take], clears and fills it, and puts it back when it is done.
+/// This keeps the capacity of the buffer, while the routine can still borrow the game state as usual.
+/// A routine must not call another routine that uses the same buffer while it has taken it out.
+#[derive(Default)]
+pub struct ScratchBuffers {
+    /// Ids of the NPCs that take their turn, see [GameState::next_round](crate::core::game::GameState::next_round).
+    pub npc_ids: Vec<EntityId>,
+
+    /// Points around an NPC, like the area in which it detects the player.
+    pub points: Vec<Point>,
+
+    /// Buffers of the A* algorithm, see [GameState::next_step_toward](crate::core::game::GameState::next_step_toward).
+    pub a_star: AStarScratch,
+
+    /// Distances to the player, see [GameState::refresh_player_map](crate::core::game::GameState::refresh_player_map).
+    pub player_map: DijkstraMap,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use crate::{
+        core::{entity_logic::Disposition, game::GameState},
+        world::{coordinate_system::Point, level::Level, worldspace::Room},
+    };
+
+    /// Counts the allocations of each thread, so tests running in parallel don't count each other's.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn rounds_do_not_allocate() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 50, 15));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(50, 12);
+
+        // Goblins wander far away from the player, the companion walks towards the player
+        for (def_id, x, y) in
+            [("goblin", 8, 7), ("goblin", 12, 16), ("goblin", 16, 9), ("wolf", 20, 12)]
+        {
+            let npc = game.create_npc(def_id.into(), Point::new(x, y)).unwrap();
+            game.current_level_mut().spawn_npc(npc).unwrap();
+        }
+        game.current_level_mut().npcs.last_mut().unwrap().disposition = Disposition::Companion;
+
+        // The first rounds grow the buffers
+        for _ in 0..3 {
+            game.next_round();
+        }
+
+        let before = ALLOCATIONS.with(Cell::get);
+        game.next_round();
+        assert_eq!(ALLOCATIONS.with(Cell::get) - before, 0);
+    }
+}
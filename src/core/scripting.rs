@@ -0,0 +1,600 @@
+//! Scripts that give handcrafted content (like vaults) some behavior without recompiling the game.
+//!
+//! Scripts are written in [Rhai](https://rhai.rs). The code of a script runs the first time the player enters its level.
+//! Coordinates are relative to the area the script belongs to (see [ScriptData]).
+//!
+//! Scripts react to the events of their level by defining functions of these names (see [ScriptEvent]):
+//!
+//! * `on_enter_level()` - The player enters the level, including the first time.
+//! * `on_death(def_id)` - An NPC of the given kind dies on the level.
+//! * `on_use(def_id)` - The player uses an item of the given kind on the level.
+//!
+//! Inside these functions, `this` is an object map that keeps its values from one event to the next, e.g. `this.kills = (this.kills ?? 0) + 1`.
+//!
+//! Scripts can only reach the game through these functions:
+//!
+//! * `log(text)` - Writes the text into the log.
+//! * `spawn_npc(def_id, x, y)` - Spawns an NPC.
+//! * `spawn_hunter(def_id, x, y)` - Spawns an NPC that hunts the player across the whole level.
+//! * `spawn_item(def_id, x, y)` - Spawns an item on the ground.
+//! * `apply_status(potion_def_id)` - Applies the effect of a potion to the player.
+//! * `tile(x, y)` - Kind of the tile at the given coordinates, e.g. `"closed_door"`.
+//! * `is_free(x, y)` - Whether nothing stands on the tile at the given coordinates.
+//! * `width()`, `height()` - Size of the area.
+//! * `depth()` - Number of the level.
+//!
+//! Scripts only touch tiles inside their area and cannot read files, so they cannot break the game state.
+//! What they spawn or apply is carried out once they are done, and each run is limited in time and depth (see [SCRIPT_MAX_OPERATIONS]).
+
+use std::{cell::RefCell, fs, rc::Rc};
+
+use rhai::{
+    AST, CallFnOptions, Dynamic, Engine, EvalAltResult, INT, Map, Scope,
+    module_resolvers::DummyModuleResolver,
+};
+
+use crate::{
+    ai::npc_ai::NpcAiState,
+    core::{game::GameState, game_items::GameItemKindDef},
+    data::{item_defs::GameItemDefId, npc_defs::NpcDefId},
+    util::{
+        errors_results::{DataError, EngineError, GameError, IoError},
+        text_log::LogData,
+    },
+    world::{coordinate_system::Point, level_data::ScriptData, tiles::TileType},
+};
+
+/// Operations a single run of a script may take, so an endless loop cannot freeze the game.
+pub const SCRIPT_MAX_OPERATIONS: u64 = 50_000;
+
+/// Depth of nested function calls a script may reach.
+pub const SCRIPT_MAX_CALL_DEPTH: usize = 16;
+
+/// Game events a script can react to, each with the function that handles it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptEvent {
+    /// The player enters the level, including the first time.
+    EnterLevel,
+    /// An NPC of the given definition dies on the level.
+    Death(NpcDefId),
+    /// The player uses an item of the given definition on the level.
+    Use(GameItemDefId),
+}
+
+impl ScriptEvent {
+    /// Name of the script function that handles the event.
+    pub fn handler_name(&self) -> &'static str {
+        match self {
+            ScriptEvent::EnterLevel => "on_enter_level",
+            ScriptEvent::Death(_) => "on_death",
+            ScriptEvent::Use(_) => "on_use",
+        }
+    }
+
+    /// Arguments the handler is called with.
+    fn arguments(&self) -> Vec<Dynamic> {
+        match self {
+            ScriptEvent::EnterLevel => Vec::new(),
+            ScriptEvent::Death(def_id) | ScriptEvent::Use(def_id) => {
+                vec![Dynamic::from(def_id.clone())]
+            }
+        }
+    }
+}
+
+/// A script of a level that has run, waiting for the events of its level.
+#[derive(Clone, Debug)]
+pub struct LoadedScript {
+    pub script: ScriptData,
+    ast: AST,
+    /// What the handlers see as `this`.
+    state: Dynamic,
+}
+
+/// Something a script asks the game to do. Carried out once the script is done (see [GameState::run_script_command]).
+#[derive(Clone, Debug)]
+pub enum ScriptCommand {
+    Log(String),
+    SpawnNpc { def_id: NpcDefId, x: usize, y: usize },
+    SpawnHunter { def_id: NpcDefId, x: usize, y: usize },
+    SpawnItem { def_id: GameItemDefId, x: usize, y: usize },
+    Status { potion_def_id: GameItemDefId },
+}
+
+/// What a running script sees of its area, and what it has asked the game to do so far.
+struct ScriptContext {
+    width: usize,
+    height: usize,
+    depth: usize,
+    /// Names of the area's tiles row by row (see [script_tile_name]).
+    tiles: Vec<String>,
+    /// Whether nothing stands on the area's tiles, row by row.
+    free: Vec<bool>,
+    commands: Vec<ScriptCommand>,
+}
+
+impl ScriptContext {
+    /// Index of the coordinates into the area's rows.
+    fn index(&self, x: INT, y: INT) -> Result<usize, Box<EvalAltResult>> {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) if x < self.width && y < self.height => Ok(y * self.width + x),
+            _ => Err(format!("({}, {}) is outside the script's area", x, y).into()),
+        }
+    }
+}
+
+/// Name of a tile type as written in scripts, e.g. `closed_door` for [TileType::Door] ([DoorType::Closed](crate::world::tiles::DoorType::Closed)).
+fn script_tile_name(tile_type: TileType) -> String {
+    tile_type.to_string().to_lowercase().replace(' ', "_")
+}
+
+/// Creates the engine scripts run in, with the limits and the functions of the game's API.
+fn script_engine(context: &Rc<RefCell<ScriptContext>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(SCRIPT_MAX_OPERATIONS)
+        .set_max_call_levels(SCRIPT_MAX_CALL_DEPTH)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(4096)
+        .set_max_array_size(1024)
+        .set_max_map_size(1024)
+        .set_module_resolver(DummyModuleResolver::new())
+        // The terminal belongs to the UI, scripts write into the log instead
+        .on_print(|_| {})
+        .on_debug(|_, _, _| {});
+    engine.disable_symbol("eval");
+
+    let ctx = Rc::clone(context);
+    engine.register_fn("log", move |text: &str| {
+        ctx.borrow_mut().commands.push(ScriptCommand::Log(text.to_string()));
+    });
+
+    let ctx = Rc::clone(context);
+    engine.register_fn(
+        "spawn_npc",
+        move |def_id: &str, x: INT, y: INT| -> Result<(), Box<EvalAltResult>> {
+            let mut ctx = ctx.borrow_mut();
+            let index = ctx.index(x, y)?;
+            let (x, y) = (index % ctx.width, index / ctx.width);
+            ctx.commands.push(ScriptCommand::SpawnNpc { def_id: def_id.to_string(), x, y });
+            Ok(())
+        },
+    );
+
+    let ctx = Rc::clone(context);
+    engine.register_fn(
+        "spawn_hunter",
+        move |def_id: &str, x: INT, y: INT| -> Result<(), Box<EvalAltResult>> {
+            let mut ctx = ctx.borrow_mut();
+            let index = ctx.index(x, y)?;
+            let (x, y) = (index % ctx.width, index / ctx.width);
+            ctx.commands.push(ScriptCommand::SpawnHunter { def_id: def_id.to_string(), x, y });
+            Ok(())
+        },
+    );
+
+    let ctx = Rc::clone(context);
+    engine.register_fn(
+        "spawn_item",
+        move |def_id: &str, x: INT, y: INT| -> Result<(), Box<EvalAltResult>> {
+            let mut ctx = ctx.borrow_mut();
+            let index = ctx.index(x, y)?;
+            let (x, y) = (index % ctx.width, index / ctx.width);
+            ctx.commands.push(ScriptCommand::SpawnItem { def_id: def_id.to_string(), x, y });
+            Ok(())
+        },
+    );
+
+    let ctx = Rc::clone(context);
+    engine.register_fn("apply_status", move |potion_def_id: &str| {
+        ctx.borrow_mut()
+            .commands
+            .push(ScriptCommand::Status { potion_def_id: potion_def_id.to_string() });
+    });
+
+    let ctx = Rc::clone(context);
+    engine.register_fn("tile", move |x: INT, y: INT| -> Result<String, Box<EvalAltResult>> {
+        let ctx = ctx.borrow();
+        Ok(ctx.tiles[ctx.index(x, y)?].clone())
+    });
+
+    let ctx = Rc::clone(context);
+    engine.register_fn("width", move || ctx.borrow().width as INT);
+    let ctx = Rc::clone(context);
+    engine.register_fn("height", move || ctx.borrow().height as INT);
+    let ctx = Rc::clone(context);
+    engine.register_fn("depth", move || ctx.borrow().depth as INT);
+
+    let ctx = Rc::clone(context);
+    engine.register_fn("is_free", move |x: INT, y: INT| -> Result<bool, Box<EvalAltResult>> {
+        let ctx = ctx.borrow();
+        Ok(ctx.free[ctx.index(x, y)?])
+    });
+
+    engine
+}
+
+impl GameState {
+    /// Runs the scripts of the current level that have not run yet, then lets all of the level's scripts know the player entered it.
+    /// Each script only runs once, but its handlers stay on the level (see [GameState::fire_script_event]).
+    ///
+    /// All scripts run, even if one of them fails.
+    ///
+    /// # Errors
+    /// * The error of the first script that could not be read, compiled or run (see [GameState::run_script]).
+    pub fn run_level_scripts(&mut self) -> Result<(), GameError> {
+        let mut result = Ok(());
+        for script in std::mem::take(&mut self.current_level_mut().scripts) {
+            let run = fs::read_to_string(&script.path)
+                .map_err(|error| GameError::from(IoError::FileReading(error)))
+                .and_then(|source| self.run_script(&script, &source));
+            if result.is_ok() {
+                result = run;
+            }
+        }
+
+        let entered = self.fire_script_event(&ScriptEvent::EnterLevel);
+        result.and(entered)
+    }
+
+    /// Runs the source of a script, carries out what it asked for and keeps the script on the level for its events.
+    ///
+    /// # Errors
+    /// * [DataError::InvalidScript] if the source is not valid Rhai.
+    /// * [EngineError::ScriptFailed] if the script failed or ran over its limits. Nothing it asked for is carried out.
+    /// * The error of the first command that could not be carried out (see [GameState::run_script_command]). The other commands are still carried out.
+    pub fn run_script(&mut self, script: &ScriptData, source: &str) -> Result<(), GameError> {
+        let context = Rc::new(RefCell::new(self.script_context(script)));
+        let engine = script_engine(&context);
+
+        let ast = engine.compile(source).map_err(|error| {
+            GameError::from(DataError::InvalidScript(script.path.clone(), error.to_string()))
+        })?;
+
+        engine.run_ast(&ast).map_err(|error| {
+            GameError::from(EngineError::ScriptFailed(script.path.clone(), error.to_string()))
+        })?;
+
+        let commands = std::mem::take(&mut context.borrow_mut().commands);
+        self.current_level_mut().loaded_scripts.push(LoadedScript {
+            script: script.clone(),
+            ast,
+            state: Dynamic::from_map(Map::new()),
+        });
+        self.run_script_commands(script, &commands)
+    }
+
+    /// Calls the handlers of the current level's scripts for the given event, then carries out what they asked for.
+    /// Scripts without a handler for the event are skipped.
+    ///
+    /// # Errors
+    /// * [EngineError::ScriptFailed] if a handler failed or ran over its limits. Nothing it asked for is carried out, the other handlers still run.
+    /// * The error of the first command that could not be carried out (see [GameState::run_script_command]).
+    pub fn fire_script_event(&mut self, event: &ScriptEvent) -> Result<(), GameError> {
+        let Some(level) = self.levels.get_mut(self.level_nr) else {
+            return Ok(());
+        };
+        let mut scripts = std::mem::take(&mut level.loaded_scripts);
+
+        let mut result = Ok(());
+        let mut requests = Vec::new();
+        for loaded in &mut scripts {
+            let has_handler = loaded.ast.iter_functions().any(|function| {
+                function.name == event.handler_name()
+                    && function.params.len() == event.arguments().len()
+            });
+            if !has_handler {
+                continue;
+            }
+
+            let context = Rc::new(RefCell::new(self.script_context(&loaded.script)));
+            let engine = script_engine(&context);
+            let options = CallFnOptions::new().eval_ast(false).bind_this_ptr(&mut loaded.state);
+            let called = engine.call_fn_with_options::<Dynamic>(
+                options,
+                &mut Scope::new(),
+                &loaded.ast,
+                event.handler_name(),
+                event.arguments(),
+            );
+
+            match called {
+                Ok(_) => {
+                    let commands = std::mem::take(&mut context.borrow_mut().commands);
+                    requests.push((loaded.script.clone(), commands));
+                }
+                Err(error) if result.is_ok() => {
+                    result = Err(GameError::from(EngineError::ScriptFailed(
+                        loaded.script.path.clone(),
+                        error.to_string(),
+                    )));
+                }
+                Err(_) => {}
+            }
+        }
+        self.current_level_mut().loaded_scripts.splice(0..0, scripts);
+
+        for (script, commands) in requests {
+            let run = self.run_script_commands(&script, &commands);
+            if result.is_ok() {
+                result = run;
+            }
+        }
+        result
+    }
+
+    /// Carries out the commands of a script, even if one of them fails.
+    ///
+    /// # Errors
+    /// * The error of the first command that could not be carried out (see [GameState::run_script_command]).
+    fn run_script_commands(
+        &mut self,
+        script: &ScriptData,
+        commands: &[ScriptCommand],
+    ) -> Result<(), GameError> {
+        let mut result = Ok(());
+        for command in commands {
+            let run = self.run_script_command(script, command);
+            if result.is_ok() {
+                result = run;
+            }
+        }
+        result
+    }
+
+    /// What a script sees of its area on the current level.
+    fn script_context(&self, script: &ScriptData) -> ScriptContext {
+        let mut tiles = Vec::with_capacity(script.width * script.height);
+        let mut free = Vec::with_capacity(script.width * script.height);
+        for y in 0..script.height {
+            for x in 0..script.width {
+                match self.script_point(script, x, y) {
+                    Ok(pos) => {
+                        tiles.push(script_tile_name(self.current_world().get_tile(pos).tile_type));
+                        free.push(self.current_level().is_available(pos));
+                    }
+                    Err(_) => {
+                        tiles.push(String::new());
+                        free.push(false);
+                    }
+                }
+            }
+        }
+
+        ScriptContext {
+            width: script.width,
+            height: script.height,
+            depth: self.level_nr,
+            tiles,
+            free,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Carries out a single command of a script.
+    ///
+    /// # Errors
+    /// * [EngineError::SpawningError] if the coordinates are outside the script's area or the tile is not available.
+    /// * [DataError::MissingItemDefinition] or [DataError::MissingNpcDefinition] if something unknown is spawned.
+    /// * [EngineError::InvalidItem] if the status is not applied with a potion.
+    pub fn run_script_command(
+        &mut self,
+        script: &ScriptData,
+        command: &ScriptCommand,
+    ) -> Result<(), GameError> {
+        match command {
+            ScriptCommand::Log(text) => self.log.info(LogData::Lore(text.clone())),
+            ScriptCommand::SpawnNpc { def_id, x, y } => {
+                let pos = self.script_spawn_point(script, *x, *y)?;
+                let npc = self.create_npc(def_id.clone(), pos)?;
+                self.current_level_mut().spawn_npc(npc)?;
+            }
+            ScriptCommand::SpawnHunter { def_id, x, y } => {
+                let pos = self.script_spawn_point(script, *x, *y)?;
+                let mut npc = self.create_npc(def_id.clone(), pos)?;
+                npc.ai_state = NpcAiState::Hunting;
+                self.current_level_mut().spawn_npc(npc)?;
+            }
+            ScriptCommand::SpawnItem { def_id, x, y } => {
+                let pos = self.script_spawn_point(script, *x, *y)?;
+                let item_id = self.register_item(def_id)?;
+                let item_sprite = self.create_item_sprite(item_id, pos)?;
+                self.current_level_mut().spawn_item_sprite(item_sprite)?;
+            }
+            ScriptCommand::Status { potion_def_id } => {
+                let item_def = self
+                    .get_item_def_by_id(potion_def_id)
+                    .ok_or(DataError::MissingItemDefinition(potion_def_id.clone()))?;
+                let GameItemKindDef::Potion { effect } = item_def.kind else {
+                    return Err(GameError::from(EngineError::InvalidItem(item_def.kind)));
+                };
+                self.apply_potion_effect(effect);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts coordinates of a script into a point of the current level.
+    ///
+    /// # Errors
+    /// * [EngineError::SpawningError] if the coordinates are outside the script's area or the level.
+    fn script_point(&self, script: &ScriptData, x: usize, y: usize) -> Result<Point, GameError> {
+        script
+            .to_world(x, y)
+            .filter(|pos| self.current_world().is_in_bounds(pos.x as isize, pos.y as isize))
+            .ok_or(GameError::from(EngineError::SpawningError(Point::new(x, y))))
+    }
+
+    /// Same as [GameState::script_point], but the tile also has to be free to spawn something on.
+    fn script_spawn_point(
+        &self,
+        script: &ScriptData,
+        x: usize,
+        y: usize,
+    ) -> Result<Point, GameError> {
+        let pos = self.script_point(script, x, y)?;
+        if !self.current_level().is_available(pos) {
+            return Err(GameError::from(EngineError::SpawningError(pos)));
+        }
+        Ok(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::entity_logic::Entity,
+        proc_gen::vaults::VaultTransform,
+        world::{level::Level, worldspace::Room},
+    };
+
+    fn game_with_room() -> GameState {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        game.levels.insert(0, level);
+        game
+    }
+
+    #[test]
+    fn scripts_run_inside_their_area() {
+        let mut game = game_with_room();
+
+        // Quarter turn: (1, 0) of the 3x2 area ends up at (1, 1)
+        let script = ScriptData {
+            path: String::new(),
+            origin: Point::new(40, 10),
+            transform: VaultTransform { rotation: 1, mirrored: false },
+            width: 3,
+            height: 2,
+        };
+        let source = r#"
+            log("Something stirs.");
+            if tile(1, 0) == "floor" && is_free(1, 0) {
+                spawn_npc("skeleton", 1, 0);
+            }
+            if tile(1, 0) == "wall" {
+                spawn_npc("goblin", 1, 0);
+            }
+        "#;
+        game.run_script(&script, source).unwrap();
+
+        let npcs = &game.current_level().npcs;
+        assert_eq!(npcs.len(), 1);
+        assert_eq!(npcs[0].def_id, "skeleton");
+        assert_eq!(game.current_level().get_npc_at(Point::new(41, 11)), Some(npcs[0].id()));
+
+        // Coordinates outside the area stop the script before it changes anything
+        let error = game.run_script(&script, r#"spawn_item("food_apple", 0, 0); tile(3, 0);"#);
+        assert!(matches!(error, Err(GameError::Engine(EngineError::ScriptFailed(..)))));
+        assert!(game.current_level().item_sprites.is_empty());
+    }
+
+    #[test]
+    fn handlers_wait_for_their_event() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        game.levels.insert(0, level);
+
+        let script = ScriptData {
+            path: String::new(),
+            origin: Point::new(10, 10),
+            transform: VaultTransform::default(),
+            width: 3,
+            height: 2,
+        };
+        let source = r#"
+            log("You feel watched.");
+            fn on_death(def_id) {
+                if def_id == "skeleton" {
+                    this.deaths = (this.deaths ?? 0) + 1;
+                    spawn_item("food_apple", this.deaths - 1, 0);
+                }
+            }
+            fn on_use(def_id) { spawn_hunter("goblin", 0, 1); }
+        "#;
+        game.run_script(&script, source).unwrap();
+        game.fire_script_event(&ScriptEvent::EnterLevel).unwrap();
+        game.fire_script_event(&ScriptEvent::Death("goblin".to_string())).unwrap();
+        assert!(game.current_level().item_sprites.is_empty());
+
+        // The handler remembers how many skeletons died
+        for x in [12, 13] {
+            let skeleton = game.create_npc("skeleton".into(), Point::new(x, 14)).unwrap();
+            game.current_level_mut().spawn_npc(skeleton.clone()).unwrap();
+            game.npc_died(skeleton, true).unwrap();
+        }
+        assert_eq!(game.current_level().item_sprites_at(Point::new(10, 10)).count(), 1);
+        assert_eq!(game.current_level().item_sprites_at(Point::new(11, 10)).count(), 1);
+
+        game.fire_script_event(&ScriptEvent::Use("food_apple".to_string())).unwrap();
+        let hunter = game.current_level().get_npc_at(Point::new(10, 11)).unwrap();
+        let hunter = game.current_level().get_npc(hunter).unwrap();
+        assert!(matches!(hunter.ai_state, NpcAiState::Hunting));
+
+        // A failing handler is reported
+        game.run_script(&script, "fn on_enter_level() { tile(5, 5); }").unwrap();
+        assert!(matches!(
+            game.fire_script_event(&ScriptEvent::EnterLevel),
+            Err(GameError::Engine(EngineError::ScriptFailed(..)))
+        ));
+    }
+
+    #[test]
+    fn shipped_scripts_compile() {
+        let context = Rc::new(RefCell::new(GameState::default().script_context(&ScriptData {
+            path: String::new(),
+            origin: Point::new(0, 0),
+            transform: VaultTransform::default(),
+            width: 0,
+            height: 0,
+        })));
+        let engine = script_engine(&context);
+        for entry in fs::read_dir("assets/scripts").unwrap() {
+            let path = entry.unwrap().path();
+            let source = fs::read_to_string(&path).unwrap();
+            assert!(engine.compile(source).is_ok(), "{} does not compile", path.display());
+        }
+    }
+
+    #[test]
+    fn scripts_are_limited() {
+        let mut game = game_with_room();
+        let script = ScriptData {
+            path: String::new(),
+            origin: Point::new(40, 10),
+            transform: VaultTransform::default(),
+            width: 2,
+            height: 2,
+        };
+
+        assert!(matches!(
+            game.run_script(&script, "let x = ;"),
+            Err(GameError::Data(DataError::InvalidScript(..)))
+        ));
+        assert!(matches!(
+            game.run_script(&script, "loop { }"),
+            Err(GameError::Engine(EngineError::ScriptFailed(..)))
+        ));
+        assert!(matches!(
+            game.run_script(&script, "fn deeper(n) { deeper(n + 1) } deeper(0);"),
+            Err(GameError::Engine(EngineError::ScriptFailed(..)))
+        ));
+        assert!(matches!(
+            game.run_script(&script, r#"import "assets/scripts/forgotten_shrine" as shrine;"#),
+            Err(GameError::Engine(EngineError::ScriptFailed(..)))
+        ));
+
+        // Variables, loops and functions work within the limits, failed spawns are reported
+        let source = r#"
+            fn corner(i) { i % width() }
+            for i in 0..3 { spawn_item("food_apple", corner(i), 0); }
+        "#;
+        let error = game.run_script(&script, source);
+        assert!(matches!(error, Err(GameError::Engine(EngineError::SpawningError(..)))));
+        assert_eq!(game.current_level().item_sprites.len(), 2);
+    }
+}
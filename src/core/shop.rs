@@ -0,0 +1,375 @@
+use std::{collections::HashMap, fmt};
+
+use rand::Rng;
+
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityId},
+        game::GameState,
+        game_items::{GameItemId, GameItemKindDef},
+    },
+    data::loot_tables::get_loot_table_by_id,
+    util::{
+        errors_results::{DataError, EngineError, FailReason, GameError, GameOutcome, GameResult},
+        rng::{DieSize, Roll},
+        text_log::LogData,
+    },
+};
+
+/// NPCs that run a shop.
+pub const SHOPKEEPER_NPCS: [&str; 1] = ["shopkeeper"];
+
+/// Loot table that is rolled once for the stock of a shop.
+const SHOP_STOCK_TABLE: &str = "shop_stock";
+
+/// Price increase (in percent) for every item of the same category the player bought this run.
+pub const SUPPLY_MARKUP_PERCENT: i32 = 15;
+
+/// Price reduction (in percent) per point of perception. Perception stands in for the player's charm.
+pub const PERCEPTION_DISCOUNT_PERCENT: i32 = 2;
+
+/// Price reduction (in percent) after a successful haggle.
+pub const HAGGLE_DISCOUNT_PERCENT: i32 = 20;
+
+/// Bonus of the shopkeeper in the opposed haggle check (d20 + bonus against the player's d20 + perception).
+const SHOPKEEPER_HAGGLE_BONUS: i16 = 3;
+
+/// Chance (in percent) that a failed haggle offends the shopkeeper, who then refuses to trade.
+const OFFEND_CHANCE: u32 = 25;
+
+/// Prices can never be reduced by more than this (in percent).
+const MAX_DISCOUNT_PERCENT: i32 = 90;
+
+/// Category of an item. Shops raise their prices per category when the player buys a lot of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ItemCategory {
+    Weapon,
+    Armor,
+    Food,
+    Potion,
+    Misc,
+}
+
+impl From<&GameItemKindDef> for ItemCategory {
+    fn from(kind: &GameItemKindDef) -> Self {
+        match kind {
+            GameItemKindDef::Weapon { .. } => ItemCategory::Weapon,
+            GameItemKindDef::Armor { .. } => ItemCategory::Armor,
+            GameItemKindDef::Food { .. } => ItemCategory::Food,
+            GameItemKindDef::Potion { .. } => ItemCategory::Potion,
+            GameItemKindDef::FireStarter
+            | GameItemKindDef::JokeBook
+            | GameItemKindDef::RemoveCurse
+            | GameItemKindDef::Trinket
+            | GameItemKindDef::Bag { .. } => ItemCategory::Misc,
+        }
+    }
+}
+
+/// Outcome of haggling with a shopkeeper. The player can haggle once per shop.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Haggle {
+    #[default]
+    NotTried,
+
+    /// The shopkeeper gives a discount of [HAGGLE_DISCOUNT_PERCENT].
+    Succeeded,
+
+    /// The shopkeeper did not budge.
+    Failed,
+}
+
+/// Stock and mood of a shopkeeper.
+#[derive(Clone, Debug, Default)]
+pub struct Shop {
+    /// Items for sale. They are registered, but neither in the world nor in the inventory.
+    pub stock: Vec<GameItemId>,
+    pub haggle: Haggle,
+    /// Offended shopkeepers refuse to trade with the player.
+    pub offended: bool,
+}
+
+/// The shops of the run and what the player bought in them.
+#[derive(Clone, Debug, Default)]
+pub struct ShopRegister {
+    /// Shops by the id of their shopkeeper. Shops are only stocked once the player trades with them.
+    pub shops: HashMap<EntityId, Shop>,
+    /// Number of items the player bought this run, per category.
+    pub purchases: HashMap<ItemCategory, u32>,
+}
+
+/// The price of an item and how it came together.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriceBreakdown {
+    /// Value of the item (see [GameItemDef::value](crate::data::item_defs::GameItemDef::value)).
+    pub base: u32,
+    /// Adjustments in percent with a short label.
+    pub modifiers: Vec<(&'static str, i32)>,
+    /// Final price in gold. At least 1.
+    pub total: u32,
+}
+
+impl PriceBreakdown {
+    fn new(base: u32, modifiers: Vec<(&'static str, i32)>) -> Self {
+        let percent = 100
+            + modifiers.iter().map(|(_, percent)| percent).sum::<i32>().max(-MAX_DISCOUNT_PERCENT);
+        let total = (base as u64 * percent as u64 / 100).max(1) as u32;
+
+        Self { base, modifiers, total }
+    }
+}
+
+impl fmt::Display for PriceBreakdown {
+    /// E.g. `18g (base 15, supply +30%, perception -6%)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}g (base {}", self.total, self.base)?;
+        for (label, percent) in &self.modifiers {
+            write!(f, ", {} {:+}%", label, percent)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl GameState {
+    /// Gets the shop of the shopkeeper and stocks it if the player trades with it for the first time.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the shopkeeper is not in the current level.
+    /// * [DataError::MissingLootTable] if the stock table is not defined.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::InvalidTarget] if the NPC does not run a shop.
+    /// * [GameOutcome::Fail] with [FailReason::OutOfRange] if the shopkeeper is not adjacent to the player.
+    /// * [GameOutcome::Fail] with [FailReason::ShopkeeperOffended] if the shopkeeper refuses to trade.
+    /// * [GameOutcome::Success] if the shop is open.
+    pub fn stock_shop(&mut self, npc_id: EntityId) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        if !SHOPKEEPER_NPCS.contains(&npc.def_id.as_str()) {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id)));
+        }
+        if npc.pos().distance_squared_from(self.player.character.pos()) != 1 {
+            return Ok(GameOutcome::Fail(FailReason::OutOfRange));
+        }
+
+        if let Some(shop) = self.shops.shops.get(&npc_id) {
+            if shop.offended {
+                return Ok(GameOutcome::Fail(FailReason::ShopkeeperOffended));
+            }
+            return Ok(GameOutcome::Success);
+        }
+
+        let loot_table = get_loot_table_by_id(SHOP_STOCK_TABLE)
+            .ok_or(DataError::MissingLootTable(SHOP_STOCK_TABLE.to_string()))?;
+
+        let mut stock = Vec::new();
+        for def_id in loot_table.roll(self.level_nr, &mut self.rng) {
+            stock.push(self.register_item(&def_id.to_string())?);
+        }
+
+        self.shops.shops.insert(npc_id, Shop { stock, ..Shop::default() });
+        Ok(GameOutcome::Success)
+    }
+
+    /// Computes the price of an item in the shop of the given shopkeeper.
+    ///
+    /// The value of the item is adjusted by
+    /// * [SUPPLY_MARKUP_PERCENT] for each item of the same category the player bought this run,
+    /// * [PERCEPTION_DISCOUNT_PERCENT] per point of perception,
+    /// * [HAGGLE_DISCOUNT_PERCENT] if the player haggled successfully with this shopkeeper.
+    ///
+    /// # Errors
+    /// * [EngineError::UnregisteredItem] if the item is not registered.
+    /// * [DataError::MissingItemDefinition] if the item has no definition.
+    pub fn item_price(
+        &self,
+        npc_id: EntityId,
+        item_id: GameItemId,
+    ) -> Result<PriceBreakdown, GameError> {
+        let item = self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
+        let item_def = self
+            .get_item_def_by_id(&item.def_id)
+            .ok_or(DataError::MissingItemDefinition(item.def_id))?;
+
+        let mut modifiers = Vec::new();
+
+        let bought =
+            self.shops.purchases.get(&ItemCategory::from(&item_def.kind)).copied().unwrap_or(0);
+        if bought > 0 {
+            modifiers.push(("supply", bought as i32 * SUPPLY_MARKUP_PERCENT));
+        }
+
+        let perception = self.player.character.stats.perception as i32;
+        if perception > 0 {
+            modifiers.push(("perception", -perception * PERCEPTION_DISCOUNT_PERCENT));
+        }
+
+        if self.shops.shops.get(&npc_id).is_some_and(|shop| shop.haggle == Haggle::Succeeded) {
+            modifiers.push(("haggled", -HAGGLE_DISCOUNT_PERCENT));
+        }
+
+        Ok(PriceBreakdown::new(item_def.value, modifiers))
+    }
+
+    /// The player buys an item from the shop of an adjacent shopkeeper.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the shopkeeper is not in the current level.
+    /// * [EngineError::UnregisteredItem] if the item is not registered.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::InvalidTarget] if the item is not for sale in this shop.
+    /// * [GameOutcome::Fail] with [FailReason::ShopkeeperOffended] if the shopkeeper refuses to trade.
+    /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player cannot carry the item.
+    /// * [GameOutcome::Fail] with [FailReason::NotEnoughGold] if the player cannot afford the item.
+    /// * [GameOutcome::Success] if the item was bought.
+    pub fn buy_item(&mut self, npc_id: EntityId, item_id: GameItemId) -> GameResult {
+        if let GameOutcome::Fail(reason) = self.stock_shop(npc_id)? {
+            return Ok(GameOutcome::Fail(reason));
+        }
+
+        let Some(index) = self
+            .shops
+            .shops
+            .get(&npc_id)
+            .and_then(|shop| shop.stock.iter().position(|id| *id == item_id))
+        else {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id)));
+        };
+
+        if self.player.character.inventory.is_full() {
+            return Ok(GameOutcome::Fail(FailReason::InventoryFull));
+        }
+
+        let price = self.item_price(npc_id, item_id)?.total;
+        if self.player.character.gold < price {
+            return Ok(GameOutcome::Fail(FailReason::NotEnoughGold { price }));
+        }
+
+        let item = self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
+        let item_def = self
+            .get_item_def_by_id(&item.def_id)
+            .ok_or(DataError::MissingItemDefinition(item.def_id))?;
+
+        if let Some(shop) = self.shops.shops.get_mut(&npc_id) {
+            shop.stock.remove(index);
+        }
+        self.player.character.gold -= price;
+        *self.shops.purchases.entry(ItemCategory::from(&item_def.kind)).or_default() += 1;
+        self.add_item_to_inv(item_id)?;
+
+        self.log.info(LogData::ItemBought { item_name: item_def.name.to_string(), price });
+        Ok(GameOutcome::Success)
+    }
+
+    /// The player haggles with an adjacent shopkeeper.
+    ///
+    /// This is an opposed check: The player rolls d20 + perception, the shopkeeper rolls d20 + [SHOPKEEPER_HAGGLE_BONUS].
+    /// If the player rolls higher, all prices of this shop drop by [HAGGLE_DISCOUNT_PERCENT].
+    /// Otherwise, the shopkeeper is offended with a chance of [OFFEND_CHANCE] percent and stops trading with the player.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the shopkeeper is not in the current level.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::ShopkeeperOffended] if the shopkeeper refuses to trade.
+    /// * [GameOutcome::Fail] with [FailReason::AlreadyHaggled] if the player already haggled with this shopkeeper.
+    /// * [GameOutcome::Success] if the player haggled (successful or not).
+    pub fn haggle(&mut self, npc_id: EntityId) -> GameResult {
+        if let GameOutcome::Fail(reason) = self.stock_shop(npc_id)? {
+            return Ok(GameOutcome::Fail(reason));
+        }
+
+        if self.shops.shops.get(&npc_id).is_some_and(|shop| shop.haggle != Haggle::NotTried) {
+            return Ok(GameOutcome::Fail(FailReason::AlreadyHaggled));
+        }
+
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        let npc_name = npc.name().to_string();
+
+        let perception = self.player.character.stats.perception as i16;
+        let player_roll = self.roll(&Roll::new(1, DieSize::D20).add_modifier(perception));
+        let shopkeeper_roll =
+            self.roll(&Roll::new(1, DieSize::D20).add_modifier(SHOPKEEPER_HAGGLE_BONUS));
+
+        let haggle = if player_roll > shopkeeper_roll { Haggle::Succeeded } else { Haggle::Failed };
+        let offended = haggle == Haggle::Failed && self.rng.random_range(0..100) < OFFEND_CHANCE;
+
+        if let Some(shop) = self.shops.shops.get_mut(&npc_id) {
+            shop.haggle = haggle;
+            shop.offended = offended;
+        }
+
+        if offended {
+            self.log.info(LogData::ShopkeeperOffended { npc_name });
+        } else if haggle == Haggle::Succeeded {
+            self.log.info(LogData::HaggleSucceeded { npc_name, discount: HAGGLE_DISCOUNT_PERCENT });
+        } else {
+            self.log.info(LogData::HaggleFailed { npc_name });
+        }
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// Lists the items of a stocked shop with their prices.
+    ///
+    /// # Returns
+    /// An empty list if the shop has not been stocked yet (see [GameState::stock_shop]).
+    pub fn shop_offers(&self, npc_id: EntityId) -> Vec<(GameItemId, String)> {
+        let Some(shop) = self.shops.shops.get(&npc_id) else {
+            return Vec::new();
+        };
+
+        shop.stock
+            .iter()
+            .filter_map(|item_id| {
+                let item = self.get_item_by_id(*item_id)?;
+                let item_def = self.get_item_def_by_id(&item.def_id)?;
+                let price = self.item_price(npc_id, *item_id).ok()?;
+                Some((*item_id, format!("{} - {}", item_def.name, price)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::entity_logic::Movable,
+        world::{coordinate_system::Point, level::Level, worldspace::Room},
+    };
+
+    #[test]
+    fn prices_follow_supply_and_perception() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        let shopkeeper = game.create_npc("shopkeeper".into(), Point::new(50, 10)).unwrap();
+        let shopkeeper_id = shopkeeper.id();
+        level.spawn_npc(shopkeeper).unwrap();
+        game.levels.insert(0, level);
+        game.player.character.move_to(Point::new(49, 10));
+
+        game.stock_shop(shopkeeper_id).unwrap();
+        let stock = game.shops.shops[&shopkeeper_id].stock.clone();
+        let (first, second) = (stock[0], stock[1]); // guaranteed healing potions
+
+        // Value 15, perception 1 gives -2%
+        assert_eq!(game.item_price(shopkeeper_id, first).unwrap().total, 14);
+
+        assert!(matches!(
+            game.buy_item(shopkeeper_id, first),
+            Ok(GameOutcome::Fail(FailReason::NotEnoughGold { price: 14 }))
+        ));
+
+        game.player.character.gold = 100;
+        assert!(matches!(game.buy_item(shopkeeper_id, first), Ok(GameOutcome::Success)));
+        assert_eq!(game.player.character.gold, 86);
+        assert!(game.player.character.inventory.contains(&first));
+
+        let price = game.item_price(shopkeeper_id, second).unwrap();
+        assert_eq!(price.modifiers, vec![("supply", 15), ("perception", -2)]);
+        assert_eq!(price.total, 16);
+        assert_eq!(price.to_string(), "16g (base 15, supply +15%, perception -2%)");
+    }
+}
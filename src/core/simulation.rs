@@ -0,0 +1,204 @@
+use rand::{SeedableRng, rngs::StdRng};
+
+use crate::{
+    core::{
+        entity_logic::Entity,
+        game::GameState,
+        player_actions::PlayerInput,
+        shop::SHOPKEEPER_NPCS,
+        town::{HEALER_NPCS, Stash},
+    },
+    data::npc_defs::npc_defs,
+    world::{coordinate_system::Point, level::Level, worldspace::Room},
+};
+
+/// Number of attacks each monster makes when measuring its damage with [monster_balance].
+pub const BALANCE_TRIALS: u32 = 1000;
+
+/// Drives a [GameState] without a terminal: Inputs are fed in directly and the state can be inspected after every turn.
+///
+/// Used by tests of whole turns (combat, AI, levels) and by balancing tools (see [monster_balance]).
+/// The stash of a simulated run has no file, so simulations never change the player's actual stash.
+pub struct Simulation {
+    pub game: GameState,
+}
+
+/// What happened during a turn of a [Simulation].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TurnReport {
+    /// Whether the input resolved into an action and the round advanced.
+    pub acted: bool,
+
+    /// Damage the player took during the turn.
+    pub damage_taken: u32,
+
+    /// Monsters the player killed during the turn.
+    pub kills: u32,
+}
+
+impl Simulation {
+    /// Starts a new run from the given seed.
+    pub fn new(seed: u64) -> Self {
+        let mut game = GameState::with_seed(seed);
+        game.stash = Stash::default();
+        Self { game }
+    }
+
+    /// Plays one turn with the given input.
+    pub fn step(&mut self, input: PlayerInput) -> TurnReport {
+        let round_nr = self.game.round_nr;
+        let damage_taken = self.game.run_stats.damage_taken;
+        let kills = self.game.run_stats.total_kills();
+
+        self.game.resolve_player_action(input);
+
+        TurnReport {
+            acted: self.game.round_nr > round_nr,
+            damage_taken: self.game.run_stats.damage_taken - damage_taken,
+            kills: self.game.run_stats.total_kills() - kills,
+        }
+    }
+
+    /// Plays the given inputs one after another, until they run out or the run is over.
+    ///
+    /// # Returns
+    /// * The reports of all turns that were played.
+    pub fn run(&mut self, inputs: impl IntoIterator<Item = PlayerInput>) -> Vec<TurnReport> {
+        let mut reports = Vec::new();
+        for input in inputs {
+            if self.is_over() {
+                break;
+            }
+            reports.push(self.step(input));
+        }
+        reports
+    }
+
+    /// Whether the player died.
+    pub fn is_over(&self) -> bool {
+        !self.game.player_is_alive()
+    }
+
+    pub fn player_pos(&self) -> Point {
+        self.game.player.character.pos()
+    }
+
+    pub fn player_hp(&self) -> u16 {
+        self.game.player.character.stats.base.hp_current
+    }
+
+    /// Lists the NPCs on the current level by definition and position.
+    pub fn npcs(&self) -> Vec<(String, Point)> {
+        self.game.current_level().npcs.iter().map(|npc| (npc.def_id.clone(), npc.pos())).collect()
+    }
+}
+
+/// Damage a monster deals to a fresh player character, measured by [monster_balance].
+#[derive(Clone, Debug)]
+pub struct MonsterBalance {
+    pub def_id: String,
+    pub name: &'static str,
+
+    /// Average damage of an attack, misses included.
+    pub average_damage: f64,
+
+    /// Share of attacks that dealt damage.
+    pub hit_rate: f64,
+}
+
+/// Lets every monster attack a fresh player character [BALANCE_TRIALS] times on a level of the given depth and measures the damage.
+///
+/// Shopkeepers and healers don't fight, so they are left out. The results are sorted by average damage, weakest first.
+pub fn monster_balance(depth: usize, seed: u64) -> Vec<MonsterBalance> {
+    let mut def_ids: Vec<&String> = npc_defs()
+        .keys()
+        .filter(|def_id| !SHOPKEEPER_NPCS.contains(&def_id.as_str()))
+        .filter(|def_id| !HEALER_NPCS.contains(&def_id.as_str()))
+        .collect();
+    def_ids.sort(); // HashMaps are random, so the monsters are sorted to keep the results of a seed the same.
+
+    let mut results: Vec<MonsterBalance> =
+        def_ids.into_iter().map(|def_id| measure_monster(def_id, depth, seed)).collect();
+    results.sort_by(|a, b| a.average_damage.total_cmp(&b.average_damage));
+    results
+}
+
+/// Measures the damage of a single monster in an empty arena.
+fn measure_monster(def_id: &str, depth: usize, seed: u64) -> MonsterBalance {
+    let mut game = GameState { rng: StdRng::seed_from_u64(seed), ..GameState::default() };
+
+    let mut arena = Level::new();
+    arena.world.carve_room(&Room::new(Point::new(5, 5), 10, 10));
+    game.levels = vec![Level::new(); depth];
+    game.levels.push(arena);
+    game.level_nr = depth;
+    game.player.character.base.pos = Point::new(8, 8);
+
+    let npc = game.create_npc(def_id.to_string(), Point::new(9, 8)).expect("Monster is defined");
+    let npc_id = npc.id();
+    let name = npc_defs()[def_id].name;
+    game.current_level_mut().spawn_npc(npc).expect("Arena is empty");
+
+    let mut hits = 0;
+    for _ in 0..BALANCE_TRIALS {
+        let damage_taken = game.run_stats.damage_taken;
+        game.npc_attack_player(npc_id).expect("Monster was spawned");
+        if game.run_stats.damage_taken > damage_taken {
+            hits += 1;
+        }
+    }
+
+    MonsterBalance {
+        def_id: def_id.to_string(),
+        name,
+        average_damage: game.run_stats.damage_taken as f64 / BALANCE_TRIALS as f64,
+        hit_rate: hits as f64 / BALANCE_TRIALS as f64,
+    }
+}
+
+/// Formats the results of [monster_balance] as a table for printing.
+pub fn balance_table(depth: usize, results: &[MonsterBalance]) -> String {
+    let mut lines = vec![
+        format!("Monster damage on floor {} ({} attacks each)", depth, BALANCE_TRIALS),
+        format!("{:<24} {:>10} {:>10}", "Monster", "Damage", "Hit rate"),
+    ];
+    lines.extend(results.iter().map(|result| {
+        format!(
+            "{:<24} {:>10.2} {:>9.0}%",
+            result.name,
+            result.average_damage,
+            result.hit_rate * 100.0
+        )
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::coordinate_system::Direction;
+
+    #[test]
+    fn simulated_turns_can_be_inspected() {
+        let mut simulation = Simulation::new(1234);
+        let start = simulation.player_pos();
+
+        let reports = simulation.run([PlayerInput::Wait, PlayerInput::Wait]);
+        assert!(reports.iter().all(|report| report.acted));
+        assert_eq!(simulation.game.round_nr, 2);
+        assert_eq!(simulation.player_pos(), start);
+        assert_eq!(simulation.player_hp(), simulation.game.player.character.stats.base.hp_max);
+        assert!(simulation.npcs().iter().all(|(def_id, _)| npc_defs().contains_key(def_id)));
+
+        simulation
+            .run([Direction::Right, Direction::Down, Direction::Left].map(PlayerInput::Direction));
+        assert!(!simulation.is_over());
+
+        let results = monster_balance(4, 73);
+        let damage = |def_id: &str| {
+            results.iter().find(|result| result.def_id == def_id).unwrap().average_damage
+        };
+        assert!(results.iter().all(|result| (0.0..=1.0).contains(&result.hit_rate)));
+        assert!(damage("borrowchecker") > damage("goblin"));
+    }
+}
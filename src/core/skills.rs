@@ -0,0 +1,174 @@
+use std::fmt;
+
+use crate::{
+    core::{game::GameState, player::PlayerCharacter},
+    util::{rng::Check, text_log::LogData},
+};
+
+/// Highest rank a skill can reach.
+pub const MAX_SKILL_RANK: u8 = 10;
+
+/// Practice needed for the first rank of a skill. Each rank after needs this much more than the one before.
+pub const SKILL_PRACTICE_STEP: u16 = 10;
+
+/// Difficulty of the stealth check the player rolls when an NPC is about to spot them.
+pub const STEALTH_DIFFICULTY: i16 = 22;
+
+/// Hit points each rank of alchemy adds to healing potions.
+pub const ALCHEMY_HEALING_PER_RANK: u16 = 2;
+
+/// Proficiencies of the player character that improve by using them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Skill {
+    /// Adds to the damage of melee attacks. Trained by attacking in melee.
+    Melee,
+    /// Adds to the damage of ranged attacks. Trained by attacking from range.
+    Archery,
+    /// Adds to the check against being spotted by monsters. Trained by attacking monsters that have not noticed the player.
+    Stealth,
+    /// Adds to the healing of potions. Trained by drinking potions.
+    Alchemy,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 4] = [Skill::Melee, Skill::Archery, Skill::Stealth, Skill::Alchemy];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Skill::Melee => "Melee",
+            Skill::Archery => "Archery",
+            Skill::Stealth => "Stealth",
+            Skill::Alchemy => "Alchemy",
+        }
+    }
+}
+
+impl fmt::Display for Skill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Rank and practice of a single skill.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SkillProgress {
+    pub rank: u8,
+    /// Practice gathered towards the next rank.
+    pub practice: u16,
+}
+
+impl SkillProgress {
+    /// Practice needed to reach the next rank. Grows with every rank, so skills improve slower the better they are.
+    pub fn practice_needed(&self) -> u16 {
+        (self.rank as u16 + 1) * SKILL_PRACTICE_STEP
+    }
+}
+
+/// The skills of the player character, indexed by [Skill].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Skills {
+    progress: [SkillProgress; Skill::ALL.len()],
+}
+
+impl Skills {
+    pub fn get(&self, skill: Skill) -> SkillProgress {
+        self.progress[skill as usize]
+    }
+
+    pub fn rank(&self, skill: Skill) -> u8 {
+        self.get(skill).rank
+    }
+
+    /// Adds practice to the skill, which rises in rank once it has enough. Ranks are capped by the character level (see [rank_cap]).
+    ///
+    /// # Returns
+    /// * The new rank if the skill improved.
+    pub fn train(&mut self, skill: Skill, amount: u16, level: u8) -> Option<u8> {
+        let progress = &mut self.progress[skill as usize];
+        if progress.rank >= rank_cap(level) {
+            return None;
+        }
+
+        progress.practice += amount;
+        if progress.practice < progress.practice_needed() {
+            return None;
+        }
+        progress.practice -= progress.practice_needed();
+        progress.rank += 1;
+        Some(progress.rank)
+    }
+}
+
+/// Highest rank skills can reach at the character level. Each level allows one more rank, up to [MAX_SKILL_RANK].
+pub fn rank_cap(level: u8) -> u8 {
+    level.min(MAX_SKILL_RANK)
+}
+
+impl PlayerCharacter {
+    /// Damage the melee or archery skill adds to the character's attacks.
+    pub fn skill_damage_bonus(&self, ranged: bool) -> i16 {
+        let skill = if ranged { Skill::Archery } else { Skill::Melee };
+        self.skills.rank(skill) as i16
+    }
+
+    /// Healing potions heal [ALCHEMY_HEALING_PER_RANK] more hit points for each rank of alchemy.
+    pub fn alchemy_healing(&self, amount: u16) -> u16 {
+        amount + self.skills.rank(Skill::Alchemy) as u16 * ALCHEMY_HEALING_PER_RANK
+    }
+}
+
+impl GameState {
+    /// Practices the skill once and tells the player if it improved.
+    pub fn train_skill(&mut self, skill: Skill) {
+        let level = self.player.character.stats.level;
+        if let Some(rank) = self.player.character.skills.train(skill, 1, level) {
+            self.log.info(LogData::SkillImproved { skill_name: skill.to_string(), rank });
+        }
+    }
+
+    /// The player tries to stay unnoticed by an NPC that is about to spot them.
+    /// Only rolled once the player has a rank in stealth, without one they are always spotted.
+    ///
+    /// # Returns
+    /// * `true` if the player stays hidden.
+    pub fn stealth_check(&mut self) -> bool {
+        let stealth = self.player.character.skills.rank(Skill::Stealth) as i16;
+        if stealth == 0 {
+            return false;
+        }
+
+        let check = Check::default().add_modifier(stealth).set_difficulty(STEALTH_DIFFICULTY);
+        self.check(&check).is_success()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skills_improve_with_practice_up_to_the_level_cap() {
+        let mut game = GameState::default();
+        let damage_before = game.player_attack_modifiers(false).damage;
+        let ranged_before = game.player_attack_modifiers(true).damage;
+
+        for _ in 0..SKILL_PRACTICE_STEP {
+            game.train_skill(Skill::Melee);
+        }
+        assert_eq!(game.player.character.skills.rank(Skill::Melee), 1);
+        assert_eq!(game.player_attack_modifiers(false).damage, damage_before + 1);
+        assert_eq!(game.player_attack_modifiers(true).damage, ranged_before);
+
+        // At level 1, rank 1 is as far as the skill goes
+        for _ in 0..100 {
+            game.train_skill(Skill::Melee);
+        }
+        assert_eq!(game.player.character.skills.get(Skill::Melee).rank, 1);
+        assert_eq!(game.player.character.skills.get(Skill::Melee).practice, 0);
+
+        // Higher ranks need more practice
+        let skills = &mut game.player.character.skills;
+        assert_eq!(skills.train(Skill::Melee, SKILL_PRACTICE_STEP, 3), None);
+        assert_eq!(skills.train(Skill::Melee, SKILL_PRACTICE_STEP, 3), Some(2));
+    }
+}
@@ -0,0 +1,270 @@
+use std::mem;
+
+use rand::Rng;
+
+use crate::{
+    ai::npc_ai::NpcAiState,
+    core::{
+        entity_logic::{Disposition, Entity, EntityId, Movable, Npc},
+        game::GameState,
+    },
+    util::text_log::LogData,
+    world::coordinate_system::{Footprint, Point},
+};
+
+/// Animals that can be tamed and the food they like.
+pub const TAMEABLE_NPCS: [(&str, &[&str]); 2] =
+    [("wolf", &["food_meat_raw", "food_meat"]), ("giant_rat", &["food_cheese", "food_bread"])];
+
+/// Chance (in percent) that an animal becomes a companion when it eats food it likes.
+pub const TAME_CHANCE: u32 = 40;
+
+/// Distance in which animals smell food they like.
+pub const FOOD_SMELL_RADIUS: usize = 5;
+
+/// Distance from the player in which companions are placed when they follow the player to another level.
+const FOLLOW_RADIUS: isize = 3;
+
+/// Gets the food an NPC likes. Only tameable animals like food.
+pub fn liked_foods(npc_def_id: &str) -> &'static [&'static str] {
+    TAMEABLE_NPCS
+        .iter()
+        .find(|(def_id, _)| *def_id == npc_def_id)
+        .map(|(_, foods)| *foods)
+        .unwrap_or(&[])
+}
+
+impl GameState {
+    /// Checks whether the player already has a companion on any level. The player can only have one pet at a time.
+    pub fn has_companion(&self) -> bool {
+        self.levels
+            .iter()
+            .flat_map(|level| &level.npcs)
+            .any(|npc| npc.disposition == Disposition::Companion)
+    }
+
+    /// Removes the companions from the current level, so they can follow the player to another one (see [GameState::place_companions]).
+    pub fn take_companions(&mut self) -> Vec<Npc> {
+        let Some(level) = self.levels.get_mut(self.level_nr) else {
+            return Vec::new();
+        };
+        let companion_ids: Vec<EntityId> = level
+            .npcs
+            .iter()
+            .filter(|npc| npc.disposition == Disposition::Companion)
+            .map(|npc| npc.id())
+            .collect();
+
+        let mut companions = Vec::new();
+        for npc_id in companion_ids {
+            if let Some(npc) = level.get_npc(npc_id).cloned() {
+                level.despawn(npc_id);
+                companions.push(npc);
+            }
+        }
+        companions
+    }
+
+    /// Places companions that followed the player on the current level, on the free tiles closest to the player.
+    ///
+    /// If there is no room within [FOLLOW_RADIUS], a companion lands on a random free tile of the level instead.
+    pub fn place_companions(&mut self, companions: Vec<Npc>) {
+        let player_pos = self.player.character.pos();
+
+        for mut npc in companions {
+            let level = self.current_level();
+            let mut points = level.world.get_points_in_radius(player_pos, FOLLOW_RADIUS);
+            points.sort_by_key(|point| (point.distance_squared_from(player_pos), point.y, point.x));
+            let point = points
+                .into_iter()
+                .find(|point| {
+                    *point != player_pos
+                        && level.is_footprint_available(Footprint::new(*point, npc.size))
+                })
+                .unwrap_or_else(|| self.random_landing_point());
+
+            npc.move_to(point);
+            let npc_name = npc.name().to_string();
+            if let Err(error) = self.current_level_mut().spawn_npc(npc) {
+                self.log.debug_warn(format!("{} could not follow the player: {}", npc_name, error));
+            }
+        }
+        self.sync_entity_registry();
+    }
+
+    /// Finds the closest food on the ground within [FOOD_SMELL_RADIUS] that the NPC likes. Only neutral animals care about food.
+    ///
+    /// # Returns
+    /// The id and position of the item sprite of the food.
+    pub fn smelled_food(&self, npc_id: EntityId) -> Option<(EntityId, Point)> {
+        let npc = self.current_level().get_npc(npc_id)?;
+        if npc.disposition != Disposition::Neutral {
+            return None;
+        }
+
+        let foods = liked_foods(&npc.def_id);
+        let npc_pos = npc.pos();
+
+        self.current_level()
+            .item_sprites
+            .iter()
+            .filter(|sprite| {
+                sprite.pos().distance_squared_from(npc_pos) <= FOOD_SMELL_RADIUS * FOOD_SMELL_RADIUS
+                    && self
+                        .get_item_by_id(sprite.item_id)
+                        .is_some_and(|item| foods.contains(&item.def_id.as_str()))
+            })
+            .min_by_key(|sprite| (sprite.pos().distance_squared_from(npc_pos), sprite.id()))
+            .map(|sprite| (sprite.id(), sprite.pos()))
+    }
+
+    /// Neutral animals eat food they like that lies next to them (diagonals included). Called once per round.
+    ///
+    /// Eating consumes the food. With a chance of [TAME_CHANCE] percent, the animal becomes the player's companion, unless the player already has one.
+    pub fn feed_animals(&mut self) {
+        let mut npc_ids = mem::take(&mut self.scratch.npc_ids);
+        npc_ids.clear();
+        npc_ids.extend(self.current_level().npcs.iter().map(|npc| npc.id()));
+
+        for &npc_id in &npc_ids {
+            let Some(npc) = self.current_level().get_npc(npc_id) else {
+                continue;
+            };
+            let (npc_name, npc_pos) = (npc.name(), npc.pos());
+
+            let Some((sprite_id, food_pos)) = self.smelled_food(npc_id) else {
+                continue;
+            };
+            if food_pos.distance_squared_from(npc_pos) > 2 {
+                continue;
+            }
+            let npc_name = npc_name.to_string();
+
+            let Some(sprite) = self.current_level().get_item_sprite(sprite_id) else {
+                continue;
+            };
+            let (item_id, item_name) = (sprite.item_id, sprite.name().to_string());
+
+            self.current_level_mut().despawn(sprite_id);
+            let _ = self.deregister_item(item_id);
+            self.log.info(LogData::AnimalEats { npc_name: npc_name.clone(), item_name });
+
+            if !self.has_companion() && self.rng.random_range(0..100) < TAME_CHANCE {
+                self.tame_npc(npc_id);
+            }
+        }
+        self.scratch.npc_ids = npc_ids;
+    }
+
+    /// Turns the NPC into the player's companion.
+    fn tame_npc(&mut self, npc_id: EntityId) {
+        let Some(npc) = self.current_level_mut().get_npc_mut(npc_id) else {
+            return;
+        };
+
+        npc.disposition = Disposition::Companion;
+        npc.ai_state = NpcAiState::Companion;
+        npc.pack = None; // Tamed animals leave their pack behind
+        let npc_name = npc.name().to_string();
+
+        self.log.info(LogData::AnimalTamed { npc_name: npc_name.clone() });
+        self.write_journal(&format!("A {} ate from my hand and now follows me around.", npc_name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::npc_ai::NpcActionKind,
+        world::{
+            level::{Level, LevelEntrance},
+            worldspace::Room,
+        },
+    };
+
+    #[test]
+    fn animals_eat_liked_food_and_only_one_becomes_a_pet() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        game.levels.insert(0, level);
+
+        let mut wolves = Vec::new();
+        for x in [40, 50] {
+            let wolf = game.create_npc("wolf".into(), Point::new(x, 10)).unwrap();
+            wolves.push(wolf.id());
+            game.current_level_mut().spawn_npc(wolf).unwrap();
+        }
+
+        // Cheese is for rats, wolves don't care
+        let cheese = game.register_item(&"food_cheese".to_string()).unwrap();
+        let sprite = game.create_item_sprite(cheese, Point::new(41, 10)).unwrap();
+        game.current_level_mut().spawn_item_sprite(sprite).unwrap();
+        assert_eq!(game.smelled_food(wolves[0]), None);
+
+        // Keep feeding both wolves until one of them is tamed
+        for _ in 0..50 {
+            for wolf_id in &wolves {
+                let pos = game.current_level().get_npc(*wolf_id).unwrap().pos();
+                let meat = game.register_item(&"food_meat_raw".to_string()).unwrap();
+                let sprite = game.create_item_sprite(meat, Point::new(pos.x, pos.y + 1)).unwrap();
+                let _ = game.current_level_mut().spawn_item_sprite(sprite);
+            }
+            game.feed_animals();
+        }
+
+        let companions: Vec<EntityId> = wolves
+            .iter()
+            .copied()
+            .filter(|id| {
+                game.current_level().get_npc(*id).unwrap().disposition == Disposition::Companion
+            })
+            .collect();
+        assert_eq!(companions.len(), 1);
+        assert!(game.get_item_by_id(cheese).is_some());
+
+        // The pet attacks monsters next to it
+        let pet_pos = game.current_level().get_npc(companions[0]).unwrap().pos();
+        let goblin =
+            game.create_npc("goblin".into(), Point::new(pet_pos.x, pet_pos.y - 1)).unwrap();
+        let goblin_id = goblin.id();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+        assert!(matches!(
+            game.companion_choose_action(companions[0]),
+            Ok(NpcActionKind::AttackNpc(target)) if target == goblin_id
+        ));
+    }
+
+    #[test]
+    fn companions_follow_the_player_between_levels() {
+        let mut game = GameState::default();
+        game.goto_level(1, LevelEntrance::Entry).unwrap();
+        let player_pos = game.player.character.pos();
+        let wolf_pos = game
+            .current_level()
+            .world
+            .get_points_in_radius(player_pos, 4)
+            .into_iter()
+            .find(|point| *point != player_pos && game.current_level().is_available(*point))
+            .unwrap();
+        let wolf = game.create_npc("wolf".into(), wolf_pos).unwrap();
+        let wolf_id = wolf.id();
+        game.current_level_mut().spawn_npc(wolf).unwrap();
+        game.tame_npc(wolf_id);
+
+        game.goto_level(2, LevelEntrance::Entry).unwrap();
+        assert!(game.levels[1].get_npc(wolf_id).is_none());
+        let wolf = game.current_level().get_npc(wolf_id).unwrap();
+        assert_eq!(wolf.disposition, Disposition::Companion);
+        assert!(wolf.pos().distance_squared_from(game.player.character.pos()) <= 9);
+        assert_eq!(game.find_npc(wolf_id).map(|(level_nr, _)| level_nr), Some(2));
+
+        // A pet left behind on another level still counts
+        game.current_level_mut().despawn(wolf_id);
+        let mut left_behind = game.create_npc("wolf".into(), wolf_pos).unwrap();
+        left_behind.disposition = Disposition::Companion;
+        game.levels[1].spawn_npc(left_behind).unwrap();
+        assert!(game.has_companion());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityId},
+        game::GameState,
+        run_stats::DamageSource,
+    },
+    util::{
+        errors_results::{EngineError, GameError, GameOutcome, GameResult},
+        rng::{Check, DieSize, Roll},
+        text_log::LogData,
+    },
+    world::{coordinate_system::Point, level::LevelEntrance, tiles::TileType},
+};
+
+/// Difficulty of the swim check (d20 + dexterity) to enter deep water.
+pub const SWIM_DIFFICULTY: i16 = 6;
+
+/// Damage the player takes for each round spent standing in lava.
+pub const LAVA_DAMAGE: u16 = 8;
+
+/// Damage the player takes when falling into a chasm.
+pub const CHASM_FALL_DAMAGE: Roll = Roll::new(2, DieSize::D6);
+
+/// Damage a spike trap deals to whoever steps on it.
+pub const SPIKE_TRAP_DAMAGE: Roll = Roll::new(2, DieSize::D4);
+
+impl GameState {
+    /// The player tries to swim into deep water.
+    ///
+    /// # Returns
+    /// * `true` if the swim check succeeded and the player can enter the water.
+    /// * `false` if the player flounders and stays in place.
+    pub fn swim_check(&mut self) -> bool {
+        let dexterity = self.player.character.stats.dexterity as i16;
+        let check = Check::default().add_modifier(dexterity).set_difficulty(SWIM_DIFFICULTY);
+
+        let success = self.check(&check).is_success();
+        if !success {
+            self.log.info(LogData::SwimFailed);
+        }
+        success
+    }
+
+    /// The player falls into a chasm and lands somewhere on the next level, taking [CHASM_FALL_DAMAGE].
+    pub fn fall_into_chasm(&mut self) -> GameResult {
+        let damage = self.roll(&CHASM_FALL_DAMAGE).max(0) as u16;
+
+        self.goto_level(self.level_nr + 1, LevelEntrance::Random)?;
+
+        self.player.character.take_damage(damage);
+        self.record_gauntlet_damage(damage);
+        self.record_damage_taken(damage, DamageSource::Fall);
+        self.log.info(LogData::ChasmFall { damage });
+
+        Ok(GameOutcome::Success)
+    }
+
+    /// The player steps on a spike trap and takes [SPIKE_TRAP_DAMAGE].
+    pub fn spring_spike_trap_on_player(&mut self) {
+        let damage = self.roll(&SPIKE_TRAP_DAMAGE).max(0) as u16;
+
+        self.player.character.take_damage(damage);
+        self.mark_hit(self.player.character.pos(), damage, false, true);
+        self.record_gauntlet_damage(damage);
+        self.record_damage_taken(damage, DamageSource::SpikeTrap);
+        self.log.info(LogData::PlayerSpikeTrap { damage });
+    }
+
+    /// The NPC steps on a spike trap and takes [SPIKE_TRAP_DAMAGE]. The trap can kill it.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the NPC is not on the current level.
+    pub fn spring_spike_trap_on_npc(&mut self, npc_id: EntityId) -> Result<(), GameError> {
+        let damage = self.roll(&SPIKE_TRAP_DAMAGE).max(0) as u16;
+
+        let npc =
+            self.current_level_mut().get_npc_mut(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        npc.stats.base.take_damage(damage);
+        let npc = npc.clone();
+
+        if self.current_world().get_tile(npc.pos()).visible {
+            self.mark_hit(npc.pos(), damage, false, false);
+            self.log.info(LogData::NpcSpikeTrap { npc_name: npc.name().to_string(), damage });
+        }
+        if !npc.stats.base.is_alive() {
+            self.npc_died(npc, false)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the effects of the terrain the player is standing on. Called once per round.
+    ///
+    /// Lava burns the player for [LAVA_DAMAGE].
+    pub fn apply_terrain_effects(&mut self) {
+        let player_pos: Point = self.player.character.pos();
+
+        if self.current_world().get_tile(player_pos).tile_type == TileType::Lava {
+            self.player.character.take_damage(LAVA_DAMAGE);
+            self.record_gauntlet_damage(LAVA_DAMAGE);
+            self.record_damage_taken(LAVA_DAMAGE, DamageSource::Lava);
+            self.log.info(LogData::LavaBurn { damage: LAVA_DAMAGE });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::movement_profile::MovementProfile,
+        core::entity_logic::Movable,
+        world::{level::Level, tiles::Collision, worldspace::Room},
+    };
+
+    #[test]
+    fn lava_burns_and_npcs_avoid_hazards() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        level.world.get_tile_mut(Point::new(50, 10)).tile_type = TileType::Lava;
+        level.world.get_tile_mut(Point::new(51, 10)).tile_type = TileType::Chasm;
+        game.levels.insert(0, level);
+
+        let goblin = game.create_npc("goblin".into(), Point::new(52, 10)).unwrap();
+        let goblin_id = goblin.id();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+
+        let hp = game.player.character.stats.base.hp_current;
+        game.player.character.move_to(Point::new(50, 10));
+        game.apply_terrain_effects();
+        assert_eq!(game.player.character.stats.base.hp_current, hp - LAVA_DAMAGE);
+
+        assert!(TileType::Chasm.is_walkable());
+        assert!(matches!(game.move_npc(goblin_id, -1, 0), Ok(GameOutcome::Fail(_))));
+    }
+
+    #[test]
+    fn spike_traps_hurt_npcs_that_walk_over_them() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(35, 5), 30, 15));
+        level.world.get_tile_mut(Point::new(50, 10)).tile_type = TileType::SpikeTrap;
+        game.levels.insert(0, level);
+        game.player.character.move_to(Point::new(40, 10));
+
+        let ogre = game.create_npc("ogre".into(), Point::new(51, 10)).unwrap();
+        let ogre_id = ogre.id();
+        game.current_level_mut().spawn_npc(ogre).unwrap();
+        let hp = game.current_level().get_npc(ogre_id).unwrap().stats.base.hp_current;
+
+        // With a free way around, NPCs step around the trap
+        let ogre_pos = game.current_level().get_npc(ogre_id).unwrap().footprint();
+        let next_step =
+            game.next_step_for(MovementProfile::MONSTER, ogre_pos, Point::new(48, 10)).unwrap();
+        let next_pos = Point::new(51, 10).get_adjacent(next_step);
+        assert!(!ogre_pos.moved_to(next_pos).contains(Point::new(50, 10)));
+
+        // Pushed onto it, they spring it like the player
+        assert!(matches!(game.move_npc(ogre_id, -1, 0), Ok(GameOutcome::Success)));
+        assert!(game.current_level().get_npc(ogre_id).unwrap().stats.base.hp_current < hp);
+    }
+}
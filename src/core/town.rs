@@ -0,0 +1,282 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use ron::de::from_reader;
+use ron::ser::{PrettyConfig, to_writer_pretty};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        entity_logic::{Entity, EntityId},
+        game::GameState,
+        game_items::GameItemId,
+    },
+    data::item_defs::{GameItemDefId, item_defs},
+    util::{
+        build_info::BuildInfo,
+        errors_results::{
+            DataError, EngineError, FailReason, GameError, GameOutcome, GameResult, IoError,
+        },
+        text_log::LogData,
+    },
+};
+
+/// Index of the town on the surface. Going up from the tutorial leads here.
+pub const TOWN_LEVEL: usize = 0;
+
+/// Index of the tutorial, the first level of the anthill. Every run starts here.
+pub const TUTORIAL_LEVEL: usize = 1;
+
+/// NPCs that heal the player for gold.
+pub const HEALER_NPCS: [&str; 1] = ["healer"];
+
+/// Price in gold the healer asks for each hit point.
+pub const HEAL_PRICE_PER_HP: u32 = 2;
+
+/// Number of items that fit into the stash.
+pub const STASH_LIMIT: usize = 10;
+
+/// Chest in the town whose contents are kept between runs.
+///
+/// Items lose their identity in the stash: Only their definition is stored, and they are registered anew when taken out.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Stash {
+    pub items: Vec<GameItemDefId>,
+
+    /// File the stash is saved to whenever it changes. Stashes without a file (e.g. in tests) are forgotten when the game ends.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+
+    /// Build of the game that saved the stash.
+    #[serde(default = "BuildInfo::unknown")]
+    pub build: BuildInfo,
+}
+
+/// Path of the file the contents of the stash are saved to, so they are kept between runs.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn stash_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("saves");
+    path.push("stash.ron");
+    Some(path)
+}
+
+impl Stash {
+    /// Loads the stash from the given file. A missing file means the stash is still empty.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        let mut stash = if path.exists() {
+            let file = File::open(path).map_err(IoError::FileReading)?;
+            from_reader::<_, Stash>(BufReader::new(file)).map_err(IoError::MapParsing)?
+        } else {
+            Stash::default()
+        };
+
+        stash.path = Some(path.to_path_buf());
+        Ok(stash)
+    }
+
+    /// Writes the stash to its file, if it has one.
+    ///
+    /// # Errors
+    /// * [IoError::FileCreation] if the file or its directory could not be created.
+    /// * [IoError::MapWriting] if the stash could not be written.
+    pub fn save(&self) -> Result<(), GameError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(IoError::FileCreation)?;
+        }
+
+        let file = File::create(path).map_err(IoError::FileCreation)?;
+        to_writer_pretty(BufWriter::new(file), self, PrettyConfig::default())
+            .map_err(IoError::MapWriting)?;
+        Ok(())
+    }
+}
+
+impl GameState {
+    /// An adjacent healer restores all of the player's hit points for [HEAL_PRICE_PER_HP] gold each.
+    ///
+    /// # Errors
+    /// * [EngineError::NpcNotFound] if the healer is not in the current level.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::InvalidTarget] if the NPC does not heal.
+    /// * [GameOutcome::Fail] with [FailReason::AlreadyHealthy] if the player is not hurt.
+    /// * [GameOutcome::Fail] with [FailReason::NotEnoughGold] if the player cannot afford the healing.
+    /// * [GameOutcome::Success] if the player was healed.
+    pub fn buy_healing(&mut self, npc_id: EntityId) -> GameResult {
+        let npc = self.current_level().get_npc(npc_id).ok_or(EngineError::NpcNotFound(npc_id))?;
+        if !HEALER_NPCS.contains(&npc.def_id.as_str()) {
+            return Ok(GameOutcome::Fail(FailReason::InvalidTarget(npc_id)));
+        }
+        let npc_name = npc.name().to_string();
+
+        let stats = &self.player.character.stats.base;
+        let missing_hp = stats.hp_max - stats.hp_current;
+        if missing_hp == 0 {
+            return Ok(GameOutcome::Fail(FailReason::AlreadyHealthy));
+        }
+
+        let price = missing_hp as u32 * HEAL_PRICE_PER_HP;
+        if self.player.character.gold < price {
+            return Ok(GameOutcome::Fail(FailReason::NotEnoughGold { price }));
+        }
+
+        self.player.character.gold -= price;
+        self.player.character.heal(missing_hp);
+
+        self.log.info(LogData::HealerHeals { npc_name, amount: missing_hp, price });
+        Ok(GameOutcome::Success)
+    }
+
+    /// Lists what the player can do at the stash: First storing each item of the inventory, then taking each stored item.
+    ///
+    /// # Returns
+    /// The ids of the inventory items that can be stored and the labels of all options, in the same order.
+    pub fn stash_offers(&self) -> (Vec<GameItemId>, Vec<String>) {
+        let mut item_ids = Vec::new();
+        let mut options = Vec::new();
+
+        for item_id in &self.player.character.inventory {
+            if let Some(item) = self.items.get(item_id)
+                && let Some(item_def) = item_defs().get(&item.def_id)
+            {
+                item_ids.push(*item_id);
+                options.push(format!("Store {}", item_def.name));
+            }
+        }
+
+        for def_id in &self.stash.items {
+            let name = item_defs().get(def_id).map_or(def_id.as_str(), |item_def| item_def.name);
+            options.push(format!("Take {}", name));
+        }
+
+        (item_ids, options)
+    }
+
+    /// Puts an item from the player's inventory into the stash.
+    ///
+    /// # Errors
+    /// * [EngineError::ItemNotInInventory] if the item is not in the player's inventory.
+    /// * [EngineError::UnregisteredItem] if the item is not registered.
+    /// * [IoError] if the stash could not be saved.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::StashFull] if there is no room in the stash.
+    /// * [GameOutcome::Fail] with [FailReason::BagNotEmpty] if the item is a bag that still holds items.
+    /// * [GameOutcome::Success] if the item was stored.
+    pub fn store_item(&mut self, item_id: GameItemId) -> GameResult {
+        if !self.player.character.inventory.contains(&item_id) {
+            return Err(GameError::from(EngineError::ItemNotInInventory(item_id)));
+        }
+        if self.stash.items.len() >= STASH_LIMIT {
+            return Ok(GameOutcome::Fail(FailReason::StashFull));
+        }
+        if self.is_filled_bag(item_id) {
+            return Ok(GameOutcome::Fail(FailReason::BagNotEmpty));
+        }
+
+        let item = self.get_item_by_id(item_id).ok_or(EngineError::UnregisteredItem(item_id))?;
+        let def_id = item.def_id.clone();
+        let item_def = self
+            .get_item_def_by_id(&def_id)
+            .ok_or(DataError::MissingItemDefinition(def_id.clone()))?;
+
+        self.remove_item_from_inv(item_id)?;
+        self.deregister_item(item_id)?;
+        self.stash.items.push(def_id);
+        self.stash.save()?;
+
+        self.log.info(LogData::ItemStored { item_name: item_def.name.to_string() });
+        Ok(GameOutcome::Success)
+    }
+
+    /// Takes the item at the given position out of the stash and puts it into the player's inventory.
+    ///
+    /// # Errors
+    /// * [DataError::MissingItemDefinition] if the stored item no longer exists in the game.
+    /// * [IoError] if the stash could not be saved.
+    ///
+    /// # Returns
+    /// * [GameOutcome::Fail] with [FailReason::NoInteraction] if there is no item at that position.
+    /// * [GameOutcome::Fail] with [FailReason::InventoryFull] if the player cannot carry the item.
+    /// * [GameOutcome::Success] if the item was taken.
+    pub fn take_from_stash(&mut self, index: usize) -> GameResult {
+        let Some(def_id) = self.stash.items.get(index).cloned() else {
+            return Ok(GameOutcome::Fail(FailReason::NoInteraction));
+        };
+        if self.player.character.inventory.is_full() {
+            return Ok(GameOutcome::Fail(FailReason::InventoryFull));
+        }
+
+        let item_def = self
+            .get_item_def_by_id(&def_id)
+            .ok_or(DataError::MissingItemDefinition(def_id.clone()))?;
+        let item_id = self.register_item(&def_id)?;
+
+        self.stash.items.remove(index);
+        self.add_item_to_inv(item_id)?;
+        self.stash.save()?;
+
+        self.log.info(LogData::ItemTakenFromStash { item_name: item_def.name.to_string() });
+        Ok(GameOutcome::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::level::LevelEntrance;
+
+    #[test]
+    fn stash_round_trip_and_healer() {
+        let mut game = GameState::default();
+        game.goto_level(TUTORIAL_LEVEL, LevelEntrance::Entry).unwrap();
+        game.goto_level_previous().unwrap();
+        assert_eq!(game.level_nr, TOWN_LEVEL);
+        assert!(game.goto_level_previous().is_err());
+
+        let sword = game.register_item(&"weapon_sword_dull".to_string()).unwrap();
+        game.add_item_to_inv(sword).unwrap();
+        assert!(matches!(game.store_item(sword), Ok(GameOutcome::Success)));
+        assert!(game.get_item_by_id(sword).is_none());
+        assert_eq!(game.stash.items, vec!["weapon_sword_dull".to_string()]);
+
+        let (item_ids, options) = game.stash_offers();
+        assert!(item_ids.is_empty());
+        assert_eq!(options.len(), 1);
+        assert!(matches!(game.take_from_stash(0), Ok(GameOutcome::Success)));
+        assert!(game.stash.items.is_empty());
+        assert_eq!(game.player.character.inventory.len(), 1);
+
+        let healer_id = game
+            .current_level()
+            .npcs
+            .iter()
+            .find(|npc| HEALER_NPCS.contains(&npc.def_id.as_str()))
+            .map(|npc| npc.id())
+            .unwrap();
+        assert!(matches!(
+            game.buy_healing(healer_id),
+            Ok(GameOutcome::Fail(FailReason::AlreadyHealthy))
+        ));
+
+        game.player.character.take_damage(5);
+        game.player.character.gold = 5 * HEAL_PRICE_PER_HP;
+        assert!(matches!(game.buy_healing(healer_id), Ok(GameOutcome::Success)));
+        assert_eq!(game.player.character.gold, 0);
+        let stats = &game.player.character.stats.base;
+        assert_eq!(stats.hp_current, stats.hp_max);
+    }
+}
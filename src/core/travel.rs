@@ -0,0 +1,150 @@
+use serde::Serialize;
+
+use crate::{
+    ai::movement_profile::MovementProfile,
+    core::{entity_logic::Entity, game::GameState, player_actions::PlayerInput},
+    util::text_log::LogData,
+    world::coordinate_system::Point,
+};
+
+/// Progress of a travel, which walks step after step until the player arrives (see [GameState::continue_travel]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TravelState {
+    /// The explored tile the player travels to.
+    pub goal: Point,
+}
+
+/// Why a travel came to an end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum TravelEnd {
+    /// The player stands on the goal.
+    Arrived,
+
+    /// A hostile NPC came into sight.
+    Interrupted,
+
+    /// The way to the goal is no longer open.
+    Blocked,
+
+    /// The player pressed a key.
+    Cancelled,
+}
+
+impl GameState {
+    /// Starts travelling to the goal. The travel is carried out by calling [GameState::continue_travel] until it is over.
+    ///
+    /// The player cannot travel with enemies in sight, and only over tiles they have already explored.
+    pub fn start_travel(&mut self, goal: Point) {
+        if self.hostile_in_sight() {
+            self.log.info(LogData::TravelEnemiesNearby);
+            return;
+        }
+        let start = self.player.character.pos();
+        if start == goal {
+            return;
+        }
+        if self.next_step_toward(MovementProfile::PLAYER, start, goal).is_none() {
+            self.log.info(LogData::TravelNoWay);
+            return;
+        }
+
+        self.log.info(LogData::TravelStarted);
+        self.travel = Some(TravelState { goal });
+    }
+
+    /// Takes one step of the current travel, or ends the travel if it is over.
+    ///
+    /// The steps are taken like the player pressed a direction, so a travel plays out the same way in a [Replay](crate::core::replay::Replay).
+    pub fn continue_travel(&mut self) {
+        let Some(travel) = self.travel else {
+            return;
+        };
+
+        let start = self.player.character.pos();
+        if start == travel.goal {
+            self.stop_travel(TravelEnd::Arrived);
+            return;
+        }
+        if self.hostile_in_sight() {
+            self.stop_travel(TravelEnd::Interrupted);
+            return;
+        }
+        let Some(direction) = self.next_step_toward(MovementProfile::PLAYER, start, travel.goal)
+        else {
+            self.stop_travel(TravelEnd::Blocked);
+            return;
+        };
+
+        let round_nr = self.round_nr;
+        self.resolve_player_action(PlayerInput::Direction(direction));
+
+        // The step failed without taking a round, so the next one would fail the same way.
+        if self.round_nr == round_nr && self.player.character.pos() == start {
+            self.stop_travel(TravelEnd::Blocked);
+        }
+    }
+
+    /// Ends the current travel and tells the player why.
+    pub fn stop_travel(&mut self, end: TravelEnd) {
+        if self.travel.take().is_some() {
+            self.log.info(LogData::TravelEnded { end });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, tiles::TileType, worldspace::Room};
+
+    fn explored_room() -> GameState {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 30, 10));
+        game.levels.insert(0, level);
+        game.player.character.base.pos = Point::new(8, 8);
+        game.compute_fov();
+        game
+    }
+
+    #[test]
+    fn travel_walks_to_the_goal() {
+        let mut game = explored_room();
+        let goal = Point::new(20, 12);
+
+        game.start_travel(goal);
+        while game.travel.is_some() {
+            game.continue_travel();
+        }
+
+        assert_eq!(game.player.character.pos(), goal);
+        assert_eq!(game.round_nr, 12 + 4);
+    }
+
+    #[test]
+    fn travel_stays_on_explored_tiles() {
+        let mut game = explored_room();
+        let goal = Point::new(20, 12);
+        game.current_world_mut().get_tile_mut(goal).explored = false;
+
+        game.start_travel(goal);
+
+        assert!(game.travel.is_none());
+        assert_eq!(game.current_world().get_tile(goal).tile_type, TileType::Floor);
+    }
+
+    #[test]
+    fn enemies_in_sight_interrupt_travel() {
+        let mut game = explored_room();
+        game.start_travel(Point::new(30, 8));
+        game.continue_travel();
+        assert!(game.travel.is_some());
+
+        let goblin = game.create_npc("goblin".into(), Point::new(14, 12)).unwrap();
+        game.current_level_mut().spawn_npc(goblin).unwrap();
+        game.continue_travel();
+
+        assert!(game.travel.is_none());
+        assert_eq!(game.player.character.pos(), Point::new(9, 8));
+    }
+}
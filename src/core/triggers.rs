@@ -0,0 +1,202 @@
+use crate::{
+    ai::{npc_ai::NpcAiState, pack::pack_positions},
+    core::{
+        entity_logic::{Disposition, Entity},
+        game::GameState,
+        gauntlet::GauntletRecord,
+    },
+    util::{errors_results::GameError, text_log::LogData},
+    world::{
+        coordinate_system::Point,
+        level_data::{SpawnData, SpawnKind, TriggerAction},
+        tiles::{DoorType, TileType},
+    },
+};
+
+/// A piece of lore a trigger wants to show in a window. The UI opens the window after the turn.
+#[derive(Clone, Debug)]
+pub struct LoreText {
+    pub title: String,
+    pub paragraphs: Vec<String>,
+}
+
+impl GameState {
+    /// Fires the triggers of the current level whose area the player stands in. Each trigger only fires once.
+    ///
+    /// An action that fails (e.g. a spawn point that is blocked) is skipped, the other actions still happen.
+    pub fn fire_triggers(&mut self) {
+        let player_pos = self.player.character.pos();
+        let (fired, waiting) = std::mem::take(&mut self.current_level_mut().triggers)
+            .into_iter()
+            .partition(|trigger| trigger.contains(player_pos));
+        self.current_level_mut().triggers = waiting;
+
+        for trigger in fired {
+            if let Err(error) = self.run_trigger_action(&trigger.action) {
+                self.log
+                    .debug_warn(format!("Trigger at ({}, {}): {}", trigger.x, trigger.y, error));
+            }
+        }
+    }
+
+    /// Runs the action of a trigger.
+    ///
+    /// # Errors
+    /// * [DataError::MissingItemDefinition](crate::util::errors_results::DataError::MissingItemDefinition) or [DataError::MissingNpcDefinition](crate::util::errors_results::DataError::MissingNpcDefinition) if something unknown is spawned.
+    pub fn run_trigger_action(&mut self, action: &TriggerAction) -> Result<(), GameError> {
+        match action {
+            TriggerAction::Ambush { spawns } => {
+                self.log.info(LogData::Ambush);
+                for spawn in spawns {
+                    self.spawn_from_trigger(spawn)?;
+                }
+            }
+            TriggerAction::Lore { title, paragraphs } => {
+                self.pending_lore =
+                    Some(LoreText { title: title.clone(), paragraphs: paragraphs.clone() });
+            }
+            TriggerAction::LockDoor { x, y } => {
+                let point = Point::new(*x, *y);
+                if !self.current_world().is_in_bounds(point.x as isize, point.y as isize) {
+                    return Ok(());
+                }
+
+                let tile = self.current_world_mut().get_tile_mut(point);
+                if !matches!(tile.tile_type, TileType::Door(DoorType::Open | DoorType::Closed)) {
+                    return Ok(());
+                }
+                tile.tile_type = TileType::Door(DoorType::Locked);
+                self.current_level_mut().locked_doors.push(point);
+                self.log.info(LogData::DoorLocked);
+            }
+            TriggerAction::StartGauntlet => {
+                let round_nr = self.round_nr;
+                let level = self.current_level_mut();
+                if level.gauntlet.is_none() {
+                    level.gauntlet = Some(GauntletRecord::new(round_nr));
+                    self.log.info(LogData::GauntletStarted);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Spawns an NPC, pack, item or chest of an ambush. Spawn points that are blocked or outside the level are skipped.
+    fn spawn_from_trigger(&mut self, spawn: &SpawnData) -> Result<(), GameError> {
+        let pos = Point::new(spawn.x, spawn.y);
+        if !self.current_world().is_in_bounds(pos.x as isize, pos.y as isize)
+            || !self.current_level().is_available(pos)
+            || pos == self.player.character.pos()
+        {
+            return Ok(());
+        }
+
+        match &spawn.kind {
+            SpawnKind::Npc { def_id } | SpawnKind::Hunter { def_id } => {
+                let mut npc = self.create_npc(def_id.clone(), pos)?;
+                if matches!(spawn.kind, SpawnKind::Hunter { .. }) {
+                    npc.ai_state = NpcAiState::Hunting;
+                }
+                self.current_level_mut().spawn_npc(npc)?;
+            }
+            SpawnKind::Pack { def_id, size } => {
+                let player_pos = self.player.character.pos();
+                let mut positions = pack_positions(self.current_level(), pos, *size + 1);
+                positions.retain(|point| *point != player_pos);
+                positions.truncate(*size);
+                for npc in self.create_pack(def_id, &positions)? {
+                    self.current_level_mut().spawn_npc(npc)?;
+                }
+            }
+            SpawnKind::Item { def_id } => {
+                let item_id = self.register_item(def_id)?;
+                let item_sprite = self.create_item_sprite(item_id, pos)?;
+                self.current_level_mut().spawn_item_sprite(item_sprite)?;
+            }
+            SpawnKind::Chest { contents } => {
+                let item_ids = self.register_items(contents)?;
+                self.current_level_mut().spawn_chest(pos, item_ids)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Opens the doors locked by triggers once no hostile NPCs are left on the current level.
+    pub fn unlock_cleared_doors(&mut self) {
+        let level = self.current_level();
+        if level.locked_doors.is_empty()
+            || level.npcs.iter().any(|npc| npc.disposition == Disposition::Hostile)
+        {
+            return;
+        }
+
+        let level = self.current_level_mut();
+        for point in std::mem::take(&mut level.locked_doors) {
+            level.world.get_tile_mut(point).tile_type = TileType::Door(DoorType::Closed);
+        }
+        self.log.info(LogData::DoorsUnlocked);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, level_data::TriggerData, worldspace::Room};
+
+    #[test]
+    fn triggers_fire_once_when_entered() {
+        let mut game = GameState::default();
+        let mut level = Level::new();
+        level.world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        level.world.get_tile_mut(Point::new(8, 10)).tile_type = TileType::Door(DoorType::Open);
+        level.triggers = vec![
+            TriggerData {
+                x: 10,
+                y: 8,
+                width: 1,
+                height: 5,
+                action: TriggerAction::LockDoor { x: 8, y: 10 },
+            },
+            TriggerData {
+                x: 10,
+                y: 10,
+                width: 1,
+                height: 1,
+                action: TriggerAction::Ambush {
+                    spawns: vec![SpawnData {
+                        kind: SpawnKind::Npc { def_id: "goblin".to_string() },
+                        x: 14,
+                        y: 10,
+                    }],
+                },
+            },
+        ];
+        game.levels.insert(0, level);
+
+        game.player.character.base.pos = Point::new(9, 10);
+        game.fire_triggers();
+        assert_eq!(game.current_level().triggers.len(), 2);
+
+        game.player.character.base.pos = Point::new(10, 10);
+        game.fire_triggers();
+        assert!(game.current_level().triggers.is_empty());
+        assert_eq!(game.current_level().npcs.len(), 1);
+        assert_eq!(
+            game.current_world().get_tile(Point::new(8, 10)).tile_type,
+            TileType::Door(DoorType::Locked)
+        );
+
+        // The door stays locked until the ambush is defeated
+        game.unlock_cleared_doors();
+        assert!(!game.current_level().locked_doors.is_empty());
+        let npc_id = game.current_level().npcs[0].id();
+        game.current_level_mut().despawn(npc_id);
+        game.unlock_cleared_doors();
+        assert_eq!(
+            game.current_world().get_tile(Point::new(8, 10)).tile_type,
+            TileType::Door(DoorType::Closed)
+        );
+    }
+}
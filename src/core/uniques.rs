@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    ai::npc_ai::AGGRO_RADIUS,
+    core::{
+        entity_logic::{Disposition, Entity, Npc},
+        game::GameState,
+    },
+    data::uniques::{UNIQUE_CHANCE, UniqueDef, UniqueId, get_unique_def_by_id, unique_defs},
+    proc_gen::population::{OUT_OF_DEPTH_RANGE, depth_danger},
+    util::errors_results::{DataError, GameError},
+    world::{coordinate_system::Point, level::Level},
+};
+
+/// The unique monsters of the run. Each unique appears only once per run, whether the player killed it or not.
+#[derive(Clone, Debug, Default)]
+pub struct UniqueRegistry {
+    pub spawned: HashSet<UniqueId>,
+}
+
+impl GameState {
+    /// Picks the unique that appears on a generated level of the given depth, with a chance of [UNIQUE_CHANCE] percent.
+    ///
+    /// Uniques appear out of depth: Their danger rating lies up to [OUT_OF_DEPTH_RANGE] points above the one of the level.
+    /// Uniques that appeared already this run are left out. Rolled with the rng of the procedural generation.
+    ///
+    /// # Returns
+    /// * `None` if the level gets no unique.
+    pub fn roll_unique(&mut self, depth: usize) -> Option<UniqueId> {
+        if self.proc_gen.random_range(0..100) >= UNIQUE_CHANCE {
+            return None;
+        }
+
+        let danger = depth_danger(depth);
+        let mut uniques: Vec<&UniqueId> = unique_defs()
+            .iter()
+            .filter(|(unique_id, _)| !self.uniques.spawned.contains(*unique_id))
+            .filter(|(_, def)| def.danger > danger && def.danger <= danger + OUT_OF_DEPTH_RANGE)
+            .map(|(unique_id, _)| unique_id)
+            .collect();
+        uniques.sort(); // HashMaps are random, so the uniques are sorted to keep seeds deterministic.
+
+        uniques.choose(&mut self.proc_gen).map(|unique_id| unique_id.to_string())
+    }
+
+    /// Creates the unique monster and writes it into the [UniqueRegistry], so it does not appear again this run.
+    ///
+    /// # Errors
+    /// * [DataError::MissingUniqueDefinition] if the unique does not exist.
+    /// * [DataError::MissingNpcDefinition] if the NPC the unique is based on does not exist.
+    pub fn create_unique(&mut self, unique_id: &str, point: Point) -> Result<Npc, GameError> {
+        let unique_def: &UniqueDef = get_unique_def_by_id(unique_id)
+            .ok_or_else(|| DataError::MissingUniqueDefinition(unique_id.to_string()))?;
+
+        let mut npc = self.create_npc(unique_def.base.to_string(), point)?;
+        npc.base.name = unique_def.name.to_string();
+        npc.base.style = npc.base.style.fg(unique_def.color);
+        npc.stats.base.hp_max *= unique_def.hp_factor;
+        npc.stats.base.hp_current = npc.stats.base.hp_max;
+        npc.stats.damage = npc.stats.damage.add_modifier(unique_def.damage_bonus);
+        npc.disposition = Disposition::Hostile;
+        npc.unique = Some(unique_id.to_string());
+
+        self.uniques.spawned.insert(unique_id.to_string());
+        Ok(npc)
+    }
+
+    /// Rolls a unique for a generated level (see [GameState::roll_unique]) and places it on a free tile out of sight of the entry.
+    ///
+    /// # Errors
+    /// * Errors of [GameState::create_unique].
+    pub fn spawn_unique(&mut self, level: &mut Level, depth: usize) -> Result<(), GameError> {
+        let Some(unique_id) = self.roll_unique(depth) else {
+            return Ok(());
+        };
+
+        let mut candidates: Vec<Point> = Vec::new();
+        for y in 0..level.world.height {
+            for x in 0..level.world.width {
+                let point = Point::new(x, y);
+                if level.is_available(point)
+                    && point.distance_squared_from(level.entry) > AGGRO_RADIUS.pow(2) * 2
+                {
+                    candidates.push(point);
+                }
+            }
+        }
+        let Some(&point) = candidates.choose(&mut self.proc_gen) else {
+            return Ok(());
+        };
+
+        let npc = self.create_unique(&unique_id, point)?;
+        self.log.debug_info(format!("Unique {} spawned at ({}, {})", npc.name(), point.x, point.y));
+        level.spawn_npc(npc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::npc_defs::npc_defs;
+
+    #[test]
+    fn uniques_are_stronger_appear_once_and_drop_their_loot() {
+        let mut game = GameState::default();
+        game.levels.insert(0, Level::new());
+
+        let grak = game.create_unique("grak", Point::new(10, 10)).unwrap();
+        let goblin_boss = &npc_defs()["goblin_boss"];
+        assert_eq!(grak.name(), "Grak, Goblin King");
+        assert_eq!(grak.stats.base.hp_max, goblin_boss.stats.base.hp_max * 2);
+
+        // Once spawned, Grak never appears again. Morwen is still too dangerous for the first level.
+        for _ in 0..200 {
+            assert_ne!(game.roll_unique(2).as_deref(), Some("grak"));
+            assert_eq!(game.roll_unique(0), None);
+        }
+
+        let corpse = game.create_corpse(&grak).unwrap();
+        assert!(
+            corpse.loot.iter().any(|item_id| game.items[item_id].def_id == "weapon_grak_cleaver")
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    core::{
+        entity_logic::Entity,
+        game::GameState,
+        town::{TOWN_LEVEL, TUTORIAL_LEVEL},
+    },
+    proc_gen::population::random_npc_def,
+    util::errors_results::GameError,
+    world::{coordinate_system::Point, tiles::TileType},
+};
+
+/// Chance (in per mille) each round that a wandering monster arrives on a level of depth 0. Each level of depth adds one.
+pub const WANDERER_BASE_CHANCE: u32 = 2;
+
+/// Number of wandering monsters a level of depth 0 can receive. Every [WANDERER_CAP_DEPTH_STEP] levels of depth add one more.
+pub const WANDERER_BASE_CAP: usize = 2;
+
+/// Levels of depth it takes to raise the cap of wandering monsters by one.
+pub const WANDERER_CAP_DEPTH_STEP: usize = 4;
+
+/// Wandering monsters arrive at least this far from the player.
+pub const WANDERER_MIN_DISTANCE: usize = 12;
+
+impl GameState {
+    /// Number of wandering monsters the current level can receive over its lifetime.
+    pub fn wanderer_cap(&self) -> usize {
+        WANDERER_BASE_CAP + self.level_nr / WANDERER_CAP_DEPTH_STEP
+    }
+
+    /// Occasionally lets a wandering monster arrive on the current level, so levels do not stay empty once cleared. Called once per round.
+    ///
+    /// The town, the tutorial and gauntlets never receive wandering monsters.
+    pub fn spawn_wandering_monsters(&mut self) -> Result<(), GameError> {
+        if self.level_nr == TOWN_LEVEL
+            || self.level_nr == TUTORIAL_LEVEL
+            || self.current_level().gauntlet.is_some()
+            || self.current_level().wanderers >= self.wanderer_cap()
+        {
+            return Ok(());
+        }
+
+        let chance = WANDERER_BASE_CHANCE + self.level_nr as u32;
+        if self.rng.random_range(0..1000) >= chance {
+            return Ok(());
+        }
+
+        self.spawn_wanderer()
+    }
+
+    /// Spawns a monster that fits the depth of the level on a floor tile far from the player that they cannot see.
+    ///
+    /// # Errors
+    /// * [GameError] if the monster could not be created or spawned.
+    pub fn spawn_wanderer(&mut self) -> Result<(), GameError> {
+        let Some(point) = self.wanderer_point() else {
+            return Ok(());
+        };
+
+        let level = self.current_level();
+        let favoured_npcs = level.affix_def().map_or(&[][..], |affix| affix.favoured_npcs);
+        let Some(def_id) = random_npc_def(level.biome, favoured_npcs, self.level_nr, &mut self.rng)
+        else {
+            return Ok(());
+        };
+
+        let npc = self.create_npc(def_id.clone(), point)?;
+        if !self.current_level().is_footprint_available(npc.footprint()) {
+            return Ok(());
+        }
+        self.current_level_mut().spawn_npc(npc)?;
+        self.current_level_mut().wanderers += 1;
+
+        Ok(())
+    }
+
+    /// Picks a free floor tile of the current level that is at least [WANDERER_MIN_DISTANCE] away from the player and out of their sight.
+    fn wanderer_point(&mut self) -> Option<Point> {
+        let player_pos = self.player.character.pos();
+        let min_distance = WANDERER_MIN_DISTANCE * WANDERER_MIN_DISTANCE;
+
+        let level = self.current_level();
+        let candidates: Vec<Point> = (0..level.world.height)
+            .flat_map(|y| (0..level.world.width).map(move |x| Point::new(x, y)))
+            .filter(|point| {
+                let tile = level.world.get_tile(*point);
+                tile.tile_type == TileType::Floor
+                    && !tile.visible
+                    && level.is_available(*point)
+                    && point.distance_squared_from(player_pos) >= min_distance
+            })
+            .collect();
+
+        candidates.choose(&mut self.rng).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{level::Level, worldspace::Room};
+
+    #[test]
+    fn wanderers_arrive_out_of_sight_up_to_the_cap() {
+        let mut game = GameState {
+            levels: vec![Level::new(), Level::new(), Level::new()],
+            level_nr: 2,
+            ..Default::default()
+        };
+        game.current_world_mut().carve_room(&Room::new(Point::new(1, 1), 40, 15));
+        game.player.character.base.pos = Point::new(2, 2);
+
+        for _ in 0..game.wanderer_cap() {
+            game.spawn_wanderer().unwrap();
+        }
+        let level = game.current_level();
+        assert_eq!(level.wanderers, game.wanderer_cap());
+        for npc in &level.npcs {
+            assert!(
+                npc.pos().distance_squared_from(Point::new(2, 2))
+                    >= WANDERER_MIN_DISTANCE * WANDERER_MIN_DISTANCE
+            );
+        }
+
+        // The cap is reached, no more wanderers arrive
+        for _ in 0..5000 {
+            game.spawn_wandering_monsters().unwrap();
+        }
+        assert_eq!(game.current_level().npcs.len(), game.wanderer_cap());
+    }
+}
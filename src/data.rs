@@ -1,4 +1,13 @@
 pub mod ascii_art;
+pub mod dialogue;
+pub mod encounters;
+pub mod floor_affixes;
 pub mod item_defs;
 pub mod levels;
+pub mod loot_tables;
+pub mod mods;
 pub mod npc_defs;
+pub mod perks;
+pub mod recipes;
+pub mod uniques;
+pub mod vaults;
@@ -18,12 +18,12 @@ pub const STARTSCREEN_ASCII: &str = r#"┌────────────
 │               ▒▒▒▒▒▒                       ▒▒▒   │                                               │      ▒▒             ▒▒▒▒▒▒                      │
 │                    ▒▒▒                        ▒  └──────────────────────────────────────+────────┘    ▒▒▒▒▒▒▒▒▒▒▒▒   ▒▒▒    ▒▒▒▒▒▒▒▒               │
 │                      ▒                ▒▒▒▒▒▒▒▒                                          ▒▒▒▒        ▒▒▒▒         ▒▒▒▒▒             ▒▒▒▒▒           │
-│                ▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒▒▒▒▒▒                        Press ENTER to start        ▒▒▒▒▒▒▒▒▒▒                                 ▒▒▒▒▒▒▒▒▒▒▒▒▒▒
-│        ▒▒▒▒▒▒▒▒▒▒           ▒▒▒▒▒                            Press SHIFT + h for help                                              ▒▒▒▒            │
-▒▒▒▒▒▒▒▒▒                          ▒                           Press SHIFT + q to quit                                             ▒▒                │
+│                ▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒▒▒▒▒▒                                                    ▒▒▒▒▒▒▒▒▒▒                                 ▒▒▒▒▒▒▒▒▒▒▒▒▒▒
+│        ▒▒▒▒▒▒▒▒▒▒           ▒▒▒▒▒                                                                                                  ▒▒▒▒            │
+▒▒▒▒▒▒▒▒▒                          ▒                                                                                               ▒▒                │
 │                                   ▒▒▒▒                                                                                      ▒▒▒▒▒                  │
-│                                       ▒                     Move: W A S D   Inventory: i                               ▒▒▒▒▒▒                      │
-│                                     ▒▒                      Wait: .     Command input: :                             ▒▒▒                           │
+│                                       ▒                                                                                ▒▒▒▒▒▒                      │
+│                                     ▒▒                                                                               ▒▒▒                           │
 │                                ▒▒▒▒▒▒                                                                               ▒▒                             │
 │                               ▒▒                   Written in Rust by Vale, Akin, Kika, Vitalina                     ▒▒▒▒▒▒▒▒▒▒                    │
 │                                ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒                                                                                ▒▒▒▒▒▒               │
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::data::npc_defs::NpcDefId;
+
+/// Jokes found in the joke book. They do not get better with age.
+pub const JOKES: [&str; 5] = [
+    "Why did the skeleton not fight? It didn't have the guts.",
+    "What do you call a goblin with a pickaxe? Whatever it wants.",
+    "How does a slime say goodbye? It doesn't. It just oozes away.",
+    "Why are dungeons so cold? Too many drafty corridors.",
+    "What is a frog's favourite drink? Croak-a-cola.",
+];
+
+/// Lazy loads the lines NPCs say when the player talks to them, keyed by NPC definition.
+///
+/// NPCs without lines cannot be talked to.
+pub fn npc_dialogue() -> &'static HashMap<NpcDefId, Vec<&'static str>> {
+    static NPC_DIALOGUE: OnceLock<HashMap<NpcDefId, Vec<&'static str>>> = OnceLock::new();
+    NPC_DIALOGUE.get_or_init(|| {
+        let mut m = HashMap::new();
+        m.insert(
+            "funny_frog".to_string(),
+            vec!["Ribbit.", "Croak!", "Ribbit ribbit.", "*stares at you knowingly*"],
+        );
+        m.insert(
+            "shopkeeper".to_string(),
+            vec![
+                "Fine wares for fine adventurers. And for you.",
+                "No refunds. Especially not for the dead.",
+                "Prices are fair. Mostly.",
+            ],
+        );
+        m.insert(
+            "healer".to_string(),
+            vec![
+                "Back from the anthill already? Let me see those wounds.",
+                "Ants bite. I stitch. That is how it has always been.",
+                "Healing is not free, but dying costs more.",
+            ],
+        );
+        m
+    })
+}
+
+/// Gets the dialogue lines of an NPC definition.
+pub fn get_dialogue(npc_def_id: &str) -> Option<&'static Vec<&'static str>> {
+    npc_dialogue().get(npc_def_id)
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::iter;
+use std::sync::OnceLock;
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::world::biome::Biome;
+
+pub type EncounterId = String;
+
+/// Chance (in percent) that a room with enemies holds a themed encounter instead of monsters picked one by one.
+pub const ENCOUNTER_CHANCE: u32 = 35;
+
+/// A themed group of monsters that is placed together in a room, like a goblin warren or a necromancer with its skeletons.
+#[derive(Clone, Debug)]
+pub struct EncounterDef {
+    /// Monster that leads the group. Leaders only spawn with their encounter.
+    pub leader: &'static str,
+    /// Monsters that accompany the leader, with how many of each.
+    pub followers: &'static [(&'static str, usize)],
+    /// Danger rating a level needs before the encounter can appear.
+    pub danger: u8,
+    /// Biomes the encounter appears in. Empty if it appears in all of them.
+    pub biomes: &'static [Biome],
+    /// Weight of the encounter when one is rolled.
+    pub weight: u32,
+}
+
+impl EncounterDef {
+    /// All monsters of the encounter, starting with the leader.
+    pub fn members(&self) -> impl Iterator<Item = &'static str> + '_ {
+        iter::once(self.leader).chain(
+            self.followers.iter().flat_map(|(def_id, count)| iter::repeat_n(*def_id, *count)),
+        )
+    }
+}
+
+/// Lazy loads the collection of encounter definitions in the game.
+pub fn encounter_defs() -> &'static HashMap<EncounterId, EncounterDef> {
+    static ENCOUNTER_DEFS: OnceLock<HashMap<EncounterId, EncounterDef>> = OnceLock::new();
+    ENCOUNTER_DEFS.get_or_init(|| {
+        let mut m = HashMap::new();
+        m.insert(
+            "goblin_warren".to_string(),
+            EncounterDef {
+                leader: "goblin_boss",
+                followers: &[("goblin", 3)],
+                danger: 2,
+                biomes: &[Biome::Dungeon, Biome::Mines],
+                weight: 40,
+            },
+        );
+        m.insert(
+            "necromancer".to_string(),
+            EncounterDef {
+                leader: "necromancer",
+                followers: &[("skeleton", 3)],
+                danger: 4,
+                biomes: &[],
+                weight: 25,
+            },
+        );
+        m
+    })
+}
+
+/// Whether the NPC leads an encounter, so it is not picked on its own (see [EncounterDef::leader]).
+pub fn is_encounter_leader(def_id: &str) -> bool {
+    encounter_defs().values().any(|def| def.leader == def_id)
+}
+
+/// Randomly picks a themed encounter for a room with enemies, with a chance of [ENCOUNTER_CHANCE] percent.
+///
+/// Only encounters of the biome that do not exceed the danger rating of the level are considered.
+///
+/// # Returns
+/// * `None` if the room gets monsters picked one by one.
+pub fn roll_encounter<R: Rng + ?Sized>(
+    biome: Biome,
+    danger: u8,
+    rng: &mut R,
+) -> Option<&'static EncounterDef> {
+    if rng.random_range(0..100) >= ENCOUNTER_CHANCE {
+        return None;
+    }
+
+    let mut encounters: Vec<(&EncounterId, &EncounterDef)> = encounter_defs()
+        .iter()
+        .filter(|(_, def)| def.danger <= danger)
+        .filter(|(_, def)| def.biomes.is_empty() || def.biomes.contains(&biome))
+        .collect();
+    encounters.sort_by_key(|(encounter_id, _)| *encounter_id); // HashMaps are random, so the encounters are sorted to keep seeds deterministic.
+
+    encounters.choose_weighted(rng, |(_, def)| def.weight).ok().map(|(_, def)| *def)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::npc_defs::npc_defs;
+
+    #[test]
+    fn encounters_reference_defined_npcs() {
+        for def in encounter_defs().values() {
+            for def_id in def.members() {
+                assert!(npc_defs().contains_key(def_id), "Unknown npc {}", def_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::world::lighting::RoomLighting;
+
+pub type FloorAffixId = String;
+
+/// Chance (in percent) that a generated level gets a floor affix.
+pub const FLOOR_AFFIX_CHANCE: u32 = 25;
+
+/// Factor by which the spawn weight of the monsters favoured by a floor affix is multiplied.
+pub const AFFIX_NPC_WEIGHT_FACTOR: u32 = 3;
+
+/// A bundle of modifiers that makes a generated level stand out. It is announced when the player arrives on the level.
+///
+/// Each modifier only affects the level the affix was rolled for.
+#[derive(Clone, Debug)]
+pub struct FloorAffixDef {
+    pub name: &'static str,
+    /// Message that is logged when the player arrives on the level.
+    pub announcement: &'static str,
+    /// Shallowest level on which the affix can appear.
+    pub min_depth: usize,
+    /// Weight of the affix when one is rolled.
+    pub weight: u32,
+    /// Number of extra lakes and rivers that are added to the level.
+    pub water_features: usize,
+    /// Monsters that spawn [AFFIX_NPC_WEIGHT_FACTOR] times as often on the level.
+    pub favoured_npcs: &'static [&'static str],
+    /// Lighting of every room of the level. Also limits the vision on the rest of the level.
+    pub lighting: Option<RoomLighting>,
+    /// NPCs that are spawned far from the entry and hunt the player across the whole level.
+    pub hunters: &'static [&'static str],
+}
+
+impl FloorAffixDef {
+    /// Maximum vision radius (in tiles) of the player anywhere on the level.
+    ///
+    /// # Returns
+    /// * `None` if the affix does not limit vision.
+    pub fn vision_radius(&self) -> Option<usize> {
+        self.lighting.and_then(|lighting| lighting.vision_radius())
+    }
+}
+
+/// Lazy loads the collection of floor affix definitions in the game.
+pub fn floor_affix_defs() -> &'static HashMap<FloorAffixId, FloorAffixDef> {
+    static FLOOR_AFFIX_DEFS: OnceLock<HashMap<FloorAffixId, FloorAffixDef>> = OnceLock::new();
+    FLOOR_AFFIX_DEFS.get_or_init(|| {
+        let mut m = HashMap::new();
+        m.insert(
+            "flooded".to_string(),
+            FloorAffixDef {
+                name: "Flooded",
+                announcement: "This floor is flooded.",
+                min_depth: 2,
+                weight: 40,
+                water_features: 4,
+                favoured_npcs: &["slime", "funny_frog", "giant_rat"],
+                lighting: None,
+                hunters: &[],
+            },
+        );
+        m.insert(
+            "hunting_party".to_string(),
+            FloorAffixDef {
+                name: "Hunting Party",
+                announcement: "A hunting party stalks this floor.",
+                min_depth: 5,
+                weight: 30,
+                water_features: 0,
+                favoured_npcs: &["wolf"],
+                lighting: None,
+                hunters: &["orc", "wolf", "wolf"],
+            },
+        );
+        m.insert(
+            "darkness".to_string(),
+            FloorAffixDef {
+                name: "Darkness",
+                announcement: "Darkness reigns here.",
+                min_depth: 4,
+                weight: 30,
+                water_features: 0,
+                favoured_npcs: &["skeleton", "assassin", "cultist"],
+                lighting: Some(RoomLighting::Dim),
+                hunters: &[],
+            },
+        );
+        m
+    })
+}
+
+pub fn get_floor_affix_by_id(affix_id: &str) -> Option<&'static FloorAffixDef> {
+    floor_affix_defs().get(affix_id)
+}
+
+/// Randomly picks the floor affix of a generated level of the given depth, with a chance of [FLOOR_AFFIX_CHANCE] percent.
+///
+/// # Returns
+/// * `None` if the level gets no affix.
+pub fn roll_floor_affix<R: Rng + ?Sized>(depth: usize, rng: &mut R) -> Option<FloorAffixId> {
+    if rng.random_range(0..100) >= FLOOR_AFFIX_CHANCE {
+        return None;
+    }
+
+    let mut affixes: Vec<(&FloorAffixId, &FloorAffixDef)> =
+        floor_affix_defs().iter().filter(|(_, def)| def.min_depth <= depth).collect();
+    affixes.sort_by_key(|(affix_id, _)| *affix_id); // HashMaps are random, so the affixes are sorted to keep seeds deterministic.
+
+    affixes
+        .choose_weighted(rng, |(_, def)| def.weight)
+        .ok()
+        .map(|(affix_id, _)| affix_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::npc_defs::npc_defs;
+
+    #[test]
+    fn affixes_reference_defined_npcs() {
+        for def in floor_affix_defs().values() {
+            for def_id in def.favoured_npcs.iter().chain(def.hunters) {
+                assert!(npc_defs().contains_key(*def_id), "Unknown npc {}", def_id);
+            }
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
-use ratatui::style::{Color, Style};
-
 use crate::{
     ai::npc_ai::AGGRO_RADIUS,
-    core::{buff_effects::PotionEffectDef, game_items::GameItemKindDef},
+    core::{
+        buff_effects::PotionEffectDef,
+        damage_types::{DamageAffinities, DamageType},
+        game_items::GameItemKindDef,
+        resistances::Resistances,
+    },
+    data::mods::merge_content_packs,
+    render::style::{Color, Style},
     util::rng::{DieSize, Roll},
 };
 
@@ -16,6 +21,10 @@ pub struct GameItemDef {
     pub name: &'static str,
     pub glyph: char,
     pub style: Style,
+    /// Base price of the item in gold. Shops adjust it (see [shop](crate::core::shop)).
+    pub value: u32,
+    /// How heavy the item is. Carrying more than the player's capacity slows them down (see [encumbrance](crate::core::encumbrance)).
+    pub weight: u16,
     pub kind: GameItemKindDef,
 }
 
@@ -32,10 +41,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Dull Sword",
                 glyph: '/',
                 style: Style::default().fg(Color::Gray),
+                value: 8,
+                weight: 5,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(1, DieSize::D10),
                     crit_chance: 5,
                     range: None,
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -45,10 +57,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Shortbow",
                 glyph: 'D',
                 style: Style::default().fg(Color::Gray),
+                value: 12,
+                weight: 3,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(1, DieSize::D4),
                     crit_chance: 5,
                     range: Some(AGGRO_RADIUS),
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -58,10 +73,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Longbow",
                 glyph: 'D',
                 style: Style::default().fg(Color::DarkGray),
+                value: 30,
+                weight: 4,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(1, DieSize::D6),
                     crit_chance: 5,
                     range: Some(AGGRO_RADIUS),
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -71,10 +89,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Crossbow",
                 glyph: 'B',
                 style: Style::default().fg(Color::Yellow),
+                value: 60,
+                weight: 6,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(1, DieSize::D4),
                     crit_chance: 15,
                     range: Some(AGGRO_RADIUS),
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -84,10 +105,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Iron Mace",
                 glyph: '/',
                 style: Style::default().fg(Color::Yellow),
+                value: 25,
+                weight: 6,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(2, DieSize::D10),
                     crit_chance: 5,
                     range: None,
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -97,10 +121,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Iron Axe",
                 glyph: '/',
                 style: Style::default().fg(Color::DarkGray),
+                value: 25,
+                weight: 6,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(2, DieSize::D6),
                     crit_chance: 7,
                     range: None,
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -110,10 +137,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Sharp Dagger",
                 glyph: '\\',
                 style: Style::default().fg(Color::White),
+                value: 6,
+                weight: 1,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(1, DieSize::D8),
                     crit_chance: 15,
                     range: None,
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -123,10 +153,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Warhammer",
                 glyph: '/',
                 style: Style::default().fg(Color::Red),
+                value: 60,
+                weight: 10,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(2, DieSize::D12),
                     crit_chance: 5,
                     range: None,
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -136,10 +169,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Short Sword",
                 glyph: '/',
                 style: Style::default().fg(Color::Gray),
+                value: 15,
+                weight: 4,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(1, DieSize::D10).add_modifier(1),
                     crit_chance: 10,
                     range: None,
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -149,10 +185,45 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Spear",
                 glyph: '/',
                 style: Style::default().fg(Color::White),
+                value: 12,
+                weight: 5,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(1, DieSize::D8),
                     crit_chance: 8,
                     range: Some(2),
+                    damage_type: DamageType::Physical,
+                },
+            },
+        );
+        m.insert(
+            "weapon_sword_flame".to_string(),
+            GameItemDef {
+                name: "Flame Sword",
+                glyph: '/',
+                style: Style::default().fg(Color::LightRed),
+                value: 60,
+                weight: 4,
+                kind: GameItemKindDef::Weapon {
+                    damage: Roll::new(1, DieSize::D8),
+                    crit_chance: 5,
+                    range: None,
+                    damage_type: DamageType::Fire,
+                },
+            },
+        );
+        m.insert(
+            "weapon_bow_frost".to_string(),
+            GameItemDef {
+                name: "Frost Bow",
+                glyph: 'D',
+                style: Style::default().fg(Color::LightCyan),
+                value: 55,
+                weight: 3,
+                kind: GameItemKindDef::Weapon {
+                    damage: Roll::new(1, DieSize::D6),
+                    crit_chance: 5,
+                    range: Some(AGGRO_RADIUS),
+                    damage_type: DamageType::Cold,
                 },
             },
         );
@@ -162,10 +233,29 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Rustacean Claw",
                 glyph: '/',
                 style: Style::default().fg(Color::Red),
+                value: 80,
+                weight: 4,
                 kind: GameItemKindDef::Weapon {
                     damage: Roll::new(2, DieSize::D8),
                     crit_chance: 15,
                     range: None,
+                    damage_type: DamageType::Physical,
+                },
+            },
+        );
+        m.insert(
+            "weapon_grak_cleaver".to_string(),
+            GameItemDef {
+                name: "Grak's Cleaver",
+                glyph: '/',
+                style: Style::default().fg(Color::LightYellow),
+                value: 90,
+                weight: 5,
+                kind: GameItemKindDef::Weapon {
+                    damage: Roll::new(1, DieSize::D12).add_modifier(1),
+                    crit_chance: 10,
+                    range: None,
+                    damage_type: DamageType::Physical,
                 },
             },
         );
@@ -175,7 +265,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Leather Armor",
                 glyph: 'A',
                 style: Style::default().fg(Color::Yellow),
-                kind: GameItemKindDef::Armor { mitigation: 2 },
+                value: 8,
+                weight: 6,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 2,
+                    resistances: Resistances::NONE,
+                    affinities: DamageAffinities { cold: 1, ..DamageAffinities::NONE },
+                },
             },
         );
         m.insert(
@@ -184,7 +280,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Chainmail Armor",
                 glyph: 'A',
                 style: Style::default().fg(Color::LightBlue),
-                kind: GameItemKindDef::Armor { mitigation: 4 },
+                value: 30,
+                weight: 12,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 4,
+                    resistances: Resistances { knockback: 25, ..Resistances::NONE },
+                    affinities: DamageAffinities { cold: -1, ..DamageAffinities::NONE },
+                },
             },
         );
         m.insert(
@@ -193,7 +295,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Plate Armor",
                 glyph: 'A',
                 style: Style::default().fg(Color::Gray),
-                kind: GameItemKindDef::Armor { mitigation: 5 },
+                value: 70,
+                weight: 20,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 5,
+                    resistances: Resistances { knockback: 50, ..Resistances::NONE },
+                    affinities: DamageAffinities { fire: -1, cold: -1, ..DamageAffinities::NONE },
+                },
             },
         );
         m.insert(
@@ -202,7 +310,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Steel Helmet",
                 glyph: 'A',
                 style: Style::default().fg(Color::Gray),
-                kind: GameItemKindDef::Armor { mitigation: 2 },
+                value: 12,
+                weight: 3,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 2,
+                    resistances: Resistances::NONE,
+                    affinities: DamageAffinities::NONE,
+                },
             },
         );
         m.insert(
@@ -211,7 +325,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Iron Gauntlets",
                 glyph: 'A',
                 style: Style::default().fg(Color::DarkGray),
-                kind: GameItemKindDef::Armor { mitigation: 1 },
+                value: 10,
+                weight: 2,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 1,
+                    resistances: Resistances::NONE,
+                    affinities: DamageAffinities::NONE,
+                },
             },
         );
         m.insert(
@@ -220,7 +340,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Wooden Shield",
                 glyph: 'A',
                 style: Style::default().fg(Color::Yellow),
-                kind: GameItemKindDef::Armor { mitigation: 3 },
+                value: 15,
+                weight: 6,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 3,
+                    resistances: Resistances { knockback: 25, ..Resistances::NONE },
+                    affinities: DamageAffinities { fire: 1, ..DamageAffinities::NONE },
+                },
             },
         );
         m.insert(
@@ -229,7 +355,13 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Cloak of Shadows",
                 glyph: 'A',
                 style: Style::default().fg(Color::Black),
-                kind: GameItemKindDef::Armor { mitigation: 2 },
+                value: 10,
+                weight: 2,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 2,
+                    resistances: Resistances::NONE,
+                    affinities: DamageAffinities { fire: -1, cold: 2, ..DamageAffinities::NONE },
+                },
             },
         );
         m.insert(
@@ -238,7 +370,28 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Rustacean Armor",
                 glyph: 'A',
                 style: Style::default().fg(Color::Red),
-                kind: GameItemKindDef::Armor { mitigation: 6 },
+                value: 90,
+                weight: 14,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 6,
+                    resistances: Resistances { poison: 25, knockback: 50, ..Resistances::NONE },
+                    affinities: DamageAffinities { cold: 2, poison: 2, ..DamageAffinities::NONE },
+                },
+            },
+        );
+        m.insert(
+            "armor_morwen_shroud".to_string(),
+            GameItemDef {
+                name: "Shroud of Morwen",
+                glyph: 'A',
+                style: Style::default().fg(Color::White),
+                value: 90,
+                weight: 2,
+                kind: GameItemKindDef::Armor {
+                    mitigation: 2,
+                    resistances: Resistances { poison: 0, fear: 50, knockback: 0 },
+                    affinities: DamageAffinities { cold: 3, arcane: 3, ..DamageAffinities::NONE },
+                },
             },
         );
         m.insert(
@@ -247,6 +400,8 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Cake",
                 glyph: '%',
                 style: Style::default().fg(Color::Red),
+                value: 10,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 1 },
             },
         );
@@ -256,6 +411,8 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Grapefruit",
                 glyph: '%',
                 style: Style::default().fg(Color::LightRed),
+                value: 3,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 2 },
             },
         );
@@ -265,6 +422,8 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Honey Jar",
                 glyph: '%',
                 style: Style::default().fg(Color::Yellow),
+                value: 5,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 4 },
             },
         );
@@ -274,6 +433,8 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Cooked Fish",
                 glyph: '%',
                 style: Style::default().fg(Color::Blue),
+                value: 4,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 6 },
             },
         );
@@ -283,6 +444,8 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Mushroom",
                 glyph: '%',
                 style: Style::default().fg(Color::Green),
+                value: 2,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 1 },
             },
         );
@@ -292,15 +455,63 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Cooked Meat",
                 glyph: '%',
                 style: Style::default().fg(Color::Red),
+                value: 5,
+                weight: 2,
                 kind: GameItemKindDef::Food { nutrition: 7 },
             },
         );
+        m.insert(
+            "food_meat_raw".to_string(),
+            GameItemDef {
+                name: "Raw Meat",
+                glyph: '%',
+                style: Style::default().fg(Color::LightRed),
+                value: 3,
+                weight: 2,
+                kind: GameItemKindDef::Food { nutrition: 2 },
+            },
+        );
+        m.insert(
+            "food_fish_raw".to_string(),
+            GameItemDef {
+                name: "Raw Fish",
+                glyph: '%',
+                style: Style::default().fg(Color::LightBlue),
+                value: 2,
+                weight: 1,
+                kind: GameItemKindDef::Food { nutrition: 2 },
+            },
+        );
+        m.insert(
+            "food_mushroom_roasted".to_string(),
+            GameItemDef {
+                name: "Roast Mushroom",
+                glyph: '%',
+                style: Style::default().fg(Color::LightGreen),
+                value: 3,
+                weight: 1,
+                kind: GameItemKindDef::Food { nutrition: 3 },
+            },
+        );
+        m.insert(
+            "food_burnt".to_string(),
+            GameItemDef {
+                name: "Burnt Food",
+                glyph: '%',
+                style: Style::default().fg(Color::DarkGray),
+                value: 0,
+                weight: 1,
+                kind: GameItemKindDef::Food { nutrition: 1 },
+            },
+        );
         m.insert(
             "food_apple".to_string(),
             GameItemDef {
                 name: "Apple",
                 glyph: '%',
                 style: Style::default().fg(Color::Red),
+                value: 2,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 2 },
             },
         );
@@ -310,6 +521,8 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Loaf of Bread",
                 glyph: '%',
                 style: Style::default().fg(Color::Yellow),
+                value: 3,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 5 },
             },
         );
@@ -319,15 +532,96 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Cheese",
                 glyph: '%',
                 style: Style::default().fg(Color::LightYellow),
+                value: 4,
+                weight: 1,
                 kind: GameItemKindDef::Food { nutrition: 3 },
             },
         );
+        m.insert(
+            "tool_flint_tinder".to_string(),
+            GameItemDef {
+                name: "Flint&Tinder",
+                glyph: '~',
+                style: Style::default().fg(Color::LightRed),
+                value: 8,
+                weight: 1,
+                kind: GameItemKindDef::FireStarter,
+            },
+        );
+        m.insert(
+            "misc_joke_book".to_string(),
+            GameItemDef {
+                name: "Joke Book",
+                glyph: '?',
+                style: Style::default().fg(Color::LightYellow),
+                value: 5,
+                weight: 2,
+                kind: GameItemKindDef::JokeBook,
+            },
+        );
+        m.insert(
+            "scroll_remove_curse".to_string(),
+            GameItemDef {
+                name: "Scroll of Uncursing",
+                glyph: '?',
+                style: Style::default().fg(Color::LightCyan),
+                value: 30,
+                weight: 1,
+                kind: GameItemKindDef::RemoveCurse,
+            },
+        );
+        m.insert(
+            "trinket_locket".to_string(),
+            GameItemDef {
+                name: "Old Locket",
+                glyph: 'o',
+                style: Style::default().fg(Color::LightYellow),
+                value: 5,
+                weight: 1,
+                kind: GameItemKindDef::Trinket,
+            },
+        );
+        m.insert(
+            "trinket_idol".to_string(),
+            GameItemDef {
+                name: "Ant Idol",
+                glyph: 'a',
+                style: Style::default().fg(Color::LightRed),
+                value: 5,
+                weight: 1,
+                kind: GameItemKindDef::Trinket,
+            },
+        );
+        m.insert(
+            "trinket_signet".to_string(),
+            GameItemDef {
+                name: "Signet Ring",
+                glyph: 'o',
+                style: Style::default().fg(Color::Yellow),
+                value: 5,
+                weight: 1,
+                kind: GameItemKindDef::Trinket,
+            },
+        );
+        m.insert(
+            "misc_bag_leather".to_string(),
+            GameItemDef {
+                name: "Leather Bag",
+                glyph: '&',
+                style: Style::default().fg(Color::Yellow),
+                value: 25,
+                weight: 1,
+                kind: GameItemKindDef::Bag { capacity: 8 },
+            },
+        );
         m.insert(
             "potion_healing_small".to_string(),
             GameItemDef {
                 name: "Small Healing Potion",
                 glyph: '!',
                 style: Style::default().fg(Color::Magenta),
+                value: 15,
+                weight: 1,
                 kind: GameItemKindDef::Potion { effect: PotionEffectDef::Heal { amount: 20 } },
             },
         );
@@ -337,6 +631,8 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Potion of Strength",
                 glyph: '!',
                 style: Style::default().fg(Color::Magenta),
+                value: 25,
+                weight: 1,
                 kind: GameItemKindDef::Potion {
                     effect: PotionEffectDef::Strength { amount: 3, duration: 100 },
                 },
@@ -348,11 +644,27 @@ pub fn item_defs() -> &'static HashMap<GameItemDefId, GameItemDef> {
                 name: "Potion of Dexterity",
                 glyph: '!',
                 style: Style::default().fg(Color::Blue),
+                value: 25,
+                weight: 1,
                 kind: GameItemKindDef::Potion {
                     effect: PotionEffectDef::Dexterity { amount: 2, duration: 100 },
                 },
             },
         );
+        m.insert(
+            "potion_see_invisible".to_string(),
+            GameItemDef {
+                name: "Potion of See Invisible",
+                glyph: '!',
+                style: Style::default().fg(Color::LightCyan),
+                value: 30,
+                weight: 1,
+                kind: GameItemKindDef::Potion {
+                    effect: PotionEffectDef::SeeInvisible { duration: 60 },
+                },
+            },
+        );
+        merge_content_packs(&mut m, "item", |pack| &pack.items);
         m
     })
 }
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use rand::{Rng, seq::IndexedRandom};
+
+pub type LootTableId = String;
+
+/// A weighted table of items that can be dropped by NPCs or found in treasure rooms.
+///
+/// Rolling a table yields all `guaranteed` items plus `rolls` picks from the weighted `pool`.
+/// Pool entries can be restricted to deeper levels and can scale their weight with depth, so better items show up more often the deeper you go.
+#[derive(Clone, Debug)]
+pub struct LootTable {
+    /// Items that are always dropped.
+    pub guaranteed: Vec<&'static str>,
+    /// Weighted entries that are picked from `rolls` times.
+    pub pool: Vec<LootEntry>,
+    /// Number of picks from the pool.
+    pub rolls: u8,
+}
+
+/// An entry in the weighted pool of a [LootTable].
+#[derive(Clone, Copy, Debug)]
+pub struct LootEntry {
+    /// Item that is dropped. `None` means that nothing drops.
+    pub item_def_id: Option<&'static str>,
+    /// Weight of the entry on level 0.
+    pub weight: u32,
+    /// Change of the weight per level of depth. Negative values make an entry rarer the deeper you go.
+    pub weight_per_depth: i32,
+    /// Shallowest level on which the entry can drop.
+    pub min_depth: usize,
+}
+
+impl LootEntry {
+    /// An item with a fixed weight, available on every level.
+    fn item(item_def_id: &'static str, weight: u32) -> Self {
+        Self { item_def_id: Some(item_def_id), weight, weight_per_depth: 0, min_depth: 0 }
+    }
+
+    /// An item that only drops from level `min_depth` onwards, with a weight that changes with depth.
+    fn deep_item(
+        item_def_id: &'static str,
+        weight: u32,
+        weight_per_depth: i32,
+        min_depth: usize,
+    ) -> Self {
+        Self { item_def_id: Some(item_def_id), weight, weight_per_depth, min_depth }
+    }
+
+    /// An entry that drops nothing.
+    fn nothing(weight: u32) -> Self {
+        Self { item_def_id: None, weight, weight_per_depth: 0, min_depth: 0 }
+    }
+
+    /// Weight of the entry at the given depth. Entries that are not available at that depth have a weight of 0.
+    pub fn weight_at(&self, depth: usize) -> u32 {
+        if depth < self.min_depth {
+            return 0;
+        }
+
+        let scaled = self.weight as i64 + self.weight_per_depth as i64 * depth as i64;
+        scaled.clamp(0, u32::MAX as i64) as u32
+    }
+}
+
+impl LootTable {
+    /// Rolls the table for the given depth.
+    ///
+    /// # Returns
+    /// The item def ids of all dropped items. Can be empty.
+    pub fn roll<R: Rng + ?Sized>(&self, depth: usize, rng: &mut R) -> Vec<&'static str> {
+        let mut drops: Vec<&'static str> = self.guaranteed.clone();
+
+        for _ in 0..self.rolls {
+            if let Ok(entry) = self.pool.choose_weighted(rng, |entry| entry.weight_at(depth))
+                && let Some(item_def_id) = entry.item_def_id
+            {
+                drops.push(item_def_id);
+            }
+        }
+
+        drops
+    }
+}
+
+/// Lazy loads the collection of loot tables in the game.
+pub fn loot_tables() -> &'static HashMap<LootTableId, LootTable> {
+    static LOOT_TABLES: OnceLock<HashMap<LootTableId, LootTable>> = OnceLock::new();
+    LOOT_TABLES.get_or_init(|| {
+        let mut m = HashMap::new();
+        m.insert("nothing".to_string(), LootTable { guaranteed: vec![], pool: vec![], rolls: 0 });
+        m.insert(
+            "treasure_room".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(10),
+                    // Weapons
+                    LootEntry::deep_item("weapon_sword_dull", 20, -1, 0),
+                    LootEntry::deep_item("weapon_dagger", 15, -1, 0),
+                    LootEntry::item("weapon_short_sword", 12),
+                    LootEntry::item("weapon_spear", 10),
+                    LootEntry::item("weapon_bow_short", 10),
+                    LootEntry::deep_item("weapon_mace", 8, 1, 3),
+                    LootEntry::deep_item("weapon_axe_iron", 8, 1, 3),
+                    LootEntry::deep_item("weapon_bow_long", 5, 1, 4),
+                    LootEntry::deep_item("weapon_bow_cross", 3, 1, 8),
+                    LootEntry::deep_item("weapon_sword_flame", 3, 1, 4),
+                    LootEntry::deep_item("weapon_bow_frost", 3, 1, 4),
+                    LootEntry::deep_item("weapon_warhammer", 3, 1, 8),
+                    // Armor
+                    LootEntry::deep_item("armor_leather", 15, -1, 0),
+                    LootEntry::item("armor_helmet", 10),
+                    LootEntry::item("armor_gauntlets", 10),
+                    LootEntry::item("armor_shield", 8),
+                    LootEntry::item("armor_cloak", 8),
+                    LootEntry::deep_item("armor_chainmail", 5, 1, 3),
+                    LootEntry::deep_item("armor_plate", 2, 1, 8),
+                    // Food
+                    LootEntry::item("food_apple", 6),
+                    LootEntry::item("food_bread", 6),
+                    LootEntry::item("food_cheese", 6),
+                    LootEntry::item("food_fish", 6),
+                    LootEntry::item("food_grapefruit", 6),
+                    LootEntry::item("food_honey", 6),
+                    LootEntry::item("food_meat", 6),
+                    LootEntry::item("food_mushroom", 6),
+                    LootEntry::item("food_meat_raw", 6),
+                    LootEntry::item("food_fish_raw", 6),
+                    // Tools
+                    LootEntry::item("tool_flint_tinder", 5),
+                    LootEntry::item("misc_joke_book", 2),
+                    LootEntry::item("scroll_remove_curse", 3),
+                    LootEntry::item("misc_bag_leather", 3),
+                    LootEntry::item("food_cake", 2),
+                    // Potions
+                    LootEntry::item("potion_healing_small", 12),
+                    LootEntry::deep_item("potion_strength", 6, 1, 2),
+                    LootEntry::deep_item("potion_dexterity", 6, 1, 2),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "shop_stock".to_string(),
+            LootTable {
+                guaranteed: vec!["potion_healing_small", "potion_healing_small"],
+                pool: vec![
+                    LootEntry::item("weapon_short_sword", 10),
+                    LootEntry::item("weapon_spear", 10),
+                    LootEntry::item("weapon_bow_short", 10),
+                    LootEntry::deep_item("weapon_mace", 8, 1, 3),
+                    LootEntry::deep_item("weapon_bow_long", 5, 1, 4),
+                    LootEntry::item("armor_helmet", 10),
+                    LootEntry::item("armor_shield", 8),
+                    LootEntry::deep_item("armor_chainmail", 5, 1, 3),
+                    LootEntry::item("food_bread", 12),
+                    LootEntry::item("food_cheese", 12),
+                    LootEntry::item("tool_flint_tinder", 8),
+                    LootEntry::item("misc_bag_leather", 4),
+                    LootEntry::item("scroll_remove_curse", 5),
+                    LootEntry::item("potion_healing_small", 12),
+                    LootEntry::deep_item("potion_strength", 6, 1, 2),
+                    LootEntry::deep_item("potion_dexterity", 6, 1, 2),
+                ],
+                rolls: 4,
+            },
+        );
+        m.insert(
+            "treasure_mines".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(10),
+                    LootEntry::item("weapon_axe_iron", 12),
+                    LootEntry::deep_item("weapon_mace", 10, 1, 3),
+                    LootEntry::deep_item("weapon_warhammer", 4, 1, 8),
+                    LootEntry::item("armor_helmet", 14),
+                    LootEntry::item("armor_gauntlets", 12),
+                    LootEntry::deep_item("armor_chainmail", 6, 1, 3),
+                    LootEntry::item("food_bread", 8),
+                    LootEntry::item("food_meat_raw", 8),
+                    LootEntry::item("tool_flint_tinder", 10),
+                    LootEntry::item("potion_healing_small", 8),
+                    LootEntry::deep_item("potion_strength", 6, 1, 2),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "treasure_crypt".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(15),
+                    LootEntry::item("weapon_short_sword", 12),
+                    LootEntry::item("weapon_mace", 10),
+                    LootEntry::deep_item("weapon_bow_cross", 3, 1, 8),
+                    LootEntry::deep_item("weapon_sword_flame", 4, 1, 3),
+                    LootEntry::item("armor_cloak", 10),
+                    LootEntry::item("armor_shield", 8),
+                    LootEntry::deep_item("armor_plate", 2, 1, 8),
+                    LootEntry::item("potion_healing_small", 15),
+                    LootEntry::deep_item("potion_strength", 8, 1, 2),
+                    LootEntry::deep_item("potion_dexterity", 8, 1, 2),
+                    LootEntry::item("potion_see_invisible", 6),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "treasure_fungal".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(10),
+                    LootEntry::item("food_mushroom", 25),
+                    LootEntry::item("food_honey", 8),
+                    LootEntry::item("food_fish_raw", 8),
+                    LootEntry::item("weapon_spear", 8),
+                    LootEntry::item("weapon_bow_short", 8),
+                    LootEntry::deep_item("weapon_bow_long", 4, 1, 4),
+                    LootEntry::item("armor_leather", 8),
+                    LootEntry::item("potion_healing_small", 12),
+                    LootEntry::deep_item("potion_dexterity", 8, 1, 2),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "beast".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![LootEntry::nothing(40), LootEntry::item("food_meat_raw", 60)],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "vermin".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(70),
+                    LootEntry::item("food_cheese", 20),
+                    LootEntry::item("food_mushroom", 10),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "goblin".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(60),
+                    LootEntry::item("food_bread", 25),
+                    LootEntry::item("tool_flint_tinder", 5),
+                    LootEntry::item("weapon_dagger", 10),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "warrior".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(50),
+                    LootEntry::item("food_meat_raw", 15),
+                    LootEntry::item("food_meat", 10),
+                    LootEntry::deep_item("weapon_axe_iron", 8, 1, 0),
+                    LootEntry::item("armor_leather", 8),
+                    LootEntry::deep_item("armor_chainmail", 2, 1, 4),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "undead".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(80),
+                    LootEntry::item("weapon_short_sword", 10),
+                    LootEntry::item("armor_helmet", 5),
+                    LootEntry::item("armor_gauntlets", 5),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "rogue".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(45),
+                    LootEntry::item("food_apple", 20),
+                    LootEntry::item("potion_healing_small", 15),
+                    LootEntry::item("weapon_dagger", 10),
+                    LootEntry::item("weapon_bow_short", 5),
+                    LootEntry::item("armor_cloak", 5),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "caster".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![
+                    LootEntry::nothing(45),
+                    LootEntry::item("potion_healing_small", 30),
+                    LootEntry::deep_item("potion_strength", 10, 1, 0),
+                    LootEntry::deep_item("potion_dexterity", 10, 1, 0),
+                    LootEntry::item("scroll_remove_curse", 5),
+                    LootEntry::item("potion_see_invisible", 5),
+                ],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "boss_ferris".to_string(),
+            LootTable {
+                guaranteed: vec!["weapon_claw_rustacean"],
+                pool: vec![LootEntry::nothing(1), LootEntry::item("armor_rustacean", 1)],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "boss_martin".to_string(),
+            LootTable {
+                guaranteed: vec!["food_cake"],
+                pool: vec![LootEntry::nothing(1), LootEntry::item("weapon_bow_long", 1)],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "unique_grak".to_string(),
+            LootTable {
+                guaranteed: vec!["weapon_grak_cleaver"],
+                pool: vec![LootEntry::nothing(1), LootEntry::item("potion_strength", 1)],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "unique_morwen".to_string(),
+            LootTable {
+                guaranteed: vec!["armor_morwen_shroud"],
+                pool: vec![LootEntry::nothing(1), LootEntry::item("scroll_remove_curse", 1)],
+                rolls: 1,
+            },
+        );
+        m.insert(
+            "boss_borrowchecker".to_string(),
+            LootTable {
+                guaranteed: vec![],
+                pool: vec![LootEntry::nothing(1), LootEntry::item("potion_strength", 1)],
+                rolls: 1,
+            },
+        );
+        m
+    })
+}
+
+/// Gets a loot table by its id.
+pub fn get_loot_table_by_id(loot_table_id: &str) -> Option<&'static LootTable> {
+    loot_tables().get(loot_table_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::data::{item_defs::item_defs, npc_defs::npc_defs};
+
+    #[test]
+    fn guaranteed_drops_always_drop() {
+        let mut rng = StdRng::seed_from_u64(73);
+        let table = get_loot_table_by_id("boss_ferris").unwrap();
+
+        for depth in 0..20 {
+            assert!(table.roll(depth, &mut rng).contains(&"weapon_claw_rustacean"));
+        }
+    }
+
+    #[test]
+    fn deep_items_do_not_drop_early() {
+        let entry = LootEntry::deep_item("weapon_warhammer", 3, 1, 8);
+
+        assert_eq!(entry.weight_at(7), 0);
+        assert_eq!(entry.weight_at(8), 11);
+        assert_eq!(LootEntry::deep_item("weapon_dagger", 15, -1, 0).weight_at(30), 0);
+    }
+
+    #[test]
+    fn loot_tables_reference_defined_items() {
+        for table in loot_tables().values() {
+            let pool_items = table.pool.iter().filter_map(|entry| entry.item_def_id);
+            for item_def_id in table.guaranteed.iter().copied().chain(pool_items) {
+                assert!(item_defs().contains_key(item_def_id), "Unknown item {}", item_def_id);
+            }
+        }
+
+        for npc_def in npc_defs().values() {
+            assert!(
+                loot_tables().contains_key(npc_def.loot_table),
+                "Unknown table of {}",
+                npc_def.name
+            );
+        }
+    }
+}
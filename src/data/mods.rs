@@ -0,0 +1,481 @@
+//! Content packs that add items, NPCs and vaults to the game without changing the crate.
+//!
+//! Every directory in the mods directory (see [mods_path]) is a content pack, which can contain:
+//!
+//! * `items.ron` - Map of item def_ids to [ModItemDef]s.
+//! * `npcs.ron` - Map of NPC def_ids to [ModNpcDef]s.
+//! * `vaults/*.ron` - [VaultData] files, like the ones in `assets/vaults`.
+//! * `themes.ron` - Map of theme names to [ModThemeDef]s. They are picked with the `theme` command like the built-in ones.
+//!
+//! Packs are merged with the built-in content in alphabetical order. A definition whose id is already taken, by the game or
+//! by an earlier pack, is skipped. So are a theme whose name is taken and a vault that spawns something nobody defined. Both are reported in the log when the
+//! game starts (see [mod_report]).
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use crate::{
+    ai::behavior::{AGGRESSIVE_BEHAVIOR, FLEEING_BEHAVIOR, STALKING_BEHAVIOR, WANDERING_BEHAVIOR},
+    core::{
+        damage_types::{DamageAffinities, DamageType},
+        entity_logic::{BaseStats, Disposition, NpcStats},
+        game_items::GameItemKindDef,
+        resistances::Resistances,
+    },
+    data::{
+        item_defs::{GameItemDef, GameItemDefId, item_defs},
+        npc_defs::{NpcDef, NpcDefId, npc_defs},
+        vaults::{VaultData, vault_defs},
+    },
+    render::{
+        style::{Color, Style},
+        theme::{PackTheme, ThemeId},
+    },
+    util::{
+        errors_results::{DataError, GameError, IoError},
+        rng::Roll,
+    },
+    world::{level_data::SpawnKind, level_loader::load_vault_from_ron},
+};
+
+/// An item as defined by a content pack. Only the simpler kinds of items can be added.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ModItemDef {
+    pub name: String,
+    /// Any single character. Wide ones like emoji are drawn in two cells by the terminal UI.
+    pub glyph: char,
+    /// Name of the color (e.g. `"red"` or `"#ff8800"`). Defaults to white.
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub value: u32,
+    #[serde(default)]
+    pub weight: u16,
+    pub kind: ModItemKind,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum ModItemKind {
+    Weapon {
+        damage: Roll,
+        #[serde(default)]
+        crit_chance: u8,
+        /// Range of a ranged weapon. Melee weapons have none.
+        #[serde(default)]
+        range: Option<usize>,
+    },
+    Armor {
+        mitigation: u16,
+    },
+    Food {
+        nutrition: u16,
+    },
+    Trinket,
+}
+
+/// A hostile monster as defined by a content pack.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ModNpcDef {
+    pub name: String,
+    /// Any single character. Wide ones like emoji are drawn in two cells by the terminal UI.
+    pub glyph: char,
+    /// Name of the color (e.g. `"red"` or `"#ff8800"`). Defaults to white.
+    #[serde(default)]
+    pub color: Option<String>,
+    pub hp: u16,
+    pub damage: Roll,
+    #[serde(default)]
+    pub dodge: u8,
+    #[serde(default)]
+    pub mitigation: u16,
+    /// Id of the loot table rolled for the corpse. Defaults to no loot.
+    #[serde(default)]
+    pub loot_table: Option<String>,
+    pub danger: u8,
+    /// Id of the AI behavior (`aggressive`, `fleeing`, `stalking` or `wandering`). Defaults to `aggressive`.
+    #[serde(default)]
+    pub behavior: Option<String>,
+}
+
+/// A color theme as defined by a content pack.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ModThemeDef {
+    /// Name of the built-in theme whose colors are changed. Defaults to `default`.
+    #[serde(default)]
+    pub base: Option<String>,
+    /// Names of the colors the game uses, and the colors they are replaced with (e.g. `"red": "#d55e00"`).
+    pub colors: HashMap<String, String>,
+}
+
+/// Definitions read from one content pack.
+#[derive(Clone, Default)]
+pub struct ContentPack {
+    /// Name of the pack's directory.
+    pub name: String,
+    pub items: Vec<(GameItemDefId, GameItemDef)>,
+    pub npcs: Vec<(NpcDefId, NpcDef)>,
+    pub vaults: Vec<VaultData>,
+    pub themes: Vec<PackTheme>,
+}
+
+/// Path of the directory the content packs are read from.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn mods_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("mods");
+    Some(path)
+}
+
+/// Lazy loads the content packs in the [mods_path]. Packs that cannot be loaded are skipped and reported.
+///
+/// Tests only use the built-in content, so they don't depend on the mods installed on the machine.
+pub fn content_packs() -> &'static Vec<ContentPack> {
+    static CONTENT_PACKS: OnceLock<Vec<ContentPack>> = OnceLock::new();
+    CONTENT_PACKS.get_or_init(|| {
+        let Some(path) = mods_path().filter(|_| !cfg!(test)) else {
+            return Vec::new();
+        };
+        let Ok(entries) = fs::read_dir(path) else {
+            return Vec::new();
+        };
+
+        let mut directories: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        directories.sort();
+
+        directories
+            .iter()
+            .filter_map(|directory| match load_content_pack(directory) {
+                Ok(pack) => Some(pack),
+                Err(error) => {
+                    report(format!(
+                        "Couldn't load content pack {}: {}",
+                        directory.display(),
+                        error
+                    ));
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+/// Reads the definitions of the content pack in the given directory. Missing files are fine, a pack may only add vaults.
+///
+/// # Errors
+/// * [IoError::FileReading] if a file exists, but could not be read.
+/// * [IoError::MapParsing] if a file is not valid.
+/// * The reason, if a definition uses an unknown color or behavior.
+pub fn load_content_pack(directory: &Path) -> Result<ContentPack, GameError> {
+    let name = directory.file_name().map_or(String::new(), |name| name.to_string_lossy().into());
+    let mut pack = ContentPack { name, ..ContentPack::default() };
+
+    let items: HashMap<GameItemDefId, ModItemDef> = load_ron_map(&directory.join("items.ron"))?;
+    for (def_id, def) in items {
+        let item_def = def.into_item_def().map_err(|error| invalid_def(&def_id, error))?;
+        pack.items.push((def_id, item_def));
+    }
+    pack.items.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let npcs: HashMap<NpcDefId, ModNpcDef> = load_ron_map(&directory.join("npcs.ron"))?;
+    for (def_id, def) in npcs {
+        let npc_def = def.into_npc_def().map_err(|error| invalid_def(&def_id, error))?;
+        pack.npcs.push((def_id, npc_def));
+    }
+    pack.npcs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let themes: HashMap<String, ModThemeDef> = load_ron_map(&directory.join("themes.ron"))?;
+    for (name, def) in themes {
+        let theme = def.into_pack_theme(&name).map_err(|error| invalid_def(&name, error))?;
+        pack.themes.push(theme);
+    }
+    pack.themes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if let Ok(entries) = fs::read_dir(directory.join("vaults")) {
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            pack.vaults.push(load_vault_from_ron(&path.to_string_lossy())?);
+        }
+    }
+
+    Ok(pack)
+}
+
+/// Adds the definitions of the content packs to the built-in ones. Definitions whose id is already taken are skipped and reported.
+pub fn merge_content_packs<T: Clone>(
+    defs: &mut HashMap<String, T>,
+    kind: &str,
+    pack_defs: impl Fn(&ContentPack) -> &[(String, T)],
+) {
+    for pack in content_packs() {
+        for (def_id, def) in pack_defs(pack) {
+            if defs.contains_key(def_id) {
+                report(format!(
+                    "Content pack {} defines the {} {}, which already exists. It was skipped.",
+                    pack.name, kind, def_id
+                ));
+                continue;
+            }
+            defs.insert(def_id.clone(), def.clone());
+        }
+    }
+}
+
+/// Vaults of the content packs that only spawn things that are defined. The others are skipped and reported.
+pub fn content_pack_vaults() -> Vec<VaultData> {
+    let mut vaults = Vec::new();
+    for pack in content_packs() {
+        for vault in &pack.vaults {
+            match vault.spawns.iter().flat_map(|spawn| spawn_def_ids(&spawn.kind)).find(|def_id| {
+                !item_defs().contains_key(*def_id) && !npc_defs().contains_key(*def_id)
+            }) {
+                Some(def_id) => report(format!(
+                    "The vault {} of content pack {} spawns {}, which is not defined. It was skipped.",
+                    vault.name, pack.name, def_id
+                )),
+                None => vaults.push(vault.clone()),
+            }
+        }
+    }
+    vaults
+}
+
+/// Lazy loads the themes of the content packs. Themes whose name is already taken are skipped and reported.
+pub fn pack_themes() -> &'static Vec<PackTheme> {
+    static PACK_THEMES: OnceLock<Vec<PackTheme>> = OnceLock::new();
+    PACK_THEMES.get_or_init(|| {
+        let mut themes: Vec<PackTheme> = Vec::new();
+        for pack in content_packs() {
+            for theme in &pack.themes {
+                if ThemeId::from_name(&theme.name).is_some()
+                    || themes.iter().any(|other| other.name == theme.name)
+                {
+                    report(format!(
+                        "Content pack {} defines the theme {}, which already exists. It was skipped.",
+                        pack.name, theme.name
+                    ));
+                    continue;
+                }
+                themes.push(theme.clone());
+            }
+        }
+        themes
+    })
+}
+
+/// What happened while the content packs were loaded: Which packs were added, and what was skipped.
+pub fn mod_report() -> Vec<String> {
+    // Merging the packs is what finds the conflicts
+    item_defs();
+    npc_defs();
+    vault_defs();
+    pack_themes();
+
+    let mut lines: Vec<String> = content_packs()
+        .iter()
+        .map(|pack| {
+            format!(
+                "Loaded content pack {}: {} items, {} NPCs, {} vaults, {} themes.",
+                pack.name,
+                pack.items.len(),
+                pack.npcs.len(),
+                pack.vaults.len(),
+                pack.themes.len()
+            )
+        })
+        .collect();
+    lines.extend(report_lines().lock().map(|lines| lines.clone()).unwrap_or_default());
+    lines
+}
+
+fn report_lines() -> &'static Mutex<Vec<String>> {
+    static REPORT: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+    REPORT.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn report(line: String) {
+    if let Ok(mut lines) = report_lines().lock() {
+        lines.push(line);
+    }
+}
+
+/// Reads a map of definitions from a RON file. A missing file holds no definitions.
+fn load_ron_map<T: for<'de> Deserialize<'de>>(
+    path: &Path,
+) -> Result<HashMap<String, T>, GameError> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let file = File::open(path).map_err(IoError::FileReading)?;
+    Ok(from_reader(BufReader::new(file)).map_err(IoError::MapParsing)?)
+}
+
+fn invalid_def(def_id: &str, reason: String) -> GameError {
+    GameError::from(DataError::InvalidModDefinition(def_id.to_string(), reason))
+}
+
+/// Ids of the items and NPCs a spawn of a vault needs.
+fn spawn_def_ids(kind: &SpawnKind) -> Vec<&String> {
+    match kind {
+        SpawnKind::Npc { def_id }
+        | SpawnKind::Item { def_id }
+        | SpawnKind::Hunter { def_id }
+        | SpawnKind::Pack { def_id, .. } => vec![def_id],
+        SpawnKind::Chest { contents } => contents.iter().collect(),
+    }
+}
+
+fn parse_color(name: &str) -> Result<Color, String> {
+    name.parse::<Color>().map_err(|_| format!("Unknown color {}", name))
+}
+
+/// Parses the color of a definition. Definitions without a color are white.
+fn parse_style(color: Option<&str>) -> Result<Style, String> {
+    Ok(Style::default().fg(color.map_or(Ok(Color::White), parse_color)?))
+}
+
+impl ModItemDef {
+    fn into_item_def(self) -> Result<GameItemDef, String> {
+        let kind = match self.kind {
+            ModItemKind::Weapon { damage, crit_chance, range } => GameItemKindDef::Weapon {
+                damage,
+                crit_chance,
+                range,
+                damage_type: DamageType::Physical,
+            },
+            ModItemKind::Armor { mitigation } => GameItemKindDef::Armor {
+                mitigation,
+                resistances: Resistances::NONE,
+                affinities: DamageAffinities::NONE,
+            },
+            ModItemKind::Food { nutrition } => GameItemKindDef::Food { nutrition },
+            ModItemKind::Trinket => GameItemKindDef::Trinket,
+        };
+
+        Ok(GameItemDef {
+            style: parse_style(self.color.as_deref())?,
+            // Definitions live as long as the game, like the built-in ones
+            name: self.name.leak(),
+            glyph: self.glyph,
+            value: self.value,
+            weight: self.weight,
+            kind,
+        })
+    }
+}
+
+impl ModNpcDef {
+    fn into_npc_def(self) -> Result<NpcDef, String> {
+        let behavior = match self.behavior.as_deref() {
+            None => AGGRESSIVE_BEHAVIOR,
+            Some(behavior) => {
+                [AGGRESSIVE_BEHAVIOR, FLEEING_BEHAVIOR, STALKING_BEHAVIOR, WANDERING_BEHAVIOR]
+                    .into_iter()
+                    .find(|known| *known == behavior)
+                    .ok_or(format!("Unknown behavior {}", behavior))?
+            }
+        };
+
+        Ok(NpcDef {
+            style: parse_style(self.color.as_deref())?,
+            name: self.name.leak(),
+            glyph: self.glyph,
+            stats: NpcStats {
+                base: BaseStats { hp_max: self.hp, hp_current: self.hp },
+                damage: self.damage,
+                dodge: self.dodge,
+                mitigation: self.mitigation,
+            },
+            loot_table: self.loot_table.map_or("nothing", |loot_table| loot_table.leak()),
+            danger: self.danger,
+            disposition: Disposition::Hostile,
+            behavior,
+            size: 1,
+            resistances: Resistances::NONE,
+            damage_type: DamageType::Physical,
+            affinities: DamageAffinities::NONE,
+            invisible: false,
+        })
+    }
+}
+
+impl ModThemeDef {
+    fn into_pack_theme(self, name: &str) -> Result<PackTheme, String> {
+        let base = match self.base.as_deref() {
+            Some(base) => ThemeId::from_name(base).ok_or(format!("Unknown theme {}", base))?,
+            None => ThemeId::Default,
+        };
+        let mut colors = self
+            .colors
+            .iter()
+            .map(|(original, replacement)| Ok((parse_color(original)?, parse_color(replacement)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        colors.sort_by_key(|(original, _)| original.to_string());
+
+        Ok(PackTheme { name: name.to_lowercase(), base, colors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_packs_are_read_from_their_directory() {
+        let directory = std::env::temp_dir().join(format!("anthill_mod_{}", std::process::id()));
+        fs::create_dir_all(directory.join("vaults")).unwrap();
+        fs::write(
+            directory.join("items.ron"),
+            r#"{ "weapon_spork": (name: "Spork", glyph: '/', color: Some("yellow"), value: 3, weight: 1, kind: Weapon(damage: "1d6+1")) }"#,
+        )
+        .unwrap();
+        fs::write(
+            directory.join("npcs.ron"),
+            r#"{ "mole": (name: "Mole", glyph: 'm', hp: 8, damage: "1d4", danger: 1, behavior: Some("fleeing")) }"#,
+        )
+        .unwrap();
+
+        let pack = load_content_pack(&directory).unwrap();
+        assert_eq!(pack.items.len(), 1);
+        assert_eq!(pack.items[0].1.name, "Spork");
+        assert_eq!(pack.npcs[0].1.behavior, FLEEING_BEHAVIOR);
+        assert_eq!(pack.npcs[0].1.loot_table, "nothing");
+        assert!(pack.vaults.is_empty());
+        assert!(pack.themes.is_empty());
+
+        fs::write(
+            directory.join("themes.ron"),
+            r##"{ "Ember": (base: Some("contrast"), colors: { "red": "#ff5000" }) }"##,
+        )
+        .unwrap();
+        let pack = load_content_pack(&directory).unwrap();
+        assert_eq!(pack.themes[0].name, "ember");
+        assert_eq!(pack.themes[0].base, ThemeId::HighContrast);
+        assert_eq!(pack.themes[0].colors, vec![(Color::Red, Color::Rgb(255, 80, 0))]);
+
+        fs::write(
+            directory.join("npcs.ron"),
+            r#"{ "mole": (name: "Mole", glyph: 'm', hp: 8, damage: "1d4", danger: 1, behavior: Some("dancing")) }"#,
+        )
+        .unwrap();
+        assert!(load_content_pack(&directory).is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
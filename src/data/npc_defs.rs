@@ -1,10 +1,15 @@
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
-use ratatui::style::{Color, Style};
-
 use crate::{
-    core::entity_logic::{BaseStats, NpcStats},
+    ai::behavior::{AGGRESSIVE_BEHAVIOR, FLEEING_BEHAVIOR, STALKING_BEHAVIOR},
+    core::{
+        damage_types::{DamageAffinities, DamageType},
+        entity_logic::{BaseStats, Disposition, NpcStats},
+        resistances::Resistances,
+    },
+    data::mods::merge_content_packs,
+    render::style::{Color, Style},
     util::rng::{DieSize, Roll},
 };
 
@@ -16,6 +21,24 @@ pub struct NpcDef {
     pub glyph: char,
     pub style: Style,
     pub stats: NpcStats,
+    /// Id of the loot table (see [loot_tables](crate::data::loot_tables::loot_tables)) that is rolled for the NPC's corpse.
+    pub loot_table: &'static str,
+    /// How dangerous the NPC is. Used by the procedural generation to pick monsters that fit the depth of a level.
+    pub danger: u8,
+    /// Attitude of the NPC towards the player when it is spawned.
+    pub disposition: Disposition,
+    /// Id of the AI behavior (see [AiBehavior](crate::ai::behavior::AiBehavior)) the NPC uses once it spotted the player.
+    pub behavior: &'static str,
+    /// Width and height of the square of tiles the NPC covers (see [Footprint](crate::world::coordinate_system::Footprint)). Large monsters cover more than one.
+    pub size: usize,
+    /// Chances of the NPC to resist ailments (see [Ailment](crate::core::resistances::Ailment)).
+    pub resistances: Resistances,
+    /// Type of the damage the NPC's attacks deal.
+    pub damage_type: DamageType,
+    /// Mitigation and vulnerabilities of the NPC against elemental damage.
+    pub affinities: DamageAffinities,
+    /// Whether the NPC is invisible to the player unless it is revealed (see [invisibility](crate::core::invisibility)).
+    pub invisible: bool,
 }
 
 /// Lazy loads the collection of npc definitions in the game.
@@ -35,6 +58,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 10,
                     mitigation: 0,
                 },
+                loot_table: "goblin",
+                danger: 1,
+                disposition: Disposition::Hostile,
+                behavior: FLEEING_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
             },
         );
         m.insert(
@@ -49,6 +81,61 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 20,
                     mitigation: 0,
                 },
+                loot_table: "nothing",
+                danger: 0,
+                disposition: Disposition::Friendly,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
+            },
+        );
+        m.insert(
+            "shopkeeper".to_string(),
+            NpcDef {
+                name: "Shopkeeper",
+                glyph: '$',
+                style: Style::default().fg(Color::Yellow),
+                stats: NpcStats {
+                    base: BaseStats { hp_max: 40, hp_current: 40 },
+                    damage: Roll::new(2, DieSize::D6),
+                    dodge: 10,
+                    mitigation: 2,
+                },
+                loot_table: "nothing",
+                danger: 0,
+                disposition: Disposition::Friendly,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
+            },
+        );
+        m.insert(
+            "healer".to_string(),
+            NpcDef {
+                name: "Healer",
+                glyph: '&',
+                style: Style::default().fg(Color::LightGreen),
+                stats: NpcStats {
+                    base: BaseStats { hp_max: 30, hp_current: 30 },
+                    damage: Roll::new(1, DieSize::D4),
+                    dodge: 10,
+                    mitigation: 1,
+                },
+                loot_table: "nothing",
+                danger: 0,
+                disposition: Disposition::Friendly,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
             },
         );
         m.insert(
@@ -63,6 +150,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 0,
                     mitigation: 2,
                 },
+                loot_table: "warrior",
+                danger: 3,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 0, fear: 25, knockback: 25 },
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
             },
         );
         m.insert(
@@ -77,6 +173,20 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 5,
                     mitigation: 1,
                 },
+                loot_table: "undead",
+                danger: 2,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 100, fear: 100, knockback: 0 },
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities {
+                    cold: 3,
+                    poison: 5,
+                    arcane: -2,
+                    ..DamageAffinities::NONE
+                },
+                invisible: false,
             },
         );
         m.insert(
@@ -91,6 +201,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 15,
                     mitigation: 0,
                 },
+                loot_table: "vermin",
+                danger: 1,
+                disposition: Disposition::Neutral,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Poison,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
             },
         );
         m.insert(
@@ -105,6 +224,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 10,
                     mitigation: 1,
                 },
+                loot_table: "rogue",
+                danger: 3,
+                disposition: Disposition::Hostile,
+                behavior: FLEEING_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
             },
         );
         m.insert(
@@ -119,6 +247,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 5,
                     mitigation: 0,
                 },
+                loot_table: "caster",
+                danger: 4,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Arcane,
+                affinities: DamageAffinities { arcane: 3, ..DamageAffinities::NONE },
+                invisible: false,
             },
         );
         m.insert(
@@ -133,6 +270,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 20,
                     mitigation: 0,
                 },
+                loot_table: "beast",
+                danger: 2,
+                disposition: Disposition::Neutral,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities { fire: -2, cold: 2, ..DamageAffinities::NONE },
+                invisible: false,
             },
         );
         m.insert(
@@ -147,6 +293,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 0,
                     mitigation: 3,
                 },
+                loot_table: "vermin",
+                danger: 3,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 100, fear: 100, knockback: 50 },
+                damage_type: DamageType::Poison,
+                affinities: DamageAffinities { fire: -2, poison: 5, ..DamageAffinities::NONE },
+                invisible: false,
             },
         );
         m.insert(
@@ -161,6 +316,66 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 0,
                     mitigation: 2,
                 },
+                loot_table: "undead",
+                danger: 4,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 100, fear: 100, knockback: 0 },
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities {
+                    fire: -3,
+                    cold: 2,
+                    poison: 5,
+                    ..DamageAffinities::NONE
+                },
+                invisible: false,
+            },
+        );
+        m.insert(
+            "ogre".to_string(),
+            NpcDef {
+                name: "Ogre",
+                glyph: 'O',
+                style: Style::default().fg(Color::Yellow),
+                stats: NpcStats {
+                    base: BaseStats { hp_max: 40, hp_current: 40 },
+                    damage: Roll::new(2, DieSize::D6),
+                    dodge: 0,
+                    mitigation: 3,
+                },
+                loot_table: "warrior",
+                danger: 6,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 2,
+                resistances: Resistances { poison: 0, fear: 50, knockback: 100 },
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities { cold: 2, arcane: -1, ..DamageAffinities::NONE },
+                invisible: false,
+            },
+        );
+        m.insert(
+            "wraith".to_string(),
+            NpcDef {
+                name: "Wraith",
+                glyph: 'w',
+                style: Style::default().fg(Color::LightCyan),
+                stats: NpcStats {
+                    base: BaseStats { hp_max: 16, hp_current: 16 },
+                    damage: Roll::new(1, DieSize::D8).add_modifier(1),
+                    dodge: 15,
+                    mitigation: 1,
+                },
+                loot_table: "undead",
+                danger: 5,
+                disposition: Disposition::Hostile,
+                behavior: STALKING_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 100, fear: 100, knockback: 0 },
+                damage_type: DamageType::Cold,
+                affinities: DamageAffinities { cold: 3, fire: -2, ..DamageAffinities::NONE },
+                invisible: true,
             },
         );
         m.insert(
@@ -175,6 +390,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 25,
                     mitigation: 0,
                 },
+                loot_table: "rogue",
+                danger: 5,
+                disposition: Disposition::Hostile,
+                behavior: STALKING_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: true,
             },
         );
         m.insert(
@@ -189,6 +413,61 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 8,
                     mitigation: 1,
                 },
+                loot_table: "caster",
+                danger: 5,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances::NONE,
+                damage_type: DamageType::Fire,
+                affinities: DamageAffinities { fire: 3, cold: -1, ..DamageAffinities::NONE },
+                invisible: false,
+            },
+        );
+        m.insert(
+            "goblin_boss".to_string(),
+            NpcDef {
+                name: "Goblin Boss",
+                glyph: 'G',
+                style: Style::default().fg(Color::Green),
+                stats: NpcStats {
+                    base: BaseStats { hp_max: 22, hp_current: 22 },
+                    damage: Roll::new(1, DieSize::D8).add_modifier(1),
+                    dodge: 10,
+                    mitigation: 2,
+                },
+                loot_table: "warrior",
+                danger: 3,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 0, fear: 50, knockback: 0 },
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
+            },
+        );
+        m.insert(
+            "necromancer".to_string(),
+            NpcDef {
+                name: "Necromancer",
+                glyph: 'N',
+                style: Style::default().fg(Color::LightMagenta),
+                stats: NpcStats {
+                    base: BaseStats { hp_max: 18, hp_current: 18 },
+                    damage: Roll::new(2, DieSize::D4).add_modifier(2),
+                    dodge: 5,
+                    mitigation: 0,
+                },
+                loot_table: "caster",
+                danger: 5,
+                disposition: Disposition::Hostile,
+                behavior: FLEEING_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 0, fear: 50, knockback: 0 },
+                damage_type: DamageType::Arcane,
+                affinities: DamageAffinities { cold: 2, arcane: 3, ..DamageAffinities::NONE },
+                invisible: false,
             },
         );
         m.insert(
@@ -203,6 +482,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 0,
                     mitigation: 4,
                 },
+                loot_table: "boss_ferris",
+                danger: 8,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 50, fear: 100, knockback: 100 },
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities { fire: -1, cold: 3, ..DamageAffinities::NONE },
+                invisible: false,
             },
         );
         m.insert(
@@ -217,6 +505,15 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 5,
                     mitigation: 6,
                 },
+                loot_table: "boss_martin",
+                danger: 9,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 50, fear: 100, knockback: 100 },
+                damage_type: DamageType::Physical,
+                affinities: DamageAffinities::NONE,
+                invisible: false,
             },
         );
         m.insert(
@@ -231,8 +528,18 @@ pub fn npc_defs() -> &'static HashMap<NpcDefId, NpcDef> {
                     dodge: 50,
                     mitigation: 0,
                 },
+                loot_table: "boss_borrowchecker",
+                danger: 10,
+                disposition: Disposition::Hostile,
+                behavior: AGGRESSIVE_BEHAVIOR,
+                size: 1,
+                resistances: Resistances { poison: 50, fear: 100, knockback: 100 },
+                damage_type: DamageType::Arcane,
+                affinities: DamageAffinities { arcane: 5, ..DamageAffinities::NONE },
+                invisible: false,
             },
         );
+        merge_content_packs(&mut m, "NPC", |pack| &pack.npcs);
         m
     })
 }
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::core::resistances::Ailment;
+
+pub type PerkId = String;
+
+/// Passive bonus a perk grants for the rest of the run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PerkEffect {
+    /// Additional chance (in percent) to land a critical hit.
+    CritChance(i16),
+    /// Poison deals only half its damage each turn (rounded up).
+    PoisonResistance,
+    /// Additional damage blocked from every hit.
+    Mitigation(i16),
+    /// Additional maximum hit points, granted when the perk is picked.
+    MaxHp(u16),
+    /// Additional chance (in percent) to dodge an attack.
+    Dodge(u8),
+    /// Additional chance (in percent) to resist the ailment.
+    Resistance(Ailment, u8),
+    /// Chance (in percent) to steal from an NPC without getting caught. Stealing is only possible with this perk.
+    Pickpocket(u8),
+}
+
+/// A perk the player can pick on a level-up milestone (see [PERK_LEVEL_INTERVAL](crate::core::perks::PERK_LEVEL_INTERVAL)).
+#[derive(Clone, Debug)]
+pub struct PerkDef {
+    pub name: &'static str,
+    /// Shown in the selection, so it should state the bonus.
+    pub description: &'static str,
+    pub effect: PerkEffect,
+}
+
+/// Lazy loads the collection of perk definitions in the game.
+pub fn perk_defs() -> &'static HashMap<PerkId, PerkDef> {
+    static PERK_DEFS: OnceLock<HashMap<PerkId, PerkDef>> = OnceLock::new();
+    PERK_DEFS.get_or_init(|| {
+        let mut m = HashMap::new();
+        m.insert(
+            "keen_edge".to_string(),
+            PerkDef {
+                name: "Keen Edge",
+                description: "+10% critical hit chance",
+                effect: PerkEffect::CritChance(10),
+            },
+        );
+        m.insert(
+            "antivenom".to_string(),
+            PerkDef {
+                name: "Antivenom",
+                description: "Poison deals half damage",
+                effect: PerkEffect::PoisonResistance,
+            },
+        );
+        m.insert(
+            "thick_skin".to_string(),
+            PerkDef {
+                name: "Thick Skin",
+                description: "+1 mitigation against every hit",
+                effect: PerkEffect::Mitigation(1),
+            },
+        );
+        m.insert(
+            "vigor".to_string(),
+            PerkDef {
+                name: "Vigor",
+                description: "+15 maximum hit points",
+                effect: PerkEffect::MaxHp(15),
+            },
+        );
+        m.insert(
+            "light_feet".to_string(),
+            PerkDef {
+                name: "Light Feet",
+                description: "+5% chance to dodge attacks",
+                effect: PerkEffect::Dodge(5),
+            },
+        );
+        m.insert(
+            "steady_stance".to_string(),
+            PerkDef {
+                name: "Steady Stance",
+                description: "+50% chance to resist knockback",
+                effect: PerkEffect::Resistance(Ailment::Knockback, 50),
+            },
+        );
+        m.insert(
+            "light_fingers".to_string(),
+            PerkDef {
+                name: "Light Fingers",
+                description: "Steal gold from NPCs (60% chance to stay unnoticed)",
+                effect: PerkEffect::Pickpocket(60),
+            },
+        );
+        m
+    })
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::data::item_defs::GameItemDefId;
+
+/// Item that cooking turns into when the food gets burnt.
+pub const BURNT_FOOD: &str = "food_burnt";
+
+/// Defines what a raw food item turns into when it is cooked at a campfire.
+#[derive(Clone, Copy, Debug)]
+pub struct CookingRecipe {
+    /// Item def id of the cooked food.
+    pub result: &'static str,
+    /// Chance (in percent) that the food gets burnt instead, turning it into [BURNT_FOOD].
+    pub burn_chance: u8,
+}
+
+/// Lazy loads the collection of cooking recipes, keyed by the item def id of the raw food.
+pub fn cooking_recipes() -> &'static HashMap<GameItemDefId, CookingRecipe> {
+    static COOKING_RECIPES: OnceLock<HashMap<GameItemDefId, CookingRecipe>> = OnceLock::new();
+    COOKING_RECIPES.get_or_init(|| {
+        let mut m = HashMap::new();
+        m.insert(
+            "food_meat_raw".to_string(),
+            CookingRecipe { result: "food_meat", burn_chance: 15 },
+        );
+        m.insert(
+            "food_fish_raw".to_string(),
+            CookingRecipe { result: "food_fish", burn_chance: 20 },
+        );
+        m.insert(
+            "food_mushroom".to_string(),
+            CookingRecipe { result: "food_mushroom_roasted", burn_chance: 30 },
+        );
+        m
+    })
+}
+
+/// Gets the cooking recipe for a raw food item.
+pub fn get_cooking_recipe(item_def_id: &str) -> Option<&'static CookingRecipe> {
+    cooking_recipes().get(item_def_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::item_defs::item_defs;
+
+    #[test]
+    fn recipes_reference_defined_items() {
+        assert!(item_defs().contains_key(BURNT_FOOD));
+
+        for (raw, recipe) in cooking_recipes() {
+            assert!(item_defs().contains_key(raw), "Unknown raw food {}", raw);
+            assert!(
+                item_defs().contains_key(recipe.result),
+                "Unknown cooked food {}",
+                recipe.result
+            );
+            assert!(recipe.burn_chance < 100);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::render::style::Color;
+
+pub type UniqueId = String;
+
+/// Chance (in percent) that a generated level holds a unique monster, if one fits its depth.
+pub const UNIQUE_CHANCE: u32 = 15;
+
+/// A named monster that appears at most once per run (see [UniqueRegistry](crate::core::uniques::UniqueRegistry)).
+///
+/// Uniques are stronger versions of a regular NPC. They only appear out of depth and always drop their special loot.
+#[derive(Clone, Debug)]
+pub struct UniqueDef {
+    pub name: &'static str,
+    /// Id of the NPC definition the unique is based on.
+    pub base: &'static str,
+    /// Color of the unique on the map, so it stands out from the regular NPCs it is based on.
+    pub color: Color,
+    /// Factor the hit points of the base NPC are multiplied by.
+    pub hp_factor: u16,
+    /// Added to the damage rolls of the base NPC.
+    pub damage_bonus: i16,
+    /// How dangerous the unique is. It only appears on levels that are too shallow for it.
+    pub danger: u8,
+    /// Id of the loot table that is rolled for the unique's corpse, with its special drops guaranteed.
+    pub loot_table: &'static str,
+}
+
+/// Lazy loads the collection of unique monster definitions in the game.
+pub fn unique_defs() -> &'static HashMap<UniqueId, UniqueDef> {
+    static UNIQUE_DEFS: OnceLock<HashMap<UniqueId, UniqueDef>> = OnceLock::new();
+    UNIQUE_DEFS.get_or_init(|| {
+        let mut m = HashMap::new();
+        m.insert(
+            "grak".to_string(),
+            UniqueDef {
+                name: "Grak, Goblin King",
+                base: "goblin_boss",
+                color: Color::LightYellow,
+                hp_factor: 2,
+                damage_bonus: 2,
+                danger: 4,
+                loot_table: "unique_grak",
+            },
+        );
+        m.insert(
+            "morwen".to_string(),
+            UniqueDef {
+                name: "Morwen the Pale",
+                base: "necromancer",
+                color: Color::White,
+                hp_factor: 2,
+                damage_bonus: 3,
+                danger: 7,
+                loot_table: "unique_morwen",
+            },
+        );
+        m
+    })
+}
+
+pub fn get_unique_def_by_id(unique_id: &str) -> Option<&'static UniqueDef> {
+    unique_defs().get(unique_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{loot_tables::get_loot_table_by_id, npc_defs::npc_defs};
+
+    #[test]
+    fn uniques_reference_defined_npcs_and_loot() {
+        for def in unique_defs().values() {
+            assert!(npc_defs().contains_key(def.base), "Unknown npc {}", def.base);
+            let loot_table = get_loot_table_by_id(def.loot_table).unwrap();
+            assert!(!loot_table.guaranteed.is_empty(), "{} has no special drop", def.name);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::mods::content_pack_vaults,
+    world::{
+        level_data::{DoorTypeData, SpawnData, TileTypeData, TriggerData},
+        level_loader::load_vault_from_ron,
+    },
+};
+
+pub fn vault_paths() -> &'static Vec<&'static str> {
+    static VAULT_PATHS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    VAULT_PATHS.get_or_init(|| {
+        vec![
+            "assets/vaults/treasure_vault.ron",
+            "assets/vaults/forgotten_shrine.ron",
+            "assets/vaults/guarded_armory.ron",
+            "assets/vaults/shop.ron",
+        ]
+    })
+}
+
+/// A small handcrafted room layout that the procedural generator can stamp into a generated level.
+///
+/// The layout is given as rows of characters (see [VaultData::tile_at]). Spawn coordinates are relative to the top left corner of the layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultData {
+    pub name: String,
+
+    /// Shallowest level on which the vault can appear.
+    #[serde(default)]
+    pub min_depth: usize,
+
+    pub layout: Vec<String>,
+
+    #[serde(default)]
+    pub spawns: Vec<SpawnData>,
+
+    /// Path of a Rhai script that reacts to events of a level with this vault (see [scripting](crate::core::scripting)). Its coordinates are relative to the layout.
+    #[serde(default)]
+    pub script: Option<String>,
+
+    /// Triggers of the vault. Their coordinates are relative to the layout.
+    #[serde(default)]
+    pub triggers: Vec<TriggerData>,
+}
+
+impl VaultData {
+    /// Width of the layout (length of the longest row).
+    pub fn width(&self) -> usize {
+        self.layout.iter().map(|row| row.chars().count()).max().unwrap_or(0)
+    }
+
+    /// Height of the layout (number of rows).
+    pub fn height(&self) -> usize {
+        self.layout.len()
+    }
+
+    /// Gets the tile of the layout at the given coordinates.
+    ///
+    /// * `#` Wall
+    /// * `.` Floor
+    /// * `+` Closed door
+    /// * `∆` Shrine
+    ///
+    /// # Returns
+    /// * `None` for spaces (and unknown characters), which leave the tile of the level untouched.
+    pub fn tile_at(&self, x: usize, y: usize) -> Option<TileTypeData> {
+        match self.layout.get(y)?.chars().nth(x)? {
+            '#' => Some(TileTypeData::Wall),
+            '.' => Some(TileTypeData::Floor),
+            '+' => Some(TileTypeData::Door(DoorTypeData::Closed)),
+            '∆' => Some(TileTypeData::Shrine),
+            _ => None,
+        }
+    }
+}
+
+/// Lazy loads the collection of vaults from the files in [vault_paths] and the content packs (see [mods](crate::data::mods)). Files that cannot be loaded are skipped.
+pub fn vault_defs() -> &'static Vec<VaultData> {
+    static VAULT_DEFS: OnceLock<Vec<VaultData>> = OnceLock::new();
+    VAULT_DEFS.get_or_init(|| {
+        let mut vaults: Vec<VaultData> =
+            vault_paths().iter().filter_map(|path| load_vault_from_ron(path).ok()).collect();
+        vaults.extend(content_pack_vaults());
+        vaults
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::level_data::SpawnKind;
+
+    #[test]
+    fn vault_files_are_valid() {
+        assert_eq!(vault_defs().len(), vault_paths().len());
+
+        for vault in vault_defs() {
+            assert!(vault.layout.iter().all(|row| row.chars().count() == vault.width()));
+
+            for spawn in &vault.spawns {
+                assert_eq!(vault.tile_at(spawn.x, spawn.y), Some(TileTypeData::Floor));
+                match &spawn.kind {
+                    SpawnKind::Npc { def_id }
+                    | SpawnKind::Hunter { def_id }
+                    | SpawnKind::Pack { def_id, .. } => {
+                        assert!(crate::data::npc_defs::npc_defs().contains_key(def_id))
+                    }
+                    SpawnKind::Item { def_id } => {
+                        assert!(crate::data::item_defs::item_defs().contains_key(def_id))
+                    }
+                    SpawnKind::Chest { contents } => assert!(
+                        contents
+                            .iter()
+                            .all(|def_id| crate::data::item_defs::item_defs().contains_key(def_id))
+                    ),
+                }
+            }
+
+            for trigger in &vault.triggers {
+                assert!(trigger.x + trigger.width <= vault.width());
+                assert!(trigger.y + trigger.height <= vault.height());
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! The engine of Anthill: Game state, rules, AI, static data and the generation of levels, without any terminal code.
+//!
+//! Frontends (like the terminal UI in `anthill-tui`) drive the game through a few entry points:
+//!
+//! * [GameState](core::game::GameState) - The whole state of a run. Created with [GameState::new](core::game::GameState::new)
+//!   or [GameState::with_seed](core::game::GameState::with_seed) for reproducible runs.
+//! * [PlayerInput](core::player_actions::PlayerInput) - Everything the player can do in a turn. Passed to
+//!   [GameState::resolve_player_action](core::game::GameState::resolve_player_action), which also lets the NPCs act.
+//! * [Log](util::text_log::Log) - What happened, as [LogData](util::text_log::LogData). Its
+//!   [LogLine](util::text_log::LogLine) is tagged with what each part stands for, so the frontend can style it or print
+//!   it as plain text.
+//! * [Style](render::style::Style) - Colors and modifiers of tiles, creatures and items, which the frontend maps to its own.
+//! * [Replay](core::replay::Replay) - Seed and inputs of a run, which plays the same run again.
+//!
+//! The modules below hold the rest of the engine. They are public so frontends and tests of other crates can reach
+//! into them (e.g. [proc_gen] to generate a level, or [combat](core::combat) to simulate fights).
+pub mod ai;
+pub mod core;
+pub mod data;
+pub mod proc_gen;
+pub mod render;
+pub mod util;
+pub mod world;
@@ -1,8 +1,17 @@
 pub mod bsp;
 pub mod bsp_nodes;
+pub mod caves;
+pub mod corridor_cleanup;
 pub mod corridors;
+pub mod gen_config;
+#[cfg(feature = "dev")]
+pub mod generator_bench;
+pub mod level_theme;
+pub mod maze;
 pub mod mst;
 pub mod population;
 pub mod proc_gen_level;
 pub mod proc_gen_room;
 pub mod proc_gen_world;
+pub mod terrain;
+pub mod vaults;
@@ -3,7 +3,10 @@ use rand::{Rng, SeedableRng, rngs::StdRng};
 /// Binary Space Partitioning to procedurally generate rooms
 /// Inspired by: https://www.youtube.com/watch?v=Pj4owFPH1Hw (Java)
 use crate::{
-    proc_gen::bsp_nodes::{MapBSPNode, NodeId},
+    proc_gen::{
+        bsp_nodes::{MapBSPNode, NodeId},
+        gen_config::GenConfig,
+    },
     world::coordinate_system::Point,
 };
 
@@ -19,16 +22,6 @@ pub const MIN_NODE_DIM: usize = 13;
 /// Minimum Distance the generated rooms should have to the edge of the map.
 pub const PADDING: usize = 2;
 
-/// Number of rooms to be generated.
-///
-/// # TO DO
-/// Randomize within a reasonable range later.
-pub const ROOM_NUMBER: usize = 10;
-
-/// In the division stage of the algorithm, a binary search partition is divided in two.
-/// From this range, a random value is pulled which represents at which fraction the division is made.
-pub const DIVIDER_RANGE: std::ops::Range<f32> = 0.4..0.6;
-
 pub struct MapBSPTree {
     /// All nodes of the tree structure in a linear vector. Ids in the tree structure reference indices of this vector.
     pub nodes: Vec<MapBSPNode>,
@@ -38,23 +31,43 @@ pub struct MapBSPTree {
 
     /// Used to track how many rooms a map has. The BSP alorithm recurses until a certain number of rooms is reached.
     pub num_rooms: usize,
+
+    /// Range of fractions at which a partition is divided (see [GenConfig::divider_range]).
+    pub divider_range: std::ops::Range<f32>,
+
+    /// Width of the map that is partitioned.
+    pub width: usize,
+
+    /// Height of the map that is partitioned.
+    pub height: usize,
 }
 
-impl Default for MapBSPTree {
-    fn default() -> Self {
+impl MapBSPTree {
+    /// Creates a tree whose root node encompasses an entire map of the given size.
+    pub fn new(width: usize, height: usize, config: &GenConfig) -> Self {
         let mut nodes = Vec::new();
         let root = nodes.len();
-        nodes.push(MapBSPNode::default());
-
-        Self { nodes, root, num_rooms: ROOM_NUMBER }
+        nodes.push(MapBSPNode::root(width, height));
+
+        Self {
+            nodes,
+            root,
+            num_rooms: config.room_number,
+            divider_range: config.divider_range.clone(),
+            width,
+            height,
+        }
     }
-}
 
-impl MapBSPTree {
-    pub fn generate_bsp(bsp_seed: u64) -> MapBSPTree {
+    pub fn generate_bsp(
+        bsp_seed: u64,
+        width: usize,
+        height: usize,
+        config: &GenConfig,
+    ) -> MapBSPTree {
         let mut rng = StdRng::seed_from_u64(bsp_seed);
 
-        let mut bsp = MapBSPTree::default();
+        let mut bsp = MapBSPTree::new(width, height, config);
         bsp.divide(&mut rng);
         bsp
     }
@@ -113,7 +126,7 @@ impl MapBSPTree {
         if self.get_node(node_id).is_leaf() {
             if room_width > room_height {
                 let new_midpoint = (point_a.x as f32
-                    + rng.random_range(DIVIDER_RANGE) * room_width as f32)
+                    + rng.random_range(self.divider_range.clone()) * room_width as f32)
                     as usize;
 
                 let left_id = self.nodes.len();
@@ -126,7 +139,7 @@ impl MapBSPTree {
                 self.get_node_mut(node_id).right = Some(right_id);
             } else {
                 let new_midpoint = (point_a.y as f32
-                    + rng.random_range(DIVIDER_RANGE) * room_height as f32)
+                    + rng.random_range(self.divider_range.clone()) * room_height as f32)
                     as usize;
 
                 let left_id = self.nodes.len();
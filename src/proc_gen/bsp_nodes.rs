@@ -1,9 +1,7 @@
 use crate::{
     proc_gen::bsp::PADDING,
     world::{
-        coordinate_system::Point,
-        level_data::RoomData,
-        worldspace::{Room, WORLD_HEIGHT, WORLD_WIDTH},
+        coordinate_system::Point, level_data::RoomData, lighting::RoomLighting, worldspace::Room,
     },
 };
 
@@ -38,24 +36,18 @@ impl MapBSPNode {
         Self { point_a, point_b, left: None, right: None }
     }
 
+    /// The root node always encompasses the entire available worldspace of the given size. This is the parent node in the tree that will be subdivided in the BSP algorithm.
+    pub fn root(width: usize, height: usize) -> Self {
+        // Leaves 2 tile-wide padding as to not encroach upon the border.
+        Self::new(Point::new(PADDING, PADDING), Point::new(width - PADDING, height - PADDING))
+    }
+
     /// Helper function to determine whether a given node is a leaf.
     pub fn is_leaf(&self) -> bool {
         self.left.is_none() && self.right.is_none()
     }
 }
 
-impl Default for MapBSPNode {
-    /// A default node always encompasses the entire available worldspace. This is the parent node in the tree that will be subdivided in the BSP algorithm.
-    fn default() -> Self {
-        Self {
-            point_a: Point::new(PADDING, PADDING), // Leaves 2 tile-wide padding as to not encroach upon the border.
-            point_b: Point::new(WORLD_WIDTH - PADDING, WORLD_HEIGHT - PADDING),
-            left: None,
-            right: None,
-        }
-    }
-}
-
 // To convert a MapNode (BSP data structure) into a Room (data structure used by the game to carve rooms into the void)
 impl From<MapBSPNode> for Room {
     fn from(value: MapBSPNode) -> Self {
@@ -73,6 +65,7 @@ impl From<MapBSPNode> for RoomData {
             y: value.point_a.y,
             width: dimensions.x as usize,
             height: dimensions.y as usize,
+            lighting: RoomLighting::default(),
         }
     }
 }
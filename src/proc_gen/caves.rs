@@ -0,0 +1,156 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    proc_gen::{bsp::PADDING, proc_gen_world::ProcGenWorld},
+    world::coordinate_system::Point,
+};
+
+/// Chance (in percent) that a cell starts out as rock before smoothing.
+pub const CAVE_FILL_CHANCE: u32 = 45;
+
+/// Number of times the cellular automaton is applied to the noise.
+pub const CAVE_SMOOTHING_STEPS: usize = 5;
+
+/// A cave needs at least this many floor tiles. Smaller caves are generated again.
+pub const CAVE_MIN_FLOOR: usize = 600;
+
+/// Maximum number of attempts to generate a cave that is large enough. The largest attempt is used if none is.
+pub const CAVE_MAX_ATTEMPTS: usize = 10;
+
+impl ProcGenWorld {
+    /// Generates a cave level with a cellular automaton.
+    ///
+    /// Starts with random noise and smooths it, so that cells surrounded by rock turn into rock and cells surrounded by open space open up.
+    /// Only the largest connected cave is kept, so every part of the level can be reached.
+    pub fn generate_caves(cave_seed: u64, width: usize, height: usize) -> Self {
+        let mut rng = StdRng::seed_from_u64(cave_seed);
+        let grid = CaveGrid { width, height };
+
+        let mut floor: Vec<Point> = Vec::new();
+        for _ in 0..CAVE_MAX_ATTEMPTS {
+            let mut open = grid.random_noise(&mut rng);
+            for _ in 0..CAVE_SMOOTHING_STEPS {
+                open = grid.smooth(&open);
+            }
+
+            let cave = grid.largest_cave(&open);
+            if cave.len() > floor.len() {
+                floor = cave;
+            }
+            if floor.len() >= CAVE_MIN_FLOOR {
+                break;
+            }
+        }
+
+        Self { rooms: Vec::new(), corridors: Vec::new(), floor, width, height }
+    }
+}
+
+/// Dimensions of the grid of cells the cave is generated in.
+#[derive(Clone, Copy)]
+struct CaveGrid {
+    width: usize,
+    height: usize,
+}
+
+impl CaveGrid {
+    /// Index of a cell in the cave grid.
+    #[inline]
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Whether a cell can be open. Cells at the edge of the map always stay rock, so the cave can be surrounded by walls.
+    fn is_inside(&self, x: usize, y: usize) -> bool {
+        (PADDING..self.width.saturating_sub(PADDING)).contains(&x)
+            && (PADDING..self.height.saturating_sub(PADDING)).contains(&y)
+    }
+
+    /// Creates a grid of random open (`true`) and rock (`false`) cells.
+    fn random_noise<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<bool> {
+        let mut open = vec![false; self.width * self.height];
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                open[self.index(x, y)] =
+                    self.is_inside(x, y) && rng.random_range(0..100) >= CAVE_FILL_CHANCE;
+            }
+        }
+
+        open
+    }
+
+    /// Applies one step of the cellular automaton.
+    ///
+    /// Cells with more than 4 rock neighbours turn into rock, cells with less than 4 open up. Cells outside the map count as rock.
+    fn smooth(&self, open: &[bool]) -> Vec<bool> {
+        let mut smoothed = open.to_vec();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !self.is_inside(x, y) {
+                    continue;
+                }
+
+                let mut rock_neighbours = 0;
+                for ny in y - 1..=y + 1 {
+                    for nx in x - 1..=x + 1 {
+                        if (nx, ny) != (x, y) && !open[self.index(nx, ny)] {
+                            rock_neighbours += 1;
+                        }
+                    }
+                }
+
+                if rock_neighbours > 4 {
+                    smoothed[self.index(x, y)] = false;
+                } else if rock_neighbours < 4 {
+                    smoothed[self.index(x, y)] = true;
+                }
+            }
+        }
+
+        smoothed
+    }
+
+    /// Finds the largest area of connected open cells using flood fill.
+    fn largest_cave(&self, open: &[bool]) -> Vec<Point> {
+        let mut visited = vec![false; open.len()];
+        let mut largest: Vec<Point> = Vec::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !open[self.index(x, y)] || visited[self.index(x, y)] {
+                    continue;
+                }
+
+                let mut cave = Vec::new();
+                let mut stack = vec![Point::new(x, y)];
+                visited[self.index(x, y)] = true;
+
+                while let Some(point) = stack.pop() {
+                    cave.push(point);
+
+                    let neighbours = [
+                        Point::new(point.x - 1, point.y),
+                        Point::new(point.x + 1, point.y),
+                        Point::new(point.x, point.y - 1),
+                        Point::new(point.x, point.y + 1),
+                    ];
+                    for neighbour in neighbours {
+                        let neighbour_index = self.index(neighbour.x, neighbour.y);
+                        if open[neighbour_index] && !visited[neighbour_index] {
+                            visited[neighbour_index] = true;
+                            stack.push(neighbour);
+                        }
+                    }
+                }
+
+                if cave.len() > largest.len() {
+                    largest = cave;
+                }
+            }
+        }
+
+        largest
+    }
+}
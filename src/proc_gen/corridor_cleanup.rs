@@ -0,0 +1,189 @@
+use std::collections::{HashSet, VecDeque};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    proc_gen::{proc_gen_room::ProcGenRoom, proc_gen_world::ProcGenWorld},
+    world::coordinate_system::{Direction, Point},
+};
+
+impl ProcGenWorld {
+    /// Post-processing pass over the corridors carved by [ProcGenWorld::a_star_corridors].
+    ///
+    /// * Dead-end stubs of hallway are pruned.
+    /// * Corridors running side by side are merged into one.
+    /// * Chains of doors in neighbouring wall tiles are reduced, so a corridor enters a room through a single door.
+    ///
+    /// Tiles are only removed if everything that was reachable before stays reachable. Corridor points inside rooms are kept,
+    /// since they mark the paths vaults must not block.
+    pub fn tidy_corridors(&mut self) {
+        let interior: HashSet<Point> = self.rooms.iter().flat_map(room_interior).collect();
+        let walls: HashSet<Point> = self.rooms.iter().flat_map(|room| room.wall_points()).collect();
+
+        let mut seen: HashSet<Point> = HashSet::new();
+        self.corridors.retain(|point| seen.insert(*point));
+
+        let mut walkable: HashSet<Point> =
+            interior.iter().chain(&self.corridors).copied().collect();
+        let Some(anchor) = self.rooms.first().and_then(|room| room_interior(room).first().copied())
+        else {
+            return;
+        };
+
+        prune_dead_ends(&mut walkable, &interior);
+
+        // Parallel corridors show up as blocks of 2x2 hallway tiles
+        let hallways: Vec<Point> = self
+            .corridors
+            .iter()
+            .copied()
+            .filter(|point| !interior.contains(point) && !walls.contains(point))
+            .collect();
+        for point in hallways {
+            if walkable.contains(&point)
+                && in_open_square(&walkable, point)
+                && stays_connected(&mut walkable, anchor, point)
+            {
+                walkable.remove(&point);
+            }
+        }
+
+        let doors: Vec<Point> =
+            self.corridors.iter().copied().filter(|point| walls.contains(point)).collect();
+        for point in doors {
+            let chained = neighbours(point)
+                .any(|neighbour| walls.contains(&neighbour) && walkable.contains(&neighbour));
+            if walkable.contains(&point) && chained && stays_connected(&mut walkable, anchor, point)
+            {
+                walkable.remove(&point);
+            }
+        }
+
+        prune_dead_ends(&mut walkable, &interior);
+
+        self.corridors.retain(|point| walkable.contains(point));
+    }
+}
+
+/// Points inside the walls of a room, as carved by [World::carve_room](crate::world::worldspace::World::carve_room).
+fn room_interior(room: &ProcGenRoom) -> Vec<Point> {
+    let (a, b) = (room.point_a, room.point_b);
+    (a.y + 1..b.y).flat_map(|y| (a.x + 1..b.x).map(move |x| Point::new(x, y))).collect()
+}
+
+/// The four orthogonal neighbours of a point. Points on the edge of the map have fewer.
+fn neighbours(point: Point) -> impl Iterator<Item = Point> {
+    Direction::iter().filter_map(move |direction| {
+        let neighbour = point.get_adjacent(direction);
+        (neighbour != point).then_some(neighbour)
+    })
+}
+
+/// Repeatedly removes corridor tiles that lead nowhere, i.e. have at most one walkable neighbour.
+fn prune_dead_ends(walkable: &mut HashSet<Point>, interior: &HashSet<Point>) {
+    loop {
+        let dead_ends: Vec<Point> = walkable
+            .iter()
+            .copied()
+            .filter(|point| !interior.contains(point))
+            .filter(|point| neighbours(*point).filter(|n| walkable.contains(n)).count() <= 1)
+            .collect();
+        if dead_ends.is_empty() {
+            return;
+        }
+        for point in dead_ends {
+            walkable.remove(&point);
+        }
+    }
+}
+
+/// Whether the point is the corner of a walkable 2x2 square.
+fn in_open_square(walkable: &HashSet<Point>, point: Point) -> bool {
+    let x_options = [point.x.checked_sub(1), Some(point.x + 1)];
+    let y_options = [point.y.checked_sub(1), Some(point.y + 1)];
+
+    x_options.iter().flatten().any(|x| {
+        y_options.iter().flatten().any(|y| {
+            walkable.contains(&Point::new(*x, point.y))
+                && walkable.contains(&Point::new(point.x, *y))
+                && walkable.contains(&Point::new(*x, *y))
+        })
+    })
+}
+
+/// Whether everything reachable from the anchor stays reachable if the point is removed.
+fn stays_connected(walkable: &mut HashSet<Point>, anchor: Point, point: Point) -> bool {
+    let before = reachable_count(walkable, anchor);
+    walkable.remove(&point);
+    let after = reachable_count(walkable, anchor);
+    walkable.insert(point);
+
+    // The point itself has to be reachable, otherwise it is no longer counted for another reason
+    after + 1 == before && before > 0
+}
+
+/// Number of walkable points reachable from the start.
+fn reachable_count(walkable: &HashSet<Point>, start: Point) -> usize {
+    if !walkable.contains(&start) {
+        return 0;
+    }
+
+    let mut visited: HashSet<Point> = HashSet::from([start]);
+    let mut queue: VecDeque<Point> = VecDeque::from([start]);
+    while let Some(point) = queue.pop_front() {
+        for neighbour in neighbours(point) {
+            if walkable.contains(&neighbour) && visited.insert(neighbour) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    visited.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        proc_gen::{bsp::MapBSPTree, gen_config::GenConfig},
+        world::worldspace::{WORLD_HEIGHT, WORLD_WIDTH},
+    };
+
+    #[test]
+    fn tidied_corridors_keep_rooms_connected() {
+        for seed in [42, 73] {
+            let config = GenConfig::default();
+            let world = ProcGenWorld::generate_from_bsp(
+                MapBSPTree::generate_bsp(seed, WORLD_WIDTH, WORLD_HEIGHT, &config),
+                seed,
+                seed,
+                &config,
+            );
+
+            let interior: HashSet<Point> = world.rooms.iter().flat_map(room_interior).collect();
+            let walls: HashSet<Point> =
+                world.rooms.iter().flat_map(|room| room.wall_points()).collect();
+            let walkable: HashSet<Point> =
+                interior.iter().chain(&world.corridors).copied().collect();
+
+            // Every room can be reached from every other
+            let anchor = room_interior(&world.rooms[0])[0];
+            assert_eq!(reachable_count(&walkable, anchor), walkable.len());
+
+            for point in world.corridors.iter().filter(|point| !interior.contains(point)) {
+                let walkable_neighbours =
+                    neighbours(*point).filter(|n| walkable.contains(n)).count();
+                assert!(walkable_neighbours >= 2, "dead end at {:?}", point);
+
+                if walls.contains(point) {
+                    assert!(
+                        !neighbours(*point)
+                            .any(|n| walls.contains(&n) && world.corridors.contains(&n)),
+                        "chain of doors at {:?}",
+                        point
+                    );
+                }
+            }
+        }
+    }
+}
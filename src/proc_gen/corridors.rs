@@ -35,7 +35,12 @@ impl ProcGenWorld {
         edges
     }
 
-    pub fn find_room_connections<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<MapEdge> {
+    /// Connects all rooms through a minimum spanning tree. Each other pair of rooms gets an extra connection with the given chance.
+    pub fn find_room_connections<R: Rng + ?Sized>(
+        &self,
+        extra_corridor_chance: f64,
+        rng: &mut R,
+    ) -> Vec<MapEdge> {
         let edges = self.all_edges();
 
         let mut connections = match mst_kruskal(edges.clone(), self.rooms.len()) {
@@ -48,7 +53,7 @@ impl ProcGenWorld {
 
         // Extra corridors for Jaquaysing
         for edge in edges {
-            if rng.random_bool(0.05) {
+            if rng.random_bool(extra_corridor_chance) {
                 connections.push(edge);
             }
         }
@@ -69,10 +74,10 @@ impl ProcGenWorld {
         connections
     }
 
-    pub fn a_star_corridors(&mut self, corridor_seed: u64) {
+    pub fn a_star_corridors(&mut self, corridor_seed: u64, extra_corridor_chance: f64) {
         let mut rng = StdRng::seed_from_u64(corridor_seed);
 
-        let connections = self.find_room_connections(&mut rng);
+        let connections = self.find_room_connections(extra_corridor_chance, &mut rng);
 
         let mut room_corners: HashSet<Point> = HashSet::new();
         let mut room_walls: HashSet<Point> = HashSet::new();
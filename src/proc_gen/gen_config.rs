@@ -0,0 +1,157 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use rand::Rng;
+use ron::de::from_reader;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    proc_gen::population::RoomEncounter,
+    util::errors_results::{GameError, IoError},
+};
+
+/// Parameters of the level generation. Passed through [ProcGenLevel::generate](crate::proc_gen::proc_gen_level::ProcGenLevel::generate).
+///
+/// Can be loaded from a RON file (see [gen_config_path]), so presets for difficulty or size can be tried without recompiling.
+/// Fields missing in the file keep their defaults.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenConfig {
+    /// Number of rooms the binary space partitioning aims for.
+    pub room_number: usize,
+
+    /// In the division stage of the binary space partitioning, a partition is divided in two.
+    /// From this range, a random value is pulled which represents at which fraction the division is made.
+    pub divider_range: Range<f32>,
+
+    /// In the shrinking stage, the binary space partitions are shrunk, so there's spacing between rooms.
+    /// From this range, a random value is pulled for each room and used to shrink the room to that fraction.
+    pub shrink_factor_range: Range<f32>,
+
+    /// Shrink factor range for the large rooms of [LevelTheme::OpenHalls](crate::proc_gen::level_theme::LevelTheme::OpenHalls).
+    pub open_halls_shrink_factor_range: Range<f32>,
+
+    /// Chance that two rooms get an extra corridor on top of the minimum spanning tree, so the level has loops.
+    pub extra_corridor_chance: f64,
+
+    /// How likely each [RoomEncounter] is.
+    pub encounter_weights: EncounterWeights,
+
+    /// Range the number of monsters in a room with enemies is picked from. Adjusted by the [Difficulty](crate::core::difficulty::Difficulty) of the run.
+    pub room_npcs: Range<usize>,
+
+    /// Chance (in percent) that a level has a campfire.
+    pub campfire_chance: u32,
+
+    /// Chance (in percent) that a level has an altar.
+    pub altar_chance: u32,
+
+    /// Chance (in percent) that a level has a vault.
+    pub vault_chance: u32,
+
+    /// Chance (in percent) that a level has a terrain feature (water, lava or a chasm).
+    pub terrain_chance: u32,
+}
+
+impl Default for GenConfig {
+    fn default() -> Self {
+        Self {
+            room_number: 10,
+            divider_range: 0.4..0.6,
+            shrink_factor_range: 0.5..0.9,
+            open_halls_shrink_factor_range: 0.85..1.0,
+            extra_corridor_chance: 0.05,
+            encounter_weights: EncounterWeights::default(),
+            room_npcs: 1..3,
+            campfire_chance: 40,
+            altar_chance: 25,
+            vault_chance: 25,
+            terrain_chance: 35,
+        }
+    }
+}
+
+impl GenConfig {
+    /// Loads the configuration from the given file. A missing file means the defaults are used.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path).map_err(IoError::FileReading)?;
+        Ok(from_reader(BufReader::new(file)).map_err(IoError::MapParsing)?)
+    }
+}
+
+/// Relative weights of the [RoomEncounter]s a region of a level is populated with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncounterWeights {
+    pub enemy: u32,
+    pub enemy_treasure: u32,
+    pub treasure: u32,
+    pub empty: u32,
+}
+
+impl Default for EncounterWeights {
+    fn default() -> Self {
+        Self { enemy: 30, enemy_treasure: 20, treasure: 25, empty: 25 }
+    }
+}
+
+impl EncounterWeights {
+    /// Picks a random encounter according to the weights. Falls back to [RoomEncounter::Empty] if all weights are zero.
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> RoomEncounter {
+        let total = self.enemy + self.enemy_treasure + self.treasure + self.empty;
+        if total == 0 {
+            return RoomEncounter::Empty;
+        }
+
+        let mut roll = rng.random_range(0..total);
+        for (weight, encounter) in [
+            (self.enemy, RoomEncounter::Enemy),
+            (self.enemy_treasure, RoomEncounter::EnemyTreasure),
+            (self.treasure, RoomEncounter::Treasure),
+        ] {
+            if roll < weight {
+                return encounter;
+            }
+            roll -= weight;
+        }
+
+        RoomEncounter::Empty
+    }
+}
+
+/// Path of the file the level generation is configured with.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn gen_config_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("gen_config.ron");
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_config_keeps_defaults() {
+        let config: GenConfig = ron::from_str("(room_number: 4, encounter_weights: (empty: 0))")
+            .expect("config should parse");
+
+        assert_eq!(config.room_number, 4);
+        assert_eq!(config.campfire_chance, GenConfig::default().campfire_chance);
+        assert_eq!(config.encounter_weights.enemy, EncounterWeights::default().enemy);
+        assert_eq!(config.encounter_weights.empty, 0);
+    }
+}
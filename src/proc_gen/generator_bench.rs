@@ -0,0 +1,162 @@
+use std::hint::black_box;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::time::{Duration, Instant};
+
+use crate::{
+    proc_gen::{
+        bsp::MapBSPTree, gen_config::GenConfig, proc_gen_level::ProcGenLevel,
+        proc_gen_room::ProcGenRoom, proc_gen_world::ProcGenWorld,
+    },
+    world::{
+        level_data::LevelData,
+        worldspace::{WORLD_HEIGHT, WORLD_WIDTH},
+    },
+};
+
+/// Map sizes the generator is measured with: smaller than, equal to and larger than the default size.
+pub const BENCH_MAP_SIZES: [(usize, usize); 3] = [(60, 20), (WORLD_WIDTH, WORLD_HEIGHT), (160, 40)];
+
+/// Full levels are generated for depths 1 to this, so every [LevelTheme](crate::proc_gen::level_theme::LevelTheme) is measured.
+pub const BENCH_MAX_DEPTH: usize = 20;
+
+/// Part of the generator that is measured.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GenStage {
+    /// Partitioning the map with [MapBSPTree::generate_bsp].
+    Bsp,
+    /// Connecting the rooms of a partitioned map with [ProcGenWorld::a_star_corridors].
+    Corridors,
+    /// Everything that happens when a new level is entered: [ProcGenLevel::generate_with_size] and the conversion into [LevelData].
+    FullLevel,
+}
+
+impl GenStage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GenStage::Bsp => "BSP",
+            GenStage::Corridors => "Corridors (A*)",
+            GenStage::FullLevel => "Full level",
+        }
+    }
+
+    /// Runs the stage once for a seed and measures how long it took. Preparations the stage depends on are not measured.
+    fn run(&self, seed: u64, width: usize, height: usize) -> Duration {
+        let config = GenConfig::default();
+        match self {
+            GenStage::Bsp => {
+                let start = Instant::now();
+                black_box(MapBSPTree::generate_bsp(seed, width, height, &config));
+                start.elapsed()
+            }
+            GenStage::Corridors => {
+                let bsp = MapBSPTree::generate_bsp(seed, width, height, &config);
+                let rooms = bsp.collect_leaves().into_iter().map(ProcGenRoom::from).collect();
+                let mut world =
+                    ProcGenWorld { rooms, corridors: Vec::new(), floor: Vec::new(), width, height };
+                world.shrink_rooms(seed, config.shrink_factor_range.clone());
+
+                let start = Instant::now();
+                world.a_star_corridors(seed, config.extra_corridor_chance);
+                start.elapsed()
+            }
+            GenStage::FullLevel => {
+                let depth = 1 + (seed as usize % BENCH_MAX_DEPTH);
+                let start = Instant::now();
+                black_box(LevelData::from(ProcGenLevel::generate_with_size(
+                    seed, depth, width, height, &config,
+                )));
+                start.elapsed()
+            }
+        }
+    }
+}
+
+/// Timings of one stage of the generator on maps of one size.
+#[derive(Debug)]
+pub struct GenBenchResult {
+    pub stage: GenStage,
+    pub width: usize,
+    pub height: usize,
+
+    /// Number of seeds the stage finished for.
+    pub runs: usize,
+
+    /// Number of seeds for which the stage panicked. These are not part of the timings.
+    pub failures: usize,
+
+    pub average: Duration,
+    pub slowest: Duration,
+}
+
+/// Measures every [GenStage] on all [BENCH_MAP_SIZES] with the seeds `0..seeds`.
+///
+/// A seed the generator panics on is counted as a failure, the others are still measured.
+pub fn bench_generator(seeds: u64) -> Vec<GenBenchResult> {
+    let mut results = Vec::new();
+
+    for (width, height) in BENCH_MAP_SIZES {
+        for stage in [GenStage::Bsp, GenStage::Corridors, GenStage::FullLevel] {
+            let mut timings = Vec::new();
+            let mut failures = 0;
+            for seed in 0..seeds {
+                match catch_unwind(AssertUnwindSafe(|| stage.run(seed, width, height))) {
+                    Ok(timing) => timings.push(timing),
+                    Err(_) => failures += 1,
+                }
+            }
+
+            let total: Duration = timings.iter().sum();
+            results.push(GenBenchResult {
+                stage,
+                width,
+                height,
+                runs: timings.len(),
+                failures,
+                average: total.checked_div(timings.len() as u32).unwrap_or_default(),
+                slowest: timings.iter().max().copied().unwrap_or_default(),
+            });
+        }
+    }
+
+    results
+}
+
+/// Formats the results of [bench_generator] as a table for printing.
+pub fn bench_table(seeds: u64, results: &[GenBenchResult]) -> String {
+    let mut lines = vec![
+        format!("Level generator timings ({} seeds per map size)", seeds),
+        format!(
+            "{:<16} {:>9} {:>6} {:>8} {:>12} {:>12}",
+            "Stage", "Size", "Runs", "Failed", "Average", "Slowest"
+        ),
+    ];
+    lines.extend(results.iter().map(|result| {
+        format!(
+            "{:<16} {:>9} {:>6} {:>8} {:>10.3}ms {:>10.3}ms",
+            result.stage.name(),
+            format!("{}x{}", result.width, result.height),
+            result.runs,
+            result.failures,
+            result.average.as_secs_f64() * 1000.0,
+            result.slowest.as_secs_f64() * 1000.0
+        )
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_stage_is_measured_on_every_map_size() {
+        let results = bench_generator(2);
+        assert_eq!(results.len(), BENCH_MAP_SIZES.len() * 3);
+        assert!(results.iter().all(|result| result.runs + result.failures == 2));
+        assert!(results.iter().all(|result| result.slowest >= result.average));
+
+        let table = bench_table(2, &results);
+        assert!(table.contains("160x40"));
+        assert!(table.contains("Corridors (A*)"));
+    }
+}
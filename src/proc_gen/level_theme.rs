@@ -0,0 +1,47 @@
+use rand::{Rng, seq::IndexedRandom};
+
+/// Generated levels shallower than this are always [LevelTheme::Dungeon]s.
+pub const FIRST_THEMED_DEPTH: usize = 4;
+
+/// Style of a procedurally generated level. Decides which generator builds the layout of the level.
+///
+/// All generators emit the same [LevelData](crate::world::level_data::LevelData), so the rest of the pipeline does not care about the theme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LevelTheme {
+    /// Rooms connected by corridors, generated with binary space partitioning.
+    #[default]
+    Dungeon,
+
+    /// Like [LevelTheme::Dungeon], but the rooms are barely shrunk, making them large open halls.
+    OpenHalls,
+
+    /// Natural caves grown with a cellular automaton.
+    Caves,
+
+    /// A labyrinth of narrow passages.
+    Maze,
+}
+
+impl LevelTheme {
+    /// Randomly picks the theme for a level of the given depth.
+    ///
+    /// The first levels are always dungeons. The deeper the level, the more likely caves and mazes become.
+    pub fn for_depth<R: Rng + ?Sized>(depth: usize, rng: &mut R) -> Self {
+        if depth < FIRST_THEMED_DEPTH {
+            return LevelTheme::Dungeon;
+        }
+
+        let depth = depth as u32;
+        let weights = [
+            (LevelTheme::Dungeon, 50),
+            (LevelTheme::OpenHalls, 20),
+            (LevelTheme::Caves, 10 + depth),
+            (LevelTheme::Maze, 5 + depth / 2),
+        ];
+
+        weights
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .map(|(theme, _)| *theme)
+            .unwrap_or_default()
+    }
+}
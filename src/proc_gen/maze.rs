@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::{
+    proc_gen::{bsp::PADDING, proc_gen_world::ProcGenWorld},
+    world::coordinate_system::Point,
+};
+
+/// Chance (in percent) that a wall between two maze cells is knocked down after generation, creating loops.
+pub const MAZE_LOOP_CHANCE: u32 = 8;
+
+impl ProcGenWorld {
+    /// Generates a maze level using a randomized depth-first search (recursive backtracker).
+    ///
+    /// The resulting perfect maze is braided a little by knocking down random walls, so that there is more than one way through it.
+    pub fn generate_maze(maze_seed: u64, width: usize, height: usize) -> Self {
+        let mut rng = StdRng::seed_from_u64(maze_seed);
+        let grid = MazeGrid::new(width, height);
+
+        let mut floor: HashSet<Point> = HashSet::new();
+        let mut visited = vec![false; grid.columns * grid.rows];
+
+        let start = (rng.random_range(0..grid.columns), rng.random_range(0..grid.rows));
+        let mut stack = vec![start];
+        visited[start.1 * grid.columns + start.0] = true;
+        floor.insert(cell_point(start));
+
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<(usize, usize)> = grid
+                .cell_neighbours(cell)
+                .into_iter()
+                .filter(|(x, y)| !visited[y * grid.columns + x])
+                .collect();
+
+            match unvisited.choose(&mut rng) {
+                Some(&next) => {
+                    visited[next.1 * grid.columns + next.0] = true;
+                    floor.insert(wall_between(cell, next));
+                    floor.insert(cell_point(next));
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        // Braiding
+        for y in 0..grid.rows {
+            for x in 0..grid.columns {
+                for neighbour in [(x + 1, y), (x, y + 1)] {
+                    if neighbour.0 < grid.columns
+                        && neighbour.1 < grid.rows
+                        && rng.random_range(0..100) < MAZE_LOOP_CHANCE
+                    {
+                        floor.insert(wall_between((x, y), neighbour));
+                    }
+                }
+            }
+        }
+
+        // Sorted, because HashSets are random.
+        let mut floor: Vec<Point> = floor.into_iter().collect();
+        floor.sort_by_key(|point| (point.y, point.x));
+
+        Self { rooms: Vec::new(), corridors: Vec::new(), floor, width, height }
+    }
+}
+
+/// Point on the map of the maze cell at the given column and row.
+fn cell_point((x, y): (usize, usize)) -> Point {
+    Point::new(PADDING + 2 * x, PADDING + 2 * y)
+}
+
+/// Point on the map of the wall between two neighbouring maze cells.
+fn wall_between(a: (usize, usize), b: (usize, usize)) -> Point {
+    let point_a = cell_point(a);
+    let point_b = cell_point(b);
+    Point::new((point_a.x + point_b.x) / 2, (point_a.y + point_b.y) / 2)
+}
+
+/// Grid of maze cells that fits into a map of a given size.
+struct MazeGrid {
+    /// Number of maze cells horizontally. Cells lie on every second tile, with walls in between.
+    columns: usize,
+
+    /// Number of maze cells vertically.
+    rows: usize,
+}
+
+impl MazeGrid {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            columns: (width.saturating_sub(2 * PADDING) / 2).max(1),
+            rows: height.saturating_sub(2 * PADDING) / 2 + 1,
+        }
+    }
+
+    /// Returns the neighbouring maze cells of a cell in the 4 cardinal directions.
+    fn cell_neighbours(&self, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut neighbours = Vec::new();
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        }
+        if x + 1 < self.columns {
+            neighbours.push((x + 1, y));
+        }
+        if y > 0 {
+            neighbours.push((x, y - 1));
+        }
+        if y + 1 < self.rows {
+            neighbours.push((x, y + 1));
+        }
+        neighbours
+    }
+}
@@ -1,21 +1,42 @@
+use std::ops::Range;
+
 use rand::{
     Rng,
-    distr::{Distribution, StandardUniform},
     seq::{IndexedRandom, SliceRandom},
 };
 
 use crate::{
-    data::{item_defs::item_defs, npc_defs::npc_defs},
-    proc_gen::{proc_gen_level::ProcGenLevel, proc_gen_room::ProcGenRoom},
+    ai::pack::{PACK_SIZE, is_pack_npc},
+    core::{shop::SHOPKEEPER_NPCS, town::HEALER_NPCS},
+    data::{
+        encounters::{EncounterDef, is_encounter_leader, roll_encounter},
+        floor_affixes::AFFIX_NPC_WEIGHT_FACTOR,
+        loot_tables::get_loot_table_by_id,
+        npc_defs::{NpcDefId, npc_defs},
+    },
+    proc_gen::proc_gen_level::ProcGenLevel,
     world::{
+        biome::{Biome, NATIVE_NPC_WEIGHT_FACTOR},
         coordinate_system::Point,
         level_data::{SpawnData, SpawnKind},
     },
 };
 
+/// Chance (in percent) that a monster is picked from above the danger rating of the level.
+const OUT_OF_DEPTH_CHANCE: u32 = 5;
+
+/// How far above the danger rating of the level an out-of-depth monster can be.
+pub(crate) const OUT_OF_DEPTH_RANGE: u8 = 3;
+
+/// Weight of monsters that match the danger rating of the level. Each point of danger below that lowers the weight by one.
+const DANGER_MATCH_WEIGHT: u32 = 4;
+
+/// How often the treasure loot table is rolled to fill a chest.
+const CHEST_ROLLS: usize = 3;
+
 /// Defines all possible "Encounters", which are variants for how a room can be populated.
 ///
-/// This implements [Distribution], where the chances of each random `RoomEncounter` are defined
+/// The chances of each random `RoomEncounter` are defined by [EncounterWeights](crate::proc_gen::gen_config::EncounterWeights).
 pub enum RoomEncounter {
     Empty,
     Enemy,
@@ -24,105 +45,327 @@ pub enum RoomEncounter {
     // Trap
 }
 
-impl Distribution<RoomEncounter> for StandardUniform {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RoomEncounter {
-        match rng.random_range(0..100) {
-            0..=29 => RoomEncounter::Enemy,
-            30..=49 => RoomEncounter::EnemyTreasure,
-            50..=74 => RoomEncounter::Treasure,
-            _ => RoomEncounter::Empty,
-        }
-    }
-}
-
 impl ProcGenLevel {
     /// Populates the level with npcs.
     ///
-    /// Populating a room requires its data, which is why populate is a method on room as well.
-    pub fn populate<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+    /// Each region of the level (see [ProcGenLevel::regions]) gets its own random encounter.
+    /// The `depth` (level number) is used to scale the loot found in the level. The [Biome] of the level decides which monsters and treasure are favoured.
+    /// A floor affix can favour some more monsters.
+    pub fn populate<R: Rng + ?Sized>(&mut self, depth: usize, rng: &mut R) {
         let blocked_points: Vec<Point> = vec![self.entry, self.exit];
-        for room in &mut self.world.rooms {
-            let encounter: RoomEncounter = rng.random();
+        let favoured_npcs = self.affix_def().map_or(&[][..], |affix| affix.favoured_npcs);
+        for mut region in self.regions() {
+            let encounter = self.config.encounter_weights.roll(rng);
+            region.retain(|point| !blocked_points.contains(point));
 
-            let mut population = room.populate(encounter, &blocked_points, rng);
+            let mut population = populate_region(
+                region,
+                encounter,
+                self.biome,
+                favoured_npcs,
+                depth,
+                self.config.room_npcs.clone(),
+                rng,
+            );
             self.spawns.append(&mut population);
         }
     }
-}
 
-impl ProcGenRoom {
-    /// Populates the room with spawn points for NPCs and Data
+    /// Spawns the hunters of the level's floor affix (see [FloorAffixDef::hunters](crate::data::floor_affixes::FloorAffixDef::hunters)).
     ///
-    /// # Arguments
-    /// * `encounter`: Type of encounter. Defines what should be spawned.
-    /// * `blocked_points`: Points that cannot be spawn points.
-    /// * `rng`: Rng Instance.
-    pub fn populate<R: Rng + ?Sized>(
-        &mut self,
-        encounter: RoomEncounter,
-        blocked_points: &[Point],
-        rng: &mut R,
-    ) -> Vec<SpawnData> {
-        let mut available_points = self.floor_points();
+    /// They are placed in the region that lies farthest from the entry, so the player has a head start.
+    pub fn add_hunters<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let hunters = self.affix_def().map_or(&[][..], |affix| affix.hunters);
+        if hunters.is_empty() {
+            return;
+        }
+
+        let entry = self.entry;
+        let Some(region) = self
+            .regions()
+            .into_iter()
+            .filter(|region| !region.is_empty())
+            .max_by_key(|region| region[0].distance_squared_from(entry))
+        else {
+            return;
+        };
+
+        let mut blocked_points: Vec<Point> = vec![self.entry, self.exit];
+        blocked_points.extend(self.spawns.iter().map(|spawn| Point::new(spawn.x, spawn.y)));
+
+        let mut available_points = region;
         available_points.retain(|point| !blocked_points.contains(point));
         available_points.shuffle(rng);
 
-        let mut population = Vec::new();
-
-        match encounter {
-            RoomEncounter::Empty => {}
-            RoomEncounter::Enemy => {
-                population.append(&mut random_npcs(&mut available_points, rng));
-            }
-            RoomEncounter::EnemyTreasure => {
-                population.append(&mut random_npcs(&mut available_points, rng));
-                population.append(&mut random_items(&mut available_points, rng));
-            }
-            RoomEncounter::Treasure => {
-                population.append(&mut random_items(&mut available_points, rng));
+        for def_id in hunters {
+            if let Some(point) = available_points.pop() {
+                let spawn_kind = SpawnKind::Hunter { def_id: def_id.to_string() };
+                self.spawns.push(SpawnData { kind: spawn_kind, x: point.x, y: point.y });
             }
         }
+    }
+}
+
+/// Populates a region (e.g. the floor of a room) with spawn points for NPCs and Data
+///
+/// # Arguments
+/// * `available_points`: Floor of the region that can be used as spawn points.
+/// * `encounter`: Type of encounter. Defines what should be spawned.
+/// * `biome`: Biome of the level. Defines the monster and treasure pools.
+/// * `favoured_npcs`: Monsters that spawn more often, because of the floor affix of the level.
+/// * `depth`: Level number. Deeper levels have better loot and more dangerous monsters.
+/// * `room_npcs`: Range the number of monsters in an encounter with enemies is picked from (see [GenConfig::room_npcs](crate::proc_gen::gen_config::GenConfig::room_npcs)).
+/// * `rng`: Rng Instance.
+pub fn populate_region<R: Rng + ?Sized>(
+    mut available_points: Vec<Point>,
+    encounter: RoomEncounter,
+    biome: Biome,
+    favoured_npcs: &[&str],
+    depth: usize,
+    room_npcs: Range<usize>,
+    rng: &mut R,
+) -> Vec<SpawnData> {
+    available_points.shuffle(rng);
+
+    let mut population = Vec::new();
 
-        population
+    match encounter {
+        RoomEncounter::Empty => {}
+        RoomEncounter::Enemy => {
+            population.append(&mut random_npcs(
+                &mut available_points,
+                biome,
+                favoured_npcs,
+                depth,
+                room_npcs,
+                rng,
+            ));
+        }
+        RoomEncounter::EnemyTreasure => {
+            population.append(&mut random_npcs(
+                &mut available_points,
+                biome,
+                favoured_npcs,
+                depth,
+                room_npcs,
+                rng,
+            ));
+            population.append(&mut random_items(&mut available_points, biome, depth, rng));
+        }
+        RoomEncounter::Treasure => {
+            population.extend(random_chest(&mut available_points, biome, depth, rng));
+        }
     }
+
+    population
 }
 
 /// Helper method that randomly selects npcs to spawn and where to put them.
-fn random_npcs<R: Rng + ?Sized>(available_points: &mut Vec<Point>, rng: &mut R) -> Vec<SpawnData> {
-    let spawns_amount = rng.random_range(1..3);
+///
+/// Some rooms hold a themed encounter (see [roll_encounter]), the others get monsters picked one by one.
+fn random_npcs<R: Rng + ?Sized>(
+    available_points: &mut Vec<Point>,
+    biome: Biome,
+    favoured_npcs: &[&str],
+    depth: usize,
+    room_npcs: Range<usize>,
+    rng: &mut R,
+) -> Vec<SpawnData> {
+    if let Some(encounter) = roll_encounter(biome, depth_danger(depth), rng) {
+        return encounter_group(available_points, encounter);
+    }
+
+    let spawns_amount = if room_npcs.is_empty() { 0 } else { rng.random_range(room_npcs) };
 
     let mut spawns: Vec<SpawnData> = Vec::new();
-    for _ in 0..spawns_amount {
-        let mut npcs: Vec<&String> = npc_defs().keys().collect();
-        npcs.sort(); // The definitions need to be sorted because apparently HashMaps are random.
+    let mut remaining = spawns_amount;
+    while remaining > 0 {
+        let npc_def_id = random_npc_def(biome, favoured_npcs, depth, rng);
+        // Packs count towards the number of monsters of the encounter with all of their members
+        let pack_size = match npc_def_id {
+            Some(def_id) if is_pack_npc(def_id) => rng.random_range(PACK_SIZE).min(remaining),
+            _ => 1,
+        };
+        remaining -= pack_size;
 
-        if let Some(npc_def_id) = npcs.choose(rng) {
-            if let Some(point) = available_points.pop() {
-                let spawn_kind = SpawnKind::Npc { def_id: npc_def_id.to_string() };
-                spawns.push(SpawnData { kind: spawn_kind, x: point.x, y: point.y });
-            }
+        if let Some(npc_def_id) = npc_def_id
+            && let Some(point) = available_points.pop()
+        {
+            let def_id = npc_def_id.to_string();
+            let spawn_kind = if pack_size > 1 {
+                SpawnKind::Pack { def_id, size: pack_size }
+            } else {
+                SpawnKind::Npc { def_id }
+            };
+            spawns.push(SpawnData { kind: spawn_kind, x: point.x, y: point.y });
         }
     }
 
     spawns
 }
 
-/// Helper method that randomly selects items to spawn as sprites and where to put them.
-fn random_items<R: Rng + ?Sized>(available_points: &mut Vec<Point>, rng: &mut R) -> Vec<SpawnData> {
-    let spawns_amount = rng.random_range(1..2);
+/// Helper method that places the monsters of an encounter as a group, around a random point of the region.
+fn encounter_group(available_points: &mut Vec<Point>, encounter: &EncounterDef) -> Vec<SpawnData> {
+    let Some(&anchor) = available_points.last() else {
+        return Vec::new();
+    };
 
     let mut spawns: Vec<SpawnData> = Vec::new();
-    for _ in 0..spawns_amount {
-        let mut item_defs: Vec<&String> = item_defs().keys().collect();
-        item_defs.sort(); // The definitions need to be sorted because apparently HashMaps are random.
+    for def_id in encounter.members() {
+        let Some((index, _)) = available_points
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, point)| point.distance_squared_from(anchor))
+        else {
+            break;
+        };
+        let point = available_points.swap_remove(index);
+        let spawn_kind = SpawnKind::Npc { def_id: def_id.to_string() };
+        spawns.push(SpawnData { kind: spawn_kind, x: point.x, y: point.y });
+    }
 
-        if let Some(item_def_id) = item_defs.choose(rng) {
-            if let Some(point) = available_points.pop() {
-                let spawn_kind = SpawnKind::Item { def_id: item_def_id.to_string() };
-                spawns.push(SpawnData { kind: spawn_kind, x: point.x, y: point.y });
-            }
+    spawns
+}
+
+/// Danger rating of the monsters that fit a level of the given depth.
+pub(crate) fn depth_danger(depth: usize) -> u8 {
+    (1 + depth / 2).min(u8::MAX as usize) as u8
+}
+
+/// Picks an NPC definition that fits the depth of the level.
+///
+/// Monsters are picked from a pool weighted towards the danger rating of the level, with weaker ones becoming rarer.
+/// Monsters native to the [Biome] are [NATIVE_NPC_WEIGHT_FACTOR] times as likely, favoured ones [AFFIX_NPC_WEIGHT_FACTOR] times.
+/// With a chance of [OUT_OF_DEPTH_CHANCE] percent, a monster from up to [OUT_OF_DEPTH_RANGE] points above the rating is picked instead.
+pub fn random_npc_def<R: Rng + ?Sized>(
+    biome: Biome,
+    favoured_npcs: &[&str],
+    depth: usize,
+    rng: &mut R,
+) -> Option<&'static NpcDefId> {
+    let danger = depth_danger(depth);
+
+    let mut npcs: Vec<(&NpcDefId, u8)> = npc_defs()
+        .iter()
+        .filter(|(def_id, _)| !SHOPKEEPER_NPCS.contains(&def_id.as_str())) // Shopkeepers only come with shops
+        .filter(|(def_id, _)| !HEALER_NPCS.contains(&def_id.as_str())) // Healers only live in the town
+        .filter(|(def_id, _)| !is_encounter_leader(def_id)) // Leaders only come with their encounter
+        .map(|(def_id, def)| (def_id, def.danger))
+        .collect();
+    npcs.sort(); // The definitions need to be sorted because apparently HashMaps are random.
+
+    if rng.random_range(0..100) < OUT_OF_DEPTH_CHANCE {
+        let out_of_depth: Vec<&NpcDefId> = npcs
+            .iter()
+            .filter(|(_, npc_danger)| {
+                *npc_danger > danger && *npc_danger <= danger.saturating_add(OUT_OF_DEPTH_RANGE)
+            })
+            .map(|(def_id, _)| *def_id)
+            .collect();
+
+        if let Some(def_id) = out_of_depth.choose(rng) {
+            return Some(def_id);
+        }
+    }
+
+    npcs.choose_weighted(rng, |(def_id, npc_danger)| {
+        if *npc_danger > danger {
+            return 0;
+        }
+
+        let mut weight = DANGER_MATCH_WEIGHT.saturating_sub((danger - npc_danger) as u32).max(1);
+        if biome.native_npcs().contains(&def_id.as_str()) {
+            weight *= NATIVE_NPC_WEIGHT_FACTOR;
+        }
+        if favoured_npcs.contains(&def_id.as_str()) {
+            weight *= AFFIX_NPC_WEIGHT_FACTOR;
+        }
+        weight
+    })
+    .ok()
+    .map(|(def_id, _)| *def_id)
+}
+
+/// Helper method that rolls the treasure loot table of the biome and decides where to put the items.
+fn random_items<R: Rng + ?Sized>(
+    available_points: &mut Vec<Point>,
+    biome: Biome,
+    depth: usize,
+    rng: &mut R,
+) -> Vec<SpawnData> {
+    let Some(loot_table) = get_loot_table_by_id(biome.treasure_loot_table()) else {
+        return Vec::new();
+    };
+
+    let mut spawns: Vec<SpawnData> = Vec::new();
+    for item_def_id in loot_table.roll(depth, rng) {
+        if let Some(point) = available_points.pop() {
+            let spawn_kind = SpawnKind::Item { def_id: item_def_id.to_string() };
+            spawns.push(SpawnData { kind: spawn_kind, x: point.x, y: point.y });
         }
     }
 
     spawns
 }
+
+/// Helper method that fills a chest with [CHEST_ROLLS] rolls of the treasure loot table of the biome and decides where to put it.
+///
+/// # Returns
+/// * `None` if there is no room for the chest.
+fn random_chest<R: Rng + ?Sized>(
+    available_points: &mut Vec<Point>,
+    biome: Biome,
+    depth: usize,
+    rng: &mut R,
+) -> Option<SpawnData> {
+    let loot_table = get_loot_table_by_id(biome.treasure_loot_table())?;
+    let point = available_points.pop()?;
+
+    let contents = (0..CHEST_ROLLS)
+        .flat_map(|_| loot_table.roll(depth, rng))
+        .map(|item_def_id| item_def_id.to_string())
+        .collect();
+
+    Some(SpawnData { kind: SpawnKind::Chest { contents }, x: point.x, y: point.y })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::data::encounters::encounter_defs;
+
+    #[test]
+    fn monsters_fit_the_depth() {
+        let mut rng = StdRng::seed_from_u64(73);
+
+        for _ in 0..500 {
+            let def_id = random_npc_def(Biome::Dungeon, &[], 0, &mut rng).unwrap();
+            assert!(npc_defs()[def_id].danger <= depth_danger(0) + OUT_OF_DEPTH_RANGE);
+        }
+
+        let deep_picks: Vec<&NpcDefId> =
+            (0..500).filter_map(|_| random_npc_def(Biome::Dungeon, &[], 16, &mut rng)).collect();
+        assert!(deep_picks.iter().any(|def_id| npc_defs()[*def_id].danger >= 8));
+    }
+
+    #[test]
+    fn encounters_are_placed_as_a_group() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut available_points: Vec<Point> =
+            (0..10).flat_map(|x| (0..10).map(move |y| Point::new(x, y))).collect();
+        available_points.shuffle(&mut rng);
+
+        let encounter = &encounter_defs()["goblin_warren"];
+        let spawns = encounter_group(&mut available_points, encounter);
+        assert_eq!(spawns.len(), 4);
+        assert!(matches!(&spawns[0].kind, SpawnKind::Npc { def_id } if def_id == "goblin_boss"));
+        assert_eq!(available_points.len(), 96);
+
+        let leader = Point::new(spawns[0].x, spawns[0].y);
+        for spawn in &spawns {
+            assert!(Point::new(spawn.x, spawn.y).distance_squared_from(leader) <= 2);
+        }
+    }
+}
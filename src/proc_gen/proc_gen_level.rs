@@ -1,14 +1,30 @@
+use std::collections::BTreeMap;
+
 use rand::{Rng, RngCore, SeedableRng, rngs::StdRng, seq::IndexedRandom};
 
 use crate::{
-    proc_gen::{bsp::MapBSPTree, proc_gen_world::ProcGenWorld},
+    data::floor_affixes::{FloorAffixDef, FloorAffixId, get_floor_affix_by_id, roll_floor_affix},
+    proc_gen::{
+        bsp::MapBSPTree, gen_config::GenConfig, level_theme::LevelTheme,
+        proc_gen_world::ProcGenWorld, vaults::PlacedVault,
+    },
     world::{
+        biome::Biome,
         coordinate_system::Point,
         level_data::{LevelData, RoomData, SpawnData, TileData, TileTypeData},
         worldspace::{WORLD_HEIGHT, WORLD_WIDTH},
     },
 };
 
+/// Width of the sectors that open floor (caves, mazes) is split into for population.
+const SECTOR_WIDTH: usize = 20;
+
+/// Height of the sectors that open floor (caves, mazes) is split into for population.
+const SECTOR_HEIGHT: usize = 12;
+
+/// Sectors with less open floor than this are not populated.
+const MIN_SECTOR_FLOOR: usize = 8;
+
 /// Data Structure that holds all data for a level that is being procedurally generated.
 /// This data structure is composed of other data structures involved in the procedural generation process.
 pub struct ProcGenLevel {
@@ -23,23 +39,82 @@ pub struct ProcGenLevel {
 
     /// Contains the lots of `SpawnData` for the entire world. (Items and Npcs)
     pub spawns: Vec<SpawnData>,
+
+    /// Points where campfires are placed.
+    pub campfires: Vec<Point>,
+
+    /// Points where altars are placed.
+    pub altars: Vec<Point>,
+
+    /// Region of the anthill the level belongs to. Influences which monsters and treasure populate it.
+    pub biome: Biome,
+
+    /// Handcrafted vaults stamped into the rooms of the level.
+    pub vaults: Vec<PlacedVault>,
+
+    /// Water, lava and chasms.
+    pub terrain: Vec<TileData>,
+
+    /// Floor affix of the level (see [floor_affix_defs](crate::data::floor_affixes::floor_affix_defs)), if it has one.
+    pub affix: Option<FloorAffixId>,
+
+    /// Parameters the level is generated with.
+    pub config: GenConfig,
 }
 
 impl ProcGenLevel {
     /// Main entry point into the procedural generation script.
     /// Generates a new RNG instance with the given seed. This way the world generation remains deterministic.
-    pub fn generate(seed: u64) -> Self {
+    /// The `depth` is the number of the level that is generated and scales its contents. It also influences the [LevelTheme], the [Biome] and the floor affix.
+    ///
+    /// The level has the default size ([WORLD_WIDTH]x[WORLD_HEIGHT]). Use [ProcGenLevel::generate_with_size] for others.
+    /// The `config` holds the tunable parameters of the generation (see [GenConfig]).
+    pub fn generate(seed: u64, depth: usize, config: &GenConfig) -> Self {
+        Self::generate_with_size(seed, depth, WORLD_WIDTH, WORLD_HEIGHT, config)
+    }
+
+    /// Same as [ProcGenLevel::generate], but the level has the given size.
+    pub fn generate_with_size(
+        seed: u64,
+        depth: usize,
+        width: usize,
+        height: usize,
+        config: &GenConfig,
+    ) -> Self {
         let mut rng = StdRng::seed_from_u64(seed);
-        let bsp_seed = rng.next_u64();
+        let layout_seed = rng.next_u64();
         let room_shrinking_seed = rng.next_u64();
         let corridor_seed = rng.next_u64();
         let population_seed = rng.next_u64();
 
-        let bsp = MapBSPTree::generate_bsp(bsp_seed);
-        let proc_gen_world =
-            ProcGenWorld::generate_from_bsp(bsp, room_shrinking_seed, corridor_seed);
+        let proc_gen_world = match LevelTheme::for_depth(depth, &mut rng) {
+            LevelTheme::Dungeon => ProcGenWorld::generate_from_bsp(
+                MapBSPTree::generate_bsp(layout_seed, width, height, config),
+                room_shrinking_seed,
+                corridor_seed,
+                config,
+            ),
+            LevelTheme::OpenHalls => ProcGenWorld::generate_open_halls(
+                MapBSPTree::generate_bsp(layout_seed, width, height, config),
+                room_shrinking_seed,
+                corridor_seed,
+                config,
+            ),
+            LevelTheme::Caves => ProcGenWorld::generate_caves(layout_seed, width, height),
+            LevelTheme::Maze => ProcGenWorld::generate_maze(layout_seed, width, height),
+        };
+
+        let biome = Biome::for_depth(depth, &mut rng);
+        let affix = roll_floor_affix(depth, &mut rng);
 
-        ProcGenLevel::generate_from_world(proc_gen_world, population_seed)
+        ProcGenLevel::generate_from_world(
+            proc_gen_world,
+            biome,
+            affix,
+            depth,
+            population_seed,
+            config,
+        )
     }
 
     /// Function to extend a [ProcGenWorld] into a [ProcGenLevel].
@@ -48,7 +123,14 @@ impl ProcGenLevel {
     ///
     /// # Usage
     /// Call [ProcGenLevel::generate] with a seed to start the world generation.
-    fn generate_from_world(world: ProcGenWorld, population_seed: u64) -> Self {
+    pub(crate) fn generate_from_world(
+        world: ProcGenWorld,
+        biome: Biome,
+        affix: Option<FloorAffixId>,
+        depth: usize,
+        population_seed: u64,
+        config: &GenConfig,
+    ) -> Self {
         let mut rng = StdRng::seed_from_u64(population_seed);
 
         let mut level = ProcGenLevel {
@@ -56,61 +138,247 @@ impl ProcGenLevel {
             entry: Point::default(),
             exit: Point::default(),
             spawns: Vec::new(),
+            campfires: Vec::new(),
+            altars: Vec::new(),
+            biome,
+            vaults: Vec::new(),
+            terrain: Vec::new(),
+            affix,
+            config: config.clone(),
         };
 
-        level.populate(&mut rng);
+        level.add_vaults(depth, &mut rng);
+        level.add_terrain(depth, &mut rng);
+        level.add_flooding(&mut rng);
+        level.populate(depth, &mut rng);
         level.add_entry_exit(&mut rng);
+        level.add_hunters(&mut rng);
+        level.add_lighting(&mut rng);
+        level.add_campfires(&mut rng);
+        level.add_altars(&mut rng);
 
         level
     }
 
+    /// Assigns a random lighting to every room, so different parts of a level feel distinct.
+    /// A floor affix with its own lighting overrides the lighting of all rooms.
+    pub fn add_lighting<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let affix_lighting = self.affix_def().and_then(|affix| affix.lighting);
+        for room in &mut self.world.rooms {
+            let lighting = rng.random();
+            room.lighting = affix_lighting.unwrap_or(lighting);
+        }
+    }
+
+    /// Gets the definition of the level's floor affix.
+    pub fn affix_def(&self) -> Option<&'static FloorAffixDef> {
+        self.affix.as_deref().and_then(get_floor_affix_by_id)
+    }
+
+    /// Returns the areas of the level that are populated independently of each other.
+    ///
+    /// These are the floors of all rooms, followed by the open floor (of caves and mazes) split into sectors.
+    /// Vaults are populated by their own spawns and nothing is placed on terrain features, so they are not part of any region.
+    pub fn regions(&self) -> Vec<Vec<Point>> {
+        let mut excluded = self.vault_footprint();
+        excluded.extend(self.terrain_footprint());
+
+        let mut regions: Vec<Vec<Point>> = self
+            .world
+            .rooms
+            .iter()
+            .map(|room| {
+                let mut floor = room.floor_points();
+                floor.retain(|point| !excluded.contains(point));
+                floor
+            })
+            .collect();
+
+        let mut sectors: BTreeMap<(usize, usize), Vec<Point>> = BTreeMap::new();
+        for point in &self.world.floor {
+            sectors
+                .entry((point.x / SECTOR_WIDTH, point.y / SECTOR_HEIGHT))
+                .or_default()
+                .push(*point);
+        }
+        regions.extend(sectors.into_values().filter(|sector| sector.len() >= MIN_SECTOR_FLOOR));
+
+        regions
+    }
+
+    /// Places a campfire in a random region with a chance of [GenConfig::campfire_chance] percent.
+    ///
+    /// The campfire is never placed on the entry, the exit or a spawn point.
+    pub fn add_campfires<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if rng.random_range(0..100) >= self.config.campfire_chance {
+            return;
+        }
+
+        let Some(mut available_points) = self.regions().choose(rng).cloned() else {
+            return;
+        };
+
+        let mut blocked_points: Vec<Point> = vec![self.entry, self.exit];
+        blocked_points.extend(self.spawns.iter().map(|spawn| Point::new(spawn.x, spawn.y)));
+
+        available_points.retain(|point| !blocked_points.contains(point));
+
+        if let Some(point) = available_points.choose(rng) {
+            self.campfires.push(*point);
+        }
+    }
+
+    /// Places an altar in a random region with a chance of [GenConfig::altar_chance] percent.
+    ///
+    /// The altar is never placed on the entry, the exit, a spawn point or a campfire.
+    pub fn add_altars<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if rng.random_range(0..100) >= self.config.altar_chance {
+            return;
+        }
+
+        let Some(mut available_points) = self.regions().choose(rng).cloned() else {
+            return;
+        };
+
+        let mut blocked_points: Vec<Point> = vec![self.entry, self.exit];
+        blocked_points.extend(self.spawns.iter().map(|spawn| Point::new(spawn.x, spawn.y)));
+        blocked_points.extend(&self.campfires);
+
+        available_points.retain(|point| !blocked_points.contains(point));
+
+        if let Some(point) = available_points.choose(rng) {
+            self.altars.push(*point);
+        }
+    }
+
     /// Adds entry points and exit points for the Map (which will be turned into stairs, up and down respectively)
+    ///
+    /// Entry and exit are put into different regions. If the level only has one region, they are put into the same one.
     pub fn add_entry_exit<R: Rng + ?Sized>(&mut self, rng: &mut R) {
-        // Define rooms that need to exist on every level.
-        let mut mandatory_rooms = self.world.rooms.choose_multiple(rng, 2);
-        let entry_room = mandatory_rooms
+        // Define regions that need to exist on every level.
+        let regions = self.regions();
+        let mut mandatory_regions = regions.choose_multiple(rng, 2);
+        let entry_region = mandatory_regions
             .next()
-            .expect("Could not choose from rooms because the room number is 0.");
-        let exit_room = mandatory_rooms
-            .next()
-            .expect("Could not choose from rooms because the room number is 0.");
+            .expect("Could not choose from regions because the region number is 0.");
+        let exit_region = mandatory_regions.next().unwrap_or(entry_region);
 
         // Determine entry
-        let entry_room_floor = entry_room.floor_points();
-        let entry_point = entry_room_floor
+        let entry_point = entry_region
             .choose(rng)
-            .expect("Room smaller than 0. Rooms are by definition bigger than 0");
+            .expect("Region smaller than 0. Regions are by definition bigger than 0");
         self.entry = *entry_point;
 
         // Determine exit
-        let exit_room_floor = exit_room.floor_points();
-        let exit_point = exit_room_floor
+        let exit_region_floor: Vec<&Point> =
+            exit_region.iter().filter(|point| **point != self.entry).collect();
+        let exit_point = exit_region_floor
             .choose(rng)
-            .expect("Room smaller than 0. Rooms are by definition bigger than 0");
-        self.exit = *exit_point;
+            .expect("Region smaller than 2. Regions are by definition bigger than 2");
+        self.exit = **exit_point;
     }
 }
 
 impl From<ProcGenLevel> for LevelData {
     fn from(value: ProcGenLevel) -> Self {
-        let room_data: Vec<RoomData> = value.world.rooms.into_iter().map(RoomData::from).collect();
+        // Open floor (caves and mazes) and the walls encasing it
+        let mut tiles: Vec<TileData> = value
+            .world
+            .floor_walls()
+            .into_iter()
+            .map(|point| TileData { x: point.x, y: point.y, tile_type: TileTypeData::Wall })
+            .collect();
+        tiles.extend(value.world.floor.iter().map(|point| TileData {
+            x: point.x,
+            y: point.y,
+            tile_type: TileTypeData::Floor,
+        }));
+
+        // Vaults
+        let mut scripts = Vec::new();
+        let mut triggers = Vec::new();
+        for vault in value.vaults {
+            tiles.extend(vault.tiles);
+            scripts.extend(vault.script);
+            triggers.extend(vault.triggers);
+        }
+
+        // Water, lava and chasms
+        tiles.extend(value.terrain);
+
+        // Entry
+        tiles.push(TileData {
+            x: value.entry.x,
+            y: value.entry.y,
+            tile_type: TileTypeData::StairsUp,
+        });
+        // Exit
+        tiles.push(TileData {
+            x: value.exit.x,
+            y: value.exit.y,
+            tile_type: TileTypeData::StairsDown,
+        });
+        tiles.extend(value.campfires.iter().map(|point| TileData {
+            x: point.x,
+            y: point.y,
+            tile_type: TileTypeData::Campfire,
+        }));
+        tiles.extend(value.altars.iter().map(|point| TileData {
+            x: point.x,
+            y: point.y,
+            tile_type: TileTypeData::Altar,
+        }));
 
-        let tiles: Vec<TileData> = vec![
-            // Entry
-            TileData { x: value.entry.x, y: value.entry.y, tile_type: TileTypeData::StairsUp },
-            // Exit
-            TileData { x: value.exit.x, y: value.exit.y, tile_type: TileTypeData::StairsDown },
-        ];
+        let room_data: Vec<RoomData> = value.world.rooms.into_iter().map(RoomData::from).collect();
 
         LevelData {
-            width: WORLD_WIDTH,
-            height: WORLD_HEIGHT,
+            width: value.world.width,
+            height: value.world.height,
             tiles,
             rooms: room_data,
             corridors: value.world.corridors,
             entry: value.entry,
             exit: value.exit,
             spawns: value.spawns,
+            biome: value.biome,
+            affix: value.affix,
+            scripts,
+            triggers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{tiles::TileType, worldspace::World};
+
+    #[test]
+    fn caves_and_mazes_emit_playable_level_data() {
+        for world in [
+            ProcGenWorld::generate_caves(73, WORLD_WIDTH, WORLD_HEIGHT),
+            ProcGenWorld::generate_maze(73, WORLD_WIDTH, WORLD_HEIGHT),
+        ] {
+            let level = ProcGenLevel::generate_from_world(
+                world,
+                Biome::Crypt,
+                None,
+                10,
+                42,
+                &GenConfig::default(),
+            );
+            assert!(level.regions().len() >= 2);
+            assert_ne!(level.entry, level.exit);
+            assert!(level.world.floor.contains(&level.exit));
+
+            let data = LevelData::from(level);
+            let mut world = World::new();
+            world.apply_level_data(&data, 10).unwrap();
+
+            assert_eq!(world.get_tile(data.entry).tile_type, TileType::StairsUp);
+            for spawn in &data.spawns {
+                assert_eq!(world.get_tile(Point::new(spawn.x, spawn.y)).tile_type, TileType::Floor);
+            }
         }
     }
 }
@@ -7,13 +7,10 @@ use crate::{
     world::{
         coordinate_system::{Point, PointVector},
         level_data::RoomData,
+        lighting::RoomLighting,
     },
 };
 
-/// In the shrinking stage of the algorithm, the binary search partitions are shrunk, so there's spacing between rooms and so it looks more natual.
-/// From this range, a random value is pulled for each room and used to shrink the room to that fraction.
-pub const SHRINK_FACTOR_RANGE: std::ops::Range<f32> = 0.5..0.9;
-
 /// Minimum dimensions a MapNode should have after shrinking.
 ///
 /// This is a separate value, so that a buffer is always possible for rooms to shrink by at least 1.
@@ -32,11 +29,14 @@ pub struct ProcGenRoom {
 
     /// Point of the end (bottom right) of the room.
     pub point_b: Point,
+
+    /// Lighting of the room.
+    pub lighting: RoomLighting,
 }
 
 impl From<MapBSPNode> for ProcGenRoom {
     fn from(value: MapBSPNode) -> Self {
-        Self { point_a: value.point_a, point_b: value.point_b }
+        Self { point_a: value.point_a, point_b: value.point_b, lighting: RoomLighting::Normal }
     }
 }
 
@@ -49,27 +49,28 @@ impl From<ProcGenRoom> for RoomData {
             y: value.point_a.y,
             width: dimensions.x as usize,
             height: dimensions.y as usize,
+            lighting: value.lighting,
         }
     }
 }
 
 impl ProcGenRoom {
-    /// Shrinks a room to a random size that is bounded by [MIN_ROOM_DIM_SHRUNK].
+    /// Shrinks a room to a random size that is bounded by [MIN_ROOM_DIM_SHRUNK]. The shrink factor is pulled from `factor_range`.
     ///
     /// Since the results of the Binary Space Partitioning Algorithm are [MapNode]s that are too large, this shrinking will make the map look more natural.
-    pub fn shrink<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+    pub fn shrink<R: Rng + ?Sized>(&mut self, factor_range: std::ops::Range<f32>, rng: &mut R) {
         let width = self.point_b.x - self.point_a.x;
         let height = self.point_b.y - self.point_a.y;
 
         // Shrunk width
         let max_width = width.saturating_sub(2); // Guarantees shrinking by at least 1.
         let new_width =
-            shrink_dimension(width, rng.random_range(SHRINK_FACTOR_RANGE)).min(max_width);
+            shrink_dimension(width, rng.random_range(factor_range.clone())).min(max_width);
 
         // Shrunk height. Max 1.5 times as large as the width. This avoids weird long rooms (due to terminal grid not being 1:1)
         let max_height = height.saturating_sub(2); // Guarantees shrinking by at least 1.
         let mut new_height =
-            shrink_dimension(height, rng.random_range(SHRINK_FACTOR_RANGE)).min(max_height);
+            shrink_dimension(height, rng.random_range(factor_range)).min(max_height);
         new_height = cmp::min(new_height, (new_width as f32 * 1.5) as usize); // Incrase size to make rooms less vertically long
 
         let min_origin_x = self.point_a.x + 1;
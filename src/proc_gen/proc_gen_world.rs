@@ -1,7 +1,9 @@
+use std::{collections::HashSet, ops::Range};
+
 use rand::{SeedableRng, rngs::StdRng};
 
 use crate::{
-    proc_gen::{bsp::MapBSPTree, proc_gen_room::ProcGenRoom},
+    proc_gen::{bsp::MapBSPTree, gen_config::GenConfig, proc_gen_room::ProcGenRoom},
     world::coordinate_system::Point,
 };
 
@@ -12,6 +14,15 @@ pub struct ProcGenWorld {
 
     /// Vector of all the tiles that will become hallways on the map.
     pub corridors: Vec<Point>,
+
+    /// Open floor that doesn't belong to any room. Used by caves and mazes.
+    pub floor: Vec<Point>,
+
+    /// Width of the generated map.
+    pub width: usize,
+
+    /// Height of the generated map.
+    pub height: usize,
 }
 
 impl ProcGenWorld {
@@ -22,23 +33,75 @@ impl ProcGenWorld {
         bsp: MapBSPTree,
         room_shrinking_seed: u64,
         corridor_seed: u64,
+        config: &GenConfig,
+    ) -> Self {
+        let rooms = bsp.collect_leaves().into_iter().map(ProcGenRoom::from).collect();
+
+        let mut world = Self {
+            rooms,
+            corridors: Vec::new(),
+            floor: Vec::new(),
+            width: bsp.width,
+            height: bsp.height,
+        };
+
+        world.shrink_rooms(room_shrinking_seed, config.shrink_factor_range.clone());
+        world.a_star_corridors(corridor_seed, config.extra_corridor_chance);
+        world.tidy_corridors();
+
+        world
+    }
+
+    /// Same as [ProcGenWorld::generate_from_bsp], but the rooms are barely shrunk, so they fill most of their partition.
+    pub fn generate_open_halls(
+        bsp: MapBSPTree,
+        room_shrinking_seed: u64,
+        corridor_seed: u64,
+        config: &GenConfig,
     ) -> Self {
         let rooms = bsp.collect_leaves().into_iter().map(ProcGenRoom::from).collect();
 
-        let mut world = Self { rooms, corridors: Vec::new() };
+        let mut world = Self {
+            rooms,
+            corridors: Vec::new(),
+            floor: Vec::new(),
+            width: bsp.width,
+            height: bsp.height,
+        };
 
-        world.shrink_rooms(room_shrinking_seed);
-        world.a_star_corridors(corridor_seed);
+        world.shrink_rooms(room_shrinking_seed, config.open_halls_shrink_factor_range.clone());
+        world.a_star_corridors(corridor_seed, config.extra_corridor_chance);
+        world.tidy_corridors();
 
         world
     }
 
-    /// Shrinks all rooms contained in the [ProcGenWorld]
-    pub fn shrink_rooms(&mut self, room_shrinking_seed: u64) {
+    /// Shrinks all rooms contained in the [ProcGenWorld] by a random factor from the given range.
+    pub fn shrink_rooms(&mut self, room_shrinking_seed: u64, factor_range: Range<f32>) {
         let mut rng = StdRng::seed_from_u64(room_shrinking_seed);
 
         for room in &mut self.rooms {
-            room.shrink(&mut rng);
+            room.shrink(factor_range.clone(), &mut rng);
         }
     }
+
+    /// Returns the points around the open [ProcGenWorld::floor] that need to become walls, so the floor is encased.
+    pub fn floor_walls(&self) -> Vec<Point> {
+        let floor: HashSet<Point> = self.floor.iter().copied().collect();
+
+        let mut seen: HashSet<Point> = HashSet::new();
+        let mut walls: Vec<Point> = Vec::new();
+        for point in &self.floor {
+            for y in point.y.saturating_sub(1)..=point.y + 1 {
+                for x in point.x.saturating_sub(1)..=point.x + 1 {
+                    let neighbour = Point::new(x, y);
+                    if !floor.contains(&neighbour) && seen.insert(neighbour) {
+                        walls.push(neighbour);
+                    }
+                }
+            }
+        }
+
+        walls
+    }
 }
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    proc_gen::{proc_gen_level::ProcGenLevel, proc_gen_room::ProcGenRoom},
+    world::{
+        coordinate_system::Point,
+        level_data::{TileData, TileTypeData},
+    },
+};
+
+/// Shallowest level on which lava pools can appear.
+const LAVA_DEPTH: usize = 6;
+
+/// Shallowest level on which chasms can appear.
+const CHASM_DEPTH: usize = 3;
+
+/// Shallowest level on which spike traps can appear.
+const SPIKE_TRAP_DEPTH: usize = 2;
+
+/// Largest number of spike traps scattered over a room.
+const MAX_SPIKE_TRAPS: usize = 4;
+
+/// Terrain features that can be added to a room of a generated level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerrainFeature {
+    /// A pool of deep water.
+    Lake,
+
+    /// A band of deep water running straight through a room.
+    River,
+
+    /// A pool of lava.
+    LavaPool,
+
+    /// A small hole in the floor that leads to the next level.
+    Chasm,
+
+    /// A few spike traps scattered over a room.
+    SpikeTraps,
+}
+
+impl TerrainFeature {
+    /// Randomly picks a feature that may appear at the given depth.
+    pub fn for_depth<R: Rng + ?Sized>(depth: usize, rng: &mut R) -> Self {
+        let weights = [
+            (TerrainFeature::Lake, 45),
+            (TerrainFeature::River, 30),
+            (TerrainFeature::LavaPool, if depth >= LAVA_DEPTH { 15 } else { 0 }),
+            (TerrainFeature::Chasm, if depth >= CHASM_DEPTH { 10 } else { 0 }),
+            (TerrainFeature::SpikeTraps, if depth >= SPIKE_TRAP_DEPTH { 15 } else { 0 }),
+        ];
+
+        weights
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .map(|(feature, _)| *feature)
+            .unwrap_or(TerrainFeature::Lake)
+    }
+}
+
+impl ProcGenLevel {
+    /// Adds a random terrain feature to a random room with a chance of [GenConfig::terrain_chance](crate::proc_gen::gen_config::GenConfig::terrain_chance) percent.
+    ///
+    /// Hazards (lava and chasms) keep a ring of floor along the room's walls and never cover corridors,
+    /// so every part of the level stays reachable without stepping into them. The same goes for spike traps.
+    /// Water is walkable, so rivers can run all the way through a room.
+    /// Vaults are never covered.
+    pub fn add_terrain<R: Rng + ?Sized>(&mut self, depth: usize, rng: &mut R) {
+        if rng.random_range(0..100) >= self.config.terrain_chance {
+            return;
+        }
+
+        let feature = TerrainFeature::for_depth(depth, rng);
+        self.add_terrain_feature(feature, rng);
+    }
+
+    /// Adds the extra lakes and rivers of a flooded level (see [FloorAffixDef::water_features](crate::data::floor_affixes::FloorAffixDef::water_features)).
+    pub fn add_flooding<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let water_features = self.affix_def().map_or(0, |affix| affix.water_features);
+        for _ in 0..water_features {
+            let feature =
+                if rng.random_bool(0.5) { TerrainFeature::Lake } else { TerrainFeature::River };
+            self.add_terrain_feature(feature, rng);
+        }
+    }
+
+    /// Adds the given terrain feature to a random room.
+    fn add_terrain_feature<R: Rng + ?Sized>(&mut self, feature: TerrainFeature, rng: &mut R) {
+        let Some(room) = self.world.rooms.choose(rng).cloned() else {
+            return;
+        };
+
+        let mut blocked: HashSet<Point> = self.vault_footprint();
+        let (points, tile_type) = match feature {
+            TerrainFeature::Lake => (pool(&room, 3, rng), TileTypeData::Water),
+            TerrainFeature::River => (river(&room, rng), TileTypeData::Water),
+            TerrainFeature::LavaPool => {
+                blocked.extend(self.world.corridors.iter().copied());
+                (pool(&room, 2, rng), TileTypeData::Lava)
+            }
+            TerrainFeature::Chasm => {
+                blocked.extend(self.world.corridors.iter().copied());
+                (pool(&room, 1, rng), TileTypeData::Chasm)
+            }
+            TerrainFeature::SpikeTraps => {
+                blocked.extend(self.world.corridors.iter().copied());
+                (scatter(&room, MAX_SPIKE_TRAPS, rng), TileTypeData::SpikeTrap)
+            }
+        };
+
+        self.terrain.extend(
+            points.into_iter().filter(|point| !blocked.contains(point)).map(|point| TileData {
+                x: point.x,
+                y: point.y,
+                tile_type: tile_type.clone(),
+            }),
+        );
+    }
+
+    /// Returns all points covered by terrain features.
+    pub fn terrain_footprint(&self) -> HashSet<Point> {
+        self.terrain.iter().map(|tile| Point::new(tile.x, tile.y)).collect()
+    }
+}
+
+/// Returns the floor of the room without the ring of floor along its walls.
+fn inner_floor(room: &ProcGenRoom) -> Vec<Point> {
+    let floor: HashSet<Point> = room.floor_points().into_iter().collect();
+
+    room.floor_points()
+        .into_iter()
+        .filter(|point| {
+            point.x > 0
+                && point.y > 0
+                && floor.contains(&Point::new(point.x - 1, point.y))
+                && floor.contains(&Point::new(point.x + 1, point.y))
+                && floor.contains(&Point::new(point.x, point.y - 1))
+                && floor.contains(&Point::new(point.x, point.y + 1))
+        })
+        .collect()
+}
+
+/// An elliptic pool on the inner floor of the room. Its width is about twice its height, so it looks round in the terminal.
+fn pool<R: Rng + ?Sized>(room: &ProcGenRoom, max_radius: usize, rng: &mut R) -> Vec<Point> {
+    let inner = inner_floor(room);
+    let Some(center) = inner.choose(rng).copied() else {
+        return Vec::new();
+    };
+    let radius = rng.random_range(1..=max_radius) as isize;
+
+    inner
+        .into_iter()
+        .filter(|point| {
+            let dx = (point.x as isize - center.x as isize) / 2;
+            let dy = point.y as isize - center.y as isize;
+            dx * dx + dy * dy < radius * radius
+        })
+        .collect()
+}
+
+/// Up to `max_count` random points on the inner floor of the room.
+fn scatter<R: Rng + ?Sized>(room: &ProcGenRoom, max_count: usize, rng: &mut R) -> Vec<Point> {
+    let inner = inner_floor(room);
+    let count = rng.random_range(1..=max_count);
+    inner.choose_multiple(rng, count).copied().collect()
+}
+
+/// A straight band of water crossing the whole room, either horizontally or vertically.
+fn river<R: Rng + ?Sized>(room: &ProcGenRoom, rng: &mut R) -> Vec<Point> {
+    let floor = room.floor_points();
+    let Some(start) = floor.choose(rng).copied() else {
+        return Vec::new();
+    };
+    let width = rng.random_range(1..=2);
+
+    if rng.random_bool(0.5) {
+        floor.into_iter().filter(|point| (start.x..start.x + width).contains(&point.x)).collect()
+    } else {
+        floor.into_iter().filter(|point| (start.y..start.y + width).contains(&point.y)).collect()
+    }
+}
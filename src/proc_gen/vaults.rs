@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use rand::{Rng, seq::IndexedRandom, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::vaults::{VaultData, vault_defs},
+    proc_gen::proc_gen_level::ProcGenLevel,
+    world::{
+        coordinate_system::Point,
+        level_data::{ScriptData, SpawnData, TileData, TriggerData},
+    },
+};
+
+/// Orientation of a vault. The layout is mirrored horizontally first and then rotated clockwise in steps of 90°.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultTransform {
+    /// Number of clockwise quarter turns (0 to 3).
+    pub rotation: u8,
+    pub mirrored: bool,
+}
+
+impl VaultTransform {
+    /// Picks a random orientation.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self { rotation: rng.random_range(0..4), mirrored: rng.random_bool(0.5) }
+    }
+
+    /// Dimensions (width, height) of a layout of the given size after the transformation.
+    pub fn dimensions(&self, width: usize, height: usize) -> (usize, usize) {
+        if self.rotation.is_multiple_of(2) { (width, height) } else { (height, width) }
+    }
+
+    /// Moves a point of a layout of the given size to its position after the transformation.
+    pub fn apply(&self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        let (mut x, mut y) = if self.mirrored { (width - 1 - x, y) } else { (x, y) };
+        let (mut width, mut height) = (width, height);
+
+        for _ in 0..self.rotation % 4 {
+            (x, y) = (height - 1 - y, x);
+            (width, height) = (height, width);
+        }
+
+        (x, y)
+    }
+}
+
+/// A vault that has been placed in a level.
+pub struct PlacedVault {
+    /// Tiles of the vault in world coordinates.
+    pub tiles: Vec<TileData>,
+
+    /// Script of the vault, with its coordinates relative to the vault.
+    pub script: Option<ScriptData>,
+
+    /// Triggers of the vault in world coordinates.
+    pub triggers: Vec<TriggerData>,
+}
+
+impl PlacedVault {
+    /// Orients a vault with the given transformation and places its top left corner at `origin`.
+    ///
+    /// # Returns
+    /// The placed vault and the spawns of the vault in world coordinates.
+    pub fn stamp(
+        vault: &VaultData,
+        transform: VaultTransform,
+        origin: Point,
+    ) -> (Self, Vec<SpawnData>) {
+        let (width, height) = (vault.width(), vault.height());
+        let to_world = |x: usize, y: usize| {
+            let (x, y) = transform.apply(x, y, width, height);
+            Point::new(origin.x + x, origin.y + y)
+        };
+
+        let mut tiles = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(tile_type) = vault.tile_at(x, y) {
+                    let point = to_world(x, y);
+                    tiles.push(TileData { x: point.x, y: point.y, tile_type });
+                }
+            }
+        }
+
+        let spawns = vault
+            .spawns
+            .iter()
+            .map(|spawn| {
+                let point = to_world(spawn.x, spawn.y);
+                SpawnData { kind: spawn.kind.clone(), x: point.x, y: point.y }
+            })
+            .collect();
+
+        let script = vault.script.as_ref().map(|path| ScriptData {
+            path: path.clone(),
+            origin,
+            transform,
+            width,
+            height,
+        });
+
+        let triggers = vault.triggers.iter().map(|trigger| trigger.placed(to_world)).collect();
+
+        (Self { tiles, script, triggers }, spawns)
+    }
+
+    /// Returns all points covered by the vault.
+    pub fn footprint(&self) -> Vec<Point> {
+        self.tiles.iter().map(|tile| Point::new(tile.x, tile.y)).collect()
+    }
+}
+
+impl ProcGenLevel {
+    /// Stamps a random vault into a random room with a chance of [GenConfig::vault_chance](crate::proc_gen::gen_config::GenConfig::vault_chance) percent.
+    ///
+    /// The vault needs to fit into the room's floor with one tile of floor around it, so it stays reachable.
+    /// It never covers a corridor, so no connection between rooms is cut off.
+    /// Only vaults that may appear at the given `depth` are considered.
+    pub fn add_vaults<R: Rng + ?Sized>(&mut self, depth: usize, rng: &mut R) {
+        if rng.random_range(0..100) >= self.config.vault_chance {
+            return;
+        }
+
+        let vaults: Vec<&VaultData> =
+            vault_defs().iter().filter(|vault| vault.min_depth <= depth).collect();
+        let Some(vault) = vaults.choose(rng) else {
+            return;
+        };
+        let transform = VaultTransform::random(rng);
+
+        let corridors: HashSet<Point> = self.world.corridors.iter().copied().collect();
+        let mut room_ids: Vec<usize> = (0..self.world.rooms.len()).collect();
+        room_ids.shuffle(rng);
+
+        for room_id in room_ids {
+            // The origins are picked from the ordered points, because the order of HashSets differs between runs
+            let floor_points = self.world.rooms[room_id].floor_points();
+            let floor: HashSet<Point> = floor_points.iter().copied().collect();
+            let origins: Vec<Point> = floor_points
+                .into_iter()
+                .filter(|origin| fits(vault, transform, *origin, &floor, &corridors))
+                .collect();
+
+            if let Some(origin) = origins.choose(rng) {
+                let (placed, mut spawns) = PlacedVault::stamp(vault, transform, *origin);
+                self.vaults.push(placed);
+                self.spawns.append(&mut spawns);
+                return;
+            }
+        }
+    }
+
+    /// Returns all points covered by the vaults of the level.
+    pub fn vault_footprint(&self) -> HashSet<Point> {
+        self.vaults.iter().flat_map(|vault| vault.footprint()).collect()
+    }
+}
+
+/// Checks whether the oriented vault can be placed at `origin`.
+///
+/// The vault and a border of one tile around it have to be on the `floor`, and the vault must not cover any `corridors`.
+fn fits(
+    vault: &VaultData,
+    transform: VaultTransform,
+    origin: Point,
+    floor: &HashSet<Point>,
+    corridors: &HashSet<Point>,
+) -> bool {
+    let (width, height) = transform.dimensions(vault.width(), vault.height());
+    if origin.x == 0 || origin.y == 0 {
+        return false;
+    }
+
+    for y in origin.y - 1..=origin.y + height {
+        for x in origin.x - 1..=origin.x + width {
+            let point = Point::new(x, y);
+            let in_vault = (origin.x..origin.x + width).contains(&x)
+                && (origin.y..origin.y + height).contains(&y);
+
+            if !floor.contains(&point) || (in_vault && corridors.contains(&point)) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::{
+        proc_gen::{
+            gen_config::GenConfig, proc_gen_room::ProcGenRoom, proc_gen_world::ProcGenWorld,
+        },
+        world::{
+            biome::Biome,
+            lighting::RoomLighting,
+            worldspace::{WORLD_HEIGHT, WORLD_WIDTH},
+        },
+    };
+
+    #[test]
+    fn transforms_keep_the_layout_in_bounds() {
+        for rotation in 0..4 {
+            for mirrored in [false, true] {
+                let transform = VaultTransform { rotation, mirrored };
+                let (width, height) = transform.dimensions(5, 3);
+
+                let mut seen = HashSet::new();
+                for y in 0..3 {
+                    for x in 0..5 {
+                        let (tx, ty) = transform.apply(x, y, 5, 3);
+                        assert!(tx < width && ty < height);
+                        assert!(seen.insert((tx, ty)));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(VaultTransform { rotation: 1, mirrored: false }.apply(0, 0, 5, 3), (2, 0));
+        assert_eq!(VaultTransform { rotation: 0, mirrored: true }.apply(0, 0, 5, 3), (4, 0));
+    }
+
+    /// A level with one large room, crossed by a corridor at `y = 10`.
+    fn level_with_one_room() -> (ProcGenLevel, ProcGenRoom) {
+        let room = ProcGenRoom {
+            point_a: Point::new(10, 2),
+            point_b: Point::new(40, 20),
+            lighting: RoomLighting::Normal,
+        };
+        let corridors: Vec<Point> = (0..50).map(|x| Point::new(x, 10)).collect();
+        let world = ProcGenWorld {
+            rooms: vec![room.clone()],
+            corridors,
+            floor: Vec::new(),
+            width: WORLD_WIDTH,
+            height: WORLD_HEIGHT,
+        };
+        let level = ProcGenLevel::generate_from_world(
+            world,
+            Biome::Dungeon,
+            None,
+            10,
+            0,
+            &GenConfig::default(),
+        );
+        (level, room)
+    }
+
+    #[test]
+    fn vaults_avoid_corridors_and_stay_reachable() {
+        let (mut level, room) = level_with_one_room();
+        let mut rng = StdRng::seed_from_u64(73);
+        while level.vaults.is_empty() {
+            level.add_vaults(10, &mut rng);
+        }
+
+        let footprint = level.vault_footprint();
+        let floor = room.floor_points();
+        assert!(footprint.iter().all(|point| floor.contains(point) && point.y != 10));
+        assert!(level.regions()[0].iter().all(|point| !footprint.contains(point)));
+    }
+
+    #[test]
+    fn vaults_are_placed_the_same_for_the_same_seed() {
+        let placement = |seed: u64| {
+            let (mut level, _) = level_with_one_room();
+            let mut rng = StdRng::seed_from_u64(seed);
+            while level.vaults.is_empty() {
+                level.add_vaults(10, &mut rng);
+            }
+            level.vaults[0].footprint()
+        };
+
+        for seed in 0..10 {
+            assert_eq!(placement(seed), placement(seed));
+        }
+    }
+}
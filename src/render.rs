@@ -1,5 +1,3 @@
-pub mod info_display;
-pub mod menu_display;
-pub mod modal_display;
-pub mod ui;
-pub mod world_display;
+//! Presentation data shared by every frontend: The colors and styles of the game, and the themes that recolor them.
+pub mod style;
+pub mod theme;
@@ -0,0 +1,163 @@
+use std::{fmt, str::FromStr};
+
+use bitflags::bitflags;
+
+/// Colors of the game's palette. Frontends map them to their own colors, e.g. the terminal colors of `anthill-tui`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The default color of the frontend.
+    #[default]
+    Reset,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+    DarkGray,
+    LightRed,
+    LightGreen,
+    LightYellow,
+    LightBlue,
+    LightMagenta,
+    LightCyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+/// Writes the name of the color, or the hex code of an RGB color (e.g. `#ff5000`).
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            named => write!(f, "{:?}", named),
+        }
+    }
+}
+
+/// Parses the name of a color (e.g. `"light red"`, `"LightRed"` or `"light_red"`) or a hex code like `"#ff5000"`.
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if let Some(hex) = text.strip_prefix('#') {
+            let channel =
+                |range| hex.get(range).and_then(|channel| u8::from_str_radix(channel, 16).ok());
+            return match (hex.len(), channel(0..2), channel(2..4), channel(4..6)) {
+                (6, Some(r), Some(g), Some(b)) => Ok(Color::Rgb(r, g, b)),
+                _ => Err(format!("Invalid hex color {}", text)),
+            };
+        }
+
+        let name: String = text
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let color = match name.as_str() {
+            "reset" => Color::Reset,
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "gray" | "grey" => Color::Gray,
+            "darkgray" | "darkgrey" => Color::DarkGray,
+            "lightred" => Color::LightRed,
+            "lightgreen" => Color::LightGreen,
+            "lightyellow" => Color::LightYellow,
+            "lightblue" => Color::LightBlue,
+            "lightmagenta" => Color::LightMagenta,
+            "lightcyan" => Color::LightCyan,
+            "white" => Color::White,
+            _ => return Err(format!("Unknown color {}", text)),
+        };
+        Ok(color)
+    }
+}
+
+bitflags! {
+    /// Text modifiers like bold or italic. Frontends that can't show one of them leave it out.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct Modifier: u16 {
+        const BOLD = 1 << 0;
+        const DIM = 1 << 1;
+        const ITALIC = 1 << 2;
+        const UNDERLINED = 1 << 3;
+        const REVERSED = 1 << 4;
+    }
+}
+
+/// How something of the game is drawn: Its colors and modifiers. Unset colors keep the color of what is drawn below.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub add_modifier: Modifier,
+    pub sub_modifier: Modifier,
+}
+
+impl Style {
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            add_modifier: Modifier::empty(),
+            sub_modifier: Modifier::empty(),
+        }
+    }
+
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    pub const fn add_modifier(mut self, modifier: Modifier) -> Self {
+        self.sub_modifier = self.sub_modifier.difference(modifier);
+        self.add_modifier = self.add_modifier.union(modifier);
+        self
+    }
+
+    pub const fn remove_modifier(mut self, modifier: Modifier) -> Self {
+        self.add_modifier = self.add_modifier.difference(modifier);
+        self.sub_modifier = self.sub_modifier.union(modifier);
+        self
+    }
+}
+
+/// A style that only sets the foreground color.
+impl From<Color> for Style {
+    fn from(color: Color) -> Self {
+        Style::new().fg(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_parse_from_names_and_hex_codes() {
+        assert_eq!("light red".parse(), Ok(Color::LightRed));
+        assert_eq!("DarkGray".parse(), Ok(Color::DarkGray));
+        assert_eq!("light_cyan".parse(), Ok(Color::LightCyan));
+        assert_eq!("#ff5000".parse(), Ok(Color::Rgb(255, 80, 0)));
+        assert!("#ff50".parse::<Color>().is_err());
+        assert!("mauve".parse::<Color>().is_err());
+        assert_eq!(Color::Rgb(255, 80, 0).to_string(), "#ff5000");
+        assert_eq!(Color::LightRed.to_string().parse(), Ok(Color::LightRed));
+
+        let style = Style::new().add_modifier(Modifier::BOLD).remove_modifier(Modifier::BOLD);
+        assert!(!style.add_modifier.contains(Modifier::BOLD));
+        assert!(style.sub_modifier.contains(Modifier::BOLD));
+    }
+}
@@ -0,0 +1,98 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use ron::de::from_reader;
+use ron::ser::{PrettyConfig, to_writer_pretty};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{
+    render::style::Color,
+    util::errors_results::{GameError, IoError},
+};
+
+/// Selectable color themes. Picked with the `theme` command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum ThemeId {
+    /// The colors the game was designed with.
+    #[default]
+    Default,
+
+    /// Brighter colors and bold highlights for dim screens and low vision.
+    HighContrast,
+
+    /// Replaces red and green, which are hard to tell apart with red-green color blindness, with the Okabe-Ito palette.
+    Deuteranopia,
+}
+
+impl ThemeId {
+    /// Name of the theme as used by the `theme` command.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeId::Default => "default",
+            ThemeId::HighContrast => "contrast",
+            ThemeId::Deuteranopia => "deuteranopia",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "default" => Some(ThemeId::Default),
+            "contrast" | "highcontrast" => Some(ThemeId::HighContrast),
+            "deuteranopia" | "colorblind" => Some(ThemeId::Deuteranopia),
+            _ => None,
+        }
+    }
+
+    /// Loads the last picked theme from the given file. A missing file means none was picked yet.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path).map_err(IoError::FileReading)?;
+        Ok(from_reader(BufReader::new(file)).map_err(IoError::MapParsing)?)
+    }
+
+    /// Writes the theme to the given file.
+    ///
+    /// # Errors
+    /// * [IoError::FileCreation] if the file or its directory could not be created.
+    /// * [IoError::MapWriting] if the theme could not be written.
+    pub fn save(&self, path: &Path) -> Result<(), GameError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(IoError::FileCreation)?;
+        }
+
+        let file = File::create(path).map_err(IoError::FileCreation)?;
+        to_writer_pretty(BufWriter::new(file), self, PrettyConfig::default())
+            .map_err(IoError::MapWriting)?;
+        Ok(())
+    }
+}
+
+/// Path of the file the last picked theme is saved to.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn theme_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("saves");
+    path.push("theme.ron");
+    Some(path)
+}
+
+/// A theme added by a content pack. It replaces some colors of a built-in theme.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackTheme {
+    pub name: String,
+    pub base: ThemeId,
+    /// Colors of the default theme and the colors they are replaced with.
+    pub colors: Vec<(Color, Color)>,
+}
@@ -1,5 +1,4 @@
-pub mod command_handler;
+pub mod build_info;
 pub mod errors_results;
-pub mod input_handler;
 pub mod rng;
 pub mod text_log;
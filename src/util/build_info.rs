@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the game, as given in `Cargo.toml`.
+pub const GAME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Commit the game was built from. Set by the build script, `unknown` if the game was not built from a git checkout.
+pub const GIT_HASH: &str = env!("ANTHILL_GIT_HASH");
+
+/// Version of the game's content (definitions of items and NPCs, levels and the formats of the files the game writes).
+/// Raise it whenever a change makes older files play out differently, like replays recorded before a balance change.
+pub const CONTENT_SCHEMA_VERSION: u32 = 1;
+
+/// Which build of the game wrote a file. Stored in the stash, replays and morgue files, so files of other versions can be recognized.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_hash: String,
+    pub content_schema: u32,
+}
+
+impl BuildInfo {
+    /// The build that is running.
+    pub fn current() -> Self {
+        Self {
+            version: GAME_VERSION.to_string(),
+            git_hash: GIT_HASH.to_string(),
+            content_schema: CONTENT_SCHEMA_VERSION,
+        }
+    }
+
+    /// Files written before the build was stored in them.
+    pub fn unknown() -> Self {
+        Self { version: "unknown".to_string(), git_hash: "unknown".to_string(), content_schema: 0 }
+    }
+
+    /// One line description, e.g. `v1.0.0-beta (3f2a1bc, content 1)`.
+    pub fn summary(&self) -> String {
+        format!("v{} ({}, content {})", self.version, self.git_hash, self.content_schema)
+    }
+
+    /// Warning shown when a file written by this build is loaded by a different one. Different commits of the same version are not worth a warning.
+    ///
+    /// # Returns
+    /// * `None` if the file was written by the same version with the same content.
+    pub fn mismatch_warning(&self, file_kind: &str) -> Option<String> {
+        let current = BuildInfo::current();
+        if self.version == current.version && self.content_schema == current.content_schema {
+            return None;
+        }
+
+        Some(format!(
+            "The {} was written by Anthill {}, but this is Anthill {}. It may not load or play out as expected.",
+            file_kind,
+            self.summary(),
+            current.summary()
+        ))
+    }
+}
+
+impl Default for BuildInfo {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_other_versions_are_warned_about() {
+        let current = BuildInfo::current();
+        assert_eq!(current.mismatch_warning("replay"), None);
+        assert_eq!(
+            BuildInfo { git_hash: "0000000".into(), ..current.clone() }.mismatch_warning("replay"),
+            None
+        );
+
+        let warning = BuildInfo::unknown().mismatch_warning("replay").unwrap();
+        assert!(warning.contains(&current.summary()));
+        assert!(BuildInfo { content_schema: 0, ..current }.mismatch_warning("stash").is_some());
+    }
+}
@@ -9,7 +9,10 @@ use crate::{
         entity_logic::EntityId,
         game_items::{GameItemId, GameItemKindDef},
     },
-    data::{item_defs::GameItemDefId, npc_defs::NpcDefId},
+    data::{
+        item_defs::GameItemDefId, loot_tables::LootTableId, npc_defs::NpcDefId, perks::PerkId,
+        uniques::UniqueId,
+    },
     util::text_log::LogData,
     world::coordinate_system::Point,
 };
@@ -47,6 +50,13 @@ pub enum FailReason {
     /// (e.g. trying to unequip armor while not wearing armor)
     EquipmentSlotEmpty,
 
+    /// Action cannot be completed because the equipped item is cursed and cannot be taken off.
+    /// (e.g. unequipping a cursed sword or swapping it for another weapon)
+    EquipmentCursed,
+
+    /// Nothing the player has equipped is cursed, so there is no curse to lift.
+    NothingCursed,
+
     /// An interaction was triggered, but the given object has no defined interaction.
     /// This should not happen, since interactions are only triggered on defined objects.
     NoInteraction,
@@ -60,8 +70,50 @@ pub enum FailReason {
     /// Target of the given action is outside the defined range for that action (e.g. ranged attack)
     OutOfRange,
 
+    /// Something blocks the line of sight between the player and the target (e.g. shooting through a wall).
+    NoLineOfSight,
+
     /// The target position is occupied by an NPC or Item.
     TileOccupied(Point),
+
+    /// Action requires a campfire next to the player, but there is none. Used in cooking logic.
+    NoCampfireNearby,
+
+    /// The item cannot be cooked, because there is no cooking recipe for it.
+    NotCookable(GameItemId),
+
+    /// There is no free tile around the player to place something on. (e.g. building a campfire)
+    NoFreeSpace,
+
+    /// The player cannot afford the item at the given price. Used in shops.
+    NotEnoughGold { price: u32 },
+
+    /// The shopkeeper was offended and refuses to trade with the player.
+    ShopkeeperOffended,
+
+    /// The player can only haggle once with each shopkeeper.
+    AlreadyHaggled,
+
+    /// The player has all their hit points, so there is nothing to heal. Used by healers.
+    AlreadyHealthy,
+
+    /// The stash in the town cannot take in any more items.
+    StashFull,
+
+    /// The door is locked until all enemies of the level are defeated.
+    DoorLocked,
+
+    /// The chest or bag cannot take in any more items.
+    ContainerFull,
+
+    /// The bag still holds items, which would be lost.
+    BagNotEmpty,
+
+    /// The player does not stand on stairs leading the way they want to go.
+    NoStairs,
+
+    /// Neither the tile of the player nor the tiles next to them can hold dropped items.
+    NoRoomToDrop,
 }
 
 impl FailReason {
@@ -74,11 +126,27 @@ impl FailReason {
             FailReason::TileNotWalkable(_) => None,
             FailReason::InventoryFull => Some(LogData::InventoryFull),
             FailReason::EquipmentSlotEmpty => Some(LogData::EquipmentSlotEmpty),
+            FailReason::EquipmentCursed => Some(LogData::EquipmentCursed),
+            FailReason::NothingCursed => Some(LogData::NothingCursed),
             FailReason::TileNotVisible(_) => None,
             FailReason::InvalidTarget(_) => None,
             FailReason::NoInteraction => Some(LogData::NoInteraction),
             FailReason::OutOfRange => Some(LogData::OutOfRange),
+            FailReason::NoLineOfSight => Some(LogData::NoLineOfSight),
             FailReason::TileOccupied(_) => Some(LogData::TileOccupied),
+            FailReason::NoCampfireNearby => Some(LogData::NoCampfireNearby),
+            FailReason::NotCookable(_) => None,
+            FailReason::NoFreeSpace => Some(LogData::NoFreeSpace),
+            FailReason::NotEnoughGold { price } => Some(LogData::NotEnoughGold { price: *price }),
+            FailReason::ShopkeeperOffended => Some(LogData::ShopkeeperRefuses),
+            FailReason::AlreadyHaggled => Some(LogData::AlreadyHaggled),
+            FailReason::AlreadyHealthy => Some(LogData::AlreadyHealthy),
+            FailReason::StashFull => Some(LogData::StashFull),
+            FailReason::DoorLocked => Some(LogData::DoorStaysLocked),
+            FailReason::ContainerFull => Some(LogData::ContainerFull),
+            FailReason::BagNotEmpty => Some(LogData::BagNotEmpty),
+            FailReason::NoStairs => Some(LogData::NoStairs),
+            FailReason::NoRoomToDrop => Some(LogData::NoRoomToDrop),
         }
     }
 }
@@ -143,6 +211,9 @@ pub enum EngineError {
     /// An ItemSprite of the given id is not registered in `GameState::item_sprites`, meaning it doesn't exist.
     ItemSpriteNotFound(EntityId),
 
+    /// A Corpse of the given id does not exist in the current level.
+    CorpseNotFound(EntityId),
+
     /// An Item that is being used by the player is not in their inventory.
     ItemNotInInventory(GameItemId),
 
@@ -154,6 +225,9 @@ pub enum EngineError {
 
     /// Tried to access a cursor, but cursor wasn't found.
     CursorNotSet,
+
+    /// The script at the given path failed while running, or ran over its limits. Holds the reason.
+    ScriptFailed(String, String),
 }
 
 impl fmt::Display for EngineError {
@@ -171,6 +245,9 @@ impl fmt::Display for EngineError {
             EngineError::ItemSpriteNotFound(sprite_id) => {
                 write!(f, "Item Sprite of id {} does not exist", sprite_id)
             }
+            EngineError::CorpseNotFound(corpse_id) => {
+                write!(f, "Corpse of id {} does not exist", corpse_id)
+            }
             EngineError::ItemNotInInventory(item_id) => {
                 write!(
                     f,
@@ -191,6 +268,9 @@ impl fmt::Display for EngineError {
             EngineError::CursorNotSet => {
                 write!(f, "Could not find a cursor instance")
             }
+            EngineError::ScriptFailed(path, reason) => {
+                write!(f, "Script {} failed: {}", path, reason)
+            }
         }
     }
 }
@@ -210,12 +290,31 @@ pub enum DataError {
     /// The npc of the given [NpcDefId] does not exist in the game.
     MissingNpcDefinition(NpcDefId),
 
+    /// The unique monster of the given [UniqueId] does not exist in the game.
+    MissingUniqueDefinition(UniqueId),
+
+    /// The perk of the given [PerkId] does not exist in the game.
+    MissingPerkDefinition(PerkId),
+
+    /// The loot table of the given [LootTableId] does not exist in the game.
+    MissingLootTable(LootTableId),
+
+    /// No AI behavior is registered under the given id.
+    MissingAiBehavior(String),
+
     /// Tried to load static world, but no static world defined for id
     StaticWorldNotFound(usize),
 
     /// World needs to fit requirements to be loaded.
-    /// * Cannot be larger than `WORLD_WIDTH`x`WORLD_HEIGHT`
+    /// * Needs a width and height of at least 1
+    /// * Tiles, rooms and corridors must lie inside the width and height
     InvalidWorldFormat(usize),
+
+    /// The script at the given path is not valid. Holds the reason.
+    InvalidScript(String, String),
+
+    /// The definition of the given id in a content pack is not valid (see [mods](crate::data::mods)). Holds the reason.
+    InvalidModDefinition(String, String),
 }
 
 impl fmt::Display for DataError {
@@ -227,12 +326,30 @@ impl fmt::Display for DataError {
             DataError::MissingNpcDefinition(npc_def_id) => {
                 write!(f, "Npc of def_id {} not defined", npc_def_id)
             }
+            DataError::MissingUniqueDefinition(unique_id) => {
+                write!(f, "Unique monster {} not defined", unique_id)
+            }
+            DataError::MissingPerkDefinition(perk_id) => {
+                write!(f, "Perk {} not defined", perk_id)
+            }
+            DataError::MissingLootTable(loot_table_id) => {
+                write!(f, "Loot table {} not defined", loot_table_id)
+            }
+            DataError::MissingAiBehavior(behavior_id) => {
+                write!(f, "AI behavior {} not registered", behavior_id)
+            }
             DataError::StaticWorldNotFound(static_world_id) => {
                 write!(f, "No static world definied for id {}", static_world_id)
             }
             DataError::InvalidWorldFormat(static_world_id) => {
                 write!(f, "WorldData for {} does not fit requirements", static_world_id)
             }
+            DataError::InvalidScript(path, reason) => {
+                write!(f, "Script {} is invalid: {}", path, reason)
+            }
+            DataError::InvalidModDefinition(def_id, reason) => {
+                write!(f, "Definition of {} is invalid: {}", def_id, reason)
+            }
         }
     }
 }
@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
-use std::{fmt, ops::Range};
+use std::{fmt, ops::RangeInclusive, str::FromStr};
 
 use rand::Rng;
+use serde::{Deserialize, Deserializer, de};
 
 use crate::{core::game::GameState, world::coordinate_system::Direction};
 
@@ -23,8 +24,22 @@ impl DieSize {
     fn upper_bound(self) -> u8 {
         self as u8
     }
-    fn range(self) -> Range<u8> {
-        1..(self.upper_bound())
+    fn range(self) -> RangeInclusive<u8> {
+        1..=self.upper_bound()
+    }
+
+    /// The die with the given number of sides, if there is one.
+    pub fn from_sides(sides: u8) -> Option<Self> {
+        match sides {
+            4 => Some(DieSize::D4),
+            6 => Some(DieSize::D6),
+            8 => Some(DieSize::D8),
+            10 => Some(DieSize::D10),
+            12 => Some(DieSize::D12),
+            20 => Some(DieSize::D20),
+            100 => Some(DieSize::D100),
+            _ => None,
+        }
     }
 }
 
@@ -42,12 +57,29 @@ impl fmt::Display for DieSize {
     }
 }
 
+/// Maximum number of times a single exploding die is rolled again (see [Roll::exploding]).
+pub const MAX_EXPLOSIONS: u8 = 10;
+
+/// Whether a [Roll] is rolled twice and which of the results counts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RollMode {
+    /// The roll is rolled once.
+    #[default]
+    Normal,
+    /// The roll is rolled twice and the better result counts.
+    Advantage,
+    /// The roll is rolled twice and the worse result counts.
+    Disadvantage,
+}
+
 /// RNG Dice Rolls
 ///
 /// The roll is resolved by calling [`Roll::roll`] and injecting a mutale reference to an RNG.
+/// Optional mechanics (advantage, exploding dice and clamps) are added with the builder methods.
 ///
 /// # Example
 /// ```
+/// use anthill_core::util::rng::{DieSize, Roll};
 /// use rand::{SeedableRng, rngs::StdRng};
 ///
 /// let mut rng = StdRng::seed_from_u64(73);
@@ -68,11 +100,27 @@ pub struct Roll {
     dice_size: DieSize,
     /// Modifier to be applied to the result.
     modifier: i16,
+    /// Whether the roll is rolled twice.
+    mode: RollMode,
+    /// Whether dice showing their highest result are rolled again and added.
+    exploding: bool,
+    /// Lowest result the roll can have, after the modifier.
+    min: Option<i16>,
+    /// Highest result the roll can have, after the modifier.
+    max: Option<i16>,
 }
 
 impl Roll {
-    pub fn new(dice_amount: u8, dice_size: DieSize) -> Self {
-        Self { dice_amount, modifier: i16::default(), dice_size }
+    pub const fn new(dice_amount: u8, dice_size: DieSize) -> Self {
+        Self {
+            dice_amount,
+            modifier: 0,
+            dice_size,
+            mode: RollMode::Normal,
+            exploding: false,
+            min: None,
+            max: None,
+        }
     }
 
     pub fn add_modifier(mut self, modifier: i16) -> Self {
@@ -80,12 +128,72 @@ impl Roll {
         self
     }
 
+    /// Rolls twice and takes the better result. Cancels out a disadvantage.
+    pub fn with_advantage(mut self) -> Self {
+        self.mode = match self.mode {
+            RollMode::Disadvantage => RollMode::Normal,
+            _ => RollMode::Advantage,
+        };
+        self
+    }
+
+    /// Rolls twice and takes the worse result. Cancels out an advantage.
+    pub fn with_disadvantage(mut self) -> Self {
+        self.mode = match self.mode {
+            RollMode::Advantage => RollMode::Normal,
+            _ => RollMode::Disadvantage,
+        };
+        self
+    }
+
+    /// Dice that show their highest result are rolled again and added, up to [MAX_EXPLOSIONS] times per die.
+    pub fn exploding(mut self) -> Self {
+        self.exploding = true;
+        self
+    }
+
+    /// The result is at least the given value. A higher existing minimum is kept.
+    pub fn at_least(mut self, min: i16) -> Self {
+        self.min = Some(self.min.map_or(min, |existing| existing.max(min)));
+        self
+    }
+
+    /// The result is at most the given value. A lower existing maximum is kept.
+    pub fn at_most(mut self, max: i16) -> Self {
+        self.max = Some(self.max.map_or(max, |existing| existing.min(max)));
+        self
+    }
+
     pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> i16 {
+        self.roll_natural(rng).1
+    }
+
+    /// Rolls and returns what the dice showed on their own (the natural result), along with the result.
+    fn roll_natural<R: Rng + ?Sized>(&self, rng: &mut R) -> (i16, i16) {
+        let natural = match self.mode {
+            RollMode::Normal => self.roll_dice(rng),
+            RollMode::Advantage => self.roll_dice(rng).max(self.roll_dice(rng)),
+            RollMode::Disadvantage => self.roll_dice(rng).min(self.roll_dice(rng)),
+        };
+
+        let result = natural.saturating_add(self.modifier);
+        let result = self.min.map_or(result, |min| result.max(min));
+        (natural, self.max.map_or(result, |max| result.min(max)))
+    }
+
+    /// Rolls the dice once, without the modifier.
+    fn roll_dice<R: Rng + ?Sized>(&self, rng: &mut R) -> i16 {
         let mut rolled_numbers: i16 = 0;
         for _ in 0..self.dice_amount {
-            rolled_numbers += rng.random_range(self.dice_size.range()) as i16;
+            for _ in 0..=MAX_EXPLOSIONS {
+                let rolled = rng.random_range(self.dice_size.range());
+                rolled_numbers = rolled_numbers.saturating_add(rolled as i16);
+                if !self.exploding || rolled < self.dice_size.upper_bound() {
+                    break;
+                }
+            }
         }
-        rolled_numbers.saturating_add(self.modifier)
+        rolled_numbers
     }
 }
 
@@ -101,12 +209,136 @@ impl fmt::Display for Roll {
     }
 }
 
+/// Reason a dice string could not be parsed into a [Roll].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RollParseError {
+    /// The string has no `d` separating the number of dice from the die size.
+    MissingDie(String),
+    /// The number of dice is not a number from 0 to 255.
+    InvalidDiceAmount(String),
+    /// The die size is not one of the [DieSize]s.
+    InvalidDieSize(String),
+    /// The modifier after the `+` or `-` is not a number.
+    InvalidModifier(String),
+    /// A number of the string has whitespace inside of it, like `1 0d6`. Whitespace around `d`, `+` and `-` is fine.
+    UnexpectedWhitespace(String),
+}
+
+impl fmt::Display for RollParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RollParseError::MissingDie(text) => {
+                write!(f, "\"{}\" is not a dice roll like \"2d6+1\"", text)
+            }
+            RollParseError::InvalidDiceAmount(amount) => {
+                write!(f, "\"{}\" is not a valid number of dice", amount)
+            }
+            RollParseError::InvalidDieSize(size) => {
+                write!(f, "There is no d{} die (use d4, d6, d8, d10, d12, d20 or d100)", size)
+            }
+            RollParseError::InvalidModifier(modifier) => {
+                write!(f, "\"{}\" is not a valid modifier", modifier)
+            }
+            RollParseError::UnexpectedWhitespace(number) => {
+                write!(f, "\"{}\" has whitespace inside of a number", number)
+            }
+        }
+    }
+}
+
+/// Parses dice strings in the `NdS+M` notation, like `"2d6+1"`, `"1d8-2"` or `"d20"`. The number of dice defaults to one,
+/// the modifier to zero. Whitespace around the `d` and the sign is ignored, so `"2d6 + 1"` parses as well.
+impl FromStr for Roll {
+    type Err = RollParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim().to_lowercase();
+        let (amount, rest) =
+            text.split_once('d').ok_or_else(|| RollParseError::MissingDie(text.clone()))?;
+
+        // Whitespace is only allowed around the `d` and the sign of the modifier
+        let number = |part: &str| {
+            let part = part.trim();
+            match part.contains(char::is_whitespace) {
+                true => Err(RollParseError::UnexpectedWhitespace(part.to_string())),
+                false => Ok(part.to_string()),
+            }
+        };
+
+        let amount = number(amount)?;
+        let dice_amount = if amount.is_empty() {
+            1
+        } else {
+            amount.parse().map_err(|_| RollParseError::InvalidDiceAmount(amount.clone()))?
+        };
+
+        let (size, modifier) = match rest.find(['+', '-']) {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+        let size = number(size)?;
+        let dice_size = size
+            .parse()
+            .ok()
+            .and_then(DieSize::from_sides)
+            .ok_or_else(|| RollParseError::InvalidDieSize(size.clone()))?;
+
+        let modifier = match modifier.split_at_checked(1) {
+            None => 0,
+            Some((sign, digits)) => {
+                let digits = number(digits)?;
+                // Only digits may follow the sign, so `1d6+-2` is not read as `1d6-2`
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(RollParseError::InvalidModifier(modifier.trim().to_string()));
+                }
+                let value: i16 = digits
+                    .parse()
+                    .map_err(|_| RollParseError::InvalidModifier(modifier.trim().to_string()))?;
+                if sign == "-" { -value } else { value }
+            }
+        };
+
+        Ok(Roll::new(dice_amount, dice_size).add_modifier(modifier))
+    }
+}
+
+/// Rolls are written as dice strings in data files (see [Roll::from_str]).
+impl<'de> Deserialize<'de> for Roll {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Margin by which a [Check] has to beat or miss its difficulty to be a critical success or failure.
+pub const CRITICAL_MARGIN: i16 = 10;
+
+/// Graded result of a [Check].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// All dice showed their highest side (a natural 20 on a d20), or the difficulty was beaten by [CRITICAL_MARGIN] or more.
+    CriticalSuccess,
+    /// The result met the difficulty.
+    Success,
+    /// The result fell short of the difficulty.
+    Failure,
+    /// All dice showed a 1, or the difficulty was missed by [CRITICAL_MARGIN] or more.
+    CriticalFailure,
+}
+
+impl CheckOutcome {
+    pub fn is_success(self) -> bool {
+        matches!(self, CheckOutcome::CriticalSuccess | CheckOutcome::Success)
+    }
+}
+
 /// RNG Resolution using [`Roll`]s.
 ///
-/// A `Check` succeeds if the resoled roll result is >= the configured difficulty
+/// A `Check` succeeds if the resoled roll result is >= the configured difficulty. How well it succeeded or failed is graded as a [CheckOutcome].
 ///
 /// # Example
 /// ```
+/// use anthill_core::util::rng::Check;
 /// use rand::{SeedableRng, rngs::StdRng};
 ///
 /// let mut rng = StdRng::seed_from_u64(73);
@@ -119,7 +351,8 @@ impl fmt::Display for Roll {
 ///     .add_modifier(strength)
 ///     .add_modifier(penalty)
 ///     .set_difficulty(difficulty)
-///     .resolve(&mut rng);
+///     .resolve(&mut rng)
+///     .is_success();
 /// ```
 pub struct Check {
     roll: Roll,
@@ -132,8 +365,20 @@ impl Check {
         Self { roll, difficulty: i16::default() }
     }
 
-    pub fn add_modifier(self, modifier: i16) -> Self {
-        self.roll.add_modifier(modifier);
+    pub fn add_modifier(mut self, modifier: i16) -> Self {
+        self.roll = self.roll.add_modifier(modifier);
+        self
+    }
+
+    /// See [Roll::with_advantage].
+    pub fn with_advantage(mut self) -> Self {
+        self.roll = self.roll.with_advantage();
+        self
+    }
+
+    /// See [Roll::with_disadvantage].
+    pub fn with_disadvantage(mut self) -> Self {
+        self.roll = self.roll.with_disadvantage();
         self
     }
 
@@ -142,9 +387,25 @@ impl Check {
         self
     }
 
-    pub fn resolve<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
-        let rolled_num = self.roll.roll(rng);
-        rolled_num >= self.difficulty
+    /// Rolls against the difficulty. Natural rolls with all dice on their highest or lowest side are critical, no matter the modifiers.
+    pub fn resolve<R: Rng + ?Sized>(&self, rng: &mut R) -> CheckOutcome {
+        let (natural, result) = self.roll.roll_natural(rng);
+        let dice_amount = self.roll.dice_amount as i16;
+        let margin = result.saturating_sub(self.difficulty);
+
+        if dice_amount > 0 && natural >= dice_amount * self.roll.dice_size.upper_bound() as i16 {
+            CheckOutcome::CriticalSuccess
+        } else if dice_amount > 0 && natural <= dice_amount {
+            CheckOutcome::CriticalFailure
+        } else if margin >= CRITICAL_MARGIN {
+            CheckOutcome::CriticalSuccess
+        } else if margin >= 0 {
+            CheckOutcome::Success
+        } else if margin <= -CRITICAL_MARGIN {
+            CheckOutcome::CriticalFailure
+        } else {
+            CheckOutcome::Failure
+        }
     }
 }
 
@@ -152,10 +413,7 @@ impl Default for Check {
     /// Creates a standard d20 roll.
     /// This is meant for common checks and attacks.
     fn default() -> Self {
-        Self {
-            roll: Roll { dice_amount: 1, dice_size: DieSize::D20, modifier: i16::default() },
-            difficulty: i16::default(),
-        }
+        Self { roll: Roll::new(1, DieSize::D20), difficulty: i16::default() }
     }
 }
 
@@ -164,13 +422,18 @@ impl GameState {
     ///
     /// # Example
     /// ```
+    /// use anthill_core::{
+    ///     core::game::GameState,
+    ///     util::rng::{DieSize, Roll},
+    /// };
+    ///
     /// let mut game = GameState::new();
     ///
     /// let strength = 5;
     /// let penalty = -2;
     ///
     /// let result = game.roll(
-    ///     Roll::new(1, DieSize::D6)
+    ///     &Roll::new(1, DieSize::D6)
     ///         .add_modifier(strength)
     ///         .add_modifier(penalty),
     /// );
@@ -183,15 +446,22 @@ impl GameState {
     ///
     /// Usage:
     /// ```
-    /// let game = GameState::new();
+    /// use anthill_core::{
+    ///     core::game::GameState,
+    ///     util::rng::{Check, CheckOutcome},
+    /// };
+    ///
+    /// let mut game = GameState::new();
     ///
     /// let strength = 5;
     /// let penalty = -2;
     /// let difficulty = 15;
     ///
-    /// let result: bool = game.check(Check::default().add_modifier(strength).add_modifier(penalty).set_difficulty(difficulty));
+    /// let result: CheckOutcome = game.check(
+    ///     &Check::default().add_modifier(strength).add_modifier(penalty).set_difficulty(difficulty),
+    /// );
     /// ```
-    pub fn check(&mut self, check: &Check) -> bool {
+    pub fn check(&mut self, check: &Check) -> CheckOutcome {
         check.resolve(&mut self.rng)
     }
 }
@@ -224,15 +494,13 @@ mod tests {
 
     #[test]
     fn modified_roll_modifies_result() {
-        let mut rng = StdRng::seed_from_u64(73);
-
         let base_roll = Roll::new(1, DieSize::D6);
         let modified_roll_positive = base_roll.add_modifier(10);
         let modified_roll_negative = base_roll.add_modifier(-10);
 
-        let base = base_roll.roll(&mut rng);
-        let modified_positive = modified_roll_positive.roll(&mut rng);
-        let modified_negative = modified_roll_negative.roll(&mut rng);
+        let base = base_roll.roll(&mut StdRng::seed_from_u64(73));
+        let modified_positive = modified_roll_positive.roll(&mut StdRng::seed_from_u64(73));
+        let modified_negative = modified_roll_negative.roll(&mut StdRng::seed_from_u64(73));
 
         assert_eq!(modified_positive, base + 10);
         assert_eq!(modified_negative, base - 10);
@@ -263,8 +531,94 @@ mod tests {
         let mut rng2 = StdRng::seed_from_u64(73);
         let mut rng3 = StdRng::seed_from_u64(73);
 
-        assert!(check_success.resolve(&mut rng2));
-        assert!(!check_failure.resolve(&mut rng3));
+        assert!(check_success.resolve(&mut rng2).is_success());
+        assert!(!check_failure.resolve(&mut rng3).is_success());
+    }
+
+    #[test]
+    fn checks_are_graded_by_natural_rolls_and_margin() {
+        let mut rng = StdRng::seed_from_u64(73);
+        let flat = |difficulty: i16| {
+            Check::new(Roll::new(0, DieSize::D20).add_modifier(5)).set_difficulty(difficulty)
+        };
+
+        assert_eq!(flat(-5).resolve(&mut rng), CheckOutcome::CriticalSuccess);
+        assert_eq!(flat(5).resolve(&mut rng), CheckOutcome::Success);
+        assert_eq!(flat(6).resolve(&mut rng), CheckOutcome::Failure);
+        assert_eq!(flat(15).resolve(&mut rng), CheckOutcome::CriticalFailure);
+
+        // A natural 20 succeeds critically, however hopeless the check is
+        let hopeless = Check::new(Roll::new(1, DieSize::D20).add_modifier(-100));
+        let mut natural_20 = false;
+        for seed in 0..200 {
+            let natural = Roll::new(1, DieSize::D20).roll(&mut StdRng::seed_from_u64(seed));
+            let outcome = hopeless.resolve(&mut StdRng::seed_from_u64(seed));
+            if natural == 20 {
+                natural_20 = true;
+                assert_eq!(outcome, CheckOutcome::CriticalSuccess);
+            } else {
+                assert_eq!(outcome, CheckOutcome::CriticalFailure);
+            }
+        }
+        assert!(natural_20);
+    }
+
+    #[test]
+    fn rolls_parse_from_dice_strings() {
+        for text in ["2d6+1", "1d8-2", "3d4", "1d100"] {
+            assert_eq!(text.parse::<Roll>().unwrap().to_string(), text);
+        }
+        assert_eq!(" D20 ".parse::<Roll>().unwrap().to_string(), "1d20");
+        assert_eq!("2 d 6 + 1".parse::<Roll>().unwrap().to_string(), "2d6+1");
+        assert_eq!("1d8 - 2".parse::<Roll>().unwrap().to_string(), "1d8-2");
+
+        #[derive(Deserialize)]
+        struct Weapon {
+            damage: Roll,
+        }
+        let weapon: Weapon = ron::from_str(r#"(damage: "2d6+1")"#).unwrap();
+        assert_eq!(weapon.damage.to_string(), "2d6+1");
+        assert!(ron::from_str::<Weapon>(r#"(damage: "2d7")"#).is_err());
+    }
+
+    #[test]
+    fn invalid_dice_strings_are_reported() {
+        let error = |text: &str| text.parse::<Roll>().unwrap_err();
+
+        assert_eq!(error("6"), RollParseError::MissingDie("6".to_string()));
+        assert_eq!(error("xd6"), RollParseError::InvalidDiceAmount("x".to_string()));
+        assert_eq!(error("1d7"), RollParseError::InvalidDieSize("7".to_string()));
+        assert_eq!(error("1d6+"), RollParseError::InvalidModifier("+".to_string()));
+        assert_eq!(error("1d6*2"), RollParseError::InvalidDieSize("6*2".to_string()));
+        assert_eq!(error("1d6+-2"), RollParseError::InvalidModifier("+-2".to_string()));
+        assert_eq!(error("1d6--2"), RollParseError::InvalidModifier("--2".to_string()));
+        assert_eq!(error("1 0d6"), RollParseError::UnexpectedWhitespace("1 0".to_string()));
+        assert_eq!(error("1d1 0"), RollParseError::UnexpectedWhitespace("1 0".to_string()));
+        assert_eq!(error("1d6 + 1 0"), RollParseError::UnexpectedWhitespace("1 0".to_string()));
+    }
+
+    #[test]
+    fn advantage_exploding_dice_and_clamps() {
+        let roll = Roll::new(1, DieSize::D20);
+        for seed in 0..50 {
+            let normal = roll.roll(&mut StdRng::seed_from_u64(seed));
+            let advantage = roll.with_advantage().roll(&mut StdRng::seed_from_u64(seed));
+            let disadvantage = roll.with_disadvantage().roll(&mut StdRng::seed_from_u64(seed));
+            assert!(disadvantage <= normal && normal <= advantage);
+
+            // Advantage and disadvantage cancel out
+            let neutral = roll.with_advantage().with_disadvantage();
+            assert_eq!(neutral.roll(&mut StdRng::seed_from_u64(seed)), normal);
+        }
+
+        let mut rng = StdRng::seed_from_u64(73);
+        let exploding = Roll::new(1, DieSize::D4).exploding();
+        let results: Vec<i16> = (0..200).map(|_| exploding.roll(&mut rng)).collect();
+        assert!(results.iter().any(|result| *result > 4));
+        assert!(results.iter().all(|result| *result != 4 && *result != 8));
+
+        let clamped = Roll::new(1, DieSize::D20).at_least(8).at_most(12);
+        assert!((0..200).all(|_| (8..=12).contains(&clamped.roll(&mut rng))));
     }
 
     #[test]
@@ -274,11 +628,11 @@ mod tests {
         let roll1 = Roll::new(1, DieSize::D20).add_modifier(40);
         let check1 = Check::new(roll1).set_difficulty(30);
 
-        assert!(check1.resolve(&mut rng));
+        assert!(check1.resolve(&mut rng).is_success());
 
         let roll2 = Roll::new(1, DieSize::D20).add_modifier(-20);
         let check2 = Check::new(roll2).set_difficulty(1);
 
-        assert!(!check2.resolve(&mut rng));
+        assert!(!check2.resolve(&mut rng).is_success());
     }
 }
@@ -1,55 +1,125 @@
 use std::{
+    borrow::Cow,
     fmt,
     fs::{self, File},
-    io::{BufWriter, Write},
-    path::PathBuf,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
 };
 
-use ratatui::{
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
+use chrono::{DateTime, Local};
+
+use ron::de::from_reader;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    core::{
+        buff_effects::PotionType, crowd_control::CrowdControl, damage_types::DamageType,
+        resistances::Ailment, resting::RestEnd, travel::TravelEnd,
+    },
+    render::style::Modifier,
+    util::errors_results::{GameError, IoError},
 };
 
 /// The game's text log. The events of the game are desribed for the user in the log.
 /// This is not a typical console log, but part of the game that describes what's happening.
 pub struct Log {
-    pub messages: Vec<LogData>,
+    pub messages: Vec<LogEntry>,
     file: Option<BufWriter<File>>,
+    /// JSON-lines copy of the log for external tools, only written if [LogFormat::JsonLines] is configured.
+    json_file: Option<BufWriter<File>>,
+    /// Round of the game that new messages are logged in.
+    round: u64,
+    /// Categories the player silenced with the `logfilter` command.
+    hidden: Vec<LogCategory>,
 }
 
 impl Log {
     pub fn new() -> Self {
-        let path = create_log_file();
+        let path = create_log_file("txt");
 
         let file = File::create(path).ok();
         let writer = file.map(BufWriter::new);
 
-        Self { messages: Vec::new(), file: writer }
+        Self { messages: Vec::new(), file: writer, json_file: None, round: 0, hidden: Vec::new() }
+    }
+
+    /// Applies the configured format of the log file. The text log is always written, JSON lines are written next to it.
+    pub fn set_format(&mut self, format: LogFormat) {
+        self.json_file = match format {
+            LogFormat::Text => None,
+            LogFormat::JsonLines => File::create(create_log_file("jsonl")).ok().map(BufWriter::new),
+        };
     }
 
-    /// Specific getter that returns all messages, but filetered by debug messages.
-    #[cfg(feature = "dev")]
-    pub fn get_messages_for_display(&self) -> Vec<&LogData> {
-        self.messages.iter().collect()
+    /// Sets the round that the following messages are logged in.
+    pub fn set_round(&mut self, round: u64) {
+        self.round = round;
     }
 
-    /// Specific getter that returns all messages, but filetered by debug messages.
-    #[cfg(not(feature = "dev"))]
-    pub fn get_messages_for_display(&self) -> Vec<&LogData> {
+    /// Specific getter that returns the messages of all categories that are shown (see [Log::is_shown]).
+    pub fn get_messages_for_display(&self) -> Vec<&LogEntry> {
         self.messages
             .iter()
-            .filter(|&message| !matches!(message, LogData::DebugInfo(_) | LogData::DebugWarn(_)))
+            .filter(|&entry| entry.data.category().is_none_or(|category| self.is_shown(category)))
             .collect()
     }
 
+    /// Whether messages of the category are shown in the log. Debug messages are only ever shown in dev builds.
+    pub fn is_shown(&self, category: LogCategory) -> bool {
+        if category == LogCategory::Debug && !cfg!(feature = "dev") {
+            return false;
+        }
+        !self.hidden.contains(&category)
+    }
+
+    /// Shows or hides the messages of a category. Hidden messages are still written to the log file.
+    pub fn set_shown(&mut self, category: LogCategory, shown: bool) {
+        self.hidden.retain(|&hidden| hidden != category);
+        if !shown {
+            self.hidden.push(category);
+        }
+    }
+
+    /// Hides the same categories as the other log, so the filters of a run carry over into the next one.
+    pub fn copy_filters_from(&mut self, other: &Log) {
+        self.hidden = other.hidden.clone();
+    }
+
     /// Add information about a new log event to the log.
     ///
-    /// This is to be used as the primary way of logging.
+    /// This is to be used as the primary way of logging. A message that repeats the last one only counts up the last entry.
+    /// Entries are written to the log file once they stop repeating, so the file shows the count as well.
     pub fn info(&mut self, log_data: LogData) {
-        self.messages.push(log_data.clone());
+        if let Some(last) = self.messages.last_mut()
+            && last.data == log_data
+        {
+            last.count += 1;
+            return;
+        }
+
+        self.write_last_to_file();
+        self.messages.push(LogEntry {
+            data: log_data,
+            count: 1,
+            round: self.round,
+            logged_at: Local::now(),
+        });
+    }
 
+    /// Writes the last entry into the log file, and into the JSON-lines file if there is one.
+    fn write_last_to_file(&mut self) {
+        let Some(last) = self.messages.last() else {
+            return;
+        };
         if let Some(file) = &mut self.file {
-            let _ = writeln!(file, "{}", log_data);
+            let _ = writeln!(file, "{}", last);
+        }
+        if let Some(file) = &mut self.json_file
+            && let Ok(line) = last.json_line()
+        {
+            let _ = writeln!(file, "{}", line);
         }
     }
 
@@ -96,21 +166,248 @@ impl Log {
     }
 }
 
-/// Creates a log file in the OS's local data directory (./local/share on Linux)
+impl Default for Log {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Log {
+    fn drop(&mut self) {
+        self.write_last_to_file();
+    }
+}
+
+/// What a part of a log message stands for. Frontends pick how each of them looks, e.g. from the colors of a theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogTag {
+    Plain,
+    /// Mentions of the player.
+    You,
+    Npc,
+    Item,
+    /// Damage, gold and other numbers.
+    Number,
+    /// Critical hits, burns and other dangers.
+    Danger,
+    /// Lore, inscriptions and what NPCs say.
+    Lore,
+    /// Damage that was blocked by armor.
+    Blocked,
+    DebugInfo,
+    DebugWarn,
+}
+
+/// A part of a log message. The modifier is added to the look the frontend picked for the tag.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogSpan<'a> {
+    pub text: Cow<'a, str>,
+    pub tag: LogTag,
+    pub modifier: Modifier,
+}
+
+impl<'a> LogSpan<'a> {
+    /// Text without any tag.
+    pub fn raw(text: impl Into<Cow<'a, str>>) -> Self {
+        Self::tagged(text, LogTag::Plain)
+    }
+
+    pub fn tagged(text: impl Into<Cow<'a, str>>, tag: LogTag) -> Self {
+        Self { text: text.into(), tag, modifier: Modifier::empty() }
+    }
+
+    pub fn add_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifier |= modifier;
+        self
+    }
+}
+
+/// A message of the log as it is shown, made up of tagged parts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LogLine<'a> {
+    pub spans: Vec<LogSpan<'a>>,
+}
+
+impl<'a> LogLine<'a> {
+    /// A line that is tagged as a whole.
+    pub fn tagged(text: impl Into<Cow<'a, str>>, tag: LogTag) -> Self {
+        Self { spans: vec![LogSpan::tagged(text, tag)] }
+    }
+
+    pub fn push_span(&mut self, span: LogSpan<'a>) {
+        self.spans.push(span);
+    }
+}
+
+impl<'a> From<Vec<LogSpan<'a>>> for LogLine<'a> {
+    fn from(spans: Vec<LogSpan<'a>>) -> Self {
+        Self { spans }
+    }
+}
+
+impl<'a> From<&'a str> for LogLine<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::tagged(text, LogTag::Plain)
+    }
+}
+
+impl From<String> for LogLine<'_> {
+    fn from(text: String) -> Self {
+        Self::tagged(text, LogTag::Plain)
+    }
+}
+
+/// Writes the text of the line without its tags.
+impl fmt::Display for LogLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.spans.iter().try_for_each(|span| write!(f, "{}", span.text))
+    }
+}
+
+/// A message of the log and how many times in a row it happened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    pub data: LogData,
+    pub count: u32,
+    /// Round of the game the message was first logged in.
+    pub round: u64,
+    pub logged_at: DateTime<Local>,
+}
+
+impl LogEntry {
+    /// The message as shown in the log, followed by the number of repeats (e.g. `(x5)`).
+    pub fn line(&self) -> LogLine<'_> {
+        let mut line = self.data.line();
+        if self.count > 1 {
+            line.push_span(LogSpan::tagged(format!(" (x{})", self.count), LogTag::Number));
+        }
+        line
+    }
+
+    /// The entry as one line of JSON, e.g. `{"turn":12,"time":"...","category":"combat","count":1,"data":{"NpcDied":{...}}}`.
+    pub fn json_line(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&JsonRecord {
+            turn: self.round,
+            time: self.logged_at.to_rfc3339(),
+            category: self.data.category().map(|category| category.name()),
+            count: self.count,
+            data: &self.data,
+        })
+    }
+}
+
+/// Layout of a line of the JSON-lines log file.
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    turn: u64,
+    time: String,
+    category: Option<&'static str>,
+    count: u32,
+    data: &'a LogData,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.data)?;
+        if self.count > 1 {
+            write!(f, " (x{})", self.count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a log file with the given extension in the OS's local data directory (./local/share on Linux)
 /// The filename is timestamped
-fn create_log_file() -> PathBuf {
+fn create_log_file(extension: &str) -> PathBuf {
     let mut path = dirs::data_local_dir().expect("No data directory found on this OS");
     path.push("Anthill");
     path.push("logs");
     fs::create_dir_all(&path).expect("Could not create data directory in OS.");
 
-    let filename = format!("anthill_log_{}.txt", chrono::Local::now().format("%Y-%m-%d-%H-%M-%S"));
+    let filename =
+        format!("anthill_log_{}.{}", Local::now().format("%Y-%m-%d-%H-%M-%S"), extension);
     path.push(filename);
 
     path
 }
 
-#[derive(Clone)]
+/// Format of the log file, set in the log config (see [log_config_path]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogFormat {
+    /// Only the messages as they are shown in the game.
+    #[default]
+    Text,
+    /// An additional `.jsonl` file with one JSON object per message, for tools that analyze runs.
+    JsonLines,
+}
+
+/// Settings of the log file, read from the player's log config.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    pub format: LogFormat,
+}
+
+impl LogConfig {
+    /// Loads the config from the given file. A missing file means the defaults are used.
+    ///
+    /// # Errors
+    /// * [IoError::FileReading] if the file exists, but could not be read.
+    /// * [IoError::MapParsing] if the file is corrupted.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path).map_err(IoError::FileReading)?;
+        Ok(from_reader(BufReader::new(file)).map_err(IoError::MapParsing)?)
+    }
+}
+
+/// Path of the file the log is configured with.
+///
+/// # Returns
+/// * `None` if the OS has no data directory.
+pub fn log_config_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("Anthill");
+    path.push("log_config.ron");
+    Some(path)
+}
+
+/// Kinds of log messages. Each kind can be hidden with the `logfilter` command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum LogCategory {
+    /// Attacks, damage and deaths.
+    Combat,
+    /// Picking up, using, buying and storing items.
+    Items,
+    /// Everything else the game tells the player, like the results of their actions.
+    System,
+    /// Lore, dialogue and inscriptions.
+    Lore,
+    /// Debug information, only shown in dev builds.
+    Debug,
+}
+
+impl LogCategory {
+    /// Name of the category as used by the `logfilter` command.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogCategory::Combat => "combat",
+            LogCategory::Items => "items",
+            LogCategory::System => "system",
+            LogCategory::Lore => "lore",
+            LogCategory::Debug => "debug",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::iter().find(|category| category.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum LogData {
     /// Used for plain-text debug information in the log.
     DebugInfo(String),
@@ -125,10 +422,12 @@ pub enum LogData {
     PlayerAttackHit {
         npc_name: String,
         damage: u16,
+        blocked: u16,
     },
     PlayerAttackHitCritical {
         npc_name: String,
         damage: u16,
+        blocked: u16,
     },
     PlayerAttackMiss {
         npc_name: String,
@@ -139,10 +438,12 @@ pub enum LogData {
     NpcAttackHit {
         npc_name: String,
         damage: u16,
+        blocked: u16,
     },
     NpcAttackHitCritical {
         npc_name: String,
         damage: u16,
+        blocked: u16,
     },
     NpcAttackMiss {
         npc_name: String,
@@ -150,12 +451,58 @@ pub enum LogData {
     NpcDied {
         npc_name: String,
     },
+    PlayerResists {
+        ailment: Ailment,
+    },
+    NpcResists {
+        npc_name: String,
+        ailment: Ailment,
+    },
+    PlayerKnockedBack {
+        npc_name: String,
+    },
+    NpcKnockedBack {
+        npc_name: String,
+    },
+    NpcRevealed {
+        npc_name: String,
+    },
+    PackLeaderDied {
+        npc_name: String,
+    },
+    PlayerCrowdControlled {
+        status: CrowdControl,
+    },
+    NpcCrowdControlled {
+        npc_name: String,
+        status: CrowdControl,
+    },
+    CrowdControlEnded {
+        status: CrowdControl,
+    },
+    PlayerElementalHit {
+        damage_type: DamageType,
+    },
+    NpcElementalHit {
+        npc_name: String,
+        damage_type: DamageType,
+    },
     InventoryFull,
     EquipmentSlotEmpty,
+    EquipmentCursed,
+    CurseRevealed {
+        item_name: String,
+    },
+    CursesLifted,
+    NothingCursed,
     UseStairsDown,
     UseStairsUp,
+    NoStairs,
     NoInteraction,
     Overdose,
+    BuffExpired {
+        potion_type: PotionType,
+    },
     PlayerHealed {
         amount: u16,
     },
@@ -163,6 +510,22 @@ pub enum LogData {
     ItemPickUp {
         item_name: String,
     },
+    ItemsHere {
+        item_name: String,
+        amount: usize,
+    },
+    NothingToPickUp,
+    ItemsDropped {
+        item_name: String,
+        amount: usize,
+        /// The items landed next to the player instead of under them.
+        beside: bool,
+    },
+    NoRoomToDrop,
+    CorpseLooted {
+        corpse_name: String,
+        item_name: String,
+    },
     LevelUp {
         new_level: u8,
     },
@@ -171,138 +534,988 @@ pub enum LogData {
     },
     TileNotVisible,
     OutOfRange,
+    NoLineOfSight,
     TileOccupied,
+    FoodCooked {
+        raw_name: String,
+        cooked_name: String,
+    },
+    FoodBurnt {
+        raw_name: String,
+    },
+    NothingToCook,
+    RestStarted,
+    RestEnemiesNearby,
+    RestNotNeeded,
+    RestEnded {
+        end: RestEnd,
+    },
+    TravelStarted,
+    TravelEnemiesNearby,
+    TravelNoWay,
+    TravelEnded {
+        end: TravelEnd,
+    },
+    NoCampfireNearby,
+    CampfireBuilt,
+    NoFreeSpace,
+    GauntletCompleted,
+    MemorialRaised,
+    MemorialInscription {
+        turns: u64,
+        damage: u32,
+    },
+    ShrineBlessing {
+        hp_bonus: u16,
+    },
+    AltarHeals,
+    AltarBlessesWeapon {
+        item_name: String,
+        bonus: i16,
+    },
+    AltarCurses {
+        strength_penalty: u8,
+        duration: u8,
+    },
+    AltarSummonsGuardians {
+        amount: usize,
+    },
+    NpcSays {
+        npc_name: String,
+        line: String,
+    },
+    NpcPetted {
+        npc_name: String,
+    },
+    NpcShoved {
+        npc_name: String,
+    },
+    GoldStolen {
+        npc_name: String,
+        amount: u32,
+    },
+    CaughtStealing {
+        npc_name: String,
+    },
+    NothingToInteract,
+    JokeRead {
+        joke: String,
+    },
+    FrogDistractsBoss {
+        npc_name: String,
+    },
+    SwimFailed,
+    LavaBurn {
+        damage: u16,
+    },
+    PlayerSpikeTrap {
+        damage: u16,
+    },
+    NpcSpikeTrap {
+        npc_name: String,
+        damage: u16,
+    },
+    SlowedByBurden,
+    ChasmFall {
+        damage: u16,
+    },
+    GoldLooted {
+        amount: u32,
+    },
+    ItemBought {
+        item_name: String,
+        price: u32,
+    },
+    NotEnoughGold {
+        price: u32,
+    },
+    HaggleSucceeded {
+        npc_name: String,
+        discount: i32,
+    },
+    HaggleFailed {
+        npc_name: String,
+    },
+    ShopkeeperOffended {
+        npc_name: String,
+    },
+    ShopkeeperRefuses,
+    AlreadyHaggled,
+    NpcAttackNpcHit {
+        attacker_name: String,
+        target_name: String,
+        damage: u16,
+    },
+    NpcAttackNpcMiss {
+        attacker_name: String,
+        target_name: String,
+    },
+    BlastHitNpc {
+        npc_name: String,
+        damage: u16,
+        blocked: u16,
+    },
+    BlastHitPlayer {
+        damage: u16,
+        blocked: u16,
+    },
+    AnimalEats {
+        npc_name: String,
+        item_name: String,
+    },
+    AnimalTamed {
+        npc_name: String,
+    },
+    SwapPlaces {
+        npc_name: String,
+    },
+    HealerHeals {
+        npc_name: String,
+        amount: u16,
+        price: u32,
+    },
+    AlreadyHealthy,
+    ItemStored {
+        item_name: String,
+    },
+    ItemTakenFromStash {
+        item_name: String,
+    },
+    StashFull,
+    StashEmpty,
+    ItemTakenFromContainer {
+        item_name: String,
+        container_name: String,
+    },
+    TookAllFromContainer {
+        amount: usize,
+        container_name: String,
+    },
+    ItemPutIntoContainer {
+        item_name: String,
+        container_name: String,
+    },
+    ContainerFull,
+    ContainerEmpty,
+    BagNotEmpty,
+    QuestAccepted {
+        description: String,
+    },
+    QuestCompleted {
+        description: String,
+        gold: u32,
+        experience: u32,
+    },
+    BurrowShifts,
+    FloorAffix {
+        announcement: String,
+    },
+    Ambush,
+    DoorLocked,
+    DoorStaysLocked,
+    DoorsUnlocked,
+    GauntletStarted,
+    PerkGained {
+        perk_name: String,
+    },
+    SkillImproved {
+        skill_name: String,
+        rank: u8,
+    },
 }
 
 impl fmt::Display for LogData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            LogData::DebugInfo(_) => write!(f, "[ INFO ] {}", self.display()),
-            LogData::DebugWarn(_) => write!(f, "[ WARN ] {}", self.display()),
-            _ => write!(f, "         {}", self.display()),
+            LogData::DebugInfo(_) => write!(f, "[ INFO ] {}", self.line()),
+            LogData::DebugWarn(_) => write!(f, "[ WARN ] {}", self.line()),
+            _ => write!(f, "         {}", self.line()),
         }
     }
 }
 
 impl LogData {
-    /// Converts LogData into a representation used in the Ratatui component for the game's log.
-    pub fn display(&self) -> Line<'_> {
+    /// The category the message belongs to, by which it can be hidden.
+    ///
+    /// # Returns
+    /// * `None` for plain text, which answers the player's commands and is always shown.
+    pub fn category(&self) -> Option<LogCategory> {
+        let category = match self {
+            LogData::Plain(_) => return None,
+            LogData::DebugInfo(_) | LogData::DebugWarn(_) => LogCategory::Debug,
+
+            LogData::PlayerAttackHit { .. }
+            | LogData::PlayerAttackHitCritical { .. }
+            | LogData::PlayerAttackMiss { .. }
+            | LogData::NpcAttackHit { .. }
+            | LogData::NpcAttackHitCritical { .. }
+            | LogData::NpcAttackMiss { .. }
+            | LogData::NpcDied { .. }
+            | LogData::NpcAttackNpcHit { .. }
+            | LogData::NpcAttackNpcMiss { .. }
+            | LogData::BlastHitNpc { .. }
+            | LogData::BlastHitPlayer { .. }
+            | LogData::LavaBurn { .. }
+            | LogData::PlayerSpikeTrap { .. }
+            | LogData::NpcSpikeTrap { .. }
+            | LogData::ChasmFall { .. }
+            | LogData::AltarSummonsGuardians { .. }
+            | LogData::Ambush
+            | LogData::PlayerResists { .. }
+            | LogData::NpcResists { .. }
+            | LogData::PlayerKnockedBack { .. }
+            | LogData::NpcKnockedBack { .. }
+            | LogData::NpcRevealed { .. }
+            | LogData::PackLeaderDied { .. }
+            | LogData::PlayerCrowdControlled { .. }
+            | LogData::NpcCrowdControlled { .. }
+            | LogData::CrowdControlEnded { .. }
+            | LogData::PlayerElementalHit { .. }
+            | LogData::NpcElementalHit { .. } => LogCategory::Combat,
+
+            LogData::PlayerEats { .. }
+            | LogData::InventoryFull
+            | LogData::EquipmentSlotEmpty
+            | LogData::EquipmentCursed
+            | LogData::CurseRevealed { .. }
+            | LogData::CursesLifted
+            | LogData::NothingCursed
+            | LogData::Overdose
+            | LogData::BuffExpired { .. }
+            | LogData::ItemPickUp { .. }
+            | LogData::ItemsHere { .. }
+            | LogData::NothingToPickUp
+            | LogData::ItemsDropped { .. }
+            | LogData::NoRoomToDrop
+            | LogData::CorpseLooted { .. }
+            | LogData::FoodCooked { .. }
+            | LogData::FoodBurnt { .. }
+            | LogData::NothingToCook
+            | LogData::CampfireBuilt
+            | LogData::NoCampfireNearby
+            | LogData::GoldLooted { .. }
+            | LogData::GoldStolen { .. }
+            | LogData::ItemBought { .. }
+            | LogData::NotEnoughGold { .. }
+            | LogData::ItemStored { .. }
+            | LogData::ItemTakenFromStash { .. }
+            | LogData::StashFull
+            | LogData::StashEmpty
+            | LogData::ItemTakenFromContainer { .. }
+            | LogData::TookAllFromContainer { .. }
+            | LogData::ItemPutIntoContainer { .. }
+            | LogData::ContainerFull
+            | LogData::ContainerEmpty
+            | LogData::BagNotEmpty
+            | LogData::SlowedByBurden => LogCategory::Items,
+
+            LogData::Lore(_)
+            | LogData::GauntletGreeting
+            | LogData::MemorialInscription { .. }
+            | LogData::NpcSays { .. }
+            | LogData::JokeRead { .. }
+            | LogData::FloorAffix { .. }
+            | LogData::BurrowShifts => LogCategory::Lore,
+
+            _ => LogCategory::System,
+        };
+        Some(category)
+    }
+
+    /// Converts LogData into the text shown in the game's log. Its parts are tagged, so frontends can style them.
+    pub fn line(&self) -> LogLine<'_> {
         match self {
-            LogData::Plain(message) => Line::from(message.to_string()),
-            LogData::DebugInfo(message) => Line::styled(message.to_string(), STYLE_DEBUG_INFO),
-            LogData::DebugWarn(message) => Line::styled(message.to_string(), STYLE_DEBUG_WARN),
-            LogData::Lore(message) => {
-                Line::styled(message.to_string(), Style::new().add_modifier(Modifier::ITALIC))
-            }
-            LogData::PlayerAttackHit { npc_name, damage } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::raw(" attack "),
-                Span::styled(npc_name, STYLE_NPC),
-                Span::raw(" and deal "),
-                Span::styled(damage.to_string(), STYLE_NUMBER),
-                Span::raw(" damage."),
-            ]),
-            LogData::PlayerAttackHitCritical { npc_name, damage } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::styled(" critically hit ", STYLE_DANGER),
-                Span::styled(npc_name, STYLE_NPC),
-                Span::raw(" and deal "),
-                Span::styled(damage.to_string(), STYLE_NUMBER),
-                Span::raw(" damage."),
-            ]),
-            LogData::PlayerAttackMiss { npc_name } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::raw(" attack "),
-                Span::styled(npc_name, STYLE_NPC),
-                Span::raw(", but miss."),
-            ]),
-            LogData::PlayerEats { item_name } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::raw(" eat "),
-                Span::styled(item_name, STYLE_ITEM),
-            ]),
-            LogData::NpcAttackHit { npc_name, damage } => Line::from(vec![
-                Span::styled(npc_name, STYLE_NPC),
-                Span::raw(" attacks "),
-                Span::styled("you", STYLE_YOU),
-                Span::raw(" and deals "),
-                Span::styled(damage.to_string(), STYLE_NUMBER),
-                Span::raw(" damage."),
-            ]),
-            LogData::NpcAttackHitCritical { npc_name, damage } => Line::from(vec![
-                Span::styled(npc_name, STYLE_NPC),
-                Span::styled(" critically hits", STYLE_DANGER),
-                Span::styled(" you", STYLE_YOU),
-                Span::raw(" and deals "),
-                Span::styled(damage.to_string(), STYLE_NUMBER),
-                Span::raw(" damage."),
-            ]),
-            LogData::NpcAttackMiss { npc_name } => Line::from(vec![
-                Span::styled(npc_name, STYLE_NPC),
-                Span::raw(" attacks "),
-                Span::styled("you", STYLE_YOU),
-                Span::raw(", but misses."),
+            LogData::Plain(message) => LogLine::from(message.to_string()),
+            LogData::DebugInfo(message) => LogLine::tagged(message.to_string(), LogTag::DebugInfo),
+            LogData::DebugWarn(message) => LogLine::tagged(message.to_string(), LogTag::DebugWarn),
+            LogData::Lore(message) => LogLine::tagged(message.to_string(), LogTag::Lore),
+            LogData::PlayerAttackHit { npc_name, damage, blocked } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" attack "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" and deal "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage"),
+                blocked_span(*blocked),
+                LogSpan::raw("."),
+            ]),
+            LogData::PlayerAttackHitCritical { npc_name, damage, blocked } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::tagged(" critically hit ", LogTag::Danger),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" and deal "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage"),
+                blocked_span(*blocked),
+                LogSpan::raw("!"),
+            ]),
+            LogData::PlayerAttackMiss { npc_name } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" dodges "),
+                LogSpan::tagged("your", LogTag::You),
+                LogSpan::raw(" attack."),
+            ]),
+            LogData::PlayerEats { item_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" eat "),
+                LogSpan::tagged(item_name, LogTag::Item),
+            ]),
+            LogData::NpcAttackHit { npc_name, damage, blocked } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" attacks "),
+                LogSpan::tagged("you", LogTag::You),
+                LogSpan::raw(" and deals "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage"),
+                blocked_span(*blocked),
+                LogSpan::raw("."),
+            ]),
+            LogData::NpcAttackHitCritical { npc_name, damage, blocked } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::tagged(" critically hits", LogTag::Danger),
+                LogSpan::tagged(" you", LogTag::You),
+                LogSpan::raw(" and deals "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage"),
+                blocked_span(*blocked),
+                LogSpan::raw("!"),
+            ]),
+            LogData::NpcAttackMiss { npc_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" dodge the attack of "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw("."),
             ]),
             LogData::NpcDied { npc_name } => {
-                Line::from(vec![Span::styled(npc_name, STYLE_NPC), Span::raw(" died.")])
+                LogLine::from(vec![LogSpan::tagged(npc_name, LogTag::Npc), LogSpan::raw(" died.")])
             }
-            LogData::InventoryFull => Line::from(vec![
-                Span::styled("Your", STYLE_YOU),
-                Span::raw(" inventory is full. Cannot add another item."),
+            LogData::PlayerResists { ailment } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(format!(" resist the {}!", ailment.name())),
+            ]),
+            LogData::NpcResists { npc_name, ailment } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(format!(" resists the {}!", ailment.name())),
+            ]),
+            LogData::PlayerKnockedBack { npc_name } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" knocks "),
+                LogSpan::tagged("you", LogTag::You),
+                LogSpan::raw(" back."),
+            ]),
+            LogData::NpcKnockedBack { npc_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" knock "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" back."),
+            ]),
+            LogData::NpcRevealed { npc_name } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::tagged(" appears out of thin air!", LogTag::Danger),
+            ]),
+            LogData::PackLeaderDied { npc_name } => LogLine::from(vec![
+                LogSpan::raw("The pack of "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" loses heart."),
+            ]),
+            LogData::PlayerCrowdControlled { status } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::tagged(format!(" are {}!", status.name()), LogTag::Danger),
+            ]),
+            LogData::NpcCrowdControlled { npc_name, status } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(format!(" is {}!", status.name())),
+            ]),
+            LogData::CrowdControlEnded { status } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(format!(" are no longer {}.", status.name())),
+            ]),
+            LogData::PlayerElementalHit { damage_type } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::tagged(format!(" are {}!", damage_type.effect()), LogTag::Danger),
+            ]),
+            LogData::NpcElementalHit { npc_name, damage_type } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(format!(" is {}!", damage_type.effect())),
+            ]),
+            LogData::InventoryFull => LogLine::from(vec![
+                LogSpan::tagged("Your", LogTag::You),
+                LogSpan::raw(" inventory is full. Cannot add another item."),
             ]),
             LogData::EquipmentSlotEmpty => {
-                Line::from("Action not possible. Required equipment slot empty.")
+                LogLine::from("Action not possible. Required equipment slot empty.")
+            }
+            LogData::EquipmentCursed => LogLine::tagged(
+                "The cursed item clings to you and will not come off.",
+                LogTag::Danger,
+            ),
+            LogData::CurseRevealed { item_name } => LogLine::from(vec![
+                LogSpan::raw("The "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::tagged(" is cursed! You cannot take it off.", LogTag::Danger),
+            ]),
+            LogData::CursesLifted => LogLine::from(vec![
+                LogSpan::raw("The curse lifts from "),
+                LogSpan::tagged("your", LogTag::You),
+                LogSpan::raw(" equipment."),
+            ]),
+            LogData::NothingCursed => LogLine::from("Nothing you have equipped is cursed."),
+            LogData::UseStairsDown => LogLine::from("You go down the stairs..."),
+            LogData::UseStairsUp => LogLine::from("You go back up the stairs..."),
+            LogData::NoStairs => LogLine::from("There are no stairs leading that way here."),
+            LogData::NoInteraction => LogLine::from("You cannot interact with that object."),
+            LogData::Overdose => LogLine::from("You are experiencing the effects of overdosing."),
+            LogData::BuffExpired { potion_type } => LogLine::from(match potion_type {
+                PotionType::Heal => "The warmth of the healing draught fades.",
+                PotionType::Strength => "You feel the strength leaving your arms.",
+                PotionType::Dexterity => "Your movements lose their nimbleness.",
+                PotionType::Poison => "The poison has run its course.",
+                PotionType::Fatigue => "Your fatigue fades away.",
+                PotionType::Cramp => "Your cramps ease up.",
+                PotionType::SeeInvisible => "The unseen fades from your sight again.",
+            }),
+            LogData::PlayerHealed { amount } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" regain "),
+                LogSpan::tagged(amount.to_string(), LogTag::Number),
+                LogSpan::raw(" hit points."),
+            ]),
+            LogData::GauntletGreeting => LogLine::from(vec![
+                LogSpan::raw("Welcome to the ").add_modifier(Modifier::ITALIC),
+                LogSpan::tagged("Gauntlet", LogTag::Danger)
+                    .add_modifier(Modifier::UNDERLINED)
+                    .add_modifier(Modifier::ITALIC),
+                LogSpan::raw(". Prove your worth!").add_modifier(Modifier::ITALIC),
+            ]),
+            LogData::ItemPickUp { item_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" picked up "),
+                LogSpan::tagged(item_name, LogTag::Item),
+            ]),
+            LogData::ItemsHere { item_name, amount: 1 } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" see "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(" here."),
+            ]),
+            LogData::ItemsHere { amount, .. } => LogLine::from(vec![
+                LogSpan::raw("There are "),
+                LogSpan::tagged(amount.to_string(), LogTag::Number),
+                LogSpan::raw(" items here."),
+            ]),
+            LogData::NothingToPickUp => LogLine::from("There is nothing here to pick up."),
+            LogData::ItemsDropped { item_name, amount, beside } => {
+                let mut spans =
+                    vec![LogSpan::tagged("You", LogTag::You), LogSpan::raw(" dropped ")];
+                if *amount > 1 {
+                    spans.push(LogSpan::tagged(amount.to_string(), LogTag::Number));
+                    spans.push(LogSpan::raw(" x "));
+                }
+                spans.push(LogSpan::tagged(item_name, LogTag::Item));
+                spans.push(LogSpan::raw(if *beside { " next to you." } else { "." }));
+                LogLine::from(spans)
+            }
+            LogData::NoRoomToDrop => LogLine::from("There is no room around you to drop anything."),
+            LogData::CorpseLooted { corpse_name, item_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" looted "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(" from the "),
+                LogSpan::tagged(corpse_name, LogTag::Npc),
+            ]),
+            LogData::LevelUp { new_level } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::tagged(" leveled up ", LogTag::Number),
+                LogSpan::raw("to level "),
+                LogSpan::tagged(new_level.to_string(), LogTag::Number),
+                LogSpan::raw("!"),
+            ]),
+            LogData::PerkGained { perk_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" gain the perk "),
+                LogSpan::tagged(perk_name, LogTag::Number),
+                LogSpan::raw("!"),
+            ]),
+            LogData::SkillImproved { skill_name, rank } => LogLine::from(vec![
+                LogSpan::raw("Your "),
+                LogSpan::tagged(skill_name, LogTag::Number),
+                LogSpan::raw(" skill improves to rank "),
+                LogSpan::tagged(rank.to_string(), LogTag::Number),
+                LogSpan::raw("!"),
+            ]),
+            LogData::LookAt { name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" see: "),
+                LogSpan::raw(name).add_modifier(Modifier::UNDERLINED),
+            ]),
+            LogData::TileNotVisible => LogLine::from("You cannot see this tile."),
+            LogData::OutOfRange => LogLine::from("Target not in range."),
+            LogData::NoLineOfSight => {
+                LogLine::from("There is no clear line of sight to the target.")
             }
-            LogData::UseStairsDown => Line::from("You go down the stairs..."),
-            LogData::UseStairsUp => Line::from("You go back up the stairs..."),
-            LogData::NoInteraction => Line::from("You cannot interact with that object."),
-            LogData::Overdose => Line::from("You are experiencing the effects of overdosing."),
-            LogData::PlayerHealed { amount } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::raw(" regain "),
-                Span::styled(amount.to_string(), STYLE_NUMBER),
-                Span::raw(" hit points."),
-            ]),
-            LogData::GauntletGreeting => Line::from(vec![
-                Span::styled("Welcome to the ", Style::new().add_modifier(Modifier::ITALIC)),
-                Span::styled(
-                    "Gauntlet",
-                    Style::new()
-                        .fg(Color::Red)
-                        .add_modifier(Modifier::UNDERLINED)
-                        .add_modifier(Modifier::ITALIC),
-                ),
-                Span::styled(". Prove your worth!", Style::new().add_modifier(Modifier::ITALIC)),
-            ]),
-            LogData::ItemPickUp { item_name } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::raw(" picked up "),
-                Span::styled(item_name, STYLE_ITEM),
-            ]),
-            LogData::LevelUp { new_level } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::styled(" leveled up ", STYLE_NUMBER),
-                Span::raw("to level "),
-                Span::styled(new_level.to_string(), STYLE_NUMBER),
-                Span::raw("!"),
-            ]),
-            LogData::LookAt { name } => Line::from(vec![
-                Span::styled("You", STYLE_YOU),
-                Span::raw(" see: "),
-                Span::styled(name, Style::new().add_modifier(Modifier::UNDERLINED)),
-            ]),
-            LogData::TileNotVisible => Line::from("You cannot see this tile."),
-            LogData::OutOfRange => Line::from("Target not in range."),
-            LogData::TileOccupied => Line::from("Position is occupied."),
+            LogData::TileOccupied => LogLine::from("Position is occupied."),
+            LogData::FoodCooked { raw_name, cooked_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" cook the "),
+                LogSpan::tagged(raw_name, LogTag::Item),
+                LogSpan::raw(" into "),
+                LogSpan::tagged(cooked_name, LogTag::Item),
+                LogSpan::raw("."),
+            ]),
+            LogData::FoodBurnt { raw_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::tagged(" burn ", LogTag::Danger),
+                LogSpan::raw("the "),
+                LogSpan::tagged(raw_name, LogTag::Item),
+                LogSpan::raw(" to a crisp."),
+            ]),
+            LogData::NothingToCook => LogLine::from("You have nothing to cook."),
+            LogData::RestStarted => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" sit down to rest."),
+            ]),
+            LogData::RestEnemiesNearby => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" cannot rest with "),
+                LogSpan::tagged("enemies", LogTag::Danger),
+                LogSpan::raw(" nearby."),
+            ]),
+            LogData::RestNotNeeded => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" are already fully rested."),
+            ]),
+            LogData::RestEnded { end } => match end {
+                RestEnd::Healed => LogLine::from(vec![
+                    LogSpan::tagged("You", LogTag::You),
+                    LogSpan::raw(" feel fully rested."),
+                ]),
+                RestEnd::TimeUp => LogLine::from(vec![
+                    LogSpan::tagged("You", LogTag::You),
+                    LogSpan::raw(" get up after a long rest."),
+                ]),
+                RestEnd::Cancelled => LogLine::from(vec![
+                    LogSpan::tagged("You", LogTag::You),
+                    LogSpan::raw(" stop resting."),
+                ]),
+                RestEnd::Interrupted => LogLine::from(vec![
+                    LogSpan::tagged("Your", LogTag::You),
+                    LogSpan::raw(" rest is interrupted by an "),
+                    LogSpan::tagged("enemy", LogTag::Danger),
+                    LogSpan::raw("!"),
+                ]),
+            },
+            LogData::TravelStarted => {
+                LogLine::from(vec![LogSpan::tagged("You", LogTag::You), LogSpan::raw(" set off.")])
+            }
+            LogData::TravelEnemiesNearby => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" cannot travel with "),
+                LogSpan::tagged("enemies", LogTag::Danger),
+                LogSpan::raw(" nearby."),
+            ]),
+            LogData::TravelNoWay => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" know no way there."),
+            ]),
+            LogData::TravelEnded { end } => match end {
+                TravelEnd::Arrived => LogLine::from(vec![
+                    LogSpan::tagged("You", LogTag::You),
+                    LogSpan::raw(" arrive."),
+                ]),
+                TravelEnd::Blocked => LogLine::from(vec![
+                    LogSpan::tagged("Your", LogTag::You),
+                    LogSpan::raw(" way is blocked."),
+                ]),
+                TravelEnd::Cancelled => LogLine::from(vec![
+                    LogSpan::tagged("You", LogTag::You),
+                    LogSpan::raw(" stop travelling."),
+                ]),
+                TravelEnd::Interrupted => LogLine::from(vec![
+                    LogSpan::tagged("Your", LogTag::You),
+                    LogSpan::raw(" travel is interrupted by an "),
+                    LogSpan::tagged("enemy", LogTag::Danger),
+                    LogSpan::raw("!"),
+                ]),
+            },
+            LogData::NoCampfireNearby => LogLine::from("You need a campfire next to you to cook."),
+            LogData::CampfireBuilt => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" strike the flint and a campfire flickers to life."),
+            ]),
+            LogData::NoFreeSpace => LogLine::from("There is no free space around you."),
+            LogData::GauntletCompleted => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" have conquered the "),
+                LogSpan::tagged("Gauntlet", LogTag::Danger).add_modifier(Modifier::ITALIC),
+                LogSpan::raw("!"),
+            ]),
+            LogData::MemorialRaised => LogLine::from(
+                "A memorial and a shrine have been raised near the stairs in honour of your victory.",
+            ),
+            LogData::MemorialInscription { turns, damage } => LogLine::from(vec![
+                LogSpan::tagged("\"Here the Gauntlet was conquered in ", LogTag::Lore),
+                LogSpan::tagged(turns.to_string(), LogTag::Number),
+                LogSpan::tagged(" turns, at the cost of ", LogTag::Lore),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::tagged(" wounds.\"", LogTag::Lore),
+            ]),
+            LogData::NpcSays { npc_name, line } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(": "),
+                LogSpan::tagged(line, LogTag::Lore),
+            ]),
+            LogData::NpcPetted { npc_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" pet the "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw("."),
+            ]),
+            LogData::NpcShoved { npc_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" shove the "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" out of your way."),
+            ]),
+            LogData::GoldStolen { npc_name, amount } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" steal "),
+                LogSpan::tagged(amount.to_string(), LogTag::Number),
+                LogSpan::raw(" gold from the "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw("."),
+            ]),
+            LogData::CaughtStealing { npc_name } => LogLine::from(vec![
+                LogSpan::raw("The "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" catches "),
+                LogSpan::tagged("you", LogTag::You),
+                LogSpan::raw(" stealing and turns on you!"),
+            ]),
+            LogData::NothingToInteract => LogLine::from("There is nothing to interact with there."),
+            LogData::JokeRead { joke } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" read: "),
+                LogSpan::tagged(joke, LogTag::Lore),
+            ]),
+            LogData::SwimFailed => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" flounder in the deep water and make no progress."),
+            ]),
+            LogData::LavaBurn { damage } => LogLine::from(vec![
+                LogSpan::tagged("The lava burns you", LogTag::Danger),
+                LogSpan::raw(" for "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage!"),
+            ]),
+            LogData::PlayerSpikeTrap { damage } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::tagged(" step on a spike trap", LogTag::Danger),
+                LogSpan::raw(" for "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage!"),
+            ]),
+            LogData::NpcSpikeTrap { npc_name, damage } => LogLine::from(vec![
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" steps on a spike trap for "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage!"),
+            ]),
+            LogData::SlowedByBurden => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" stagger under the weight of your belongings and lose a turn."),
+            ]),
+            LogData::ChasmFall { damage } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" fall into the chasm and land hard, taking "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage."),
+            ]),
+            LogData::GoldLooted { amount } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" find "),
+                LogSpan::tagged(amount.to_string(), LogTag::Number),
+                LogSpan::raw(" gold."),
+            ]),
+            LogData::ItemBought { item_name, price } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" buy the "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(" for "),
+                LogSpan::tagged(price.to_string(), LogTag::Number),
+                LogSpan::raw(" gold."),
+            ]),
+            LogData::NotEnoughGold { price } => LogLine::from(vec![
+                LogSpan::raw("You cannot afford that. It costs "),
+                LogSpan::tagged(price.to_string(), LogTag::Number),
+                LogSpan::raw(" gold."),
+            ]),
+            LogData::HaggleSucceeded { npc_name, discount } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" talk the "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" down by "),
+                LogSpan::tagged(format!("{}%", discount), LogTag::Number),
+                LogSpan::raw("."),
+            ]),
+            LogData::HaggleFailed { npc_name } => LogLine::from(vec![
+                LogSpan::raw("The "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" does not budge on the prices."),
+            ]),
+            LogData::ShopkeeperOffended { npc_name } => LogLine::from(vec![
+                LogSpan::raw("The "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::tagged(" is offended by your offer", LogTag::Danger),
+                LogSpan::raw(" and closes the shop."),
+            ]),
+            LogData::ShopkeeperRefuses => {
+                LogLine::from("The shopkeeper refuses to trade with you.")
+            }
+            LogData::AlreadyHaggled => LogLine::from("You already haggled here."),
+            LogData::NpcAttackNpcHit { attacker_name, target_name, damage } => LogLine::from(vec![
+                LogSpan::tagged(attacker_name, LogTag::Npc),
+                LogSpan::raw(" hits "),
+                LogSpan::tagged(target_name, LogTag::Npc),
+                LogSpan::raw(" for "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage."),
+            ]),
+            LogData::NpcAttackNpcMiss { attacker_name, target_name } => LogLine::from(vec![
+                LogSpan::tagged(attacker_name, LogTag::Npc),
+                LogSpan::raw(" misses "),
+                LogSpan::tagged(target_name, LogTag::Npc),
+                LogSpan::raw("."),
+            ]),
+            LogData::BlastHitNpc { npc_name, damage, blocked } => LogLine::from(vec![
+                LogSpan::raw("The blast hits "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" for "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage"),
+                blocked_span(*blocked),
+                LogSpan::raw("."),
+            ]),
+            LogData::BlastHitPlayer { damage, blocked } => LogLine::from(vec![
+                LogSpan::raw("The blast hits "),
+                LogSpan::tagged("you", LogTag::You),
+                LogSpan::raw(" for "),
+                LogSpan::tagged(damage.to_string(), LogTag::Number),
+                LogSpan::raw(" damage"),
+                blocked_span(*blocked),
+                LogSpan::raw("."),
+            ]),
+            LogData::AnimalEats { npc_name, item_name } => LogLine::from(vec![
+                LogSpan::raw("The "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" eats the "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw("."),
+            ]),
+            LogData::SwapPlaces { npc_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" swap places with the "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw("."),
+            ]),
+            LogData::AnimalTamed { npc_name } => LogLine::from(vec![
+                LogSpan::raw("The "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" wags its tail. It is now "),
+                LogSpan::tagged("your companion", LogTag::You),
+                LogSpan::raw("!"),
+            ]),
+            LogData::HealerHeals { npc_name, amount, price } => LogLine::from(vec![
+                LogSpan::raw("The "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw(" tends to your wounds. "),
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" heal "),
+                LogSpan::tagged(amount.to_string(), LogTag::Number),
+                LogSpan::raw(" HP for "),
+                LogSpan::tagged(price.to_string(), LogTag::Number),
+                LogSpan::raw(" gold."),
+            ]),
+            LogData::AlreadyHealthy => LogLine::from("You are not hurt."),
+            LogData::ItemStored { item_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" put the "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(" into the stash."),
+            ]),
+            LogData::ItemTakenFromStash { item_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" take the "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(" out of the stash."),
+            ]),
+            LogData::StashFull => LogLine::from("The stash is full."),
+            LogData::StashEmpty => LogLine::from("Both the stash and your pockets are empty."),
+            LogData::ItemTakenFromContainer { item_name, container_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" take the "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(format!(" out of the {}.", container_name)),
+            ]),
+            LogData::TookAllFromContainer { amount, container_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(format!(" take {} items out of the {}.", amount, container_name)),
+            ]),
+            LogData::ItemPutIntoContainer { item_name, container_name } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" put the "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(format!(" into the {}.", container_name)),
+            ]),
+            LogData::ContainerFull => LogLine::from("There is no more room in there."),
+            LogData::ContainerEmpty => {
+                LogLine::from("It is empty, and you have nothing to put in.")
+            }
+            LogData::BagNotEmpty => LogLine::from("You have to empty the bag first."),
+            LogData::QuestAccepted { description } => LogLine::from(vec![
+                LogSpan::raw("New quest: "),
+                LogSpan::tagged(description, LogTag::Lore),
+                LogSpan::raw(". (j to see your quests)"),
+            ]),
+            LogData::QuestCompleted { description, gold, experience } => LogLine::from(vec![
+                LogSpan::raw("Quest completed: "),
+                LogSpan::tagged(description, LogTag::Lore),
+                LogSpan::raw(format!(". You receive {} gold and {} EXP.", gold, experience)),
+            ]),
+            LogData::BurrowShifts => LogLine::tagged(
+                "Somewhere in the dark, the walls of the burrow shift.",
+                LogTag::Lore,
+            ),
+            LogData::FloorAffix { announcement } => LogLine::from(vec![
+                LogSpan::tagged(announcement, LogTag::Danger).add_modifier(Modifier::ITALIC),
+            ]),
+            LogData::Ambush => LogLine::tagged("It's an ambush!", LogTag::Danger),
+            LogData::DoorLocked => LogLine::from(vec![
+                LogSpan::raw("The door slams shut behind "),
+                LogSpan::tagged("you", LogTag::You),
+                LogSpan::raw(" and locks itself."),
+            ]),
+            LogData::DoorStaysLocked => {
+                LogLine::from("The door is locked. It won't open while enemies remain.")
+            }
+            LogData::DoorsUnlocked => {
+                LogLine::from("With the last enemy defeated, the locked doors click open.")
+            }
+            LogData::GauntletStarted => {
+                LogLine::tagged("The clock of the Gauntlet starts ticking.", LogTag::Danger)
+            }
+            LogData::FrogDistractsBoss { npc_name } => LogLine::from(vec![
+                LogSpan::raw("A familiar "),
+                LogSpan::tagged("Funny Frog", LogTag::Npc),
+                LogSpan::raw(" hops in and distracts "),
+                LogSpan::tagged(npc_name, LogTag::Npc),
+                LogSpan::raw("!"),
+            ]),
+            LogData::ShrineBlessing { hp_bonus } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" pray at the shrine. Your maximum hit points rise by "),
+                LogSpan::tagged(hp_bonus.to_string(), LogTag::Number),
+                LogSpan::raw("."),
+            ]),
+            LogData::AltarHeals => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" pray at the altar. A warm light mends all your wounds."),
+            ]),
+            LogData::AltarBlessesWeapon { item_name, bonus } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" pray at the altar. Your "),
+                LogSpan::tagged(item_name, LogTag::Item),
+                LogSpan::raw(" glows and deals "),
+                LogSpan::tagged(format!("+{}", bonus), LogTag::Number),
+                LogSpan::raw(" damage."),
+            ]),
+            LogData::AltarCurses { strength_penalty, duration } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" pray at the altar, but the gods are displeased. "),
+                LogSpan::tagged("Cursed!", LogTag::Danger),
+                LogSpan::raw(" Strength reduced by "),
+                LogSpan::tagged(strength_penalty.to_string(), LogTag::Number),
+                LogSpan::raw(" for "),
+                LogSpan::tagged(duration.to_string(), LogTag::Number),
+                LogSpan::raw(" turns."),
+            ]),
+            LogData::AltarSummonsGuardians { amount } => LogLine::from(vec![
+                LogSpan::tagged("You", LogTag::You),
+                LogSpan::raw(" pray at the altar, but the gods are displeased. "),
+                LogSpan::tagged(amount.to_string(), LogTag::Number),
+                LogSpan::tagged(" guardians rise to punish you!", LogTag::Danger),
+            ]),
         }
     }
 }
 
-// Pre-defined theme
-const STYLE_DEBUG_INFO: Style = Style::new().fg(Color::DarkGray);
-const STYLE_DEBUG_WARN: Style = Style::new().fg(Color::Red);
-const STYLE_YOU: Style = Style::new().add_modifier(Modifier::ITALIC);
-const STYLE_NPC: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::ITALIC);
-const STYLE_ITEM: Style = Style::new().fg(Color::Magenta).add_modifier(Modifier::BOLD);
-const STYLE_NUMBER: Style = Style::new().fg(Color::Cyan);
-const STYLE_DANGER: Style = Style::new().fg(Color::Red);
+/// Helper that creates the span noting how much damage was absorbed by mitigation. Empty if nothing was blocked.
+fn blocked_span(blocked: u16) -> LogSpan<'static> {
+    if blocked == 0 {
+        LogSpan::raw("")
+    } else {
+        LogSpan::tagged(format!(" ({} blocked)", blocked), LogTag::Blocked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_without_file() -> Log {
+        Log { messages: Vec::new(), file: None, json_file: None, round: 0, hidden: Vec::new() }
+    }
+
+    #[test]
+    fn hidden_categories_are_filtered_from_the_display() {
+        let mut log = log_without_file();
+        log.info(LogData::PlayerAttackMiss { npc_name: "Goblin".into() });
+        log.info(LogData::ItemPickUp { item_name: "Apple".into() });
+        log.print("Hello".into());
+
+        log.set_shown(LogCategory::Combat, false);
+        log.set_shown(LogCategory::System, false);
+        let shown: Vec<Option<LogCategory>> =
+            log.get_messages_for_display().iter().map(|entry| entry.data.category()).collect();
+        assert_eq!(shown, [Some(LogCategory::Items), None]);
+        assert_eq!(log.messages.len(), 3);
+
+        let mut next_run = log_without_file();
+        next_run.copy_filters_from(&log);
+        assert!(!next_run.is_shown(LogCategory::Combat));
+
+        log.set_shown(LogCategory::Combat, true);
+        assert_eq!(log.get_messages_for_display().len(), 3);
+        assert_eq!(LogCategory::from_name("Lore"), Some(LogCategory::Lore));
+    }
+
+    #[test]
+    fn repeated_messages_are_counted_in_one_entry() {
+        let mut log = log_without_file();
+        for _ in 0..5 {
+            log.info(LogData::NpcAttackMiss { npc_name: "Wolf".into() });
+        }
+        log.info(LogData::NpcAttackMiss { npc_name: "Goblin".into() });
+        log.info(LogData::NpcAttackMiss { npc_name: "Wolf".into() });
+
+        let counts: Vec<u32> = log.messages.iter().map(|entry| entry.count).collect();
+        assert_eq!(counts, [5, 1, 1]);
+        assert!(log.messages[0].to_string().ends_with("Wolf. (x5)"));
+    }
+
+    #[test]
+    fn entries_are_serialized_with_their_round() {
+        let mut log = log_without_file();
+        log.set_round(12);
+        log.info(LogData::NpcAttackHit { npc_name: "Wolf".into(), damage: 3, blocked: 1 });
+        log.info(LogData::NpcAttackHit { npc_name: "Wolf".into(), damage: 3, blocked: 1 });
+
+        let line = log.messages[0].json_line().unwrap();
+        assert!(line.starts_with(r#"{"turn":12,"time":""#));
+        assert!(line.ends_with(
+            r#""category":"combat","count":2,"data":{"NpcAttackHit":{"npc_name":"Wolf","damage":3,"blocked":1}}}"#
+        ));
+
+        let config: LogConfig = ron::from_str("(format: JsonLines)").unwrap();
+        assert_eq!(config.format, LogFormat::JsonLines);
+    }
+}
@@ -1,7 +1,10 @@
+pub mod biome;
 pub mod coordinate_system;
 pub mod level;
 pub mod level_data;
 pub mod level_loader;
+pub mod lighting;
+pub mod los;
 pub mod tiles;
 pub mod vision;
 pub mod worldspace;
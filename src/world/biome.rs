@@ -0,0 +1,128 @@
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::render::style::{Color, Style};
+use crate::world::tiles::TileType;
+
+/// Generated levels shallower than this always belong to the [Biome::Dungeon].
+pub const FIRST_BIOME_DEPTH: usize = 3;
+
+/// Factor by which the spawn weight of a biome's native monsters is multiplied.
+pub const NATIVE_NPC_WEIGHT_FACTOR: u32 = 3;
+
+/// Region of the anthill a level belongs to. Picked by the procedural generator and stored in the level data.
+///
+/// The biome colors the tiles of the level, decides which monsters and treasure are found in it
+/// and greets the player with some flavor text on entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Biome {
+    /// Plain halls of the anthill. Nothing is changed.
+    #[default]
+    Dungeon,
+
+    /// Abandoned mines, dug out by bandits and goblins.
+    Mines,
+
+    /// Burial chambers haunted by the undead.
+    Crypt,
+
+    /// Damp caverns overgrown with glowing fungi.
+    FungalCaverns,
+}
+
+impl Biome {
+    /// Randomly picks the biome for a level of the given depth.
+    ///
+    /// The first levels always belong to the dungeon. Crypts only appear further down.
+    pub fn for_depth<R: Rng + ?Sized>(depth: usize, rng: &mut R) -> Self {
+        if depth < FIRST_BIOME_DEPTH {
+            return Biome::Dungeon;
+        }
+
+        let weights = [
+            (Biome::Dungeon, 40),
+            (Biome::Mines, 25),
+            (Biome::FungalCaverns, 20),
+            (Biome::Crypt, if depth >= 6 { 25 } else { 0 }),
+        ];
+
+        weights
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .map(|(biome, _)| *biome)
+            .unwrap_or_default()
+    }
+
+    /// Applies the biome's color palette to the style of a tile.
+    ///
+    /// Only the structure of the level (floors, walls and hallways) is recolored. Everything else keeps its own style.
+    pub fn palette(&self, tile_type: TileType, style: Style) -> Style {
+        let color = match (self, tile_type) {
+            (Biome::Dungeon, _) => return style,
+            (Biome::Mines, TileType::Floor) => Color::Rgb(150, 110, 70),
+            (Biome::Mines, TileType::Wall) => Color::Rgb(190, 140, 90),
+            (Biome::Mines, TileType::Hallway) => Color::Rgb(110, 80, 50),
+            (Biome::Crypt, TileType::Floor) => Color::Rgb(130, 130, 150),
+            (Biome::Crypt, TileType::Wall) => Color::Rgb(200, 200, 220),
+            (Biome::Crypt, TileType::Hallway) => Color::Rgb(90, 90, 110),
+            (Biome::FungalCaverns, TileType::Floor) => Color::Rgb(90, 150, 110),
+            (Biome::FungalCaverns, TileType::Wall) => Color::Rgb(120, 190, 150),
+            (Biome::FungalCaverns, TileType::Hallway) => Color::Rgb(60, 110, 80),
+            _ => return style,
+        };
+
+        style.fg(color)
+    }
+
+    /// Monsters that are at home in the biome. They spawn [NATIVE_NPC_WEIGHT_FACTOR] times as often as others.
+    pub fn native_npcs(&self) -> &'static [&'static str] {
+        match self {
+            Biome::Dungeon => &[],
+            Biome::Mines => &["goblin", "bandit", "giant_rat", "orc"],
+            Biome::Crypt => &["skeleton", "zombie", "cultist", "dark_mage", "wraith"],
+            Biome::FungalCaverns => &["slime", "funny_frog", "giant_rat", "wolf"],
+        }
+    }
+
+    /// Loot table that is rolled for treasure found in the biome.
+    pub fn treasure_loot_table(&self) -> &'static str {
+        match self {
+            Biome::Dungeon => "treasure_room",
+            Biome::Mines => "treasure_mines",
+            Biome::Crypt => "treasure_crypt",
+            Biome::FungalCaverns => "treasure_fungal",
+        }
+    }
+
+    /// Flavor text that is logged when the player enters a level of the biome.
+    ///
+    /// # Returns
+    /// * `None` for the [Biome::Dungeon], which has nothing special to say.
+    pub fn entry_flavor(&self) -> Option<&'static str> {
+        match self {
+            Biome::Dungeon => None,
+            Biome::Mines => {
+                Some("..Rotten beams hold up the ceiling. Somewhere, a pickaxe is still at work.")
+            }
+            Biome::Crypt => Some("..Rows of tombs line the walls. The dead here do not rest easy."),
+            Biome::FungalCaverns => {
+                Some("..The air is thick with spores. Mushrooms glow softly in the dark.")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{loot_tables::get_loot_table_by_id, npc_defs::npc_defs};
+
+    #[test]
+    fn biomes_reference_defined_data() {
+        for biome in [Biome::Dungeon, Biome::Mines, Biome::Crypt, Biome::FungalCaverns] {
+            assert!(get_loot_table_by_id(biome.treasure_loot_table()).is_some());
+            for def_id in biome.native_npcs() {
+                assert!(npc_defs().contains_key(*def_id), "Unknown npc {}", def_id);
+            }
+        }
+    }
+}
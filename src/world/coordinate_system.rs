@@ -133,7 +133,7 @@ impl From<Direction> for PointVector {
 }
 
 /// Represents the 4 cardinal directions Up, Right, Down, Left.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
 pub enum Direction {
     Up,
     Right,
@@ -157,3 +157,46 @@ impl TryFrom<PointVector> for Direction {
         }
     }
 }
+
+/// Tiles covered by an entity: a square of `size`x`size` tiles whose top left corner is the entity's position.
+///
+/// Most entities cover a single tile. Large monsters (see [NpcDef::size](crate::data::npc_defs::NpcDef::size)) cover more.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Footprint {
+    pub origin: Point,
+    pub size: usize,
+}
+
+impl Footprint {
+    pub fn new(origin: Point, size: usize) -> Self {
+        Self { origin, size: size.max(1) }
+    }
+
+    /// Footprint of an entity that covers a single tile.
+    pub fn single(origin: Point) -> Self {
+        Self::new(origin, 1)
+    }
+
+    /// The same footprint with its top left corner at another point.
+    pub fn moved_to(&self, origin: Point) -> Self {
+        Self::new(origin, self.size)
+    }
+
+    /// All points covered by the footprint, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Footprint { origin, size } = *self;
+        (origin.y..origin.y + size)
+            .flat_map(move |y| (origin.x..origin.x + size).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.origin.x..self.origin.x + self.size).contains(&point.x)
+            && (self.origin.y..self.origin.y + self.size).contains(&point.y)
+    }
+
+    /// Whether the point is next to the footprint (not diagonally) without being covered by it.
+    pub fn is_adjacent_to(&self, point: Point) -> bool {
+        !self.contains(point)
+            && self.points().any(|covered| covered.distance_squared_from(point) == 1)
+    }
+}
@@ -2,18 +2,27 @@
 
 use std::collections::HashMap;
 
-use rand::RngCore;
+use rand::{RngCore, seq::IndexedRandom};
 
+use crate::ai::npc_ai::NpcAiState;
+use crate::ai::pack::pack_positions;
+use crate::core::corpses::Corpse;
 use crate::core::entity_logic::{Entity, Npc};
-use crate::core::game_items::GameItemSprite;
+use crate::core::game_items::{GameItemId, GameItemSprite};
+use crate::core::gauntlet::GauntletRecord;
+#[cfg(feature = "scripting")]
+use crate::core::scripting::LoadedScript;
+use crate::core::town::{TOWN_LEVEL, TUTORIAL_LEVEL};
+use crate::data::floor_affixes::{FloorAffixDef, FloorAffixId, get_floor_affix_by_id};
 use crate::data::levels::level_paths;
 use crate::proc_gen::proc_gen_level::ProcGenLevel;
 use crate::util::errors_results::{DataError, EngineError};
 use crate::util::text_log::LogData;
-use crate::world::coordinate_system::Point;
-use crate::world::level_data::{LevelData, SpawnKind};
+use crate::world::biome::Biome;
+use crate::world::coordinate_system::{Footprint, Point};
+use crate::world::level_data::{LevelData, ScriptData, SpawnKind, TriggerData};
 use crate::world::level_loader::load_world_from_ron;
-use crate::world::tiles::Collision;
+use crate::world::tiles::{Collision, TileType};
 use crate::{
     core::{entity_logic::EntityId, game::GameState},
     util::errors_results::GameError,
@@ -23,15 +32,16 @@ use crate::{
 /// The game has procedurally generated levels, but also at some fixed points, there are handmade pre-defined levels.
 /// This constant defines at what interval these levels are supposed to appear.
 ///
-/// Example with default interval of `8`: Static level appears at levels 2, 10, 18, 26, 34, ...
+/// Example with default interval of `8`: Static level appears at levels 3, 11, 19, 27, 35, ...
 const STATIC_LEVEL_INTERVAL: usize = 8;
 
 /// Checks if a given level is a gauntlet (=handcrafted level with extra challenge)
 /// Gauntlets occur at an interval of [STATIC_LEVEL_INTERVAL]
 fn is_gauntlet_level(level: usize) -> bool {
-    level % STATIC_LEVEL_INTERVAL == 2
+    level % STATIC_LEVEL_INTERVAL == 3
 }
 
+#[derive(Clone)]
 pub struct Level {
     pub world: World,
 
@@ -43,6 +53,40 @@ pub struct Level {
 
     pub item_sprites: Vec<GameItemSprite>,
     pub item_sprites_index: HashMap<EntityId, usize>,
+
+    pub corpses: Vec<Corpse>,
+    pub corpses_index: HashMap<EntityId, usize>,
+
+    /// Contents of the [TileType::Chest]s of the level, by their position.
+    pub chests: HashMap<Point, Vec<GameItemId>>,
+
+    /// Record of the player's run, if the level is a gauntlet.
+    pub gauntlet: Option<GauntletRecord>,
+
+    /// Region of the anthill the level belongs to.
+    pub biome: Biome,
+
+    /// Floor affix of the level (see [floor_affix_defs](crate::data::floor_affixes::floor_affix_defs)), if it has one.
+    pub affix: Option<FloorAffixId>,
+
+    /// Scripts that have not run yet. They run the first time the player enters the level.
+    pub scripts: Vec<ScriptData>,
+
+    /// Scripts that have run, waiting for the events of the level.
+    #[cfg(feature = "scripting")]
+    pub loaded_scripts: Vec<LoadedScript>,
+
+    /// Triggers that have not fired yet.
+    pub triggers: Vec<TriggerData>,
+
+    /// Doors locked by triggers. They open again once all enemies of the level are defeated.
+    pub locked_doors: Vec<Point>,
+
+    /// Number of wandering monsters that have arrived on the level since it was generated.
+    pub wanderers: usize,
+
+    /// Round the player last left the level. Cleared once the level caught up with the rounds it missed (see [GameState::catch_up_level]).
+    pub left_round: Option<u64>,
 }
 
 impl Level {
@@ -58,9 +102,34 @@ impl Level {
 
             item_sprites: Vec::new(),
             item_sprites_index: HashMap::new(),
+
+            corpses: Vec::new(),
+            corpses_index: HashMap::new(),
+
+            chests: HashMap::new(),
+
+            gauntlet: None,
+
+            biome: Biome::default(),
+            affix: None,
+
+            scripts: Vec::new(),
+            #[cfg(feature = "scripting")]
+            loaded_scripts: Vec::new(),
+            triggers: Vec::new(),
+            locked_doors: Vec::new(),
+
+            wanderers: 0,
+
+            left_round: None,
         }
     }
 
+    /// Gets the definition of the level's floor affix.
+    pub fn affix_def(&self) -> Option<&'static FloorAffixDef> {
+        self.affix.as_deref().and_then(get_floor_affix_by_id)
+    }
+
     pub fn get_npc(&self, id: EntityId) -> Option<&Npc> {
         self.npc_index.get(&id).map(|&index| &self.npcs[index])
     }
@@ -77,13 +146,21 @@ impl Level {
         self.item_sprites_index.get(&id).map(|&index| &mut self.item_sprites[index])
     }
 
+    pub fn get_corpse(&self, id: EntityId) -> Option<&Corpse> {
+        self.corpses_index.get(&id).map(|&index| &self.corpses[index])
+    }
+
+    pub fn get_corpse_mut(&mut self, id: EntityId) -> Option<&mut Corpse> {
+        self.corpses_index.get(&id).map(|&index| &mut self.corpses[index])
+    }
+
     /// Looks through NPCs to find one at the given `Point`.
     ///
     /// # Returns
     /// Returns `Some(EntityId)` if an npc was found.
     pub fn get_npc_at(&self, point: Point) -> Option<EntityId> {
         for npc in &self.npcs {
-            if npc.pos() == point {
+            if npc.footprint().contains(point) {
                 return Some(npc.id());
             }
         }
@@ -105,33 +182,64 @@ impl Level {
         None
     }
 
+    /// All item sprites lying at the given `Point`. Items pile up, so there may be several.
+    pub fn item_sprites_at(&self, point: Point) -> impl Iterator<Item = &GameItemSprite> {
+        self.item_sprites.iter().filter(move |item_sprite| item_sprite.pos() == point)
+    }
+
+    /// Looks through corpses to find one at the given `Point`.
+    ///
+    /// # Returns
+    /// Returns `Some(EntityId)` if a corpse was found.
+    pub fn get_corpse_at(&self, point: Point) -> Option<EntityId> {
+        self.corpses.iter().find(|corpse| corpse.pos() == point).map(|corpse| corpse.id())
+    }
+
     /// Checks if a given point is:
     /// - In Bounds
     /// - Not occupied by NPCs
     /// - Not occupied by item_sprites
     /// - Walkable
     pub fn is_available(&self, point: Point) -> bool {
-        let in_bounds = self.world.is_in_bounds(point.x as isize, point.y as isize);
-        let not_occupied = !self.is_occupied(point);
-        let walkable = self.world.get_tile(point).tile_type.is_walkable();
+        // Checked first, the tile of a point out of bounds can't be looked at
+        if !self.world.is_in_bounds(point.x as isize, point.y as isize) {
+            return false;
+        }
 
-        in_bounds && not_occupied && walkable
+        !self.is_occupied(point) && self.world.get_tile(point).tile_type.is_walkable()
     }
 
     /// Checks if a given point is occupied by an NPC or Item Sprite.
     pub fn is_occupied(&self, point: Point) -> bool {
-        let occupied_by_npc = self.npcs.iter().any(|npc| npc.base.pos == point);
+        let occupied_by_npc = self.npcs.iter().any(|npc| npc.footprint().contains(point));
         let occupied_by_item_sprite = self.item_sprites.iter().any(|item| item.base.pos == point);
         occupied_by_npc || occupied_by_item_sprite
     }
 
+    /// Checks if items can be put down at a given point. Items pile up, but they would be lost in water, lava or chasms.
+    pub fn can_hold_items(&self, point: Point) -> bool {
+        let in_bounds = self.world.is_in_bounds(point.x as isize, point.y as isize);
+        if !in_bounds || self.get_npc_at(point).is_some() {
+            return false;
+        }
+
+        let tile_type = self.world.get_tile(point).tile_type;
+        tile_type.is_walkable()
+            && !matches!(tile_type, TileType::Water | TileType::Lava | TileType::Chasm)
+    }
+
+    /// Checks if all points of the footprint are available (see [Level::is_available]).
+    pub fn is_footprint_available(&self, footprint: Footprint) -> bool {
+        footprint.points().all(|point| self.is_available(point))
+    }
+
     /// Spawns an NPC on the map.
     ///
-    /// The function checks whether the target position is free.  
-    /// If the position is unavailable, a `GameError::SpawningError` is returned.
+    /// The function checks whether all tiles the NPC covers are free.  
+    /// If a tile is unavailable, a `GameError::SpawningError` is returned.
     /// On success, the NPC is added to the internal list and its ID is indexed.
     pub fn spawn_npc(&mut self, npc: Npc) -> Result<(), GameError> {
-        if !self.is_available(npc.pos()) {
+        if !self.is_footprint_available(npc.footprint()) {
             let err = GameError::from(EngineError::SpawningError(npc.pos()));
             return Err(err);
         }
@@ -146,8 +254,15 @@ impl Level {
     }
 
     /// Spawns an item sprite on the map.
+    ///
+    /// Items pile up on a tile, so it only has to be walkable and free of NPCs.
     pub fn spawn_item_sprite(&mut self, item_sprite: GameItemSprite) -> Result<(), GameError> {
-        if !self.is_available(item_sprite.pos()) {
+        let point = item_sprite.pos();
+        let in_bounds = self.world.is_in_bounds(point.x as isize, point.y as isize);
+        if !in_bounds
+            || !self.world.get_tile(point).tile_type.is_walkable()
+            || self.get_npc_at(point).is_some()
+        {
             let err = GameError::from(EngineError::SpawningError(item_sprite.pos()));
             return Err(err);
         }
@@ -161,9 +276,39 @@ impl Level {
         Ok(())
     }
 
+    /// Places a chest holding the given items on the map. The chest replaces the floor tile it stands on.
+    pub fn spawn_chest(&mut self, pos: Point, contents: Vec<GameItemId>) -> Result<(), GameError> {
+        if !self.is_available(pos) {
+            return Err(GameError::from(EngineError::SpawningError(pos)));
+        }
+
+        self.world.get_tile_mut(pos).tile_type = TileType::Chest;
+        self.chests.insert(pos, contents);
+
+        Ok(())
+    }
+
+    /// Places a corpse on the map.
+    ///
+    /// Corpses don't occupy their tile, so the only requirement is that the tile is walkable.
+    pub fn spawn_corpse(&mut self, corpse: Corpse) -> Result<(), GameError> {
+        if !self.world.get_tile(corpse.pos()).tile_type.is_walkable() {
+            let err = GameError::from(EngineError::SpawningError(corpse.pos()));
+            return Err(err);
+        }
+
+        let corpse_id = corpse.id();
+
+        self.corpses.push(corpse);
+        let index = self.corpses.len() - 1;
+        self.corpses_index.insert(corpse_id, index);
+
+        Ok(())
+    }
+
     /// Removes an entity from the level if it exists.
     ///
-    /// Looks up the ID in NPCs and item sprites. Uses `swap_remove`
+    /// Looks up the ID in NPCs, item sprites and corpses. Uses `swap_remove`
     /// and fixes the moved entity’s index if needed.
     pub fn despawn(&mut self, id: EntityId) {
         if let Some(&index) = self.npc_index.get(&id) {
@@ -185,15 +330,34 @@ impl Level {
             }
 
             self.item_sprites_index.remove(&id);
+            return;
         }
+
+        if let Some(&index) = self.corpses_index.get(&id) {
+            self.corpses.swap_remove(index);
+
+            if let Some(moved) = self.corpses.get(index) {
+                self.corpses_index.insert(moved.id(), index);
+            }
+
+            self.corpses_index.remove(&id);
+        }
+    }
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 /// All possibilities where a level can be entered. Used in [GameState::goto_level].
-/// Can be extended in the future with `Custom(Point)` or `Random` in cases like traps, where you fall through the floor.
+/// Can be extended in the future with `Custom(Point)` in cases like traps.
 pub enum LevelEntrance {
     Entry,
     Exit,
+    /// A random free floor tile, e.g. after falling through a chasm.
+    Random,
 }
 
 impl GameState {
@@ -219,58 +383,133 @@ impl GameState {
 
     /// Moves the player to a different level of number `index`.
     ///
-    /// Lazily loads/generates a level.
+    /// Lazily loads/generates a level. Skipped levels (e.g. when falling through a chasm) are generated on the way, so the dungeon of a seed is always the same.
+    /// Levels are kept once generated: Item sprites, corpses, doors and explored tiles stay as the player left them.
+    /// NPCs catch up with the rounds the player was away (see [GameState::catch_up_level]).
+    /// Only the visibility of the level that is left is cleared, since the player can no longer see it.
+    /// The [EntityRegistry](crate::core::entity_registry::EntityRegistry) remembers where the entities of the level that is left are.
+    ///
     /// The player will be placed at the level's entry or exit, as defined by `entrance_point`.
+    /// Levels of a special [Biome] greet the player with some flavor text. Levels with a floor affix announce it.
+    /// With the `scripting` feature, the scripts of a level run the first time the player enters it, and their `on_enter_level` handlers every time.
+    /// A failing script does not keep the player from arriving, its error is returned once the level is entered.
+    /// Triggers at the point the player arrives at fire right away.
     pub fn goto_level(
         &mut self,
         index: usize,
         entrance_point: LevelEntrance,
     ) -> Result<(), GameError> {
+        if let Some(level) = self.levels.get_mut(self.level_nr) {
+            for tile in level.world.tiles.iter_mut() {
+                tile.make_invisible();
+            }
+            level.left_round = Some(self.round_nr);
+        }
+        // Companions follow the player, they are placed next to the player once the new level is entered
+        let companions = self.take_companions();
+        self.sync_entity_registry();
+
         match self.levels.get(index) {
-            Some(_) => self.level_nr = index,
+            Some(_) => {
+                self.level_nr = index;
+                self.raise_gauntlet_memorial();
+            }
             None => {
-                self.initialize_level(index)?;
+                for missing_index in self.levels.len()..=index {
+                    self.initialize_level(missing_index)?;
+                }
                 self.level_nr = index;
             }
         }
 
+        if let Some(flavor) = self.current_level().biome.entry_flavor() {
+            self.log.info(LogData::Lore(flavor.to_string()));
+        }
+        if let Some(affix) = self.current_level().affix_def() {
+            self.log.info(LogData::FloorAffix { announcement: affix.announcement.to_string() });
+        }
+
+        self.run_stats.deepest_level = self.run_stats.deepest_level.max(index);
+
         self.player.character.base.pos = match entrance_point {
             LevelEntrance::Entry => self.current_level().entry,
             LevelEntrance::Exit => self.current_level().exit,
+            LevelEntrance::Random => self.random_landing_point(),
         };
+        self.place_companions(companions);
+
+        self.catch_up_level();
+
+        #[cfg(feature = "scripting")]
+        let scripts_result = self.run_level_scripts();
+
+        self.fire_triggers();
+
+        self.place_quest_items()?;
 
         self.compute_fov();
 
+        #[cfg(feature = "scripting")]
+        scripts_result?;
+
         Ok(())
     }
 
+    /// Picks a random free floor tile of the current level. Falls back to the level's entry if there is none.
+    pub fn random_landing_point(&mut self) -> Point {
+        let level = self.current_level();
+        let landing_points: Vec<Point> = (0..level.world.height)
+            .flat_map(|y| (0..level.world.width).map(move |x| Point::new(x, y)))
+            .filter(|point| {
+                level.world.get_tile(*point).tile_type == TileType::Floor
+                    && level.is_available(*point)
+            })
+            .collect();
+
+        let entry = level.entry;
+
+        landing_points.choose(&mut self.rng).copied().unwrap_or(entry)
+    }
+
     /// Calls [GameState::goto_level] for the next relative level.
     pub fn goto_level_next(&mut self) -> Result<(), GameError> {
         self.goto_level(self.level_nr + 1, LevelEntrance::Entry)
     }
 
     /// Calls [GameState::goto_level] for the previous relative level.
+    ///
+    /// # Errors
+    /// * [EngineError::LevelNotFound] if the player is already in the town, which has nothing above it.
     pub fn goto_level_previous(&mut self) -> Result<(), GameError> {
-        self.goto_level(self.level_nr - 1, LevelEntrance::Exit)
+        let index =
+            self.level_nr.checked_sub(1).ok_or(EngineError::LevelNotFound(self.level_nr))?;
+        self.goto_level(index, LevelEntrance::Exit)
     }
 
     /// Initializes a new level of the given index.
     ///
-    /// - Level 0 is loaded from the level file "level_01.ron" (Tutorial level).
-    /// - Level 1 and every [STATIC_LEVEL_INTERVAL] levels thereafter are loaded from the level file "level_02.ron" (Gauntlet level)
+    /// - Level 0 is loaded from the level file "town.ron" (Town on the surface).
+    /// - Level 1 is loaded from the level file "level_01.ron" (Tutorial level).
+    /// - Level 3 and every [STATIC_LEVEL_INTERVAL] levels thereafter are loaded from the level file "level_02.ron" (Gauntlet level)
     /// - All other levels are procedurally generated.
     pub fn initialize_level(&mut self, index: usize) -> Result<(), GameError> {
         let new_level: Level = match index {
-            0 => self.load_static_level(0).map_err(|error| {
+            TOWN_LEVEL => self.load_static_level(2).map_err(|error| {
+                self.log.debug_warn(format!("Couldn't load level {}", error));
+                error
+            })?,
+            TUTORIAL_LEVEL => self.load_static_level(0).map_err(|error| {
                 self.log.debug_warn(format!("Couldn't load level {}", error));
                 error
             })?,
             level_index if is_gauntlet_level(level_index) => {
                 self.log.info(LogData::GauntletGreeting);
-                self.load_static_level(1).map_err(|error| {
+                let mut level = self.load_static_level(1).map_err(|error| {
                     self.log.debug_warn(format!("Couldn't load level {}", error));
                     error
-                })?
+                })?;
+                level.gauntlet = Some(GauntletRecord::new(self.round_nr));
+                level
             }
             level_index => self.load_generated_level(level_index).map_err(|error| {
                 self.log.debug_warn(format!("Couldn't generate level {}", error));
@@ -278,6 +517,7 @@ impl GameState {
             })?,
         };
 
+        self.entity_registry.index_level(index, &new_level);
         self.levels.insert(index, new_level);
 
         Ok(())
@@ -300,6 +540,10 @@ impl GameState {
         level.world.apply_level_data(&data, level_nr)?;
         level.entry = data.entry;
         level.exit = data.exit;
+        level.biome = data.biome;
+        level.affix = data.affix.clone();
+        level.scripts = data.scripts.clone();
+        level.triggers = data.triggers.clone();
 
         for spawn in &data.spawns {
             let pos = Point::new(spawn.x, spawn.y);
@@ -314,11 +558,26 @@ impl GameState {
                     let npc = self.create_npc(def_id.clone(), pos)?;
                     level.spawn_npc(npc)?;
                 }
+                SpawnKind::Hunter { def_id } => {
+                    let mut npc = self.create_npc(def_id.clone(), pos)?;
+                    npc.ai_state = NpcAiState::Hunting;
+                    level.spawn_npc(npc)?;
+                }
+                SpawnKind::Pack { def_id, size } => {
+                    let positions = pack_positions(&level, pos, *size);
+                    for npc in self.create_pack(def_id, &positions)? {
+                        level.spawn_npc(npc)?;
+                    }
+                }
                 SpawnKind::Item { def_id } => {
-                    let item_id = self.register_item(def_id)?;
+                    let item_id = self.register_found_item(def_id)?;
                     let item_sprite = self.create_item_sprite(item_id, pos)?;
                     level.spawn_item_sprite(item_sprite)?;
                 }
+                SpawnKind::Chest { contents } => {
+                    let item_ids = self.register_found_items(contents)?;
+                    level.spawn_chest(pos, item_ids)?;
+                }
             }
         }
 
@@ -334,7 +593,8 @@ impl GameState {
         let level_seed = self.proc_gen.next_u64();
         self.log.debug_info(format!("Current Level Seed: {}", level_seed));
 
-        let proc_gen = ProcGenLevel::generate(level_seed);
+        let config = self.difficulty.adjust_gen_config(&self.gen_config);
+        let proc_gen = ProcGenLevel::generate(level_seed, level_nr, &config);
         let data = LevelData::from(proc_gen);
         self.log.debug_info(format!("RNG State after Proc-Gen: {}", self.proc_gen.next_u64()));
 
@@ -343,6 +603,10 @@ impl GameState {
         level.world.apply_level_data(&data, level_nr)?;
         level.entry = data.entry;
         level.exit = data.exit;
+        level.biome = data.biome;
+        level.affix = data.affix.clone();
+        level.scripts = data.scripts.clone();
+        level.triggers = data.triggers.clone();
 
         for spawn in &data.spawns {
             let pos = Point::new(spawn.x, spawn.y);
@@ -355,16 +619,146 @@ impl GameState {
             match &spawn.kind {
                 SpawnKind::Npc { def_id } => {
                     let npc = self.create_npc(def_id.clone(), pos)?;
+                    // Large monsters may not fit where their spawn was placed
+                    if !level.is_footprint_available(npc.footprint()) {
+                        self.log.debug_warn(format!("Spawn blocked at ({}, {})", spawn.x, spawn.y));
+                        continue;
+                    }
+                    level.spawn_npc(npc)?;
+                }
+                SpawnKind::Hunter { def_id } => {
+                    let mut npc = self.create_npc(def_id.clone(), pos)?;
+                    npc.ai_state = NpcAiState::Hunting;
                     level.spawn_npc(npc)?;
                 }
+                SpawnKind::Pack { def_id, size } => {
+                    let positions = pack_positions(&level, pos, *size);
+                    for npc in self.create_pack(def_id, &positions)? {
+                        level.spawn_npc(npc)?;
+                    }
+                }
                 SpawnKind::Item { def_id } => {
-                    let item_id = self.register_item(def_id)?;
+                    let item_id = self.register_found_item(def_id)?;
                     let item_sprite = self.create_item_sprite(item_id, pos)?;
                     level.spawn_item_sprite(item_sprite)?;
                 }
+                SpawnKind::Chest { contents } => {
+                    let item_ids = self.register_found_items(contents)?;
+                    level.spawn_chest(pos, item_ids)?;
+                }
             }
         }
+        self.spawn_unique(&mut level, level_nr)?;
 
         Ok(level)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::tiles::DoorType;
+
+    /// Walks down to the gauntlet on level 3 and back up to the tutorial.
+    fn round_trip(game: &mut GameState) {
+        game.goto_level(2, LevelEntrance::Entry).unwrap();
+        game.goto_level(3, LevelEntrance::Entry).unwrap();
+        game.goto_level(2, LevelEntrance::Exit).unwrap();
+        game.goto_level(1, LevelEntrance::Exit).unwrap();
+    }
+
+    #[test]
+    fn points_out_of_bounds_are_not_available() {
+        let mut game = GameState::default();
+        game.goto_level(1, LevelEntrance::Entry).unwrap();
+        let level = game.current_level();
+        assert!(!level.is_available(Point::new(0, 999)));
+        assert!(!level.is_available(Point::new(level.world.width, 0)));
+
+        let goblin = game.create_npc("goblin".into(), Point::new(0, 999)).unwrap();
+        assert!(game.current_level_mut().spawn_npc(goblin).is_err());
+    }
+
+    #[test]
+    fn revisited_levels_keep_items_npcs_and_doors() {
+        let mut game = GameState::default();
+        game.goto_level(1, LevelEntrance::Entry).unwrap();
+
+        let drop_point = game.random_landing_point();
+        let item_id = game.register_item(&"food_apple".to_string()).unwrap();
+        let sprite = game.create_item_sprite(item_id, drop_point).unwrap();
+        let sprite_id = sprite.id();
+        game.current_level_mut().spawn_item_sprite(sprite).unwrap();
+
+        if let Some(npc_id) = game.current_level().npcs.first().map(|npc| npc.id()) {
+            game.current_level_mut().despawn(npc_id);
+        }
+        let npc_ids: Vec<EntityId> = game.current_level().npcs.iter().map(|npc| npc.id()).collect();
+
+        let door_point = game.random_landing_point();
+        game.current_world_mut().get_tile_mut(door_point).tile_type =
+            TileType::Door(DoorType::Open);
+
+        round_trip(&mut game);
+
+        assert_eq!(game.level_nr, 1);
+        assert_eq!(game.current_level().get_item_sprite_at(drop_point), Some(sprite_id));
+        assert_eq!(
+            game.current_level().npcs.iter().map(|npc| npc.id()).collect::<Vec<_>>(),
+            npc_ids
+        );
+        assert_eq!(
+            game.current_world().get_tile(door_point).tile_type,
+            TileType::Door(DoorType::Open)
+        );
+    }
+
+    #[test]
+    fn revisited_levels_keep_exploration_but_not_visibility() {
+        let mut game = GameState::default();
+        game.goto_level(1, LevelEntrance::Entry).unwrap();
+        let explored: Vec<bool> =
+            game.current_world().tiles.iter().map(|tile| tile.explored).collect();
+        assert!(game.current_world().get_tile(game.current_level().entry).visible);
+
+        game.goto_level(2, LevelEntrance::Entry).unwrap();
+        assert!(game.levels[1].world.tiles.iter().all(|tile| !tile.visible));
+
+        game.goto_level(3, LevelEntrance::Entry).unwrap();
+        game.goto_level(2, LevelEntrance::Exit).unwrap();
+        assert!(game.levels[1].world.tiles.iter().all(|tile| !tile.visible));
+        assert!(game.levels[3].world.tiles.iter().all(|tile| !tile.visible));
+
+        game.goto_level(1, LevelEntrance::Exit).unwrap();
+        let tiles = &game.current_world().tiles;
+        assert!(explored.iter().zip(tiles.iter()).all(|(before, tile)| !before || tile.explored));
+        assert!(game.current_world().get_tile(game.current_level().exit).visible);
+    }
+
+    #[test]
+    fn skipped_levels_are_generated_in_order() {
+        let mut falling = GameState::default();
+        falling.goto_level(1, LevelEntrance::Entry).unwrap();
+        falling.goto_level(4, LevelEntrance::Random).unwrap();
+
+        let mut walking = GameState::default();
+        for index in 0..=4 {
+            walking.goto_level(index, LevelEntrance::Entry).unwrap();
+        }
+
+        assert_eq!(falling.levels.len(), 5);
+        assert_eq!(falling.level_nr, 4);
+        for (fallen, walked) in falling.levels.iter().zip(walking.levels.iter()) {
+            assert_eq!(fallen.entry, walked.entry);
+            assert_eq!(fallen.exit, walked.exit);
+            assert!(
+                fallen
+                    .world
+                    .tiles
+                    .iter()
+                    .zip(walked.world.tiles.iter())
+                    .all(|(a, b)| a.tile_type == b.tile_type)
+            );
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    proc_gen::vaults::VaultTransform,
     util::errors_results::{DataError, GameError},
     world::{
+        biome::Biome,
         coordinate_system::Point,
-        tiles::{DoorType, Tile, TileType},
+        lighting::RoomLighting,
+        tiles::{DoorType, ShrineState, TileType},
         worldspace::{Room, World},
     },
 };
@@ -31,6 +34,19 @@ pub struct LevelData {
 
     #[serde(default)]
     pub spawns: Vec<SpawnData>,
+
+    #[serde(default)]
+    pub biome: Biome,
+
+    /// Floor affix of the level (see [floor_affix_defs](crate::data::floor_affixes::floor_affix_defs)), if it has one.
+    #[serde(default)]
+    pub affix: Option<String>,
+
+    #[serde(default)]
+    pub scripts: Vec<ScriptData>,
+
+    #[serde(default)]
+    pub triggers: Vec<TriggerData>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +55,9 @@ pub struct RoomData {
     pub y: usize,
     pub width: usize,
     pub height: usize,
+
+    #[serde(default)]
+    pub lighting: RoomLighting,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,7 +67,7 @@ pub struct TileData {
     pub tile_type: TileTypeData,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TileTypeData {
     Floor,
     Wall,
@@ -56,9 +75,17 @@ pub enum TileTypeData {
     Door(DoorTypeData),
     StairsDown,
     StairsUp,
+    Campfire,
+    Stash,
+    Shrine,
+    Altar,
+    Water,
+    Lava,
+    Chasm,
+    SpikeTrap,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DoorTypeData {
     Open,
     Closed,
@@ -74,29 +101,162 @@ pub struct SpawnData {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SpawnKind {
-    Npc { def_id: String },
-    Item { def_id: String },
+    Npc {
+        def_id: String,
+    },
+    Item {
+        def_id: String,
+    },
+    /// An NPC that hunts the player across the whole level (see [NpcAiState::Hunting](crate::ai::npc_ai::NpcAiState::Hunting)).
+    Hunter {
+        def_id: String,
+    },
+    /// A pack of NPCs of the same kind, led by the one spawned on this point (see [pack](crate::ai::pack)). The others are spawned around it.
+    Pack {
+        def_id: String,
+        size: usize,
+    },
+    /// A chest holding the given items (see [TileType::Chest](crate::world::tiles::TileType::Chest)).
+    Chest {
+        contents: Vec<String>,
+    },
+}
+
+/// A script that reacts to events of the level, like the player entering it for the first time. Scripts only run in builds with the `scripting` feature.
+///
+/// The coordinates used by the script are relative to an area of the level (e.g. a vault), so the script works wherever the area is placed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptData {
+    /// Path of the script file.
+    pub path: String,
+
+    /// Top left corner of the area.
+    pub origin: Point,
+
+    /// Orientation of the area.
+    #[serde(default)]
+    pub transform: VaultTransform,
+
+    /// Size of the area before the transformation.
+    pub width: usize,
+    pub height: usize,
+}
+
+impl ScriptData {
+    /// Converts coordinates of the script into a point of the level.
+    ///
+    /// # Returns
+    /// * `None` if the coordinates are outside the area of the script.
+    #[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+    pub fn to_world(&self, x: usize, y: usize) -> Option<Point> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let (x, y) = self.transform.apply(x, y, self.width, self.height);
+        Some(Point::new(self.origin.x + x, self.origin.y + y))
+    }
+}
+
+/// An area of a level that fires an action the first time the player steps into it. Each trigger only fires once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerData {
+    /// Top left corner of the area.
+    pub x: usize,
+    pub y: usize,
+
+    /// Size of the area. A single tile, unless given.
+    #[serde(default = "single_tile")]
+    pub width: usize,
+    #[serde(default = "single_tile")]
+    pub height: usize,
+
+    pub action: TriggerAction,
+}
+
+fn single_tile() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TriggerAction {
+    /// Spawns monsters (or items) around the player.
+    Ambush { spawns: Vec<SpawnData> },
+
+    /// Shows a piece of lore in a window.
+    Lore { title: String, paragraphs: Vec<String> },
+
+    /// Locks the door at the given point until all enemies of the level are defeated. Used to lock the door behind the player.
+    LockDoor { x: usize, y: usize },
+
+    /// Starts the gauntlet record of the level (see [GauntletRecord](crate::core::gauntlet::GauntletRecord)), unless it is already running.
+    StartGauntlet,
+}
+
+impl TriggerData {
+    /// Checks whether the point is inside the area of the trigger.
+    pub fn contains(&self, point: Point) -> bool {
+        (self.x..self.x + self.width).contains(&point.x)
+            && (self.y..self.y + self.height).contains(&point.y)
+    }
+
+    /// Moves the trigger to other coordinates, e.g. from the coordinates of a vault layout into those of the level.
+    ///
+    /// `to_world` converts a point of the trigger. The area stays a rectangle, even if the conversion rotates or mirrors it.
+    pub fn placed(&self, to_world: impl Fn(usize, usize) -> Point) -> Self {
+        let a = to_world(self.x, self.y);
+        let b = to_world(self.x + self.width - 1, self.y + self.height - 1);
+
+        let action = match &self.action {
+            TriggerAction::Ambush { spawns } => TriggerAction::Ambush {
+                spawns: spawns
+                    .iter()
+                    .map(|spawn| {
+                        let point = to_world(spawn.x, spawn.y);
+                        SpawnData { kind: spawn.kind.clone(), x: point.x, y: point.y }
+                    })
+                    .collect(),
+            },
+            TriggerAction::LockDoor { x, y } => {
+                let point = to_world(*x, *y);
+                TriggerAction::LockDoor { x: point.x, y: point.y }
+            }
+            other => other.clone(),
+        };
+
+        Self {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+            width: a.x.abs_diff(b.x) + 1,
+            height: a.y.abs_diff(b.y) + 1,
+            action,
+        }
+    }
 }
 
 impl World {
     /// Applies level data to a world.
     ///
     /// This level data might come from one of the world level files or a procedurally generated world.
+    /// The world takes on the size of the level data.
     ///
     /// # Errors
     /// * [DataError::InvalidWorldFormat] if the world format is corrupted and cannot be read.
     pub fn apply_level_data(&mut self, data: &LevelData, index: usize) -> Result<(), GameError> {
-        if data.width != self.width || data.height != self.height {
+        if data.width == 0 || data.height == 0 {
             return Err(GameError::from(DataError::InvalidWorldFormat(index)));
         }
 
-        for t in self.tiles.iter_mut() {
-            *t = Tile::default();
-        }
+        *self = World::with_size(data.width, data.height);
 
         for r in &data.rooms {
+            if r.x + r.width >= self.width || r.y + r.height >= self.height {
+                return Err(GameError::from(DataError::InvalidWorldFormat(index)));
+            }
+
             let room = Room::new(Point::new(r.x, r.y), r.width, r.height);
             self.carve_room(&room);
+            self.light_room(&room, r.lighting);
         }
 
         for td in &data.tiles {
@@ -112,15 +272,27 @@ impl World {
                 TileTypeData::Hallway => TileType::Hallway,
                 TileTypeData::StairsDown => TileType::StairsDown,
                 TileTypeData::StairsUp => TileType::StairsUp,
+                TileTypeData::Campfire => TileType::Campfire,
+                TileTypeData::Stash => TileType::Stash,
+                TileTypeData::Shrine => TileType::Shrine(ShrineState::Active),
+                TileTypeData::Altar => TileType::Altar(ShrineState::Active),
+                TileTypeData::Water => TileType::Water,
+                TileTypeData::Lava => TileType::Lava,
+                TileTypeData::Chasm => TileType::Chasm,
+                TileTypeData::SpikeTrap => TileType::SpikeTrap,
                 TileTypeData::Door(DoorTypeData::Archway) => TileType::Door(DoorType::Archway),
                 TileTypeData::Door(DoorTypeData::Open) => TileType::Door(DoorType::Open),
                 TileTypeData::Door(DoorTypeData::Closed) => TileType::Door(DoorType::Closed),
             };
 
-            self.tiles[idx] = Tile::new(tile_type);
+            self.tiles[idx].tile_type = tile_type;
         }
 
         for corridor_point in &data.corridors {
+            if corridor_point.x >= self.width || corridor_point.y >= self.height {
+                return Err(GameError::from(DataError::InvalidWorldFormat(index)));
+            }
+
             let updated_tile = match self.get_tile(*corridor_point).tile_type {
                 TileType::Void => TileType::Hallway,
                 TileType::Wall => TileType::Door(DoorType::Archway),
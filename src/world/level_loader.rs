@@ -6,6 +6,7 @@ use std::io::{BufReader, BufWriter};
 use ron::de::from_reader;
 use ron::ser::{PrettyConfig, to_writer_pretty};
 
+use crate::data::vaults::VaultData;
 use crate::util::errors_results::{GameError, IoError};
 use crate::world::level_data::LevelData;
 
@@ -16,6 +17,13 @@ pub fn load_world_from_ron(path: &str) -> Result<LevelData, GameError> {
     Ok(data)
 }
 
+pub fn load_vault_from_ron(path: &str) -> Result<VaultData, GameError> {
+    let file = File::open(path).map_err(IoError::FileReading)?;
+    let reader = BufReader::new(file);
+    let data: VaultData = from_reader(reader).map_err(IoError::MapParsing)?;
+    Ok(data)
+}
+
 pub fn save_world_to_ron(world_data: &LevelData, path: &str) -> Result<(), GameError> {
     let file = File::create(path).map_err(IoError::FileCreation)?;
     let writer = BufWriter::new(file);
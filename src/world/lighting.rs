@@ -0,0 +1,62 @@
+use rand::{
+    Rng,
+    distr::{Distribution, StandardUniform},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::render::style::{Color, Style};
+
+/// Lighting (and mood) of a room. Assigned per room by the procedural generator and stored in the level data.
+///
+/// The lighting modulates the style of the room's floor and limits how far the player can see while standing in it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomLighting {
+    /// Regular lighting. Nothing is changed.
+    #[default]
+    Normal,
+
+    /// Barely lit room. Vision is limited.
+    Dim,
+
+    /// Room lit by torches on the walls.
+    Torchlit,
+
+    /// Room lit by the faint glow of fungi growing on the floor. Vision is slightly limited.
+    FungalGlow,
+}
+
+impl RoomLighting {
+    /// Maximum vision radius (in tiles) of the player while standing in a room with this lighting.
+    ///
+    /// # Returns
+    /// * `None` if vision is not limited.
+    pub fn vision_radius(&self) -> Option<usize> {
+        match self {
+            RoomLighting::Normal => None,
+            RoomLighting::Dim => Some(5),
+            RoomLighting::Torchlit => None,
+            RoomLighting::FungalGlow => Some(9),
+        }
+    }
+
+    /// Applies the lighting to the style of a tile.
+    pub fn modulate(&self, style: Style) -> Style {
+        match self {
+            RoomLighting::Normal => style,
+            RoomLighting::Dim => style.fg(Color::DarkGray),
+            RoomLighting::Torchlit => style.fg(Color::Yellow),
+            RoomLighting::FungalGlow => style.fg(Color::LightCyan),
+        }
+    }
+}
+
+impl Distribution<RoomLighting> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RoomLighting {
+        match rng.random_range(0..100) {
+            0..=59 => RoomLighting::Normal,
+            60..=79 => RoomLighting::Dim,
+            80..=94 => RoomLighting::Torchlit,
+            _ => RoomLighting::FungalGlow,
+        }
+    }
+}
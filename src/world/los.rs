@@ -0,0 +1,77 @@
+use crate::world::{coordinate_system::Point, tiles::Opacity, worldspace::World};
+
+/// Whether `b` can be seen from `a`. Used by gameplay (combat, AI), while [GameState::compute_fov](crate::core::game::GameState::compute_fov) is used for rendering.
+///
+/// Walks the Bresenham line between the two points. Only the tiles in between have to be see-through, so a wall can be seen from the floor next to it.
+pub fn has_line_of_sight(a: Point, b: Point, world: &World) -> bool {
+    if !world.is_in_bounds(a.x as isize, a.y as isize)
+        || !world.is_in_bounds(b.x as isize, b.y as isize)
+    {
+        return false;
+    }
+
+    line_between(a, b).all(|point| !world.get_tile(point).tile_type.is_opaque())
+}
+
+/// Points on the Bresenham line between `a` and `b`, without the two points themselves.
+fn line_between(a: Point, b: Point) -> impl Iterator<Item = Point> {
+    let (x0, y0) = (a.x as isize, a.y as isize);
+    let (x1, y1) = (b.x as isize, b.y as isize);
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let step_x = if x0 < x1 { 1 } else { -1 };
+    let step_y = if y0 < y1 { 1 } else { -1 };
+
+    let mut x = x0;
+    let mut y = y0;
+    let mut error = dx + dy;
+
+    std::iter::from_fn(move || {
+        if (x, y) == (x1, y1) {
+            return None;
+        }
+
+        let doubled_error = 2 * error;
+        if doubled_error >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled_error <= dx {
+            error += dx;
+            y += step_y;
+        }
+        Some((x, y))
+    })
+    .take_while(move |&(x, y)| (x, y) != (x1, y1))
+    .map(|(x, y)| Point::new(x as usize, y as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{tiles::TileType, worldspace::Room};
+
+    fn open_world() -> World {
+        let mut world = World::new();
+        world.carve_room(&Room::new(Point::new(5, 5), 20, 10));
+        world
+    }
+
+    #[test]
+    fn open_rooms_are_in_sight() {
+        let world = open_world();
+        assert!(has_line_of_sight(Point::new(6, 6), Point::new(20, 12), &world));
+        assert!(has_line_of_sight(Point::new(20, 12), Point::new(6, 6), &world));
+        assert!(has_line_of_sight(Point::new(8, 8), Point::new(8, 8), &world));
+    }
+
+    #[test]
+    fn walls_block_the_line_but_can_be_seen() {
+        let mut world = open_world();
+        world.get_tile_mut(Point::new(10, 8)).tile_type = TileType::Wall;
+
+        assert!(!has_line_of_sight(Point::new(7, 8), Point::new(14, 8), &world));
+        assert!(has_line_of_sight(Point::new(7, 8), Point::new(10, 8), &world));
+        assert!(has_line_of_sight(Point::new(7, 8), Point::new(14, 12), &world));
+    }
+}
@@ -1,10 +1,11 @@
 #![allow(dead_code)]
 
-use ratatui::style::{Color, Style};
+use crate::render::style::{Color, Style};
+use crate::world::lighting::RoomLighting;
 
 /// Represents the basic building block of the world.
 ///
-/// The `World` consists of `WORLD_WIDTH` x `WORLD_WIDTH` (default: 100x25) Tiles.
+/// The `World` consists of `width` x `height` (default: 100x25) Tiles.
 /// Tiles stand for the static environment of the world, not entities.
 #[derive(Clone, Copy, Debug)]
 pub struct Tile {
@@ -15,11 +16,13 @@ pub struct Tile {
     /// Whether the tile has ever been seen by the player.
     /// Non-visible, previously explored areas appear gray.
     pub explored: bool,
+    /// Lighting of the room the tile belongs to.
+    pub lighting: RoomLighting,
 }
 
 impl Tile {
     pub fn new(tile_type: TileType) -> Self {
-        Self { tile_type, visible: false, explored: false }
+        Self { tile_type, visible: false, explored: false, lighting: RoomLighting::Normal }
     }
 
     /// Reveal the tile to the player.
@@ -40,7 +43,12 @@ impl Tile {
 
 impl Default for Tile {
     fn default() -> Self {
-        Self { tile_type: TileType::Void, visible: false, explored: false }
+        Self {
+            tile_type: TileType::Void,
+            visible: false,
+            explored: false,
+            lighting: RoomLighting::Normal,
+        }
     }
 }
 
@@ -67,6 +75,36 @@ pub enum TileType {
 
     /// Stairs that lead back up the dungeon floors
     StairsUp,
+
+    /// A campfire that can be used to cook raw food. Blocks movement, but not vision.
+    Campfire,
+
+    /// A chest in the town whose contents are kept between runs. Blocks movement, but not vision.
+    Stash,
+
+    /// A chest in a treasure room. Its contents are kept by the [Level](crate::world::level::Level). Blocks movement, but not vision.
+    Chest,
+
+    /// Memorial of a completed gauntlet. Its inscription describes the player's run.
+    Memorial,
+
+    /// Shrine of a completed gauntlet that grants a permanent blessing once.
+    Shrine(ShrineState),
+
+    /// Altar of the depths. Praying at it once brings a boon or a curse, depending on the gods' mood.
+    Altar(ShrineState),
+
+    /// Deep water. Entering it requires a swim check, so it slows down the player.
+    Water,
+
+    /// Molten rock. Burns everyone standing in it each round.
+    Lava,
+
+    /// A hole in the floor. Whoever steps into it falls down to the next level.
+    Chasm,
+
+    /// Spikes hidden in the floor. They hurt whoever steps on them, the player and NPCs alike.
+    SpikeTrap,
 }
 
 impl std::fmt::Display for TileType {
@@ -79,8 +117,21 @@ impl std::fmt::Display for TileType {
             TileType::Door(DoorType::Archway) => write!(f, "Archway"),
             TileType::Door(DoorType::Closed) => write!(f, "Closed Door"),
             TileType::Door(DoorType::Open) => write!(f, "Open Door"),
+            TileType::Door(DoorType::Locked) => write!(f, "Locked Door"),
             TileType::StairsDown => write!(f, "Stairs leading further down..."),
             TileType::StairsUp => write!(f, "Stairs leading back up."),
+            TileType::Campfire => write!(f, "Campfire"),
+            TileType::Stash => write!(f, "Stash"),
+            TileType::Chest => write!(f, "Chest"),
+            TileType::Memorial => write!(f, "Memorial"),
+            TileType::Shrine(ShrineState::Active) => write!(f, "Shrine"),
+            TileType::Shrine(ShrineState::Depleted) => write!(f, "Depleted Shrine"),
+            TileType::Altar(ShrineState::Active) => write!(f, "Altar"),
+            TileType::Altar(ShrineState::Depleted) => write!(f, "Desecrated Altar"),
+            TileType::Water => write!(f, "Deep Water"),
+            TileType::Lava => write!(f, "Lava"),
+            TileType::Chasm => write!(f, "Chasm"),
+            TileType::SpikeTrap => write!(f, "Spike Trap"),
         }
     }
 }
@@ -95,6 +146,18 @@ pub enum DoorType {
 
     /// No door is present. Basically just a hole in the wall.
     Archway,
+
+    /// The door was locked by a trigger. It opens once all enemies of the level are defeated.
+    Locked,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShrineState {
+    /// The shrine can still be prayed at.
+    Active,
+
+    /// The shrine's blessing was already received.
+    Depleted,
 }
 
 /// A trait for giving something a visual representation in the TUI style.
@@ -102,14 +165,31 @@ pub trait Drawable {
     /// Returns the unicode `char` to be used in the graphical representation.
     fn glyph(&self) -> char;
 
-    /// Returns the [ratatui] [Style] to be used in the graphical representation.
+    /// Returns the [Style] to be used in the graphical representation.
     fn style(&self) -> Style;
+
+    /// Returns the plain ASCII `char` drawn instead of the glyph in ASCII mode (e.g. the `ascii` command of the terminal UI).
+    fn ascii_glyph(&self) -> char {
+        let glyph = self.glyph();
+        if glyph.is_ascii() { glyph } else { '?' }
+    }
+}
+
+/// ASCII glyph standing in for a glyph that cannot be drawn: The first letter or digit of the name, or `?` if it has none.
+pub fn fallback_glyph(name: &str) -> char {
+    name.chars().find(char::is_ascii_alphanumeric).unwrap_or('?')
 }
 
 /// A trait for defining whether something can be walked through by the player and NPCs or not.
 pub trait Collision {
     /// Returns a boolean denoting whether something can be walked through or not.
     fn is_walkable(&self) -> bool;
+
+    /// Returns the cost for NPCs to walk through something. Used for pathfinding.
+    ///
+    /// # Returns
+    /// * `None` if NPCs cannot or will not walk through it.
+    fn movement_cost(&self) -> Option<usize>;
 }
 
 /// A trait for defining whether an object is opaque or see-through.
@@ -136,8 +216,31 @@ impl Collision for TileType {
             TileType::Door(DoorType::Open) => true,
             TileType::Door(DoorType::Closed) => false,
             TileType::Door(DoorType::Archway) => true,
+            TileType::Door(DoorType::Locked) => false,
             TileType::StairsDown => true,
             TileType::StairsUp => true,
+            TileType::Campfire => false,
+            TileType::Stash => false,
+            TileType::Chest => false,
+            TileType::Memorial => false,
+            TileType::Shrine(_) => false,
+            TileType::Altar(_) => false,
+            TileType::Water => true,
+            TileType::Lava => true,
+            TileType::Chasm => true,
+            TileType::SpikeTrap => true,
+        }
+    }
+
+    /// NPCs avoid lava and chasms and only swim through water if there is no better way.
+    /// They know where the spike traps are and only walk over them if the way around is much longer.
+    fn movement_cost(&self) -> Option<usize> {
+        match self {
+            TileType::Water => Some(3),
+            TileType::SpikeTrap => Some(8),
+            TileType::Lava | TileType::Chasm => None,
+            tile_type if tile_type.is_walkable() => Some(1),
+            _ => None,
         }
     }
 }
@@ -154,8 +257,19 @@ impl Drawable for TileType {
             TileType::Door(DoorType::Archway) => '·',
             TileType::Door(DoorType::Open) => '_',
             TileType::Door(DoorType::Closed) => '+',
+            TileType::Door(DoorType::Locked) => '+',
             TileType::StairsDown => '>',
             TileType::StairsUp => '<',
+            TileType::Campfire => '^',
+            TileType::Stash => '=',
+            TileType::Chest => '■',
+            TileType::Memorial => '†',
+            TileType::Shrine(_) => '∆',
+            TileType::Altar(_) => 'Π',
+            TileType::Water => '≈',
+            TileType::Lava => '≈',
+            TileType::Chasm => ':',
+            TileType::SpikeTrap => '×',
         }
     }
     fn style(&self) -> Style {
@@ -165,9 +279,38 @@ impl Drawable for TileType {
             TileType::Wall => Style::default().fg(Color::White),
             TileType::Hallway => Style::default().fg(Color::DarkGray),
             TileType::Door(DoorType::Archway) => Style::default().fg(Color::Gray),
+            TileType::Door(DoorType::Locked) => Style::default().fg(Color::Red),
             TileType::Door(_) => Style::default().fg(Color::Yellow),
             TileType::StairsDown => Style::default().fg(Color::White),
             TileType::StairsUp => Style::default().fg(Color::White),
+            TileType::Campfire => Style::default().fg(Color::LightRed),
+            TileType::Stash => Style::default().fg(Color::Yellow),
+            TileType::Chest => Style::default().fg(Color::Yellow),
+            TileType::Memorial => Style::default().fg(Color::White),
+            TileType::Shrine(ShrineState::Active) => Style::default().fg(Color::LightMagenta),
+            TileType::Shrine(ShrineState::Depleted) => Style::default().fg(Color::DarkGray),
+            TileType::Altar(ShrineState::Active) => Style::default().fg(Color::LightCyan),
+            TileType::Altar(ShrineState::Depleted) => Style::default().fg(Color::DarkGray),
+            TileType::Water => Style::default().fg(Color::Blue),
+            TileType::Lava => Style::default().fg(Color::LightRed).bg(Color::Red),
+            TileType::Chasm => Style::default().fg(Color::DarkGray),
+            TileType::SpikeTrap => Style::default().fg(Color::LightYellow),
+        }
+    }
+
+    /// # Note
+    /// Walls are rendered using directional ASCII characters instead, which the frontend calculates like the unicode ones.
+    fn ascii_glyph(&self) -> char {
+        match self {
+            TileType::Floor | TileType::Door(DoorType::Archway) => '.',
+            TileType::Hallway => '#',
+            TileType::Chest => '&',
+            TileType::Memorial => 'T',
+            TileType::Shrine(_) => '*',
+            TileType::Altar(_) => '_',
+            TileType::Water | TileType::Lava => '~',
+            TileType::SpikeTrap => 'x',
+            tile_type => tile_type.glyph(),
         }
     }
 }
@@ -182,8 +325,19 @@ impl Opacity for TileType {
             TileType::Door(DoorType::Open) => false,
             TileType::Door(DoorType::Closed) => true,
             TileType::Door(DoorType::Archway) => false,
+            TileType::Door(DoorType::Locked) => true,
             TileType::StairsDown => false,
             TileType::StairsUp => false,
+            TileType::Campfire => false,
+            TileType::Stash => false,
+            TileType::Chest => false,
+            TileType::Memorial => false,
+            TileType::Shrine(_) => false,
+            TileType::Altar(_) => false,
+            TileType::Water => false,
+            TileType::Lava => false,
+            TileType::Chasm => false,
+            TileType::SpikeTrap => false,
         }
     }
 }
@@ -198,8 +352,21 @@ impl Interactable for TileType {
             TileType::Door(DoorType::Open) => false,
             TileType::Door(DoorType::Closed) => true,
             TileType::Door(DoorType::Archway) => false,
-            TileType::StairsDown => true,
-            TileType::StairsUp => true,
+            TileType::Door(DoorType::Locked) => true,
+            TileType::StairsDown => false,
+            TileType::StairsUp => false,
+            TileType::Campfire => true,
+            TileType::Stash => true,
+            TileType::Chest => true,
+            TileType::Memorial => true,
+            TileType::Shrine(ShrineState::Active) => true,
+            TileType::Shrine(ShrineState::Depleted) => false,
+            TileType::Altar(ShrineState::Active) => true,
+            TileType::Altar(ShrineState::Depleted) => false,
+            TileType::Water => false,
+            TileType::Lava => false,
+            TileType::Chasm => false,
+            TileType::SpikeTrap => false,
         }
     }
 }
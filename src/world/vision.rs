@@ -39,33 +39,46 @@ impl From<ViewPoint> for Point {
 }
 
 /// The entrypoint to the program. Call this function to compute the field of view from an origin tile.
-fn compute_fov(origin: Point, world: &mut World) {
-    // Make the tile of origin (where player is) visible and explored
-    world.mark_visible(origin);
-    world.mark_explored(origin);
+///
+/// The vision radius is limited by the lighting of the origin tile (see [RoomLighting::vision_radius](crate::world::lighting::RoomLighting::vision_radius))
+/// and by `max_radius`, whichever is smaller.
+fn compute_fov(origin: Point, max_radius: Option<usize>, world: &mut World) {
+    let vision_radius = match (world.get_tile(origin).lighting.vision_radius(), max_radius) {
+        (Some(radius), Some(max_radius)) => Some(radius.min(max_radius)),
+        (radius, max_radius) => radius.or(max_radius),
+    };
 
     // Make all tiles invisible
     for tile in world.tiles.iter_mut() {
         tile.make_invisible();
     }
 
+    // Make the tile of origin (where player is) visible and explored
+    world.mark_visible(origin);
+    world.mark_explored(origin);
+
     // Determine which tiles to make visible
     for direction in Direction::iter() {
         let quadrant = Quadrant::new(direction, origin.into());
 
         let first_row = Row::new(1, Rational::new(-1, 1), Rational::new(1, 1));
-        scan(origin, first_row, quadrant, world);
+        scan(origin, vision_radius, first_row, quadrant, world);
     }
 }
 
 /// Scan a row and recursively scan all of its children. If you think of each quadrant as a tree of rows, this essentially is a depth-first tree traversal.
-fn scan(_origin: Point, row: Row, quadrant: Quadrant, world: &mut World) {
+fn scan(
+    origin: Point,
+    vision_radius: Option<usize>,
+    row: Row,
+    quadrant: Quadrant,
+    world: &mut World,
+) {
     let mut prev_tile: Option<ViewPoint> = None;
     let mut row = row;
 
-    let row_tiles: Vec<_> = row.tiles().collect(); // Cloning was required since I change values.
-
-    for tile in row_tiles {
+    // The iterator doesn't borrow the row, since its values change while scanning.
+    for tile in row.tiles() {
         let point: Point = quadrant.transform(tile).into();
 
         // Points out of bounds are not rendered
@@ -81,15 +94,12 @@ fn scan(_origin: Point, row: Row, quadrant: Quadrant, world: &mut World) {
         let prev_tile_is_floor =
             prev_tile.is_some_and(|prev| !world.is_opaque(quadrant.transform(prev).into()));
 
-        // Vision Range = 30 tiles (commented out, so now vision range is infinite)
-        // if (Point::from(quadrant.transform(tile)).distance_squared_from(origin) as f32).sqrt()
-        //     >= 30.0
-        // {
-        //     continue;
-        // }
+        // Vision range is only limited by the lighting of the room the player is standing in.
+        let out_of_range =
+            vision_radius.is_some_and(|radius| point.distance_squared_from(origin) > radius.pow(2));
 
         // Tile is in both start and end slope
-        if tile_is_wall || is_symmetric(row, tile) {
+        if !out_of_range && (tile_is_wall || is_symmetric(row, tile)) {
             let point = quadrant.transform(tile);
             world.mark_visible(point.into());
             world.mark_explored(point.into());
@@ -104,12 +114,12 @@ fn scan(_origin: Point, row: Row, quadrant: Quadrant, world: &mut World) {
         if prev_tile_is_floor && tile_is_wall {
             let mut next_row = row.next();
             next_row.end_slope = slope(tile);
-            scan(_origin, next_row, quadrant, world);
+            scan(origin, vision_radius, next_row, quadrant, world);
         }
         prev_tile = Some(tile);
     }
     if prev_tile.is_some_and(|tile| !world.is_opaque(quadrant.transform(tile).into())) {
-        scan(_origin, row.next(), quadrant, world);
+        scan(origin, vision_radius, row.next(), quadrant, world);
     }
 }
 
@@ -145,8 +155,11 @@ impl FogOfWar for World {
 
 impl GameState {
     /// Compute the field of view at the current point in time of the game.
+    ///
+    /// A floor affix (like darkness) can limit the vision on the whole level.
     pub fn compute_fov(&mut self) {
-        compute_fov(self.player.character.pos(), self.current_world_mut());
+        let max_radius = self.current_level().affix_def().and_then(|affix| affix.vision_radius());
+        compute_fov(self.player.character.pos(), max_radius, self.current_world_mut());
     }
 }
 
@@ -203,7 +216,7 @@ impl Row {
     }
 
     /// Returns an iterator over the tiles in the row. This function considers a tile to be in the row if the sector swept out by the row’s start and end slopes overlaps with a diamond inscribed in the tile. If the diamond is only tangent to the sector, it does not become part of the row.
-    fn tiles(&self) -> impl Iterator<Item = ViewPoint> {
+    fn tiles(&self) -> impl Iterator<Item = ViewPoint> + use<> {
         let depth_times_start = Rational::new(self.depth, 1) * self.start_slope;
         let depth_times_end = Rational::new(self.depth, 1) * self.end_slope;
 
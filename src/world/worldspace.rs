@@ -1,7 +1,11 @@
 use crate::world::coordinate_system::Point;
+use crate::world::lighting::RoomLighting;
 use crate::world::tiles::{Tile, TileType};
 
+/// Default width of a level. Levels can be larger or smaller (see [LevelData::width](crate::world::level_data::LevelData::width)).
 pub const WORLD_WIDTH: usize = 100;
+
+/// Default height of a level.
 pub const WORLD_HEIGHT: usize = 25;
 
 // ----------------------------------------------
@@ -24,19 +28,22 @@ impl Room {
 //                World Struct
 // ----------------------------------------------
 
+#[derive(Clone)]
 pub struct World {
     pub width: usize,
     pub height: usize,
-    pub tiles: [Tile; WORLD_WIDTH * WORLD_HEIGHT], // Grid is 100 wide and 25 high.
+    pub tiles: Vec<Tile>, // Grid is `width` wide and `height` high.
 }
 
 impl World {
+    /// Creates an empty world of the default size ([WORLD_WIDTH]x[WORLD_HEIGHT]).
     pub fn new() -> Self {
-        Self {
-            width: WORLD_WIDTH,
-            height: WORLD_HEIGHT,
-            tiles: [Tile::default(); WORLD_WIDTH * WORLD_HEIGHT],
-        }
+        Self::with_size(WORLD_WIDTH, WORLD_HEIGHT)
+    }
+
+    /// Creates an empty world of the given size.
+    pub fn with_size(width: usize, height: usize) -> Self {
+        Self { width, height, tiles: vec![Tile::default(); width * height] }
     }
 
     /// Function to get an index for the 1-dimensional [World::tiles] array using x- and y-coordinates.
@@ -68,6 +75,13 @@ impl World {
     /// Returns a vector of [Point]s within the given radius around the given point of origin.
     pub fn get_points_in_radius(&self, point: Point, radius: isize) -> Vec<Point> {
         let mut points = Vec::new();
+        self.points_in_radius_into(point, radius, &mut points);
+        points
+    }
+
+    /// Same as [World::get_points_in_radius], but fills the given buffer (after clearing it) instead of allocating a new one.
+    pub fn points_in_radius_into(&self, point: Point, radius: isize, points: &mut Vec<Point>) {
+        points.clear();
         let x = point.x as isize;
         let y = point.y as isize;
 
@@ -83,8 +97,6 @@ impl World {
                 }
             }
         }
-
-        points
     }
 
     /// Carves a rectangular room into the map.
@@ -113,14 +125,19 @@ impl World {
             self.get_tile_mut(Point::new(x, oy + h)).tile_type = TileType::Wall;
         }
     }
+
+    /// Sets the lighting of all tiles inside the room (excluding its walls).
+    pub fn light_room(&mut self, room: &Room, lighting: RoomLighting) {
+        for y in room.origin.y + 1..room.origin.y + room.height {
+            for x in room.origin.x + 1..room.origin.x + room.width {
+                self.get_tile_mut(Point::new(x, y)).lighting = lighting;
+            }
+        }
+    }
 }
 
 impl Default for World {
     fn default() -> Self {
-        Self {
-            width: WORLD_WIDTH,
-            height: WORLD_HEIGHT,
-            tiles: [Tile::default(); WORLD_WIDTH * WORLD_HEIGHT],
-        }
+        Self::new()
     }
 }